@@ -0,0 +1,928 @@
+// [v3.6] Python type stubs generated from the Rust signatures.
+// Parses src/*.rs, follows the registrations in `#[pymodule] fn theus_core` (add_class /
+// add_function / add, including helpers such as `errors::register`) and renders every
+// exported #[pyclass] (fields, #[pymethods], getters, class attributes), #[pyfunction] and
+// exception type as a typed .pyi. The text is written to $OUT_DIR/theus_core.pyi and embedded
+// in the module, so `theus_core.generate_stubs(path)` always matches the compiled surface.
+// Rules:
+// - Parameters come from #[pyo3(signature = ...)] when present (defaults rendered when they are
+//   literals, `...` otherwise), else from the Rust arguments (trailing Option<T> defaults to None).
+// - Rust types map to Python ones (String -> str, Option<T> -> T | None, Vec<T> -> list[T],
+//   exported pyclasses by name, anything unknown -> Any).
+// - `#[cfg(feature = "...")]` is honoured for items and registrations.
+// NOTE: Only the top-level module is rendered; the `shm` and `testing` submodules are not.
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+use syn::visit::Visit;
+use syn::{Attribute, Expr, FnArg, GenericArgument, ImplItem, Item, Lit, Meta, Pat, PathArguments, ReturnType, Type};
+
+const MODULE_FN: &str = "theus_core";
+/// Python keywords cannot be declared in a stub (e.g. the `global` getter); such members are left out.
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+const HEADER: &str = "\
+# Generated from the Rust signatures by build.rs -- do not edit by hand.
+# Regenerate with `python scripts/gen_stubs.py` (wraps `theus_core.generate_stubs`).
+from typing import Any
+";
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut files = BTreeMap::new();
+    for entry in std::fs::read_dir("src").expect("read src/") {
+        let path = entry.expect("src entry").path();
+        if path.extension().is_some_and(|e| e == "rs") {
+            let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let text = std::fs::read_to_string(&path).expect("read source");
+            let file = syn::parse_file(&text).unwrap_or_else(|e| panic!("parse {}: {e}", path.display()));
+            files.insert(stem, file);
+        }
+    }
+    let crate_items = Crate::collect(&files);
+    let stubs = crate_items.render();
+    let out = Path::new(&std::env::var("OUT_DIR").expect("OUT_DIR")).join("theus_core.pyi");
+    std::fs::write(out, stubs).expect("write stubs");
+}
+
+// ---------------------------------------------------------------------------
+// Attribute helpers
+// ---------------------------------------------------------------------------
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|a| a.path().segments.last().is_some_and(|s| s.ident == name))
+}
+
+fn attr_tokens<'a>(attrs: &'a [Attribute], name: &str) -> impl Iterator<Item = TokenStream> + 'a {
+    let name = name.to_string();
+    attrs.iter().filter(move |a| a.path().is_ident(&name)).map(|a| match &a.meta {
+        Meta::List(list) => list.tokens.clone(),
+        _ => TokenStream::new(),
+    })
+}
+
+/// Top-level comma-separated pieces of `tokens`.
+fn split_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut out = vec![Vec::new()];
+    for tt in tokens {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => out.push(Vec::new()),
+            _ => out.last_mut().unwrap().push(tt),
+        }
+    }
+    out.retain(|piece| !piece.is_empty());
+    out
+}
+
+/// `key = value` options of attributes like #[pyclass(...)] / #[pyo3(...)]; bare flags map to
+/// an empty token list.
+fn options(attrs: &[Attribute], name: &str) -> HashMap<String, Vec<TokenTree>> {
+    let mut out = HashMap::new();
+    for tokens in attr_tokens(attrs, name) {
+        for piece in split_commas(tokens) {
+            let TokenTree::Ident(key) = &piece[0] else { continue };
+            let value = match piece.get(1) {
+                Some(TokenTree::Punct(p)) if p.as_char() == '=' => piece[2..].to_vec(),
+                _ => Vec::new(),
+            };
+            out.insert(key.to_string(), value);
+        }
+    }
+    out
+}
+
+fn string_option(opts: &HashMap<String, Vec<TokenTree>>, key: &str) -> Option<String> {
+    match opts.get(key)?.first()? {
+        TokenTree::Literal(lit) => Some(lit.to_string().trim_matches('"').to_string()),
+        TokenTree::Ident(ident) => Some(ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Evaluates `#[cfg(...)]` the way this build sees it (features from Cargo, `test` off).
+fn cfg_enabled(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|a| a.path().is_ident("cfg")).all(|a| match &a.meta {
+        Meta::List(list) => eval_cfg(&list.tokens.clone().into_iter().collect::<Vec<_>>()),
+        _ => true,
+    })
+}
+
+fn eval_cfg(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Ident(op), TokenTree::Group(g)] => {
+            let args: Vec<bool> = split_commas(g.stream()).iter().map(|p| eval_cfg(p)).collect();
+            match op.to_string().as_str() {
+                "not" => !args.first().copied().unwrap_or(false),
+                "all" => args.iter().all(|b| *b),
+                "any" => args.iter().any(|b| *b),
+                _ => true,
+            }
+        }
+        [TokenTree::Ident(key), TokenTree::Punct(_), TokenTree::Literal(value)] if key == "feature" => {
+            let feature = value.to_string().trim_matches('"').to_uppercase().replace('-', "_");
+            std::env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+        }
+        [TokenTree::Ident(key)] if key == "test" => false,
+        _ => true,
+    }
+}
+
+fn doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs.iter().filter(|a| a.path().is_ident("doc")).filter_map(|a| match &a.meta {
+        Meta::NameValue(nv) => match &nv.value {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Str(s) => Some(s.value().strip_prefix(' ').map_or_else(|| s.value(), str::to_string)),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }).collect();
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+// ---------------------------------------------------------------------------
+// Collected items
+// ---------------------------------------------------------------------------
+
+enum Param {
+    Named { name: String, ty: Option<Box<Type>>, default: Option<String> },
+    Args(String),
+    Kwargs(String),
+    Slash,
+    Star,
+}
+
+#[derive(PartialEq)]
+enum Kind {
+    Instance,
+    Static,
+    Class,
+    Init,
+    Getter,
+    ClassAttr,
+}
+
+struct Method {
+    name: String,
+    kind: Kind,
+    params: Vec<Param>,
+    ret: Option<Type>,
+    is_async: bool,
+    doc: Option<String>,
+}
+
+struct Class {
+    /// Python name (`#[pyclass(name = ...)]` or the Rust one).
+    name: String,
+    doc: Option<String>,
+    /// Python name and Rust type of #[pyo3(get)] fields.
+    fields: Vec<(String, Type)>,
+    variants: Vec<String>,
+    methods: Vec<Method>,
+}
+
+struct Exception {
+    base: syn::Path,
+}
+
+/// Registered type whose Python class is built at runtime by a `*_type(py)` helper.
+struct Dynamic {
+    bases: Vec<String>,
+    fields: Vec<String>,
+}
+
+#[derive(Default)]
+struct Crate<'a> {
+    /// By (file stem, Rust name): `delta::Transaction` is not `engine::Transaction`.
+    classes: BTreeMap<(String, String), Class>,
+    exceptions: BTreeMap<String, Exception>,
+    functions: BTreeMap<(String, String), Method>,
+    fns: HashMap<(String, String), &'a syn::ItemFn>,
+    consts: HashMap<(String, String), Vec<String>>,
+    /// Exported names -> what they are, for the top-level module only.
+    exported_classes: BTreeMap<String, (String, String)>,
+    exported_exceptions: BTreeMap<String, String>,
+    exported_functions: Vec<(String, String)>,
+    dynamic_fns: BTreeMap<String, (String, String)>,
+    dynamics: BTreeMap<String, Dynamic>,
+    /// Attributes set on exception types (by Rust name) during registration
+    /// (`busy.setattr("requester", ..)`).
+    exception_attrs: HashMap<String, Vec<String>>,
+}
+
+impl<'a> Crate<'a> {
+    fn collect(files: &'a BTreeMap<String, syn::File>) -> Self {
+        let mut krate = Crate::default();
+        for (stem, file) in files {
+            for item in &file.items {
+                krate.collect_item(stem, item);
+            }
+        }
+        krate.follow_registrations();
+        krate
+    }
+
+    fn collect_item(&mut self, stem: &str, item: &'a Item) {
+        match item {
+            Item::Struct(s) if has_attr(&s.attrs, "pyclass") && cfg_enabled(&s.attrs) => {
+                let get_all = options(&s.attrs, "pyclass").contains_key("get_all");
+                let fields = s.fields.iter().filter_map(|f| {
+                    let opts = options(&f.attrs, "pyo3");
+                    (get_all || opts.contains_key("get")).then(|| {
+                        let name = string_option(&opts, "name").unwrap_or_else(|| f.ident.as_ref().unwrap().to_string());
+                        (name, f.ty.clone())
+                    })
+                }).collect();
+                self.class_entry(stem, &s.ident.to_string(), &s.attrs).fields = fields;
+            }
+            Item::Enum(e) if has_attr(&e.attrs, "pyclass") && cfg_enabled(&e.attrs) => {
+                let variants = e.variants.iter()
+                    .map(|v| string_option(&options(&v.attrs, "pyo3"), "name").unwrap_or_else(|| v.ident.to_string()))
+                    .collect();
+                self.class_entry(stem, &e.ident.to_string(), &e.attrs).variants = variants;
+            }
+            Item::Impl(imp) if has_attr(&imp.attrs, "pymethods") && cfg_enabled(&imp.attrs) => {
+                let Type::Path(tp) = &*imp.self_ty else { return };
+                let rust = tp.path.segments.last().unwrap().ident.to_string();
+                let methods: Vec<Method> = imp.items.iter().filter_map(|i| match i {
+                    ImplItem::Fn(f) if cfg_enabled(&f.attrs) && !has_attr(&f.attrs, "setter") => Some(method(&f.attrs, &f.sig, Some(&rust))),
+                    _ => None,
+                }).collect();
+                self.class_entry(stem, &rust, &[]).methods.extend(methods);
+            }
+            Item::Fn(f) => {
+                if has_attr(&f.attrs, "pyfunction") && cfg_enabled(&f.attrs) {
+                    self.functions.insert((stem.to_string(), f.sig.ident.to_string()), method(&f.attrs, &f.sig, None));
+                }
+                self.fns.insert((stem.to_string(), f.sig.ident.to_string()), f);
+            }
+            Item::Macro(m) if m.mac.path.segments.last().is_some_and(|s| s.ident == "create_exception") => {
+                let pieces = split_commas(m.mac.tokens.clone());
+                if let [_, name, base] = pieces.as_slice() {
+                    let name: TokenStream = name.iter().cloned().collect();
+                    let base: TokenStream = base.iter().cloned().collect();
+                    if let Ok(base) = syn::parse2::<syn::Path>(base) {
+                        self.exceptions.insert(name.to_string(), Exception { base });
+                    }
+                }
+            }
+            Item::Const(c) => {
+                if let Expr::Reference(r) = &*c.expr {
+                    if let Expr::Array(arr) = &*r.expr {
+                        let values: Vec<String> = arr.elems.iter().filter_map(|e| match e {
+                            Expr::Lit(l) => match &l.lit { Lit::Str(s) => Some(s.value()), _ => None },
+                            _ => None,
+                        }).collect();
+                        if values.len() == arr.elems.len() {
+                            self.consts.insert((stem.to_string(), c.ident.to_string()), values);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn class_entry(&mut self, stem: &str, rust: &str, attrs: &[Attribute]) -> &mut Class {
+        let class = self.classes.entry((stem.to_string(), rust.to_string())).or_insert_with(|| Class {
+            name: rust.to_string(),
+            doc: None,
+            fields: Vec::new(),
+            variants: Vec::new(),
+            methods: Vec::new(),
+        });
+        if !attrs.is_empty() {
+            class.name = string_option(&options(attrs, "pyclass"), "name").unwrap_or_else(|| rust.to_string());
+            class.doc = doc(attrs);
+        }
+        class
+    }
+
+    fn follow_registrations(&mut self) {
+        let Some(entry) = self.fns.get(&("lib".to_string(), MODULE_FN.to_string())).copied() else {
+            panic!("no #[pymodule] fn {MODULE_FN} in src/lib.rs");
+        };
+        let module_param = module_params(entry).into_iter().next().expect("module parameter");
+        let mut walker = Registrations { krate: self, file: "lib".into(), modules: HashMap::new(), locals: HashMap::new(), loops: HashMap::new(), depth: 0 };
+        walker.modules.insert(module_param, true);
+        walker.visit_block(&entry.block);
+        let dynamic_fns = std::mem::take(&mut self.dynamic_fns);
+        for (name, (file, func)) in &dynamic_fns {
+            let dynamic = self.dynamic(&dynamic_fns, file, func);
+            self.dynamics.insert(name.clone(), dynamic);
+        }
+        self.dynamic_fns = dynamic_fns;
+    }
+
+    /// Bases and fields of the runtime-built type returned by `file::func`.
+    fn dynamic(&self, dynamic_fns: &BTreeMap<String, (String, String)>, file: &str, func: &str) -> Dynamic {
+        let mut scan = DynamicScan { krate: self, dynamic_fns, file: file.to_string(), bases: Vec::new(), fields: Vec::new(), expanding: 0 };
+        if let Some(f) = self.fns.get(&(file.to_string(), func.to_string())) {
+            scan.visit_block(&f.block);
+        }
+        let mut bases = Vec::new();
+        for base in scan.bases {
+            if !bases.contains(&base) {
+                bases.push(base);
+            }
+        }
+        Dynamic { bases, fields: scan.fields }
+    }
+
+    fn resolve_fn(&self, file: &str, path: &syn::Path) -> Option<(String, String)> {
+        let segs: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        let name = segs.last()?.clone();
+        let file = match segs.as_slice() {
+            [_] => file.to_string(),
+            [.., module, _] => module.clone(),
+            [] => return None,
+        };
+        self.fns.contains_key(&(file.clone(), name.clone())).then_some((file, name))
+    }
+
+    // -----------------------------------------------------------------------
+    // Rendering
+    // -----------------------------------------------------------------------
+
+    fn render(&self) -> String {
+        let mut out = String::from(HEADER);
+        let mut blocks: BTreeMap<String, String> = BTreeMap::new();
+        for (name, rust) in &self.exported_classes {
+            blocks.insert(name.clone(), self.render_class(name, rust));
+        }
+        for (name, rust) in &self.exported_exceptions {
+            let exc = &self.exceptions[rust];
+            let mut text = format!("class {name}({}):", self.exception_base(&exc.base));
+            let attrs = self.exception_attrs.get(rust).cloned().unwrap_or_default();
+            push_body(&mut text, None, &attrs.iter().map(|a| format!("{a}: Any")).collect::<Vec<_>>());
+            blocks.insert(name.clone(), text);
+        }
+        for (name, dynamic) in &self.dynamics {
+            let bases = if dynamic.bases.is_empty() { "Exception".to_string() } else { dynamic.bases.join(", ") };
+            let mut text = format!("class {name}({bases}):");
+            push_body(&mut text, None, &dynamic.fields.iter().map(|a| format!("{a}: Any")).collect::<Vec<_>>());
+            blocks.insert(name.clone(), text);
+        }
+        for block in blocks.values() {
+            out.push('\n');
+            out.push_str(block);
+            out.push('\n');
+        }
+        let mut functions: Vec<(&String, &Method)> = self.exported_functions.iter()
+            .filter_map(|key| self.functions.get(key).map(|m| (&m.name, m)))
+            .collect();
+        functions.sort_by_key(|(name, _)| (*name).clone());
+        for (_, function) in functions {
+            out.push('\n');
+            out.push_str(&self.render_method(function, None, ""));
+        }
+        out
+    }
+
+    fn render_class(&self, name: &str, key: &(String, String)) -> String {
+        let class = &self.classes[key];
+        let mut text = format!("class {name}:");
+        let mut lines: Vec<String> = class.variants.iter().map(|v| format!("{v}: {name}")).collect();
+        for (field, ty) in class.fields.iter().filter(|(f, _)| !KEYWORDS.contains(&f.as_str())) {
+            lines.push(format!("{field}: {}", self.py_type(ty, Some(name))));
+        }
+        let mut methods: Vec<&Method> = class.methods.iter().filter(|m| !KEYWORDS.contains(&m.name.as_str())).collect();
+        methods.sort_by(|a, b| a.name.cmp(&b.name));
+        for m in methods.iter().filter(|m| m.kind == Kind::ClassAttr) {
+            lines.push(format!("{}: {}", m.name, m.ret.as_ref().map_or("Any".into(), |t| self.py_type(t, Some(name)))));
+        }
+        let rendered: Vec<String> = methods.iter().filter(|m| m.kind != Kind::ClassAttr)
+            .map(|m| self.render_method(m, Some(name), "    "))
+            .collect();
+        push_body(&mut text, class.doc.as_deref(), &lines);
+        if !rendered.is_empty() {
+            if text.ends_with(": ...") {
+                text.truncate(text.len() - 4);
+            }
+            for block in rendered {
+                text.push('\n');
+                text.push_str(block.trim_end_matches('\n'));
+            }
+        }
+        text
+    }
+
+    fn render_method(&self, m: &Method, class: Option<&str>, indent: &str) -> String {
+        let mut params: Vec<String> = Vec::new();
+        match (class, &m.kind) {
+            (Some(_), Kind::Instance | Kind::Init | Kind::Getter) => params.push("self".into()),
+            (Some(_), Kind::Class) => params.push("cls".into()),
+            _ => {}
+        }
+        for p in &m.params {
+            params.push(match p {
+                Param::Named { name, ty, default } => {
+                    let ty = ty.as_ref().map_or("Any".into(), |t| self.py_type(t, class));
+                    match default {
+                        Some(d) => format!("{name}: {ty} = {d}"),
+                        None => format!("{name}: {ty}"),
+                    }
+                }
+                Param::Args(name) => format!("*{name}: Any"),
+                Param::Kwargs(name) => format!("**{name}: Any"),
+                Param::Slash => "/".into(),
+                Param::Star => "*".into(),
+            });
+        }
+        let ret = match (&m.kind, &m.ret) {
+            (Kind::Init, _) | (_, None) => "None".to_string(),
+            (_, Some(t)) => self.py_type(t, class),
+        };
+        let mut text = String::new();
+        match if class.is_some() { &m.kind } else { &Kind::Instance } {
+            Kind::Getter => writeln!(text, "{indent}@property").unwrap(),
+            Kind::Static => writeln!(text, "{indent}@staticmethod").unwrap(),
+            Kind::Class => writeln!(text, "{indent}@classmethod").unwrap(),
+            _ => {}
+        }
+        let keyword = if m.is_async { "async def" } else { "def" };
+        write!(text, "{indent}{keyword} {}({}) -> {ret}:", m.name, params.join(", ")).unwrap();
+        match &m.doc {
+            Some(d) => {
+                text.push('\n');
+                text.push_str(&docstring(d, &format!("{indent}    ")));
+                writeln!(text, "\n{indent}    ...").unwrap();
+            }
+            None => text.push_str(" ...\n"),
+        }
+        text
+    }
+
+    fn exception_base(&self, base: &syn::Path) -> String {
+        let last = base.segments.last().unwrap().ident.to_string();
+        if base.segments.iter().any(|s| s.ident == "exceptions") || (base.segments.len() == 1 && last.starts_with("Py") && !self.exceptions.contains_key(&last)) {
+            return last.strip_prefix("Py").unwrap_or(&last).to_string();
+        }
+        self.exported_exceptions.iter().find(|(_, rust)| **rust == last).map_or(last.clone(), |(name, _)| name.clone())
+    }
+
+    /// Python annotation for a Rust type.
+    fn py_type(&self, ty: &Type, class: Option<&str>) -> String {
+        match ty {
+            Type::Reference(r) => self.py_type(&r.elem, class),
+            Type::Paren(p) => self.py_type(&p.elem, class),
+            Type::Group(g) => self.py_type(&g.elem, class),
+            Type::Tuple(t) if t.elems.is_empty() => "None".into(),
+            Type::Tuple(t) => format!("tuple[{}]", t.elems.iter().map(|e| self.py_type(e, class)).collect::<Vec<_>>().join(", ")),
+            Type::Slice(s) if is_ident(&s.elem, "u8") => "bytes".into(),
+            Type::Slice(s) => format!("list[{}]", self.py_type(&s.elem, class)),
+            Type::Path(p) => {
+                let seg = p.path.segments.last().unwrap();
+                let args: Vec<&Type> = match &seg.arguments {
+                    PathArguments::AngleBracketed(a) => a.args.iter().filter_map(|g| match g {
+                        GenericArgument::Type(t) => Some(t),
+                        _ => None,
+                    }).collect(),
+                    _ => Vec::new(),
+                };
+                let arg = |i: usize| args.get(i).map_or("Any".to_string(), |t| self.py_type(t, class));
+                match seg.ident.to_string().as_str() {
+                    "Option" => {
+                        let inner = arg(0);
+                        if inner == "Any" { inner } else { format!("{inner} | None") }
+                    }
+                    "PyResult" | "Result" | "Arc" | "Box" | "Rc" | "Cow" => arg(0),
+                    "Py" | "Bound" | "Borrowed" | "PyRef" | "PyRefMut" => args.last().map_or("Any".into(), |t| self.py_type(t, class)),
+                    "Vec" if args.first().is_some_and(|t| is_ident(t, "u8")) => "bytes".into(),
+                    "Vec" | "VecDeque" => format!("list[{}]", arg(0)),
+                    "HashSet" | "BTreeSet" => format!("set[{}]", arg(0)),
+                    "HashMap" | "BTreeMap" | "IndexMap" => format!("dict[{}, {}]", arg(0), arg(1)),
+                    "String" | "str" | "char" | "PathBuf" | "Path" | "PyString" => "str".into(),
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "PyLong" | "PyInt" => "int".into(),
+                    "f32" | "f64" | "PyFloat" => "float".into(),
+                    "bool" | "PyBool" => "bool".into(),
+                    "PyDict" => "dict[str, Any]".into(),
+                    "PyList" => "list[Any]".into(),
+                    "PyTuple" => "tuple[Any, ...]".into(),
+                    "PyBytes" => "bytes".into(),
+                    "PySet" => "set[Any]".into(),
+                    "PyType" => "type".into(),
+                    "Self" => class.unwrap_or("Any").into(),
+                    name => self.exported_classes.iter().find(|(_, (_, rust))| rust == name).map_or("Any".into(), |(py, _)| py.clone()),
+                }
+            }
+            _ => "Any".into(),
+        }
+    }
+}
+
+fn is_ident(ty: &Type, name: &str) -> bool {
+    matches!(ty, Type::Path(p) if p.path.is_ident(name))
+}
+
+fn docstring(text: &str, indent: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    let body: Vec<String> = escaped.lines().map(|l| if l.trim().is_empty() { String::new() } else { format!("{indent}{l}") }).collect();
+    if body.len() == 1 {
+        format!("{indent}\"\"\"{}\"\"\"", escaped.trim())
+    } else {
+        format!("{indent}\"\"\"\n{}\n{indent}\"\"\"", body.join("\n"))
+    }
+}
+
+/// Appends a class body (docstring + attribute lines), or `...` when both are empty.
+fn push_body(text: &mut String, doc: Option<&str>, lines: &[String]) {
+    if doc.is_none() && lines.is_empty() {
+        text.push_str(" ...");
+        return;
+    }
+    if let Some(d) = doc {
+        text.push('\n');
+        text.push_str(&docstring(d, "    "));
+    }
+    for line in lines {
+        text.push_str("\n    ");
+        text.push_str(line);
+    }
+}
+
+/// Names of `&Bound<PyModule>` parameters of a registration function.
+fn module_params(f: &syn::ItemFn) -> Vec<String> {
+    f.sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(t) if quote_type(&t.ty).contains("PyModule") => match &*t.pat {
+            Pat::Ident(i) => Some(i.ident.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }).collect()
+}
+
+fn quote_type(ty: &Type) -> String {
+    let mut tokens = TokenStream::new();
+    quote::ToTokens::to_tokens(ty, &mut tokens);
+    tokens.to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Signatures
+// ---------------------------------------------------------------------------
+
+/// `class`: Rust name of the #[pymethods] type, `None` for a #[pyfunction].
+fn method(attrs: &[Attribute], sig: &syn::Signature, class: Option<&str>) -> Method {
+    let opts = options(attrs, "pyo3");
+    let kind = if class.is_none() {
+        Kind::Static
+    } else if has_attr(attrs, "new") {
+        Kind::Init
+    } else if has_attr(attrs, "getter") {
+        Kind::Getter
+    } else if has_attr(attrs, "classattr") {
+        Kind::ClassAttr
+    } else if has_attr(attrs, "staticmethod") {
+        Kind::Static
+    } else if has_attr(attrs, "classmethod") {
+        Kind::Class
+    } else {
+        Kind::Instance
+    };
+    let rust_name = sig.ident.to_string();
+    let name = match kind {
+        Kind::Init => "__init__".to_string(),
+        Kind::Getter => attr_tokens(attrs, "getter").next().map(|t| t.to_string()).filter(|t| !t.is_empty())
+            .unwrap_or_else(|| rust_name.strip_prefix("get_").unwrap_or(&rust_name).to_string()),
+        _ => string_option(&opts, "name").unwrap_or(rust_name),
+    };
+    // Rust arguments Python sees: drop the receiver, `py: Python`, `slf` / `cls` handles.
+    let mut rust_args: Vec<(String, Type)> = Vec::new();
+    for (i, arg) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(t) = arg else { continue };
+        let Pat::Ident(ident) = &*t.pat else { continue };
+        let ty_text = quote_type(&t.ty);
+        let is_receiver = i == 0 && match kind {
+            Kind::Class => true,
+            Kind::Static => false,
+            _ => class.is_some_and(|c| ty_text.split(|ch: char| !ch.is_alphanumeric() && ch != '_').any(|w| w == "Self" || w == c)),
+        };
+        if ty_text.starts_with("Python") || is_receiver {
+            continue;
+        }
+        rust_args.push((ident.ident.to_string(), (*t.ty).clone()));
+    }
+    let params = if let Some(tokens) = opts.get("signature") {
+        signature_params(tokens, &rust_args)
+    } else {
+        let trailing_options = rust_args.iter().rev().take_while(|(_, t)| quote_type(t).starts_with("Option")).count();
+        let cut = rust_args.len() - trailing_options;
+        rust_args.iter().enumerate()
+            .map(|(i, (name, ty))| Param::Named { name: name.clone(), ty: Some(Box::new(ty.clone())), default: (i >= cut).then(|| "None".to_string()) })
+            .collect()
+    };
+    let ret = match &sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, t) => Some((**t).clone()),
+    };
+    Method { name, kind, params, ret, is_async: sig.asyncness.is_some(), doc: doc(attrs) }
+}
+
+fn signature_params(tokens: &[TokenTree], rust_args: &[(String, Type)]) -> Vec<Param> {
+    let Some(TokenTree::Group(group)) = tokens.first() else { return Vec::new() };
+    if group.delimiter() != Delimiter::Parenthesis {
+        return Vec::new();
+    }
+    let ty_of = |name: &str| rust_args.iter().find(|(n, _)| n == name).map(|(_, t)| Box::new(t.clone()));
+    split_commas(group.stream()).into_iter().map(|piece| {
+        let stars = piece.iter().take_while(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '*')).count();
+        let ident = piece.iter().find_map(|t| match t {
+            TokenTree::Ident(i) => Some(i.to_string()),
+            _ => None,
+        });
+        match (stars, ident) {
+            (0, None) => Param::Slash,
+            (1, Some(name)) => Param::Args(name),
+            (2, Some(name)) => Param::Kwargs(name),
+            (_, Some(name)) => {
+                let default = piece.iter().position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '='))
+                    .map(|eq| py_default(&piece[eq + 1..].iter().cloned().collect::<TokenStream>().to_string()));
+                Param::Named { ty: ty_of(&name), name, default }
+            }
+            // `*` alone (and malformed pieces).
+            _ => Param::Star,
+        }
+    }).collect()
+}
+
+/// Python rendering of a signature default; non-literal expressions become `...`.
+fn py_default(rust: &str) -> String {
+    let text = rust.replace(' ', "");
+    match text.as_str() {
+        "None" => "None".into(),
+        "true" => "True".into(),
+        "false" => "False".into(),
+        _ if text.starts_with('"') && text.ends_with('"') => rust.trim().to_string(),
+        _ if text.trim_start_matches('-').parse::<f64>().is_ok() => text,
+        _ => "...".into(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registration walk
+// ---------------------------------------------------------------------------
+
+struct Registrations<'c, 'a> {
+    krate: &'c mut Crate<'a>,
+    file: String,
+    /// Module handles in scope; `true` for the top-level module.
+    modules: HashMap<String, bool>,
+    locals: HashMap<String, Expr>,
+    loops: HashMap<String, Vec<String>>,
+    depth: usize,
+}
+
+impl Registrations<'_, '_> {
+    fn top_level(&self, receiver: &Expr) -> bool {
+        path_ident(receiver).and_then(|r| self.modules.get(&r).copied()).unwrap_or(false)
+    }
+
+    /// Rust type named by `py.get_type_bound::<T>()` inside `expr` (following locals).
+    fn type_of(&self, expr: &Expr) -> Option<syn::Path> {
+        if let Some(ident) = path_ident(expr) {
+            return self.locals.get(&ident).and_then(|e| self.type_of(e));
+        }
+        let mut find = TypeBound(None);
+        find.visit_expr(expr);
+        find.0
+    }
+
+    /// (file stem, Rust name) an item path refers to from the current file.
+    fn key(&self, path: &syn::Path) -> (String, String) {
+        let segs: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        let file = if segs.len() > 1 { segs[segs.len() - 2].clone() } else { self.file.clone() };
+        (file, segs.last().unwrap().clone())
+    }
+
+    fn register(&mut self, name: Option<String>, path: &syn::Path) {
+        let key = self.key(path);
+        if let Some(class) = self.krate.classes.get(&key) {
+            self.krate.exported_classes.insert(name.unwrap_or_else(|| class.name.clone()), key);
+        } else if self.krate.exceptions.contains_key(&key.1) {
+            self.krate.exported_exceptions.insert(name.unwrap_or_else(|| key.1.clone()), key.1);
+        }
+    }
+}
+
+struct TypeBound(Option<syn::Path>);
+
+impl<'ast> Visit<'ast> for TypeBound {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if self.0.is_none() && call.method == "get_type_bound" {
+            if let Some(GenericArgument::Type(Type::Path(p))) = call.turbofish.as_ref().and_then(|t| t.args.first()) {
+                self.0 = Some(p.path.clone());
+            }
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+}
+
+fn path_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(p) => p.path.get_ident().map(ToString::to_string),
+        Expr::Reference(r) => path_ident(&r.expr),
+        Expr::Unary(u) => path_ident(&u.expr),
+        Expr::Try(t) => path_ident(&t.expr),
+        _ => None,
+    }
+}
+
+fn called_fn(expr: &Expr) -> Option<&syn::Path> {
+    match expr {
+        Expr::Call(c) => match &*c.func {
+            Expr::Path(p) => Some(&p.path),
+            _ => None,
+        },
+        Expr::Try(t) => called_fn(&t.expr),
+        _ => None,
+    }
+}
+
+impl<'ast> Visit<'ast> for Registrations<'_, '_> {
+    fn visit_stmt(&mut self, stmt: &'ast syn::Stmt) {
+        let attrs = match stmt {
+            syn::Stmt::Local(l) => &l.attrs,
+            syn::Stmt::Macro(m) => &m.attrs,
+            syn::Stmt::Expr(Expr::Block(b), _) => &b.attrs,
+            _ => return syn::visit::visit_stmt(self, stmt),
+        };
+        if cfg_enabled(attrs) {
+            syn::visit::visit_stmt(self, stmt);
+        }
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let (Pat::Ident(ident), Some(init)) = (&local.pat, &local.init) {
+            let name = ident.ident.to_string();
+            if called_fn(&init.expr).is_some_and(|p| p.segments.iter().any(|s| s.ident == "PyModule")) {
+                self.modules.insert(name.clone(), false);
+            }
+            self.locals.insert(name, (*init.expr).clone());
+        }
+        syn::visit::visit_local(self, local);
+    }
+
+    fn visit_expr_for_loop(&mut self, f: &'ast syn::ExprForLoop) {
+        let bound = match (&*f.pat, path_ident(&f.expr)) {
+            (Pat::Ident(i), Some(source)) => self.krate.consts.get(&(self.file.clone(), source)).cloned().map(|v| (i.ident.to_string(), v)),
+            _ => None,
+        };
+        if let Some((var, values)) = &bound {
+            self.loops.insert(var.clone(), values.clone());
+        }
+        syn::visit::visit_expr_for_loop(self, f);
+        if let Some((var, _)) = bound {
+            self.loops.remove(&var);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        let args: Vec<&Expr> = call.args.iter().collect();
+        match method.as_str() {
+            "add_class" if self.top_level(&call.receiver) => {
+                if let Some(GenericArgument::Type(Type::Path(p))) = call.turbofish.as_ref().and_then(|t| t.args.first()) {
+                    self.register(None, &p.path);
+                }
+            }
+            "add_function" if self.top_level(&call.receiver) => {
+                let mut mac = None;
+                for arg in &args {
+                    let mut find = WrapMacro(None);
+                    find.visit_expr(arg);
+                    mac = mac.or(find.0);
+                }
+                if let Some(path) = mac {
+                    let segs: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+                    let file = if segs.len() > 1 { segs[segs.len() - 2].clone() } else { self.file.clone() };
+                    self.krate.exported_functions.push((file, segs.last().unwrap().clone()));
+                }
+            }
+            "add" if self.top_level(&call.receiver) && args.len() == 2 => {
+                if let Expr::Lit(syn::ExprLit { lit: Lit::Str(name), .. }) = args[0] {
+                    if let Some(path) = self.type_of(args[1]) {
+                        self.register(Some(name.value()), &path);
+                    } else if let Some(path) = called_fn(args[1]) {
+                        if let Some(target) = self.krate.resolve_fn(&self.file, path) {
+                            self.krate.dynamic_fns.insert(name.value(), target);
+                        }
+                    }
+                }
+            }
+            "setattr" => {
+                if let (Some(path), Some(first)) = (self.type_of(&call.receiver), args.first()) {
+                    let rust = path.segments.last().unwrap().ident.to_string();
+                    let attrs: Vec<String> = match first {
+                        Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) => vec![s.value()],
+                        other => path_ident(other).and_then(|v| self.loops.get(&v).cloned()).unwrap_or_default(),
+                    };
+                    let entry = self.krate.exception_attrs.entry(rust).or_default();
+                    for attr in attrs {
+                        if !entry.contains(&attr) {
+                            entry.push(attr);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // Registration helpers taking the module (`errors::register(py, m)`, `shm::theus_shm(py, &shm_mod)`).
+        if let Expr::Path(p) = &*call.func {
+            let passed: Vec<bool> = call.args.iter().filter_map(|a| path_ident(a).and_then(|i| self.modules.get(&i).copied())).collect();
+            if let (Some((file, name)), Some(top)) = (self.krate.resolve_fn(&self.file, &p.path), passed.first().copied()) {
+                if self.depth < 8 {
+                    let f = self.krate.fns[&(file.clone(), name)];
+                    if let Some(param) = module_params(f).into_iter().next() {
+                        let mut inner = Registrations {
+                            krate: &mut *self.krate,
+                            file,
+                            modules: HashMap::from([(param, top)]),
+                            locals: HashMap::new(),
+                            loops: HashMap::new(),
+                            depth: self.depth + 1,
+                        };
+                        inner.visit_block(&f.block);
+                    }
+                }
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+}
+
+struct WrapMacro(Option<syn::Path>);
+
+impl<'ast> Visit<'ast> for WrapMacro {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.is_ident("wrap_pyfunction") {
+            if let Some(first) = split_commas(mac.tokens.clone()).into_iter().next() {
+                self.0 = syn::parse2(first.into_iter().collect()).ok();
+            }
+        }
+    }
+}
+
+/// Collects the bases / fields of a runtime-built exception type.
+struct DynamicScan<'c, 'a> {
+    krate: &'c Crate<'a>,
+    dynamic_fns: &'c BTreeMap<String, (String, String)>,
+    file: String,
+    bases: Vec<String>,
+    fields: Vec<String>,
+    expanding: usize,
+}
+
+impl<'ast> Visit<'ast> for DynamicScan<'_, '_> {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "get_type_bound" {
+            if let Some(GenericArgument::Type(Type::Path(p))) = call.turbofish.as_ref().and_then(|t| t.args.first()) {
+                let rust = p.path.segments.last().unwrap().ident.to_string();
+                if rust != "PyType" {
+                    self.bases.push(self.krate.exception_base(&p.path));
+                }
+            }
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(p) = &*call.func {
+            if let Some(target) = self.krate.resolve_fn(&self.file, &p.path) {
+                if let Some((name, _)) = self.dynamic_fns.iter().find(|(_, t)| **t == target) {
+                    self.bases.push(name.clone());
+                } else if self.expanding < 4 {
+                    // Helper building the type (e.g. `hybrid`): its fixed bases come first.
+                    self.expanding += 1;
+                    let f = self.krate.fns[&target];
+                    self.visit_block(&f.block);
+                    self.expanding -= 1;
+                }
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if let Some(ident) = path.path.get_ident() {
+            if let Some(values) = self.krate.consts.get(&(self.file.clone(), ident.to_string())) {
+                for v in values {
+                    if !self.fields.contains(v) {
+                        self.fields.push(v.clone());
+                    }
+                }
+            }
+        }
+    }
+}
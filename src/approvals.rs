@@ -0,0 +1,176 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyPermissionError, PyValueError};
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Two-person rule for compliance-sensitive paths. Commits of non-admin transactions
+// do not write below an approval path: those writes are parked as a proposal and reach the
+// state only when a GRANT holder other than the proposer approves them. Every step goes to
+// the audit log (APPROVAL_PROPOSED / APPROVAL_GRANTED / APPROVAL_REJECTED).
+// NOTE: Approved writes are merged into the *current* state; they do not re-check the
+// version the proposal was made against.
+
+struct Proposal {
+    tx: u64,
+    proposer: Option<String>,
+    writes: Vec<(String, PyObject)>,
+    created_ms: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    paths: Vec<String>,
+    /// Holders of the GRANT capability.
+    approvers: BTreeSet<String>,
+    proposals: BTreeMap<u64, Proposal>,
+    next_id: u64,
+}
+
+#[derive(Default)]
+pub struct ApprovalQueue {
+    inner: Mutex<Inner>,
+}
+
+fn lookup<'py>(root: &Bound<'py, PyAny>, segments: &[&str]) -> Option<Bound<'py, PyAny>> {
+    let mut current = root.clone();
+    for seg in segments {
+        current = current.downcast::<PyDict>().ok()?.get_item(seg).ok()??;
+    }
+    Some(current)
+}
+
+fn committed_value<'py>(py: Python<'py>, state: &State, segments: &[&str]) -> Option<Bound<'py, PyAny>> {
+    let root = state.data.get(segments[0])?.bind(py).clone();
+    lookup(&root, &segments[1..])
+}
+
+impl ApprovalQueue {
+    pub fn set_paths(&self, paths: Vec<String>) -> PyResult<()> {
+        let mut normalized = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = path.replace('[', ".").replace(']', "");
+            if path.is_empty() || path.split('.').any(str::is_empty) {
+                return Err(PyValueError::new_err(format!("Invalid approval path '{path}'")));
+            }
+            normalized.push(path);
+        }
+        normalized.sort();
+        normalized.dedup();
+        self.inner.relock().paths = normalized;
+        Ok(())
+    }
+
+    pub fn paths(&self) -> Vec<String> {
+        self.inner.relock().paths.clone()
+    }
+
+    pub fn grant(&self, approver: String) {
+        crate::audit::log_global("APPROVER_GRANTED", &approver);
+        self.inner.relock().approvers.insert(approver);
+    }
+
+    pub fn revoke(&self, approver: &str) -> bool {
+        let removed = self.inner.relock().approvers.remove(approver);
+        if removed {
+            crate::audit::log_global("APPROVER_REVOKED", approver);
+        }
+        removed
+    }
+
+    pub fn approvers(&self) -> Vec<String> {
+        self.inner.relock().approvers.iter().cloned().collect()
+    }
+
+    /// Moves writes below approval paths out of `pending` when they differ from `state`.
+    pub fn extract(&self, py: Python, pending: &Bound<PyDict>, state: &State) -> PyResult<Vec<(String, PyObject)>> {
+        let paths = self.paths();
+        let mut parked = Vec::new();
+        for path in &paths {
+            let segments: Vec<&str> = path.split('.').collect();
+            let Some(value) = lookup(pending.as_any(), &segments) else { continue };
+            let unchanged = match committed_value(py, state, &segments) {
+                Some(committed) => committed.eq(&value).unwrap_or(false),
+                None => false,
+            };
+            if unchanged {
+                continue;
+            }
+            let (last, parents) = segments.split_last().expect("approval paths are non-empty");
+            if let Some(parent) = lookup(pending.as_any(), parents) {
+                parent.downcast::<PyDict>()?.del_item(*last)?;
+            }
+            parked.push((path.clone(), value.unbind()));
+        }
+        Ok(parked)
+    }
+
+    pub fn propose(&self, tx: u64, proposer: Option<String>, tags: Option<&crate::tags::Tags>, writes: Vec<(String, PyObject)>) -> u64 {
+        let mut inner = self.inner.relock();
+        inner.next_id += 1;
+        let id = inner.next_id;
+        let paths: Vec<&str> = writes.iter().map(|(p, _)| p.as_str()).collect();
+        crate::audit::log_global_tagged("APPROVAL_PROPOSED", &format!(
+            "#{id} tx={tx} by {} paths={paths:?}", proposer.as_deref().unwrap_or("<anonymous>")
+        ), tags);
+        inner.proposals.insert(id, Proposal { tx, proposer, writes, created_ms: crate::clock::now_ms() });
+        id
+    }
+
+    pub fn proposals(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        let inner = self.inner.relock();
+        inner.proposals.iter()
+            .map(|(id, p)| {
+                let writes = PyDict::new_bound(py);
+                for (path, value) in &p.writes {
+                    writes.set_item(path, value.clone_ref(py))?;
+                }
+                let d = PyDict::new_bound(py);
+                d.set_item("id", id)?;
+                d.set_item("tx", p.tx)?;
+                d.set_item("proposer", p.proposer.as_deref())?;
+                d.set_item("writes", writes)?;
+                d.set_item("created_ms", p.created_ms)?;
+                Ok(d.into_any().unbind())
+            })
+            .collect()
+    }
+
+    /// GRANT + two-person checks for `approver` on proposal `id`.
+    fn check(inner: &Inner, id: u64, approver: &str) -> PyResult<()> {
+        let proposal = inner.proposals.get(&id).ok_or_else(|| PyKeyError::new_err(format!("No pending proposal #{id}")))?;
+        if !inner.approvers.contains(approver) {
+            return Err(PyPermissionError::new_err(format!("'{approver}' lacks the GRANT capability")));
+        }
+        if proposal.proposer.as_deref() == Some(approver) {
+            return Err(PyPermissionError::new_err(format!("Two-person rule: '{approver}' proposed #{id} and cannot approve it")));
+        }
+        Ok(())
+    }
+
+    /// `data` for `compare_and_swap` carrying the proposal's writes (the proposal stays queued).
+    pub fn approved_data(&self, py: Python, id: u64, approver: &str) -> PyResult<Py<PyDict>> {
+        let inner = self.inner.relock();
+        Self::check(&inner, id, approver)?;
+        let data = PyDict::new_bound(py).unbind();
+        for (path, value) in &inner.proposals[&id].writes {
+            crate::structures_helper::set_nested_value(py, &data, path, value)?;
+        }
+        Ok(data)
+    }
+
+    pub fn applied(&self, id: u64, approver: &str, version: u64) {
+        self.inner.relock().proposals.remove(&id);
+        crate::audit::log_global("APPROVAL_GRANTED", &format!("#{id} by {approver} -> v{version}"));
+    }
+
+    pub fn reject(&self, id: u64, approver: &str, reason: Option<&str>) -> PyResult<()> {
+        let mut inner = self.inner.relock();
+        Self::check(&inner, id, approver)?;
+        inner.proposals.remove(&id);
+        crate::audit::log_global("APPROVAL_REJECTED", &format!("#{id} by {approver}: {}", reason.unwrap_or("-")));
+        Ok(())
+    }
+}
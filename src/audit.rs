@@ -2,7 +2,7 @@ use pyo3::prelude::*;
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::locks::Relock;
 
 // ============================================================================
 // Exception Types
@@ -44,10 +44,17 @@ pub struct AuditLogEntry {
     pub key: String,
     #[pyo3(get)]
     pub message: String,
+    /// [v3.6] Tags of the transaction the event belongs to.
+    pub tags: Option<crate::tags::Tags>,
 }
 
 #[pymethods]
 impl AuditLogEntry {
+    #[getter]
+    fn tags<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        crate::tags::to_dict(py, self.tags.as_ref())
+    }
+
     fn __str__(&self) -> String {
         format!("[{}] {}: {}", self.timestamp, self.key, self.message)
     }
@@ -68,7 +75,7 @@ pub struct RingBuffer {
 }
 
 impl RingBuffer {
-    #[must_use] 
+    #[must_use]
     pub fn new(capacity: usize) -> Self {
         RingBuffer {
             buffer: Vec::with_capacity(capacity),
@@ -88,7 +95,7 @@ impl RingBuffer {
         self.count += 1;
     }
 
-    #[must_use] 
+    #[must_use]
     pub fn get_all(&self) -> Vec<AuditLogEntry> {
         if self.buffer.len() < self.capacity {
             // Not yet wrapped around
@@ -104,12 +111,44 @@ impl RingBuffer {
         }
     }
 
-    #[must_use] 
+    #[must_use]
     pub fn len(&self) -> usize {
         self.buffer.len()
     }
 
-    #[must_use] 
+    /// [v3.6] fsck "audit" check: cursor and count agree with the stored entries (rebuilt on
+    /// `repair`). Timestamps are not compared: test clocks move them backwards legitimately.
+    pub fn fsck(&mut self, repair: bool) -> Vec<crate::fsck::Issue> {
+        use crate::fsck::Issue;
+        let mut issues = Vec::new();
+        if self.capacity == 0 {
+            return issues;
+        }
+        let len = self.buffer.len();
+        let in_sync = if len < self.capacity {
+            self.write_pos == len && self.count == len
+        } else {
+            len == self.capacity && self.count >= len && self.write_pos == self.count % self.capacity
+        };
+        if !in_sync {
+            issues.push(Issue::error("audit", format!(
+                "ring cursor out of sync: {len} entries, capacity {}, write_pos {}, count {}", self.capacity, self.write_pos, self.count
+            )).repairable(repair));
+            if repair {
+                self.buffer.truncate(self.capacity);
+                let len = self.buffer.len();
+                if len < self.capacity {
+                    (self.write_pos, self.count) = (len, len);
+                } else {
+                    self.count = self.count.max(len);
+                    self.write_pos = self.count % self.capacity;
+                }
+            }
+        }
+        issues
+    }
+
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.buffer.is_empty()
     }
@@ -256,15 +295,15 @@ impl AuditSystem {
     }
 
     /// Get current count for a key.
-    #[must_use] 
+    #[must_use]
     pub fn get_count(&self, key: &str) -> u32 {
         *self.counts.get(key).unwrap_or(&0)
     }
 
     /// Get total count across all keys.
-    #[must_use] 
+    #[must_use]
     pub fn get_count_all(&self) -> usize {
-        self.ring_buffer.lock().unwrap().count
+        self.ring_buffer.relock().count
     }
 
     /// Log a general event to ring buffer.
@@ -274,31 +313,61 @@ impl AuditSystem {
     }
 
     /// Get all logs from ring buffer.
-    #[must_use] 
-    pub fn get_logs(&self) -> Vec<AuditLogEntry> {
-        self.ring_buffer.lock().unwrap().get_all()
+    /// [v3.6] `tags` keeps only events of transactions carrying all of those tags.
+    #[pyo3(signature = (tags=None))]
+    pub fn get_logs(&self, tags: Option<&Bound<'_, pyo3::types::PyDict>>) -> PyResult<Vec<AuditLogEntry>> {
+        let filter = crate::tags::from_py(tags)?;
+        let mut logs = self.ring_buffer.relock().get_all();
+        logs.retain(|e| crate::tags::matches(e.tags.as_ref(), filter.as_ref()));
+        Ok(logs)
     }
 
     /// Get number of logs in buffer.
     #[getter]
-    #[must_use] 
+    #[must_use]
     pub fn ring_buffer_len(&self) -> usize {
-        self.ring_buffer.lock().unwrap().len()
+        self.ring_buffer.relock().len()
     }
 }
 
 impl AuditSystem {
     fn log_internal(&self, key: &str, message: &str) {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_or(0.0, |d| d.as_secs_f64());
+        let timestamp = crate::clock::now_secs();
 
         let entry = AuditLogEntry {
             timestamp,
             key: key.to_string(),
             message: message.to_string(),
+            tags: None,
         };
 
-        self.ring_buffer.lock().unwrap().push(entry);
+        crate::testing::on_audit(&entry);
+        self.ring_buffer.relock().push(entry);
     }
 }
+
+/// [v3.6] fsck "audit" check of the process-global ring buffer.
+pub fn fsck_global(repair: bool) -> Vec<crate::fsck::Issue> {
+    crate::globals::GLOBAL_AUDIT_BUFFER.get().map_or_else(Vec::new, |buffer| buffer.relock().fsck(repair))
+}
+
+/// [v3.6] Push an engine-internal event into the process-global audit ring buffer
+/// (initialized with the default capacity if no `AuditSystem` exists yet).
+pub fn log_global(key: &str, message: &str) {
+    log_global_tagged(key, message, None);
+}
+
+/// [v3.6] `log_global` for events raised on behalf of a (possibly tagged) transaction.
+pub fn log_global_tagged(key: &str, message: &str, tags: Option<&crate::tags::Tags>) {
+    use crate::globals::GLOBAL_AUDIT_BUFFER;
+    let timestamp = crate::clock::now_secs();
+    let buffer = GLOBAL_AUDIT_BUFFER.get_or_init(|| Arc::new(Mutex::new(RingBuffer::new(1000))));
+    let entry = AuditLogEntry {
+        timestamp,
+        key: key.to_string(),
+        message: message.to_string(),
+        tags: tags.cloned(),
+    };
+    crate::testing::on_audit(&entry);
+    buffer.relock().push(entry);
+}
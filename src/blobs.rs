@@ -0,0 +1,295 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::types::{PyBytes, PyDict, PyList, PyString, PyTuple};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Content-addressable blob store. `ctx.blobs.put(data)` (or `tx.blobs` / `engine.blobs`)
+// writes the bytes once to a file named by their SHA-256 and returns the id
+// `"sha256:<hex>"`; state stores only that id, so snapshots and deltas stay small and equal
+// payloads are deduplicated. `blobs.get(id)` reads them back.
+// `engine.gc_blobs()` counts, per blob, the retained state versions (current, retained and
+// pinned, see pins.rs) whose Data/Heavy zones reference its id, and deletes the unreferenced
+// ones - except blobs put by a transaction that is still open.
+// NOTE: Blobs put outside a transaction are unprotected: reference them in a commit before the
+// next gc. Ids inside spilled or compressed values are not seen by the scan. The default
+// directory is a temp dir removed with the engine; `set_blob_dir()` keeps blobs on disk.
+
+const PREFIX: &str = "sha256:";
+
+struct Blob {
+    size: u64,
+    /// Referencing versions as of the last gc.
+    refs: usize,
+    /// Transaction that put it (protected while open).
+    put_by: Option<u64>,
+}
+
+struct Inner {
+    dir: Option<PathBuf>,
+    owned: bool,
+    blobs: HashMap<String, Blob>,
+    bytes: u64,
+    reclaimed: u64,
+}
+
+pub struct BlobStore {
+    inner: Mutex<Inner>,
+}
+
+impl Default for BlobStore {
+    fn default() -> Self {
+        BlobStore { inner: Mutex::new(Inner { dir: None, owned: true, blobs: HashMap::new(), bytes: 0, reclaimed: 0 }) }
+    }
+}
+
+impl Drop for BlobStore {
+    fn drop(&mut self) {
+        let inner = self.inner.get_mut().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let (true, Some(dir)) = (inner.owned, &inner.dir) {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+fn io_err(e: &std::io::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("Blob store I/O failed: {e}"))
+}
+
+fn is_blob_id(s: &str) -> bool {
+    s.len() == PREFIX.len() + 64 && s.starts_with(PREFIX) && s[PREFIX.len()..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+impl Inner {
+    fn dir(&mut self) -> PyResult<PathBuf> {
+        if let Some(dir) = &self.dir {
+            return Ok(dir.clone());
+        }
+        let dir = std::env::temp_dir().join(format!("theus_blobs_{}_{}", std::process::id(), uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).map_err(|e| io_err(&e))?;
+        self.dir = Some(dir.clone());
+        Ok(dir)
+    }
+
+    fn file(&mut self, id: &str) -> PyResult<PathBuf> {
+        Ok(self.dir()?.join(&id[PREFIX.len()..]))
+    }
+}
+
+impl BlobStore {
+    /// Moves the store to `path` (created if missing); existing blob files there are adopted
+    /// (unreferenced until the next gc). Only allowed while the store is empty.
+    pub fn set_dir(&self, path: &str) -> PyResult<()> {
+        let mut inner = self.inner.relock();
+        if !inner.blobs.is_empty() {
+            return Err(PyValueError::new_err("set_blob_dir() must be called before the first put"));
+        }
+        let dir = PathBuf::from(path);
+        std::fs::create_dir_all(&dir).map_err(|e| io_err(&e))?;
+        for entry in std::fs::read_dir(&dir).map_err(|e| io_err(&e))? {
+            let entry = entry.map_err(|e| io_err(&e))?;
+            let id = format!("{PREFIX}{}", entry.file_name().to_string_lossy());
+            if is_blob_id(&id) {
+                let size = entry.metadata().map_err(|e| io_err(&e))?.len();
+                inner.bytes += size;
+                inner.blobs.insert(id, Blob { size, refs: 0, put_by: None });
+            }
+        }
+        if let (true, Some(old)) = (inner.owned, inner.dir.take()) {
+            let _ = std::fs::remove_dir_all(old);
+        }
+        inner.dir = Some(dir);
+        inner.owned = false;
+        Ok(())
+    }
+
+    pub fn put(&self, data: &[u8], tx: Option<u64>) -> PyResult<String> {
+        let id = format!("{PREFIX}{}", crate::cap_tokens::hex(&Sha256::digest(data)));
+        let mut inner = self.inner.relock();
+        if let Some(blob) = inner.blobs.get_mut(&id) {
+            blob.put_by = tx.or(blob.put_by);
+            return Ok(id);
+        }
+        let file = inner.file(&id)?;
+        // Write-then-rename so a crash never leaves a truncated file under a valid id.
+        let partial = file.with_extension("part");
+        std::fs::write(&partial, data).map_err(|e| io_err(&e))?;
+        std::fs::rename(&partial, &file).map_err(|e| io_err(&e))?;
+        inner.bytes += data.len() as u64;
+        inner.blobs.insert(id.clone(), Blob { size: data.len() as u64, refs: 0, put_by: tx });
+        Ok(id)
+    }
+
+    pub fn get(&self, id: &str) -> PyResult<Vec<u8>> {
+        let mut inner = self.inner.relock();
+        if !inner.blobs.contains_key(id) {
+            return Err(PyKeyError::new_err(format!("Unknown blob '{id}'")));
+        }
+        let file = inner.file(id)?;
+        std::fs::read(file).map_err(|e| io_err(&e))
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.inner.relock().blobs.contains_key(id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.relock().blobs.is_empty()
+    }
+
+    /// Recounts references over `states` and deletes unreferenced blobs not put by an `open`
+    /// transaction. Returns the number of blobs deleted.
+    pub fn gc(&self, py: Python, states: &[Py<State>], open: &HashSet<u64>) -> PyResult<usize> {
+        // NOTE: The scan runs Python-level traversal, so it runs before taking the lock.
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for state in states {
+            let mut seen = HashSet::new();
+            let state = state.borrow(py);
+            for value in state.data.values().chain(state.heavy.values()) {
+                collect_ids(value.bind(py), &mut seen)?;
+            }
+            for id in seen {
+                *counts.entry(id).or_default() += 1;
+            }
+        }
+        let mut inner = self.inner.relock();
+        let mut doomed = Vec::new();
+        for (id, blob) in &mut inner.blobs {
+            blob.refs = counts.get(id).copied().unwrap_or(0);
+            if blob.refs == 0 && !blob.put_by.is_some_and(|tx| open.contains(&tx)) {
+                doomed.push(id.clone());
+            }
+        }
+        for id in &doomed {
+            let file = inner.file(id)?;
+            match std::fs::remove_file(file) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(io_err(&e)),
+            }
+            let blob = inner.blobs.remove(id).expect("collected above");
+            inner.bytes -= blob.size;
+            inner.reclaimed += 1;
+        }
+        Ok(doomed.len())
+    }
+
+    /// [v3.6] fsck "registry" check of the blob registry against its directory: entries whose
+    /// file is missing (dropped on `repair`), files nobody registered (adopted on `repair`, so
+    /// the next gc reclaims them if unreferenced) and byte accounting (recomputed on `repair`).
+    pub fn fsck(&self, repair: bool) -> PyResult<Vec<crate::fsck::Issue>> {
+        use crate::fsck::Issue;
+        let mut issues = Vec::new();
+        let mut inner = self.inner.relock();
+        let Some(dir) = inner.dir.clone() else { return Ok(issues) };
+        let mut on_disk = HashMap::new();
+        for entry in std::fs::read_dir(&dir).map_err(|e| io_err(&e))? {
+            let entry = entry.map_err(|e| io_err(&e))?;
+            let id = format!("{PREFIX}{}", entry.file_name().to_string_lossy());
+            if is_blob_id(&id) {
+                on_disk.insert(id, entry.metadata().map_err(|e| io_err(&e))?.len());
+            }
+        }
+        let mut missing: Vec<String> = inner.blobs.keys().filter(|id| !on_disk.contains_key(*id)).cloned().collect();
+        missing.sort();
+        for id in missing {
+            issues.push(Issue::error("registry", format!("blob {id} is registered but its file is missing")).repairable(repair));
+            if repair {
+                let blob = inner.blobs.remove(&id).expect("collected above");
+                inner.bytes = inner.bytes.saturating_sub(blob.size);
+            }
+        }
+        let mut stray: Vec<(String, u64)> = on_disk.into_iter().filter(|(id, _)| !inner.blobs.contains_key(id)).collect();
+        stray.sort();
+        for (id, size) in stray {
+            issues.push(Issue::warning("registry", format!("blob file {id} is not registered")).repairable(repair));
+            if repair {
+                inner.bytes += size;
+                inner.blobs.insert(id, Blob { size, refs: 0, put_by: None });
+            }
+        }
+        let bytes: u64 = inner.blobs.values().map(|b| b.size).sum();
+        if bytes != inner.bytes {
+            issues.push(Issue::error("registry", format!("blob store accounts {} bytes, registry holds {bytes}", inner.bytes)).repairable(repair));
+            if repair {
+                inner.bytes = bytes;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// `{dir, blobs, bytes, referenced, reclaimed}`; `referenced` is as of the last gc.
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let inner = self.inner.relock();
+        let d = PyDict::new_bound(py);
+        d.set_item("dir", inner.dir.as_ref().map(|p| p.to_string_lossy().to_string()))?;
+        d.set_item("blobs", inner.blobs.len())?;
+        d.set_item("bytes", inner.bytes)?;
+        d.set_item("referenced", inner.blobs.values().filter(|b| b.refs > 0).count())?;
+        d.set_item("reclaimed", inner.reclaimed)?;
+        Ok(d.into_any().unbind())
+    }
+}
+
+fn collect_ids(value: &Bound<'_, PyAny>, out: &mut HashSet<String>) -> PyResult<()> {
+    if let Ok(s) = value.downcast::<PyString>() {
+        let s = s.to_str()?;
+        if is_blob_id(s) {
+            out.insert(s.to_string());
+        }
+    } else if let Ok(d) = value.downcast::<PyDict>() {
+        for (_, v) in d.iter() {
+            collect_ids(&v, out)?;
+        }
+    } else if let Ok(l) = value.downcast::<PyList>() {
+        for v in l.iter() {
+            collect_ids(&v, out)?;
+        }
+    } else if let Ok(t) = value.downcast::<PyTuple>() {
+        for v in t.iter() {
+            collect_ids(&v, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle on the engine's blob store (`ctx.blobs`, `tx.blobs`, `engine.blobs`).
+#[pyclass(module = "theus_core", frozen)]
+pub struct BlobHandle {
+    store: Arc<BlobStore>,
+    tx: Option<u64>,
+}
+
+impl BlobHandle {
+    pub fn new(store: Arc<BlobStore>, tx: Option<u64>) -> Self {
+        BlobHandle { store, tx }
+    }
+}
+
+#[pymethods]
+impl BlobHandle {
+    /// Store `data` and return its id (`"sha256:<hex>"`); equal payloads share one blob.
+    fn put(&self, data: &[u8]) -> PyResult<String> {
+        self.store.put(data, self.tx)
+    }
+
+    /// The bytes of blob `id`. Raises `KeyError` for an unknown (or collected) id.
+    fn get<'py>(&self, py: Python<'py>, id: &str) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.store.get(id)?))
+    }
+
+    fn __contains__(&self, id: &str) -> bool {
+        self.store.contains(id)
+    }
+
+    fn __repr__(&self) -> String {
+        match self.tx {
+            Some(tx) => format!("BlobHandle(tx={tx})"),
+            None => "BlobHandle()".to_string(),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::structures::OutboxMsg;
+use crate::zones::{CAP_APPEND, CAP_DELETE, CAP_READ, CAP_UPDATE};
+use crate::locks::Relock;
+
+// [v3.6] Break-glass access for production incidents.
+// `engine.break_glass(["internal_keys", "tenants.acme"], "INC-1234: rotate leaked key", 900)`
+// lifts the Private-zone and tenancy restrictions on those paths (and below) for `ttl_s`
+// seconds:
+// - Non-Constant paths (and everything below them except Constant fields) get READ | APPEND |
+//   UPDATE | DELETE in this engine's transactions: guards stop hiding Private fields there,
+//   proxies and `update_many` accept writes. The process contract still applies. Constant paths
+//   are refused.
+// - Tenant roots (or `tenants` itself) named by a grant accept writes from other scopes (see
+//   tenancy.rs); each such commit is audited as BREAK_GLASS_USED.
+// Grants and revocations are audited under BREAK_GLASS and announced on the outbox (topic
+// "theus.break_glass", priority 100) as `{event, id, paths, reason, actor, granted_at_ms,
+// expires_at_ms}`. A grant ends at expiry or with `revoke_break_glass(id)`.
+// NOTE: Grants belong to one engine and every check compares against the clock directly, so
+// access ends at expiry even mid-transaction. The "expired" notice is sent by the next sweep
+// (transaction open, `break_glass()` or `break_glass_grants()`).
+
+pub const TOPIC: &str = "theus.break_glass";
+const PRIORITY: i32 = 100;
+const CAPS: u8 = CAP_READ | CAP_APPEND | CAP_UPDATE | CAP_DELETE;
+
+/// Grants open on any engine: lets the access paths skip the lookup when there are none.
+static OPEN: AtomicUsize = AtomicUsize::new(0);
+/// Bumped whenever any engine opens or ends a grant (guard decision cache invalidation).
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub fn any_open() -> bool {
+    OPEN.load(Ordering::Relaxed) > 0
+}
+
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+struct Grant {
+    id: u64,
+    paths: Vec<String>,
+    reason: String,
+    actor: Option<String>,
+    granted_at_ms: u64,
+    expires_at_ms: u64,
+}
+
+#[derive(Default)]
+struct Registry {
+    grants: Vec<Arc<Grant>>,
+    next_id: u64,
+}
+
+/// `path` is `granted` or below it.
+fn under(granted: &str, path: &str) -> bool {
+    path.strip_prefix(granted).is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+impl Grant {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let out = PyDict::new_bound(py);
+        out.set_item("id", self.id)?;
+        out.set_item("paths", self.paths.clone())?;
+        out.set_item("reason", &self.reason)?;
+        out.set_item("actor", self.actor.as_deref())?;
+        out.set_item("granted_at_ms", self.granted_at_ms)?;
+        out.set_item("expires_at_ms", self.expires_at_ms)?;
+        Ok(out)
+    }
+
+    /// Audit line + outbox notification for `event` ("granted" / "expired" / "revoked").
+    fn announce(&self, py: Python, event: &str) -> PyResult<OutboxMsg> {
+        let who = self.actor.as_deref().unwrap_or("<unknown>");
+        crate::audit::log_global("BREAK_GLASS", &format!(
+            "!!! BREAK-GLASS #{} {} (actor {who}): paths={:?} reason='{}' expires_at_ms={}",
+            self.id, event.to_uppercase(), self.paths, self.reason, self.expires_at_ms
+        ));
+        let payload = self.to_dict(py)?;
+        payload.set_item("event", event)?;
+        Ok(OutboxMsg {
+            topic: TOPIC.to_string(),
+            payload: Arc::new(payload.into_any().unbind()),
+            schema: None,
+            content_type: None,
+            idempotency_key: format!("break_glass:{}:{event}", self.id),
+            priority: PRIORITY,
+            ordering_key: Some(format!("break_glass:{}", self.id)),
+            attempts: 0,
+            created_at_ms: crate::outbox::now_ms(),
+            tags: None,
+            generated_key: false,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct BreakGlass {
+    inner: Mutex<Registry>,
+}
+
+impl BreakGlass {
+    /// Opens a grant; returns its id and the notification to enqueue.
+    pub fn grant(&self, py: Python, paths: Vec<String>, reason: &str, ttl_s: f64, actor: Option<String>) -> PyResult<(u64, OutboxMsg)> {
+        if reason.trim().is_empty() {
+            return Err(PyValueError::new_err("break_glass() requires a reason"));
+        }
+        if paths.is_empty() || paths.iter().any(String::is_empty) {
+            return Err(PyValueError::new_err("break_glass() needs at least one non-empty path"));
+        }
+        if let Some(path) = paths.iter().find(|p| crate::zones::is_absolute_ceiling(&crate::zones::resolve_zone(p))) {
+            return Err(PyValueError::new_err(format!("break_glass() cannot lift the Constant zone ('{path}')")));
+        }
+        let expires_at_ms = crate::cap_tokens::expiry(ttl_s)?;
+        let mut registry = self.inner.relock();
+        registry.next_id += 1;
+        let grant = Arc::new(Grant {
+            id: registry.next_id,
+            paths,
+            reason: reason.to_string(),
+            actor,
+            granted_at_ms: crate::clock::now_ms(),
+            expires_at_ms,
+        });
+        registry.grants.push(grant.clone());
+        drop(registry);
+        OPEN.fetch_add(1, Ordering::Relaxed);
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+        Ok((grant.id, grant.announce(py, "granted")?))
+    }
+
+    /// Ends the grants matching `ended`.
+    fn end(&self, py: Python, event: &str, ended: impl Fn(&Grant) -> bool) -> PyResult<Vec<OutboxMsg>> {
+        let mut registry = self.inner.relock();
+        let (gone, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut registry.grants).into_iter().partition(|g| ended(g));
+        registry.grants = kept;
+        drop(registry);
+        if !gone.is_empty() {
+            OPEN.fetch_sub(gone.len(), Ordering::Relaxed);
+            GENERATION.fetch_add(1, Ordering::SeqCst);
+        }
+        gone.iter().map(|g| g.announce(py, event)).collect()
+    }
+
+    /// Revokes grant `id` early; no notification if it was not active.
+    pub fn revoke(&self, py: Python, id: u64) -> PyResult<Vec<OutboxMsg>> {
+        self.end(py, "revoked", |g| g.id == id)
+    }
+
+    /// Ends the grants whose expiry has passed.
+    pub fn sweep(&self, py: Python) -> PyResult<Vec<OutboxMsg>> {
+        let now = crate::clock::now_ms();
+        if !self.inner.relock().grants.iter().any(|g| g.expires_at_ms <= now) {
+            return Ok(Vec::new());
+        }
+        self.end(py, "expired", |g| g.expires_at_ms <= now)
+    }
+
+    /// Id of an unexpired grant naming `path` or one of its ancestors.
+    pub fn covering(&self, path: &str) -> Option<u64> {
+        let now = crate::clock::now_ms();
+        self.inner.relock().grants.iter()
+            .find(|g| g.expires_at_ms > now && g.paths.iter().any(|p| under(p, path)))
+            .map(|g| g.id)
+    }
+
+    /// Caps an unexpired grant gives `path` (never for Constant fields).
+    pub fn caps(&self, path: &str) -> Option<u8> {
+        if crate::zones::is_absolute_ceiling(&crate::zones::resolve_zone(path)) {
+            return None;
+        }
+        self.covering(path).map(|_| CAPS)
+    }
+
+    /// Active grants as dicts, oldest first.
+    pub fn describe(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyList::empty_bound(py);
+        for grant in &self.inner.relock().grants {
+            out.append(grant.to_dict(py)?)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+}
+
+impl Drop for BreakGlass {
+    fn drop(&mut self) {
+        let open = self.inner.get_mut().map_or(0, |r| r.grants.len());
+        if open > 0 {
+            OPEN.fetch_sub(open, Ordering::Relaxed);
+            GENERATION.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
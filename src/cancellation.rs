@@ -0,0 +1,134 @@
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] Cooperative cancellation. Every process run gets a CancellationToken reachable as
+// `ctx.token`; processes poll `ctx.cancelled` or call `ctx.raise_if_cancelled()` between
+// steps. Inside a transaction the token shares the transaction's cancel flag, so a cancelled
+// run can no longer write or commit (and the heartbeat abort policy cancels it too).
+// `engine.cancel(process | token)` flips the flag and, while the run is being awaited, also
+// cancels the asyncio task awaiting it so that code receives a standard `asyncio.CancelledError`.
+// NOTE: Sync processes run on a worker thread (`asyncio.to_thread`); cancelling their task
+// stops the caller from waiting but the thread only stops when it checks the token.
+
+pyo3::create_exception!(theus_core, ProcessCancelledError, crate::engine::TransactionCancelledError);
+
+#[pyclass(module = "theus_core", frozen)]
+pub struct CancellationToken {
+    flag: Arc<AtomicBool>,
+    process: String,
+    reason: Mutex<Option<String>>,
+    task: Mutex<Option<PyObject>>,
+}
+
+impl CancellationToken {
+    pub fn new(process: &str, flag: Arc<AtomicBool>) -> Self {
+        CancellationToken {
+            flag,
+            process: process.to_string(),
+            reason: Mutex::new(None),
+            task: Mutex::new(None),
+        }
+    }
+
+    pub fn attach_task(&self, task: Option<PyObject>) {
+        *self.task.relock() = task;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+    }
+
+    pub fn check(&self) -> PyResult<()> {
+        if !self.is_cancelled() {
+            return Ok(());
+        }
+        let reason = self.reason.relock().clone().unwrap_or_else(|| "cancelled".to_string());
+        Err(ProcessCancelledError::new_err(format!("Process '{}' cancelled: {reason}", self.process)))
+    }
+}
+
+#[pymethods]
+impl CancellationToken {
+    /// A standalone token (not tied to a run), e.g. for tests or manual plumbing.
+    #[new]
+    #[pyo3(signature = (process=""))]
+    fn py_new(process: &str) -> Self {
+        CancellationToken::new(process, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[getter]
+    fn cancelled(&self) -> bool {
+        self.is_cancelled()
+    }
+
+    #[getter]
+    fn process(&self) -> String {
+        self.process.clone()
+    }
+
+    #[getter]
+    fn reason(&self) -> Option<String> {
+        self.reason.relock().clone()
+    }
+
+    /// Requests cancellation; returns False if the token was already cancelled.
+    /// The attached asyncio task (if any) is cancelled on its own loop.
+    #[pyo3(signature = (reason=None))]
+    pub fn cancel(&self, py: Python, reason: Option<String>) -> PyResult<bool> {
+        if self.flag.swap(true, Ordering::SeqCst) {
+            return Ok(false);
+        }
+        *self.reason.relock() = reason;
+        if let Some(task) = self.task.relock().as_ref() {
+            let task = task.bind(py);
+            if !task.call_method0("done")?.is_truthy()? {
+                let cancel = task.getattr("cancel")?;
+                task.call_method0("get_loop")?.call_method1("call_soon_threadsafe", (cancel,))?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn raise_if_cancelled(&self) -> PyResult<()> {
+        self.check()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CancellationToken(process={:?}, cancelled={})", self.process, self.is_cancelled())
+    }
+}
+
+/// Tokens of the runs currently in flight, keyed by run id.
+#[derive(Default)]
+pub struct Tokens {
+    live: Mutex<BTreeMap<u64, Py<CancellationToken>>>,
+    next: AtomicU64,
+}
+
+impl Tokens {
+    pub fn register(&self, token: Py<CancellationToken>) -> u64 {
+        let id = self.next.fetch_add(1, Ordering::Relaxed);
+        self.live.relock().insert(id, token);
+        id
+    }
+
+    pub fn release(&self, id: u64) {
+        self.live.relock().remove(&id);
+    }
+
+    /// Cancels every live run of `process`; returns how many were newly cancelled.
+    pub fn cancel_process(&self, py: Python, process: &str, reason: Option<&str>) -> PyResult<usize> {
+        let matching: Vec<Py<CancellationToken>> = self.live.relock().values()
+            .filter(|t| t.get().process == process)
+            .map(|t| t.clone_ref(py))
+            .collect();
+        let mut n = 0;
+        for token in matching {
+            n += usize::from(token.get().cancel(py, reason.map(str::to_string))?);
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,151 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyPermissionError, PyValueError};
+use pyo3::types::{PyBytes, PyDict};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Mutex;
+use crate::guards::ContextGuard;
+use crate::locks::Relock;
+
+// [v3.6] Signed capability tokens for cross-process delegation. A parent holding a guard
+// issues `tcap1.<payload>.<mac>` (HMAC-SHA256 over the input/output path sets, the capability
+// ceiling and the expiry, keyed by the engine). A worker given the token and the key rebuilds
+// a ContextGuard with exactly those rights (`theus_core.guard_from_token`).
+// NOTE: Admin rights are never delegated, and expiry uses the wall clock (`clock::now_ms`).
+
+const PREFIX: &str = "tcap1";
+const MIN_KEY_LEN: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Rights carried by a token.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Grant {
+    #[serde(rename = "in")]
+    pub inputs: Vec<String>,
+    #[serde(rename = "out")]
+    pub outputs: Vec<String>,
+    pub caps: u8,
+    pub strict: bool,
+    /// Strict-mode private names the delegating guard could read.
+    pub private: Vec<String>,
+    #[serde(rename = "exp")]
+    pub expires_at_ms: u64,
+}
+
+/// Engine-held signing key (random per engine until `set_capability_key`).
+pub struct TokenKey {
+    key: Mutex<Vec<u8>>,
+}
+
+impl Default for TokenKey {
+    fn default() -> Self {
+        let mut key = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        TokenKey { key: Mutex::new(key) }
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+pub(crate) fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+fn mac(key: &[u8], payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(PREFIX.as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+impl TokenKey {
+    pub fn get(&self) -> Vec<u8> {
+        self.key.relock().clone()
+    }
+
+    pub fn set(&self, key: Vec<u8>) -> PyResult<()> {
+        if key.len() < MIN_KEY_LEN {
+            return Err(PyValueError::new_err(format!("Capability key must be at least {MIN_KEY_LEN} bytes")));
+        }
+        *self.key.relock() = key;
+        Ok(())
+    }
+
+    pub fn issue(&self, grant: &Grant) -> PyResult<String> {
+        sign(&self.get(), grant)
+    }
+}
+
+/// Wall-clock expiry `ttl_s` seconds from now.
+pub fn expiry(ttl_s: f64) -> PyResult<u64> {
+    if ttl_s.is_nan() || ttl_s <= 0.0 {
+        return Err(PyValueError::new_err("ttl_s must be positive"));
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(crate::clock::now_ms() + (ttl_s * 1000.0) as u64)
+}
+
+pub fn sign(key: &[u8], grant: &Grant) -> PyResult<String> {
+    let json = serde_json::to_vec(grant).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let payload = hex(&json);
+    let tag = mac(key, &payload).finalize().into_bytes();
+    Ok(format!("{PREFIX}.{payload}.{}", hex(&tag)))
+}
+
+/// The grant in `token` if its signature matches `key` and it has not expired.
+pub fn verify(key: &[u8], token: &str) -> PyResult<Grant> {
+    let invalid = || PyPermissionError::new_err("Invalid capability token");
+    let mut parts = token.split('.');
+    let (Some(PREFIX), Some(payload), Some(tag), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    let tag = unhex(tag).ok_or_else(invalid)?;
+    mac(key, payload).verify_slice(&tag).map_err(|_| invalid())?;
+    let grant: Grant = unhex(payload)
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .ok_or_else(invalid)?;
+    if crate::clock::now_ms() >= grant.expires_at_ms {
+        return Err(PyPermissionError::new_err("Capability token expired"));
+    }
+    Ok(grant)
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn grant_to_dict(py: Python, grant: &Grant) -> PyResult<PyObject> {
+    let d = PyDict::new_bound(py);
+    d.set_item("inputs", &grant.inputs)?;
+    d.set_item("outputs", &grant.outputs)?;
+    d.set_item("caps", grant.caps)?;
+    d.set_item("strict_guards", grant.strict)?;
+    d.set_item("private_allowlist", &grant.private)?;
+    d.set_item("expires_at", grant.expires_at_ms as f64 / 1000.0)?;
+    Ok(d.into_any().unbind())
+}
+
+/// `{inputs, outputs, caps, strict_guards, private_allowlist, expires_at}` of a valid token;
+/// `PermissionError` otherwise.
+#[pyfunction]
+pub fn verify_capability_token(py: Python, token: &str, key: &Bound<'_, PyBytes>) -> PyResult<PyObject> {
+    grant_to_dict(py, &verify(key.as_bytes(), token)?)
+}
+
+/// A `ContextGuard` over `target` holding exactly the rights delegated by `token`.
+#[pyfunction]
+#[pyo3(signature = (target, token, key, tx=None, path_prefix=None))]
+pub fn guard_from_token(target: PyObject, token: &str, key: &Bound<'_, PyBytes>, tx: Option<Py<crate::engine::Transaction>>, path_prefix: Option<String>) -> PyResult<ContextGuard> {
+    let grant = verify(key.as_bytes(), token)?;
+    ContextGuard::delegated(target, grant, path_prefix.unwrap_or_default(), tx)
+}
@@ -0,0 +1,158 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::indexes::{diff, Pattern};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Change data capture for downstream consumers.
+// `engine.subscribe_changes(sink, ["domain.orders.*"], fields=["status", "customer.id"])`
+// calls `sink(events)` once per commit with the matching changes, each
+// `{path, op, version, actor, value[, old_value]}` - op "insert" / "update" / "delete",
+// value None on delete. Filtering and projection happen in Rust before Python sees anything:
+// patterns select paths the same way secondary indexes do (names and `*`; see indexes.rs) and
+// only differing values produce events; `fields` keeps just those (dotted) keys of dict values;
+// `ops` narrows the kinds. Private-zone patterns are rejected.
+// NOTE: Sinks run after the commit, so their errors go to sys.unraisablehook.
+
+const OPS: &[&str] = &["insert", "update", "delete"];
+
+struct Subscription {
+    id: u64,
+    sink: PyObject,
+    patterns: Vec<Pattern>,
+    fields: Option<Vec<String>>,
+    ops: Option<Vec<String>>,
+    include_old: bool,
+    events: AtomicU64,
+    errors: AtomicU64,
+}
+
+/// `value` reduced to the dotted `fields` it has (non-dict values are kept whole).
+fn project<'py>(py: Python<'py>, value: Bound<'py, PyAny>, fields: &[String]) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(dict) = value.downcast::<PyDict>() else { return Ok(value) };
+    let out = PyDict::new_bound(py);
+    for field in fields {
+        let mut source = dict.clone();
+        let mut target = out.clone();
+        let mut parts = field.split('.').peekable();
+        while let Some(part) = parts.next() {
+            let Some(v) = source.get_item(part)? else { break };
+            if parts.peek().is_none() {
+                target.set_item(part, v)?;
+                break;
+            }
+            let Ok(next) = v.downcast_into::<PyDict>() else { break };
+            let nested = if let Some(t) = target.get_item(part)?.and_then(|t| t.downcast_into::<PyDict>().ok()) { t } else {
+                let t = PyDict::new_bound(py);
+                target.set_item(part, &t)?;
+                t
+            };
+            source = next;
+            target = nested;
+        }
+    }
+    Ok(out.into_any())
+}
+
+impl Subscription {
+    fn events<'py>(&self, py: Python<'py>, old: &State, new: &State, changed: &[String]) -> PyResult<Bound<'py, PyList>> {
+        let out = PyList::empty_bound(py);
+        for pattern in &self.patterns {
+            for (path, before, after) in diff(py, pattern, Some(old), new, Some(changed))? {
+                let op = match (&before, &after) {
+                    (None, Some(_)) => "insert",
+                    (Some(_), None) => "delete",
+                    (Some(b), Some(a)) if !b.eq(a).unwrap_or(false) => "update",
+                    _ => continue,
+                };
+                if self.ops.as_ref().is_some_and(|ops| !ops.iter().any(|o| o == op)) {
+                    continue;
+                }
+                let shape = |v: Option<Bound<'py, PyAny>>| -> PyResult<PyObject> {
+                    match (v, &self.fields) {
+                        (Some(v), Some(fields)) => Ok(project(py, v, fields)?.unbind()),
+                        (Some(v), None) => Ok(v.unbind()),
+                        (None, _) => Ok(py.None()),
+                    }
+                };
+                let root: String = path.split(['.', '[']).take(2).collect::<Vec<_>>().join(".");
+                let event = PyDict::new_bound(py);
+                event.set_item("path", &path)?;
+                event.set_item("op", op)?;
+                event.set_item("version", new.version)?;
+                event.set_item("actor", new.key_last_writer.get(root.as_str()).and_then(|w| w.actor.as_deref()))?;
+                event.set_item("value", shape(after)?)?;
+                if self.include_old {
+                    event.set_item("old_value", shape(before)?)?;
+                }
+                out.append(event)?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Default)]
+pub struct ChangeFeed {
+    next_id: AtomicU64,
+    subscriptions: Mutex<Vec<Arc<Subscription>>>,
+}
+
+impl ChangeFeed {
+    pub fn subscribe(&self, sink: PyObject, paths: &[String], fields: Option<Vec<String>>, ops: Option<Vec<String>>, include_old: bool) -> PyResult<u64> {
+        if paths.is_empty() {
+            return Err(PyValueError::new_err("subscribe_changes() needs at least one path pattern"));
+        }
+        let patterns = paths.iter().map(|p| Pattern::parse(p)).collect::<PyResult<Vec<_>>>()?;
+        if let Some(bad) = ops.iter().flatten().find(|op| !OPS.contains(&op.as_str())) {
+            return Err(PyValueError::new_err(format!("Unknown change op '{bad}' (expected one of {OPS:?})")));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let sub = Subscription { id, sink, patterns, fields, ops, include_old, events: AtomicU64::new(0), errors: AtomicU64::new(0) };
+        self.subscriptions.relock().push(Arc::new(sub));
+        Ok(id)
+    }
+
+    pub fn unsubscribe(&self, id: u64) -> bool {
+        let mut subs = self.subscriptions.relock();
+        let before = subs.len();
+        subs.retain(|s| s.id != id);
+        subs.len() != before
+    }
+
+    /// Delivers the changes of one commit to every subscription with matching events.
+    pub fn committed(&self, py: Python, old: &State, new: &State, changed: &[String]) -> PyResult<()> {
+        let subs: Vec<Arc<Subscription>> = self.subscriptions.relock().clone();
+        for sub in subs {
+            let events = sub.events(py, old, new, changed)?;
+            if events.is_empty() {
+                continue;
+            }
+            sub.events.fetch_add(events.len() as u64, Ordering::Relaxed);
+            if let Err(e) = sub.sink.call1(py, (events,)) {
+                sub.errors.fetch_add(1, Ordering::Relaxed);
+                e.write_unraisable_bound(py, Some(sub.sink.bind(py)));
+            }
+        }
+        Ok(())
+    }
+
+    /// `{id: {paths, fields, ops, include_old, events, errors}}`.
+    pub fn describe(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        for sub in self.subscriptions.relock().iter() {
+            let row = PyDict::new_bound(py);
+            row.set_item("paths", sub.patterns.iter().map(|p| p.text.clone()).collect::<Vec<_>>())?;
+            row.set_item("fields", sub.fields.clone())?;
+            row.set_item("ops", sub.ops.clone())?;
+            row.set_item("include_old", sub.include_old)?;
+            row.set_item("events", sub.events.load(Ordering::Relaxed))?;
+            row.set_item("errors", sub.errors.load(Ordering::Relaxed))?;
+            out.set_item(sub.id, row)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+}
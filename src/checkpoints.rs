@@ -0,0 +1,35 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+// [v3.6] Partial commit policy. A transaction opened with `partial_commit=True` may call
+// `tx.checkpoint()` (or `ctx.checkpoint()` inside a process); if the block later raises, the
+// writes made up to the last checkpoint are committed anyway and everything after it is
+// discarded. The exception still propagates. Checkpoints and the partial commit are audited
+// (TX_CHECKPOINT / TX_PARTIAL_COMMIT) and the outcome is `tx.commit_status == "partial"`.
+// NOTE: A checkpoint deep-copies what a commit would write at that point (logged deltas,
+// shadow mutations, `update()` payloads), so later in-place mutations cannot leak into it.
+// Heavy values are kept by reference. A failing partial commit is recorded in the rollback
+// report (`checkpoint.error`), never raised over the original exception.
+
+/// What the transaction would have committed at `tx.checkpoint()`.
+pub struct Checkpoint {
+    /// 1-based, counts the checkpoints taken by the transaction.
+    pub index: u32,
+    pub deltas: Vec<crate::delta::DeltaEntry>,
+    pub pending_data: Py<PyDict>,
+    pub pending_heavy: Py<PyDict>,
+    /// Outbox messages / signals / staged events queued before the checkpoint.
+    pub outbox: usize,
+    pub signals: usize,
+    pub events: usize,
+}
+
+/// `entry` with its value deep-copied (the shadow it was inferred from keeps changing).
+pub fn freeze(py: Python, deepcopy: &Bound<'_, PyAny>, entry: &crate::delta::DeltaEntry) -> PyResult<crate::delta::DeltaEntry> {
+    let mut frozen = entry.clone();
+    frozen.value = match &entry.value {
+        Some(v) => Some(deepcopy.call1((v.bind(py),))?.unbind()),
+        None => None,
+    };
+    Ok(frozen)
+}
@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Instant;
+use pyo3::prelude::*;
+use crate::structures::ContextError;
+
+// [v3.6] Single source of time for the engine: transaction timeouts / watchdog / leak ages
+// (monotonic), idempotency-window TTLs, outbox and audit / meta log timestamps (wall).
+// Swappable at runtime: system (default), logical (reproducible runs) or a manual test clock.
+// The clock is process-wide - every engine, audit log and token shares it - so the Python
+// switches are module functions named for that: `use_global_test_clock()`,
+// `advance_global_clock_ms()`, `use_global_logical_clock()`, `use_global_system_clock()` and
+// `global_clock()`.
+// NOTE: Real-time waits (shutdown drain, rule sandbox budget) and latency metrics stay on
+// `Instant` - advancing a fake clock must not make them spin or report bogus latencies.
+
+pub trait Clock: Send + Sync {
+    /// Wall-clock milliseconds since the Unix epoch.
+    fn now_ms(&self) -> u64;
+    /// Milliseconds on a clock that never goes backwards (for durations).
+    fn monotonic_ms(&self) -> u64;
+    fn kind(&self) -> &'static str;
+}
+
+static PROCESS_START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    #[allow(clippy::cast_possible_truncation)]
+    fn now_ms(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn monotonic_ms(&self) -> u64 {
+        PROCESS_START.elapsed().as_millis() as u64
+    }
+
+    fn kind(&self) -> &'static str {
+        "system"
+    }
+}
+
+/// 2023-11-14T22:13:20Z - arbitrary but stable origin for logical time.
+const LOGICAL_EPOCH_MS: u64 = 1_700_000_000_000;
+
+/// Reproducible runs: a fixed epoch advancing 1ms per wall-clock reading, so timestamps
+/// depend only on the order of events. Durations do not advance on their own.
+struct LogicalClock {
+    ticks: AtomicU64,
+}
+
+impl Clock for LogicalClock {
+    fn now_ms(&self) -> u64 {
+        LOGICAL_EPOCH_MS + self.ticks.fetch_add(1, Ordering::SeqCst)
+    }
+
+    fn monotonic_ms(&self) -> u64 {
+        self.ticks.load(Ordering::SeqCst)
+    }
+
+    fn kind(&self) -> &'static str {
+        "logical"
+    }
+}
+
+/// Manual clock for tests: time only moves on `advance_ms`.
+pub struct TestClock {
+    start_ms: u64,
+    offset_ms: AtomicU64,
+}
+
+impl TestClock {
+    pub fn advance(&self, ms: u64) -> u64 {
+        self.start_ms + self.offset_ms.fetch_add(ms, Ordering::SeqCst) + ms
+    }
+}
+
+impl Clock for TestClock {
+    fn now_ms(&self) -> u64 {
+        self.start_ms + self.offset_ms.load(Ordering::SeqCst)
+    }
+
+    fn monotonic_ms(&self) -> u64 {
+        self.offset_ms.load(Ordering::SeqCst)
+    }
+
+    fn kind(&self) -> &'static str {
+        "test"
+    }
+}
+
+enum Active {
+    Dyn(Arc<dyn Clock>),
+    Test(Arc<TestClock>),
+}
+
+impl Active {
+    fn clock(&self) -> &dyn Clock {
+        match self {
+            Active::Dyn(c) => c.as_ref(),
+            Active::Test(c) => c.as_ref(),
+        }
+    }
+}
+
+static CLOCK: LazyLock<RwLock<Active>> = LazyLock::new(|| RwLock::new(Active::Dyn(Arc::new(SystemClock))));
+
+fn set(active: Active) {
+    *CLOCK.write().unwrap_or_else(std::sync::PoisonError::into_inner) = active;
+}
+
+fn with<T>(f: impl FnOnce(&Active) -> T) -> T {
+    f(&CLOCK.read().unwrap_or_else(std::sync::PoisonError::into_inner))
+}
+
+pub fn use_system() {
+    set(Active::Dyn(Arc::new(SystemClock)));
+}
+
+/// Switch to the logical clock, restarting it from its epoch.
+pub fn use_logical() {
+    set(Active::Dyn(Arc::new(LogicalClock { ticks: AtomicU64::new(0) })));
+}
+
+/// Install a manual test clock starting at `start_ms` (default: current wall time).
+pub fn use_test(start_ms: Option<u64>) {
+    let start_ms = start_ms.unwrap_or_else(|| SystemClock.now_ms());
+    set(Active::Test(Arc::new(TestClock { start_ms, offset_ms: AtomicU64::new(0) })));
+}
+
+/// Advance the test clock; `None` if the active clock is not a test clock.
+pub fn advance(ms: u64) -> Option<u64> {
+    with(|a| match a {
+        Active::Test(c) => Some(c.advance(ms)),
+        Active::Dyn(_) => None,
+    })
+}
+
+pub fn kind() -> &'static str {
+    with(|a| a.clock().kind())
+}
+
+/// Wall-clock milliseconds since the Unix epoch.
+pub fn now_ms() -> u64 {
+    with(|a| a.clock().now_ms())
+}
+
+/// Monotonic milliseconds (origin unspecified; only differences are meaningful).
+pub fn monotonic_ms() -> u64 {
+    with(|a| a.clock().monotonic_ms())
+}
+
+/// Wall-clock seconds (float) for audit / meta log entries.
+#[allow(clippy::cast_precision_loss)]
+pub fn now_secs() -> f64 {
+    now_ms() as f64 / 1000.0
+}
+
+/// [v3.6] Replace the process-wide clock with a manual one starting at `start_ms` (default:
+/// now); only `advance_global_clock_ms` moves it. Affects every engine in the process:
+/// transaction timeouts, watchdog/leak ages, idempotency TTLs, outbox and audit timestamps.
+#[pyfunction]
+#[pyo3(signature = (start_ms=None))]
+pub fn use_global_test_clock(start_ms: Option<u64>) {
+    use_test(start_ms);
+}
+
+/// [v3.6] Advance the process-wide test clock; returns the new wall time in ms.
+#[pyfunction]
+pub fn advance_global_clock_ms(ms: u64) -> PyResult<u64> {
+    advance(ms).ok_or_else(|| ContextError::new_err(format!(
+        "advance_global_clock_ms() requires the test clock (active clock: {})", kind()
+    )))
+}
+
+/// [v3.6] Switch the process-wide clock to logical time: a fixed epoch advancing 1ms per
+/// wall-clock reading, so timestamps depend only on the order of events (golden tests,
+/// together with `engine.set_deterministic(seed)`).
+#[pyfunction]
+pub fn use_global_logical_clock() {
+    use_logical();
+}
+
+/// [v3.6] Restore the system clock for the whole process.
+#[pyfunction]
+pub fn use_global_system_clock() {
+    use_system();
+}
+
+/// [v3.6] `(kind, wall time in ms)` of the process-wide clock.
+#[pyfunction]
+pub fn global_clock() -> (&'static str, u64) {
+    (kind(), now_ms())
+}
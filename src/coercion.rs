@@ -0,0 +1,138 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyFloat, PyList, PyLong, PyString, PyType};
+
+// [v3.6] Write-time normalization. `engine.add_coercion("domain.orders.*.total", "to_decimal")`
+// registers a middleware (see middleware.rs) that rewrites values written through proxies to
+// matching paths before they are delta-logged, so "int where Decimal is expected" or naive
+// datetimes are fixed instead of failing validation at commit. A rule is a built-in name or a
+// callable `rule(value) -> value`; a list of rules applies left to right.
+// Built-ins leave values they cannot convert unchanged, so validation still reports them:
+//   to_decimal     int / float / str -> decimal.Decimal (floats via str(), not binary expansion)
+//   to_utc         naive datetime -> UTC, aware datetime -> astimezone(UTC), ISO str -> datetime
+//   strip_strings  strips str values, recursing into dicts and lists
+// Coercions run at a low priority: before other middlewares on write (they see normalized
+// values) and before encrypt_fields() seals the result.
+
+pub const BUILTINS: &[&str] = &["to_decimal", "to_utc", "strip_strings"];
+
+static DECIMAL: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static DATETIME: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static UTC: GILOnceCell<PyObject> = GILOnceCell::new();
+
+enum Rule {
+    ToDecimal,
+    ToUtc,
+    StripStrings,
+    Custom(PyObject),
+}
+
+pub struct Coercion {
+    rules: Vec<Rule>,
+}
+
+impl Rule {
+    fn parse(rule: &Bound<'_, PyAny>) -> PyResult<Rule> {
+        if let Ok(name) = rule.extract::<String>() {
+            return match name.as_str() {
+                "to_decimal" => Ok(Rule::ToDecimal),
+                "to_utc" => Ok(Rule::ToUtc),
+                "strip_strings" => Ok(Rule::StripStrings),
+                _ => Err(PyValueError::new_err(format!("Unknown coercion '{name}' (expected one of {BUILTINS:?} or a callable)"))),
+            };
+        }
+        if rule.is_callable() {
+            return Ok(Rule::Custom(rule.clone().unbind()));
+        }
+        Err(PyTypeError::new_err("A coercion rule must be a built-in name or a callable"))
+    }
+
+    fn apply<'py>(&self, py: Python<'py>, value: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Rule::ToDecimal => to_decimal(py, value),
+            Rule::ToUtc => to_utc(py, value),
+            Rule::StripStrings => strip_strings(py, value),
+            Rule::Custom(f) => f.bind(py).call1((value,)),
+        }
+    }
+}
+
+fn to_decimal<'py>(py: Python<'py>, value: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let decimal = DECIMAL.import(py, "decimal", "Decimal")?;
+    // bool is an int subclass; True -> Decimal(1) would hide a real type error.
+    let numeric = (value.is_instance_of::<PyLong>() && !value.is_instance_of::<pyo3::types::PyBool>())
+        || value.is_instance_of::<PyFloat>();
+    if numeric {
+        return decimal.call1((value.str()?,));
+    }
+    if let Ok(s) = value.downcast::<PyString>() {
+        return Ok(decimal.call1((s.to_str()?.trim(),)).unwrap_or(value));
+    }
+    Ok(value)
+}
+
+fn to_utc<'py>(py: Python<'py>, value: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let datetime = DATETIME.import(py, "datetime", "datetime")?;
+    let utc = UTC.get_or_try_init(py, || -> PyResult<PyObject> {
+        Ok(py.import_bound("datetime")?.getattr("timezone")?.getattr("utc")?.unbind())
+    })?.bind(py);
+    let value = match value.downcast::<PyString>() {
+        Ok(s) => match datetime.call_method1("fromisoformat", (s.to_str()?.trim(),)) {
+            Ok(parsed) => parsed,
+            Err(_) => return Ok(value),
+        },
+        Err(_) => value,
+    };
+    if !value.is_instance(datetime)? {
+        return Ok(value);
+    }
+    if value.getattr("tzinfo")?.is_none() {
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("tzinfo", utc)?;
+        return value.call_method("replace", (), Some(&kwargs));
+    }
+    value.call_method1("astimezone", (utc,))
+}
+
+fn strip_strings<'py>(py: Python<'py>, value: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if let Ok(s) = value.downcast::<PyString>() {
+        return Ok(PyString::new_bound(py, s.to_str()?.trim()).into_any());
+    }
+    if let Ok(d) = value.downcast::<PyDict>() {
+        let out = PyDict::new_bound(py);
+        for (k, v) in d.iter() {
+            out.set_item(k, strip_strings(py, v)?)?;
+        }
+        return Ok(out.into_any());
+    }
+    if let Ok(l) = value.downcast::<PyList>() {
+        let items = l.iter().map(|v| strip_strings(py, v)).collect::<PyResult<Vec<_>>>()?;
+        return Ok(PyList::new_bound(py, items).into_any());
+    }
+    Ok(value)
+}
+
+impl Coercion {
+    /// `rules`: a built-in name, a callable, or a list of those.
+    pub fn new(rules: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let rules = match rules.downcast::<PyList>() {
+            Ok(list) => list.iter().map(|r| Rule::parse(&r)).collect::<PyResult<Vec<_>>>()?,
+            Err(_) => vec![Rule::parse(rules)?],
+        };
+        if rules.is_empty() {
+            return Err(PyValueError::new_err("At least one coercion rule is required"));
+        }
+        Ok(Coercion { rules })
+    }
+}
+
+impl crate::middleware::Middleware for Coercion {
+    fn intercept(&self, py: Python, _path: &str, value: PyObject, _op: &str) -> PyResult<PyObject> {
+        let mut value = value.into_bound(py);
+        for rule in &self.rules {
+            value = rule.apply(py, value)?;
+        }
+        Ok(value.unbind())
+    }
+}
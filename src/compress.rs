@@ -0,0 +1,237 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+use std::sync::{Arc, Mutex};
+use crate::spill::{HotCache, is_placeholder, lookup, parse_prefix, replace_cow};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Compressed storage for cold Data paths (`engine.set_compression("domain.reports")`).
+// On commit, a newly written value under a compressed prefix is msgpack-encoded, compressed
+// (zstd or lz4) and kept in the State as a `CompressedValue`. Proxy/guard reads decompress it
+// lazily (through the same fault-in hooks as spilled values); recently decompressed payloads
+// stay in a small LRU so hot reads skip the codec.
+// NOTE: A path both compressed and spilled stays compressed in memory.
+
+/// Default size of the decompressed-payload LRU.
+pub const DEFAULT_CACHE_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Clone, Copy)]
+enum Codec {
+    Zstd(i32),
+    Lz4,
+}
+
+impl Codec {
+    fn parse(name: &str, level: Option<i32>) -> PyResult<Self> {
+        match name {
+            "zstd" => Ok(Codec::Zstd(level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL))),
+            "lz4" => Ok(Codec::Lz4),
+            other => Err(PyValueError::new_err(format!("Unknown codec '{other}' (expected 'zstd' or 'lz4')"))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Codec::Zstd(_) => "zstd",
+            Codec::Lz4 => "lz4",
+        }
+    }
+
+    fn compress(self, raw: &[u8]) -> PyResult<Vec<u8>> {
+        match self {
+            Codec::Zstd(level) => zstd::bulk::compress(raw, level)
+                .map_err(|e| PyValueError::new_err(format!("zstd compression failed: {e}"))),
+            Codec::Lz4 => Ok(lz4_flex::compress(raw)),
+        }
+    }
+
+    fn decompress(self, data: &[u8], raw_len: usize) -> PyResult<Vec<u8>> {
+        match self {
+            Codec::Zstd(_) => zstd::bulk::decompress(data, raw_len)
+                .map_err(|e| PyValueError::new_err(format!("zstd decompression failed: {e}"))),
+            Codec::Lz4 => lz4_flex::decompress(data, raw_len)
+                .map_err(|e| PyValueError::new_err(format!("lz4 decompression failed: {e}"))),
+        }
+    }
+}
+
+struct Rule {
+    prefix: String,
+    segments: Vec<String>,
+    codec: Codec,
+    values: u64,
+    raw_bytes: u64,
+    stored_bytes: u64,
+}
+
+struct Inner {
+    rules: Vec<Rule>,
+    cache: HotCache,
+    next_id: u64,
+}
+
+pub struct CompressionStore {
+    inner: Mutex<Inner>,
+}
+
+impl Default for CompressionStore {
+    fn default() -> Self {
+        CompressionStore { inner: Mutex::new(Inner { rules: Vec::new(), cache: HotCache::new(DEFAULT_CACHE_BYTES), next_id: 0 }) }
+    }
+}
+
+impl CompressionStore {
+    /// Sets (or with `codec=None` removes) the policy for `prefix`.
+    pub fn set_rule(&self, prefix: &str, codec: Option<&str>, level: Option<i32>) -> PyResult<()> {
+        let segments = parse_prefix("Compression", prefix)?;
+        let codec = codec.map(|c| Codec::parse(c, level)).transpose()?;
+        let mut inner = self.inner.relock();
+        inner.rules.retain(|r| r.prefix != prefix);
+        if let Some(codec) = codec {
+            inner.rules.push(Rule { prefix: prefix.to_string(), segments, codec, values: 0, raw_bytes: 0, stored_bytes: 0 });
+        }
+        Ok(())
+    }
+
+    pub fn set_cache_bytes(&self, cache_bytes: usize) {
+        self.inner.relock().cache.set_capacity(cache_bytes);
+    }
+
+    pub fn prefixes(&self) -> Vec<Vec<String>> {
+        self.inner.relock().rules.iter().map(|r| r.segments.clone()).collect()
+    }
+
+    /// Replaces newly written values at compressed prefixes in `state` with placeholders.
+    pub fn apply(self: &Arc<Self>, py: Python, state: &Bound<'_, PyAny>) -> PyResult<()> {
+        let rules: Vec<(Vec<String>, Codec)> = {
+            let inner = self.inner.relock();
+            if inner.rules.is_empty() {
+                return Ok(());
+            }
+            inner.rules.iter().map(|r| (r.segments.clone(), r.codec)).collect()
+        };
+        let mut state = state.downcast::<State>()?.borrow_mut();
+        for (idx, (segments, codec)) in rules.iter().enumerate() {
+            let Some(zone) = state.data.get(&segments[0]).map(|z| z.bind(py).clone()) else { continue };
+            let Some(value) = lookup(&zone, &segments[1..]) else { continue };
+            if is_placeholder(&value) {
+                continue;
+            }
+            let raw = crate::state_codec::encode_value(py, &value)?;
+            let data = codec.compress(&raw)?;
+            let id = {
+                let mut inner = self.inner.relock();
+                if let Some(rule) = inner.rules.get_mut(idx) {
+                    rule.values += 1;
+                    rule.raw_bytes += raw.len() as u64;
+                    rule.stored_bytes += data.len() as u64;
+                }
+                inner.next_id += 1;
+                inner.next_id
+            };
+            let placeholder = Py::new(py, CompressedValue { store: self.clone(), id, codec: *codec, data, raw_len: raw.len() })?;
+            let new_zone = replace_cow(&zone, &segments[1..], placeholder.bind(py).as_any())?;
+            state.data.insert(segments[0].clone(), Arc::new(new_zone.unbind()));
+            state.rehash(py, &segments[0])?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let inner = self.inner.relock();
+        let paths = PyDict::new_bound(py);
+        let (mut raw, mut stored) = (0u64, 0u64);
+        for rule in &inner.rules {
+            let d = PyDict::new_bound(py);
+            d.set_item("codec", rule.codec.name())?;
+            d.set_item("values", rule.values)?;
+            d.set_item("raw_bytes", rule.raw_bytes)?;
+            d.set_item("stored_bytes", rule.stored_bytes)?;
+            d.set_item("saved_bytes", i128::from(rule.raw_bytes) - i128::from(rule.stored_bytes))?;
+            paths.set_item(&rule.prefix, d)?;
+            raw += rule.raw_bytes;
+            stored += rule.stored_bytes;
+        }
+        let reads = inner.cache.hits + inner.cache.misses;
+        let d = PyDict::new_bound(py);
+        d.set_item("paths", paths)?;
+        d.set_item("raw_bytes", raw)?;
+        d.set_item("stored_bytes", stored)?;
+        d.set_item("saved_bytes", i128::from(raw) - i128::from(stored))?;
+        d.set_item("reads", reads)?;
+        d.set_item("hits", inner.cache.hits)?;
+        d.set_item("misses", inner.cache.misses)?;
+        d.set_item("hit_rate", if reads == 0 { 0.0 } else { inner.cache.hits as f64 / reads as f64 })?;
+        d.set_item("cached_bytes", inner.cache.bytes)?;
+        d.set_item("cache_bytes", inner.cache.capacity)?;
+        Ok(d.into_any().unbind())
+    }
+}
+
+/// A Data value held compressed in the State. Reads through proxies decompress it
+/// transparently; `load()` does so explicitly.
+#[pyclass(module = "theus_core")]
+pub struct CompressedValue {
+    store: Arc<CompressionStore>,
+    id: u64,
+    codec: Codec,
+    data: Vec<u8>,
+    raw_len: usize,
+}
+
+#[pymethods]
+impl CompressedValue {
+    /// Decode the value (a fresh object on every call).
+    pub fn load(&self, py: Python) -> PyResult<PyObject> {
+        let cached = self.store.inner.relock().cache.get(self.id);
+        let raw = if let Some(raw) = cached { raw } else {
+            let raw = Arc::new(self.codec.decompress(&self.data, self.raw_len)?);
+            self.store.inner.relock().cache.insert(self.id, raw.clone());
+            raw
+        };
+        Ok(crate::state_codec::decode_value(py, &raw)?.unbind())
+    }
+
+    #[getter]
+    fn codec(&self) -> &'static str {
+        self.codec.name()
+    }
+
+    /// Compressed size held in memory.
+    #[getter]
+    fn nbytes(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Encoded size before compression.
+    #[getter]
+    fn raw_nbytes(&self) -> usize {
+        self.raw_len
+    }
+
+    fn __eq__(&self, py: Python, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if let Ok(o) = other.downcast::<CompressedValue>() {
+            let o = o.borrow();
+            if Arc::ptr_eq(&self.store, &o.store) && self.id == o.id {
+                return Ok(true);
+            }
+            return self.load(py)?.bind(py).eq(o.load(py)?);
+        }
+        self.load(py)?.bind(py).eq(other)
+    }
+
+    /// Placeholders are immutable: copies share the compressed bytes.
+    fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __deepcopy__(slf: Py<Self>, _memo: PyObject) -> Py<Self> {
+        slf
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CompressedValue({}, {} -> {} bytes)", self.codec.name(), self.raw_len, self.data.len())
+    }
+}
@@ -0,0 +1,182 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError};
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::ThreadId;
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Read-through cache for expensive derived values.
+// `engine.define_computed("derived.totals", fn, deps=["domain.orders"])` registers `fn(state)`;
+// `engine.computed("derived.totals")` returns its value for the committed state. Entries are
+// keyed by (path, dependency version) - the newest `key_last_modified` of any dependency
+// (zone.field granularity, so "domain.orders.o1" depends on "domain.orders"; no deps = the
+// state version). Commits that do not touch a dependency keep the entry; touching one makes
+// the next read recompute. Single flight: callers arriving while a value is being computed for
+// the same version wait for it (without holding the GIL) instead of computing it again, and
+// share its result or error. Errors are not cached. `load_state` and friends drop everything.
+
+struct Flight {
+    owner: ThreadId,
+    done: Mutex<Option<Result<PyObject, PyErr>>>,
+    ready: Condvar,
+}
+
+enum Role {
+    Lead(Arc<Flight>),
+    Wait(Arc<Flight>),
+}
+
+enum Slot {
+    Empty,
+    Ready(u64, PyObject),
+    Computing(u64, Arc<Flight>),
+}
+
+#[derive(Default, Clone, Copy)]
+struct Stats {
+    hits: u64,
+    misses: u64,
+    waits: u64,
+    errors: u64,
+}
+
+struct Computed {
+    func: PyObject,
+    deps: Vec<String>,
+    slot: Mutex<Slot>,
+    stats: Mutex<Stats>,
+}
+
+impl Computed {
+    fn dep_version(&self, state: &State) -> u64 {
+        if self.deps.is_empty() {
+            return state.version;
+        }
+        // Every commit also bumps the bare zone key, so it only counts for whole-zone deps.
+        let related = |key: &str, dep: &str| {
+            key == dep
+                || (key.contains('.') && dep.strip_prefix(key).is_some_and(|r| r.starts_with('.')))
+                || key.strip_prefix(dep).is_some_and(|r| r.starts_with('.'))
+        };
+        state.key_last_modified.iter()
+            .filter(|(key, _)| self.deps.iter().any(|dep| related(key, dep)))
+            .map(|(_, v)| *v)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Default)]
+pub struct ComputedCache {
+    entries: Mutex<HashMap<String, Arc<Computed>>>,
+}
+
+impl ComputedCache {
+    pub fn define(&self, path: &str, func: PyObject, deps: Vec<String>) {
+        let entry = Computed { func, deps, slot: Mutex::new(Slot::Empty), stats: Mutex::default() };
+        self.entries.relock().insert(path.to_string(), Arc::new(entry));
+    }
+
+    pub fn remove(&self, path: &str) -> bool {
+        self.entries.relock().remove(path).is_some()
+    }
+
+    /// Drop the cached value of `path` (of every path if None); definitions and stats stay.
+    pub fn invalidate(&self, path: Option<&str>) {
+        for (name, entry) in self.entries.relock().iter() {
+            if path.is_none_or(|p| p == name) {
+                let mut slot = entry.slot.relock();
+                if matches!(*slot, Slot::Ready(..)) {
+                    *slot = Slot::Empty;
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, py: Python, path: &str, state: &Bound<'_, State>) -> PyResult<PyObject> {
+        let entry = self.entries.relock().get(path).cloned()
+            .ok_or_else(|| PyKeyError::new_err(format!("No computed value defined at '{path}'")))?;
+        let version = entry.dep_version(&state.borrow());
+        let role = {
+            let mut slot = entry.slot.relock();
+            match &*slot {
+                Slot::Ready(v, value) if *v == version => {
+                    entry.stats.relock().hits += 1;
+                    return Ok(value.clone_ref(py));
+                }
+                Slot::Computing(v, flight) if *v == version => {
+                    if flight.owner == std::thread::current().id() {
+                        return Err(PyRuntimeError::new_err(format!("Computed value '{path}' depends on itself")));
+                    }
+                    entry.stats.relock().waits += 1;
+                    Role::Wait(flight.clone())
+                }
+                _ => {
+                    let flight = Arc::new(Flight { owner: std::thread::current().id(), done: Mutex::new(None), ready: Condvar::new() });
+                    *slot = Slot::Computing(version, flight.clone());
+                    entry.stats.relock().misses += 1;
+                    Role::Lead(flight)
+                }
+            }
+        };
+        match role {
+            Role::Wait(flight) => {
+                let waiting = flight.clone();
+                py.allow_threads(move || {
+                    let mut done = waiting.done.relock();
+                    while done.is_none() {
+                        done = waiting.ready.wait(done).unwrap();
+                    }
+                });
+                match flight.done.relock().as_ref().expect("flight finished") {
+                    Ok(value) => Ok(value.clone_ref(py)),
+                    Err(e) => Err(e.clone_ref(py)),
+                }
+            }
+            Role::Lead(flight) => {
+                let result = entry.func.call1(py, (state,));
+                {
+                    let mut slot = entry.slot.relock();
+                    // A newer dependency version may have started its own flight meanwhile.
+                    if matches!(&*slot, Slot::Computing(_, f) if Arc::ptr_eq(f, &flight)) {
+                        *slot = match &result {
+                            Ok(value) => Slot::Ready(version, value.clone_ref(py)),
+                            Err(_) => Slot::Empty,
+                        };
+                    }
+                }
+                if result.is_err() {
+                    entry.stats.relock().errors += 1;
+                }
+                *flight.done.relock() = Some(match &result {
+                    Ok(value) => Ok(value.clone_ref(py)),
+                    Err(e) => Err(e.clone_ref(py)),
+                });
+                flight.ready.notify_all();
+                result
+            }
+        }
+    }
+
+    /// `{path: {hits, misses, waits, errors, cached_version}}`.
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        for (path, entry) in self.entries.relock().iter() {
+            let stats = *entry.stats.relock();
+            let cached = match &*entry.slot.relock() {
+                Slot::Ready(v, _) => Some(*v),
+                _ => None,
+            };
+            let row = PyDict::new_bound(py);
+            row.set_item("hits", stats.hits)?;
+            row.set_item("misses", stats.misses)?;
+            row.set_item("waits", stats.waits)?;
+            row.set_item("errors", stats.errors)?;
+            row.set_item("cached_version", cached)?;
+            out.set_item(path, row)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+}
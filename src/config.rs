@@ -3,7 +3,7 @@ use pyo3::create_exception;
 use serde::Deserialize;
 use std::collections::HashMap;
 
-create_exception!(theus.config, SchemaViolationError, pyo3::exceptions::PyException);
+create_exception!(theus.config, SchemaViolationError, crate::structures::ContextError);
 
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -1,7 +1,18 @@
 use pyo3::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use rand::Rng;
+use crate::locks::Relock;
+
+/// Why a `RetryDecision` was made (v3.6).
+#[pyclass(module = "theus_core", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RetryReason {
+    Backoff = 0,      // Regular exponential backoff
+    BlockedByVip = 1, // Another process holds the Priority Ticket
+    VipGranted = 2,   // Retries exhausted -> this caller was promoted to VIP
+    VipRetry = 3,     // Caller already holds the Priority Ticket
+    Exhausted = 4,    // Retries exhausted and VIP slot taken: give up
+}
 
 #[pyclass(module = "theus_core")]
 #[derive(Clone, Debug)]
@@ -10,15 +21,28 @@ pub struct RetryDecision {
     pub should_retry: bool,
     #[pyo3(get)]
     pub wait_ms: u64,
+    /// [v3.6] Jittered backoff scaled by current contention (other keys with open conflicts).
+    #[pyo3(get)]
+    pub suggested_backoff_ms: u64,
+    #[pyo3(get)]
+    pub attempts_remaining: u32,
+    #[pyo3(get)]
+    pub reason: RetryReason,
 }
 
 #[pymethods]
 impl RetryDecision {
     fn __repr__(&self) -> String {
-        format!("RetryDecision(retry={}, wait={}ms)", self.should_retry, self.wait_ms)
+        format!(
+            "RetryDecision(retry={}, wait={}ms, suggested={}ms, remaining={}, reason={:?})",
+            self.should_retry, self.wait_ms, self.suggested_backoff_ms, self.attempts_remaining, self.reason
+        )
     }
 }
 
+/// Upper bound for `suggested_backoff_ms`.
+const MAX_SUGGESTED_BACKOFF_MS: u64 = 1000;
+
 /// Manages conflict resolution policies (Backoff, Priority)
 #[pyclass(module = "theus_core")]
 pub struct ConflictManager {
@@ -28,6 +52,8 @@ pub struct ConflictManager {
     vip_holder: Arc<Mutex<Option<String>>>,
     max_retries: u32,
     base_backoff_ms: u64,
+    // [v3.6] Jitter source; the owning engine's, so its seed covers backoff too.
+    rng: Arc<crate::determinism::Determinism>,
 }
 
 #[pymethods]
@@ -40,22 +66,87 @@ impl ConflictManager {
             vip_holder: Arc::new(Mutex::new(None)),
             max_retries,
             base_backoff_ms,
+            rng: Arc::default(),
         }
     }
 
     /// Report a conflict failure for a process/key.
     /// Returns a decision on whether to retry and how long to wait.
     pub fn report_conflict(&self, key: &str) -> RetryDecision {
-        let mut map = self.failures.lock().unwrap();
+        let (should_retry, wait_ms, reason, contenders, count) = self.decide(key);
+        RetryDecision {
+            should_retry,
+            wait_ms,
+            suggested_backoff_ms: if should_retry { Self::suggest_backoff(wait_ms, contenders) } else { 0 },
+            attempts_remaining: self.max_retries.saturating_sub(count),
+            reason,
+        }
+    }
+
+    /// Report success to reset counters.
+    pub fn report_success(&self, key: String) {
+        let mut map = self.failures.relock();
+        map.remove(&key);
+        
+        // Release VIP if held
+        let mut vip_lock = self.vip_holder.relock();
+        if *vip_lock == Some(key) {
+            *vip_lock = None;
+        }
+    }
+    
+    /// Get current failure count (Internal Diagnostic)
+    pub fn get_failure_count(&self, key: &str) -> u32 {
+        let map = self.failures.relock();
+        *map.get(key).unwrap_or(&0)
+    }
+    
+    /// Check if action is blocked by VIP
+    pub fn is_blocked(&self, requester: Option<String>) -> bool {
+        let vip = self.vip_holder.relock();
+        if let Some(ref holder) = *vip {
+            if let Some(req) = requester {
+                return holder != &req;
+            }
+            return true; // Anonymous requests blocked by VIP
+        }
+        false
+    }
+}
+
+impl ConflictManager {
+    /// [v3.6] Draw jitter from `rng` (the owning engine's) instead of a private source.
+    #[must_use]
+    pub fn with_rng(mut self, rng: Arc<crate::determinism::Determinism>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// [v3.6] Drops `key`'s failure count and the priority ticket if it holds it (a stuck
+    /// holder must not block everyone else). Returns True if the ticket was released.
+    pub fn release(&self, key: &str) -> bool {
+        self.failures.relock().remove(key);
+        let mut vip = self.vip_holder.relock();
+        if vip.as_deref() == Some(key) {
+            *vip = None;
+            return true;
+        }
+        false
+    }
+
+    /// Core policy: (`should_retry`, `wait_ms`, reason, `contending_keys`, `failure_count`).
+    fn decide(&self, key: &str) -> (bool, u64, RetryReason, usize, u32) {
+        let mut map = self.failures.relock();
+        let contenders = map.len() + usize::from(!map.contains_key(key));
         let count = map.entry(key.to_string()).or_insert(0);
         
-        let mut vip_lock = self.vip_holder.lock().unwrap();
+        let mut vip_lock = self.vip_holder.relock();
         
         // Check if I am blocked by another VIP
         if let Some(ref current_vip) = *vip_lock {
             if current_vip != key {
                 // I am blocked by a VIP. Wait nicely.
-                return RetryDecision { should_retry: true, wait_ms: 50 }; // 50ms snooze
+                return (true, 50, RetryReason::BlockedByVip, contenders, *count); // 50ms snooze
             }
         }
         
@@ -70,15 +161,15 @@ impl ConflictManager {
                  // Let's reset counter to 0 so it doesn't fail immediately max limit check.
                  // *count = 0; 
                  // Return immediate retry with VIP status.
-                 return RetryDecision { should_retry: true, wait_ms: 1 };
+                 return (true, 1, RetryReason::VipGranted, contenders, *count);
             } else if *vip_lock == Some(key.to_string()) {
                  // I am already VIP. Keep trying.
                  // Don't fail me.
-                 return RetryDecision { should_retry: true, wait_ms: 1 };
+                 return (true, 1, RetryReason::VipRetry, contenders, *count);
             }
             // VIP occupied by someone else, and I hit limit.
             // Give up.
-            return RetryDecision { should_retry: false, wait_ms: 0 };
+            return (false, 0, RetryReason::Exhausted, contenders, *count);
         }
 
         *count += 1;
@@ -88,45 +179,18 @@ impl ConflictManager {
         // delay = base * 2^(attempts-1)
         let mut delay = self.base_backoff_ms * (1 << (attempts - 1).min(10)); 
         
-        // Add random Jitter +/- 20%
-        let mut rng = rand::thread_rng();
-        let jitter = rng.gen_range(0.8..1.2);
+        // Add random Jitter +/- 20% (seeded in deterministic mode)
+        let jitter = self.rng.uniform(0.8, 1.2);
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
         { delay = (delay as f64 * jitter) as u64; }
         
-        RetryDecision { 
-            should_retry: true, 
-            wait_ms: delay 
-        }
+        (true, delay, RetryReason::Backoff, contenders, attempts)
     }
 
-    /// Report success to reset counters.
-    pub fn report_success(&self, key: String) {
-        let mut map = self.failures.lock().unwrap();
-        map.remove(&key);
-        
-        // Release VIP if held
-        let mut vip_lock = self.vip_holder.lock().unwrap();
-        if *vip_lock == Some(key) {
-            *vip_lock = None;
-        }
-    }
-    
-    /// Get current failure count (Internal Diagnostic)
-    pub fn get_failure_count(&self, key: &str) -> u32 {
-        let map = self.failures.lock().unwrap();
-        *map.get(key).unwrap_or(&0)
-    }
-    
-    /// Check if action is blocked by VIP
-    pub fn is_blocked(&self, requester: Option<String>) -> bool {
-        let vip = self.vip_holder.lock().unwrap();
-        if let Some(ref holder) = *vip {
-            if let Some(req) = requester {
-                return holder != &req;
-            }
-            return true; // Anonymous requests blocked by VIP
-        }
-        false
+    /// Scale the (already jittered) `wait_ms` by contention, capped at `MAX_SUGGESTED_BACKOFF_MS`.
+    fn suggest_backoff(wait_ms: u64, contenders: usize) -> u64 {
+        wait_ms
+            .saturating_mul(contenders.max(1) as u64)
+            .clamp(1, MAX_SUGGESTED_BACKOFF_MS)
     }
 }
@@ -0,0 +1,70 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyTypeError;
+use pyo3::types::{PyString, PyType};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use crate::locks::Relock;
+
+// [v3.6] Per-type shadow copiers (`engine.set_copier("DataFrame", lambda df: df.copy())`).
+// `get_shadow` asks the registry before falling back to `copy.deepcopy`, so values with a
+// cheap (or the only working) copy routine do not have to live in the Heavy zone. Types are
+// matched along the MRO by `module.QualName` or bare `QualName`.
+// NOTE: Only the shadowed value itself is looked up; copiers are not consulted for objects
+// nested inside a deep-copied container.
+
+#[derive(Default)]
+pub struct CopierRegistry {
+    copiers: Mutex<BTreeMap<String, PyObject>>,
+}
+
+fn qualified_name(ty: &Bound<'_, PyType>) -> PyResult<(String, String)> {
+    let module: String = ty.getattr("__module__")?.extract()?;
+    let qualname: String = ty.getattr("__qualname__")?.extract()?;
+    Ok((format!("{module}.{qualname}"), qualname))
+}
+
+impl CopierRegistry {
+    /// Registers (or with `copier=None` removes) the copier for `ty` (a type or type name).
+    pub fn set(&self, ty: &Bound<'_, PyAny>, copier: Option<PyObject>) -> PyResult<()> {
+        let name = if let Ok(t) = ty.downcast::<PyType>() {
+            qualified_name(t)?.0
+        } else if let Ok(s) = ty.downcast::<PyString>() {
+            s.to_str()?.to_string()
+        } else {
+            return Err(PyTypeError::new_err("set_copier expects a type or a type name"));
+        };
+        if let Some(c) = &copier {
+            if !c.bind(ty.py()).is_callable() {
+                return Err(PyTypeError::new_err(format!("Copier for '{name}' is not callable")));
+            }
+        }
+        let mut copiers = self.copiers.relock();
+        match copier {
+            Some(c) => copiers.insert(name, c),
+            None => copiers.remove(&name),
+        };
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.copiers.relock().keys().cloned().collect()
+    }
+
+    /// `(registered name, copier)` for the closest type in `value`'s MRO.
+    pub fn find(&self, py: Python, value: &Bound<'_, PyAny>) -> Option<(String, PyObject)> {
+        let copiers = self.copiers.relock();
+        if copiers.is_empty() {
+            return None;
+        }
+        let mro = value.get_type().mro();
+        for ty in mro.iter() {
+            let Ok((full, bare)) = ty.downcast::<PyType>().map_err(PyErr::from).and_then(|t| qualified_name(t)) else { continue };
+            for name in [full, bare] {
+                if let Some(c) = copiers.get(&name) {
+                    return Some((name, c.clone_ref(py)));
+                }
+            }
+        }
+        None
+    }
+}
@@ -15,22 +15,148 @@ pub fn log_heavy_access(path: &str) {
     }
 }
 
+/// [v3.6] Delta op codes. `DeltaEntry.op` stays a string; members compare equal to their
+/// code (`DeltaOp.SET == "SET"`) and are accepted wherever an op string is.
+#[pyclass(module = "theus_core", frozen)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DeltaOp {
+    #[pyo3(name = "SET")]
+    Set,
+    #[pyo3(name = "SET_ITEM")]
+    SetItem,
+    #[pyo3(name = "APPEND")]
+    Append,
+    #[pyo3(name = "EXTEND")]
+    Extend,
+    #[pyo3(name = "POP")]
+    Pop,
+    #[pyo3(name = "REMOVE")]
+    Remove,
+    #[pyo3(name = "CLEAR")]
+    Clear,
+    #[pyo3(name = "UPDATE")]
+    Update,
+    #[pyo3(name = "TENSOR_MUTATION")]
+    TensorMutation,
+}
+
+impl DeltaOp {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DeltaOp::Set => "SET",
+            DeltaOp::SetItem => "SET_ITEM",
+            DeltaOp::Append => "APPEND",
+            DeltaOp::Extend => "EXTEND",
+            DeltaOp::Pop => "POP",
+            DeltaOp::Remove => "REMOVE",
+            DeltaOp::Clear => "CLEAR",
+            DeltaOp::Update => "UPDATE",
+            DeltaOp::TensorMutation => "TENSOR_MUTATION",
+        }
+    }
+}
+
+/// An op argument: a `DeltaOp` or its (free-form) string code.
+pub struct OpCode(pub String);
+
+impl From<DeltaOp> for OpCode {
+    fn from(op: DeltaOp) -> Self {
+        OpCode(op.as_str().to_string())
+    }
+}
+
+impl<'py> FromPyObject<'py> for OpCode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(op) = ob.downcast::<DeltaOp>() {
+            return Ok(OpCode(op.get().as_str().to_string()));
+        }
+        Ok(OpCode(ob.extract()?))
+    }
+}
+
+#[pymethods]
+#[allow(clippy::trivially_copy_pass_by_ref)]
+impl DeltaOp {
+    #[getter]
+    fn code(&self) -> &'static str {
+        self.as_str()
+    }
+
+    fn __str__(&self) -> &'static str {
+        self.as_str()
+    }
+
+    /// Hashes like its code, so members and strings share dict/set slots.
+    fn __hash__(&self, py: Python) -> PyResult<isize> {
+        pyo3::types::PyString::new_bound(py, self.as_str()).hash()
+    }
+
+    fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: pyo3::basic::CompareOp) -> PyObject {
+        let py = other.py();
+        let Ok(OpCode(code)) = other.extract::<OpCode>() else { return py.NotImplemented() };
+        match op {
+            pyo3::basic::CompareOp::Eq => (code == self.as_str()).into_py(py),
+            pyo3::basic::CompareOp::Ne => (code != self.as_str()).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DeltaOp.{}", self.as_str())
+    }
+}
 
 #[derive(Debug)]
 #[pyclass(module = "theus_core")]
 pub struct DeltaEntry {
-    #[pyo3(get)]
-    pub path: String,
-    #[pyo3(get)]
-    pub op: String,
+    /// [v3.6] Path, op and key are interned (`intern.rs`).
+    pub path: crate::intern::Sym,
+    pub op: crate::intern::Sym,
     #[pyo3(get)]
     pub value: Option<Py<PyAny>>,
     #[pyo3(get)]
     pub old_value: Option<Py<PyAny>>,
     #[pyo3(get)]
     pub target: Option<Py<PyAny>>,
-    #[pyo3(get)]
-    pub key: Option<String>,
+    pub key: Option<crate::intern::Sym>,
+    /// [v3.6] Tags of the transaction that logged this delta.
+    pub tags: Option<crate::tags::Tags>,
+}
+
+impl DeltaEntry {
+    /// [v3.6] `{path, op, value, old_value}` (dry runs, rollback reports).
+    pub fn summary<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        let d = pyo3::types::PyDict::new_bound(py);
+        d.set_item("path", &*self.path)?;
+        d.set_item("op", &*self.op)?;
+        d.set_item("value", self.value.as_ref().map(|v| v.clone_ref(py)))?;
+        d.set_item("old_value", self.old_value.as_ref().map(|v| v.clone_ref(py)))?;
+        Ok(d)
+    }
+}
+
+#[pymethods]
+impl DeltaEntry {
+    #[getter]
+    fn path(&self) -> &str {
+        &self.path
+    }
+
+    #[getter]
+    fn op(&self) -> &str {
+        &self.op
+    }
+
+    #[getter]
+    fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    #[getter]
+    fn tags<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        crate::tags::to_dict(py, self.tags.as_ref())
+    }
 }
 
 impl Clone for DeltaEntry {
@@ -43,6 +169,7 @@ impl Clone for DeltaEntry {
                 old_value: self.old_value.as_ref().map(|v| v.clone_ref(py)),
                 target: self.target.as_ref().map(|v| v.clone_ref(py)),
                 key: self.key.clone(),
+                tags: self.tags.clone(),
             }
         })
     }
@@ -57,15 +184,16 @@ pub struct Transaction {
 impl Transaction {
     pub(crate) fn log_internal(
         &mut self, 
-        path: String, 
-        op: String, 
+        path: &str, 
+        op: &str, 
         value: Option<PyObject>, 
         old_value: Option<PyObject>, 
         target: Option<PyObject>, 
-        key: Option<String>
+        key: Option<&str>
     ) {
+        use crate::intern::intern;
         self.delta_log.push(DeltaEntry {
-            path, op, value, old_value, target, key 
+            path: intern(path), op: intern(op), value, old_value, target, key: key.map(intern), tags: None
         });
     }
 }
@@ -89,6 +217,7 @@ impl Transaction {
                 old_value: entry.old_value.as_ref().map(|v| v.clone_ref(py)),
                 target: entry.target.as_ref().map(|v| v.clone_ref(py)),
                 key: entry.key.clone(),
+                tags: entry.tags.clone(),
             };
             let py_obj = Py::new(py, cloned)?;
             list.append(py_obj)?;
@@ -97,7 +226,7 @@ impl Transaction {
     }
 
     #[new]
-    #[must_use] 
+    #[must_use]
     pub fn new() -> Self {
         Transaction { 
             delta_log: Vec::new(),
@@ -107,16 +236,17 @@ impl Transaction {
 
     #[pyo3(signature = (path, op, value=None, old_value=None, target=None, key=None))]
     #[pyo3(name = "log")]
+    #[allow(clippy::needless_pass_by_value)]
     fn log_py(
         &mut self, 
-        path: String, 
-        op: String, 
+        path: &str, 
+        op: OpCode, 
         value: Option<PyObject>, 
         old_value: Option<PyObject>, 
         target: Option<PyObject>, 
-        key: Option<String>
+        key: Option<&str>
     ) {
-        self.log_internal(path, op, value, old_value, target, key);
+        self.log_internal(path, &op.0, value, old_value, target, key);
     }
     
     #[pyo3(signature = (original, path=None))]
@@ -243,8 +373,8 @@ impl Transaction {
     pub fn rollback(&mut self, py: Python) -> PyResult<()> {
         for entry in self.delta_log.iter().rev() {
              if let (Some(target), Some(key), Some(old)) = (&entry.target, &entry.key, &entry.old_value) {
-                 if entry.op == "SET" {
-                     target.bind(py).setattr(key.as_str(), old)?;
+                 if &*entry.op == DeltaOp::Set.as_str() {
+                     target.bind(py).setattr(&**key, old)?;
                  }
              }
         }
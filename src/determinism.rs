@@ -0,0 +1,55 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Mutex;
+
+// [v3.6] Seeded randomness for reproducible runs (golden tests), one per engine.
+// With a seed set, every randomized decision of that engine (backoff jitter, fault
+// probabilities, generated outbox idempotency keys) draws from its own StdRng, so two engines
+// seeded alike replay alike whatever other engines in the process do.
+// NOTE: Timestamps come from the process-wide clock (clock.rs), not from here;
+// `use_global_logical_clock()` makes them depend only on the order of events.
+
+#[derive(Default)]
+pub struct Determinism {
+    seeded: Mutex<Option<(u64, StdRng)>>,
+}
+
+impl Determinism {
+    /// `Some(seed)` enables deterministic mode (re-seeding restarts the sequence), `None` disables it.
+    pub fn set_seed(&self, seed: Option<u64>) {
+        *self.seeded.lock().unwrap_or_else(std::sync::PoisonError::into_inner) =
+            seed.map(|s| (s, StdRng::seed_from_u64(s)));
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seeded.lock().unwrap_or_else(std::sync::PoisonError::into_inner).as_ref().map(|(s, _)| *s)
+    }
+
+    /// Run `f` with the seeded RNG if deterministic mode is on, else with the thread RNG.
+    fn with_rng<T>(&self, f: impl FnOnce(&mut dyn rand::RngCore) -> T) -> T {
+        let mut guard = self.seeded.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        match guard.as_mut() {
+            Some((_, rng)) => f(rng),
+            None => f(&mut rand::thread_rng()),
+        }
+    }
+
+    /// Uniform float in `[low, high)`.
+    pub fn uniform(&self, low: f64, high: f64) -> f64 {
+        self.with_rng(|rng| rng.gen_range(low..high))
+    }
+
+    /// Random (v4) UUID string; reproducible in deterministic mode.
+    pub fn uuid4(&self) -> String {
+        uuid_from(self.with_rng(|rng| rng.gen()))
+    }
+}
+
+/// Random (v4) UUID string from the thread RNG, for ids minted outside any engine.
+pub fn uuid4() -> String {
+    uuid_from(rand::thread_rng().gen())
+}
+
+fn uuid_from(bytes: [u8; 16]) -> String {
+    uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+}
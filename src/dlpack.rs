@@ -0,0 +1,141 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyTypeError;
+use pyo3::types::PyDict;
+
+// [v3.6] DLPack awareness for the Heavy zone. Tensors are identified by the DLPack protocol
+// (`__dlpack__` + `__dlpack_device__`), so torch, cupy, jax, ... are handled without
+// importing them. Device tensors are never deep-copied into transaction shadows, and heavy
+// handles can hand CUDA tensors to another process through CUDA IPC (torch / cupy only).
+
+/// `DLPack` `DLDeviceType` code -> name.
+pub fn device_name(code: i32) -> &'static str {
+    match code {
+        1 => "cpu",
+        2 => "cuda",
+        3 => "cuda_host",
+        4 => "opencl",
+        7 => "vulkan",
+        8 => "metal",
+        9 => "vpi",
+        10 => "rocm",
+        11 => "rocm_host",
+        13 => "cuda_managed",
+        14 => "oneapi",
+        15 => "webgpu",
+        16 => "hexagon",
+        _ => "unknown",
+    }
+}
+
+/// `(device_type, device_id)` of a `DLPack` producer, None for anything else.
+pub fn device_of(value: &Bound<'_, PyAny>) -> Option<(i32, i32)> {
+    if !value.hasattr("__dlpack__").unwrap_or(false) {
+        return None;
+    }
+    value.call_method0("__dlpack_device__").ok()?.extract().ok()
+}
+
+/// True for `DLPack` tensors living off the host (copying them is costly or impossible).
+pub fn is_device_tensor(value: &Bound<'_, PyAny>) -> bool {
+    matches!(device_of(value), Some((code, _)) if code != 1)
+}
+
+/// Deepcopy memo mapping every device tensor reachable from `value` (through dicts,
+/// lists, tuples and instance `__dict__`s) to itself, so `copy.deepcopy` shares them.
+/// None when there are none.
+pub fn share_memo<'py>(py: Python<'py>, value: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyDict>>> {
+    fn walk<'py>(value: &Bound<'py, PyAny>, memo: &Bound<'py, PyDict>, depth: usize) -> PyResult<()> {
+        use pyo3::types::{PyBool, PyFloat, PyList, PyLong, PyString, PyTuple};
+        if depth > 32 || value.is_none() || value.is_exact_instance_of::<PyString>() || value.is_exact_instance_of::<PyLong>()
+            || value.is_exact_instance_of::<PyFloat>() || value.is_exact_instance_of::<PyBool>() {
+            return Ok(());
+        }
+        if let Ok(d) = value.downcast::<PyDict>() {
+            return d.values().iter().try_for_each(|v| walk(&v, memo, depth + 1));
+        }
+        if let Ok(l) = value.downcast::<PyList>() {
+            return l.iter().try_for_each(|v| walk(&v, memo, depth + 1));
+        }
+        if let Ok(t) = value.downcast::<PyTuple>() {
+            return t.iter().try_for_each(|v| walk(&v, memo, depth + 1));
+        }
+        if is_device_tensor(value) {
+            return memo.set_item(value.as_ptr() as usize, value);
+        }
+        match value.getattr("__dict__") {
+            Ok(attrs) if attrs.is_instance_of::<PyDict>() => walk(&attrs, memo, depth + 1),
+            _ => Ok(()),
+        }
+    }
+    let memo = PyDict::new_bound(py);
+    walk(value, &memo, 0)?;
+    Ok((!memo.is_empty()).then_some(memo))
+}
+
+fn module_of(value: &Bound<'_, PyAny>) -> String {
+    value.get_type().getattr("__module__").and_then(|m| m.extract()).unwrap_or_default()
+}
+
+/// Picklable CUDA IPC description of `value` for `ipc_import` in another process.
+pub fn ipc_export(py: Python, value: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let module = module_of(value);
+    let payload = PyDict::new_bound(py);
+    match device_of(value) {
+        Some((2, _)) if module.starts_with("torch") => {
+            let reduced = py.import_bound("torch.multiprocessing.reductions")?
+                .call_method1("reduce_tensor", (value,))?;
+            payload.set_item("kind", "torch")?;
+            payload.set_item("rebuild", reduced.get_item(0)?)?;
+            payload.set_item("args", reduced.get_item(1)?)?;
+        }
+        Some((2, device)) if module.starts_with("cupy") => {
+            let data = value.getattr("data")?;
+            let mem = data.getattr("mem")?;
+            let base: usize = mem.getattr("ptr")?.extract()?;
+            let ptr: usize = data.getattr("ptr")?.extract()?;
+            let handle = py.import_bound("cupy.cuda.runtime")?.call_method1("ipcGetMemHandle", (base,))?;
+            payload.set_item("kind", "cupy")?;
+            payload.set_item("handle", handle)?;
+            payload.set_item("offset", ptr - base)?;
+            payload.set_item("size", mem.getattr("size")?)?;
+            payload.set_item("shape", value.getattr("shape")?)?;
+            payload.set_item("strides", value.getattr("strides")?)?;
+            payload.set_item("dtype", value.getattr("dtype")?.getattr("str")?)?;
+            payload.set_item("device", device)?;
+        }
+        Some((code, _)) => {
+            return Err(PyTypeError::new_err(format!(
+                "No CUDA IPC route for {module} tensor on '{}'; share host memory through the ManagedAllocator instead",
+                device_name(code)
+            )));
+        }
+        None => return Err(PyTypeError::new_err("Heavy value is not a DLPack tensor")),
+    }
+    Ok(payload.into_any().unbind())
+}
+
+/// Open a tensor exported by `ipc_export` (in the receiving process).
+pub fn ipc_import(py: Python, payload: &Bound<'_, PyDict>) -> PyResult<PyObject> {
+    let item = |k: &str| -> PyResult<Bound<'_, PyAny>> {
+        payload.get_item(k)?.ok_or_else(|| PyTypeError::new_err(format!("IPC payload missing '{k}'")))
+    };
+    match item("kind")?.extract::<String>()?.as_str() {
+        "torch" => {
+            let args = item("args")?.downcast_into::<pyo3::types::PyTuple>()?;
+            Ok(item("rebuild")?.call1(args)?.unbind())
+        }
+        "cupy" => {
+            let cuda = py.import_bound("cupy.cuda")?;
+            let device: i32 = item("device")?.extract()?;
+            let ptr = cuda.getattr("runtime")?.call_method1("ipcOpenMemHandle", (item("handle")?,))?;
+            let mem = cuda.getattr("UnownedMemory")?.call1((ptr, item("size")?, py.None(), device))?;
+            let memptr = cuda.getattr("MemoryPointer")?.call1((mem, item("offset")?))?;
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("memptr", memptr)?;
+            kwargs.set_item("strides", item("strides")?)?;
+            let cupy = py.import_bound("cupy")?;
+            Ok(cupy.getattr("ndarray")?.call((item("shape")?, item("dtype")?), Some(&kwargs))?.unbind())
+        }
+        other => Err(PyTypeError::new_err(format!("Unknown IPC payload kind '{other}'"))),
+    }
+}
@@ -1,46 +1,256 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyList};
+use pyo3::types::{PyAny, PyBytes, PyDict, PyList};
 use crate::structures::{State, ContextError, OutboxMsg};
 use crate::conflict::{ConflictManager, RetryDecision};
+use crate::outbox::{now_ms, DeadLetter, InboxState, OutboxMetrics, ProcessedIdWindow};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Instant;
 use crate::structures_helper::set_nested_value;
+use crate::locks::Relock;
 
 pyo3::create_exception!(theus_core, WriteTimeoutError, pyo3::exceptions::PyTimeoutError);
+pyo3::create_exception!(theus_core, TransactionCancelledError, pyo3::exceptions::PyRuntimeError);
+pyo3::create_exception!(theus_core, TransactionLimitError, TransactionCancelledError);
+pyo3::create_exception!(theus_core, MaintenanceModeError, ContextError);
+pyo3::create_exception!(theus_core, EngineShutdownError, ContextError);
+pyo3::create_exception!(theus_core, IntegrityError, ContextError);
+
+static NEXT_TX_ID: AtomicU64 = AtomicU64::new(1);
+
+/// [v3.6] Registry entry for a transaction that has not exited yet.
+struct OpenTx {
+    /// `clock::monotonic_ms()` at creation.
+    created_at: u64,
+    created_at_ms: u64,
+    stack: Option<String>,
+    closed: Arc<AtomicBool>,
+    warned: bool,
+    /// Uncommitted writes shared with `peek_pending` (provisional transactions only).
+    provisional: Option<crate::provisional::Provisional>,
+    /// `shadow_cache` and `full_path_map`, checked by `fsck()`.
+    shadows: ShadowMaps,
+}
+
+type ShadowMaps = (
+    Arc<Mutex<std::collections::HashMap<usize, (PyObject, PyObject)>>>,
+    Arc<Mutex<std::collections::HashMap<String, PyObject>>>,
+);
+
+/// [v3.6] Idle-transaction leak policy.
+#[derive(Default)]
+struct LeakPolicy {
+    threshold_ms: Option<u64>,
+    capture_stack: bool,
+    on_leak: Option<PyObject>,
+}
+
+/// [v3.6] Commits slower than `threshold_ms` report their `tx.stats()` breakdown.
+struct SlowCommitPolicy {
+    threshold_ms: f64,
+    callback: Option<PyObject>,
+}
+
+/// [v3.6] Per-transaction counters and timings (`tx.stats()`).
+#[derive(Default)]
+struct TxStats {
+    shadows: u64,
+    shadow_copy: std::time::Duration,
+    hash_skips: u64,
+    inference: std::time::Duration,
+    schema: std::time::Duration,
+    commit: std::time::Duration,
+}
+
+/// [v3.6] Soft-deadline policy applied to every transaction of an engine.
+pub struct WatchdogConfig {
+    pub soft_deadline_ms: u64,
+    pub callback: Option<PyObject>,
+    pub abort: bool,
+}
+
+/// [v3.6] Hard caps applied to every transaction of an engine (None = unlimited).
+#[derive(Clone, Copy, Default)]
+pub struct TxLimits {
+    pub max_deltas: Option<usize>,
+    pub max_paths: Option<usize>,
+}
+
+/// [v3.6] Whether a shadow still equals its committed value: the `compare_all` verdict, or
+/// Python `==` when ambiguous (NumPy-style array results are reduced with `.all()`).
+fn shadow_equal(py: Python, verdict: crate::shadow_compare::Verdict, shadow: &PyObject, current: &PyObject) -> bool {
+    match verdict {
+        crate::shadow_compare::Verdict::Equal => true,
+        crate::shadow_compare::Verdict::Different => false,
+        crate::shadow_compare::Verdict::Ambiguous => match shadow.bind(py).rich_compare(current.bind(py), pyo3::basic::CompareOp::Eq) {
+            Ok(res) => match res.is_truthy() {
+                Ok(b) => b,
+                // Fallback for NumPy arrays: (a == b).all()
+                Err(_) => res.call_method0("all").is_ok_and(|x| x.is_truthy().unwrap_or(false)),
+            },
+            Err(_) => false,
+        },
+    }
+}
+
+/// [v3.6] `true` for `"repeatable_read"`, `false` for `"read_committed"`.
+fn parse_isolation(level: &str) -> PyResult<bool> {
+    match level {
+        "read_committed" => Ok(false),
+        "repeatable_read" => Ok(true),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown isolation level '{other}' (expected 'read_committed' or 'repeatable_read')"
+        ))),
+    }
+}
+
+fn isolation_name(repeatable: bool) -> &'static str {
+    if repeatable { "repeatable_read" } else { "read_committed" }
+}
+
+/// [v3.6] Debug registry of committed containers per Data-zone key: the zone hash the
+/// tags were taken at, plus (path, id, hash) for every nested container.
+#[derive(Default)]
+struct EscapeRegistry {
+    zones: std::collections::HashMap<String, (u64, Vec<crate::integrity::ContainerTag>)>,
+}
 
 /// Helper to collect outbox messages in Transaction
 #[pyclass(module = "theus_core")]
 pub struct OutboxCollector {
     buffer: Arc<Mutex<Vec<OutboxMsg>>>,
+    // [v3.6] Only the engine-level collector tracks delivery; transaction collectors have None.
+    metrics: Option<Arc<Mutex<OutboxMetrics>>>,
 }
 
 #[pymethods]
 impl OutboxCollector {
-    fn add(&self, msg: OutboxMsg) {
-        self.buffer.lock().unwrap().push(msg);
+    fn add(&self, py: Python, msg: OutboxMsg) -> PyResult<()> {
+        // [v3.6] Processes with an effect budget must declare "outbox".
+        crate::pure_io::check(py, "outbox", &format!("outbox.add: {}", msg.topic))?;
+        self.buffer.relock().push(msg);
+        Ok(())
     }
     
     /// [v3.3] Drain all messages from the buffer for Python-side flush
     fn drain(&self) -> Vec<OutboxMsg> {
-        self.buffer.lock().unwrap().drain(..).collect()
+        self.buffer.relock().drain(..).collect()
     }
     
     /// [v3.3] Get current message count
     fn len(&self) -> usize {
-        self.buffer.lock().unwrap().len()
+        self.buffer.relock().len()
+    }
+
+    /// [v3.6] Non-destructive delivery metrics.
+    fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let (queued, oldest_age_ms) = {
+            let q = self.buffer.relock();
+            let now = now_ms();
+            (q.len(), q.iter().map(|m| now.saturating_sub(m.created_at_ms)).max())
+        };
+        let d = PyDict::new_bound(py);
+        d.set_item("queued", queued)?;
+        d.set_item("oldest_age_ms", oldest_age_ms)?;
+        let m = self.metrics.as_ref().map(|m| m.relock());
+        d.set_item("in_flight", m.as_ref().map_or(0, |m| m.in_flight))?;
+        d.set_item("delivered", m.as_ref().map_or(0, |m| m.delivered))?;
+        d.set_item("failed", m.as_ref().map_or(0, |m| m.failed))?;
+        d.set_item("dead", m.as_ref().map_or(0, |m| m.dead_letters.len()))?;
+        Ok(d.into_any().unbind())
+    }
+
+    /// [v3.6] Snapshot of messages that exhausted their delivery attempts.
+    fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.metrics.as_ref().map_or_else(Vec::new, |m| m.relock().dead_letters.clone())
+    }
+
+    /// [v3.6] Move dead letters (all, or those with the given idempotency keys) back to the
+    /// front of the queue, ahead of the same-key messages held behind them.
+    #[pyo3(signature = (keys=None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn requeue_dead_letters(&self, keys: Option<Vec<String>>) -> usize {
+        let Some(ref metrics) = self.metrics else { return 0 };
+        let taken = metrics.relock().take_dead(keys.as_deref());
+        let n = taken.len();
+        self.buffer.relock().splice(0..0, taken.into_iter().map(|d| {
+            let mut msg = d.msg;
+            msg.attempts = 0;
+            msg
+        }));
+        n
+    }
+
+    /// [v3.6] Drop dead letters (all, or those with the given idempotency keys).
+    #[pyo3(signature = (keys=None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn purge_dead_letters(&self, keys: Option<Vec<String>>) -> usize {
+        self.metrics.as_ref().map_or(0, |m| m.relock().take_dead(keys.as_deref()).len())
     }
 }
 
 #[pyclass(module = "theus_core", subclass)]
 pub struct TheusEngine {
-    state: Py<State>,
+    pub(crate) state: Py<State>,
     outbox: Arc<Mutex<Vec<OutboxMsg>>>,
     worker: Arc<Mutex<Option<PyObject>>>,
     pub schema: Arc<Mutex<Option<PyObject>>>,
     pub audit_system: Arc<Mutex<Option<PyObject>>>, 
     pub strict_guards: Arc<Mutex<bool>>,             // NEW: I/O Policy
     pub strict_cas: Arc<Mutex<bool>>,                // NEW: Concurrency Policy
+    private_allowlist: Arc<Mutex<Vec<String>>>,      // [v3.6] Strict-mode private names
     conflict_manager: Arc<ConflictManager>,
+    processed_ids: Arc<Mutex<ProcessedIdWindow>>,
+    inbox_handler: Arc<Mutex<Option<PyObject>>>,
+    inbox: Arc<Mutex<InboxState>>,
+    outbox_metrics: Arc<Mutex<OutboxMetrics>>,
+    pub(crate) event_watchers: Arc<Mutex<Vec<PyObject>>>,
+    watchdog: Arc<Mutex<Option<WatchdogConfig>>>,
+    tx_limits: Arc<Mutex<TxLimits>>,
+    shadow_cache_limit: Arc<Mutex<Option<u64>>>, // [v3.6] Soft bound on shadow bytes per transaction
+    shadow_hashing: Arc<AtomicBool>,   // [v3.6] Content-hash shortcut for delta inference
+    repeatable_reads: Arc<AtomicBool>,
+    pub(crate) history: Arc<Mutex<crate::pins::VersionHistory>>,
+    open_txs: Arc<Mutex<std::collections::HashMap<u64, OpenTx>>>,
+    leak_policy: Arc<Mutex<LeakPolicy>>,
+    slow_commit: Arc<Mutex<Option<SlowCommitPolicy>>>,
+    maintenance: Arc<Mutex<Option<String>>>,
+    shutting_down: Arc<AtomicBool>,
+    escape_tracking: Arc<Mutex<Option<EscapeRegistry>>>,
+    faults: Arc<crate::faults::FaultRegistry>,
+    // [v3.6] This engine's seeded randomness (`set_deterministic`).
+    pub(crate) determinism: Arc<crate::determinism::Determinism>,
+    recorder: Arc<Mutex<Option<Arc<crate::recorder::Recorder>>>>,
+    approvals: Arc<crate::approvals::ApprovalQueue>,
+    pub(crate) lineage: Arc<crate::lineage::Lineage>,
+    heavy_store: Arc<crate::heavy_store::HeavyStore>,
+    blobs: Arc<crate::blobs::BlobStore>,
+    computed: Arc<crate::computed::ComputedCache>,
+    indexes: Arc<crate::indexes::Indexes>,
+    search: Arc<crate::search::TextIndex>,
+    changes: Arc<crate::cdc::ChangeFeed>,
+    tenants: Arc<crate::tenancy::Tenants>,
+    glass: Arc<crate::break_glass::BreakGlass>,
+    snapshot_base: Arc<Mutex<Option<(u64, String)>>>, // [v3.6] (version, digest) of the last snapshot
+    spill: Arc<Mutex<Option<Arc<crate::spill::SpillStore>>>>,
+    compression: Arc<crate::compress::CompressionStore>,
+    streaming_commit: Arc<Mutex<Option<usize>>>,
+    copiers: Arc<crate::copiers::CopierRegistry>,
+    schema_fields: Arc<crate::schema_fields::SchemaFields>,
+    capability_key: Arc<crate::cap_tokens::TokenKey>,
+    signals: crate::signal_queue::SignalQueue,
+    pub(crate) meta_watch: Arc<crate::meta_watch::MetaWatch>,
+    conflict_sim: Arc<crate::testing::ConflictSim>,
+    pure_io: Arc<crate::pure_io::PurePolicy>,
+    heartbeats: Arc<crate::heartbeat::Heartbeats>,
+    tokens: Arc<crate::cancellation::Tokens>,
+    group: crate::group_commit::GroupSlot,
+    pub(crate) op_ids: Arc<Mutex<crate::op_ids::CommittedOps>>,
+    traces: Arc<Mutex<crate::trace::Traces>>,
+    timers: Arc<Mutex<crate::timers::Timers>>,
+    state_machines: Arc<crate::state_machines::StateMachines>,
+    middleware: Arc<crate::middleware::Pipeline>,
+    lock_epoch: crate::locks::LockEpoch, // [v3.6] Lock recoveries charged to this engine
 }
 
 #[pymethods]
@@ -48,6 +258,8 @@ impl TheusEngine {
     #[new]
     fn new(py: Python) -> PyResult<Self> {
         let state = Py::new(py, State::new(None, None, None, 0, 1000, py)?)?;
+        let determinism = Arc::new(crate::determinism::Determinism::default());
+        let conflict_manager = Arc::new(ConflictManager::new(5, 2).with_rng(determinism.clone()));
         Ok(TheusEngine { 
             state,
             outbox: Arc::new(Mutex::new(Vec::new())),
@@ -56,29 +268,95 @@ impl TheusEngine {
             audit_system: Arc::new(Mutex::new(None)),
             strict_guards: Arc::new(Mutex::new(false)),
             strict_cas: Arc::new(Mutex::new(false)),
-            conflict_manager: Arc::new(ConflictManager::new(5, 2)), 
+            private_allowlist: Arc::new(Mutex::new(Vec::new())),
+            heartbeats: Arc::new(crate::heartbeat::Heartbeats::new(conflict_manager.clone())),
+            tokens: Arc::new(crate::cancellation::Tokens::default()),
+            conflict_manager,
+            processed_ids: Arc::new(Mutex::new(ProcessedIdWindow::new(86_400_000, 100_000))),
+            inbox_handler: Arc::new(Mutex::new(None)),
+            inbox: Arc::new(Mutex::new(InboxState::new())),
+            outbox_metrics: Arc::new(Mutex::new(OutboxMetrics::new(3))),
+            event_watchers: Arc::new(Mutex::new(Vec::new())),
+            watchdog: Arc::new(Mutex::new(None)),
+            tx_limits: Arc::new(Mutex::new(TxLimits::default())),
+            shadow_cache_limit: Arc::new(Mutex::new(None)),
+            shadow_hashing: Arc::new(AtomicBool::new(false)),
+            repeatable_reads: Arc::new(AtomicBool::new(false)),
+            history: Arc::new(Mutex::new(crate::pins::VersionHistory::default())),
+            open_txs: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            leak_policy: Arc::new(Mutex::new(LeakPolicy::default())),
+            slow_commit: Arc::new(Mutex::new(None)),
+            maintenance: Arc::new(Mutex::new(None)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            escape_tracking: Arc::new(Mutex::new(None)),
+            faults: Arc::new(crate::faults::FaultRegistry::new(determinism.clone())),
+            determinism,
+            recorder: Arc::new(Mutex::new(None)),
+            approvals: Arc::new(crate::approvals::ApprovalQueue::default()),
+            lineage: Arc::new(crate::lineage::Lineage::default()),
+            heavy_store: Arc::new(crate::heavy_store::HeavyStore::default()),
+            blobs: Arc::new(crate::blobs::BlobStore::default()),
+            computed: Arc::new(crate::computed::ComputedCache::default()),
+            indexes: Arc::new(crate::indexes::Indexes::default()),
+            search: Arc::new(crate::search::TextIndex::default()),
+            changes: Arc::new(crate::cdc::ChangeFeed::default()),
+            tenants: Arc::new(crate::tenancy::Tenants::default()),
+            glass: Arc::new(crate::break_glass::BreakGlass::default()),
+            snapshot_base: Arc::new(Mutex::new(None)),
+            spill: Arc::new(Mutex::new(None)),
+            compression: Arc::new(crate::compress::CompressionStore::default()),
+            streaming_commit: Arc::new(Mutex::new(None)),
+            copiers: Arc::new(crate::copiers::CopierRegistry::default()),
+            schema_fields: Arc::new(crate::schema_fields::SchemaFields::default()),
+            capability_key: Arc::new(crate::cap_tokens::TokenKey::default()),
+            signals: crate::signal_queue::SignalQueue::default(),
+            meta_watch: Arc::new(crate::meta_watch::MetaWatch::default()),
+            conflict_sim: Arc::new(crate::testing::ConflictSim::default()),
+            pure_io: Arc::new(crate::pure_io::PurePolicy::default()),
+            group: Arc::new(Mutex::new(None)),
+            op_ids: Arc::new(Mutex::new(crate::op_ids::CommittedOps::default())),
+            traces: Arc::new(Mutex::new(crate::trace::Traces::default())),
+            timers: Arc::new(Mutex::new(crate::timers::Timers::default())),
+            state_machines: Arc::new(crate::state_machines::StateMachines::default()),
+            middleware: Arc::new(crate::middleware::Pipeline::default()),
+            lock_epoch: crate::locks::LockEpoch::default(),
         })
     }
     
     fn set_audit_system(&self, audit: PyObject) {
-        let mut a = self.audit_system.lock().unwrap();
+        let mut a = self.audit_system.relock();
         *a = Some(audit);
     }
 
     // Explicit Feature Toggles (POP Manifesto)
     fn set_strict_guards(&self, enabled: bool) {
-        let mut s = self.strict_guards.lock().unwrap();
+        let mut s = self.strict_guards.relock();
         *s = enabled;
     }
 
+    /// [v3.6] Private attribute names (e.g. `_asdict`, `_fields`) that guards still
+    /// resolve under strict mode. Replaces the previous list; `__dict__` is rejected.
+    fn set_private_allowlist(&self, names: Vec<String>) -> PyResult<()> {
+        *self.private_allowlist.relock() = crate::guards::normalize_private_allowlist(names)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn private_allowlist(&self) -> Vec<String> {
+        self.private_allowlist.relock().clone()
+    }
+
     fn set_strict_cas(&self, enabled: bool) {
-        let mut s = self.strict_cas.lock().unwrap();
+        let mut s = self.strict_cas.relock();
         *s = enabled;
     }
 
-    fn set_schema(&self, schema: PyObject) {
-        let mut s = self.schema.lock().unwrap();
+    fn set_schema(&self, py: Python, schema: PyObject) -> PyResult<()> {
+        // [v3.6] Field index for proxy `schema_fields()` / "did you mean" hints.
+        self.schema_fields.rebuild(py, Some(schema.bind(py)))?;
+        let mut s = self.schema.relock();
         *s = Some(schema);
+        Ok(())
     }
     
     // Conflict APIs for Python Retry Loop
@@ -100,299 +378,2955 @@ impl TheusEngine {
     fn outbox(&self) -> OutboxCollector {
         OutboxCollector {
             buffer: self.outbox.clone(),
+            metrics: Some(self.outbox_metrics.clone()),
         }
     }
 
+    /// [v3.6] Claimable queue of committed signals (`engine.signals.claim(n, consumer_id)`).
+    #[getter]
+    fn signals(&self) -> crate::signal_queue::SignalQueue {
+        self.signals.clone()
+    }
+
+    /// [v3.6] Test helpers (`engine.testing.simulate_conflict(paths, at_version)`).
+    #[getter]
+    fn testing(&self) -> crate::testing::EngineTesting {
+        crate::testing::EngineTesting::new(self.conflict_sim.clone())
+    }
+
     // Return Transaction.
-    #[pyo3(signature = (write_timeout_ms=5000))]
-    #[allow(clippy::unnecessary_wraps)]
-    fn transaction(slf: Py<TheusEngine>, py: Python, write_timeout_ms: u64) -> PyResult<Transaction> {
-        Ok(Transaction {
-            engine: slf,
-            pending_data: PyDict::new_bound(py).unbind(),
-            pending_heavy: PyDict::new_bound(py).unbind(),
-            pending_signal: PyList::empty_bound(py).unbind(), // Fix: PyList
-            pending_outbox: Arc::new(Mutex::new(Vec::new())),
-            start_time: None,
-            start_version: 0,
-            write_timeout_ms,
-            delta_log: Arc::new(Mutex::new(Vec::new())),
-            shadow_cache: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            path_to_shadow: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            full_path_map: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            shadows_inferred: Arc::new(Mutex::new(false)),
-        })
+    #[pyo3(signature = (write_timeout_ms=5000, actor=None, admin=false, tags=None, isolation=None, op_id=None, partial_commit=false, provisional=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn transaction(slf: Py<TheusEngine>, py: Python, write_timeout_ms: u64, actor: Option<String>, admin: bool, tags: Option<&Bound<'_, PyDict>>, isolation: Option<&str>, op_id: Option<String>, partial_commit: bool, provisional: bool) -> PyResult<Transaction> {
+        let tags = crate::tags::from_py(tags)?;
+        let mut tx = Transaction::fresh(py, slf, write_timeout_ms)?;
+        tx.actor = actor;
+        tx.admin = admin;
+        tx.tags = tags;
+        if let Some(level) = isolation {
+            tx.repeatable = parse_isolation(level)?;
+        }
+        tx.op_id = op_id;
+        tx.partial_commit = partial_commit;
+        if provisional {
+            tx.share_pending(py);
+        }
+        Ok(tx)
+    }
+
+    /// [v3.6] Write the `{path: value}` of `provider` (`EnvProvider`, `CallbackProvider` or any
+    /// object with `fetch()`) in one admin transaction by actor "seed:<name>". The audit log
+    /// gets the injected paths, not the values. Returns the seeded paths.
+    #[allow(clippy::needless_pass_by_value)]
+    fn seed_from(slf: Py<TheusEngine>, py: Python, provider: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        let (name, pairs) = crate::seeding::fetch(provider)?;
+        if pairs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let paths: Vec<String> = pairs.iter().map(|(p, _)| p.clone()).collect();
+        let mut tx = Transaction::fresh(py, slf.clone_ref(py), 5000)?;
+        tx.actor = Some(format!("seed:{name}"));
+        tx.admin = true;
+        let tx = Bound::new(py, tx)?;
+        tx.call_method0("__enter__")?;
+        if let Err(e) = tx.call_method1("update_many", (pairs,)) {
+            tx.call_method1("__exit__", (e.get_type_bound(py), e.value_bound(py), py.None()))?;
+            return Err(e);
+        }
+        tx.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+        let version = slf.borrow(py).state.bind(py).borrow().version;
+        let redacted: Vec<String> = paths.iter().map(|p| format!("{p}=<redacted>")).collect();
+        crate::audit::log_global("STATE_SEEDED", &format!("{name} -> v{version}: {}", redacted.join(", ")));
+        Ok(paths)
+    }
+
+    /// [v3.6] Version the idempotent operation `op_id` committed in (None if unknown or
+    /// already forgotten).
+    fn committed_op(&self, op_id: &str) -> Option<u64> {
+        self.op_ids.relock().get(op_id)
+    }
 
+    /// [v3.6] How many committed op ids are remembered (oldest forgotten first).
+    fn set_op_id_retention(&self, max_entries: usize) {
+        self.op_ids.relock().set_capacity(max_entries);
     }
 
     fn attach_worker(&self, worker: PyObject) {
-        let mut w = self.worker.lock().unwrap();
+        let mut w = self.worker.relock();
         *w = Some(worker);
     }
     
-    fn process_outbox(&self, py: Python) -> PyResult<()> {
-        let msgs: Vec<OutboxMsg>;
-        {
-            let mut q = self.outbox.lock().unwrap();
+    /// NOTE: The engine is not borrowed while the worker runs, so a worker may open
+    /// transactions (e.g. to record an acknowledgement) on this engine.
+    /// [v3.6] Messages sharing an `ordering_key` with a dead letter stay queued until it is
+    /// requeued or purged.
+    fn process_outbox(slf: &Bound<'_, Self>, py: Python) -> PyResult<()> {
+        let (msgs, worker, outbox, metrics, processed_ids, faults) = {
+            let this = slf.borrow();
+            let blocked = this.outbox_metrics.relock().blocked_keys();
+            let mut q = this.outbox.relock();
             if q.is_empty() {
                 return Ok(());
             }
-            msgs = q.drain(..).collect();
-        }
+            let (held, msgs): (Vec<OutboxMsg>, Vec<OutboxMsg>) = q.drain(..)
+                .partition(|m| m.ordering_key.as_ref().is_some_and(|k| blocked.contains(k)));
+            q.extend(held);
+            let msgs = crate::outbox::delivery_order(msgs);
+            let worker = this.worker.relock().as_ref().map(|w| w.clone_ref(py));
+            (msgs, worker, this.outbox.clone(), this.outbox_metrics.clone(), this.processed_ids.clone(), this.faults.clone())
+        };
         
         // Call worker
-        let w_guard = self.worker.lock().unwrap();
-        if let Some(ref worker) = *w_guard {
-             for msg in msgs {
-                 // Convert OutboxMsg to Python object? 
-                 // It is a PyClass, so passing it is fine.
-                 // We need to convert `msg` (Rust struct) to PyObject.
-                 // OutboxMsg implements Clone.
-                 // But `msg` is owned `OutboxMsg`. 
-                 // To pass to Python, we wrap it in Py::new or into_py?
-                 // Since OutboxMsg is #[pyclass(module = "theus_core")], we can create new Python instance.
-                 let py_msg = Py::new(py, msg)?;
-                 worker.call1(py, (py_msg,))?;
+        if let Some(ref worker) = worker {
+             metrics.relock().in_flight = msgs.len();
+             let mut iter = msgs.into_iter();
+             while let Some(mut msg) = iter.next() {
+                 // [v3.6] Exactly-once: skip messages whose side effect already completed.
+                 if processed_ids.relock().contains(&msg.idempotency_key, now_ms()) {
+                     metrics.relock().in_flight -= 1;
+                     continue;
+                 }
+                 let key = msg.idempotency_key.clone();
+                 let py_msg = Py::new(py, msg.clone())?;
+                 let delivered = faults.check("outbox").and_then(|()| worker.call1(py, (py_msg,)));
+                 if let Err(e) = delivered {
+                     // NOTE: Re-queue the failed message and everything after it (in order)
+                     // so a redelivery attempt does not lose undelivered work.
+                     // [v3.6] Messages that exhaust `max_attempts` move to the dead-letter store.
+                     msg.attempts += 1;
+                     let mut metrics = metrics.relock();
+                     metrics.failed += 1;
+                     metrics.in_flight = 0;
+                     let mut remaining: Vec<OutboxMsg> = Vec::new();
+                     if msg.attempts >= metrics.max_attempts {
+                         metrics.dead_letters.push(DeadLetter { msg, error: e.to_string(), failed_at_ms: now_ms() });
+                     } else {
+                         remaining.push(msg);
+                     }
+                     remaining.extend(iter);
+                     outbox.relock().splice(0..0, remaining);
+                     return Err(e);
+                 }
+                 processed_ids.relock().mark(key, now_ms());
+                 let mut metrics = metrics.relock();
+                 metrics.delivered += 1;
+                 metrics.in_flight -= 1;
              }
         }
         Ok(())
     }
 
-    #[pyo3(signature = (expected_version, data=None, heavy=None, signal=None, requester=None))]
-    fn compare_and_swap(
-        &mut self, 
-        py: Python, 
-        expected_version: u64, 
-        data: Option<PyObject>, 
-        heavy: Option<PyObject>,
-        signal: Option<PyObject>,
-        requester: Option<String>
-    ) -> PyResult<()> {
-        // v3.3 Priority Ticket Check
-        if self.conflict_manager.is_blocked(requester) {
-             return Err(ContextError::new_err("System Busy (VIP Access Only)"));
-        }
+    /// [v3.6] Failed deliveries before a message is dead-lettered (default 3).
+    fn set_outbox_max_attempts(&self, max_attempts: u32) {
+        self.outbox_metrics.relock().max_attempts = max_attempts.max(1);
+    }
 
-        // [FIX] Enforce Strict CAS if enabled (Explicit)
-        let strict_cas = *self.strict_cas.lock().unwrap();
-        
-        let current_state_bound = self.state.bind(py);
-        let current_state = current_state_bound.borrow();
-        let current_version = current_state.version;
-        
-        if current_version != expected_version {
-            if strict_cas {
-                 return Err(ContextError::new_err(format!(
-                    "Strict CAS Mismatch: Expected {expected_version}, Found {current_version} (Strict CAS Enabled)"
-                )));
-            }
+    /// [v3.6] Transaction watchdog. Once a transaction runs past `soft_deadline_ms`,
+    /// `callback(info)` fires once (or a `RuntimeWarning` if no callback). With `action="abort"`
+    /// the transaction is cancelled: proxy writes raise and the commit is rejected.
+    /// Pass `soft_deadline_ms=None` to disable.
+    #[pyo3(signature = (soft_deadline_ms=None, callback=None, action="warn"))]
+    fn set_transaction_watchdog(&self, soft_deadline_ms: Option<u64>, callback: Option<PyObject>, action: &str) -> PyResult<()> {
+        let abort = match action {
+            "warn" => false,
+            "abort" => true,
+            other => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown watchdog action '{other}' (expected 'warn' or 'abort')"))),
+        };
+        *self.watchdog.relock() = soft_deadline_ms.map(|soft_deadline_ms| WatchdogConfig { soft_deadline_ms, callback, abort });
+        Ok(())
+    }
 
-            // v3.3 Smart CAS: Check Key-Level Conflicts
-            // If the specific keys we are updating haven't changed since expected_version,
-            // we can safely merge even if global version bumped.
-            
-            let mut safe = true;
-            
-            // v3.1: Check FIELD-Level Conflicts (domain.counter, not just domain)
-            // Check Data Keys
-            if let Some(ref d) = data {
-                if let Ok(d_dict) = d.downcast_bound::<PyDict>(py) {
-                    for (zone_k, zone_v) in d_dict.iter() {
-                         let zone_key = zone_k.extract::<String>()?;
-                         
-                         // Check nested fields if value is a dict
-                         if let Ok(inner_dict) = zone_v.downcast::<PyDict>() {
-                             for (ik, _) in inner_dict {
-                                 let inner_key = ik.extract::<String>()?;
-                                 let field_path = format!("{zone_key}.{inner_key}");  // "domain.counter"
-                                 
-                                 if let Some(last_ver) = current_state.key_last_modified.get(&field_path) {
-                                     if *last_ver > expected_version {
-                                         safe = false;
-                                         break;
-                                     }
-                                 }
-                             }
-                         } else {
-                             // Non-dict value: fall back to zone-level check
-                             if let Some(last_ver) = current_state.key_last_modified.get(&zone_key) {
-                                 if *last_ver > expected_version {
-                                     safe = false;
-                                 }
-                             }
-                         }
-                         if !safe { break; }
-                    }
-                }
-            }
-            
-            // Check Heavy Keys (if safe so far)
-            if safe {
-                if let Some(ref h) = heavy {
-                    if let Ok(h_dict) = h.downcast_bound::<PyDict>(py) {
-                        for (zone_k, zone_v) in h_dict.iter() {
-                             let zone_key = zone_k.extract::<String>()?;
-                             
-                             // Check nested fields if value is a dict
-                             if let Ok(inner_dict) = zone_v.downcast::<PyDict>() {
-                                 for (ik, _) in inner_dict {
-                                     let inner_key = ik.extract::<String>()?;
-                                     let field_path = format!("{zone_key}.{inner_key}");
-                                     
-                                     if let Some(last_ver) = current_state.key_last_modified.get(&field_path) {
-                                         if *last_ver > expected_version {
-                                             safe = false;
-                                             break;
-                                         }
-                                     }
-                                 }
-                             } else {
-                                 // Non-dict value: fall back to zone-level check
-                                 if let Some(last_ver) = current_state.key_last_modified.get(&zone_key) {
-                                     if *last_ver > expected_version {
-                                         safe = false;
-                                     }
-                                 }
-                             }
-                             if !safe { break; }
-                        }
-                    }
-                }
-            }
+    /// [v3.6] Hard caps per transaction: at most `max_deltas` logged deltas and `max_paths`
+    /// distinct paths written (None = unlimited). Exceeding one cancels the transaction,
+    /// logs a `TX_LIMIT_EXCEEDED` audit event and raises `TransactionLimitError`. Applies to
+    /// transactions opened afterwards.
+    #[pyo3(signature = (max_deltas=None, max_paths=None))]
+    fn set_transaction_limits(&self, max_deltas: Option<usize>, max_paths: Option<usize>) {
+        *self.tx_limits.relock() = TxLimits { max_deltas, max_paths };
+    }
 
-            if !safe {
-                return Err(ContextError::new_err(format!(
-                    "CAS Version Mismatch (Conflict Detected): Expected {expected_version}, Found {current_version} (Keys Changed)"
-                )));
-            }
-            // If safe, fall through to update (Optimistic Merge)
-        }
+    /// [v3.6] Soft bound on the estimated bytes of deepcopied shadows a transaction keeps
+    /// (None = unbounded). Past it, least recently used shadows that are untouched, have no
+    /// logged delta and are not referenced outside the transaction are evicted and re-copied
+    /// on their next access. Applies to transactions opened afterwards.
+    #[pyo3(signature = (max_bytes=None))]
+    fn set_shadow_cache_limit(&self, max_bytes: Option<u64>) {
+        *self.shadow_cache_limit.relock() = max_bytes;
+    }
 
-        // We must drop the borrow before calling Python method `update` on the object
-        // because `update` might need mutable access or create new object?
-        // Actually `update` is a method on `State` which is immutable self.
-        // But `call_method` might re-enter?
-        // Safe practice: drop borrow.
-        drop(current_state);
+    /// [v3.6] Record a content hash (XXH3 of the canonical encoding, or of the pickle) of
+    /// every shadow when it is created; at commit a shadow whose hash is unchanged is skipped
+    /// without comparing it (no Python `__eq__`). Costs one hash per shadow up front; a 64-bit
+    /// collision would hide a write. Applies to transactions opened afterwards.
+    fn set_shadow_hashing(&self, enabled: bool) {
+        self.shadow_hashing.store(enabled, Ordering::SeqCst);
+    }
 
-        // [INC-023] Clone signal before moving into State.update() so we can publish
-        // after commit. State.update() only latches last_signals (Flux); actual publish
-        // is deferred to after self.state is updated below.
-        let signal_for_publish = signal.as_ref().map(|s| s.clone_ref(py));
+    /// [v3.6] Lifecycle field: proxy writes to paths matching `path_pattern` (`*` = one
+    /// segment) must follow `transitions` (`{state: [next states]}`); a first value must be
+    /// in `initial` (default: any declared state). Illegal jumps raise `IllegalTransitionError`
+    /// and are audited as `ILLEGAL_TRANSITION`. Redefining a pattern replaces its machine.
+    #[pyo3(signature = (path_pattern, transitions, initial=None))]
+    fn define_state_machine(&self, path_pattern: &str, transitions: &Bound<'_, PyDict>, initial: Option<Vec<String>>) -> PyResult<()> {
+        self.state_machines.define(path_pattern, transitions, initial)
+    }
 
-        let new_state_obj = current_state_bound.call_method(
-            "update", 
-            (data, heavy, signal), 
-            None
-        )?;
+    /// [v3.6] Stop enforcing the machine for `path_pattern`. Returns False if there was none.
+    fn remove_state_machine(&self, path_pattern: &str) -> bool {
+        self.state_machines.remove(path_pattern)
+    }
 
-        // [v3.1.2] Schema Enforcement for CAS (Critical Gatekeeper)
-        // Ensure new state is valid before replacing self.state
-        {
-             let schema_mutex = self.schema.lock().unwrap(); // Use separate var to avoid borrow conflict
-             if let Some(ref schema) = *schema_mutex {
-                 // Validate Resulting State
-                 let frozen_data = new_state_obj.getattr("data")?;
-                 let dict_data = frozen_data.call_method0("to_dict")?;
-                 
-                 if let Err(e) = schema.call_method1(py, "model_validate", (dict_data,)) {
-                     // Reject Commit!
-                     return Err(crate::config::SchemaViolationError::new_err(format!("Schema Violation (CAS): {e}")));
-                 }
-             }
+    /// [v3.6] Defined machines: `{pattern: {"transitions": {..}, "initial": [..] | None}}`.
+    fn state_machines<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.state_machines.export(py)
+    }
+
+    /// [v3.6] Intercept proxy operations: `handler(path, value, op) -> value` runs on leaf
+    /// reads ("get") and writes ("set" / "append" / "insert"), may transform the value or
+    /// veto by raising `MiddlewareVetoError`. Writes run by ascending `priority`, reads in
+    /// reverse. `paths` (prefixes, `*` = one segment) and `ops` narrow where it applies.
+    /// Re-adding `name` replaces it; applies to transactions opened afterwards too.
+    #[pyo3(signature = (name, handler, priority=0, paths=None, ops=None))]
+    fn add_middleware(&self, py: Python, name: &str, handler: PyObject, priority: i32, paths: Option<Vec<String>>, ops: Option<Vec<String>>) -> PyResult<()> {
+        if !handler.bind(py).is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!("Middleware '{name}' must be callable")));
         }
-        
-        self.state = new_state_obj.extract::<Py<State>>()?;
+        self.middleware.add_py(name, handler, priority, paths, ops)
+    }
 
-        // [INC-023] Deferred signal dispatch — fires AFTER self.state is committed.
-        // Guarantees that subscribers see consistent state when they receive the event.
-        // If schema validation failed above, this line is never reached — no orphaned signals.
-        if let Some(sig) = signal_for_publish {
-            self.state.bind(py).borrow().publish_signals(py, Some(sig))?;
+    /// [v3.6] Store values at `paths` (middleware patterns, e.g. "domain.users.*.token")
+    /// encrypted: proxy writes and matching `tx.update()` / `update_many()` values are sealed
+    /// with the key `key_provider(key_id) -> 32 bytes`, bound to their path, and proxy reads
+    /// decrypt, so snapshots, deltas and audit values only hold ciphertext.
+    /// Registered as middleware `name`; the high default `priority` makes it run last on write
+    /// and first on read, so other middlewares see plaintext.
+    #[pyo3(signature = (paths, key_provider, key_id="default".to_string(), name="encryption", priority=1_000_000))]
+    fn encrypt_fields(&self, py: Python, paths: Vec<String>, key_provider: PyObject, key_id: String, name: &str, priority: i32) -> PyResult<()> {
+        if !key_provider.bind(py).is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err("key_provider must be callable"));
         }
+        let cipher = crate::field_crypto::FieldCipher::new(key_provider, key_id)?;
+        self.middleware.add(name, Arc::new(cipher), priority, Some(paths), None)
+    }
 
-        Ok(())
+    /// [v3.6] Normalize values written to `path` (middleware pattern) before they are logged.
+    /// `rules` is `"to_decimal"` / `"to_utc"` / `"strip_strings"`, a callable `rule(value) -> value`,
+    /// or a list of those applied in order. Registered as middleware "coerce:<path>"; re-adding
+    /// a path replaces its rules.
+    #[pyo3(signature = (path, rules, priority=-1_000_000))]
+    fn add_coercion(&self, path: &str, rules: &Bound<'_, PyAny>, priority: i32) -> PyResult<()> {
+        let coercion = crate::coercion::Coercion::new(rules)?;
+        let ops = ["set", "append", "insert"].map(String::from).to_vec();
+        self.middleware.add(&format!("coerce:{path}"), Arc::new(coercion), priority, Some(vec![path.to_string()]), Some(ops))
     }
 
-    #[pyo3(signature = (name, func, tx=None))]
-    fn execute_process_async<'py>(
-        &self, 
-        py: Python<'py>, 
-        name: &str, 
-        func: PyObject,
-        tx: Option<PyObject>
-    ) -> PyResult<Bound<'py, PyAny>> {
-        let _ = name; 
-        
-        let inspect = py.import("inspect")?;
-        let is_coroutine = inspect.call_method1("iscoroutinefunction", (&func,))?.is_truthy()?;
-        
-        // if tx.is_some() { println!("DEBUG: execute_process_async with TX"); } else { println!("DEBUG: execute_process_async NO TX"); }
+    /// [v3.6] Drop the coercion rules of `path`. Returns False if there were none.
+    fn remove_coercion(&self, path: &str) -> bool {
+        self.middleware.remove(&format!("coerce:{path}"))
+    }
 
-        // Create Ephemeral Context (RAII)
-        let local_dict = PyDict::new_bound(py);
-        
-        let py_tx: Option<Py<Transaction>> = tx.map(|t| t.extract(py)).transpose()?;
-        
-        // [v3.3 Fix] Share Outbox Buffer with Transaction if present
-        let outbox_buffer = if let Some(ref t) = py_tx {
-            t.borrow(py).pending_outbox.clone()
-        } else {
-            Arc::new(Mutex::new(Vec::new()))
-        };
+    /// [v3.6] Tag paths with their canonical unit (`{"domain.job.timeout": "ms"}`); proxy
+    /// writes of `(value, unit)` tuples or pint-like quantities are converted to it. A None unit
+    /// removes the tag. `annotate_reads=True` makes leaf reads return `(value, unit)`.
+    /// Registered as middlewares "unit:<path>".
+    #[pyo3(signature = (units, annotate_reads=false))]
+    fn set_units(&self, units: std::collections::BTreeMap<String, Option<String>>, annotate_reads: bool) -> PyResult<()> {
+        let ops = if annotate_reads { None } else { Some(["set", "append", "insert"].map(String::from).to_vec()) };
+        // Validate everything first so a bad entry leaves the tags unchanged.
+        let tags = units.into_iter()
+            .map(|(path, unit)| Ok((path, unit.map(|u| crate::units::UnitTag::new(&u, annotate_reads)).transpose()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        for (path, tag) in tags {
+            let name = format!("unit:{path}");
+            match tag {
+                Some(tag) => self.middleware.add(&name, Arc::new(tag), i32::MIN, Some(vec![path]), ops.clone())?,
+                None => { self.middleware.remove(&name); }
+            }
+        }
+        Ok(())
+    }
 
-        let ctx = Py::new(py, crate::structures::ProcessContext {
-            state: self.state.clone_ref(py),
-            local: local_dict.unbind(),
-            outbox: crate::structures::Outbox {
-                messages: outbox_buffer 
-            },
-            tx: py_tx, 
-        })?;
+    /// [v3.6] Unregister a middleware. Returns False if there was none.
+    fn remove_middleware(&self, name: &str) -> bool {
+        self.middleware.remove(name)
+    }
+
+    /// [v3.6] Registered middleware names in write order.
+    fn middlewares(&self) -> Vec<String> {
+        self.middleware.names()
+    }
+
+    /// [v3.6] Per-middleware timing: `{name: {priority, calls, vetoes, errors, total_ms, avg_us}}`.
+    #[pyo3(signature = (reset=false))]
+    fn middleware_stats<'py>(&self, py: Python<'py>, reset: bool) -> PyResult<Bound<'py, PyDict>> {
+        self.middleware.stats(py, reset)
+    }
+
+    /// [v3.6] Default isolation of new transactions: `"read_committed"` (reads see the latest
+    /// commit) or `"repeatable_read"` (reads resolve against the state pinned at `__enter__`).
+    fn set_isolation(&self, level: &str) -> PyResult<()> {
+        self.repeatable_reads.store(parse_isolation(level)?, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[getter]
+    fn isolation(&self) -> &'static str {
+        isolation_name(self.repeatable_reads.load(Ordering::SeqCst))
+    }
+
+    /// [v3.6] Read-only handle for plugins: reads, watchers and history with Private-zone
+    /// data always redacted, and no way to open a transaction or reach this engine.
+    fn observer(slf: Py<Self>) -> crate::observer::ObserverEngine {
+        crate::observer::ObserverEngine::new(slf)
+    }
+
+    /// [v3.6] Read handle on `version` (None = current) that keeps it alive while writers
+    /// continue; older versions must still be retained (`set_version_retention`). Unpinned by
+    /// `view.unpin()`, leaving a `with` block, or when the view is garbage-collected.
+    #[pyo3(signature = (version=None))]
+    fn pin(&self, py: Python, version: Option<u64>) -> PyResult<crate::pins::PinnedView> {
+        crate::pins::pin(py, &self.history, &self.state, version)
+    }
+
+    /// [v3.6] Evaluate a JSONPath-style `expr` ("$.domain.orders[?(@.status == 'failed')]")
+    /// over the Data zones of `version` (None = current; older ones must be retained).
+    /// Returns plain values - or `(path, value)` tuples - with Private-zone data redacted.
+    #[pyo3(signature = (expr, version=None, with_paths=false))]
+    fn query(&self, py: Python, expr: &str, version: Option<u64>, with_paths: bool) -> PyResult<PyObject> {
+        let query = crate::query::Query::parse(expr)?;
+        let state = crate::pins::state_at(py, &self.history, &self.state, version)?;
+        query.run(py, &state.bind(py).borrow(), with_paths)
+    }
+
+    /// [v3.6] Maintain an index of the values at `pattern` (`"domain.orders[*].customer_id"`)
+    /// for `lookup()`. `name` defaults to the pattern; re-creating a name rebuilds it.
+    /// Returns the name.
+    #[pyo3(signature = (pattern, name=None))]
+    fn create_index(&self, py: Python, pattern: &str, name: Option<String>) -> PyResult<String> {
+        let pattern = crate::indexes::Pattern::parse(pattern)?;
+        let name = name.unwrap_or_else(|| pattern.text.clone());
+        self.indexes.create(py, &name, pattern, &self.state.bind(py).borrow())?;
+        Ok(name)
+    }
+
+    /// [v3.6] Drop index `name`. Returns False if there was none.
+    fn drop_index(&self, name: &str) -> bool {
+        self.indexes.drop_index(name)
+    }
+
+    /// [v3.6] Sorted paths whose value at index `index` equals `value` (committed state).
+    fn lookup(&self, index: &str, value: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        self.indexes.lookup(index, value)
+    }
+
+    /// [v3.6] `{name: {pattern, keys, entries}}` of the secondary indexes.
+    fn index_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.indexes.stats(py)
+    }
+
+    /// [v3.6] Make the strings at (and under) `paths` ("domain.tickets.*.body") searchable
+    /// with `search()`. Replaces the previous paths; an empty list turns search off.
+    #[allow(clippy::needless_pass_by_value)]
+    fn enable_search(&self, py: Python, paths: Vec<String>) -> PyResult<()> {
+        let patterns = paths.iter().map(|p| crate::indexes::Pattern::parse(p)).collect::<PyResult<Vec<_>>>()?;
+        self.search.configure(py, patterns, &self.state.bind(py).borrow())
+    }
+
+    /// [v3.6] `(path, snippet, version)` of searchable strings containing every word of
+    /// `terms` (case-insensitive), in path order.
+    #[pyo3(signature = (terms, limit=50))]
+    fn search(&self, terms: &str, limit: usize) -> Vec<(String, String, u64)> {
+        self.search.search(terms, limit)
+    }
+
+    /// [v3.6] `{patterns, documents, terms}` of the full-text index.
+    fn search_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.search.stats(py)
+    }
+
+    /// [v3.6] Change data capture: after each commit `sink(events)` gets the changes at
+    /// `paths` ("domain.orders.*"), as `{path, op, version, actor, value[, old_value]}` with
+    /// values projected to `fields` and kinds limited to `ops` ("insert" / "update" /
+    /// "delete"). Returns the subscription id.
+    #[pyo3(signature = (sink, paths, fields=None, ops=None, include_old=false))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn subscribe_changes(&self, sink: &Bound<'_, PyAny>, paths: Vec<String>, fields: Option<Vec<String>>, ops: Option<Vec<String>>, include_old: bool) -> PyResult<u64> {
+        if !sink.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err("subscribe_changes() needs a callable sink(events)"));
+        }
+        self.changes.subscribe(sink.clone().unbind(), &paths, fields, ops, include_old)
+    }
+
+    /// [v3.6] Cancel a change subscription. Returns False if the id is unknown.
+    fn unsubscribe_changes(&self, id: u64) -> bool {
+        self.changes.unsubscribe(id)
+    }
+
+    /// [v3.6] `{id: {paths, fields, ops, include_old, events, errors}}` of change subscriptions.
+    fn change_subscriptions(&self, py: Python) -> PyResult<PyObject> {
+        self.changes.describe(py)
+    }
+
+    /// [v3.6] Register tenant `name` (idempotent) and return its handle; its paths are rooted
+    /// under `tenants.<name>` and other scopes may not write there (see tenancy.rs).
+    fn tenant(slf: Py<TheusEngine>, py: Python, name: String) -> PyResult<crate::tenancy::TenantHandle> {
+        let engine = slf.borrow(py);
+        engine.tenants.register(py, &name, &engine.state.bind(py).borrow())?;
+        drop(engine);
+        Ok(crate::tenancy::TenantHandle::new(slf, name))
+    }
+
+    /// [v3.6] Registered tenant names, sorted.
+    fn tenants(&self) -> Vec<String> {
+        self.tenants.names()
+    }
+
+    /// [v3.6] Lift Private-zone and tenancy restrictions on `paths` for `ttl_s` seconds, for
+    /// incidents (see `break_glass.rs`). `reason` is mandatory; the grant is audited and
+    /// announced on the outbox, and revoked automatically at expiry. Returns the grant id.
+    #[pyo3(signature = (paths, reason, ttl_s, actor=None))]
+    fn break_glass(&self, py: Python, paths: Vec<String>, reason: &str, ttl_s: f64, actor: Option<String>) -> PyResult<u64> {
+        self.sweep_break_glass(py)?;
+        let (id, notice) = self.glass.grant(py, paths, reason, ttl_s, actor)?;
+        self.announce(vec![notice]);
+        Ok(id)
+    }
+
+    /// [v3.6] End break-glass grant `id` early. Returns False if it was not active.
+    fn revoke_break_glass(&self, py: Python, id: u64) -> PyResult<bool> {
+        let ended = self.glass.revoke(py, id)?;
+        let revoked = !ended.is_empty();
+        self.announce(ended);
+        Ok(revoked)
+    }
+
+    /// [v3.6] Active break-glass grants: `[{id, paths, reason, actor, granted_at_ms, expires_at_ms}]`.
+    fn break_glass_grants(&self, py: Python) -> PyResult<PyObject> {
+        self.sweep_break_glass(py)?;
+        self.glass.describe(py)
+    }
+
+    /// [v3.6] `{tenant: {keys, bytes, commits, outbox, quota}}`, maintained per commit.
+    fn tenant_usage(&self, py: Python) -> PyResult<PyObject> {
+        self.tenants.usage(py)
+    }
+
+    /// [v3.6] Cap tenant `name`'s keys, bytes, commits or outbox messages (None = unlimited;
+    /// replaces the previous quota). Commits over a cap raise `TenantQuotaExceededError`.
+    #[pyo3(signature = (name, max_keys=None, max_bytes=None, max_commits=None, max_outbox=None))]
+    fn set_tenant_quota(&self, name: &str, max_keys: Option<u64>, max_bytes: Option<u64>, max_commits: Option<u64>, max_outbox: Option<u64>) -> PyResult<()> {
+        self.tenants.set_quota(name, crate::tenancy::Quota { max_keys, max_bytes, max_commits, max_outbox })
+    }
+
+    /// [v3.6] Keep the last `versions` committed states pinnable (0 = current only).
+    /// Pinned versions survive pruning.
+    fn set_version_retention(&self, py: Python, versions: usize) {
+        let mut history = self.history.relock();
+        history.set_retention(versions);
+        history.record(py, &self.state);
+    }
+
+    /// [v3.6] `with engine.group_commit(max_batch, window_ms) as group:` merges the commits
+    /// of transactions closed inside the block into shared versions (one per flush). See
+    /// `group.results` / `tx.commit_status` for per-transaction outcomes.
+    #[pyo3(signature = (max_batch=64, window_ms=None))]
+    fn group_commit(slf: Py<Self>, py: Python, max_batch: usize, window_ms: Option<u64>) -> PyResult<crate::group_commit::CommitGroup> {
+        let slot = slf.borrow(py).group.clone();
+        crate::group_commit::CommitGroup::new(slf, slot, max_batch, window_ms)
+    }
+
+    /// [v3.6] `{"retention", "retained": [versions], "pinned": {version: views}}`.
+    fn pinned_versions(&self, py: Python) -> PyResult<PyObject> {
+        self.history.relock().info(py)
+    }
+
+    #[getter]
+    fn transaction_limits(&self, py: Python) -> PyResult<PyObject> {
+        let limits = *self.tx_limits.relock();
+        let out = PyDict::new_bound(py);
+        out.set_item("max_deltas", limits.max_deltas)?;
+        out.set_item("max_paths", limits.max_paths)?;
+        Ok(out.into_any().unbind())
+    }
+
+    /// [v3.6] Engine-wide read-only mode: commits, CAS and proxy writes fail fast
+    /// with `MaintenanceModeError` carrying `reason` until `exit_maintenance()`.
+    fn enter_maintenance(&self, reason: String) {
+        *self.maintenance.relock() = Some(reason);
+    }
+
+    fn exit_maintenance(&self) {
+        *self.maintenance.relock() = None;
+    }
+
+    /// [v3.6] Current maintenance reason, or None when writable.
+    #[getter]
+    fn maintenance_reason(&self) -> Option<String> {
+        self.maintenance.relock().clone()
+    }
+
+    /// [v3.6] Ordered teardown: reject new transactions, wait up to `timeout_ms` for
+    /// open ones to exit, flush the outbox (and audit sink if it has `flush()`),
+    /// and return a report of anything abandoned.
+    #[pyo3(signature = (timeout_ms=5000))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn shutdown(slf: Py<TheusEngine>, py: Python, timeout_ms: u64) -> PyResult<PyObject> {
+        // NOTE: Borrow the engine only briefly: in-flight commits need `borrow_mut` to publish state.
+        slf.borrow(py).shutting_down.store(true, Ordering::SeqCst);
+
+        // 1. Drain in-flight transactions (release the GIL so their threads can finish).
+        let deadline = Instant::now() + std::time::Duration::from_millis(timeout_ms);
+        loop {
+            let drained = {
+                let engine = slf.borrow(py);
+                engine.sweep_leaks(py)?;
+                let empty = engine.open_txs.relock().is_empty();
+                empty
+            };
+            if drained || Instant::now() >= deadline {
+                break;
+            }
+            py.allow_threads(|| std::thread::sleep(std::time::Duration::from_millis(5)));
+        }
+        let abandoned = slf.borrow(py).open_transactions(py)?;
+
+        // 2. Flush outbox through the attached worker.
+        let has_worker = slf.borrow(py).worker.relock().is_some();
+        let outbox_error = if has_worker {
+            Self::process_outbox(slf.bind(py), py).err().map(|e| e.to_string())
+        } else {
+            None
+        };
+        let undelivered = slf.borrow(py).outbox.relock().len();
+
+        // 3. Flush audit sink.
+        let audit = slf.borrow(py).audit_system.relock().as_ref().map(|a| a.clone_ref(py));
+        let audit_error = match audit {
+            Some(a) if a.bind(py).hasattr("flush")? => a.call_method0(py, "flush").err().map(|e| e.to_string()),
+            _ => None,
+        };
+
+        let report = PyDict::new_bound(py);
+        report.set_item("clean", abandoned.is_empty() && undelivered == 0 && outbox_error.is_none() && audit_error.is_none())?;
+        report.set_item("abandoned_transactions", abandoned)?;
+        report.set_item("undelivered_messages", undelivered)?;
+        report.set_item("outbox_error", outbox_error)?;
+        report.set_item("audit_error", audit_error)?;
+        Ok(report.into_any().unbind())
+    }
+
+    #[getter]
+    fn is_shutdown(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// [v3.6] Self-check of the engine's internal bookkeeping (see fsck.rs): open transactions'
+    /// shadows, conflict stamps, outbox queue, audit ring and the Heavy / blob / spill registries.
+    /// Returns `{ok, version, checks, issues, repaired}`; `repair=True` fixes the issues marked
+    /// repairable.
+    #[pyo3(signature = (repair=false))]
+    fn fsck(&self, py: Python, repair: bool) -> PyResult<PyObject> {
+        let open: Vec<(u64, ShadowMaps)> = self.open_txs.relock().iter()
+            .filter(|(_, tx)| !tx.closed.load(Ordering::SeqCst))
+            .map(|(id, tx)| (*id, (tx.shadows.0.clone(), tx.shadows.1.clone())))
+            .collect();
+        let mut issues = Vec::new();
+        for (id, (cache, full_path_map)) in &open {
+            issues.extend(crate::fsck::shadows(py, *id, &cache.relock(), &full_path_map.relock()));
+        }
+        let version = {
+            let mut state = self.state.bind(py).try_borrow_mut()?;
+            issues.extend(crate::fsck::stamps(py, &mut state, repair, open.is_empty()));
+            issues.extend(self.heavy_store.fsck(py, state.heavy.values().map(std::convert::AsRef::as_ref), repair));
+            state.version
+        };
+        {
+            let mut queue = self.outbox.relock();
+            let metrics = self.outbox_metrics.relock();
+            issues.extend(crate::outbox::fsck(&mut queue, &mut self.processed_ids.relock(), &metrics.dead_letters, repair));
+        }
+        issues.extend(crate::audit::fsck_global(repair));
+        issues.extend(self.blobs.fsck(repair)?);
+        if let Some(spill) = self.spill.relock().clone() {
+            issues.extend(spill.fsck());
+        }
+        crate::fsck::report(py, version, &issues)
+    }
+
+    /// [v3.6] Recompute the Data zone checksum and compare it with the one recorded at
+    /// commit time. A mismatch means committed state was mutated outside a transaction
+    /// (e.g. through an escaped raw reference); it is logged as `INTEGRITY_VIOLATION`.
+    /// Checksums are only recorded while `set_escape_tracking(True)` is on.
+    #[pyo3(signature = (raise_on_mismatch=false))]
+    fn verify_integrity(&self, py: Python, raise_on_mismatch: bool) -> PyResult<PyObject> {
+        let state = self.state.bind(py).borrow();
+        if !state.integrity {
+            return Err(ContextError::new_err("verify_integrity() needs set_escape_tracking(True): checksums are only recorded in that debug mode"));
+        }
+        let actual = state.recompute_data_hashes(py)?;
+        let mut changed: Vec<&String> = actual.iter()
+            .filter(|(k, h)| state.data_hashes.get(*k) != Some(*h))
+            .map(|(k, _)| k)
+            .collect();
+        changed.sort();
+
+        let ok = changed.is_empty();
+        let changed_paths = self.escaped_paths(py, &state, &changed)?;
+        if !ok {
+            let mut msg = format!("State v{} Data zone changed outside a transaction: {changed:?}", state.version);
+            if !changed_paths.is_empty() {
+                let paths: Vec<String> = changed_paths.iter().map(|(p, kind)| format!("{p} ({kind})")).collect();
+                msg.push_str("; paths: ");
+                msg.push_str(&paths.join(", "));
+            }
+            crate::audit::log_global("INTEGRITY_VIOLATION", &msg);
+            if raise_on_mismatch {
+                return Err(IntegrityError::new_err(msg));
+            }
+        }
+
+        let report = PyDict::new_bound(py);
+        report.set_item("ok", ok)?;
+        report.set_item("version", state.version)?;
+        report.set_item("expected", crate::integrity::combine(&state.data_hashes))?;
+        report.set_item("actual", crate::integrity::combine(&actual))?;
+        report.set_item("changed_keys", changed)?;
+        let paths = PyList::empty_bound(py);
+        for (path, kind) in changed_paths {
+            let entry = PyDict::new_bound(py);
+            entry.set_item("path", path)?;
+            entry.set_item("kind", kind)?;
+            paths.append(entry)?;
+        }
+        report.set_item("changed_paths", paths)?;
+        Ok(report.into_any().unbind())
+    }
+
+    /// [v3.6] Evaluate an untrusted rule expression against a read-only copy of the Data
+    /// subtree at `path` ("" = whole zone). Runs in the Rust sandbox (`crate::rules`) with
+    /// step/time/memory budgets; exceeding one raises `RuleLimitError`, bad rules `RuleError`.
+    #[pyo3(signature = (path, rule, timeout_ms=50, max_steps=100_000, max_memory=1_048_576))]
+    fn eval_rule(&self, py: Python, path: &str, rule: &str, timeout_ms: u64, max_steps: u64, max_memory: usize) -> PyResult<PyObject> {
+        let root = {
+            let state = self.state.bind(py).borrow();
+            let mut segments = path.split('.').filter(|s| !s.is_empty());
+            match segments.next() {
+                None => {
+                    let zone = PyDict::new_bound(py);
+                    for (k, v) in &state.data {
+                        zone.set_item(k, v.bind(py))?;
+                    }
+                    zone.into_any()
+                }
+                Some(key) => {
+                    let mut node = state.data.get(key)
+                        .ok_or_else(|| crate::rules::RuleError::new_err(format!("Path '{path}' not found")))?
+                        .bind(py).clone();
+                    for seg in segments {
+                        node = match seg.parse::<usize>() {
+                            Ok(i) if node.is_instance_of::<PyList>() => node.get_item(i),
+                            _ => node.get_item(seg),
+                        }.map_err(|_| crate::rules::RuleError::new_err(format!("Path '{path}' not found")))?;
+                    }
+                    node
+                }
+            }
+        };
+
+        let value = crate::rules::Value::from_py(&root, max_memory)?;
+        let limits = crate::rules::RuleLimits {
+            max_steps,
+            timeout: std::time::Duration::from_millis(timeout_ms),
+            max_memory,
+        };
+        let rule = rule.to_string();
+        // Pure Rust from here on: release the GIL while the rule runs.
+        py.allow_threads(|| crate::rules::evaluate(&rule, &value, &limits))?.into_py(py)
+    }
+
+    /// [v3.6] Serialize the committed Data + Heavy zones (plus version, key versions, committed op ids and pending timers) to
+    /// msgpack bytes for cross-process transfer. numpy arrays travel as raw buffers, and
+    /// shared-memory arrays as their segment name (zero-copy). Never falls back to pickle.
+    fn dumps_state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let state = self.state.bind(py).borrow();
+        let timers = self.timers.relock().export(py)?;
+        let blob = crate::state_codec::encode_state(py, &state, &self.op_ids.relock().entries(), &timers)?;
+        *self.snapshot_base.relock() = Some((state.version, crate::snapshots::digest(&blob)));
+        Ok(PyBytes::new_bound(py, &blob))
+    }
+
+    /// [v3.6] Serialize only what changed since the previous snapshot (`dumps_state()` or an
+    /// earlier `dumps_incremental()`): changed fields, key lists, op ids and timers. Chains
+    /// are replayed with `load_snapshot(full, *incrementals)`.
+    fn dumps_incremental<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let Some((base_version, base_digest)) = self.snapshot_base.relock().clone() else {
+            return Err(pyo3::exceptions::PyValueError::new_err("No base snapshot: take a full one with dumps_state() first"));
+        };
+        let state = self.state.bind(py).borrow();
+        let timers = self.timers.relock().export(py)?;
+        let blob = crate::snapshots::encode_incremental(py, &state, base_version, &base_digest, &self.op_ids.relock().entries(), &timers)?;
+        *self.snapshot_base.relock() = Some((state.version, crate::snapshots::digest(&blob)));
+        Ok(PyBytes::new_bound(py, &blob))
+    }
+
+    /// [v3.6] Replace the committed state with one produced by `dumps_state()`. The signal
+    /// hub and meta log of this engine are kept; version and key versions come from the blob.
+    fn load_state(&mut self, py: Python, blob: &[u8]) -> PyResult<()> {
+        self.ensure_writable()?;
+        let decoded = crate::state_codec::decode_state(py, blob)?;
+        self.install_decoded(py, decoded)?;
+        *self.snapshot_base.relock() = Some((self.state.borrow(py).version, crate::snapshots::digest(blob)));
+        Ok(())
+    }
+
+    /// [v3.6] Replace the committed state with a full snapshot plus the incrementals taken
+    /// after it, in order. Each link's base version and digest are checked; on any mismatch
+    /// nothing is loaded.
+    #[pyo3(signature = (full, *incrementals))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn load_snapshot(&mut self, py: Python, full: &[u8], incrementals: Vec<Vec<u8>>) -> PyResult<()> {
+        self.ensure_writable()?;
+        let mut decoded = crate::state_codec::decode_state(py, full)?;
+        let mut digest = crate::snapshots::digest(full);
+        for blob in &incrementals {
+            crate::snapshots::apply_incremental(py, &mut decoded, &digest, blob)?;
+            digest = crate::snapshots::digest(blob);
+        }
+        self.install_decoded(py, decoded)?;
+        *self.snapshot_base.relock() = Some((self.state.borrow(py).version, digest));
+        Ok(())
+    }
+
+    /// [v3.6] Committed state in the stable, versioned snapshot container (see
+    /// `snapshot_format.rs`): header with config digest, schema fingerprint and zone policies,
+    /// the state payload, the embedded JSON schema, and a SHA-256 trailer.
+    fn export_snapshot<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let profile = self.snapshot_profile(py)?;
+        let state = self.state.bind(py).borrow();
+        let timers = self.timers.relock().export(py)?;
+        let payload = crate::state_codec::encode_state(py, &state, &self.op_ids.relock().entries(), &timers)?;
+        let blob = crate::snapshot_format::encode(py, &profile, state.version, &payload)?;
+        Ok(PyBytes::new_bound(py, &blob))
+    }
+
+    /// [v3.6] Replace the committed state with an `export_snapshot()` container. Format errors
+    /// raise `SnapshotFormatError`; with `strict` (default) a different schema or conflicting zone
+    /// policies raise `SnapshotIncompatibleError`. Nothing is loaded on error.
+    #[pyo3(signature = (blob, strict=true))]
+    fn import_snapshot(&mut self, py: Python, blob: &[u8], strict: bool) -> PyResult<()> {
+        self.ensure_writable()?;
+        let container = crate::snapshot_format::decode(py, blob)?;
+        if strict {
+            crate::snapshot_format::check_compatible(py, &container, &self.snapshot_profile(py)?)?;
+        }
+        let decoded = crate::state_codec::decode_state(py, container.sections["state"])?;
+        self.install_decoded(py, decoded)?;
+        *self.snapshot_base.relock() = None;
+        Ok(())
+    }
+
+    /// [v3.6] Validate an `export_snapshot()` container and return its header, plus the
+    /// embedded JSON schema under "schema" (None without one).
+    #[staticmethod]
+    fn inspect_snapshot<'py>(py: Python<'py>, blob: &[u8]) -> PyResult<Bound<'py, PyDict>> {
+        let container = crate::snapshot_format::decode(py, blob)?;
+        let schema = container.sections.get("schema").map(|s| String::from_utf8_lossy(s).into_owned());
+        container.header.set_item("schema", schema)?;
+        Ok(container.header)
+    }
+
+    /// [v3.6] Build a fresh engine whose state is loaded from `dumps_state()` bytes.
+    #[staticmethod]
+    fn loads_state(py: Python, blob: &[u8]) -> PyResult<Self> {
+        let mut engine = Self::new(py)?;
+        engine.load_state(py, blob)?;
+        Ok(engine)
+    }
+
+    /// [v3.6] Serve flattened Data-zone state and version metadata to other processes/hosts
+    /// over TCP (length-prefixed msgpack, see `state_server`). Every request must carry one of
+    /// `tokens`; Private-zone paths are always redacted, `redact_zones` adds more (e.g. `["log"]`).
+    #[cfg(feature = "state-server")]
+    #[pyo3(signature = (tokens, host="127.0.0.1", port=0, redact_zones=None))]
+    fn serve_state(slf: Py<TheusEngine>, py: Python, tokens: Vec<String>, host: &str, port: u16, redact_zones: Option<Vec<String>>) -> PyResult<crate::state_server::StateServer> {
+        crate::state_server::StateServer::start(py, slf, host, port, tokens, redact_zones)
+    }
+
+    /// [v3.6] Reproducible runs: `seed` makes this engine's backoff jitter, fault
+    /// probabilities and generated outbox keys come from its own seeded RNG; `None` restores
+    /// real randomness. Other engines are unaffected. Timestamps follow the process-wide
+    /// clock (see `use_global_logical_clock`).
+    #[pyo3(signature = (seed=None))]
+    fn set_deterministic(&self, seed: Option<u64>) {
+        self.determinism.set_seed(seed);
+    }
+
+    #[getter]
+    fn deterministic_seed(&self) -> Option<u64> {
+        self.determinism.seed()
+    }
+
+    /// [v3.6] Commit `{key: value}` to the Signal zone after `delay_ms`, at `at_ms` (engine
+    /// wall time) or every `every_ms`. Returns the timer id (`timer_id` replaces a timer).
+    #[pyo3(signature = (key, value=None, delay_ms=0, at_ms=None, every_ms=None, timer_id=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn schedule_signal(&self, py: Python, key: String, value: Option<PyObject>, delay_ms: u64, at_ms: Option<u64>, every_ms: Option<u64>, timer_id: Option<String>) -> PyResult<String> {
+        let value = value.unwrap_or_else(|| true.into_py(py));
+        self.timers.relock().schedule(timer_id, crate::timers::Action::Signal { key, value }, delay_ms, at_ms, every_ms)
+    }
+
+    /// [v3.6] Run the registered process `process` (with `kwargs`) on the same schedules as
+    /// `schedule_signal`.
+    #[pyo3(signature = (process, delay_ms=0, at_ms=None, every_ms=None, timer_id=None, kwargs=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn schedule_process(&self, process: String, delay_ms: u64, at_ms: Option<u64>, every_ms: Option<u64>, timer_id: Option<String>, kwargs: Option<Py<PyDict>>) -> PyResult<String> {
+        self.timers.relock().schedule(timer_id, crate::timers::Action::Process { name: process, kwargs }, delay_ms, at_ms, every_ms)
+    }
+
+    /// [v3.6] Drop a pending timer. Returns False if there was none.
+    fn cancel_timer(&self, timer_id: &str) -> bool {
+        self.timers.relock().cancel(timer_id)
+    }
+
+    /// [v3.6] Pending timers: `[{id, kind, target, payload, fire_at_ms, every_ms, fired}]`.
+    fn timers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        self.timers.relock().export(py)
+    }
+
+    /// [v3.6] Claim the timers due at `now_ms` (default: engine clock) for firing:
+    /// `[(id, kind, target, payload)]`. One-shot timers are removed, recurring ones advanced.
+    #[pyo3(signature = (now_ms=None))]
+    fn take_due_timers(&self, py: Python, now_ms: Option<u64>) -> Vec<(String, &'static str, String, PyObject)> {
+        self.timers.relock().take_due(py, now_ms.unwrap_or_else(crate::clock::now_ms))
+    }
+
+    /// [v3.6] Chaos testing: make injection `point` ("commit", "cas", "schema", "shadow",
+    /// "outbox", "panic") fail with `probability`, at most `times` times. Failures raise the real
+    /// exception type for that point, so retry / rollback paths are exercised as-is; "panic"
+    /// panics inside the commit's critical section.
+    #[pyo3(signature = (point, probability=1.0, times=None, message=None))]
+    fn inject_fault(&self, point: &str, probability: f64, times: Option<u32>, message: Option<String>) -> PyResult<()> {
+        self.faults.inject(point, probability, times, message)
+    }
+
+    /// [v3.6] Disarm one injection point, or all of them.
+    #[pyo3(signature = (point=None))]
+    fn clear_faults(&self, point: Option<&str>) {
+        self.faults.clear(point);
+    }
+
+    /// [v3.6] `{point: {probability, remaining, fired}}` for armed faults.
+    fn fault_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.faults.stats(py)
+    }
+
+    /// [v3.6] Record the ordered operations of every transaction into `path` until
+    /// `stop_recording()`; re-run them with `theus_core.replay_recording(engine, path)`.
+    fn start_recording(&self, py: Python, path: &str) -> PyResult<()> {
+        let mut recorder = self.recorder.relock();
+        if let Some(active) = recorder.as_ref() {
+            return Err(ContextError::new_err(format!("Already recording to '{}'", active.path())));
+        }
+        *recorder = Some(Arc::new(crate::recorder::Recorder::create(py, path)?));
+        Ok(())
+    }
+
+    /// [v3.6] Close the recording: `{path, records}`, or None if not recording.
+    /// NOTE: Transactions opened before this call keep writing until the file is closed;
+    /// their ops after that point are dropped (replay reports them as unfinished).
+    fn stop_recording(&self, py: Python) -> PyResult<Option<PyObject>> {
+        let Some(recorder) = self.recorder.relock().take() else { return Ok(None) };
+        let records = recorder.finish()?;
+        let out = PyDict::new_bound(py);
+        out.set_item("path", recorder.path())?;
+        out.set_item("records", records)?;
+        Ok(Some(out.into_any().unbind()))
+    }
+
+    /// [v3.6] Path of the active recording, if any.
+    #[getter]
+    fn recording(&self) -> Option<String> {
+        self.recorder.relock().as_ref().map(|r| r.path().to_string())
+    }
+
+    /// [v3.6] Paths whose writes need a second approver (two-person rule). Commits of
+    /// non-admin transactions park such writes as proposals; see `proposals()`.
+    /// Replaces the previous list; an empty list disables the rule.
+    fn require_approval(&self, paths: Vec<String>) -> PyResult<()> {
+        self.approvals.set_paths(paths)
+    }
+
+    #[getter]
+    fn approval_paths(&self) -> Vec<String> {
+        self.approvals.paths()
+    }
+
+    /// [v3.6] Give `approver` the GRANT capability (may approve/reject proposals).
+    fn grant_approver(&self, approver: String) {
+        self.approvals.grant(approver);
+    }
+
+    fn revoke_approver(&self, approver: &str) -> bool {
+        self.approvals.revoke(approver)
+    }
+
+    #[getter]
+    fn approvers(&self) -> Vec<String> {
+        self.approvals.approvers()
+    }
+
+    /// [v3.6] Pending proposals: `[{id, tx, proposer, writes: {path: value}, created_ms}]`.
+    fn proposals(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.approvals.proposals(py)
+    }
+
+    /// [v3.6] Commit proposal `id` on behalf of `approver` (a GRANT holder other than the
+    /// proposer). Returns the new state version.
+    fn approve(&mut self, py: Python, id: u64, approver: &str) -> PyResult<u64> {
+        let data = self.approvals.approved_data(py, id, approver)?;
+        let version = self.state.bind(py).borrow().version;
+        self.compare_and_swap(py, version, Some(data.into_any()), None, None, Some(approver.to_string()))?;
+        let version = self.state.bind(py).borrow().version;
+        self.approvals.applied(id, approver, version);
+        Ok(version)
+    }
+
+    /// [v3.6] Drop proposal `id` (same GRANT / two-person checks as `approve`).
+    #[pyo3(signature = (id, approver, reason=None))]
+    fn reject(&self, id: u64, approver: &str, reason: Option<&str>) -> PyResult<()> {
+        self.approvals.reject(id, approver, reason)
+    }
+
+    /// [v3.6] Recent writers of `path` (or of paths above / below it), newest first:
+    /// `[{version, tx, actor, tags, paths, ts_ms}]`. `tx` is None for `compare_and_swap`
+    /// writes, whose actor is the `requester`. `tags` keeps only commits carrying those tags.
+    #[pyo3(signature = (path, depth=10, tags=None))]
+    fn blame(&self, py: Python, path: &str, depth: usize, tags: Option<&Bound<'_, PyDict>>) -> PyResult<Vec<PyObject>> {
+        self.lineage.blame(py, path, depth, crate::tags::from_py(tags)?.as_ref())
+    }
+
+    /// [v3.6] Number of commits kept for `blame()` (default 1000; 0 disables the log).
+    fn set_lineage_retention(&self, commits: usize) {
+        self.lineage.set_retention(commits);
+    }
+
+    /// [v3.6] Register a Heavy value under reference counting and return its handle.
+    /// `nbytes` defaults to `value.nbytes` (or `sys.getsizeof`) and counts against the
+    /// heavy quota; `on_release(value)` runs once no handle or open transaction holds it.
+    #[pyo3(signature = (name, value, nbytes=None, on_release=None))]
+    fn alloc_heavy(&self, py: Python, name: String, value: PyObject, nbytes: Option<usize>, on_release: Option<PyObject>) -> PyResult<crate::heavy_store::HeavyHandle> {
+        self.heavy_store.alloc(py, name, value, nbytes, on_release)
+    }
+
+    /// [v3.6] Byte budget for `alloc_heavy` (None = unlimited). Existing handles are kept.
+    #[pyo3(signature = (quota_bytes=None))]
+    fn set_heavy_quota(&self, quota_bytes: Option<usize>) {
+        self.heavy_store.set_quota(quota_bytes);
+    }
+
+    /// [v3.6] `{used_bytes, quota_bytes, handles}` of the managed Heavy store.
+    fn heavy_usage(&self, py: Python) -> PyResult<PyObject> {
+        self.heavy_store.usage(py)
+    }
+
+    /// [v3.6] Content-addressable blob store: `engine.blobs.put(data)` returns an id to keep
+    /// in state instead of the bytes. Inside processes use `ctx.blobs` (protects the blob from
+    /// `gc_blobs` until the transaction closes).
+    #[getter]
+    fn blobs(&self) -> crate::blobs::BlobHandle {
+        crate::blobs::BlobHandle::new(self.blobs.clone(), None)
+    }
+
+    /// [v3.6] Keep blobs in `path` (created if missing, kept after the engine) instead of a
+    /// temp dir; blob files already there are adopted. Call before the first put.
+    fn set_blob_dir(&self, path: &str) -> PyResult<()> {
+        self.blobs.set_dir(path)
+    }
+
+    /// [v3.6] Delete blobs no retained state version references (see `set_version_retention`
+    /// and `pin`); blobs put by still-open transactions are kept. Returns the number deleted.
+    fn gc_blobs(&self, py: Python) -> PyResult<usize> {
+        let mut states = vec![self.committed_state(py)];
+        states.extend(self.history.relock().states(py));
+        let open: std::collections::HashSet<u64> = self.open_txs.relock().iter()
+            .filter(|(_, tx)| !tx.closed.load(Ordering::SeqCst)).map(|(id, _)| *id).collect();
+        self.blobs.gc(py, &states, &open)
+    }
+
+    /// [v3.6] `{dir, blobs, bytes, referenced, reclaimed}` of the blob store.
+    fn blob_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.blobs.stats(py)
+    }
+
+    /// [v3.6] Cache `func(state)` at `path` (a name for `computed()`, not a state path).
+    /// It is recomputed when a commit touches one of `deps` (every commit without deps);
+    /// concurrent readers of a stale value share one computation. Re-defining replaces it.
+    #[pyo3(signature = (path, func, deps=None))]
+    fn define_computed(&self, path: &str, func: &Bound<'_, PyAny>, deps: Option<Vec<String>>) -> PyResult<()> {
+        if !func.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err("define_computed() needs a callable func(state)"));
+        }
+        let deps = deps.unwrap_or_default().iter().map(|d| Transaction::normalize_path(d)).collect();
+        self.computed.define(path, func.clone().unbind(), deps);
+        Ok(())
+    }
+
+    /// [v3.6] The value of the computed `path` for the committed state (read-through).
+    fn computed(&self, py: Python, path: &str) -> PyResult<PyObject> {
+        let state = self.committed_state(py);
+        self.computed.get(py, path, state.bind(py))
+    }
+
+    /// [v3.6] Drop the cached value of `path` (all paths if None); the next read recomputes.
+    #[pyo3(signature = (path=None))]
+    fn invalidate_computed(&self, path: Option<&str>) {
+        self.computed.invalidate(path);
+    }
+
+    /// [v3.6] Forget the computed `path`. Returns False if it was not defined.
+    fn remove_computed(&self, path: &str) -> bool {
+        self.computed.remove(path)
+    }
+
+    /// [v3.6] `{path: {hits, misses, waits, errors, cached_version}}` of computed values.
+    fn computed_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.computed.stats(py)
+    }
+
+    /// [v3.6] Keep the values at `prefixes` (`zone.field` paths) in a memory-mapped file
+    /// instead of RAM; reads through proxies fault them back in. `path` defaults to a temp
+    /// file removed with the engine; `cache_bytes` bounds the in-memory LRU of hot records.
+    /// Values already committed at those paths are spilled immediately.
+    #[pyo3(signature = (prefixes, path=None, cache_bytes=64 * 1024 * 1024))]
+    fn spill_to_disk(&self, py: Python, prefixes: Vec<String>, path: Option<String>, cache_bytes: usize) -> PyResult<()> {
+        let store = crate::spill::SpillStore::open(prefixes, path, cache_bytes)?;
+        store.apply(py, self.state.bind(py).as_any())?;
+        *self.spill.relock() = Some(store);
+        Ok(())
+    }
+
+    /// [v3.6] `{path, records, file_bytes, spilled_bytes, cached_bytes, cache_bytes, hits,
+    /// misses}` of the spill store, or None when spilling is off.
+    fn spill_stats(&self, py: Python) -> PyResult<Option<PyObject>> {
+        self.spill_store().map(|s| s.stats(py)).transpose()
+    }
+
+    /// [v3.6] Keep the value at `prefix` (`zone.field` path) compressed in the State with
+    /// `codec` ("zstd" or "lz4"; `level` applies to zstd); reads through proxies decompress it
+    /// lazily. `codec=None` removes the policy. A value already committed there is compressed
+    /// immediately.
+    #[pyo3(signature = (prefix, codec=Some("zstd"), level=None))]
+    fn set_compression(&self, py: Python, prefix: &str, codec: Option<&str>, level: Option<i32>) -> PyResult<()> {
+        self.compression.set_rule(prefix, codec, level)?;
+        self.compression.apply(py, self.state.bind(py).as_any())
+    }
+
+    /// [v3.6] Size of the LRU of decompressed payloads shared by all compressed paths.
+    fn set_compression_cache(&self, cache_bytes: usize) {
+        self.compression.set_cache_bytes(cache_bytes);
+    }
+
+    /// [v3.6] `{paths: {prefix: {codec, values, raw_bytes, stored_bytes, saved_bytes}},
+    /// raw_bytes, stored_bytes, saved_bytes, reads, hits, misses, hit_rate, cached_bytes,
+    /// cache_bytes}` of compressed storage.
+    fn compression_stats(&self, py: Python) -> PyResult<PyObject> {
+        self.compression.stats(py)
+    }
+
+    /// [v3.6] Streaming commit: transactions that logged at least `min_deltas` deltas apply
+    /// them straight onto copy-on-write copies of the touched zones instead of first building
+    /// a pending dict (lower peak memory for giant transactions). None disables. Transactions
+    /// are never streamed while approval paths are configured.
+    /// NOTE: `engine.execute()` hands its transaction an explicit pending dict (contract checks
+    /// need it), which takes precedence over deltas - streaming pays off for direct
+    /// `engine.transaction()` blocks.
+    #[pyo3(signature = (min_deltas=None))]
+    fn set_streaming_commit(&self, min_deltas: Option<usize>) {
+        *self.streaming_commit.relock() = min_deltas;
+    }
+
+    /// [v3.6] Shadow copies of `type_` values (a type, `"module.QualName"` or bare
+    /// `"QualName"`; subclasses included) are made with `copier(value)` instead of
+    /// `copy.deepcopy`. `copier=None` removes the entry. A failing copier still fails the
+    /// shadow copy.
+    #[pyo3(signature = (type_, copier=None))]
+    fn set_copier(&self, type_: &Bound<'_, PyAny>, copier: Option<PyObject>) -> PyResult<()> {
+        self.copiers.set(type_, copier)
+    }
+
+    /// [v3.6] Type names with a registered copier.
+    fn copiers(&self) -> Vec<String> {
+        self.copiers.names()
+    }
+
+    /// [v3.6] Signed token granting `inputs`/`outputs` (capped by `caps`) for `ttl_s`
+    /// seconds; workers rebuild the guard with `theus_core.guard_from_token(target, token, key)`.
+    #[pyo3(signature = (inputs, outputs, caps=crate::zones::CAP_ALL, ttl_s=300.0, strict_guards=false))]
+    fn issue_capability_token(&self, inputs: Vec<String>, outputs: Vec<String>, caps: u8, ttl_s: f64, strict_guards: bool) -> PyResult<String> {
+        let expires_at_ms = crate::cap_tokens::expiry(ttl_s)?;
+        self.capability_key.issue(&crate::cap_tokens::Grant {
+            inputs, outputs, caps: caps & crate::zones::CAP_ALL, strict: strict_guards, private: Vec::new(), expires_at_ms,
+        })
+    }
+
+    /// [v3.6] Rights of a token signed by this engine; `PermissionError` if forged or expired.
+    fn verify_capability_token(&self, py: Python, token: &str) -> PyResult<PyObject> {
+        crate::cap_tokens::grant_to_dict(py, &crate::cap_tokens::verify(&self.capability_key.get(), token)?)
+    }
+
+    /// [v3.6] Token signing key (hand it to workers over a trusted channel).
+    fn capability_key<'py>(&self, py: Python<'py>) -> Bound<'py, pyo3::types::PyBytes> {
+        pyo3::types::PyBytes::new_bound(py, &self.capability_key.get())
+    }
+
+    /// [v3.6] Share a signing key across engines (at least 16 bytes). Outstanding tokens
+    /// signed with the previous key stop verifying.
+    fn set_capability_key(&self, key: Vec<u8>) -> PyResult<()> {
+        self.capability_key.set(key)
+    }
+
+    /// [v3.6] I/O enforcement for PURE processes: `mode` "record" logs file / network /
+    /// subprocess calls made inside `pure_scope`, "reject" also fails them with
+    /// `PermissionError`, "off" (default) disables the check. `allow` lists categories
+    /// (`"file_read"`, `"file_write"`, "network", "subprocess") that stay permitted.
+    #[pyo3(signature = (mode=None, allow=None))]
+    fn set_pure_io_policy(&self, py: Python, mode: Option<&str>, allow: Option<Vec<String>>) -> PyResult<()> {
+        self.pure_io.set(py, mode, allow)
+    }
+
+    /// [v3.6] `(mode, allow)` set by `set_pure_io_policy`.
+    #[getter]
+    fn pure_io_policy(&self) -> (&'static str, Vec<String>) {
+        self.pure_io.get()
+    }
+
+    /// [v3.6] Context manager wrapping a process call (`process` names it in records).
+    /// With `effects` (the contract's effect budget, `contract` its reference) every effect
+    /// class outside the budget is rejected; otherwise the PURE I/O policy applies.
+    #[pyo3(signature = (process, effects=None, contract=None))]
+    fn pure_scope(&self, py: Python, process: String, effects: Option<Vec<String>>, contract: Option<String>) -> PyResult<crate::pure_io::PureScope> {
+        self.pure_io.scope(py, process, effects, contract)
+    }
+
+    /// [v3.6] I/O seen inside PURE processes: `[{process, category, event, detail,
+    /// rejected, ts_ms}]`, oldest first (the last 1000 are kept).
+    #[pyo3(signature = (clear=false))]
+    fn pure_io_violations(&self, py: Python, clear: bool) -> PyResult<Vec<PyObject>> {
+        self.pure_io.violations(py, clear)
+    }
+
+    /// [v3.6] Dead-man switch: record progress for the running `process` (called from the
+    /// process itself). Returns how many of its runs were refreshed.
+    fn heartbeat(&self, process: &str) -> usize {
+        self.heartbeats.beat(process)
+    }
+
+    /// [v3.6] Expected time between heartbeats (or since start) for `process`, or for every
+    /// process when `process=None`. Runs silent for longer are reported as stuck;
+    /// `expected_ms=None` removes the expectation.
+    #[pyo3(signature = (expected_ms, process=None))]
+    fn set_expected_duration(&self, expected_ms: Option<u64>, process: Option<String>) {
+        self.heartbeats.set_expected(process, expected_ms);
+    }
+
+    /// [v3.6] `action="abort"` cancels the transaction of stuck runs (checked every
+    /// `check_interval_ms` by a background timer and on `health()`) and releases the
+    /// conflict priority slot they hold; "report" (default) only reports them.
+    #[pyo3(signature = (action, check_interval_ms=None))]
+    fn set_stuck_policy(&self, py: Python, action: &str, check_interval_ms: Option<u64>) -> PyResult<()> {
+        self.heartbeats.set_policy(py, action, check_interval_ms)
+    }
+
+    /// [v3.6] Trace the next run of `process`: every guard / proxy read and write it makes
+    /// (path, allowed / denied, duration) is recorded, capped at `max_events`. Fetch the result
+    /// with `trace_report(process)` once the run has finished.
+    #[pyo3(signature = (process, max_events=crate::trace::DEFAULT_MAX_EVENTS))]
+    fn trace(&self, process: String, max_events: usize) {
+        self.traces.relock().arm(process, max_events);
+    }
+
+    /// [v3.6] Report of the last traced run of `process`: `{process, tx_id, started_ms,
+    /// duration_ms, status, error, reads, writes, denied, dropped, events: [{path, access,
+    /// allowed, rule, at_us, duration_us}]}`, or None if it has not been traced.
+    fn trace_report(&self, py: Python, process: &str) -> Option<PyObject> {
+        self.traces.relock().report(py, process)
+    }
+
+    /// [v3.6] Processes armed by `trace()` whose next run has not started yet.
+    fn pending_traces(&self) -> Vec<String> {
+        self.traces.relock().pending()
+    }
+
+    /// [v3.6] Record the paths every run reads and writes, keeping the last `window` runs per
+    /// process (`window=0` stops tracking and forgets them). See `observed_access()`.
+    #[pyo3(signature = (window=100))]
+    fn track_contract_drift(&self, window: usize) {
+        self.traces.relock().set_window(window);
+    }
+
+    /// [v3.6] `{runs, window, reads, writes}`: the paths `process` was allowed to read / write
+    /// over its last `runs` (at most `window`) tracked runs.
+    fn observed_access<'py>(&self, py: Python<'py>, process: &str) -> PyResult<Bound<'py, PyDict>> {
+        let traces = self.traces.relock();
+        let (runs, reads, writes) = traces.observed(process);
+        let out = PyDict::new_bound(py);
+        out.set_item("runs", runs)?;
+        out.set_item("window", traces.window())?;
+        out.set_item("reads", reads)?;
+        out.set_item("writes", writes)?;
+        Ok(out)
+    }
+
+    /// [v3.6] Process health: `{status: "ok"|"degraded", running: [{run_id, process, tx_id,
+    /// elapsed_ms, since_heartbeat_ms, heartbeats, expected_ms, stuck, aborted}], stuck,
+    /// aborted_total, policy}`.
+    fn health(&self, py: Python) -> PyResult<PyObject> {
+        self.heartbeats.health(py)
+    }
+
+    /// [v3.6] Cancels every in-flight run of `target` (a process name) or the given
+    /// `CancellationToken`; returns how many runs were newly cancelled.
+    #[pyo3(signature = (target, reason=None))]
+    fn cancel(&self, py: Python, target: &Bound<'_, PyAny>, reason: Option<String>) -> PyResult<usize> {
+        if let Ok(token) = target.downcast::<crate::cancellation::CancellationToken>() {
+            return Ok(usize::from(token.get().cancel(py, reason)?));
+        }
+        let process: String = target.extract().map_err(|_| pyo3::exceptions::PyTypeError::new_err(
+            "cancel() expects a process name or a CancellationToken"
+        ))?;
+        self.tokens.cancel_process(py, &process, reason.as_deref())
+    }
+
+    /// [v3.6] Debug mode for escaped references: every commit records the content hash of
+    /// the Data keys it touched and every committed container is tagged (path + identity +
+    /// content hash), so `verify_integrity()` can report *which* path was mutated, replaced,
+    /// added or removed outside a transaction. Costly; off by default.
+    fn set_escape_tracking(&self, py: Python, enabled: bool) -> PyResult<()> {
+        self.state.bind(py).borrow_mut().set_integrity(py, enabled)?;
+        *self.escape_tracking.relock() = enabled.then(EscapeRegistry::default);
+        if enabled {
+            self.tag_committed_state(py)?;
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Leak detection: transactions still open after `threshold_ms` are reported once
+    /// to the audit log (key `TX_LEAK`) and to `on_leak(info)` if given. `capture_stack`
+    /// records the creation stack (costly; meant for debugging). `threshold_ms=None` disables.
+    #[pyo3(signature = (threshold_ms=None, capture_stack=false, on_leak=None))]
+    fn set_leak_detection(&self, threshold_ms: Option<u64>, capture_stack: bool, on_leak: Option<PyObject>) {
+        *self.leak_policy.relock() = LeakPolicy { threshold_ms, capture_stack, on_leak };
+    }
+
+    /// [v3.6] Commits taking longer than `threshold_ms` are reported with their `tx.stats()`
+    /// breakdown (plus `tx_id`, `threshold_ms`, `tags`): to the audit log (key `SLOW_COMMIT`)
+    /// and to `callback(info)`, or else as a warning on the `theus.engine` logger (breakdown
+    /// in the record's `tx_stats` attribute). `threshold_ms=None` disables.
+    #[pyo3(signature = (threshold_ms=None, callback=None))]
+    fn set_slow_commit_threshold(&self, threshold_ms: Option<f64>, callback: Option<PyObject>) {
+        *self.slow_commit.relock() = threshold_ms.map(|threshold_ms| SlowCommitPolicy { threshold_ms, callback });
+    }
+
+    /// [v3.6] Transactions created on this engine that have not exited yet (oldest first).
+    fn open_transactions(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.sweep_leaks(py)?;
+        let open = self.open_txs.relock();
+        let mut entries: Vec<(&u64, &OpenTx)> = open.iter().collect();
+        entries.sort_by_key(|(id, _)| **id);
+        entries.into_iter().map(|(id, tx)| Self::open_tx_info(py, *id, tx)).collect()
+    }
+
+    /// [v3.6] Uncommitted writes overlapping `path` from open transactions opened with
+    /// `provisional=True`: `[{tx_id, actor, path, op, value, provisional}]`, oldest
+    /// transaction first. Values are copies; committed state and isolation are unaffected.
+    fn peek_pending<'py>(&self, py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyList>> {
+        let target = crate::provisional::segments(path);
+        let out = PyList::empty_bound(py);
+        // NOTE: Copying values runs Python code, so the registry lock is released first.
+        let mut shared: Vec<(u64, crate::provisional::Provisional)> = self.open_txs.relock().iter()
+            .filter(|(_, tx)| !tx.closed.load(Ordering::SeqCst))
+            .filter_map(|(id, tx)| tx.provisional.as_ref().map(|p| (*id, p.clone_ref(py))))
+            .collect();
+        shared.sort_by_key(|(id, _)| *id);
+        for (id, pending) in &shared {
+            pending.collect(py, *id, &target, &out)?;
+        }
+        Ok(out)
+    }
+
+    /// [v3.6] Register `watcher(topic, payload)` for events emitted via `tx.emit()`.
+    fn add_event_watcher(&self, watcher: PyObject) {
+        self.event_watchers.relock().push(watcher);
+    }
+
+    /// [v3.6] Remove a watcher previously added. Returns True if it was registered.
+    fn remove_event_watcher(&self, py: Python, watcher: &Bound<'_, PyAny>) -> bool {
+        let mut watchers = self.event_watchers.relock();
+        let before = watchers.len();
+        watchers.retain(|w| !w.bind(py).is(watcher));
+        watchers.len() != before
+    }
+
+    /// [v3.6] Config epoch: bumped by every commit that changes a Meta path.
+    #[getter]
+    fn meta_epoch(&self) -> u64 {
+        self.meta_watch.epoch()
+    }
+
+    /// [v3.6] Call `callback(paths, epoch)` after each commit changing Meta paths. Returns
+    /// `callback`, so it also works as a decorator.
+    fn on_meta_change(&self, py: Python, callback: PyObject) -> PyObject {
+        self.meta_watch.add(callback.clone_ref(py));
+        callback
+    }
+
+    /// [v3.6] Remove a Meta listener. Returns True if it was registered.
+    fn remove_meta_listener(&self, py: Python, callback: &Bound<'_, PyAny>) -> bool {
+        self.meta_watch.remove(py, callback)
+    }
+
+    /// [v3.6] Register the inbox handler: `handler(tx, event)` runs inside a transaction.
+    fn attach_inbox_handler(&self, handler: PyObject) {
+        *self.inbox_handler.relock() = Some(handler);
+    }
+
+    /// [v3.6] Inbox: consume an external event transactionally.
+    /// - `dedup_key` (or `event.idempotency_key`) already consumed => skipped.
+    /// - `order_key` + `sequence` (starting at 1) enforce per-key ordering; early events are parked.
+    ///   A parked event whose handler fails stays parked (audited as `INBOX_HANDLER_FAILED`) and is
+    ///   retried by the next ingest for its key.
+    /// Returns the number of events applied by this call (0 = duplicate/stale/parked).
+    #[pyo3(signature = (event, dedup_key=None, order_key=None, sequence=None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn ingest(
+        slf: Py<TheusEngine>,
+        py: Python,
+        event: PyObject,
+        dedup_key: Option<String>,
+        order_key: Option<String>,
+        sequence: Option<u64>,
+    ) -> PyResult<usize> {
+        let handler = slf.borrow(py).inbox_handler.relock().as_ref().map(|h| h.clone_ref(py))
+            .ok_or_else(|| ContextError::new_err("No inbox handler attached. Call attach_inbox_handler() first."))?;
+        let dedup_key = match dedup_key {
+            Some(k) => Some(k),
+            None => event.bind(py).getattr("idempotency_key").ok().and_then(|k| k.extract::<String>().ok()),
+        };
+
+        let inbox = slf.borrow(py).inbox.clone();
+        if let Some(ref k) = dedup_key {
+            if inbox.relock().consumed.contains(k, now_ms()) {
+                return Ok(0);
+            }
+        }
+
+        let (order_key, sequence) = match (order_key, sequence) {
+            (None, None) => {
+                Self::ingest_one(&slf, py, &handler, &event, dedup_key, None)?;
+                return Ok(1);
+            }
+            (Some(k), Some(s)) => (k, s),
+            _ => return Err(pyo3::exceptions::PyValueError::new_err("order_key and sequence must be given together")),
+        };
+
+        let early = {
+            let ib = inbox.relock();
+            let last = ib.last_seq.get(&order_key).copied().unwrap_or(0);
+            if sequence <= last {
+                return Ok(0);
+            }
+            sequence > last + 1
+        };
+        let mut applied = 0;
+        if early {
+            inbox.relock().parked.entry(order_key.clone()).or_default().insert(sequence, (event, dedup_key));
+        } else {
+            Self::ingest_one(&slf, py, &handler, &event, dedup_key, Some((&order_key, sequence)))?;
+            applied = 1;
+        }
+
+        // Drain parked successors now that the gap is closed (this also retries one whose
+        // handler failed during an earlier drain).
+        loop {
+            let next = {
+                let mut ib = inbox.relock();
+                let next_seq = ib.last_seq.get(&order_key).copied().unwrap_or(0) + 1;
+                ib.parked.get_mut(&order_key).and_then(|q| q.remove(&next_seq)).map(|e| (next_seq, e))
+            };
+            let Some((seq, (ev, key))) = next else { break };
+            if key.as_ref().is_some_and(|k| inbox.relock().consumed.contains(k, now_ms())) {
+                inbox.relock().last_seq.insert(order_key.clone(), seq);
+                continue;
+            }
+            if let Err(err) = Self::ingest_one(&slf, py, &handler, &ev, key.clone(), Some((&order_key, seq))) {
+                // Events already applied by this call stay counted; the failed one goes back to
+                // the parking lot (still blocking its successors) until the next ingest for the key.
+                crate::audit::log_global("INBOX_HANDLER_FAILED", &format!("{order_key}#{seq} stays parked: {err}"));
+                inbox.relock().parked.entry(order_key.clone()).or_default().insert(seq, (ev, key));
+                break;
+            }
+            applied += 1;
+        }
+        Ok(applied)
+    }
+
+    /// [v3.6] Configure how long completed idempotency keys are remembered.
+    #[pyo3(signature = (retention_ms, max_entries=100_000))]
+    fn set_outbox_retention(&self, retention_ms: u64, max_entries: usize) {
+        let mut w = self.processed_ids.relock();
+        w.retention_ms = retention_ms;
+        w.max_entries = max_entries;
+    }
+
+    /// [v3.6] Worker API: has the side effect for `key` already completed?
+    fn is_processed(&self, key: &str) -> bool {
+        self.processed_ids.relock().contains(key, now_ms())
+    }
+
+    /// [v3.6] Worker API: record completion of `key`. Returns False if already recorded.
+    fn mark_processed(&self, key: String) -> bool {
+        self.processed_ids.relock().mark(key, now_ms())
+    }
+
+    /// [v3.6] Export pending messages + processed-id window as plain data for persistence.
+    fn outbox_snapshot(&self, py: Python) -> PyResult<PyObject> {
+        let pending = PyList::empty_bound(py);
+        for msg in self.outbox.relock().iter() {
+            let d = PyDict::new_bound(py);
+            d.set_item("topic", &msg.topic)?;
+            d.set_item("payload", msg.payload.as_ref())?;
+            d.set_item("schema", &msg.schema)?;
+            d.set_item("content_type", &msg.content_type)?;
+            d.set_item("idempotency_key", &msg.idempotency_key)?;
+            d.set_item("priority", msg.priority)?;
+            d.set_item("ordering_key", &msg.ordering_key)?;
+            d.set_item("attempts", msg.attempts)?;
+            d.set_item("created_at_ms", msg.created_at_ms)?;
+            d.set_item("tags", crate::tags::to_dict(py, msg.tags.as_ref())?)?;
+            pending.append(d)?;
+        }
+        let processed = self.processed_ids.relock().entries();
+
+        let snap = PyDict::new_bound(py);
+        snap.set_item("pending", pending)?;
+        snap.set_item("processed", processed)?;
+        Ok(snap.into_any().unbind())
+    }
+
+    /// [v3.6] Restore a snapshot produced by `outbox_snapshot()` (e.g. after a crash).
+    fn restore_outbox(&self, snapshot: &Bound<'_, PyDict>) -> PyResult<()> {
+        let mut restored = Vec::new();
+        if let Some(pending) = snapshot.get_item("pending")? {
+            for item in pending.iter()? {
+                let d = item?.downcast_into::<PyDict>()?;
+                let field = |k: &str| -> PyResult<Option<Bound<'_, PyAny>>> { d.get_item(k) };
+                restored.push(OutboxMsg {
+                    topic: field("topic")?.ok_or_else(|| ContextError::new_err("Outbox snapshot entry missing 'topic'"))?.extract()?,
+                    payload: Arc::new(field("payload")?.map_or_else(|| d.py().None(), Bound::unbind)),
+                    schema: field("schema")?.map(|v| v.extract()).transpose()?.flatten(),
+                    content_type: field("content_type")?.map(|v| v.extract()).transpose()?.flatten(),
+                    idempotency_key: field("idempotency_key")?.ok_or_else(|| ContextError::new_err("Outbox snapshot entry missing 'idempotency_key'"))?.extract()?,
+                    priority: field("priority")?.map(|v| v.extract()).transpose()?.unwrap_or(0),
+                    ordering_key: field("ordering_key")?.map(|v| v.extract()).transpose()?.flatten(),
+                    attempts: field("attempts")?.map(|v| v.extract()).transpose()?.unwrap_or(0),
+                    created_at_ms: field("created_at_ms")?.map(|v| v.extract()).transpose()?.unwrap_or_else(now_ms),
+                    tags: crate::tags::from_py(field("tags")?.as_ref().map(|v| v.downcast::<PyDict>()).transpose()?)?,
+                    generated_key: false,
+                });
+            }
+        }
+
+        {
+            let mut w = self.processed_ids.relock();
+            w.clear();
+            if let Some(processed) = snapshot.get_item("processed")? {
+                for item in processed.iter()? {
+                    let (key, ts): (String, u64) = item?.extract()?;
+                    w.mark(key, ts);
+                }
+            }
+        }
+        self.outbox.relock().extend(restored);
+        Ok(())
+    }
+
+    #[pyo3(signature = (expected_version, data=None, heavy=None, signal=None, requester=None))]
+    #[allow(clippy::too_many_lines)]
+    fn compare_and_swap(
+        &mut self, 
+        py: Python, 
+        expected_version: u64, 
+        data: Option<PyObject>, 
+        heavy: Option<PyObject>,
+        signal: Option<PyObject>,
+        requester: Option<String>
+    ) -> PyResult<()> {
+        self.ensure_writable()?;
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(EngineShutdownError::new_err("Engine is shut down: no new writes accepted"));
+        }
+        let commit_started = Instant::now();
+
+        self.faults.check("cas")?;
+
+        // v3.3 Priority Ticket Check
+        if self.conflict_manager.is_blocked(requester.clone()) {
+             return Err(crate::errors::busy(py, requester.as_deref()));
+        }
+
+        // [FIX] Enforce Strict CAS if enabled (Explicit)
+        let strict_cas = *self.strict_cas.relock();
+        
+        let current_state_bound = self.state.bind(py);
+        let current_state = current_state_bound.borrow();
+        let current_version = current_state.version;
+        
+        if current_version != expected_version {
+            if strict_cas {
+                 return Err(crate::errors::version_mismatch(py, crate::messages::STRICT_CAS_MISMATCH, None, expected_version, current_version));
+            }
+
+            // v3.3 Smart CAS: Check Key-Level Conflicts
+            // If the specific keys we are updating haven't changed since expected_version,
+            // we can safely merge even if global version bumped.
+            
+            let mut safe = true;
+            let mut conflict_path: Option<String> = None;
+            
+            // v3.1: Check FIELD-Level Conflicts (domain.counter, not just domain)
+            // Check Data Keys
+            if let Some(ref d) = data {
+                if let Ok(d_dict) = d.downcast_bound::<PyDict>(py) {
+                    for (zone_k, zone_v) in d_dict.iter() {
+                         let zone_key = zone_k.extract::<String>()?;
+                         
+                         // Check nested fields if value is a dict
+                         if let Ok(inner_dict) = zone_v.downcast::<PyDict>() {
+                             for (ik, _) in inner_dict {
+                                 let inner_key = ik.extract::<String>()?;
+                                 let field_path = format!("{zone_key}.{inner_key}");  // "domain.counter"
+                                 
+                                 if let Some(last_ver) = current_state.key_last_modified.get(field_path.as_str()) {
+                                     if *last_ver > expected_version {
+                                         safe = false;
+                                         conflict_path = Some(field_path.clone());
+                                         break;
+                                     }
+                                 }
+                             }
+                         } else {
+                             // Non-dict value: fall back to zone-level check
+                             if let Some(last_ver) = current_state.key_last_modified.get(zone_key.as_str()) {
+                                 if *last_ver > expected_version {
+                                     safe = false;
+                                     conflict_path = Some(zone_key.clone());
+                                 }
+                             }
+                         }
+                         if !safe { break; }
+                    }
+                }
+            }
+            
+            // Check Heavy Keys (if safe so far)
+            if safe {
+                if let Some(ref h) = heavy {
+                    if let Ok(h_dict) = h.downcast_bound::<PyDict>(py) {
+                        for (zone_k, zone_v) in h_dict.iter() {
+                             let zone_key = zone_k.extract::<String>()?;
+                             
+                             // Check nested fields if value is a dict
+                             if let Ok(inner_dict) = zone_v.downcast::<PyDict>() {
+                                 for (ik, _) in inner_dict {
+                                     let inner_key = ik.extract::<String>()?;
+                                     let field_path = format!("{zone_key}.{inner_key}");
+                                     
+                                     if let Some(last_ver) = current_state.key_last_modified.get(field_path.as_str()) {
+                                         if *last_ver > expected_version {
+                                             safe = false;
+                                             conflict_path = Some(field_path.clone());
+                                             break;
+                                         }
+                                     }
+                                 }
+                             } else {
+                                 // Non-dict value: fall back to zone-level check
+                                 if let Some(last_ver) = current_state.key_last_modified.get(zone_key.as_str()) {
+                                     if *last_ver > expected_version {
+                                         safe = false;
+                                         conflict_path = Some(zone_key.clone());
+                                     }
+                                 }
+                             }
+                             if !safe { break; }
+                        }
+                    }
+                }
+            }
+
+            if !safe {
+                return Err(crate::errors::version_mismatch(py, crate::messages::CAS_MISMATCH, conflict_path.as_deref(), expected_version, current_version));
+            }
+            // If safe, fall through to update (Optimistic Merge)
+        }
+
+        // [v3.6] Conflict armed by `engine.testing.simulate_conflict`.
+        let mut touched = Vec::new();
+        for zones in [&data, &heavy].into_iter().flatten() {
+            if let Ok(zones) = zones.downcast_bound::<PyDict>(py) {
+                touched.extend(crate::testing::written_paths(zones)?);
+            }
+        }
+        // [v3.6] Raw CAS is unscoped: no writes under tenant roots (see tenancy.rs).
+        self.tenants.check(py, None, &touched, 0, &self.glass)?;
+        if let Some(found) = self.conflict_sim.take(&touched, current_version) {
+            return Err(crate::errors::version_mismatch(py, crate::messages::CAS_MISMATCH, None, expected_version, found));
+        }
+
+        // We must drop the borrow before calling Python method `update` on the object
+        // because `update` might need mutable access or create new object?
+        // Actually `update` is a method on `State` which is immutable self.
+        // But `call_method` might re-enter?
+        // Safe practice: drop borrow.
+        drop(current_state);
+
+        // [INC-023] Clone signal before moving into State.update() so we can publish
+        // after commit. State.update() only latches last_signals (Flux); actual publish
+        // is deferred to after self.state is updated below.
+        let signal_for_publish = signal.as_ref().map(|s| s.clone_ref(py));
+        let written = data.as_ref().map(|d| d.clone_ref(py));
+        let delta_count: usize = [&data, &heavy].iter()
+            .filter_map(|zone| zone.as_ref().and_then(|z| z.bind(py).len().ok()))
+            .sum();
+
+        let new_state_obj = current_state_bound.call_method(
+            "update", 
+            (data, heavy, signal), 
+            None
+        )?;
+
+        // [v3.1.2] Schema Enforcement for CAS (Critical Gatekeeper)
+        // Ensure new state is valid before replacing self.state
+        self.faults.check("schema")?;
+        {
+             let schema_mutex = self.schema.relock(); // Use separate var to avoid borrow conflict
+             if let Some(ref schema) = *schema_mutex {
+                 // Validate Resulting State
+                 let frozen_data = new_state_obj.getattr("data")?;
+                 let mut dict_data = frozen_data.call_method0("to_dict")?;
+                 dict_data = self.materialize_placeholders(py, dict_data)?;
+                 
+                 if let Err(e) = schema.call_method1(py, "model_validate", (dict_data,)) {
+                     // Reject Commit!
+                     return Err(crate::errors::coded::<crate::config::SchemaViolationError>(py, crate::messages::render(crate::messages::SCHEMA_VIOLATION_CAS, &[("error", &e)])));
+                 }
+             }
+        }
+        
+        let changed = crate::lineage::stamp(py, &self.lineage, current_state_bound, &new_state_obj, written.as_ref().map(|d| d.bind(py)), requester, None, None)?;
+        self.store_placeholders(py, &new_state_obj)?;
+        let previous = current_state_bound.clone();
+        self.state = new_state_obj.extract::<Py<State>>()?;
+        self.history.relock().record(py, &self.state);
+        self.tag_committed_state(py)?;
+        crate::metrics::record_commit(commit_started, delta_count);
+
+        // [INC-023] Deferred signal dispatch — fires AFTER self.state is committed.
+        // Guarantees that subscribers see consistent state when they receive the event.
+        // If schema validation failed above, this line is never reached — no orphaned signals.
+        if let Some(sig) = signal_for_publish {
+            let version = self.state.bind(py).borrow().version;
+            self.signals.enqueue(py, sig.bind(py), version)?;
+            self.state.bind(py).borrow().publish_signals(py, Some(sig))?;
+        }
+        self.meta_watch.committed(py, &changed);
+        self.indexes.committed(py, &previous.borrow(), &self.state.bind(py).borrow(), &changed)?;
+        self.search.committed(py, &previous.borrow(), &self.state.bind(py).borrow(), &changed)?;
+        self.changes.committed(py, &previous.borrow(), &self.state.bind(py).borrow(), &changed)?;
+
+        Ok(())
+    }
+
+    #[pyo3(signature = (name, func, tx=None))]
+    fn execute_process_async<'py>(
+        &self, 
+        py: Python<'py>, 
+        name: &str, 
+        func: PyObject,
+        tx: Option<PyObject>
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inspect = py.import("inspect")?;
+        let is_coroutine = inspect.call_method1("iscoroutinefunction", (&func,))?.is_truthy()?;
+        
+        // if tx.is_some() { println!("DEBUG: execute_process_async with TX"); } else { println!("DEBUG: execute_process_async NO TX"); }
+
+        // Create Ephemeral Context (RAII)
+        let local_dict = PyDict::new_bound(py);
+        
+        let py_tx: Option<Py<Transaction>> = tx.map(|t| t.extract(py)).transpose()?;
+        // [v3.6] Heartbeat tracking: the run stays registered while the awaitable runs.
+        let tracked = py_tx.as_ref().map(|t| {
+            let t = t.borrow(py);
+            (t.id, t.cancelled.clone())
+        });
+        // [v3.6] Inside a transaction the token shares its cancel flag.
+        let flag = tracked.as_ref().map_or_else(|| Arc::new(AtomicBool::new(false)), |(_, f)| f.clone());
+        let token = Py::new(py, crate::cancellation::CancellationToken::new(name, flag))?;
+        
+        // [v3.3 Fix] Share Outbox Buffer with Transaction if present
+        let outbox_buffer = if let Some(ref t) = py_tx {
+            t.borrow(py).pending_outbox.clone()
+        } else {
+            Arc::new(Mutex::new(Vec::new()))
+        };
+
+        // [v3.6] Repeatable reads: the process sees the transaction's pinned snapshot.
+        let state = py_tx.as_ref().map_or_else(|| self.state.clone_ref(py), |t| t.borrow(py).read_state(py));
+        let ctx = Py::new(py, crate::structures::ProcessContext {
+            state,
+            local: local_dict.unbind(),
+            outbox: crate::structures::Outbox {
+                messages: outbox_buffer 
+            },
+            tx: py_tx, 
+            token: token.clone_ref(py),
+        })?;
 
         let args = (ctx,);
 
-        let coro_obj: PyObject = if is_coroutine {
-            func.call1(py, args)?
+        let asyncio = py.import("asyncio")?;
+        let coro_obj: PyObject = if is_coroutine {
+            func.call1(py, args)?
+        } else {
+            asyncio.call_method1("to_thread", (func, args.0))?.unbind()
+        };
+
+        let (tx_id, cancel) = tracked.unzip();
+        let run = crate::heartbeat::TrackedRun::new(
+            name, tx_id, cancel, token, self.heartbeats.clone(), self.tokens.clone(), self.traces.clone(),
+        );
+        crate::heartbeat::track(py, coro_obj, run)
+    }
+}
+
+// Transaction
+// Removed duplicate `pyo3::types` import
+// PyList should be imported at top level or merged.
+
+// ... 
+
+#[pyclass(module = "theus_core")]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Transaction {
+    engine: Py<TheusEngine>,
+    pub(crate) pending_data: Py<PyDict>,
+    pub(crate) pending_heavy: Py<PyDict>,
+    pub(crate) pending_signal: Py<PyList>, // Changed from PyDict to PyList
+    pending_outbox: Arc<Mutex<Vec<OutboxMsg>>>,
+    start_time: Option<u64>, // clock::monotonic_ms() at __enter__
+    pub(crate) start_version: u64,
+    write_timeout_ms: u64,
+    // [v3.1 Zero Trust] Unified Delta Log
+    pub delta_log: Arc<Mutex<Vec<crate::delta::DeltaEntry>>>, 
+    pub shadow_cache: Arc<Mutex<std::collections::HashMap<usize, (PyObject, PyObject)>>>, // id -> (original, shadow)
+    pub path_to_shadow: Arc<Mutex<std::collections::HashMap<String, PyObject>>>, // root -> shadow (for legacy commit)
+    pub full_path_map: Arc<Mutex<std::collections::HashMap<String, PyObject>>>, // full_path -> shadow (for diff merging)
+    pub shadows_inferred: Arc<Mutex<bool>>, // [v3.3] Prevent double-inference hangs
+    pending_events: Arc<Mutex<Vec<(String, PyObject)>>>, // [v3.6] tx.emit() staging
+    cancelled: Arc<AtomicBool>,       // [v3.6] Cooperative cancellation flag
+    watchdog_fired: Arc<AtomicBool>,  // [v3.6] Soft-deadline callback fires once
+    pub closed: Arc<AtomicBool>,      // [v3.6] Set once __exit__ runs (commit or rollback)
+    pub(crate) id: u64,               // [v3.6] Engine-unique id (leak tracking)
+    faults: Arc<crate::faults::FaultRegistry>, // [v3.6] Chaos hooks (shared with engine)
+    recorder: Option<Arc<crate::recorder::Recorder>>, // [v3.6] Active recording at creation
+    pub(crate) actor: Option<String>, // [v3.6] Who opened the tx (proposer for approvals)
+    admin: bool,                      // [v3.6] Admin txs bypass the approval queue
+    approvals: Arc<crate::approvals::ApprovalQueue>,
+    proposal: Mutex<Option<u64>>,     // [v3.6] Proposal parked by this tx's commit
+    pub(crate) tags: Option<crate::tags::Tags>, // [v3.6] Correlation ids (request_id, ...)
+    heavy_store: Arc<crate::heavy_store::HeavyStore>,
+    blobs: Arc<crate::blobs::BlobStore>, // [v3.6] Content-addressable payloads
+    heavy_pins: Mutex<Vec<u64>>,      // [v3.6] Managed heavy handles pinned while open
+    copiers: Arc<crate::copiers::CopierRegistry>, // [v3.6] Per-type shadow copiers
+    pub(crate) schema_fields: Arc<crate::schema_fields::SchemaFields>, // [v3.6] Declared fields per path
+    stats: Mutex<TxStats>,            // [v3.6] tx.stats() counters
+    shadow_budget: Mutex<crate::shadow_budget::ShadowBudget>, // [v3.6] Shadow cache memory bound
+    shadow_hashing: bool,             // [v3.6] Engine setting at creation
+    shadow_hashes: Mutex<std::collections::HashMap<usize, u64>>, // [v3.6] Active id -> shadow hash at creation
+    pub(crate) proxy_pool: Mutex<std::collections::HashMap<(usize, String), Py<crate::proxy::SupervisorProxy>>>, // [v3.6] (target id, path) -> proxy
+    streamed: AtomicBool,             // [v3.6] Committed through the streaming path
+    read_only: AtomicBool,            // [v3.6] Closed without writes (no commit, same version)
+    limits: TxLimits,                 // [v3.6] Engine caps at creation
+    touched: Mutex<std::collections::HashSet<String>>, // [v3.6] Distinct written paths (only with max_paths)
+    repeatable: bool,                 // [v3.6] Repeatable-read isolation
+    snapshot: Mutex<Option<Py<State>>>, // [v3.6] State pinned at __enter__ (repeatable reads)
+    pub(crate) outcome: Mutex<crate::group_commit::CommitOutcome>, // [v3.6] commit_status / version / error
+    pub(crate) op_id: Option<String>, // [v3.6] Idempotency key (see op_ids)
+    rollback: Mutex<Option<PyObject>>, // [v3.6] Report of the discarded writes (last_rollback_report)
+    partial_commit: bool,             // [v3.6] Commit the last checkpoint if the block raises
+    checkpoint: Mutex<Option<crate::checkpoints::Checkpoint>>,
+    state_machines: Arc<crate::state_machines::StateMachines>, // [v3.6] Lifecycle fields
+    pub(crate) middleware: Arc<crate::middleware::Pipeline>, // [v3.6] Proxy interceptors
+    pub(crate) tenant: Option<String>, // [v3.6] Paths rooted under tenants.<name> (see tenancy.rs)
+    lock_epoch: crate::locks::LockEpoch, // [v3.6] The engine's lock-recovery epoch (see locks.rs)
+    lock_mark: crate::locks::EpochMark,  // [v3.6] ... as seen at creation
+}
+
+/// [v3.6] One writer of an installed version: `(written, actor, tx id, tags)` for lineage.
+pub(crate) type CommitWriter<'py> = (Bound<'py, PyDict>, Option<String>, Option<u64>, Option<crate::tags::Tags>);
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        // [v3.6] Garbage-collected without __exit__: no longer holds shadows, drop from leak registry.
+        self.closed.store(true, Ordering::SeqCst);
+        if !self.heavy_pins.get_mut().unwrap_or_else(std::sync::PoisonError::into_inner).is_empty() {
+            Python::with_gil(|py| self.unpin_heavy(py));
+        }
+    }
+}
+
+impl TheusEngine {
+    /// Identifies this engine's outbox to `testing` captures.
+    pub(crate) fn outbox_key(&self) -> usize {
+        Arc::as_ptr(&self.outbox) as usize
+    }
+
+    /// [v3.6] Enqueue engine-originated messages (no transaction to stage them in).
+    fn announce(&self, msgs: Vec<OutboxMsg>) {
+        crate::testing::on_outbox(self.outbox_key(), &msgs);
+        self.outbox.relock().extend(msgs);
+    }
+
+    /// [v3.6] Ends expired break-glass grants (auditing, notifying).
+    pub(crate) fn sweep_break_glass(&self, py: Python) -> PyResult<()> {
+        let ended = self.glass.sweep(py)?;
+        self.announce(ended);
+        Ok(())
+    }
+
+    pub(crate) fn spill_store(&self) -> Option<Arc<crate::spill::SpillStore>> {
+        self.spill.relock().clone()
+    }
+
+    /// Swaps newly written values under compressed / spilled prefixes of a new State for
+    /// placeholders.
+    pub(crate) fn store_placeholders(&self, py: Python, state: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.compression.apply(py, state)?;
+        if let Some(store) = self.spill_store() {
+            store.apply(py, state)?;
+        }
+        Ok(())
+    }
+
+    /// `State.data.to_dict()` with placeholders loaded (schema validation).
+    pub(crate) fn materialize_placeholders<'py>(&self, py: Python<'py>, data: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let mut prefixes = self.compression.prefixes();
+        prefixes.extend(self.spill_store().map(|s| s.prefixes()).unwrap_or_default());
+        if prefixes.is_empty() {
+            return Ok(data);
+        }
+        crate::spill::materialize(py, &prefixes, &data)
+    }
+
+    /// [v3.6] Install a decoded snapshot as the committed state (schema-checked). The signal
+    /// hub and meta log of this engine are kept.
+    fn install_decoded(&mut self, py: Python, decoded: crate::state_codec::DecodedState) -> PyResult<()> {
+        if let Some(ref schema) = *self.schema.relock() {
+            if let Err(e) = schema.call_method1(py, "model_validate", (&decoded.data,)) {
+                return Err(crate::errors::coded::<crate::config::SchemaViolationError>(py, crate::messages::render(crate::messages::SCHEMA_VIOLATION_LOAD, &[("error", &e)])));
+            }
+        }
+
+        let (signal, meta_logs, meta_capacity) = {
+            let current = self.state.bind(py).borrow();
+            (current.signal.clone(), current.meta_logs.clone(), current.meta_capacity)
+        };
+        let mut state = State::new(
+            Some(decoded.data.into_any().unbind()),
+            Some(decoded.heavy.into_any().unbind()),
+            None,
+            decoded.version,
+            meta_capacity,
+            py,
+        )?;
+        state.signal = signal;
+        state.meta_logs = meta_logs;
+        state.key_last_modified = decoded.key_last_modified.into_iter().map(|(k, v)| (crate::intern::intern(&k), v)).collect();
+        if self.escape_tracking.relock().is_some() {
+            state.set_integrity(py, true)?;
+        }
+
+        self.state = Py::new(py, state)?;
+        self.history.relock().record(py, &self.state);
+        self.computed.invalidate(None);
+        self.indexes.rebuild(py, &self.state.bind(py).borrow())?;
+        self.search.rebuild(py, &self.state.bind(py).borrow())?;
+        self.tenants.rebuild(py, &self.state.bind(py).borrow())?;
+        self.op_ids.relock().replace(decoded.op_ids);
+        if let Some(timers) = &decoded.timers {
+            self.timers.relock().restore(timers)?;
+        }
+        self.tag_committed_state(py)
+    }
+
+
+    /// [v3.6] Config, schema and zone policies recorded in (and checked against) snapshot
+    /// containers.
+    fn snapshot_profile(&self, py: Python) -> PyResult<crate::snapshot_format::EngineProfile> {
+        let config = std::collections::BTreeMap::from([
+            ("strict_guards", self.strict_guards.relock().to_string()),
+            ("strict_cas", self.strict_cas.relock().to_string()),
+            ("isolation", isolation_name(self.repeatable_reads.load(Ordering::SeqCst)).to_string()),
+        ]);
+        let schema = self.schema.relock().as_ref().map(|s| s.clone_ref(py));
+        Ok(crate::snapshot_format::EngineProfile {
+            config,
+            schema_json: schema.map(|s| crate::snapshot_format::schema_json(py, s.bind(py))).transpose()?,
+            zone_policies: crate::zones::physics_overrides(),
+        })
+    }
+
+    /// Currently committed state (cheap handle clone).
+    pub(crate) fn committed_state(&self, py: Python) -> Py<State> {
+        self.state.clone_ref(py)
+    }
+
+    /// [v3.6] Re-tag Data-zone keys whose committed hash moved since they were last tagged.
+    /// Untouched zones keep their old tags, so earlier out-of-band edits stay detectable.
+    #[allow(clippy::similar_names)]
+    fn tag_committed_state(&self, py: Python) -> PyResult<()> {
+        let state = self.state.bind(py).borrow();
+        // NOTE: Never hold the registry lock while walking Python objects.
+        let stale: Vec<(String, u64)> = {
+            let registry = self.escape_tracking.relock();
+            let Some(ref registry) = *registry else { return Ok(()) };
+            state.data_hashes.iter()
+                .filter(|(k, h)| registry.zones.get(*k).map(|z| z.0) != Some(**h))
+                .map(|(k, h)| (k.clone(), *h))
+                .collect()
+        };
+        let mut fresh = Vec::with_capacity(stale.len());
+        for (key, hash) in stale {
+            if let Some(value) = state.data.get(&key) {
+                let tags = crate::integrity::container_tags(&key, value.bind(py))?;
+                fresh.push((key, hash, tags));
+            }
+        }
+        if let Some(ref mut registry) = *self.escape_tracking.relock() {
+            for (key, hash, tags) in fresh {
+                registry.zones.insert(key, (hash, tags));
+            }
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Deepest changed container paths under `keys`, as (path, kind) pairs.
+    /// Empty unless escape tracking is enabled.
+    fn escaped_paths(&self, py: Python, state: &State, keys: &[&String]) -> PyResult<Vec<(String, &'static str)>> {
+        let tagged: Vec<(String, Vec<crate::integrity::ContainerTag>)> = {
+            let registry = self.escape_tracking.relock();
+            let Some(ref registry) = *registry else { return Ok(Vec::new()) };
+            keys.iter().map(|k| {
+                let tags = registry.zones.get(*k).map_or_else(Vec::new, |(_, tags)| tags.clone());
+                ((*k).clone(), tags)
+            }).collect()
+        };
+
+        let mut changed = Vec::new();
+        for (key, old) in tagged {
+            let now: std::collections::HashMap<String, (usize, u64)> = match state.data.get(&key) {
+                Some(v) => crate::integrity::container_tags(&key, v.bind(py))?
+                    .into_iter().map(|t| (t.path, (t.id, t.hash))).collect(),
+                None => std::collections::HashMap::new(),
+            };
+            let mut zone: Vec<(String, &'static str)> = Vec::new();
+            for tag in &old {
+                match now.get(&tag.path) {
+                    None => zone.push((tag.path.clone(), "removed")),
+                    Some((id, _)) if *id != tag.id => zone.push((tag.path.clone(), "replaced")),
+                    Some((_, hash)) if *hash != tag.hash => zone.push((tag.path.clone(), "mutated")),
+                    _ => {}
+                }
+            }
+            for path in now.keys() {
+                if !old.iter().any(|t| &t.path == path) {
+                    zone.push((path.clone(), "added"));
+                }
+            }
+            if zone.is_empty() {
+                // Scalar zone (nothing to tag) - report the key itself.
+                zone.push((key, "mutated"));
+            }
+            // Keep only the deepest changes: drop entries that have a changed descendant.
+            let all: Vec<String> = zone.iter().map(|(p, _)| p.clone()).collect();
+            zone.retain(|(p, _)| {
+                let prefix = format!("{p}.");
+                !all.iter().any(|other| other.starts_with(&prefix))
+            });
+            zone.sort();
+            changed.extend(zone);
+        }
+        Ok(changed)
+    }
+
+    /// [v3.6] Fail fast while the engine is in maintenance mode.
+    pub fn ensure_writable(&self) -> PyResult<()> {
+        match self.maintenance.relock().as_ref() {
+            Some(reason) => Err(MaintenanceModeError::new_err(format!("Engine is in maintenance mode (read-only): {reason}"))),
+            None => Ok(()),
+        }
+    }
+
+    fn open_tx_info(py: Python, id: u64, tx: &OpenTx) -> PyResult<PyObject> {
+        let d = PyDict::new_bound(py);
+        d.set_item("id", id)?;
+        #[allow(clippy::cast_possible_truncation)]
+        d.set_item("age_ms", crate::clock::monotonic_ms().saturating_sub(tx.created_at))?;
+        d.set_item("created_at_ms", tx.created_at_ms)?;
+        d.set_item("stack", &tx.stack)?;
+        Ok(d.into_any().unbind())
+    }
+
+    /// [v3.6] Track a new transaction and report any that outlived the leak threshold.
+    fn register_open_tx(&self, py: Python, id: u64, closed: Arc<AtomicBool>, shadows: ShadowMaps) -> PyResult<()> {
+        let capture = self.leak_policy.relock().capture_stack;
+        let stack = if capture {
+            let frames = py.import_bound("traceback")?.call_method0("format_stack")?;
+            Some(frames.extract::<Vec<String>>()?.concat())
+        } else {
+            None
+        };
+        self.open_txs.relock().insert(id, OpenTx {
+            created_at: crate::clock::monotonic_ms(),
+            created_at_ms: crate::outbox::now_ms(),
+            stack,
+            closed,
+            warned: false,
+            provisional: None,
+            shadows,
+        });
+        self.sweep_leaks(py)
+    }
+
+    fn sweep_leaks(&self, py: Python) -> PyResult<()> {
+        let (threshold, hook) = {
+            let policy = self.leak_policy.relock();
+            (policy.threshold_ms, policy.on_leak.as_ref().map(|h| h.clone_ref(py)))
+        };
+        let mut leaked = Vec::new();
+        {
+            let mut open = self.open_txs.relock();
+            // Dropped-without-exit transactions flag themselves closed in Drop.
+            open.retain(|_, tx| !tx.closed.load(Ordering::SeqCst));
+            let Some(threshold) = threshold else { return Ok(()) };
+            for (id, tx) in open.iter_mut() {
+                #[allow(clippy::cast_possible_truncation)]
+                let age = crate::clock::monotonic_ms().saturating_sub(tx.created_at);
+                if !tx.warned && age > threshold {
+                    tx.warned = true;
+                    crate::audit::log_global("TX_LEAK", &format!("Transaction #{id} open for {age}ms (threshold {threshold}ms)"));
+                    leaked.push(Self::open_tx_info(py, *id, tx)?);
+                }
+            }
+        }
+        if let Some(hook) = hook {
+            for info in leaked {
+                if let Err(e) = hook.call1(py, (info,)) {
+                    e.write_unraisable_bound(py, Some(hook.bind(py)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run one inbox event inside its own transaction; record it as consumed only after commit.
+    fn ingest_one(
+        slf: &Py<TheusEngine>,
+        py: Python,
+        handler: &PyObject,
+        event: &PyObject,
+        dedup_key: Option<String>,
+        ordering: Option<(&String, u64)>,
+    ) -> PyResult<()> {
+        let tx = Py::new(py, Transaction::fresh(py, slf.clone_ref(py), 5000)?)?;
+        let tx = tx.bind(py);
+        tx.call_method0("__enter__")?;
+        if let Err(e) = handler.call1(py, (tx, event)) {
+            tx.call_method1("__exit__", (e.get_type_bound(py), e.value_bound(py), py.None()))?;
+            return Err(e);
+        }
+        tx.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+
+        let inbox = slf.borrow(py).inbox.clone();
+        let mut ib = inbox.relock();
+        if let Some(k) = dedup_key {
+            ib.consumed.mark(k, now_ms());
+        }
+        if let Some((k, seq)) = ordering {
+            ib.last_seq.insert(k.clone(), seq);
+        }
+        Ok(())
+    }
+}
+
+impl Transaction {
+    /// Single construction point for `TheusEngine.transaction()` and `Transaction(...)`.
+    pub(crate) fn fresh(py: Python, engine: Py<TheusEngine>, write_timeout_ms: u64) -> PyResult<Self> {
+        let id = NEXT_TX_ID.fetch_add(1, Ordering::Relaxed);
+        let closed = Arc::new(AtomicBool::new(false));
+        if engine.borrow(py).shutting_down.load(Ordering::SeqCst) {
+            return Err(EngineShutdownError::new_err("Engine is shutting down: no new transactions accepted"));
+        }
+        engine.borrow(py).sweep_break_glass(py)?;
+        let shadow_cache = Arc::new(Mutex::new(std::collections::HashMap::new()));
+        let full_path_map = Arc::new(Mutex::new(std::collections::HashMap::new()));
+        engine.borrow(py).register_open_tx(py, id, closed.clone(), (shadow_cache.clone(), full_path_map.clone()))?;
+        let faults = engine.borrow(py).faults.clone();
+        let recorder = engine.borrow(py).recorder.relock().clone();
+        let approvals = engine.borrow(py).approvals.clone();
+        let heavy_store = engine.borrow(py).heavy_store.clone();
+        let blobs = engine.borrow(py).blobs.clone();
+        let copiers = engine.borrow(py).copiers.clone();
+        let schema_fields = engine.borrow(py).schema_fields.clone();
+        let state_machines = engine.borrow(py).state_machines.clone();
+        let middleware = engine.borrow(py).middleware.clone();
+        let lock_epoch = engine.borrow(py).lock_epoch.clone();
+        let limits = *engine.borrow(py).tx_limits.relock();
+        let shadow_budget = crate::shadow_budget::ShadowBudget::new(*engine.borrow(py).shadow_cache_limit.relock());
+        let repeatable = engine.borrow(py).repeatable_reads.load(Ordering::SeqCst);
+        let shadow_hashing = engine.borrow(py).shadow_hashing.load(Ordering::SeqCst);
+        Ok(Transaction {
+            engine,
+            pending_data: PyDict::new_bound(py).unbind(),
+            pending_heavy: PyDict::new_bound(py).unbind(),
+            pending_signal: PyList::empty_bound(py).unbind(),
+            pending_outbox: Arc::new(Mutex::new(Vec::new())),
+            start_time: None,
+            start_version: 0,
+            lock_epoch: lock_epoch.clone(),
+            lock_mark: lock_epoch.mark(),
+            write_timeout_ms,
+            delta_log: Arc::new(Mutex::new(Vec::new())),
+            shadow_cache,
+            path_to_shadow: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            full_path_map,
+            shadows_inferred: Arc::new(Mutex::new(false)),
+            pending_events: Arc::new(Mutex::new(Vec::new())),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            watchdog_fired: Arc::new(AtomicBool::new(false)),
+            closed,
+            id,
+            faults,
+            recorder,
+            actor: None,
+            admin: false,
+            approvals,
+            proposal: Mutex::new(None),
+            tags: None,
+            heavy_store,
+            blobs,
+            heavy_pins: Mutex::new(Vec::new()),
+            copiers,
+            schema_fields,
+            stats: Mutex::new(TxStats::default()),
+            shadow_budget: Mutex::new(shadow_budget),
+            shadow_hashing,
+            shadow_hashes: Mutex::new(std::collections::HashMap::new()),
+            proxy_pool: Mutex::new(std::collections::HashMap::new()),
+            streamed: AtomicBool::new(false),
+            read_only: AtomicBool::new(false),
+            limits,
+            touched: Mutex::new(std::collections::HashSet::new()),
+            repeatable,
+            snapshot: Mutex::new(None),
+            outcome: Mutex::new(crate::group_commit::CommitOutcome::default()),
+            op_id: None,
+            rollback: Mutex::new(None),
+            partial_commit: false,
+            checkpoint: Mutex::new(None),
+            state_machines,
+            middleware,
+            tenant: None,
+        })
+    }
+
+    /// [v3.6] Drops least recently used shadows other than `keep` until the budget fits,
+    /// skipping pinned, modified or externally referenced ones. Runs under the cache lock
+    /// with proxy operations re-entrant (user `__eq__`).
+    fn evict_shadows(&self, py: Python, cache: &mut std::collections::HashMap<usize, (PyObject, PyObject)>, keep: usize) {
+        let pinned: Vec<String> = self.delta_log.relock().iter().map(|d| d.path.to_string()).collect();
+        let candidates = self.shadow_budget.relock().candidates(keep);
+        let _hooks = crate::reentrancy::hooks();
+        for (id, path) in candidates {
+            if !self.shadow_budget.relock().over() {
+                break;
+            }
+            if pinned.iter().any(|p| crate::shadow_budget::overlaps(p, &path)) {
+                continue;
+            }
+            let Some((shadow, current)) = cache.get(&id).map(|(s, c)| (s.clone_ref(py), c.clone_ref(py))) else { continue };
+            // Pooled proxies nobody else holds go with the entry.
+            let pooled: Vec<(usize, String)> = self.proxy_pool.relock_reset().iter()
+                .filter(|((target, _), proxy)| *target == shadow.bind(py).as_ptr() as usize && proxy.get_refcnt(py) == 1)
+                .map(|(key, _)| key.clone())
+                .collect();
+            let root = path.split(['.', '[']).next().unwrap_or(&path).to_string();
+            let by_root = self.path_to_shadow.relock().get(&root).is_some_and(|s| s.is(&shadow));
+            // The cache entry, the clone above, the root map and the pooled proxies.
+            let expected = 2 + isize::from(by_root) + isize::try_from(pooled.len()).unwrap_or(isize::MAX);
+            if shadow.get_refcnt(py) != expected {
+                continue;
+            }
+            let verdict = crate::shadow_compare::compare_all(py, &[(shadow.clone_ref(py), current.clone_ref(py))])[0];
+            if !shadow_equal(py, verdict, &shadow, &current) {
+                continue;
+            }
+            cache.remove(&id);
+            self.shadow_hashes.relock().remove(&id);
+            self.full_path_map.relock().retain(|_, v| v.bind(py).as_ptr() as usize != id);
+            if by_root {
+                self.path_to_shadow.relock().remove(&root);
+            }
+            let mut pool = self.proxy_pool.relock_reset();
+            for key in &pooled {
+                pool.remove(key);
+            }
+            drop(pool);
+            self.shadow_budget.relock().evict(id);
+        }
+    }
+
+    /// [v3.6] State reads resolve against: the pinned snapshot or the latest commit.
+    pub(crate) fn read_state(&self, py: Python) -> Py<State> {
+        match self.snapshot.relock().as_ref() {
+            Some(state) => state.clone_ref(py),
+            None => self.engine.borrow(py).state.clone_ref(py),
+        }
+    }
+
+    /// [v3.6] Exposes this transaction's uncommitted writes to `engine.peek_pending`.
+    fn share_pending(&self, py: Python) {
+        let shared = crate::provisional::Provisional {
+            actor: self.actor.clone(),
+            delta_log: self.delta_log.clone(),
+            pending_data: self.pending_data.clone_ref(py),
+        };
+        if let Some(open) = self.engine.borrow(py).open_txs.relock().get_mut(&self.id) {
+            open.provisional = Some(shared);
+        }
+    }
+
+    /// [v3.6] Enforces the engine's transaction limits before `paths` are appended to a
+    /// delta log already holding `logged` entries. A breach cancels the transaction.
+    fn admit<'a>(&self, logged: usize, paths: impl ExactSizeIterator<Item = &'a str>) -> PyResult<()> {
+        let TxLimits { max_deltas, max_paths } = self.limits;
+        let added = paths.len();
+        let breach = if max_deltas.is_some_and(|max| logged + added > max) {
+            Some(format!("max_deltas={} exceeded ({} deltas logged)", max_deltas.unwrap_or_default(), logged + added))
+        } else if let Some(max) = max_paths {
+            let mut touched = self.touched.relock();
+            let mut last = None;
+            for path in paths {
+                if touched.insert(path.to_string()) {
+                    last = Some(path);
+                }
+            }
+            (touched.len() > max).then(|| format!(
+                "max_paths={max} exceeded ({} distinct paths written, last '{}')", touched.len(), last.unwrap_or_default()
+            ))
         } else {
-            let asyncio = py.import("asyncio")?;
-            asyncio.call_method1("to_thread", (func, args.0))?.unbind()
+            None
         };
+        let Some(breach) = breach else { return Ok(()) };
+        self.cancelled.store(true, Ordering::SeqCst);
+        let message = format!("Transaction {} aborted: {breach}", self.id);
+        crate::audit::log_global_tagged("TX_LIMIT_EXCEEDED", &message, self.tags.as_ref());
+        Err(TransactionLimitError::new_err(message))
+    }
+
+    /// [v3.6] The engine's capability-token signing key.
+    pub(crate) fn capability_key(&self, py: Python) -> Arc<crate::cap_tokens::TokenKey> {
+        self.engine.borrow(py).capability_key.clone()
+    }
+
+    /// [v3.6] Unrecorded throwaway transaction on the same engine (dry runs).
+    pub(crate) fn scratch(&self, py: Python) -> PyResult<Self> {
+        let mut tx = Self::fresh(py, self.engine.clone_ref(py), self.write_timeout_ms)?;
+        tx.recorder = None;
+        Ok(tx)
+    }
+
+    /// Commit half of `__exit__` (the with-block raised nothing).
+    #[allow(clippy::too_many_lines)]
+    fn try_commit(&self, py: Python, handle: &Bound<'_, Self>) -> PyResult<()> {
+        // [v3.6] A cancelled transaction (or one hitting maintenance mode, or open while a
+        // panic poisoned an engine lock) never commits.
+        if let Err(e) = self.ensure_writable(py).and_then(|()| self.lock_epoch.check(py, self.lock_mark)) {
+            self.pending_events.relock().clear();
+            return Err(e);
+        }
+
+        // [v3.6] Idempotent op already committed (a retried job): drop the writes, report the
+        // original version.
+        if let Some(version) = self.committed_op(py)? {
+            let error = format!("Duplicate op_id '{}' (committed in version {version})", self.op_id.as_deref().unwrap_or_default());
+            self.pending_events.relock().clear();
+            self.pending_outbox.relock().clear();
+            if let Some(rec) = &self.recorder {
+                rec.record(py, self.id, "rollback", &[("error", error.into_py(py).into_bound(py))]);
+            }
+            let mut outcome = self.outcome.relock();
+            (outcome.status, outcome.version) = (Some("duplicate"), Some(version));
+            return Ok(());
+        }
+
+        // Enforce Timeout
+        if let Some(start) = self.start_time {
+             let elapsed_ms = crate::clock::monotonic_ms().saturating_sub(start);
+             if elapsed_ms > self.write_timeout_ms {
+                 return Err(WriteTimeoutError::new_err(format!(
+                     "Transaction timed out after {elapsed_ms}ms (limit {}ms)", 
+                     self.write_timeout_ms
+                 )));
+             }
+        }
+
+        let commit_started = Instant::now();
+        let engine = self.engine.bind(py);
         
-        Ok(coro_obj.bind(py).clone())
+        // [v3.1.2] Differential Shadow Merging:
+        // 1. Infer mutations from shadows
+        self.infer_shadow_deltas(py)?;
+        // [v3.6] Read-only fast path: nothing logged or staged, so there is nothing to check,
+        // validate or version - the committed State (and its version) stays as is.
+        if self.is_read_only(py) {
+            // [v3.6] Nothing written, but a tenant's outbox quota still applies.
+            let engine_borrow = engine.borrow();
+            engine_borrow.tenants.check(py, self.tenant.as_deref(), &[], self.pending_outbox.relock().len(), &engine_borrow.glass)?;
+            drop(engine_borrow);
+            self.read_only.store(true, Ordering::SeqCst);
+            self.remember_op(py, engine.borrow().state.bind(py).borrow().version);
+            return self.dispatch_staged(py);
+        }
+        // 2. Apply delta_log to pending_data ([v3.6] or stream it onto copy-on-write zones)
+        let streamed = if self.should_stream(py) {
+            Some(self.stream_deltas(py)?)
+        } else {
+            self.commit(py)?;
+            None
+        };
+        self.faults.check("commit")?;
+
+        // [OCC] Field-level conflict detection (Smart CAS — same policy as compare_and_swap).
+        // Runs after pending_data is fully populated (post-shadow-infer + post-commit).
+        // Raises CAS Version Mismatch → triggers execute() retry loop.
+        if self.start_version > 0 {
+            let conflict = {
+                let engine_borrow = engine.borrow();
+                let current_state_bound = engine_borrow.state.bind(py);
+                let current_state = current_state_bound.borrow();
+                let current_version = current_state.version;
+
+                if current_version == self.start_version {
+                    None
+                } else {
+                    let mut safe = true;
+                    let mut conflict_path: Option<String> = None;
+                    let pending = self.pending_data.bind(py);
+
+                    'outer: for (zone_k, zone_v) in pending.iter() {
+                        let zone_key = zone_k.extract::<String>()?;
+                        if let Ok(inner_dict) = zone_v.downcast::<pyo3::types::PyDict>() {
+                            for (ik, _) in inner_dict.iter() {
+                                let inner_key = ik.extract::<String>()?;
+                                let field_path = format!("{zone_key}.{inner_key}");
+                                if let Some(last_ver) = current_state.key_last_modified.get(field_path.as_str()) {
+                                    if *last_ver > self.start_version {
+                                        safe = false;
+                                        conflict_path = Some(field_path.clone());
+                                        break 'outer;
+                                    }
+                                }
+                            }
+                        } else if let Some(last_ver) = current_state.key_last_modified.get(zone_key.as_str()) {
+                            if *last_ver > self.start_version {
+                                safe = false;
+                                conflict_path = Some(zone_key.clone());
+                            }
+                        }
+                        if !safe { break; }
+                    }
+
+                    if safe {
+                        let stale = |path: &str| current_state.key_last_modified.get(path).is_some_and(|v| *v > self.start_version);
+                        safe = !streamed.iter().flatten().any(|(zone, _, fields)| {
+                            (fields.is_empty() && stale(zone)) || fields.iter().any(|f| stale(&format!("{zone}.{f}")))
+                        });
+                    }
+                    if safe { None } else { Some((self.start_version, current_version, conflict_path)) }
+                }
+                // engine_borrow, current_state_bound, current_state all drop here
+            };
+
+            if let Some((expected, found, path)) = conflict {
+                return Err(crate::errors::version_mismatch(py, crate::messages::CAS_MISMATCH, path.as_deref(), expected, found));
+            }
+        }
+
+        // [v3.6] Conflict armed by `engine.testing.simulate_conflict`.
+        {
+            let mut touched = crate::testing::written_paths(self.pending_data.bind(py))?;
+            touched.extend(crate::testing::written_paths(self.pending_heavy.bind(py))?);
+            for (zone, _, fields) in streamed.iter().flatten() {
+                touched.push(zone.clone());
+                touched.extend(fields.iter().map(|f| format!("{zone}.{f}")));
+            }
+            let engine_borrow = engine.borrow();
+            // [v3.6] Tenant isolation (see tenancy.rs).
+            engine_borrow.tenants.check(py, self.tenant.as_deref(), &touched, self.pending_outbox.relock().len(), &engine_borrow.glass)?;
+            let current_version = engine_borrow.state.bind(py).borrow().version;
+            if let Some(found) = engine_borrow.conflict_sim.take(&touched, current_version) {
+                return Err(crate::errors::version_mismatch(py, crate::messages::CAS_MISMATCH, None, self.start_version, found));
+            }
+        }
+
+        // [v3.6] Two-person rule: writes below approval paths wait for an approver.
+        let parked = if self.admin {
+            Vec::new()
+        } else {
+            let engine_borrow = engine.borrow();
+            let state = engine_borrow.state.bind(py).borrow();
+            self.approvals.extract(py, self.pending_data.bind(py), &state)?
+        };
+
+        // [v3.6] Streamed zones replace the committed ones wholesale; `written` (lineage) lists them.
+        let (written, zones) = match streamed {
+            Some(zones) => {
+                let written = self.pending_data.bind(py).copy()?;
+                for (zone, _, fields) in &zones {
+                    let touched = PyDict::new_bound(py);
+                    for field in fields {
+                        touched.set_item(field, py.None())?;
+                    }
+                    written.set_item(zone, touched)?;
+                }
+                self.streamed.store(true, Ordering::SeqCst);
+                (written, Some(zones))
+            }
+            None => (self.pending_data.bind(py).clone(), None),
+        };
+
+        // [v3.6] Group commit: stage behind the open batch instead of installing a version now.
+        let group = engine.borrow().group.relock().clone();
+        if let Some(group) = group.filter(|_| zones.is_none() && parked.is_empty()) {
+            return crate::group_commit::stage(py, &group, engine, handle, self);
+        }
+
+        let schema_started = Instant::now();
+        let version = Self::install(
+            py, engine, self.pending_data.bind(py), self.pending_heavy.bind(py), self.pending_signal.bind(py), zones,
+            &[(written, self.actor.clone(), Some(self.id), self.tags.clone())],
+        )?;
+        self.stats.relock().schema = schema_started.elapsed();
+        self.remember_op(py, version);
+        if !parked.is_empty() {
+            *self.proposal.relock() = Some(self.approvals.propose(self.id, self.actor.clone(), self.tags.as_ref(), parked));
+        }
+        crate::metrics::record_commit(commit_started, self.delta_log.relock().len());
+
+        self.dispatch_staged(py)
+    }
+
+    /// [v3.6] Installs one new State version: `State.update(data, heavy, signal)` (plus streamed
+    /// zones), schema validation, lineage stamped per writer `(written, actor, tx, tags)`, the
+    /// engine swap, signal dispatch and meta watchers. Returns the new version.
+    #[allow(clippy::similar_names)]
+    pub(crate) fn install(
+        py: Python,
+        engine: &Bound<'_, TheusEngine>,
+        data: &Bound<'_, PyDict>,
+        heavy: &Bound<'_, PyDict>,
+        signal: &Bound<'_, PyList>,
+        zones: Option<Vec<(String, PyObject, Vec<String>)>>,
+        writers: &[CommitWriter<'_>],
+    ) -> PyResult<u64> {
+        let _scope = engine.borrow().lock_epoch.enter();
+        let current_state_obj = engine.getattr("state")?;
+        // Optimistic Update: Create new state version
+        let new_state_obj = current_state_obj.call_method1("update", (data, heavy, signal))?;
+        if let Some(zones) = zones {
+            new_state_obj.downcast::<State>()?.borrow_mut().replace_zones(py, zones)?;
+        }
+
+        // Schema Enforcement (Phase 32.2)
+        engine.borrow().faults.check("schema")?;
+        {
+             let engine_borrow = engine.borrow();
+             let schema_guard = engine_borrow.schema.relock();
+             if let Some(ref schema) = *schema_guard {
+                 // Convert State.data to Dict for Pydantic validation
+                 // We validate the *Resulting* state data to ensure consistency.
+                 
+                 // Access property via getattr, not call_method
+                 let frozen_data = new_state_obj.getattr("data")?;
+                 let mut dict_data = frozen_data.call_method0("to_dict")?;
+                 dict_data = engine_borrow.materialize_placeholders(py, dict_data)?;
+                 
+                 // Pydantic model_validate
+                 if let Err(e) = schema.call_method1(py, "model_validate", (dict_data,)) {
+                      return Err(crate::errors::coded::<crate::config::SchemaViolationError>(py, crate::messages::render(crate::messages::SCHEMA_VIOLATION, &[("error", &e)])));
+                 }
+             }
+        }
+
+        // [v3.6] Tenant usage of the replaced roots; quotas refuse the commit here.
+        let tenants = engine.borrow().tenants.clone();
+        let measured = tenants.prepare(py, &current_state_obj.downcast::<State>()?.borrow(), &new_state_obj.downcast::<State>()?.borrow())?;
+
+        // [v3.0.27] Direct Rust field assignment — replaces stringly-typed call_method1("commit_state", ...).
+        // Closing the OCC bypass: commit_state is no longer exported via #[pymethods].
+        let changed = {
+            let lineage = engine.borrow().lineage.clone();
+            let mut changed = Vec::new();
+            for (written, actor, id, tags) in writers {
+                changed.extend(crate::lineage::stamp(py, &lineage, &current_state_obj, &new_state_obj, Some(written.as_any()), actor.clone(), *id, tags.clone())?);
+            }
+            engine.borrow().store_placeholders(py, &new_state_obj)?;
+            let mut engine_ref = engine.borrow_mut();
+            let history = engine_ref.history.clone();
+            let mut history = history.relock();
+            engine_ref.faults.panic_point();
+            engine_ref.state = new_state_obj.extract::<Py<State>>()?;
+            history.record(py, &engine_ref.state);
+            changed
+        };
+        tenants.committed(measured);
+        engine.borrow().tag_committed_state(py)?;
+
+        // [INC-023] Deferred signal dispatch — fires AFTER data is committed to engine.state.
+        // State.update() above only populated last_signals (Flux latch), no publish yet.
+        // Now that engine.state is updated, subscribers will see consistent state.
+        let committed_state = engine.getattr("state")?;
+        let version = committed_state.getattr("version")?.extract()?;
+        engine.borrow().signals.enqueue(py, signal, version)?;
+        committed_state.call_method1("publish_signals", (signal,))?;
+        let meta_watch = engine.borrow().meta_watch.clone();
+        meta_watch.committed(py, &changed);
+        let (indexes, search, changes) = {
+            let engine_ref = engine.borrow();
+            (engine_ref.indexes.clone(), engine_ref.search.clone(), engine_ref.changes.clone())
+        };
+        let (old, new) = (current_state_obj.downcast::<State>()?.borrow(), committed_state.downcast::<State>()?.borrow());
+        indexes.committed(py, &old, &new, &changed)?;
+        search.committed(py, &old, &new, &changed)?;
+        changes.committed(py, &old, &new, &changed)?;
+        Ok(version)
+    }
+
+    /// [v3.6] True when the transaction logged no delta and staged no explicit update
+    /// (`{zone: {}}` merges are no-ops).
+    fn is_read_only(&self, py: Python) -> bool {
+        let untouched = |d: &Bound<'_, PyDict>| d.values().iter().all(|v| v.downcast::<PyDict>().is_ok_and(pyo3::types::PyDictMethods::is_empty));
+        self.delta_log.relock().is_empty()
+            && untouched(self.pending_data.bind(py))
+            && untouched(self.pending_heavy.bind(py))
+            && self.pending_signal.bind(py).is_empty()
+    }
+
+    /// [v3.6] Version this transaction's `op_id` already committed in. An open commit group
+    /// still holding the op is flushed first.
+    fn committed_op(&self, py: Python) -> PyResult<Option<u64>> {
+        let Some(op) = self.op_id.as_deref() else { return Ok(None) };
+        let engine = self.engine.bind(py);
+        let group = engine.borrow().group.relock().clone();
+        if let Some(group) = group.filter(|g| crate::group_commit::holds_op(py, g, op)) {
+            crate::group_commit::flush(py, &group, engine)?;
+        }
+        Ok(engine.borrow().op_ids.relock().get(op))
+    }
+
+    /// [v3.6] Records this transaction's `op_id` as committed in `version`.
+    fn remember_op(&self, py: Python, version: u64) {
+        if let Some(op) = &self.op_id {
+            self.engine.borrow(py).op_ids.relock().record(op, version);
+        }
+    }
+
+    /// [v3.6] Settles a transaction staged in a commit group: `Ok(version)` dispatches its
+    /// outbox and events, `Err` drops them. Either way the recorder logs the outcome.
+    pub(crate) fn settle(&self, py: Python, outcome: Result<u64, String>) -> PyResult<()> {
+        let mut o = self.outcome.relock();
+        match outcome {
+            Ok(version) => {
+                (o.status, o.version) = (Some("committed"), Some(version));
+                drop(o);
+                self.remember_op(py, version);
+                if let Some(rec) = &self.recorder {
+                    rec.record(py, self.id, "commit", &[("version", version.into_py(py).into_bound(py))]);
+                }
+                self.dispatch_staged(py)
+            }
+            Err(error) => {
+                self.pending_events.relock().clear();
+                self.pending_outbox.relock().clear();
+                if let Some(rec) = &self.recorder {
+                    rec.record(py, self.id, "rollback", &[("error", error.clone().into_py(py).into_bound(py))]);
+                }
+                (o.status, o.error) = (Some("failed"), Some(error));
+                Ok(())
+            }
+        }
+    }
+
+    /// Hands staged outbox messages and domain events over once the commit is settled.
+    #[allow(clippy::unnecessary_wraps)]
+    fn dispatch_staged(&self, py: Python) -> PyResult<()> {
+        let engine = self.engine.bind(py);
+        // Commit Outbox to Engine
+        {
+            let mut pending = self.pending_outbox.relock();
+            // Access Engine Outbox
+            let engine_ref = engine.borrow();
+            let msgs = pending.drain(..).map(|m| self.stamp(m, &engine_ref.determinism)).collect::<Vec<_>>();
+
+            if let Some(tenant) = &self.tenant {
+                engine_ref.tenants.sent(tenant, msgs.len());
+            }
+            crate::testing::on_outbox(engine_ref.outbox_key(), &msgs);
+            engine_ref.outbox.relock().extend(msgs);
+        }
+
+        // [v3.6] Staged domain events — committed state is visible to watchers by now.
+        // NOTE: The commit already happened, so watcher errors go to sys.unraisablehook
+        // instead of masquerading as a transaction failure.
+        let events: Vec<(String, PyObject)> = self.pending_events.relock().drain(..).collect();
+        if !events.is_empty() {
+            let (hub, watchers) = {
+                let engine_ref = engine.borrow();
+                let hub = engine_ref.state.bind(py).borrow().signal.clone();
+                let watchers: Vec<PyObject> = engine_ref.event_watchers.relock().iter().map(|w| w.clone_ref(py)).collect();
+                (hub, watchers)
+            };
+            for (topic, payload) in events {
+                hub.publish(format!("{topic}:{}", payload.bind(py)));
+                for w in &watchers {
+                    if let Err(e) = w.call1(py, (&topic, payload.clone_ref(py))) {
+                        e.write_unraisable_bound(py, Some(w.bind(py)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// [v3.6] What closing without a commit discards: a dict of `tx_id`, `actor`, `reason`,
+    /// `error`, `start_version`, `deltas` (`[{path, op, value, old_value}]`), `shadows`,
+    /// `updates` (roots passed to `update()`), `outbox`, `signals` and `heavy`.
+    /// Stored for `last_rollback_report()` and set as `rollback_report` on the exception.
+    fn record_rollback(&self, py: Python, reason: &str, error: &Bound<'_, PyAny>) -> PyResult<()> {
+        // Writes made straight into shadows only become deltas through inference.
+        let _ = self.infer_shadow_deltas(py);
+        let deltas = PyList::empty_bound(py);
+        for entry in self.delta_log.relock().iter() {
+            deltas.append(entry.summary(py)?)?;
+        }
+        let mut shadows: Vec<String> = self.full_path_map.relock().keys().cloned().collect();
+        shadows.sort();
+        let report = PyDict::new_bound(py);
+        report.set_item("tx_id", self.id)?;
+        report.set_item("actor", self.actor.as_deref())?;
+        report.set_item("reason", reason)?;
+        report.set_item("error", error.repr()?)?;
+        report.set_item("start_version", self.start_version)?;
+        report.set_item("deltas", deltas)?;
+        report.set_item("shadows", shadows)?;
+        report.set_item("updates", self.pending_data.bind(py).keys())?;
+        report.set_item("outbox", self.pending_outbox.relock().len())?;
+        report.set_item("signals", self.pending_signal.bind(py).len())?;
+        report.set_item("heavy", self.pending_heavy.bind(py).keys())?;
+        let _ = error.setattr("rollback_report", &report);
+        *self.rollback.relock() = Some(report.into_any().unbind());
+        Ok(())
+    }
+
+    /// [v3.6] Freeze what a commit would write right now (see `checkpoints`). Inference runs
+    /// on the side: its deltas go into the checkpoint, not the log.
+    fn take_checkpoint(&self, py: Python) -> PyResult<crate::checkpoints::Checkpoint> {
+        let logged = self.delta_log.relock().len();
+        *self.shadows_inferred.relock() = false;
+        let inferred = self.infer_shadow_deltas(py);
+        let entries: Vec<crate::delta::DeltaEntry> = {
+            let mut log = self.delta_log.relock();
+            let entries = log.clone();
+            log.truncate(logged);
+            entries
+        };
+        *self.shadows_inferred.relock() = false;
+        inferred?;
+        let deepcopy = py.import_bound("copy")?.getattr("deepcopy")?;
+        let deltas = entries.iter().map(|e| crate::checkpoints::freeze(py, &deepcopy, e)).collect::<PyResult<Vec<_>>>()?;
+        let index = self.checkpoint.relock().as_ref().map_or(1, |c| c.index + 1);
+        Ok(crate::checkpoints::Checkpoint {
+            index,
+            deltas,
+            pending_data: deepcopy.call1((self.pending_data.bind(py),))?.downcast_into::<PyDict>()?.unbind(),
+            pending_heavy: self.pending_heavy.bind(py).copy()?.unbind(),
+            outbox: self.pending_outbox.relock().len(),
+            signals: self.pending_signal.bind(py).len(),
+            events: self.pending_events.relock().len(),
+        })
+    }
+
+    /// [v3.6] The block raised after a checkpoint: put the transaction back to that point
+    /// and commit it. The outcome goes to the rollback report, never over the exception.
+    fn commit_checkpoint(&self, py: Python, handle: &Bound<'_, Self>, checkpoint: crate::checkpoints::Checkpoint) -> PyResult<()> {
+        let (index, deltas) = (checkpoint.index, checkpoint.deltas.len());
+        *self.delta_log.relock() = checkpoint.deltas;
+        // The checkpoint already holds the shadow mutations made before it.
+        *self.shadows_inferred.relock() = true;
+        let pending_data = self.pending_data.bind(py);
+        pending_data.clear();
+        pending_data.update(checkpoint.pending_data.bind(py).as_mapping())?;
+        let pending_heavy = self.pending_heavy.bind(py);
+        pending_heavy.clear();
+        pending_heavy.update(checkpoint.pending_heavy.bind(py).as_mapping())?;
+        self.pending_outbox.relock().truncate(checkpoint.outbox);
+        let signals = self.pending_signal.bind(py);
+        signals.del_slice(checkpoint.signals, signals.len())?;
+        self.pending_events.relock().truncate(checkpoint.events);
+
+        let result = self.try_commit(py, handle);
+        let staged = result.is_ok() && self.outcome.relock().status.is_some();
+        let version = self.engine.borrow(py).state.bind(py).borrow().version;
+        let error = result.as_ref().err().map(ToString::to_string);
+        if !staged {
+            let mut outcome = self.outcome.relock();
+            match &error {
+                None => (outcome.status, outcome.version) = (Some("partial"), Some(version)),
+                Some(e) => (outcome.status, outcome.error) = (Some("failed"), Some(e.clone())),
+            }
+        }
+        let message = match &error {
+            None => format!("Transaction #{} committed checkpoint {index} ({deltas} deltas) at version {version} after a failure", self.id),
+            Some(e) => format!("Transaction #{} could not commit checkpoint {index}: {e}", self.id),
+        };
+        crate::audit::log_global_tagged("TX_PARTIAL_COMMIT", &message, self.tags.as_ref());
+
+        if let Some(report) = self.rollback.relock().as_ref() {
+            let info = PyDict::new_bound(py);
+            info.set_item("index", index)?;
+            info.set_item("deltas", deltas)?;
+            info.set_item("version", error.is_none().then_some(version))?;
+            info.set_item("error", error)?;
+            report.bind(py).set_item("checkpoint", info)?;
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Drop the references this transaction holds on managed heavy handles.
+    fn unpin_heavy(&self, py: Python) {
+        let pins = std::mem::take(&mut *self.heavy_pins.relock());
+        for id in pins {
+            self.heavy_store.decref(py, id);
+        }
+    }
+
+    /// [v3.6] Report this (committed) transaction if it exceeded the slow-commit threshold.
+    /// NOTE: The commit already happened, so callback errors go to sys.unraisablehook.
+    fn report_slow_commit(&self, py: Python) -> PyResult<()> {
+        let (threshold_ms, callback) = {
+            let engine = self.engine.borrow(py);
+            let policy = engine.slow_commit.relock();
+            let Some(policy) = policy.as_ref() else { return Ok(()) };
+            (policy.threshold_ms, policy.callback.as_ref().map(|cb| cb.clone_ref(py)))
+        };
+        let commit_ms = self.stats.relock().commit.as_secs_f64() * 1000.0;
+        if commit_ms <= threshold_ms {
+            return Ok(());
+        }
+        let info = self.stats(py)?.into_bound(py).downcast_into::<PyDict>()?;
+        info.set_item("tx_id", self.id)?;
+        info.set_item("threshold_ms", threshold_ms)?;
+        info.set_item("tags", crate::tags::to_dict(py, self.tags.as_ref())?)?;
+        let message = format!("Slow commit: transaction #{} took {commit_ms:.1}ms (threshold {threshold_ms}ms): {}", self.id, info.repr()?);
+        crate::audit::log_global_tagged("SLOW_COMMIT", &message, self.tags.as_ref());
+        if let Some(cb) = callback {
+            if let Err(e) = cb.call1(py, (info,)) {
+                e.write_unraisable_bound(py, Some(cb.bind(py)));
+            }
+        } else {
+            let extra = PyDict::new_bound(py);
+            extra.set_item("tx_stats", info)?;
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("extra", extra)?;
+            py.import_bound("logging")?.call_method1("getLogger", ("theus.engine",))?
+                .call_method("warning", (message,), Some(&kwargs))?;
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Messages inherit the transaction's tags unless they carry their own, and a
+    /// seeded engine re-draws generated idempotency keys from its RNG.
+    fn stamp(&self, mut msg: OutboxMsg, rng: &crate::determinism::Determinism) -> OutboxMsg {
+        if msg.tags.is_none() {
+            msg.tags.clone_from(&self.tags);
+        }
+        if msg.generated_key && rng.seed().is_some() {
+            msg.idempotency_key = rng.uuid4();
+        }
+        msg
+    }
+
+    fn record_set(&self, py: Python, entry: &crate::delta::DeltaEntry, inferred: bool) {
+        let Some(rec) = &self.recorder else { return };
+        let value = entry.value.as_ref().map_or_else(|| py.None(), |v| v.clone_ref(py));
+        rec.record(py, self.id, "set", &[
+            ("path", pyo3::types::PyString::new_bound(py, &entry.path).into_any()),
+            ("value", value.into_bound(py)),
+            ("inferred", inferred.into_py(py).into_bound(py)),
+        ]);
+    }
+
+    /// [v3.6] Streaming commit applies (threshold reached, no approval paths).
+    fn should_stream(&self, py: Python) -> bool {
+        let Some(min_deltas) = *self.engine.borrow(py).streaming_commit.relock() else { return false };
+        self.delta_log.relock().len() >= min_deltas && self.approvals.paths().is_empty()
+    }
+
+    /// [v3.6] Streaming half of `commit()`: same ordering and explicit-update precedence, but
+    /// Data deltas land on copy-on-write copies of the committed zones (Heavy deltas still go
+    /// to `pending_heavy`). Returns `(zone, new zone, touched top-level keys)`.
+    fn stream_deltas(&self, py: Python) -> PyResult<Vec<(String, PyObject, Vec<String>)>> {
+        let mut explicit_paths: std::collections::HashSet<String> = std::collections::HashSet::new();
+        Self::collect_pending_paths(py, self.pending_data.bind(py).as_any(), "", &mut explicit_paths)?;
+
+        let committed = self.engine.borrow(py).state.clone_ref(py);
+        let committed = committed.bind(py).borrow();
+        let mut zones: std::collections::BTreeMap<String, (Bound<PyAny>, std::collections::BTreeSet<String>)> = std::collections::BTreeMap::new();
+        let mut owned: std::collections::HashSet<usize> = std::collections::HashSet::new();
+
+        let log = self.delta_log.relock();
+        let mut sorted_entries: Vec<&crate::delta::DeltaEntry> = log.iter()
+            .filter(|e| &*e.op == crate::delta::DeltaOp::Set.as_str() && e.value.is_some())
+            .collect();
+        sorted_entries.sort_by_key(|e| e.path.len());
+
+        for entry in sorted_entries {
+            let Some(new_val) = &entry.value else { continue };
+            let norm_entry_path = Self::normalize_path(&entry.path);
+            if explicit_paths.iter().any(|p| {
+                p == &norm_entry_path || p.starts_with(&format!("{norm_entry_path}.")) || norm_entry_path.starts_with(&format!("{p}."))
+            }) {
+                continue;
+            }
+            if crate::zones::resolve_zone(&entry.path) == crate::zones::ContextZone::Heavy {
+                set_nested_value(py, &self.pending_heavy, entry.path.strip_prefix("heavy.").unwrap_or(&entry.path), new_val)?;
+                continue;
+            }
+            let (zone, rest) = norm_entry_path.split_once('.').unwrap_or((&norm_entry_path, ""));
+            if rest.is_empty() {
+                let value = new_val.bind(py).getattr("supervisor_target").unwrap_or_else(|_| new_val.bind(py).clone());
+                let fields = match value.downcast::<PyDict>() {
+                    Ok(d) => d.keys().iter().map(|k| k.str().map(|s| s.to_string())).collect::<PyResult<_>>()?,
+                    Err(_) => std::collections::BTreeSet::new(),
+                };
+                owned.insert(value.as_ptr() as usize);
+                zones.insert(zone.to_string(), (value, fields));
+                continue;
+            }
+            let slot = match zones.entry(zone.to_string()) {
+                std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+                std::collections::btree_map::Entry::Vacant(e) => {
+                    let base = match committed.data.get(zone) {
+                        Some(z) => crate::structures_helper::shallow_copy(py, z.bind(py))?,
+                        None => PyDict::new_bound(py).into_any(),
+                    };
+                    owned.insert(base.as_ptr() as usize);
+                    e.insert((base, std::collections::BTreeSet::new()))
+                }
+            };
+            let field = rest.split('.').next().unwrap_or(rest);
+            slot.1.insert(field.to_string());
+            // Keep the original (bracketed) form below the zone so list indices stay indices.
+            let sub_path = entry.path.strip_prefix(zone).map_or(rest, |p| p.trim_start_matches('.'));
+            crate::structures_helper::set_nested_value_cow(py, &slot.0, sub_path, new_val, &mut owned)?;
+        }
+        Ok(zones.into_iter().map(|(zone, (value, fields))| (zone, value.unbind(), fields.into_iter().collect())).collect())
+    }
+
+    /// [v3.6] Caps a break-glass grant of this transaction's engine gives `path`, if any.
+    pub(crate) fn glass_caps(&self, py: Python, path: &str) -> Option<u8> {
+        if !crate::break_glass::any_open() {
+            return None;
+        }
+        self.engine.try_borrow(py).ok()?.glass.caps(path)
+    }
+
+    /// [v3.6] Zone/capability gate for one `update_many` prefix (same rules as proxy writes).
+    fn check_bulk_write(&self, py: Python, path: &str, zone: &crate::zones::ContextZone) -> PyResult<()> {
+        let caps = self.glass_caps(py, path)
+            .or_else(|| crate::zones::get_physics_override(path))
+            .unwrap_or_else(|| crate::zones::get_zone_physics(zone));
+        let bypass = self.admin && !crate::zones::is_absolute_ceiling(zone);
+        if !bypass && caps & crate::zones::CAP_UPDATE == 0 {
+            return Err(pyo3::exceptions::PyPermissionError::new_err(format!(
+                "Permission Denied: UPDATE capability required for '{path}'. (Current Lens: {caps:04b})"
+            )));
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Write gate used by proxies and `update()`: maintenance mode + watchdog.
+    pub fn ensure_writable(&self, py: Python) -> PyResult<()> {
+        self.engine.borrow(py).ensure_writable()?;
+        self.check_watchdog(py)
+    }
+
+    /// [v3.6] Evaluate the engine watchdog; fire the soft-deadline callback once.
+    /// Returns Err(TransactionCancelledError) once the transaction is cancelled.
+    pub fn check_watchdog(&self, py: Python) -> PyResult<()> {
+        if !self.cancelled.load(Ordering::SeqCst) {
+            if let Some(start) = self.start_time {
+                #[allow(clippy::cast_possible_truncation)]
+                let elapsed_ms = crate::clock::monotonic_ms().saturating_sub(start);
+                let fire = {
+                    let engine = self.engine.borrow(py);
+                    let cfg = engine.watchdog.relock();
+                    match cfg.as_ref() {
+                        Some(c) if elapsed_ms > c.soft_deadline_ms && !self.watchdog_fired.swap(true, Ordering::SeqCst) => {
+                            Some((c.soft_deadline_ms, c.callback.as_ref().map(|cb| cb.clone_ref(py)), c.abort))
+                        }
+                        _ => None,
+                    }
+                };
+                if let Some((deadline_ms, callback, abort)) = fire {
+                    if abort {
+                        self.cancelled.store(true, Ordering::SeqCst);
+                    }
+                    let info = PyDict::new_bound(py);
+                    info.set_item("elapsed_ms", elapsed_ms)?;
+                    info.set_item("deadline_ms", deadline_ms)?;
+                    info.set_item("action", if abort { "abort" } else { "warn" })?;
+                    if let Some(cb) = callback { cb.call1(py, (info,))?; } else {
+                        let msg = format!("Transaction exceeded soft deadline: {elapsed_ms}ms > {deadline_ms}ms");
+                        py.import_bound("warnings")?.call_method1("warn", (msg, py.get_type_bound::<pyo3::exceptions::PyRuntimeWarning>()))?;
+                    }
+                }
+            }
+        }
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(TransactionCancelledError::new_err("Transaction cancelled (watchdog deadline exceeded or tx.cancel() called)"));
+        }
+        Ok(())
     }
-}
-
-// Transaction
-// Removed duplicate `pyo3::types` import
-// PyList should be imported at top level or merged.
-
-// ... 
-
-#[pyclass(module = "theus_core")]
-pub struct Transaction {
-    engine: Py<TheusEngine>,
-    pending_data: Py<PyDict>,
-    pending_heavy: Py<PyDict>,
-    pending_signal: Py<PyList>, // Changed from PyDict to PyList
-    pending_outbox: Arc<Mutex<Vec<OutboxMsg>>>,
-    start_time: Option<Instant>,
-    start_version: u64,
-    write_timeout_ms: u64,
-    // [v3.1 Zero Trust] Unified Delta Log
-    pub delta_log: Arc<Mutex<Vec<crate::delta::DeltaEntry>>>, 
-    pub shadow_cache: Arc<Mutex<std::collections::HashMap<usize, (PyObject, PyObject)>>>, // id -> (original, shadow)
-    pub path_to_shadow: Arc<Mutex<std::collections::HashMap<String, PyObject>>>, // root -> shadow (for legacy commit)
-    pub full_path_map: Arc<Mutex<std::collections::HashMap<String, PyObject>>>, // full_path -> shadow (for diff merging)
-    pub shadows_inferred: Arc<Mutex<bool>>, // [v3.3] Prevent double-inference hangs
-}
 
-impl Transaction {
     /// Collect all explicit pending paths from a nested dict.
     ///
     /// Example:
-    /// {"domain": {"documents": {...}, "`outbox_queue"`: [...]}}
-    /// => "domain", "domain.documents", "`domain.outbox_queue`"
+    /// {"domain": {"documents": {...}, `"outbox_queue"`: [...]}}
+    /// => "domain", "domain.documents", `"domain.outbox_queue"`
+    #[allow(clippy::only_used_in_recursion)]
     fn collect_pending_paths(
         py: Python,
         obj: &Bound<'_, PyAny>,
@@ -416,6 +3350,14 @@ impl Transaction {
         Ok(())
     }
 
+    /// [v3.6] `path` rooted under this transaction's tenant (unchanged when unscoped).
+    fn scoped_path<'a>(&self, path: &'a str) -> PyResult<std::borrow::Cow<'a, str>> {
+        match &self.tenant {
+            Some(tenant) => Ok(crate::tenancy::scoped(tenant, path)?.into()),
+            None => Ok(path.into()),
+        }
+    }
+
     /// Normalize path representation for robust overlap checks.
     /// Converts bracket notation (a[b][c]) into dotted form (a.b.c).
     fn normalize_path(path: &str) -> String {
@@ -427,29 +3369,71 @@ impl Transaction {
 #[pymethods]
 impl Transaction {
     #[new]
-    #[pyo3(signature = (engine=None, write_timeout_ms=5000))]
-    fn new(py: Python, engine: Option<Py<TheusEngine>>, write_timeout_ms: u64) -> PyResult<Self> {
+    #[pyo3(signature = (engine=None, write_timeout_ms=5000, actor=None, admin=false, tags=None, isolation=None, op_id=None, partial_commit=false, provisional=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(py: Python, engine: Option<Py<TheusEngine>>, write_timeout_ms: u64, actor: Option<String>, admin: bool, tags: Option<&Bound<'_, PyDict>>, isolation: Option<&str>, op_id: Option<String>, partial_commit: bool, provisional: bool) -> PyResult<Self> {
         let engine_obj = if let Some(e) = engine { e } else {
             let engine_struct = TheusEngine::new(py)?;
             Py::new(py, engine_struct)?
         };
 
-        Ok(Transaction {
-            engine: engine_obj,
-            pending_data: PyDict::new_bound(py).unbind(),
-            pending_heavy: PyDict::new_bound(py).unbind(),
-            pending_signal: PyList::empty_bound(py).unbind(), // Init empty list
-            pending_outbox: Arc::new(Mutex::new(Vec::new())),
-            start_time: None,
-            start_version: 0,
-            write_timeout_ms,
-            delta_log: Arc::new(Mutex::new(Vec::new())),
-            shadow_cache: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            path_to_shadow: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            full_path_map: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            shadows_inferred: Arc::new(Mutex::new(false)),
-        })
+        let tags = crate::tags::from_py(tags)?;
+        let mut tx = Transaction::fresh(py, engine_obj, write_timeout_ms)?;
+        tx.actor = actor;
+        tx.admin = admin;
+        tx.tags = tags;
+        if let Some(level) = isolation {
+            tx.repeatable = parse_isolation(level)?;
+        }
+        tx.op_id = op_id;
+        tx.partial_commit = partial_commit;
+        if provisional {
+            tx.share_pending(py);
+        }
+        Ok(tx)
+    }
+
+    #[getter]
+    fn actor(&self) -> Option<String> {
+        self.actor.clone()
+    }
+
+    /// [v3.6] Idempotency key: a transaction whose `op_id` already committed is a no-op.
+    #[getter]
+    fn op_id(&self) -> Option<String> {
+        self.op_id.clone()
+    }
+
+    /// [v3.6] Record a contract denial decided outside the Rust guards (the Python
+    /// `ContextGuard`) in this transaction's trace, if its run is being traced.
+    fn trace_denied(&self, path: String, access: &str) {
+        let access = if access == "read" { "read" } else { "write" };
+        crate::trace::record_denied(self.id, path, access, "contract");
+    }
 
+    /// [v3.6] True while a break-glass grant of this transaction's engine covers `path` (the
+    /// Python `ContextGuard` uses it to stop hiding Private fields).
+    fn break_glass_covers(&self, py: Python, path: &str) -> bool {
+        self.glass_caps(py, path).is_some()
+    }
+
+    /// [v3.6] Correlation tags, stamped onto this tx's deltas, outbox messages, audit events
+    /// and lineage entries.
+    #[getter]
+    fn tags<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        crate::tags::to_dict(py, self.tags.as_ref())
+    }
+
+    /// [v3.6] The engine's blob store; blobs put here survive `gc_blobs` while this tx is open.
+    #[getter]
+    pub(crate) fn blobs(&self) -> crate::blobs::BlobHandle {
+        crate::blobs::BlobHandle::new(self.blobs.clone(), Some(self.id))
+    }
+
+    /// [v3.6] Id of the proposal this tx's commit parked (writes awaiting approval), if any.
+    #[getter]
+    fn proposal(&self) -> Option<u64> {
+        *self.proposal.relock()
     }
     
     // ... getters ...
@@ -457,6 +3441,7 @@ impl Transaction {
     fn outbox(&self) -> OutboxCollector {
         OutboxCollector {
             buffer: self.pending_outbox.clone(),
+            metrics: None,
         }
     }
 
@@ -485,40 +3470,175 @@ impl Transaction {
         self.pending_signal.clone_ref(py).into_py(py)
     }
 
+    /// [v3.6] True once the watchdog (action="abort") or `cancel()` stopped this transaction.
+    /// Long-running processes should poll this and return early.
+    #[getter]
+    fn cancelled(&self, py: Python) -> bool {
+        self.check_watchdog(py).is_err()
+    }
+
+    #[getter]
+    fn isolation(&self) -> &'static str {
+        isolation_name(self.repeatable)
+    }
+
+    /// [v3.6] State pinned at `__enter__` under repeatable reads (None otherwise or once closed).
+    /// Always None for tenant transactions: the snapshot spans every tenant (use `read()`).
+    #[getter]
+    fn snapshot(&self, py: Python) -> Option<Py<State>> {
+        if self.tenant.is_some() {
+            return None;
+        }
+        self.snapshot.relock().as_ref().map(|s| s.clone_ref(py))
+    }
+
+    /// [v3.6] Value at `path` ("domain.cfg.x", "heavy.frame") as of the pinned snapshot, or
+    /// the latest commit under read-committed isolation; containers come back as read-only
+    /// proxies. Pending writes of this transaction are not visible.
+    #[pyo3(signature = (path, default=None))]
+    fn read(&self, py: Python, path: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+        let path = self.scoped_path(path)?;
+        let state = self.read_state(py);
+        let state = state.bind(py).borrow();
+        crate::pins::read_path(py, &state, &path, default)
+    }
+
+    /// [v3.6] Engine-unique transaction id (matches `engine.open_transactions()`).
+    #[getter]
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// [v3.6] Cooperatively cancel: subsequent writes raise and the commit is rejected.
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// [v3.6] Stage a domain event. Published to the signal hub and event watchers
+    /// only after this transaction commits; discarded on rollback.
+    fn emit(&self, topic: String, payload: PyObject) {
+        self.pending_events.relock().push((topic, payload));
+    }
+
     #[pyo3(signature = (data=None, heavy=None, signal=None))]
+    #[allow(clippy::needless_pass_by_value)]
     fn update(&self, py: Python, data: Option<PyObject>, heavy: Option<PyObject>, signal: Option<PyObject>) -> PyResult<()> {
-        if let Some(d) = data {
+        self.ensure_writable(py)?;
+        // [v3.6] Tenant transactions: data is rooted under tenants.<name>; heavy / signal are engine-wide.
+        let data = match (&self.tenant, data) {
+            (Some(_), _) if heavy.is_some() || signal.is_some() => {
+                return Err(ContextError::new_err("Tenant transactions can only update data (heavy and signal are engine-wide)"));
+            }
+            (Some(tenant), Some(d)) => {
+                let scoped = PyDict::new_bound(py);
+                scoped.set_item(tenant, d)?;
+                let root = PyDict::new_bound(py);
+                root.set_item(crate::tenancy::ROOT, scoped)?;
+                Some(root.into_any().unbind())
+            }
+            (_, data) => data,
+        };
+        // [v3.6] Field encryption covers update payloads too (see field_crypto.rs).
+        let data = data.map(|d| self.middleware.seal(py, "", d.bind(py))).transpose()?;
+        if let Some(d) = &data {
              let d_bound = d.bind(py);
              if let Ok(d_dict) = d_bound.downcast::<PyDict>() {
                  // [FIX v3.1.2] Use Deep In-Place Update to prevent "Silent Overwrite" within transaction
                  crate::structures_helper::deep_update_inplace(py, self.pending_data.bind(py), d_dict)?;
+                 crate::profiler::record_update("", d_dict);
              } else {
                  return Err(ContextError::new_err("update data must be a dict"));
              }
         }
-        if let Some(h) = heavy {
+        if let Some(h) = &heavy {
              let h_bound = h.bind(py);
              if let Ok(h_dict) = h_bound.downcast::<PyDict>() {
                  // [FIX v3.1.2] Deep In-Place Update for Heavy Zone too
                  crate::structures_helper::deep_update_inplace(py, self.pending_heavy.bind(py), h_dict)?;
+                 crate::profiler::record_update("", h_dict);
              } else {
                  return Err(ContextError::new_err("heavy update data must be a dict"));
              }
         }
-        if let Some(s) = signal {
+        if let Some(s) = &signal {
              // For signals, we append the delta dict to the list to preserve sequence
              let s_bound = s.bind(py);
              self.pending_signal.bind(py).append(s_bound)?;
         }
+        if let Some(rec) = &self.recorder {
+            let fields: Vec<(&str, Bound<PyAny>)> = [("data", &data), ("heavy", &heavy), ("signal", &signal)]
+                .into_iter()
+                .filter_map(|(k, v)| v.as_ref().map(|v| (k, v.bind(py).clone())))
+                .collect();
+            rec.record(py, self.id, "update", &fields);
+        }
         Ok(())
     }
     
+    /// [v3.6] Bulk write: `tx.update_many([("domain.users.u1.score", 3), ...])`. Paths (dict
+    /// keys, dotted or bracketed) are grouped by parent; each parent gets one zone/capability
+    /// check (UPDATE required; admin transactions bypass all but CONSTANT), then every value is
+    /// merged into the pending buffers in one pass and logged as a compact delta (no old value).
+    /// Returns the number of writes.
+    fn update_many(&self, py: Python, pairs: &Bound<'_, PyAny>) -> PyResult<usize> {
+        self.ensure_writable(py)?;
+        let mut writes: Vec<(String, PyObject)> = Vec::new();
+        for pair in pairs.iter()? {
+            let (path, value): (String, PyObject) = pair?.extract()?;
+            let path = Self::normalize_path(&self.scoped_path(&path)?);
+            if path.split('.').any(str::is_empty) {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!("Invalid path '{path}' in update_many")));
+            }
+            let value = self.middleware.seal(py, &path, value.bind(py))?;
+            writes.push((path, value));
+        }
+
+        let mut checked: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        for (path, _) in &writes {
+            let (parent, leaf) = path.rsplit_once('.').unwrap_or(("", path.as_str()));
+            let leaf_zone = crate::zones::resolve_zone(leaf);
+            if leaf_zone != crate::zones::ContextZone::Data {
+                self.check_bulk_write(py, path, &leaf_zone)?;
+            }
+            if !parent.is_empty() && checked.insert(parent) {
+                self.check_bulk_write(py, parent, &crate::zones::resolve_zone(parent))?;
+            }
+        }
+
+        let mut log = self.delta_log.relock();
+        self.admit(log.len(), writes.iter().map(|(p, _)| p.as_str()))?;
+        log.reserve(writes.len());
+        for (path, value) in &writes {
+            let heavy = crate::zones::resolve_zone(path) == crate::zones::ContextZone::Heavy;
+            let (target, target_path) = match path.strip_prefix("heavy.") {
+                Some(rest) if heavy => (&self.pending_heavy, rest),
+                _ => (&self.pending_data, path.as_str()),
+            };
+            set_nested_value(py, target, target_path, value)?;
+            let entry = crate::delta::DeltaEntry {
+                path: crate::intern::intern(path),
+                op: crate::intern::intern(crate::delta::DeltaOp::Set.as_str()),
+                value: Some(value.clone_ref(py)),
+                old_value: None,
+                target: None,
+                key: None,
+                tags: self.tags.clone(),
+            };
+            crate::profiler::record_write(path);
+            if self.recorder.is_some() {
+                self.record_set(py, &entry, false);
+            }
+            log.push(entry);
+        }
+        Ok(writes.len())
+    }
+
     /// Get shadow updates keyed by root path (e.g., 'domain' -> `shadow_dict`)
     /// This extracts all modified root-level objects for committing to State.
     fn get_shadow_updates(&self, py: Python) -> PyResult<PyObject> {
         let result = PyDict::new_bound(py);
-        let path_map = self.path_to_shadow.lock().unwrap();
-        let delta_log = self.delta_log.lock().unwrap();
+        let path_map = self.path_to_shadow.relock();
+        let delta_log = self.delta_log.relock();
         
         // Collect unique root paths from delta_log (entries that were actually mutated)
         let mut modified_roots: std::collections::HashSet<String> = std::collections::HashSet::new();
@@ -554,10 +3674,10 @@ impl Transaction {
         
         // 1. Replay Delta Log (from Proxies & Shadow Inference)
         {
-            let delta_log = self.delta_log.lock().unwrap();
+            let delta_log = self.delta_log.relock();
             for entry in delta_log.iter() {
                 // Only consider SET operations with a value
-                if entry.op == "SET" {
+                if &*entry.op == crate::delta::DeltaOp::Set.as_str() {
                     if let Some(ref new_val) = entry.value {
                          crate::structures_helper::set_nested_value(py, &result, &entry.path, new_val)?;
                     }
@@ -578,23 +3698,67 @@ impl Transaction {
     /// [v3.1.2] Expose raw delta log for strict contract validation
     #[allow(clippy::unnecessary_wraps)]
     fn get_delta_log(&self, _py: Python) -> PyResult<Vec<String>> {
-        let delta_log = self.delta_log.lock().unwrap();
+        let delta_log = self.delta_log.relock();
         // Return only paths, values not needed for validation usually
-        let paths: Vec<String> = delta_log.iter().map(|e| e.path.clone()).collect();
+        let paths: Vec<String> = delta_log.iter().map(|e| e.path.to_string()).collect();
         Ok(paths)
     }
 
+    /// [v3.6] True when the commit took the streaming path (`engine.set_streaming_commit`).
+    #[getter]
+    fn streamed(&self) -> bool {
+        self.streamed.load(Ordering::SeqCst)
+    }
+
+    /// [v3.6] `{shadows, shadow_copy_ms, shadow_bytes, shadow_evictions, shadow_hash_skips,
+    /// deltas, inference_ms, schema_ms, commit_ms}`: shadow copies made (and time spent
+    /// copying), estimated bytes of the shadows held and evicted under `set_shadow_cache_limit`
+    /// (bytes are 0 when unbounded), shadows skipped by `set_shadow_hashing`, deltas logged,
+    /// and the time spent inferring shadow deltas, validating the schema and committing
+    /// (`__exit__`).
+    fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+        let stats = self.stats.relock();
+        let out = PyDict::new_bound(py);
+        out.set_item("shadows", stats.shadows)?;
+        out.set_item("shadow_copy_ms", ms(stats.shadow_copy))?;
+        {
+            let budget = self.shadow_budget.relock();
+            out.set_item("shadow_bytes", budget.bytes())?;
+            out.set_item("shadow_evictions", budget.evicted())?;
+        }
+        out.set_item("shadow_hash_skips", stats.hash_skips)?;
+        out.set_item("deltas", self.delta_log.relock().len())?;
+        out.set_item("inference_ms", ms(stats.inference))?;
+        out.set_item("schema_ms", ms(stats.schema))?;
+        out.set_item("commit_ms", ms(stats.commit))?;
+        Ok(out.into_any().unbind())
+    }
+
+    /// [v3.6] True when the transaction wrote nothing and closed without committing
+    /// (`engine.state` and its version are unchanged).
+    #[getter]
+    fn read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    /// [v3.6] Logged deltas as `DeltaEntry` objects (each carrying this tx's `tags`).
+    #[getter]
+    fn deltas(&self) -> Vec<crate::delta::DeltaEntry> {
+        self.delta_log.relock().clone()
+    }
+
     /// [v3.3] Manual Flush for Flux Engine / `execute()`
     #[allow(clippy::unnecessary_wraps)]
     fn flush_outbox(&self, py: Python) -> PyResult<()> {
-        let mut pending = self.pending_outbox.lock().unwrap();
+        let mut pending = self.pending_outbox.relock();
         if pending.is_empty() { return Ok(()); }
         
-        let msgs = pending.drain(..).collect::<Vec<_>>();
-        
         let engine = self.engine.bind(py);
         let engine_ref = engine.borrow();
-        engine_ref.outbox.lock().unwrap().extend(msgs);
+        let msgs = pending.drain(..).map(|m| self.stamp(m, &engine_ref.determinism)).collect::<Vec<_>>();
+        crate::testing::on_outbox(engine_ref.outbox_key(), &msgs);
+        engine_ref.outbox.relock().extend(msgs);
         Ok(())
     }
 
@@ -602,185 +3766,172 @@ impl Transaction {
 
     #[allow(clippy::unnecessary_wraps)]
     fn __enter__(mut slf: PyRefMut<Self>, py: Python) -> PyResult<Py<Self>> {
-        slf.start_time = Some(Instant::now());
+        slf.start_time = Some(crate::clock::monotonic_ms());
         // [OCC] Capture state version at transaction open — baseline for conflict detection
         let engine = slf.engine.bind(py);
         let engine_borrow = engine.borrow();
-        slf.start_version = engine_borrow.state.bind(py).borrow().version;
+        let state = engine_borrow.state.bind(py).borrow();
+        slf.start_version = state.version;
+        if slf.repeatable {
+            *slf.snapshot.relock() = Some(engine_borrow.state.clone_ref(py));
+        }
+        // [v3.6] Managed heavy values seen by this tx stay alive until it closes.
+        if !slf.heavy_store.is_empty() {
+            let pins = slf.heavy_store.pin_all(py, state.heavy.values().map(std::convert::AsRef::as_ref));
+            slf.heavy_pins.relock().extend(pins);
+        }
+        drop(state);
         drop(engine_borrow);
+        if let Some(rec) = &slf.recorder {
+            rec.record(py, slf.id, "begin", &[
+                ("start_version", slf.start_version.into_py(py).into_bound(py)),
+                ("write_timeout_ms", slf.write_timeout_ms.into_py(py).into_bound(py)),
+            ]);
+        }
         Ok(slf.into())
     }
 
     #[allow(clippy::needless_pass_by_value)]
     fn __exit__(
-        &self, 
+        slf: &Bound<'_, Self>,
         py: Python, 
         exc_type: Option<PyObject>, 
-        _exc_value: Option<PyObject>, 
+        exc_value: Option<PyObject>, 
         _traceback: Option<PyObject>
     ) -> PyResult<()> {
-        
-        if exc_type.is_some() {
+        let this = slf.borrow();
+        let _scope = this.lock_epoch.enter();
+        this.closed.store(true, Ordering::SeqCst);
+        this.engine.borrow(py).open_txs.relock().remove(&this.id);
+        this.unpin_heavy(py);
+        this.snapshot.relock().take();
+        this.proxy_pool.relock_reset().clear();
+
+        if let Some(exc) = exc_type {
+            let error = exc_value.as_ref().map_or_else(|| exc.bind(py).clone(), |v| v.bind(py).clone());
+            this.record_rollback(py, "exception", &error)?;
+            let checkpoint = this.checkpoint.relock().take();
+            if let Some(checkpoint) = checkpoint {
+                this.commit_checkpoint(py, slf, checkpoint)?;
+            }
+            this.pending_events.relock().clear();
+            if let Some(rec) = &this.recorder {
+                let error = match exc_value {
+                    Some(v) => format!("{}: {}", exc.bind(py).getattr("__name__")?, v.bind(py)),
+                    None => exc.bind(py).getattr("__name__")?.to_string(),
+                };
+                rec.record(py, this.id, "rollback", &[("error", error.into_py(py).into_bound(py))]);
+            }
             return Ok(());
         }
 
-        // Enforce Timeout
-        if let Some(start) = self.start_time {
-             #[allow(clippy::cast_possible_truncation)]
-             if start.elapsed().as_millis() as u64 > self.write_timeout_ms {
-                 return Err(WriteTimeoutError::new_err(format!(
-                     "Transaction timed out after {}ms (limit {}ms)", 
-                     start.elapsed().as_millis(), 
-                     self.write_timeout_ms
-                 )));
-             }
+        let commit_started = Instant::now();
+        let result = this.try_commit(py, slf);
+        this.stats.relock().commit = commit_started.elapsed();
+        // [v3.6] Staged behind a commit group: the flush settles (and records) it, possibly
+        // already (a full batch flushes while its last member is still closing).
+        if result.is_ok() && this.outcome.relock().status.is_some() {
+            return Ok(());
         }
-
-        let engine = self.engine.bind(py);
-        let current_state_obj = engine.getattr("state")?;
-        
-        // [v3.1.2] Differential Shadow Merging:
-        // 1. Infer mutations from shadows
-        self.infer_shadow_deltas(py)?;
-        // 2. Apply delta_log to pending_data
-        self.commit(py)?;
-
-        // [OCC] Field-level conflict detection (Smart CAS — same policy as compare_and_swap).
-        // Runs after pending_data is fully populated (post-shadow-infer + post-commit).
-        // Raises CAS Version Mismatch → triggers execute() retry loop.
-        if self.start_version > 0 {
-            let conflict = {
-                let engine_borrow = engine.borrow();
-                let current_state_bound = engine_borrow.state.bind(py);
-                let current_state = current_state_bound.borrow();
-                let current_version = current_state.version;
-
-                if current_version == self.start_version {
-                    None
-                } else {
-                    let mut safe = true;
-                    let pending = self.pending_data.bind(py);
-
-                    'outer: for (zone_k, zone_v) in pending.iter() {
-                        let zone_key = zone_k.extract::<String>()?;
-                        if let Ok(inner_dict) = zone_v.downcast::<pyo3::types::PyDict>() {
-                            for (ik, _) in inner_dict.iter() {
-                                let inner_key = ik.extract::<String>()?;
-                                let field_path = format!("{zone_key}.{inner_key}");
-                                if let Some(last_ver) = current_state.key_last_modified.get(&field_path) {
-                                    if *last_ver > self.start_version {
-                                        safe = false;
-                                        break 'outer;
-                                    }
-                                }
-                            }
-                        } else if let Some(last_ver) = current_state.key_last_modified.get(&zone_key) {
-                            if *last_ver > self.start_version {
-                                safe = false;
-                            }
-                        }
-                        if !safe { break; }
-                    }
-
-                    if safe { None } else { Some((self.start_version, current_version)) }
-                }
-                // engine_borrow, current_state_bound, current_state all drop here
-            };
-
-            if let Some((expected, found)) = conflict {
-                return Err(ContextError::new_err(format!(
-                    "CAS Version Mismatch (Conflict Detected): Expected {expected}, Found {found} (Keys Changed)"
-                )));
-            }
+        if result.is_ok() {
+            this.report_slow_commit(py)?;
         }
-
-        // Optimistic Update: Create new state version
-        let new_state_obj = current_state_obj.call_method(
-            "update", 
-            (self.pending_data.clone_ref(py), self.pending_heavy.clone_ref(py), self.pending_signal.clone_ref(py)), 
-            None
-        )?;
-
-        // Schema Enforcement (Phase 32.2)
+        let version = this.engine.borrow(py).state.bind(py).borrow().version;
         {
-             let engine_borrow = engine.borrow();
-             let schema_guard = engine_borrow.schema.lock().unwrap();
-             if let Some(ref schema) = *schema_guard {
-                 // Convert State.data to Dict for Pydantic validation
-                 // We validate the *Resulting* state data to ensure consistency.
-                 
-                 // Access property via getattr, not call_method
-                 // Access property via getattr, not call_method
-                 let frozen_data = new_state_obj.getattr("data")?;
-                 let dict_data = frozen_data.call_method0("to_dict")?;
-                 
-                 // Pydantic model_validate
-                 if let Err(e) = schema.call_method1(py, "model_validate", (dict_data,)) {
-                      return Err(crate::config::SchemaViolationError::new_err(format!("Schema Violation: {e}")));
-                 }
-             }
+            let mut outcome = this.outcome.relock();
+            match &result {
+                Ok(()) => (outcome.status, outcome.version) = (Some("committed"), Some(version)),
+                Err(e) => (outcome.status, outcome.error) = (Some("failed"), Some(e.to_string())),
+            }
         }
-
-        // [v3.0.27] Direct Rust field assignment — replaces stringly-typed call_method1("commit_state", ...).
-        // Closing the OCC bypass: commit_state is no longer exported via #[pymethods].
-        {
-            let mut engine_ref = engine.borrow_mut();
-            engine_ref.state = new_state_obj.extract::<Py<State>>()?;
+        if let Err(e) = &result {
+            this.record_rollback(py, "commit_failed", e.value_bound(py))?;
         }
-
-        // [INC-023] Deferred signal dispatch — fires AFTER data is committed to engine.state.
-        // State.update() above only populated last_signals (Flux latch), no publish yet.
-        // Now that engine.state is updated, subscribers will see consistent state.
-        {
-            let committed_state = engine.getattr("state")?;
-            committed_state.call_method1(
-                "publish_signals",
-                (self.pending_signal.clone_ref(py),)
-            )?;
+        if let Some(rec) = &this.recorder {
+            match &result {
+                Ok(()) => rec.record(py, this.id, "commit", &[("version", version.into_py(py).into_bound(py))]),
+                Err(e) => rec.record(py, this.id, "rollback", &[("error", e.to_string().into_py(py).into_bound(py))]),
+            }
         }
+        result
+    }
 
-        // Commit Outbox to Engine
-        {
-            let mut pending = self.pending_outbox.lock().unwrap();
-            let msgs = pending.drain(..).collect::<Vec<_>>();
-            
-            // Access Engine Outbox
-            let engine_ref = engine.borrow();
-            engine_ref.outbox.lock().unwrap().extend(msgs);
+    /// [v3.6] "committed", "failed", "staged" (waiting in a commit group), "duplicate" (`op_id`
+    /// already committed; nothing written), "partial" (the block raised; its last checkpoint
+    /// was committed) or None (open, rolled back by an exception).
+    #[getter]
+    fn commit_status(&self) -> Option<&'static str> {
+        self.outcome.relock().status
+    }
+
+    /// [v3.6] State version this transaction's writes landed in.
+    #[getter]
+    fn committed_version(&self) -> Option<u64> {
+        self.outcome.relock().version
+    }
+
+    /// [v3.6] What the last rollback of this transaction discarded (an exception in the
+    /// block, or a failed commit), or None. Also attached to that exception as
+    /// `rollback_report`.
+    fn last_rollback_report(&self, py: Python) -> Option<PyObject> {
+        self.rollback.relock().as_ref().map(|r| r.clone_ref(py))
+    }
+
+    /// [v3.6] Mark the writes made so far as committed even if the block later raises
+    /// (requires `partial_commit=True`). Returns the checkpoint number.
+    fn checkpoint(&self, py: Python) -> PyResult<u32> {
+        if !self.partial_commit {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err("checkpoint() requires a transaction opened with partial_commit=True"));
         }
+        self.ensure_writable(py)?;
+        let checkpoint = self.take_checkpoint(py)?;
+        let (index, deltas) = (checkpoint.index, checkpoint.deltas.len());
+        *self.checkpoint.relock() = Some(checkpoint);
+        crate::audit::log_global_tagged(
+            "TX_CHECKPOINT",
+            &format!("Transaction #{} checkpoint {index}: {deltas} deltas", self.id),
+            self.tags.as_ref(),
+        );
+        Ok(index)
+    }
 
-        Ok(())
+    /// [v3.6] Why the commit failed (None unless `commit_status == "failed"`).
+    #[getter]
+    fn commit_error(&self) -> Option<String> {
+        self.outcome.relock().error.clone()
     }
 
     /// [v3.1.2] Infer Deltas from Shadow Mutations (Differential Merging)
     #[allow(clippy::unnecessary_wraps)]
-    fn infer_shadow_deltas(&self, py: Python) -> PyResult<()> {
+    pub(crate) fn infer_shadow_deltas(&self, py: Python) -> PyResult<()> {
         // [v3.3] Idempotency Check: Prevent hangs during Transaction.__exit__ if already inferred
         {
-            let mut inferred = self.shadows_inferred.lock().unwrap();
+            let mut inferred = self.shadows_inferred.relock();
             if *inferred {
                 return Ok(());
             }
             *inferred = true;
         }
+        let started = Instant::now();
 
         // [DEADLOCK FIX] Snapshot paths to avoid holding full_path_map (Order: Cache -> Path in get_shadow)
         // We must NOT hold full_path_map lock while acquiring shadow_cache.
         let entries: Vec<(String, PyObject)> = {
-             let path_map = self.full_path_map.lock().unwrap();
+             let path_map = self.full_path_map.relock();
              path_map.iter().map(|(k, v)| (k.clone(), v.clone_ref(py))).collect()
         };
 
 
 
-        let mut new_deltas = Vec::new();
-
+        // Pair every tracked shadow with its original (skipping untouched identities).
+        let mut paths = Vec::new();
+        let mut pairs = Vec::new();
         for (path, current) in entries {
             let current_id = current.bind(py).as_ptr() as usize;
             
             // Lock cache briefly to get original
             let original_opt = {
-                let cache = self.shadow_cache.lock().unwrap();
+                let cache = self.shadow_cache.relock();
                 cache.get(&current_id).map(|(orig, _)| orig.clone_ref(py))
             };
 
@@ -788,21 +3939,32 @@ impl Transaction {
                  if original.bind(py).as_ptr() == current.bind(py).as_ptr() {
                       continue;
                  }
-                 
+                 // [v3.6] Unchanged content hash: skip the comparison.
+                 let recorded = self.shadow_hashes.relock().get(&current_id).copied();
+                 if let Some(recorded) = recorded {
+                      let hooks = crate::reentrancy::hooks();
+                      let unchanged = crate::shadow_compare::fingerprint(original.bind(py)) == Some(recorded);
+                      drop(hooks);
+                      if unchanged {
+                           self.stats.relock().hash_skips += 1;
+                           continue;
+                      }
+                 }
+                 paths.push(path);
+                 pairs.push((original, current));
+            }
+        }
+
+        // [v3.6] Plain-data pairs are compared on rayon workers without the GIL.
+        let verdicts = crate::shadow_compare::compare_all(py, &pairs);
+
+        let mut new_deltas = Vec::new();
+        // Proxy operations from user __eq__ implementations are re-entrant.
+        let hooks = crate::reentrancy::hooks();
+        for ((path, (original, current)), verdict) in paths.into_iter().zip(pairs).zip(verdicts) {
                  // Perform Python Comparison (MAY RELEASE GIL / RE-ENTER)
                  // Critical: Do not hold any Rust locks here.
-                 let are_equal = match original.bind(py).rich_compare(current.bind(py), pyo3::basic::CompareOp::Eq) {
-                     Ok(res) => {
-                         match res.is_truthy() {
-                             Ok(b) => b,
-                             Err(_) => {
-                                 // Fallback for NumPy arrays: (a == b).all()
-                                 res.call_method0("all").is_ok_and(|x| x.is_truthy().unwrap_or(false))
-                             }
-                         }
-                     },
-                     Err(_) => false 
-                 };
+                 let are_equal = shadow_equal(py, verdict, &original, &current);
                  
                  if !are_equal {
                      // NOTE: For first-access (non-cache-hit) paths, user receives and mutates
@@ -812,21 +3974,31 @@ impl Transaction {
                      // in commit() handles the cache-hit case by skipping stale parent deltas
                      // when a more specific child delta exists.
                      new_deltas.push(crate::delta::DeltaEntry {
-                         path: path.clone(),
-                         op: "SET".to_string(),
+                         path: crate::intern::intern(&path),
+                         op: crate::intern::intern(crate::delta::DeltaOp::Set.as_str()),
                          value: Some(original.clone_ref(py)),
                          old_value: Some(current.clone_ref(py)),
                          target: None,
                          key: None,
+                         tags: self.tags.clone(),
                      });
                  }
-            }
         }
+        drop(hooks);
         
         if !new_deltas.is_empty() {
-            let mut log = self.delta_log.lock().unwrap();
+            let logged = self.delta_log.relock().len();
+            self.admit(logged, new_deltas.iter().map(|d| &*d.path))?;
+            for d in &new_deltas {
+                crate::profiler::record_write(&d.path);
+                if self.recorder.is_some() {
+                    self.record_set(py, d, true);
+                }
+            }
+            let mut log = self.delta_log.relock();
             log.extend(new_deltas);
         }
+        self.stats.relock().inference = started.elapsed();
         Ok(())
     }
 
@@ -835,9 +4007,10 @@ impl Transaction {
     pub fn get_shadow(&self, py: Python, val: PyObject, path: Option<String>) -> PyResult<PyObject> {
         let id = val.bind(py).as_ptr() as usize;
 
-        let mut cache = self.shadow_cache.lock().unwrap();
+        let mut cache = self.shadow_cache.relock();
         
         if let Some((orig, _shadow)) = cache.get(&id) {
+             self.shadow_budget.relock().touch(id);
              // NOTE: [v3.3.1 FIX] Return `orig` (the deepcopy). User mutations MUST go to
              // the deepcopy so infer_shadow_deltas can detect them by comparing orig vs current.
              return Ok(orig.clone_ref(py));
@@ -851,11 +4024,41 @@ impl Transaction {
             }
         }
 
+        // [v3.6] Device tensors and heavy handles are shared, never deep-copied.
+        let bound = val.bind(py);
+        if crate::dlpack::is_device_tensor(bound) || bound.is_instance_of::<crate::heavy_store::HeavyHandle>() {
+            crate::delta::log_heavy_access(path.as_deref().unwrap_or("<unknown>"));
+            cache.insert(id, (val.clone_ref(py), val.clone_ref(py)));
+            return Ok(val);
+        }
+
         // Deep Copy
         // NOTE: [v3.3.2 FIX] Fail-fast on deepcopy failure instead of silently returning
         // the original object. Silent fallback breaks transaction isolation.
         let copy_mod = py.import("copy")?;
-        let shadow = match copy_mod.call_method1("deepcopy", (&val,)) { 
+        self.faults.check("shadow")?;
+        // [v3.6] A registered copier replaces deepcopy for its type; it must return a new object.
+        // Proxy operations from __deepcopy__ / copiers are re-entrant (the cache lock is held).
+        let hooks = crate::reentrancy::hooks();
+        let copy_started = Instant::now();
+        let copied = match self.copiers.find(py, bound) {
+            Some((name, copier)) => copier.call1(py, (&val,)).map(|c| c.into_bound(py)).and_then(|c| {
+                if c.is(bound) {
+                    Err(pyo3::exceptions::PyValueError::new_err(format!("copier for '{name}' returned the original object")))
+                } else {
+                    Ok(c)
+                }
+            }),
+            // [v3.6] Device tensors nested in the value are shared with the shadow, not copied.
+            None => copy_mod.call_method1("deepcopy", (&val, crate::dlpack::share_memo(py, val.bind(py))?)),
+        };
+        drop(hooks);
+        {
+            let mut stats = self.stats.relock();
+            stats.shadows += 1;
+            stats.shadow_copy += copy_started.elapsed();
+        }
+        let shadow = match copied { 
             Ok(s) => s.unbind(),
             Err(e) => {
                  let type_name = val.bind(py).get_type().name().map_or_else(|_| "unknown".to_string(), |n| n.to_string());
@@ -866,8 +4069,20 @@ impl Transaction {
             }
         };
         
+        if crate::metrics::enabled() {
+            crate::metrics::ENGINE_METRICS.shadow_copy_bytes.record(crate::metrics::estimate_size(shadow.bind(py), 0));
+        }
+
         // Disable Legacy Lock Manager on Shadow
         let _ = shadow.bind(py).setattr("_lock_manager", py.None());
+
+        if self.shadow_hashing {
+            let hooks = crate::reentrancy::hooks();
+            if let Some(hash) = crate::shadow_compare::fingerprint(shadow.bind(py)) {
+                self.shadow_hashes.relock().insert(id, hash);
+            }
+            drop(hooks);
+        }
         
         // Cache the mapping: Active ID -> (Original, Shadow)
         // Original is the deepcopy, Shadow is the active object (val)
@@ -877,14 +4092,27 @@ impl Transaction {
         if let Some(ref p) = path {
             let root = p.split(['.', '[']).next().unwrap_or(p).to_string();
             {
-                let mut path_map = self.path_to_shadow.lock().unwrap();
+                let mut path_map = self.path_to_shadow.relock();
                 // Only insert if root not already present (preserve deeper shadows if multiple accesses)
                 path_map.entry(root).or_insert_with(|| shadow.clone_ref(py));
             }
             // v3.1.2: Store FULL path for Differential Shadow Merging
             // [FIX v3.3] Track ACTIVE object in full_path_map, not the copy!
-            let mut full_map = self.full_path_map.lock().unwrap();
-            full_map.insert(p.clone(), val.clone_ref(py));
+            self.full_path_map.relock().insert(p.clone(), val.clone_ref(py));
+
+            // [v3.6] Bounded shadow cache (see shadow_budget.rs).
+            let bounded = self.shadow_budget.relock().bounded();
+            if bounded {
+                let bytes = crate::metrics::estimate_size(shadow.bind(py), 0);
+                let over = {
+                    let mut budget = self.shadow_budget.relock();
+                    budget.admit(id, p.clone(), bytes);
+                    budget.over()
+                };
+                if over {
+                    self.evict_shadows(py, &mut cache, id);
+                }
+            }
         }
 
         Ok(shadow)
@@ -897,7 +4125,7 @@ impl Transaction {
         // We scope the lock tightly to avoid holding it during set_nested_value,
         // which could re-enter get_shadow and deadlock.
         {
-            let cache = self.shadow_cache.lock().unwrap();
+            let cache = self.shadow_cache.relock();
             // NOTE: This loop performs no mutations — it's a legacy analysis block.
             // The actual commit logic uses delta_log below.
             for (_, (original, shadow)) in cache.iter() {
@@ -926,9 +4154,9 @@ impl Transaction {
             Self::collect_pending_paths(py, pending_bound.as_any(), "", &mut explicit_paths)?;
         }
 
-        let log = self.delta_log.lock().unwrap();
+        let log = self.delta_log.relock();
         let mut sorted_entries: Vec<&crate::delta::DeltaEntry> = log.iter()
-            .filter(|e| e.op == "SET" && e.value.is_some())
+            .filter(|e| &*e.op == crate::delta::DeltaOp::Set.as_str() && e.value.is_some())
             .collect();
         sorted_entries.sort_by_key(|e| e.path.len());
         
@@ -971,15 +4199,30 @@ impl Transaction {
     #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
     pub fn log_delta(&self, py: Python, path: &str, old_val: Option<PyObject>, new_val: Option<PyObject>) -> PyResult<()> {
         let entry = crate::delta::DeltaEntry {
-            path: path.to_string(),
-            op: "SET".to_string(),
+            path: crate::intern::intern(path),
+            op: crate::intern::intern(crate::delta::DeltaOp::Set.as_str()),
             value: new_val.as_ref().map(|v| v.clone_ref(py)),
             old_value: old_val.as_ref().map(|v| v.clone_ref(py)),
             target: None,
             key: None,
+            tags: self.tags.clone(),
         };
         
-        self.delta_log.lock().unwrap().push(entry);
+        if let (Some(new), false) = (&new_val, self.state_machines.is_empty()) {
+            let _hooks = crate::reentrancy::hooks();
+            if let Err(violation) = self.state_machines.check(path, old_val.as_ref().map(|o| o.bind(py)), new.bind(py))? {
+                let err = crate::errors::illegal_transition(py, path, &violation);
+                crate::audit::log_global_tagged("ILLEGAL_TRANSITION", &err.value_bound(py).str()?.to_string(), self.tags.as_ref());
+                return Err(err);
+            }
+        }
+        let logged = self.delta_log.relock().len();
+        self.admit(logged, std::iter::once(path))?;
+        crate::profiler::record_write(&entry.path);
+        if self.recorder.is_some() {
+            self.record_set(py, &entry, false);
+        }
+        self.delta_log.relock().push(entry);
         Ok(())
     }
 
@@ -988,7 +4231,7 @@ impl Transaction {
     pub fn log_internal(
         &self, 
         _path: String, 
-        _op: String, 
+        _op: crate::delta::OpCode, 
         _new_val: Option<PyObject>, 
         _old_val: Option<PyObject>, 
         _obj_ref: Option<PyObject>, 
@@ -1022,7 +4265,7 @@ impl Transaction {
     #[allow(clippy::needless_pass_by_value)]
     pub fn is_known_shadow(&self, py: Python, obj: PyObject) -> bool {
         let ptr = obj.bind(py).as_ptr() as usize;
-        let cache = self.shadow_cache.lock().unwrap();
+        let cache = self.shadow_cache.relock();
         
         if let Some((_, shadow)) = cache.get(&ptr) {
              // If the pointer we queried matches the stored shadow pointer, it IS a shadow.
@@ -0,0 +1,228 @@
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyTuple, PyType};
+use crate::messages::{self, Message};
+use crate::structures::ContextError;
+
+// [v3.6] Exception hierarchy under ContextError, so callers can `except VersionMismatchError`
+// instead of matching message text:
+//   ContextError
+//   ├── ConflictError            path, expected_version, actual_version
+//   │   └── VersionMismatchError (CAS / OCC mismatches; retried by execute)
+//   ├── BusyError                requester (priority ticket held by another process)
+//   ├── SchemaViolationError
+//   ├── IllegalTransitionError   path, pattern, from_state, to_state, allowed
+//   ├── PermissionDeniedError    path, zone, required, caps, rule, details (also a PermissionError)
+//   └── QuotaExceededError       path, limit, requested (also a MemoryError)
+//       └── TenantQuotaExceededError  + tenant, resource
+// Structured fields are instance attributes; fields a raise site cannot fill stay None. Every
+// error raised through this module also carries `code`, its stable message-catalog code
+// (see messages.rs).
+// NOTE: Messages are unchanged, so existing string checks keep working.
+
+pyo3::create_exception!(theus_core, ConflictError, ContextError);
+pyo3::create_exception!(theus_core, VersionMismatchError, ConflictError);
+pyo3::create_exception!(theus_core, BusyError, ContextError);
+pyo3::create_exception!(theus_core, IllegalTransitionError, ContextError);
+
+static PERMISSION_DENIED: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static QUOTA_EXCEEDED: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static TENANT_QUOTA_EXCEEDED: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+const CONFLICT_FIELDS: &[&str] = &["path", "expected_version", "actual_version"];
+const PERMISSION_FIELDS: &[&str] = &["path", "zone", "required", "caps", "rule", "details"];
+const QUOTA_FIELDS: &[&str] = &["path", "limit", "requested"];
+const TENANT_QUOTA_FIELDS: &[&str] = &["tenant", "resource"];
+const TRANSITION_FIELDS: &[&str] = &["path", "pattern", "from_state", "to_state", "allowed"];
+
+/// `ContextError` subclass that also derives from the builtin `compat` (kept for callers
+/// catching `PermissionError` / `MemoryError`).
+fn hybrid<'py>(py: Python<'py>, cell: &'static GILOnceCell<Py<PyType>>, name: &str, compat: Bound<'py, PyType>, fields: &[&str]) -> PyResult<Bound<'py, PyType>> {
+    let ty = cell.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+        let ns = PyDict::new_bound(py);
+        ns.set_item("__module__", "theus_core")?;
+        for field in fields {
+            ns.set_item(*field, py.None())?;
+        }
+        let bases = PyTuple::new_bound(py, [py.get_type_bound::<ContextError>(), compat]);
+        Ok(py.get_type_bound::<PyType>().call1((name, bases, ns))?.downcast_into::<PyType>()?.unbind())
+    })?;
+    Ok(ty.bind(py).clone())
+}
+
+pub fn permission_denied_type(py: Python) -> PyResult<Bound<PyType>> {
+    hybrid(py, &PERMISSION_DENIED, "PermissionDeniedError", py.get_type_bound::<pyo3::exceptions::PyPermissionError>(), PERMISSION_FIELDS)
+}
+
+pub fn quota_exceeded_type(py: Python) -> PyResult<Bound<PyType>> {
+    hybrid(py, &QUOTA_EXCEEDED, "QuotaExceededError", py.get_type_bound::<pyo3::exceptions::PyMemoryError>(), QUOTA_FIELDS)
+}
+
+pub fn tenant_quota_exceeded_type(py: Python) -> PyResult<Bound<PyType>> {
+    let ty = TENANT_QUOTA_EXCEEDED.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+        let ns = PyDict::new_bound(py);
+        ns.set_item("__module__", "theus_core")?;
+        for field in TENANT_QUOTA_FIELDS {
+            ns.set_item(*field, py.None())?;
+        }
+        let bases = PyTuple::new_bound(py, [quota_exceeded_type(py)?]);
+        Ok(py.get_type_bound::<PyType>().call1(("TenantQuotaExceededError", bases, ns))?.downcast_into::<PyType>()?.unbind())
+    })?;
+    Ok(ty.bind(py).clone())
+}
+
+/// `err` with `fields` set as attributes on its exception instance.
+pub fn with_fields(py: Python, err: PyErr, fields: &[(&str, PyObject)]) -> PyErr {
+    let value = err.value_bound(py);
+    for (name, v) in fields {
+        // Setting a plain attribute on an exception instance cannot fail.
+        let _ = value.setattr(*name, v);
+    }
+    err
+}
+
+/// `T` carrying the catalog `message` and its code.
+pub fn coded<T: pyo3::PyTypeInfo>(py: Python, message: Message) -> PyErr {
+    with_fields(py, PyErr::new::<T, _>(message.text), &[("code", message.code.into_py(py))])
+}
+
+/// CAS / OCC mismatch (`code` is `CAS_MISMATCH` or `STRICT_CAS_MISMATCH`): expected `expected`,
+/// found `actual` (first conflicting `path` if known).
+pub fn version_mismatch(py: Python, code: &'static str, path: Option<&str>, expected: u64, actual: u64) -> PyErr {
+    let message = messages::render(code, &[("expected", &expected), ("actual", &actual)]);
+    with_fields(py, coded::<VersionMismatchError>(py, message), &[
+        ("path", path.into_py(py)),
+        ("expected_version", expected.into_py(py)),
+        ("actual_version", actual.into_py(py)),
+    ])
+}
+
+pub fn busy(py: Python, requester: Option<&str>) -> PyErr {
+    let message = messages::render(messages::SYSTEM_BUSY, &[]);
+    with_fields(py, coded::<BusyError>(py, message), &[("requester", requester.into_py(py))])
+}
+
+/// [v3.6] Why an access was refused, for tooling that suggests contract fixes. `rule` names
+/// the check that failed:
+/// - `"contract"`: path outside the process's declared inputs / outputs
+/// - `"capability"`: the proxy's capability lens lacks `required`
+/// - `"zone_physics"`: the zone (or a physics override) forbids the mutation
+/// - `"pure"`: PURE processes are read-only
+/// - `"no_transaction"`: mutation outside a transaction
+/// - `"private"`: `_private` attribute under strict guards
+/// - `"control_zone_input"`: strict guards reject Signal / Meta inputs
+/// - `"effect_budget"`: I/O effect outside the process's budget (PURE I/O sandbox)
+/// - `"tenant"`: write across tenant roots (see `tenancy.rs`)
+pub struct Denial<'a> {
+    pub path: Option<&'a str>,
+    pub rule: &'static str,
+    pub required: Option<u8>,
+    pub caps: Option<u8>,
+}
+
+impl<'a> Denial<'a> {
+    pub fn new(path: &'a str, rule: &'static str) -> Self {
+        Denial { path: Some(path), rule, required: None, caps: None }
+    }
+
+    /// Access needing `required` under the capability mask `caps`.
+    pub fn needs(self, required: u8, caps: u8) -> Self {
+        Denial { required: Some(required), caps: Some(caps), ..self }
+    }
+}
+
+pub fn permission_denied(py: Python, message: Message, denial: &Denial) -> PyErr {
+    crate::trace::denied(denial.rule);
+    let err = match permission_denied_type(py) {
+        Ok(ty) => PyErr::from_type_bound(ty, message.text),
+        Err(e) => return e,
+    };
+    let cap = |bits: Option<u8>| bits.map(|b| crate::zones::Capability::from_bits(b).into_py(py)).into_py(py);
+    let zone = denial.path.map(|p| format!("{:?}", crate::zones::path_physics(p).0).to_lowercase());
+    // `details`: the same payload as plain data (capability masks as ints) for JSON tooling.
+    let details = PyDict::new_bound(py);
+    let _ = details.set_item("path", denial.path);
+    let _ = details.set_item("zone", zone.as_deref());
+    let _ = details.set_item("required", denial.required);
+    let _ = details.set_item("caps", denial.caps);
+    let _ = details.set_item("rule", denial.rule);
+    let err = with_fields(py, err, &[
+        ("path", denial.path.into_py(py)),
+        ("zone", zone.into_py(py)),
+        ("required", cap(denial.required)),
+        ("caps", cap(denial.caps)),
+        ("rule", denial.rule.into_py(py)),
+        ("code", message.code.into_py(py)),
+    ]);
+    with_fields(py, err, &[("details", details.into_any().unbind())])
+}
+
+/// Heavy allocation of `requested` bytes for `path` over the `limit` quota (`used` in use).
+pub fn quota_exceeded(py: Python, path: &str, used: usize, limit: usize, requested: usize) -> PyErr {
+    let message = messages::render(messages::HEAVY_QUOTA, &[("path", &path), ("requested", &requested), ("used", &used), ("limit", &limit)]);
+    let err = match quota_exceeded_type(py) {
+        Ok(ty) => PyErr::from_type_bound(ty, message.text),
+        Err(e) => return e,
+    };
+    with_fields(py, err, &[
+        ("code", message.code.into_py(py)),
+        ("path", path.into_py(py)),
+        ("limit", limit.into_py(py)),
+        ("requested", requested.into_py(py)),
+    ])
+}
+
+/// Commit needing `requested` units of `resource` ("keys", "bytes", "commits", "outbox") for
+/// `tenant`, over its `limit` (see `tenancy.rs`).
+pub fn tenant_quota_exceeded(py: Python, tenant: &str, resource: &str, limit: u64, requested: u64) -> PyErr {
+    let message = messages::render(messages::TENANT_QUOTA, &[("tenant", &tenant), ("resource", &resource), ("requested", &requested), ("limit", &limit)]);
+    let err = match tenant_quota_exceeded_type(py) {
+        Ok(ty) => PyErr::from_type_bound(ty, message.text),
+        Err(e) => return e,
+    };
+    with_fields(py, err, &[
+        ("code", message.code.into_py(py)),
+        ("path", crate::tenancy::root(tenant).into_py(py)),
+        ("limit", limit.into_py(py)),
+        ("requested", requested.into_py(py)),
+        ("tenant", tenant.into_py(py)),
+        ("resource", resource.into_py(py)),
+    ])
+}
+
+/// Write at `path` rejected by a state machine (see `state_machines.rs`).
+pub fn illegal_transition(py: Python, path: &str, v: &crate::state_machines::Violation) -> PyErr {
+    let from = v.from.as_deref().unwrap_or("<unset>");
+    let allowed = if v.allowed.is_empty() { "nothing".to_string() } else { v.allowed.join(", ") };
+    let message = messages::render(messages::ILLEGAL_TRANSITION, &[("path", &path), ("from", &from), ("to", &v.to), ("pattern", &v.pattern), ("allowed", &allowed)]);
+    with_fields(py, coded::<IllegalTransitionError>(py, message), &[
+        ("path", path.into_py(py)),
+        ("pattern", v.pattern.clone().into_py(py)),
+        ("from_state", v.from.clone().into_py(py)),
+        ("to_state", v.to.clone().into_py(py)),
+        ("allowed", v.allowed.clone().into_py(py)),
+    ])
+}
+
+/// Adds the hierarchy to the module (class-level defaults for the structured fields).
+pub fn register(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    py.get_type_bound::<ContextError>().setattr("code", py.None())?;
+    let conflict = py.get_type_bound::<ConflictError>();
+    for field in CONFLICT_FIELDS {
+        conflict.setattr(*field, py.None())?;
+    }
+    let busy = py.get_type_bound::<BusyError>();
+    busy.setattr("requester", py.None())?;
+    m.add("ConflictError", conflict)?;
+    m.add("VersionMismatchError", py.get_type_bound::<VersionMismatchError>())?;
+    m.add("BusyError", busy)?;
+    let transition = py.get_type_bound::<IllegalTransitionError>();
+    for field in TRANSITION_FIELDS {
+        transition.setattr(*field, py.None())?;
+    }
+    m.add("IllegalTransitionError", transition)?;
+    m.add("PermissionDeniedError", permission_denied_type(py)?)?;
+    m.add("QuotaExceededError", quota_exceeded_type(py)?)?;
+    m.add("TenantQuotaExceededError", tenant_quota_exceeded_type(py)?)?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] Fault injection for chaos testing. Injected failures raise the same exception
+// types as the real failure, so retry / rollback / dead-letter paths run unchanged.
+// Disarmed cost is one atomic load per injection point. Draws go through
+// `crate::determinism`, so a seeded engine replays the same chaos run.
+
+/// Injection points and what they simulate.
+pub const POINTS: [&str; 6] = [
+    "commit", // Transaction commit hits an OCC conflict ("CAS Version Mismatch")
+    "cas",    // compare_and_swap hits a version conflict
+    "schema", // schema validation rejects the new state
+    "shadow", // deepcopy of a value for a transaction shadow fails
+    "outbox", // outbox worker raises while delivering a message
+    "panic",  // a bug panics while a commit holds the history lock (poisons it, see locks.rs)
+];
+
+struct Fault {
+    probability: f64,
+    remaining: Option<u32>,
+    fired: u64,
+    message: Option<String>,
+}
+
+#[derive(Default)]
+pub struct FaultRegistry {
+    armed: AtomicBool,
+    faults: Mutex<HashMap<&'static str, Fault>>,
+    rng: Arc<crate::determinism::Determinism>,
+}
+
+impl FaultRegistry {
+    /// Probabilities draw from `rng` (the engine's), so they replay under its seed.
+    pub fn new(rng: Arc<crate::determinism::Determinism>) -> Self {
+        FaultRegistry { rng, ..Self::default() }
+    }
+
+    pub fn inject(&self, point: &str, probability: f64, times: Option<u32>, message: Option<String>) -> PyResult<()> {
+        let point = POINTS.iter().find(|p| **p == point).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("Unknown fault point '{point}' (expected one of {POINTS:?})"))
+        })?;
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(pyo3::exceptions::PyValueError::new_err("probability must be within [0, 1]"));
+        }
+        let mut faults = self.faults.relock();
+        faults.insert(point, Fault { probability, remaining: times, fired: 0, message });
+        self.armed.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn clear(&self, point: Option<&str>) {
+        let mut faults = self.faults.relock();
+        match point {
+            Some(p) => { faults.remove(p); }
+            None => faults.clear(),
+        }
+        self.armed.store(!faults.is_empty(), Ordering::SeqCst);
+    }
+
+    /// Should `point` fail now? Returns the failure detail when it fires.
+    pub fn fire(&self, point: &str) -> Option<String> {
+        if !self.armed.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut faults = self.faults.relock();
+        let fault = faults.get_mut(point)?;
+        if fault.remaining == Some(0) || self.rng.uniform(0.0, 1.0) >= fault.probability {
+            return None;
+        }
+        fault.fired += 1;
+        if let Some(n) = fault.remaining.as_mut() {
+            *n -= 1;
+        }
+        let detail = fault.message.clone().unwrap_or_else(|| format!("injected fault at '{point}'"));
+        crate::audit::log_global("FAULT_INJECTED", &format!("{point}: {detail}"));
+        Some(detail)
+    }
+
+    /// `Err` shaped like the real failure at `point` if the fault fires.
+    pub fn check(&self, point: &str) -> PyResult<()> {
+        let Some(detail) = self.fire(point) else { return Ok(()) };
+        Err(match point {
+            "commit" | "cas" => Python::with_gil(|py| crate::errors::coded::<crate::errors::VersionMismatchError>(
+                py, crate::messages::render(crate::messages::INJECTED_CONFLICT, &[("detail", &detail)]),
+            )),
+            "schema" => Python::with_gil(|py| crate::errors::coded::<crate::config::SchemaViolationError>(
+                py, crate::messages::render(crate::messages::INJECTED_SCHEMA_VIOLATION, &[("detail", &detail)]),
+            )),
+            "shadow" => pyo3::exceptions::PyRuntimeError::new_err(format!("Transaction isolation failure (Injected): {detail}")),
+            _ => pyo3::exceptions::PyRuntimeError::new_err(format!("Outbox delivery failed (Injected): {detail}")),
+        })
+    }
+
+    /// Panics if the "panic" fault fires; called with a lock held so the panic poisons it.
+    pub fn panic_point(&self) {
+        if let Some(detail) = self.fire("panic") {
+            panic!("Injected panic: {detail}");
+        }
+    }
+
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        for (point, f) in self.faults.relock().iter() {
+            let d = PyDict::new_bound(py);
+            d.set_item("probability", f.probability)?;
+            d.set_item("remaining", f.remaining)?;
+            d.set_item("fired", f.fired)?;
+            out.set_item(*point, d)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+}
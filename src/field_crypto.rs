@@ -0,0 +1,100 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyBytes;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use crate::cap_tokens::{hex, unhex};
+use crate::structures::ContextError;
+
+// [v3.6] Transparent field encryption, as a built-in middleware (see middleware.rs).
+// `engine.encrypt_fields(["domain.users.*.token"], key_provider)` encrypts values written
+// through proxies to matching paths and decrypts them on read. The stored value is the
+// string `tenc1:<key_id>:<hex(nonce | ciphertext)>` (ChaCha20-Poly1305, msgpack plaintext,
+// key id and field path as associated data), so snapshots, deltas, dumps_state() blobs and
+// audit values only ever hold ciphertext. `key_provider(key_id) -> bytes` returns the 32-byte
+// key; it is called per operation, so rotating keys means writing with a new `key_id` while the
+// provider still answers for the old ones.
+// Proxy writes and matching values in `tx.update()` / `update_many()` payloads are encrypted.
+// A written string that already looks like ciphertext is kept only if it authenticates for
+// that very path (a value copied back in place); anything else, including plaintext that
+// happens to start with `tenc1:`, is encrypted like any other value.
+// NOTE: The path binding means ciphertext does not survive a move: a value under a list index
+// no longer decrypts once the list is reordered. Key such fields by id, not position.
+
+pyo3::create_exception!(theus_core, DecryptionError, ContextError);
+
+const PREFIX: &str = "tenc1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+pub struct FieldCipher {
+    key_provider: PyObject,
+    key_id: String,
+}
+
+impl FieldCipher {
+    pub fn new(key_provider: PyObject, key_id: String) -> PyResult<Self> {
+        if key_id.contains(':') {
+            return Err(PyValueError::new_err("key_id must not contain ':'"));
+        }
+        Ok(FieldCipher { key_provider, key_id })
+    }
+
+    fn cipher(&self, py: Python, key_id: &str) -> PyResult<ChaCha20Poly1305> {
+        let key = self.key_provider.call1(py, (key_id,))?;
+        let key: Vec<u8> = key.bind(py).downcast::<PyBytes>()
+            .map(|b| b.as_bytes().to_vec())
+            .map_err(|_| PyValueError::new_err(format!("key_provider('{key_id}') must return bytes")))?;
+        if key.len() != KEY_LEN {
+            return Err(PyValueError::new_err(format!("key_provider('{key_id}') returned {} bytes, expected {KEY_LEN}", key.len())));
+        }
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    fn encrypt(&self, py: Python, path: &str, value: &Bound<'_, PyAny>) -> PyResult<String> {
+        let plaintext = crate::state_codec::encode_value(py, value)?;
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let sealed = self.cipher(py, &self.key_id)?
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: &plaintext, aad: &aad(&self.key_id, path) })
+            .map_err(|_| PyValueError::new_err("Encryption failed"))?;
+        Ok(format!("{PREFIX}{}:{}{}", self.key_id, hex(&nonce), hex(&sealed)))
+    }
+
+    fn decrypt<'py>(&self, py: Python<'py>, path: &str, stored: &str) -> PyResult<Bound<'py, PyAny>> {
+        let failed = |why: &str| DecryptionError::new_err(format!("Cannot decrypt '{path}': {why}"));
+        let (key_id, body) = stored[PREFIX.len()..].split_once(':').ok_or_else(|| failed("malformed ciphertext"))?;
+        let bytes = unhex(body).filter(|b| b.len() > NONCE_LEN).ok_or_else(|| failed("malformed ciphertext"))?;
+        let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+        let plaintext = self.cipher(py, key_id)?
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad: &aad(key_id, path) })
+            .map_err(|_| failed("authentication failed (wrong key or tampered value)"))?;
+        crate::state_codec::decode_value(py, &plaintext)
+    }
+}
+
+/// Key id and normalized field path (`users[0]` and `users.0` bind alike).
+fn aad(key_id: &str, path: &str) -> Vec<u8> {
+    let path = path.replace('[', ".").replace(']', "");
+    [key_id.as_bytes(), &[0], path.as_bytes()].concat()
+}
+
+impl crate::middleware::Middleware for FieldCipher {
+    fn intercept(&self, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+        let bound = value.bind(py);
+        let stored = bound.extract::<String>().ok().filter(|s| s.starts_with(PREFIX));
+        match (op, stored) {
+            ("get", Some(stored)) => Ok(self.decrypt(py, path, &stored)?.unbind()),
+            // Plaintext written before encryption was enabled.
+            ("get", None) => Ok(value),
+            _ if bound.is_none() => Ok(value),
+            (_, Some(stored)) if self.decrypt(py, path, &stored).is_ok() => Ok(value),
+            _ => Ok(self.encrypt(py, path, bound)?.into_py(py)),
+        }
+    }
+
+    fn seals_updates(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,136 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use crate::structures::State;
+
+// [v3.6] `engine.fsck(repair=False)`: self-check of the engine's internal bookkeeping.
+// Each issue is reported under the check that found it:
+// - "shadows": every path an open transaction tracks in its full_path_map has a shadow_cache
+//   entry (otherwise its writes cannot be inferred at commit).
+// - "key_last_modified": conflict stamps are not newer than the state and name existing
+//   Data / Heavy keys. Stamps of deleted keys still guard open transactions, so they are only
+//   dropped while none is open; future stamps are clamped to the current version.
+// - "outbox": queued messages that were already delivered or are queued twice (dropped on
+//   repair), or that also sit in the dead-letter store (reported only).
+// - "audit": the global audit ring's cursor and count agree with its contents (rebuilt on
+//   repair), so no event was overwritten or skipped.
+// - "registry": byte accounting and dangling references of the managed Heavy store, the blob
+//   registry against its directory, and the spill record table against the spill file.
+// Errors make `ok` false until repaired; warnings are informational. Repairs are audited
+// as FSCK_REPAIR.
+
+pub const CHECKS: [&str; 5] = ["shadows", "key_last_modified", "outbox", "audit", "registry"];
+
+pub struct Issue {
+    pub check: &'static str,
+    /// "error" or "warning".
+    pub severity: &'static str,
+    pub detail: String,
+    pub repairable: bool,
+    pub repaired: bool,
+}
+
+impl Issue {
+    pub fn error(check: &'static str, detail: String) -> Self {
+        Issue { check, severity: "error", detail, repairable: false, repaired: false }
+    }
+
+    pub fn warning(check: &'static str, detail: String) -> Self {
+        Issue { check, severity: "warning", detail, repairable: false, repaired: false }
+    }
+
+    /// Marks the issue as safe to fix; `repaired` when the fix was applied.
+    #[must_use]
+    pub fn repairable(mut self, repaired: bool) -> Self {
+        self.repairable = true;
+        self.repaired = repaired;
+        self
+    }
+}
+
+type ShadowCache = HashMap<usize, (PyObject, PyObject)>;
+
+/// "shadows" check of one open transaction.
+pub fn shadows(py: Python, tx: u64, cache: &ShadowCache, full_path_map: &HashMap<String, PyObject>) -> Vec<Issue> {
+    let mut paths: Vec<&String> = full_path_map.iter()
+        .filter(|(_, active)| !cache.contains_key(&(active.bind(py).as_ptr() as usize)))
+        .map(|(path, _)| path)
+        .collect();
+    paths.sort();
+    paths.into_iter()
+        .map(|path| Issue::error("shadows", format!("tx #{tx}: '{path}' is tracked for commit but has no shadow")))
+        .collect()
+}
+
+/// `"key_last_modified"` check; `prune` allows dropping stamps of deleted keys.
+pub fn stamps(py: Python, state: &mut State, repair: bool, prune: bool) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let version = state.version;
+    let mut keys: Vec<crate::intern::Sym> = state.key_last_modified.keys().cloned().collect();
+    keys.sort();
+    for key in keys {
+        if !key_exists(py, state, &key) {
+            let fixed = repair && prune;
+            if fixed {
+                state.key_last_modified.remove(&key);
+            }
+            let issue = Issue::warning("key_last_modified", format!("stamp for missing key '{key}'"));
+            issues.push(if prune { issue.repairable(fixed) } else { issue });
+            continue;
+        }
+        let stamped = state.key_last_modified[&key];
+        if stamped > version {
+            if repair {
+                state.key_last_modified.insert(key.clone(), version);
+            }
+            issues.push(Issue::error("key_last_modified", format!("'{key}' stamped v{stamped}, newer than state v{version}")).repairable(repair));
+        }
+    }
+    issues
+}
+
+/// `zone` or `zone.field` present in the Data or Heavy zone.
+fn key_exists(py: Python, state: &State, key: &str) -> bool {
+    let (zone, field) = match key.split_once('.') {
+        Some((zone, field)) => (zone, Some(field)),
+        None => (key, None),
+    };
+    let Some(root) = state.data.get(zone).or_else(|| state.heavy.get(zone)) else { return false };
+    let Some(field) = field else { return true };
+    let root = root.bind(py);
+    match root.downcast::<PyDict>() {
+        Ok(d) => d.contains(field).unwrap_or(false),
+        Err(_) => root.hasattr(field).unwrap_or(false),
+    }
+}
+
+/// `{ok, version, checks: {name: issue count}, issues: [{check, severity, detail, repairable,
+/// repaired}], repaired}`.
+pub fn report(py: Python, version: u64, issues: &[Issue]) -> PyResult<PyObject> {
+    let out = PyDict::new_bound(py);
+    let ok = !issues.iter().any(|i| i.severity == "error" && !i.repaired);
+    out.set_item("ok", ok)?;
+    out.set_item("version", version)?;
+    let checks = PyDict::new_bound(py);
+    for check in CHECKS {
+        checks.set_item(check, issues.iter().filter(|i| i.check == check).count())?;
+    }
+    out.set_item("checks", checks)?;
+    let list = issues.iter().map(|i| {
+        let d = PyDict::new_bound(py);
+        d.set_item("check", i.check)?;
+        d.set_item("severity", i.severity)?;
+        d.set_item("detail", &i.detail)?;
+        d.set_item("repairable", i.repairable)?;
+        d.set_item("repaired", i.repaired)?;
+        Ok(d)
+    }).collect::<PyResult<Vec<_>>>()?;
+    out.set_item("issues", list)?;
+    let repaired = issues.iter().filter(|i| i.repaired).count();
+    out.set_item("repaired", repaired)?;
+    if repaired > 0 {
+        let details: Vec<&str> = issues.iter().filter(|i| i.repaired).map(|i| i.detail.as_str()).collect();
+        crate::audit::log_global("FSCK_REPAIR", &format!("State v{version}: repaired {repaired} issue(s): {}", details.join("; ")));
+    }
+    Ok(out.into_any().unbind())
+}
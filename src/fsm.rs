@@ -2,6 +2,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use serde_yaml::Value;
 use std::sync::Mutex;
+use crate::locks::Relock;
 
 // ============================================================================
 // Flux DSL AST (Abstract Syntax Tree)
@@ -219,25 +220,25 @@ impl WorkflowEngine {
     /// Get current FSM state.
     #[getter]
     fn fsm_state(&self) -> FSMState {
-        *self.fsm_state.lock().unwrap()
+        *self.fsm_state.relock()
     }
 
     /// Alias for `fsm_state` (for test compatibility).
     #[getter]
     fn state(&self) -> FSMState {
-        *self.fsm_state.lock().unwrap()
+        *self.fsm_state.relock()
     }
 
     /// Get state transition history.
     #[getter]
     fn state_history(&self) -> Vec<FSMState> {
-        self.state_history.lock().unwrap().clone()
+        self.state_history.relock().clone()
     }
 
     /// Add an observer callback for state changes.
     /// Callback signature: (`old_state`, `new_state`) -> None
     fn add_state_observer(&self, callback: PyObject) {
-        self.observers.lock().unwrap().push(callback);
+        self.observers.relock().push(callback);
     }
 
     /// Execute the workflow using the provided executor callback.
@@ -529,18 +530,18 @@ impl WorkflowEngine {
     /// Transition to a new FSM state, record in history, and notify observers.
     #[allow(clippy::unnecessary_wraps)]
     fn transition_state(&self, py: Python, new_state: FSMState) -> PyResult<()> {
-        let old_state = *self.fsm_state.lock().unwrap();
+        let old_state = *self.fsm_state.relock();
         
         // Update state
-        *self.fsm_state.lock().unwrap() = new_state;
+        *self.fsm_state.relock() = new_state;
         
         // Record in history
-        self.state_history.lock().unwrap().push(new_state);
+        self.state_history.relock().push(new_state);
         
         // Notify observers
         // Use a clone to avoid holding the lock while calling Python code (Deadlock Prevention)
         let observers: Vec<PyObject> = {
-            let guard = self.observers.lock().unwrap();
+            let guard = self.observers.relock();
             guard.iter().map(|o| o.clone_ref(py)).collect()
         };
         
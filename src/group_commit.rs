@@ -0,0 +1,254 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::engine::{TheusEngine, Transaction};
+use crate::structures::ContextError;
+use crate::locks::Relock;
+
+// [v3.6] Group commit for high-frequency small transactions. Inside
+// `with engine.group_commit(max_batch, window_ms):` a transaction that passes its OCC check is
+// staged instead of installing its own version; the batch is flushed as ONE new version
+// (deltas merged in commit order, schema validated once, one signal / meta-watcher round) when
+// it reaches `max_batch`, when `window_ms` has elapsed since its first member, or when the
+// group closes. Each member still reports its own outcome (`tx.commit_status`,
+// `group.results`): if the merged install fails, members are installed one by one so only
+// the offending transaction fails.
+// NOTE: Staged writes are not visible until the flush. A transaction writing a path that is
+// already staged flushes the batch and fails with the usual CAS conflict (retried by execute).
+
+/// Where a transaction's commit ended up.
+#[derive(Default)]
+pub struct CommitOutcome {
+    pub status: Option<&'static str>,
+    pub version: Option<u64>,
+    pub error: Option<String>,
+}
+
+pub struct GroupState {
+    max_batch: usize,
+    window: Option<Duration>,
+    opened_at: Option<Instant>,
+    staged: Vec<Py<Transaction>>,
+    paths: HashSet<String>,
+    results: Vec<PyObject>,
+}
+
+/// Engine slot holding the open group (at most one).
+pub type GroupSlot = Arc<Mutex<Option<Arc<Mutex<GroupState>>>>>;
+
+fn overlaps(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.starts_with(short) && (long.len() == short.len() || long.as_bytes()[short.len()] == b'.')
+}
+
+/// Paths `tx` writes, heavy keys under "heavy.".
+fn written(py: Python, tx: &Transaction) -> PyResult<Vec<String>> {
+    let mut paths = crate::testing::written_paths(tx.pending_data.bind(py))?;
+    for (k, _) in tx.pending_heavy.bind(py).iter() {
+        paths.push(format!("heavy.{}", k.str()?));
+    }
+    Ok(paths)
+}
+
+/// Stages `tx` (the borrowed `handle`) behind the open batch; flushes when the batch is full
+/// or its window has elapsed.
+pub fn stage(py: Python, group: &Arc<Mutex<GroupState>>, engine: &Bound<'_, TheusEngine>, handle: &Bound<'_, Transaction>, tx: &Transaction) -> PyResult<()> {
+    let paths = written(py, tx)?;
+    let clash = {
+        let g = group.relock();
+        paths.iter().find(|p| g.paths.iter().any(|s| overlaps(p, s))).cloned()
+    };
+    if let Some(path) = clash {
+        flush(py, group, engine)?;
+        let found = engine.borrow().state.bind(py).borrow().version;
+        return Err(crate::errors::version_mismatch(py, crate::messages::CAS_MISMATCH, Some(&path), tx.start_version, found));
+    }
+    let full = {
+        let mut g = group.relock();
+        g.opened_at.get_or_insert_with(Instant::now);
+        g.staged.push(handle.clone().unbind());
+        g.paths.extend(paths);
+        g.staged.len() >= g.max_batch || g.window.zip(g.opened_at).is_some_and(|(w, t)| t.elapsed() >= w)
+    };
+    tx.outcome.relock().status = Some("staged");
+    if full {
+        flush(py, group, engine)?;
+    }
+    Ok(())
+}
+
+/// Is a transaction with `op_id` staged in `group`?
+pub fn holds_op(py: Python, group: &Arc<Mutex<GroupState>>, op_id: &str) -> bool {
+    group.relock().staged.iter().any(|t| t.borrow(py).op_id.as_deref() == Some(op_id))
+}
+
+/// Installs every staged transaction; returns this flush's per-transaction results.
+#[allow(clippy::similar_names)]
+pub fn flush(py: Python, group: &Arc<Mutex<GroupState>>, engine: &Bound<'_, TheusEngine>) -> PyResult<Vec<PyObject>> {
+    let staged = {
+        let mut g = group.relock();
+        g.opened_at = None;
+        g.paths.clear();
+        std::mem::take(&mut g.staged)
+    };
+    if staged.is_empty() {
+        return Ok(Vec::new());
+    }
+    let started = Instant::now();
+    let txs: Vec<PyRef<Transaction>> = staged.iter().map(|t| t.borrow(py)).collect();
+
+    // A commit outside the group (compare_and_swap) may have landed since staging.
+    let mut live = Vec::new();
+    let mut outcomes: Vec<Result<u64, String>> = Vec::with_capacity(txs.len());
+    {
+        let engine_ref = engine.borrow();
+        let state = engine_ref.state.bind(py).borrow();
+        for tx in &txs {
+            let stale = written(py, tx)?.iter().any(|p| state.key_last_modified.get(p.as_str()).is_some_and(|v| *v > tx.start_version));
+            if stale {
+                outcomes.push(Err(format!(
+                    "CAS Version Mismatch (Conflict Detected): Expected {}, Found {} (Keys Changed)", tx.start_version, state.version
+                )));
+            } else {
+                live.push(outcomes.len());
+                outcomes.push(Ok(0));
+            }
+        }
+    }
+
+    let merged = {
+        let data = PyDict::new_bound(py);
+        let heavy = PyDict::new_bound(py);
+        let signal = PyList::empty_bound(py);
+        for &i in &live {
+            let tx = &txs[i];
+            for (zone, fields) in tx.pending_data.bind(py).iter() {
+                match (data.get_item(&zone)?.map(pyo3::types::PyAnyMethods::downcast_into::<PyDict>), fields.downcast::<PyDict>()) {
+                    (Some(Ok(existing)), Ok(fields)) => {
+                        let combined = existing.copy()?;
+                        combined.update(fields.as_mapping())?;
+                        data.set_item(zone, combined)?;
+                    }
+                    _ => data.set_item(zone, fields)?,
+                }
+            }
+            heavy.update(tx.pending_heavy.bind(py).as_mapping())?;
+            for s in tx.pending_signal.bind(py).iter() {
+                signal.append(s)?;
+            }
+        }
+        let writers: Vec<_> = live.iter().map(|&i| {
+            let tx = &txs[i];
+            (tx.pending_data.bind(py).clone(), tx.actor.clone(), Some(tx.id), tx.tags.clone())
+        }).collect();
+        Transaction::install(py, engine, &data, &heavy, &signal, None, &writers)
+    };
+    match merged {
+        Ok(version) => live.iter().for_each(|&i| outcomes[i] = Ok(version)),
+        // Fall back to one version per member so only the offending ones fail.
+        Err(_) => {
+            for &i in &live {
+                let tx = &txs[i];
+                let writers = [(tx.pending_data.bind(py).clone(), tx.actor.clone(), Some(tx.id), tx.tags.clone())];
+                outcomes[i] = Transaction::install(
+                    py, engine, tx.pending_data.bind(py), tx.pending_heavy.bind(py), tx.pending_signal.bind(py), None, &writers,
+                ).map_err(|e| e.to_string());
+            }
+        }
+    }
+
+    let mut results = Vec::with_capacity(txs.len());
+    let mut deltas = 0;
+    for (tx, outcome) in txs.iter().zip(outcomes) {
+        deltas += tx.delta_log.relock().len();
+        tx.settle(py, outcome)?;
+        let o = tx.outcome.relock();
+        let entry = PyDict::new_bound(py);
+        entry.set_item("tx_id", tx.id)?;
+        entry.set_item("status", o.status)?;
+        entry.set_item("version", o.version)?;
+        entry.set_item("error", o.error.as_deref())?;
+        results.push(entry.into_any().unbind());
+    }
+    crate::metrics::record_commit(started, deltas);
+    group.relock().results.extend(results.iter().map(|r| r.clone_ref(py)));
+    Ok(results)
+}
+
+/// `with engine.group_commit(...) as group:` - see the module comment.
+#[pyclass(module = "theus_core")]
+pub struct CommitGroup {
+    engine: Py<TheusEngine>,
+    slot: GroupSlot,
+    state: Arc<Mutex<GroupState>>,
+}
+
+impl CommitGroup {
+    pub fn new(engine: Py<TheusEngine>, slot: GroupSlot, max_batch: usize, window_ms: Option<u64>) -> PyResult<Self> {
+        if max_batch == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("max_batch must be at least 1"));
+        }
+        let state = GroupState {
+            max_batch,
+            window: window_ms.map(Duration::from_millis),
+            opened_at: None,
+            staged: Vec::new(),
+            paths: HashSet::new(),
+            results: Vec::new(),
+        };
+        Ok(CommitGroup { engine, slot, state: Arc::new(Mutex::new(state)) })
+    }
+
+    fn close(&self) {
+        let mut slot = self.slot.relock();
+        if slot.as_ref().is_some_and(|g| Arc::ptr_eq(g, &self.state)) {
+            *slot = None;
+        }
+    }
+}
+
+#[pymethods]
+impl CommitGroup {
+    fn __enter__(slf: Py<Self>, py: Python) -> PyResult<Py<Self>> {
+        {
+            let this = slf.borrow(py);
+            let mut slot = this.slot.relock();
+            if slot.is_some() {
+                return Err(ContextError::new_err("A commit group is already open on this engine"));
+            }
+            *slot = Some(this.state.clone());
+        }
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&self, py: Python, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<()> {
+        let result = flush(py, &self.state, self.engine.bind(py));
+        self.close();
+        result.map(drop)
+    }
+
+    /// Installs the staged transactions now; returns their results.
+    fn flush(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        flush(py, &self.state, self.engine.bind(py))
+    }
+
+    /// Transactions waiting for the next flush.
+    #[getter]
+    fn pending(&self) -> usize {
+        self.state.relock().staged.len()
+    }
+
+    /// `[{tx_id, status, version, error}]` for every flushed member, in commit order.
+    #[getter]
+    fn results(&self, py: Python) -> Vec<PyObject> {
+        self.state.relock().results.iter().map(|r| r.clone_ref(py)).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let g = self.state.relock();
+        format!("CommitGroup(max_batch={}, pending={}, flushed={})", g.max_batch, g.staged.len(), g.results.len())
+    }
+}
@@ -1,25 +1,104 @@
 
 use pyo3::prelude::*;
 use pyo3::exceptions::PyPermissionError;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyTuple};
 use crate::engine::Transaction;
+use crate::errors::Denial;
 
-use crate::proxy::SupervisorProxy;
-use crate::zones::{resolve_zone, ContextZone, get_zone_physics, is_absolute_ceiling, CAP_READ, CAP_UPDATE, CAP_APPEND, CAP_DELETE};
+use crate::zones::{resolve_zone, ContextZone, get_zone_physics, is_absolute_ceiling, CAP_READ, CAP_UPDATE, CAP_APPEND, CAP_DELETE, CAP_EXECUTE, CAP_ALL};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use crate::locks::Relock;
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 pub struct SharedPolicy {
     pub inputs: Vec<String>,
     pub outputs: Vec<String>,
     pub strict_guards: bool,
+    /// [v3.6] Underscore attributes still readable in strict mode (sorted, deduped).
+    pub private_allowlist: Vec<String>,
+    /// [v3.6] Capability ceiling (narrowed for guards rebuilt from a delegation token).
+    pub caps: u8,
+    /// [v3.6] Warn mode: accesses outside the contract are audited (`"CONTRACT_WARN"`) and
+    /// allowed instead of denied. Zone physics still apply.
+    pub contract_warn: bool,
+}
+
+/// [v3.6] Validates a strict-mode private allowlist: names must start with `_`, and
+/// `__dict__` stays forbidden (RFC-001 §10).
+pub fn normalize_private_allowlist(names: Vec<String>) -> PyResult<Vec<String>> {
+    for name in &names {
+        if !name.starts_with('_') {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!("'{name}' is not a private attribute name")));
+        }
+        if name == "__dict__" {
+            return Err(PyPermissionError::new_err("'__dict__' cannot be allowlisted"));
+        }
+    }
+    let mut names = names;
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// [v3.6] What `apply_guard` does with a value at a given path (independent of its type).
+#[derive(Clone, Copy)]
+enum GuardDecision {
+    /// System zones (Signal / Meta / Log): returned as-is.
+    Passthrough,
+    /// PRIVATE zone read by a non-admin: None.
+    Hidden,
+    Wrap { can_write: bool, caps: u8 },
+}
+
+const DECISION_CACHE_CAPACITY: usize = 1024;
+
+/// [v3.6] Bounded LRU of `apply_guard` decisions, shared by a guard and the guards it spawns
+/// (same policy, same admin flag). Dropped when physics overrides or break-glass grants change;
+/// decisions relying on a grant are never cached (grants expire). `_elevate` gives the guard a
+/// fresh one.
+#[derive(Default)]
+struct DecisionCache {
+    entries: HashMap<String, (GuardDecision, u64)>,
+    tick: u64,
+    generation: (u64, u64),
+}
+
+impl DecisionCache {
+    fn get(&mut self, path: &str) -> Option<GuardDecision> {
+        let generation = (crate::zones::overrides_generation(), crate::break_glass::generation());
+        if generation != self.generation {
+            self.entries.clear();
+            self.generation = generation;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(path).map(|(decision, used)| {
+            *used = tick;
+            *decision
+        })
+    }
+
+    fn insert(&mut self, path: String, decision: GuardDecision) {
+        if self.entries.len() >= DECISION_CACHE_CAPACITY {
+            if let Some(oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone()) {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(path, (decision, self.tick));
+    }
 }
 
 static POLICY_REGISTRY: std::sync::LazyLock<Mutex<HashMap<SharedPolicy, Arc<SharedPolicy>>>> = std::sync::LazyLock::new(|| {
     Mutex::new(HashMap::new())
 });
 
+fn intern_policy(config: SharedPolicy) -> Arc<SharedPolicy> {
+    let mut registry = POLICY_REGISTRY.relock();
+    registry.entry(config.clone()).or_insert_with(|| Arc::new(config)).clone()
+}
+
 #[pyclass(dict, subclass)]
 pub struct ContextGuard {
     #[pyo3(get, name = "_target")]
@@ -30,22 +109,24 @@ pub struct ContextGuard {
     is_admin: bool,
     #[pyo3(get, set)]
     log: Option<PyObject>,
+    decisions: Arc<Mutex<DecisionCache>>,
 }
 
 impl ContextGuard {
     // ... (new_internal remains same)
-    pub fn new_internal(target: PyObject, inputs: Vec<String>, outputs: Vec<String>, path_prefix: String, tx: Option<Py<Transaction>>, is_admin: bool, strict_guards: bool) -> PyResult<Self> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_internal(target: PyObject, inputs: Vec<String>, outputs: Vec<String>, path_prefix: String, tx: Option<Py<Transaction>>, is_admin: bool, strict_guards: bool, private_allowlist: Vec<String>) -> PyResult<Self> {
           // RFC-001 Section 8: Flyweight Pattern
           let config = SharedPolicy {
               inputs,
               outputs,
               strict_guards,
+              private_allowlist,
+              caps: CAP_ALL,
+              contract_warn: false,
           };
           
-          let policy = {
-              let mut registry = POLICY_REGISTRY.lock().unwrap();
-              registry.entry(config.clone()).or_insert_with(|| Arc::new(config)).clone()
-          };
+          let policy = intern_policy(config);
 
           // Strict Mode: Check for Forbidden Input Zones
           if policy.strict_guards {
@@ -53,9 +134,10 @@ impl ContextGuard {
                   let zone = resolve_zone(inp);
                   match zone {
                       ContextZone::Signal | ContextZone::Meta => {
-                          return Err(PyPermissionError::new_err(
-                              format!("SECURITY VIOLATION: Input '{inp}' belongs to restricted Control Zone {zone:?}.")
-                          ));
+                          return Err(Python::with_gil(|py| crate::errors::permission_denied(py,
+                              crate::messages::render(crate::messages::CONTROL_ZONE_INPUT, &[("path", &inp), ("zone", &format!("{zone:?}"))]),
+                              &Denial::new(inp, "control_zone_input"),
+                          )));
                       },
                       _ => {}
                   }
@@ -69,14 +151,52 @@ impl ContextGuard {
              tx,
              is_admin,
              log: None,
+             decisions: Arc::default(),
          })
     }
 
+    /// [v3.6] Non-admin guard holding exactly the rights of a verified delegation token.
+    pub fn delegated(target: PyObject, grant: crate::cap_tokens::Grant, path_prefix: String, tx: Option<Py<Transaction>>) -> PyResult<Self> {
+        let mut guard = Self::new_internal(target, grant.inputs, grant.outputs, path_prefix, tx, false, grant.strict, grant.private)?;
+        guard.policy = intern_policy(SharedPolicy { caps: grant.caps, ..(*guard.policy).clone() });
+        Ok(guard)
+    }
+
+    /// [v3.6] Trace span for an access through this guard (no-op unless tracing).
+    fn trace(&self, py: Python, path: impl FnOnce() -> String, access: &'static str) -> crate::trace::Span {
+        crate::trace::span(|| self.tx.as_ref().and_then(|t| t.try_borrow(py).ok().map(|t| t.id)), path, access)
+    }
+
     fn check_permissions(&self, full_path: &str, is_write: bool) -> PyResult<()> {
-        if self.is_admin { return Ok(()); }
+        // [v3.6] A tenant transaction never reaches another tenant's root (see tenancy.rs).
+        if let Some(tx) = &self.tx {
+            let escaped = Python::with_gil(|py| {
+                let tenant = tx.bind(py).try_borrow().ok()?.tenant.clone()?;
+                crate::tenancy::outside(&tenant, full_path).then(|| crate::tenancy::guard_denied(py, &tenant, full_path))
+            });
+            if let Some(err) = escaped {
+                return Err(err);
+            }
+        }
+        if !self.allows(full_path, is_write) {
+            if self.policy.contract_warn {
+                let op = if is_write { "write" } else { "read" };
+                crate::audit::log_global("CONTRACT_WARN", &format!("{op} {full_path}"));
+                return Ok(());
+            }
+            let op = if is_write { "Write" } else { "Read" };
+            let required = if is_write { CAP_UPDATE } else { CAP_READ };
+            return Err(crate::testing::denied(Denial { required: Some(required), ..Denial::new(full_path, "contract") }, crate::messages::render(crate::messages::ILLEGAL_ACCESS, &[("op", &op), ("path", &full_path)])));
+        }
+        Ok(())
+    }
+
+    /// Whether the contract covers `full_path` (a probe: refusals are not recorded).
+    fn allows(&self, full_path: &str, is_write: bool) -> bool {
+        if self.is_admin { return true; }
         
-        let is_ok = if is_write {
-             self.policy.outputs.iter().any(|rule| {
+        if is_write {
+             self.policy.caps & (CAP_APPEND | CAP_UPDATE | CAP_DELETE) != 0 && self.policy.outputs.iter().any(|rule| {
                 rule == full_path || 
                 rule.starts_with(&format!("{full_path}.")) || 
                 full_path.starts_with(&format!("{rule}.")) || 
@@ -90,62 +210,84 @@ impl ContextGuard {
                 full_path.starts_with(&format!("{rule}.")) || 
                 full_path.starts_with(&format!("{rule}["))
              })
-        };
-
-        if !is_ok {
-            let op = if is_write { "Write" } else { "Read" };
-            return Err(PyPermissionError::new_err(format!("Illegal {op}: '{full_path}'")));
         }
-        Ok(())
     }
 
-    fn apply_guard(&self, py: Python, val: PyObject, full_path: String) -> PyResult<PyObject> {
-        // println!("DEBUG: apply_guard called for path: '{}'", full_path);
-        // std::io::stdout().flush().unwrap();
-        
-        let val_bound = val.bind(py);
-        let type_name = val_bound.get_type().name()?.to_string();
+    /// Path of `key` below this guard (same rules as `__getitem__`).
+    fn key_path(&self, key: &Bound<'_, PyAny>) -> String {
+        match key.extract::<isize>() {
+            Ok(idx) => format!("{}[{}]", self.path_prefix, idx),
+            Err(_) => crate::introspect::join(&self.path_prefix, &key.to_string()),
+        }
+    }
 
-        // NOTE: Whitelist includes Numpy scalar types (float64, int64...) for framework robustness.
-        // These are immutable and should not be wrapped by ContextGuard.
-        if ["int", "float", "str", "bool", "NoneType", "float64", "float32", "int64", "int32", "int16", "int8", "uint64", "uint32", "uint16", "uint16", "uint8", "bool_", "Transaction"].contains(&type_name.as_str()) {
-             return Ok(val);
+    /// [v3.6] Readable `(key, path, value)` entries: dict items, or public attributes.
+    #[allow(clippy::type_complexity)]
+    fn readable_entries<'py>(&self, py: Python<'py>) -> PyResult<Vec<(Bound<'py, PyAny>, String, Bound<'py, PyAny>)>> {
+        use crate::introspect::Access;
+        self.check_self_read()?;
+        let target = self.target.bind(py);
+        if let Ok(d) = target.downcast::<PyDict>() {
+            return Ok(d.iter()
+                .filter_map(|(k, v)| {
+                    let path = self.key_path(&k);
+                    self.access(&path)?;
+                    Some((k, path, v))
+                })
+                .collect());
         }
+        Ok(crate::introspect::visible_children(target, &self.path_prefix, true, self)?
+            .into_iter()
+            .map(|(name, v)| {
+                let path = crate::introspect::join(&self.path_prefix, &name);
+                (pyo3::types::PyString::new_bound(py, &name).into_any(), path, v)
+            })
+            .collect())
+    }
 
-        if val_bound.is_callable() {
-             return Ok(val);
+    /// Read check on the guarded value itself (the root context is always readable).
+    fn check_self_read(&self) -> PyResult<()> {
+        if self.path_prefix.is_empty() {
+            return Ok(());
         }
+        self.check_permissions(&self.path_prefix, false)
+    }
 
-        // Check if Transaction is present
-        // If NO Transaction (strict_mode=False), return raw value immediately
-        let Some(tx) = &self.tx else {
-                // println!("DEBUG: No Transaction for guard path '{}', returning raw value", full_path);
-                // std::io::stdout().flush().unwrap();
-                return Ok(val); 
-            };
+    /// [v3.6] Caps a break-glass grant of the transaction's engine gives `path` (see `break_glass.rs`).
+    fn granted(&self, path: &str) -> Option<u8> {
+        if !crate::break_glass::any_open() {
+            return None;
+        }
+        let tx = self.tx.as_ref()?;
+        Python::with_gil(|py| tx.bind(py).try_borrow().ok()?.glass_caps(py, path))
+    }
 
-        
+    /// The decision for `full_path`, and whether it relies on a break-glass grant.
+    fn decide(&self, full_path: &str) -> (GuardDecision, bool) {
         // [RFC-001] Logic: Calculate Intersection
-        let zone = resolve_zone(&full_path);
-        let zone_physics = get_zone_physics(&zone);
+        let zone = resolve_zone(full_path);
 
         // [INC-022] System Infrastructure Zone bypass: Signal/Meta/Log zones contain
         // non-transactional runtime objects (SignalHub, broadcast channels, log buffers).
         // These must NOT be deepcopied — they are not part of the MVCC snapshot graph.
         // Return the object as-is; no CoW isolation is semantically valid here.
         if matches!(zone, ContextZone::Signal | ContextZone::Meta | ContextZone::Log) {
-            return Ok(val);
+            return (GuardDecision::Passthrough, false);
         }
 
+        // [v3.6] A break-glass grant widens the zone physics (Private included).
+        let granted = self.granted(full_path);
+        let zone_physics = granted.unwrap_or_else(|| get_zone_physics(&zone));
+
         // [RFC-001 Handbook §1.1] PRIVATE zone: non-admin cannot read at all.
         // Return Python None to hide the field completely.
-        if zone == ContextZone::Private && !self.is_admin {
-            return Ok(py.None());
+        if zone == ContextZone::Private && !self.is_admin && granted.is_none() {
+            return (GuardDecision::Hidden, false);
         }
         
-        let can_write = self.check_permissions(&full_path, true).is_ok();
+        let can_write = self.allows(full_path, true) || self.policy.contract_warn;
         
-        let final_caps = if self.is_admin && !is_absolute_ceiling(&zone) {
+        let caps = if self.is_admin && !is_absolute_ceiling(&zone) {
             // Admin bypasses zone physics, EXCEPT for CONSTANT zones (is_absolute_ceiling)
             CAP_READ | CAP_APPEND | CAP_UPDATE | CAP_DELETE
         } else {
@@ -158,7 +300,73 @@ impl ContextGuard {
             };
             zone_physics & process_license
         };
+        (GuardDecision::Wrap { can_write, caps: caps & self.policy.caps }, granted.is_some())
+    }
+
+    fn apply_guard(&self, py: Python, val: PyObject, full_path: String) -> PyResult<PyObject> {
+        // println!("DEBUG: apply_guard called for path: '{}'", full_path);
+        // std::io::stdout().flush().unwrap();
+        
+        let val = crate::spill::fault_in(py, val)?;
+        let val_bound = val.bind(py);
+        let type_name = val_bound.get_type().name()?.to_string();
+
+        // NOTE: Whitelist includes Numpy scalar types (float64, int64...) for framework robustness.
+        // These are immutable and should not be wrapped by ContextGuard.
+        if ["int", "float", "str", "bool", "NoneType", "float64", "float32", "int64", "int32", "int16", "int8", "uint64", "uint32", "uint16", "uint16", "uint8", "bool_", "Transaction"].contains(&type_name.as_str()) {
+             return Ok(val);
+        }
+
+        if val_bound.is_callable() {
+             return Ok(val);
+        }
+
+        // Check if Transaction is present
+        // If NO Transaction (strict_mode=False), return raw value immediately
+        let Some(tx) = &self.tx else {
+                // println!("DEBUG: No Transaction for guard path '{}', returning raw value", full_path);
+                // std::io::stdout().flush().unwrap();
+                return Ok(val); 
+            };
+
+
+        // [v3.6] Zone / permission math is cached per path.
+        let cached = self.decisions.relock_reset().get(&full_path);
+        let decision = if let Some(decision) = cached { decision } else {
+            let (decision, granted) = self.decide(&full_path);
+            if !granted {
+                self.decisions.relock_reset().insert(full_path.clone(), decision);
+            }
+            decision
+        };
+        let (can_write, final_caps) = match decision {
+            GuardDecision::Passthrough => return Ok(val),
+            GuardDecision::Hidden => return Ok(py.None()),
+            GuardDecision::Wrap { can_write, caps } => (can_write, caps),
+        };
 
+        // [v3.6] Held across get_shadow so copiers / __deepcopy__ hooks reading the context
+        // get a raw view instead of re-entering it.
+        let op = crate::reentrancy::read(py, || full_path.clone())?;
+        if op.nested {
+             return crate::proxy::raw_view(py, val, full_path, final_caps);
+        }
+
+
+        // [v3.6] A tenant transaction sees `tenants` through a guard, so each tenant below it is
+        // checked (a proxy would expose them all).
+        if full_path == crate::tenancy::ROOT && tx.borrow(py).tenant.is_some() {
+            let shadow = tx.bind(py).borrow_mut().get_shadow(py, val, Some(full_path.clone()))?;
+            return Ok(Py::new(py, ContextGuard {
+                target: shadow,
+                policy: self.policy.clone(),
+                path_prefix: full_path,
+                tx: Some(tx.clone_ref(py)),
+                is_admin: self.is_admin,
+                log: None,
+                decisions: self.decisions.clone(),
+            })?.into_py(py));
+        }
 
         if type_name == "dict" {
              // println!("DEBUG: Dict detected at '{}'", full_path);
@@ -170,16 +378,15 @@ impl ContextGuard {
                  tx_bound.borrow_mut().get_shadow(py, val.clone_ref(py), Some(full_path.clone()))?
              };
 
-             let proxy = SupervisorProxy::new(
+             return crate::proxy::pooled_proxy(
                  py,
-                 shadow, 
+                 shadow,
                  full_path,
-                 !can_write, 
+                 !can_write,
                  if can_write { Some(tx.clone_ref(py).into_py(py)) } else { None },
-                 true, // is_shadow (Explicitly created via get_shadow)
+                 true, // is_shadow (created via get_shadow)
                  final_caps,
              );
-             return Ok(Py::new(py, proxy)?.into_py(py));
         }
         
         // [RFC-001] Handle Lists via SupervisorProxy if restricted capabilities
@@ -201,16 +408,15 @@ impl ContextGuard {
              // So we MUST wrap lists in SupervisorProxy to fix security hole!
              // So we ALWAYS wrap List in SupervisorProxy now.
              
-             let proxy = SupervisorProxy::new(
+             return crate::proxy::pooled_proxy(
                  py,
                  shadow,
                  full_path,
                  !can_write,
                  if can_write { Some(tx.clone_ref(py).into_py(py)) } else { None },
-                 true,
+                 true, // is_shadow (created via get_shadow)
                  final_caps,
              );
-             return Ok(Py::new(py, proxy)?.into_py(py));
         }
 
         // v3.1: Nested SupervisorProxy Upgrade (Object/Dict)
@@ -226,16 +432,15 @@ impl ContextGuard {
              let tx_bound = tx.bind(py);
              let shadow = tx_bound.borrow_mut().get_shadow(py, inner, Some(full_path.clone()))?; 
              
-             let proxy = SupervisorProxy::new(
+             return crate::proxy::pooled_proxy(
                  py,
-                 shadow, 
+                 shadow,
                  full_path.clone(),
                  !can_write,
                  if can_write { Some(tx.clone_ref(py).into_py(py)) } else { None },
-                 true, // is_shadow
+                 true, // is_shadow (created via get_shadow)
                  final_caps,
              );
-             return Ok(Py::new(py, proxy)?.into_py(py));
         }
         // println!("DEBUG: Regular Object detected at '{}': Type={}", full_path, type_name);
         // std::io::stdout().flush().unwrap();
@@ -250,15 +455,36 @@ impl ContextGuard {
             tx: Some(tx.clone_ref(py)),
             is_admin: self.is_admin,
             log: None,
+            decisions: self.decisions.clone(),
         })?.into_py(py))
     }
 }
 
+impl crate::introspect::Access for ContextGuard {
+    fn access(&self, path: &str) -> Option<bool> {
+        let zone = resolve_zone(path);
+        let granted = self.granted(path);
+        if zone == ContextZone::Private && !self.is_admin && granted.is_none() {
+            return None;
+        }
+        if !self.allows(path, false) {
+            return None;
+        }
+        let caps = if self.is_admin && !is_absolute_ceiling(&zone) {
+            CAP_ALL
+        } else {
+            granted.unwrap_or_else(|| crate::introspect::physics(path))
+        } & self.policy.caps;
+        Some(self.tx.is_some() && self.allows(path, true) && crate::introspect::can_mutate(caps))
+    }
+}
+
 #[pymethods]
 impl ContextGuard {
     #[new]
-    #[pyo3(signature = (target, inputs, outputs, path_prefix=None, tx=None, is_admin=false, strict_guards=false))]
-    fn new(target: PyObject, inputs: &Bound<'_, PyAny>, outputs: &Bound<'_, PyAny>, path_prefix: Option<String>, tx: Option<Py<Transaction>>, is_admin: bool, strict_guards: bool) -> PyResult<Self> {
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (target, inputs, outputs, path_prefix=None, tx=None, is_admin=false, strict_guards=false, private_allowlist=None, contract_warn=false))]
+    fn new(target: PyObject, inputs: &Bound<'_, PyAny>, outputs: &Bound<'_, PyAny>, path_prefix: Option<String>, tx: Option<Py<Transaction>>, is_admin: bool, strict_guards: bool, private_allowlist: Option<Vec<String>>, contract_warn: bool) -> PyResult<Self> {
         let prefix = path_prefix.unwrap_or_default();
         
         // ... (vector conversion omitted for brevity, logic remains same)
@@ -277,7 +503,12 @@ impl ContextGuard {
         let inputs_vec = to_vec(inputs)?;
         let outputs_vec = to_vec(outputs)?;
 
-        Self::new_internal(target, inputs_vec, outputs_vec, prefix, tx, is_admin, strict_guards)
+        let allowlist = normalize_private_allowlist(private_allowlist.unwrap_or_default())?;
+        let mut guard = Self::new_internal(target, inputs_vec, outputs_vec, prefix, tx, is_admin, strict_guards, allowlist)?;
+        if contract_warn {
+            guard.policy = intern_policy(SharedPolicy { contract_warn, ..(*guard.policy).clone() });
+        }
+        Ok(guard)
     }
 
     /// [v3.3 FIX] Native getter for outbox to bypass __getattr__ shadowing from #[pyclass(dict)]
@@ -307,8 +538,15 @@ impl ContextGuard {
         Arc::as_ptr(&self.policy) as usize
     }
 
+    /// [v3.6] Private names readable despite strict mode.
+    #[getter]
+    fn private_allowlist(&self) -> Vec<String> {
+        self.policy.private_allowlist.clone()
+    }
+
     fn __getattr__(&self, py: Python, name: &str) -> PyResult<PyObject> {
-        if self.policy.strict_guards && name.starts_with('_') {
+        // [v3.6] Allowlisted private names (e.g. namedtuple `_asdict`) pass strict mode.
+        if self.policy.strict_guards && name.starts_with('_') && !self.policy.private_allowlist.iter().any(|n| n == name) {
             // NOTE: Dunder attributes (__xxx__) must raise AttributeError, not PermissionError.
             // Libraries like NumPy probe __array_struct__, __array_interface__ etc.
             // and need AttributeError to gracefully fallback. Only block single-underscore privates.
@@ -317,7 +555,8 @@ impl ContextGuard {
                     format!("'ContextGuard' object has no attribute '{name}'")
                 ));
             }
-            return Err(PyPermissionError::new_err(format!("Access to private attribute '{name}' denied in Strict Mode")));
+            let path = if self.path_prefix.is_empty() { name.to_string() } else { format!("{}.{name}", self.path_prefix) };
+            return Err(crate::errors::permission_denied(py, crate::messages::render(crate::messages::PRIVATE_ATTRIBUTE, &[("name", &name)]), &Denial::new(&path, "private")));
         }
 
         if name.starts_with('_') {
@@ -334,10 +573,20 @@ impl ContextGuard {
         if name == "outbox" || name == "policy_id" {
              return self.target.bind(py).getattr(name)?.extract();
         }
+        // [v3.6] Cancellation hooks and the blob store of the ProcessContext root
+        if self.path_prefix.is_empty() && matches!(name, "cancelled" | "raise_if_cancelled" | "token" | "blobs") {
+             return self.target.bind(py).getattr(name)?.extract();
+        }
 
+        let _trace = self.trace(py, || full_path.clone(), "read");
         self.check_permissions(&full_path, false)?;
 
-        let val = self.target.bind(py).getattr(name)?.unbind();
+        let target = self.target.bind(py);
+        let val = match target.downcast::<PyDict>() {
+            // Dict targets (the `tenants` view of a tenant transaction) expose their keys.
+            Ok(d) => d.get_item(name)?.ok_or_else(|| pyo3::exceptions::PyAttributeError::new_err(name.to_string()))?,
+            Err(_) => target.getattr(name)?,
+        }.unbind();
         self.apply_guard(py, val, full_path)
     }
 
@@ -353,7 +602,9 @@ impl ContextGuard {
             format!("{}.{}", self.path_prefix, name)
         };
 
+        let _trace = self.trace(py, || full_path.clone(), "write");
         self.check_permissions(&full_path, true)?;
+        let _op = crate::reentrancy::write(py, || full_path.clone())?;
 
         let old_val = self.target.bind(py).getattr(name.as_str()).ok().map(pyo3::Bound::unbind);
 
@@ -368,15 +619,15 @@ impl ContextGuard {
         let zone = resolve_zone(&name);
         
         // [RFC-001 §5] Check Zone Physics on write
-        let zone_physics = get_zone_physics(&zone);
+        let zone_physics = self.granted(&full_path).unwrap_or_else(|| get_zone_physics(&zone));
         let mut mutation_caps = zone_physics;
         if self.is_admin && !is_absolute_ceiling(&zone) {
-            mutation_caps = 31u8; // Full caps
+            mutation_caps = CAP_ALL; // Full caps
         }
         
         if (mutation_caps & CAP_UPDATE) == 0 {
-             return Err(PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for '{full_path}' (Zone Physics blocked it).")
+             return Err(crate::testing::denied(Denial::new(&full_path, "zone_physics").needs(CAP_UPDATE, mutation_caps),
+                crate::messages::render(crate::messages::ZONE_PHYSICS, &[("capability", &"UPDATE"), ("path", &full_path)])
             ));
         }
         
@@ -385,14 +636,14 @@ impl ContextGuard {
                 let tx_ref = tx.bind(py).borrow_mut();
                 tx_ref.log_internal(
                 full_path.clone(),
-                "SET".to_string(),
+                crate::delta::DeltaOp::Set.into(),
                 Some(value.clone_ref(py)),
                 old_val,
                 Some(self.target.clone_ref(py)),
                 Some(name.clone())
             )?;
             } else {
-                 return Err(PyPermissionError::new_err(format!("Security Violation: Write to '{full_path}' denied (No active transaction).")));
+                 return Err(crate::testing::denied(Denial::new(&full_path, "no_transaction"), crate::messages::render(crate::messages::NO_TRANSACTION, &[("path", &full_path)])));
             }
         }
         
@@ -422,6 +673,7 @@ impl ContextGuard {
                 }
             };
             
+            let _trace = self.trace(py, || full_path.clone(), "read");
             self.check_permissions(&full_path, false)?;
             return self.apply_guard(py, val, full_path);
         }
@@ -447,7 +699,9 @@ impl ContextGuard {
              format!("{}.{}", self.path_prefix, key_str)
         };
 
+        let _trace = self.trace(py, || full_path.clone(), "write");
         self.check_permissions(&full_path, true)?;
+        let _op = crate::reentrancy::write(py, || full_path.clone())?;
         
         let mut value_to_set = value.clone_ref(py);
         if let Ok(inner) = value.bind(py).getattr("supervisor_target") {
@@ -465,15 +719,15 @@ impl ContextGuard {
         };
 
         // [RFC-001 §5] Check Zone Physics on item write
-        let zone_physics = get_zone_physics(&zone);
+        let zone_physics = self.granted(&full_path).unwrap_or_else(|| get_zone_physics(&zone));
         let mut mutation_caps = zone_physics;
         if self.is_admin && !is_absolute_ceiling(&zone) {
-            mutation_caps = 31u8; // Full caps
+            mutation_caps = CAP_ALL; // Full caps
         }
         
         if (mutation_caps & CAP_UPDATE) == 0 {
-             return Err(PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for '{full_path}' (Zone Physics blocked it).")
+             return Err(crate::testing::denied(Denial::new(&full_path, "zone_physics").needs(CAP_UPDATE, mutation_caps),
+                crate::messages::render(crate::messages::ZONE_PHYSICS, &[("capability", &"UPDATE"), ("path", &full_path)])
             ));
         }
 
@@ -482,14 +736,14 @@ impl ContextGuard {
                 let tx_ref = tx.bind(py).borrow_mut();
                 tx_ref.log_internal(
                     full_path.clone(),
-                    "SET_ITEM".to_string(), 
+                    crate::delta::DeltaOp::SetItem.into(), 
                     Some(value_to_set.clone_ref(py)),
                     old_val,
                     Some(self.target.clone_ref(py)),
                     Some(key.to_string())
                 )?;
             } else {
-                 return Err(PyPermissionError::new_err(format!("Security Violation: Write to '{full_path}' denied (No active transaction).")));
+                 return Err(crate::testing::denied(Denial::new(&full_path, "no_transaction"), crate::messages::render(crate::messages::NO_TRANSACTION, &[("path", &full_path)])));
             }
         }
 
@@ -497,8 +751,96 @@ impl ContextGuard {
         Ok(())
     }
 
+    /// [v3.6] Dry run: calls `func(guard)` on a throwaway transaction and returns the deltas
+    /// it would produce as `[{path, op, value, old_value}]`. Nothing is committed.
+    /// NOTE: Root guards read committed state, so this tx's own pending writes are not seen.
+    #[allow(clippy::needless_pass_by_value)]
+    fn plan(&self, py: Python, func: PyObject) -> PyResult<Vec<PyObject>> {
+        let Some(tx) = &self.tx else {
+            return Err(crate::structures::ContextError::new_err("plan() needs a transaction-bound guard"));
+        };
+        let scratch = Py::new(py, tx.borrow(py).scratch(py)?)?;
+        let result = (|| {
+            let target = if let Ok(pc) = self.target.bind(py).downcast::<crate::structures::ProcessContext>() {
+                Py::new(py, pc.borrow().rebind(py, scratch.clone_ref(py))?)?.into_any()
+            } else if self.path_prefix.is_empty() {
+                self.target.clone_ref(py)
+            } else {
+                scratch.borrow(py).get_shadow(py, self.target.clone_ref(py), Some(self.path_prefix.clone()))?
+            };
+            let guard = Py::new(py, ContextGuard {
+                target,
+                policy: self.policy.clone(),
+                path_prefix: self.path_prefix.clone(),
+                tx: Some(scratch.clone_ref(py)),
+                is_admin: self.is_admin,
+                log: None,
+                decisions: self.decisions.clone(),
+            })?;
+            // Proxies log to the thread-local tx: keep the real one out of the dry run.
+            let saved = crate::proxy::swap_thread_tx(Some(scratch.clone_ref(py).into_any()));
+            let outcome = func.call1(py, (guard,));
+            crate::proxy::swap_thread_tx(saved);
+            outcome?;
+
+            let scratch = scratch.borrow(py);
+            scratch.infer_shadow_deltas(py)?;
+            let log = scratch.delta_log.relock();
+            log.iter().map(|e| Ok(e.summary(py)?.into_any().unbind())).collect()
+        })();
+        scratch.borrow(py).closed.store(true, std::sync::atomic::Ordering::SeqCst);
+        result
+    }
+
+    /// [v3.6] Mapping membership only reports keys this contract can read.
     fn __contains__(&self, py: Python, key: PyObject) -> PyResult<bool> {
-        self.target.bind(py).contains(key)
+        use crate::introspect::Access;
+        let target = self.target.bind(py);
+        if target.is_instance_of::<PyDict>() && self.access(&self.key_path(key.bind(py))).is_none() {
+            return Ok(false);
+        }
+        target.contains(key)
+    }
+
+    /// [v3.6] `dict.get` with a read check on the key; containers come back guarded.
+    #[pyo3(signature = (key, default=None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn get(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        let path = self.key_path(key.bind(py));
+        self.check_permissions(&path, false)?;
+        let target = self.target.bind(py);
+        let val = if let Ok(d) = target.downcast::<PyDict>() {
+            d.get_item(&key)?
+        } else if let Ok(name) = key.extract::<String>(py) {
+            target.getattr(name.as_str()).ok()
+        } else {
+            None
+        };
+        match val {
+            Some(v) => self.apply_guard(py, v.unbind(), path),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    /// [v3.6] Keys (or attribute names) this contract can read.
+    fn keys(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        Ok(self.readable_entries(py)?.into_iter().map(|(k, _, _)| k.unbind()).collect())
+    }
+
+    /// [v3.6] Readable values, guarded like `__getitem__` results.
+    fn values(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.readable_entries(py)?
+            .into_iter()
+            .map(|(_, path, v)| self.apply_guard(py, v.unbind(), path))
+            .collect()
+    }
+
+    /// [v3.6] Readable `(key, value)` pairs, values guarded like `__getitem__` results.
+    fn items(&self, py: Python) -> PyResult<Vec<(PyObject, PyObject)>> {
+        self.readable_entries(py)?
+            .into_iter()
+            .map(|(k, path, v)| Ok((k.unbind(), self.apply_guard(py, v.unbind(), path)?)))
+            .collect()
     }
 
     fn __iter__(&self, py: Python) -> PyResult<PyObject> {
@@ -506,6 +848,148 @@ impl ContextGuard {
         Ok(iter.unbind())
     }
 
+    /// [v3.6] `len(guard)`: size of the guarded container (needs read access to it).
+    fn __len__(&self, py: Python) -> PyResult<usize> {
+        self.check_self_read()?;
+        self.target.bind(py).len()
+    }
+
+    /// [v3.6] Python truthiness of the guarded value (needs read access to it): empty
+    /// containers are falsy, objects without `__bool__`/`__len__` are truthy.
+    fn __bool__(&self, py: Python) -> PyResult<bool> {
+        self.check_self_read()?;
+        self.target.bind(py).is_truthy()
+    }
+
+    /// [v3.6] Signed token delegating this guard's rights (paths, capability ceiling narrowed
+    /// by `caps`, strict mode) to a worker for `ttl_s` seconds. See `theus_core.guard_from_token`.
+    #[pyo3(signature = (ttl_s=300.0, caps=None))]
+    fn delegation_token(&self, py: Python, ttl_s: f64, caps: Option<u8>) -> PyResult<String> {
+        if self.is_admin {
+            return Err(PyPermissionError::new_err("Admin rights cannot be delegated"));
+        }
+        let Some(tx) = &self.tx else {
+            return Err(PyPermissionError::new_err("Delegation requires an active transaction"));
+        };
+        let grant = crate::cap_tokens::Grant {
+            inputs: self.policy.inputs.clone(),
+            outputs: self.policy.outputs.clone(),
+            caps: self.policy.caps & caps.unwrap_or(CAP_ALL),
+            strict: self.policy.strict_guards,
+            private: self.policy.private_allowlist.clone(),
+            expires_at_ms: crate::cap_tokens::expiry(ttl_s)?,
+        };
+        tx.borrow(py).capability_key(py).issue(&grant)
+    }
+
+    /// [v3.6] Invoke the callable stored at `path` as `fn(child_ctx, *args, **kwargs)`.
+    /// Requires read access to `path` plus the EXECUTE capability of its zone; the callable
+    /// gets a read-only child guard. Every invocation is recorded in the audit buffer.
+    #[pyo3(signature = (path, *args, **kwargs))]
+    fn run(&self, py: Python, path: &str, args: &Bound<'_, PyTuple>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<PyObject> {
+        let full_path = if self.path_prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}.{}", self.path_prefix, path)
+        };
+
+        // NOTE: Physics overrides (Mutable/AppendOnly/Immutable) describe mutation rights only,
+        // so EXECUTE is decided by the zone itself.
+        let zone = resolve_zone(&full_path);
+        let caps = if self.is_admin && !is_absolute_ceiling(&zone) { CAP_ALL } else { get_zone_physics(&zone) } & self.policy.caps;
+        let tags = self.tx.as_ref().and_then(|t| t.borrow(py).tags.clone());
+        if let Err(e) = self.check_permissions(&full_path, false) {
+            crate::audit::log_global_tagged("EXECUTE_DENIED", &full_path, tags.as_ref());
+            return Err(e);
+        }
+        if caps & CAP_EXECUTE == 0 {
+            crate::audit::log_global_tagged("EXECUTE_DENIED", &full_path, tags.as_ref());
+            return Err(crate::testing::denied(Denial::new(&full_path, "capability").needs(CAP_EXECUTE, caps),
+                crate::messages::render(crate::messages::CAPABILITY_REQUIRED, &[
+                    ("capability", &"EXECUTE"), ("operation", &"call"), ("path", &full_path), ("lens", &format!("{caps:04b}")),
+                ])
+            ));
+        }
+
+        let mut func = self.target.bind(py).clone();
+        for segment in path.split('.') {
+            func = if func.is_instance_of::<PyDict>() {
+                func.get_item(segment)?
+            } else {
+                func.getattr(segment).or_else(|_| func.get_item(segment))?
+            };
+        }
+        // Proxies wrap any non-primitive value, callables included.
+        if let Ok(inner) = func.getattr("supervisor_target") {
+            func = inner;
+        }
+        if !func.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!("'{full_path}' is not callable")));
+        }
+
+        let child = ContextGuard::new_internal(
+            self.target.clone_ref(py),
+            self.policy.inputs.clone(),
+            Vec::new(), // read-only
+            self.path_prefix.clone(),
+            self.tx.as_ref().map(|t| t.clone_ref(py)),
+            false,
+            self.policy.strict_guards,
+            self.policy.private_allowlist.clone(),
+        )?;
+        let child = ContextGuard { policy: intern_policy(SharedPolicy { caps: self.policy.caps, ..(*child.policy).clone() }), ..child };
+        let mut call_args = vec![Py::new(py, child)?.into_any()];
+        call_args.extend(args.iter().map(pyo3::Bound::unbind));
+
+        let result = func.call(PyTuple::new_bound(py, call_args), kwargs);
+        match &result {
+            Ok(_) => crate::audit::log_global_tagged("EXECUTE", &format!("{full_path} ok"), tags.as_ref()),
+            Err(e) => crate::audit::log_global_tagged("EXECUTE", &format!("{full_path} failed: {e}"), tags.as_ref()),
+        }
+        result.map(pyo3::Bound::unbind)
+    }
+
+    /// [v3.6] `dir(ctx)`: the guard API plus the fields this contract can read.
+    fn __dir__(slf: &Bound<'_, Self>) -> PyResult<Vec<String>> {
+        let guard = slf.borrow();
+        let fields = crate::introspect::visible_names(guard.target.bind(slf.py()), &guard.path_prefix, true, &*guard)?;
+        crate::introspect::dir_with(slf.as_any(), fields)
+    }
+
+    /// [v3.6] Readable subtree as `{path: {zone, access, type, len?}}` ("rw" = writable
+    /// under this contract), at most `max_depth` levels and `max_keys` children per level.
+    #[pyo3(signature = (max_depth=3, max_keys=50, narrow=None))]
+    fn describe(&self, py: Python, max_depth: usize, max_keys: usize, narrow: Option<&Bound<'_, PyAny>>) -> PyResult<PyObject> {
+        let access = crate::introspect::Narrowed { base: self, narrow };
+        crate::introspect::describe(py, self.target.bind(py), &self.path_prefix, true, max_depth, max_keys, &access)
+    }
+
+    /// [v3.6] Readable subtree drawn as indented text (or HTML) with zone badges; leaf
+    /// values are truncated and at most `max_nodes` entries are shown.
+    #[pyo3(signature = (html=false, max_depth=3, max_keys=20, max_nodes=200, narrow=None))]
+    fn render_tree(&self, py: Python, html: bool, max_depth: usize, max_keys: usize, max_nodes: usize, narrow: Option<&Bound<'_, PyAny>>) -> PyResult<String> {
+        let title = if self.path_prefix.is_empty() {
+            "ContextGuard (root)".to_string()
+        } else {
+            format!("ContextGuard '{}'", self.path_prefix)
+        };
+        let limits = crate::introspect::Limits { max_depth, max_keys, max_nodes };
+        let access = crate::introspect::Narrowed { base: self, narrow };
+        crate::introspect::render(self.target.bind(py), &self.path_prefix, true, &title, html, &limits, &access)
+    }
+
+    /// [v3.6] Jupyter rich display.
+    fn _repr_html_(&self, py: Python) -> PyResult<String> {
+        self.render_tree(py, true, 3, 20, 200, None)
+    }
+
+    /// [v3.6] `IPython` pretty printer.
+    fn _repr_pretty_(&self, py: Python, p: &Bound<'_, PyAny>, cycle: bool) -> PyResult<()> {
+        let text = if cycle { "ContextGuard(...)".to_string() } else { self.render_tree(py, false, 3, 20, 200, None)? };
+        p.call_method1("text", (text,))?;
+        Ok(())
+    }
+
     /// DX Log method: ctx.log("msg")
     /// Writes to standard output for now (or could use meta logs if accessible)
     #[allow(clippy::unused_self)]
@@ -516,6 +1000,9 @@ impl ContextGuard {
     /// [RFC-001] Elevate this guard to Admin status for current thread.
     /// Used by `AdminTransaction` context manager.
     fn _elevate(&mut self, enabled: bool) {
+        if self.is_admin != enabled {
+            self.decisions = Arc::default();
+        }
         self.is_admin = enabled;
     }
 }
@@ -0,0 +1,298 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use crate::cancellation::{CancellationToken, Tokens};
+use crate::conflict::ConflictManager;
+use crate::locks::Relock;
+use crate::trace::{Session, Traces};
+
+// [v3.6] Dead-man switch for processes. `execute_process_async` registers every run while
+// its awaitable runs (see `tracked()` below); the process (or anyone holding its name)
+// calls `engine.heartbeat(name)` to prove progress. A run is stuck once the time since its
+// last heartbeat (or start) exceeds its expected duration. `engine.health()` reports stuck
+// runs; with `set_stuck_policy("abort")` a background timer also cancels their transaction
+// (writes and commit then raise TransactionCancelledError) and releases the conflict
+// manager's priority slot they hold.
+// NOTE: Ages use the engine clock (`clock::monotonic_ms`); the timer thread itself sleeps in
+// real time and holds no GIL, so it only flips flags - Python code never runs on it.
+
+pub const DEFAULT_CHECK_INTERVAL_MS: u64 = 1000;
+
+struct Run {
+    process: String,
+    tx_id: Option<u64>,
+    cancel: Option<Arc<AtomicBool>>,
+    started_at: u64,
+    last_beat: u64,
+    beats: u64,
+    aborted: bool,
+}
+
+#[derive(Default)]
+struct Policy {
+    default_expected_ms: Option<u64>,
+    expected_ms: HashMap<String, u64>,
+    abort: bool,
+    interval_ms: u64,
+}
+
+pub struct Heartbeats {
+    runs: Mutex<BTreeMap<u64, Run>>,
+    policy: Mutex<Policy>,
+    next_run: AtomicU64,
+    aborted_total: AtomicU64,
+    conflicts: Arc<ConflictManager>,
+    /// Set while a timer thread is running for this engine.
+    timer: AtomicBool,
+}
+
+impl Heartbeats {
+    pub fn new(conflicts: Arc<ConflictManager>) -> Self {
+        Heartbeats {
+            runs: Mutex::new(BTreeMap::new()),
+            policy: Mutex::new(Policy { interval_ms: DEFAULT_CHECK_INTERVAL_MS, ..Policy::default() }),
+            next_run: AtomicU64::new(1),
+            aborted_total: AtomicU64::new(0),
+            conflicts,
+            timer: AtomicBool::new(false),
+        }
+    }
+
+    /// Registers a run of `process` (optionally inside transaction `tx_id`, cancelled via
+    /// `cancel`); returns its id for `finish`.
+    pub fn start(&self, process: &str, tx_id: Option<u64>, cancel: Option<Arc<AtomicBool>>) -> u64 {
+        let id = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let now = crate::clock::monotonic_ms();
+        self.runs.relock().insert(id, Run {
+            process: process.to_string(),
+            tx_id,
+            cancel,
+            started_at: now,
+            last_beat: now,
+            beats: 0,
+            aborted: false,
+        });
+        id
+    }
+
+    pub fn finish(&self, run: u64) {
+        self.runs.relock().remove(&run);
+    }
+
+    /// Records progress for every running `process` run; returns how many were refreshed.
+    pub fn beat(&self, process: &str) -> usize {
+        let now = crate::clock::monotonic_ms();
+        let mut runs = self.runs.relock();
+        let mut n = 0;
+        for run in runs.values_mut().filter(|r| r.process == process) {
+            run.last_beat = now;
+            run.beats += 1;
+            n += 1;
+        }
+        n
+    }
+
+    pub fn set_expected(&self, process: Option<String>, expected_ms: Option<u64>) {
+        let mut policy = self.policy.relock();
+        match (process, expected_ms) {
+            (None, ms) => policy.default_expected_ms = ms,
+            (Some(p), Some(ms)) => { policy.expected_ms.insert(p, ms); }
+            (Some(p), None) => { policy.expected_ms.remove(&p); }
+        }
+    }
+
+    /// `action` "report" or "abort"; "abort" starts the timer thread (once per engine).
+    pub fn set_policy(self: &Arc<Self>, py: Python, action: &str, check_interval_ms: Option<u64>) -> PyResult<()> {
+        let abort = match action {
+            "report" => false,
+            "abort" => true,
+            other => return Err(PyValueError::new_err(format!("Unknown stuck policy '{other}' (expected 'report' or 'abort')"))),
+        };
+        {
+            let mut policy = self.policy.relock();
+            policy.abort = abort;
+            if let Some(ms) = check_interval_ms {
+                policy.interval_ms = ms.max(1);
+            }
+        }
+        if abort && !self.timer.swap(true, Ordering::SeqCst) {
+            let weak = Arc::downgrade(self);
+            py.allow_threads(|| {
+                std::thread::Builder::new()
+                    .name("theus-heartbeat".to_string())
+                    .spawn(move || timer_loop(&weak))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Flags runs past their expected duration and, under the abort policy, cancels them.
+    /// Returns whether the abort policy is active.
+    fn sweep(&self) -> bool {
+        let policy = self.policy.relock();
+        let now = crate::clock::monotonic_ms();
+        let mut runs = self.runs.relock();
+        for run in runs.values_mut() {
+            let Some(expected) = policy.expected_ms.get(&run.process).copied().or(policy.default_expected_ms) else { continue };
+            if run.aborted || now.saturating_sub(run.last_beat) <= expected || !policy.abort {
+                continue;
+            }
+            run.aborted = true;
+            self.aborted_total.fetch_add(1, Ordering::Relaxed);
+            if let Some(cancel) = &run.cancel {
+                cancel.store(true, Ordering::SeqCst);
+            }
+            self.conflicts.release(&run.process);
+            crate::audit::log_global("PROCESS_STUCK", &format!(
+                "{} aborted after {}ms without heartbeat (expected {expected}ms)", run.process, now.saturating_sub(run.last_beat)
+            ));
+        }
+        policy.abort
+    }
+
+    /// `{status, running: [...], stuck, aborted_total, policy}`.
+    pub fn health(&self, py: Python) -> PyResult<PyObject> {
+        self.sweep();
+        let policy = self.policy.relock();
+        let now = crate::clock::monotonic_ms();
+        let runs = self.runs.relock();
+        let mut stuck = 0usize;
+        let mut running = Vec::with_capacity(runs.len());
+        for (id, run) in runs.iter() {
+            let expected = policy.expected_ms.get(&run.process).copied().or(policy.default_expected_ms);
+            let silent_ms = now.saturating_sub(run.last_beat);
+            let is_stuck = run.aborted || expected.is_some_and(|e| silent_ms > e);
+            stuck += usize::from(is_stuck);
+            let d = PyDict::new_bound(py);
+            d.set_item("run_id", id)?;
+            d.set_item("process", &run.process)?;
+            d.set_item("tx_id", run.tx_id)?;
+            d.set_item("elapsed_ms", now.saturating_sub(run.started_at))?;
+            d.set_item("since_heartbeat_ms", silent_ms)?;
+            d.set_item("heartbeats", run.beats)?;
+            d.set_item("expected_ms", expected)?;
+            d.set_item("stuck", is_stuck)?;
+            d.set_item("aborted", run.aborted)?;
+            running.push(d);
+        }
+        let out = PyDict::new_bound(py);
+        out.set_item("status", if stuck == 0 { "ok" } else { "degraded" })?;
+        out.set_item("running", running)?;
+        out.set_item("stuck", stuck)?;
+        out.set_item("aborted_total", self.aborted_total.load(Ordering::Relaxed))?;
+        out.set_item("policy", if policy.abort { "abort" } else { "report" })?;
+        Ok(out.into_any().unbind())
+    }
+}
+
+fn timer_loop(heartbeats: &Weak<Heartbeats>) {
+    loop {
+        let interval = match heartbeats.upgrade() {
+            Some(h) => h.policy.relock().interval_ms,
+            None => return,
+        };
+        std::thread::sleep(std::time::Duration::from_millis(interval));
+        let Some(h) = heartbeats.upgrade() else { return };
+        if !h.sweep() {
+            h.timer.store(false, Ordering::SeqCst);
+            return;
+        }
+    }
+}
+
+// Wraps the process awaitable in a plain coroutine (no Task), so it runs in the caller's
+// task with the caller's scheduling. Registration happens when the coroutine starts and is
+// undone in `finally`, so an awaitable that is never awaited leaves nothing behind.
+const TRACKED_RUN_SRC: &std::ffi::CStr = c"
+import asyncio
+
+async def tracked(awaitable, run):
+    run.enter(asyncio.current_task())
+    status, error = 'ok', None
+    try:
+        return await awaitable
+    except asyncio.CancelledError:
+        status = 'cancelled'
+        raise
+    except BaseException as exc:
+        status, error = 'error', exc
+        raise
+    finally:
+        run.exit(status, error)
+";
+
+static TRACKED_RUN: GILOnceCell<PyObject> = GILOnceCell::new();
+
+/// `tracked(awaitable, run)` coroutine that keeps `run` registered while `awaitable` runs.
+pub fn track(py: Python<'_>, awaitable: PyObject, run: TrackedRun) -> PyResult<Bound<'_, PyAny>> {
+    let tracked = TRACKED_RUN.get_or_try_init(py, || -> PyResult<PyObject> {
+        let module = PyModule::from_code(py, TRACKED_RUN_SRC, c"theus_core/tracked_run.py", c"theus_core.tracked_run")?;
+        Ok(module.getattr("tracked")?.unbind())
+    })?;
+    tracked.bind(py).call1((awaitable, run))
+}
+
+/// One process run, as seen by `tracked()`: heartbeat registration, trace recording and the
+/// live cancellation token.
+#[pyclass(module = "theus_core")]
+pub struct TrackedRun {
+    process: String,
+    tx_id: Option<u64>,
+    cancel: Option<Arc<AtomicBool>>,
+    token: Py<CancellationToken>,
+    heartbeats: Arc<Heartbeats>,
+    tokens: Arc<Tokens>,
+    traces: Arc<Mutex<Traces>>,
+    active: Mutex<Option<Active>>,
+}
+
+/// Registrations held while the run is active.
+struct Active {
+    run: u64,
+    token_id: u64,
+    session: Option<Arc<Session>>,
+}
+
+impl TrackedRun {
+    pub fn new(
+        process: &str,
+        tx_id: Option<u64>,
+        cancel: Option<Arc<AtomicBool>>,
+        token: Py<CancellationToken>,
+        heartbeats: Arc<Heartbeats>,
+        tokens: Arc<Tokens>,
+        traces: Arc<Mutex<Traces>>,
+    ) -> Self {
+        TrackedRun { process: process.to_string(), tx_id, cancel, token, heartbeats, tokens, traces, active: Mutex::new(None) }
+    }
+}
+
+#[pymethods]
+impl TrackedRun {
+    fn enter(&self, py: Python, task: PyObject) {
+        let run = self.heartbeats.start(&self.process, self.tx_id, self.cancel.clone());
+        // [v3.6] Trace mode: the first run after engine.trace(name) records its accesses
+        // (every run does while drift tracking is on).
+        let session = self.tx_id.and_then(|id| crate::trace::open(&self.process, id, &self.traces));
+        self.token.get().attach_task((!task.is_none(py)).then_some(task));
+        let token_id = self.tokens.register(self.token.clone_ref(py));
+        *self.active.relock() = Some(Active { run, token_id, session });
+    }
+
+    #[pyo3(signature = (status, error=None))]
+    fn exit(&self, py: Python, status: &str, error: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let Some(Active { run, token_id, session }) = self.active.relock().take() else { return Ok(()) };
+        self.heartbeats.finish(run);
+        // The awaiting task outlives the run; a late cancel must not reach it.
+        self.token.get().attach_task(None);
+        self.tokens.release(token_id);
+        match session {
+            Some(session) => crate::trace::close(py, &session, status, error.map(|e| e.repr()).transpose()?.map(|r| r.to_string())),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::locks::Relock;
+
+// [v3.6] Managed Heavy objects. `engine.alloc_heavy()` registers a value (shm block, GPU
+// buffer, ...) under a reference count and returns a `HeavyHandle`. References are held by
+// handles (until `release()` or garbage collection) and by open transactions, which pin
+// every handle in the committed Heavy zone for their lifetime. When the count drops to
+// zero the entry is dropped, its bytes leave the quota and `on_release(value)` runs.
+// NOTE: Finalizer errors go to sys.unraisablehook; they fire on whatever thread drops the
+// last reference.
+// DLPack tensors record their device at allocation; their handles are DLPack producers
+// themselves, never copied (copy/deepcopy return the handle) and not picklable - cross-process
+// handoff goes through `ipc_export()` / `HeavyHandle.ipc_import()`.
+
+struct Entry {
+    name: String,
+    value: PyObject,
+    nbytes: usize,
+    refs: usize,
+    on_release: Option<PyObject>,
+    device: Option<(i32, i32)>,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<u64, Entry>,
+    used_bytes: usize,
+    quota_bytes: Option<usize>,
+    next_id: u64,
+}
+
+#[derive(Default)]
+pub struct HeavyStore {
+    inner: Mutex<Inner>,
+}
+
+/// Size of `value`: its `nbytes` attribute (numpy, torch, shm views) or `sys.getsizeof`.
+fn measure(py: Python, value: &Bound<'_, PyAny>) -> PyResult<usize> {
+    if let Ok(n) = value.getattr("nbytes").and_then(|n| n.extract::<usize>()) {
+        return Ok(n);
+    }
+    py.import_bound("sys")?.call_method1("getsizeof", (value,))?.extract()
+}
+
+impl HeavyStore {
+    pub fn set_quota(&self, quota_bytes: Option<usize>) {
+        self.inner.relock().quota_bytes = quota_bytes;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.relock().entries.is_empty()
+    }
+
+    pub fn alloc(
+        self: &Arc<Self>, py: Python, name: String, value: PyObject, nbytes: Option<usize>, on_release: Option<PyObject>,
+    ) -> PyResult<HeavyHandle> {
+        let nbytes = match nbytes {
+            Some(n) => n,
+            None => measure(py, value.bind(py))?,
+        };
+        let mut inner = self.inner.relock();
+        if let Some(quota) = inner.quota_bytes {
+            if inner.used_bytes + nbytes > quota {
+                return Err(crate::errors::quota_exceeded(py, &name, inner.used_bytes, quota, nbytes));
+            }
+        }
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.used_bytes += nbytes;
+        let device = crate::dlpack::device_of(value.bind(py));
+        inner.entries.insert(id, Entry { name, value, nbytes, refs: 1, on_release, device });
+        Ok(HeavyHandle { id, store: self.clone(), held: AtomicBool::new(true) })
+    }
+
+    /// Adds a reference; false if the entry is already finalized.
+    pub fn incref(&self, id: u64) -> bool {
+        match self.inner.relock().entries.get_mut(&id) {
+            Some(entry) => {
+                entry.refs += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops a reference and finalizes the entry once none remain.
+    pub fn decref(&self, py: Python, id: u64) {
+        let finalized = {
+            let mut inner = self.inner.relock();
+            let Some(entry) = inner.entries.get_mut(&id) else { return };
+            entry.refs -= 1;
+            if entry.refs > 0 {
+                return;
+            }
+            let entry = inner.entries.remove(&id).expect("entry present");
+            inner.used_bytes -= entry.nbytes;
+            entry
+        };
+        crate::audit::log_global("HEAVY_RELEASED", &format!("#{id} '{}' ({} bytes)", finalized.name, finalized.nbytes));
+        if let Some(cb) = &finalized.on_release {
+            if let Err(e) = cb.call1(py, (finalized.value.clone_ref(py),)) {
+                e.write_unraisable_bound(py, Some(cb.bind(py)));
+            }
+        }
+    }
+
+    /// Pins every managed handle among `values` (committed Heavy zone) for a transaction.
+    pub fn pin_all<'a>(&self, py: Python, values: impl IntoIterator<Item = &'a PyObject>) -> Vec<u64> {
+        values.into_iter()
+            .filter_map(|v| v.downcast_bound::<HeavyHandle>(py).ok().map(|h| h.borrow().id))
+            .filter(|&id| self.incref(id))
+            .collect()
+    }
+
+    /// [v3.6] fsck "registry" check: byte accounting (recomputed on `repair`), unreferenced
+    /// entries, and handles in the committed Heavy zone whose entry is gone.
+    pub fn fsck<'a>(&self, py: Python, committed: impl IntoIterator<Item = &'a PyObject>, repair: bool) -> Vec<crate::fsck::Issue> {
+        use crate::fsck::Issue;
+        let mut issues = Vec::new();
+        let mut inner = self.inner.relock();
+        let used: usize = inner.entries.values().map(|e| e.nbytes).sum();
+        if used != inner.used_bytes {
+            issues.push(Issue::error("registry", format!("heavy store accounts {} bytes, entries hold {used}", inner.used_bytes)).repairable(repair));
+            if repair {
+                inner.used_bytes = used;
+            }
+        }
+        let mut ids: Vec<u64> = inner.entries.iter().filter(|(_, e)| e.refs == 0).map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        for id in ids {
+            issues.push(Issue::error("registry", format!("heavy handle #{id} '{}' has no references left", inner.entries[&id].name)));
+        }
+        for value in committed {
+            if let Ok(handle) = value.downcast_bound::<HeavyHandle>(py) {
+                let id = handle.borrow().id;
+                if !inner.entries.contains_key(&id) {
+                    issues.push(Issue::error("registry", format!("committed Heavy zone holds released handle #{id}")));
+                }
+            }
+        }
+        issues
+    }
+
+    pub fn usage(&self, py: Python) -> PyResult<PyObject> {
+        let inner = self.inner.relock();
+        let d = PyDict::new_bound(py);
+        d.set_item("used_bytes", inner.used_bytes)?;
+        d.set_item("quota_bytes", inner.quota_bytes)?;
+        d.set_item("handles", inner.entries.len())?;
+        Ok(d.into_any().unbind())
+    }
+}
+
+/// Reference to a managed Heavy value. Store it in the Heavy zone and read it with `get()`.
+#[pyclass(module = "theus_core")]
+pub struct HeavyHandle {
+    id: u64,
+    store: Arc<HeavyStore>,
+    /// This handle still owns one reference.
+    held: AtomicBool,
+}
+
+impl Drop for HeavyHandle {
+    fn drop(&mut self) {
+        if self.held.swap(false, Ordering::SeqCst) {
+            Python::with_gil(|py| self.store.decref(py, self.id));
+        }
+    }
+}
+
+impl HeavyHandle {
+    fn dlpack_device(&self) -> PyResult<(i32, i32)> {
+        self.entry_field(|e| e.device)?
+            .ok_or_else(|| pyo3::exceptions::PyTypeError::new_err(format!("Heavy handle #{} does not hold a DLPack tensor", self.id)))
+    }
+
+    fn entry_field<T>(&self, f: impl FnOnce(&Entry) -> T) -> PyResult<T> {
+        let inner = self.store.inner.relock();
+        inner.entries.get(&self.id).map(f)
+            .ok_or_else(|| PyValueError::new_err(format!("Heavy handle #{} was released", self.id)))
+    }
+}
+
+#[pymethods]
+impl HeavyHandle {
+    #[getter]
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[getter]
+    fn name(&self) -> PyResult<String> {
+        self.entry_field(|e| e.name.clone())
+    }
+
+    #[getter]
+    fn nbytes(&self) -> PyResult<usize> {
+        self.entry_field(|e| e.nbytes)
+    }
+
+    /// `(device, index)` of a `DLPack` tensor, e.g. `("cuda", 0)`; None for other values.
+    #[getter]
+    fn device(&self) -> PyResult<Option<(&'static str, i32)>> {
+        self.entry_field(|e| e.device.map(|(code, idx)| (crate::dlpack::device_name(code), idx)))
+    }
+
+    /// Live references (handles + pinning transactions); 0 once finalized.
+    #[getter]
+    fn refcount(&self) -> usize {
+        self.entry_field(|e| e.refs).unwrap_or(0)
+    }
+
+    /// The managed value. Readable until the last reference is gone, even after this
+    /// handle's own `release()`.
+    fn get(&self, py: Python) -> PyResult<PyObject> {
+        self.entry_field(|e| e.value.clone_ref(py))
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn __dlpack__(&self, py: Python, args: &Bound<'_, pyo3::types::PyTuple>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<PyObject> {
+        self.dlpack_device()?;
+        Ok(self.get(py)?.bind(py).call_method("__dlpack__", args, kwargs)?.unbind())
+    }
+
+    fn __dlpack_device__(&self) -> PyResult<(i32, i32)> {
+        self.dlpack_device()
+    }
+
+    /// A handle is a reference: copies share the managed value.
+    fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __deepcopy__(slf: Py<Self>, _memo: PyObject) -> Py<Self> {
+        slf
+    }
+
+    fn __reduce__(&self) -> PyResult<PyObject> {
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "HeavyHandle #{} cannot be pickled; use ipc_export() to hand a CUDA tensor to another process", self.id
+        )))
+    }
+
+    /// Picklable CUDA IPC payload for the managed tensor (torch / cupy on CUDA).
+    fn ipc_export(&self, py: Python) -> PyResult<PyObject> {
+        crate::dlpack::ipc_export(py, self.get(py)?.bind(py))
+    }
+
+    /// Rebuild the tensor from an `ipc_export()` payload in the receiving process.
+    #[staticmethod]
+    fn ipc_import(py: Python, payload: &Bound<'_, PyDict>) -> PyResult<PyObject> {
+        crate::dlpack::ipc_import(py, payload)
+    }
+
+    /// New handle holding its own reference (e.g. for a worker outliving the transaction).
+    fn acquire(&self) -> PyResult<HeavyHandle> {
+        if !self.store.incref(self.id) {
+            return Err(PyValueError::new_err(format!("Heavy handle #{} was released", self.id)));
+        }
+        Ok(HeavyHandle { id: self.id, store: self.store.clone(), held: AtomicBool::new(true) })
+    }
+
+    /// Give up this handle's reference (idempotent).
+    fn release(&self, py: Python) {
+        if self.held.swap(false, Ordering::SeqCst) {
+            self.store.decref(py, self.id);
+        }
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&self, py: Python, _args: &Bound<'_, pyo3::types::PyTuple>) {
+        self.release(py);
+    }
+
+    fn __repr__(&self) -> String {
+        match self.entry_field(|e| (e.name.clone(), e.nbytes, e.refs)) {
+            Ok((name, nbytes, refs)) => format!("HeavyHandle(#{}, '{name}', nbytes={nbytes}, refs={refs})", self.id),
+            Err(_) => format!("HeavyHandle(#{}, released)", self.id),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyLong, PyString, PyTuple};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Secondary indexes. `engine.create_index("domain.orders[*].customer_id")` maps every
+// value found at the pattern to the paths holding it; `engine.lookup(index, value)` answers
+// "which orders belong to customer 7" without scanning. Pattern segments are names or `*`
+// (any dict key / list index, `[*]` is the same); the zone must be named.
+// Maintenance is incremental: after each commit only the `zone.field` roots lineage reports as
+// changed are re-walked, and within them the walk skips subtrees the new state shares with the
+// old one (commits copy on write, so untouched containers keep their identity).
+// Keys are str / int / float / bool / None values; other leaves are not indexed. Private-zone
+// patterns are rejected. Loading a snapshot rebuilds every index.
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Seg {
+    Name(String),
+    Any,
+}
+
+/// A parsed `zone.a[*].b` pattern.
+#[derive(Clone)]
+pub struct Pattern {
+    pub text: String,
+    segs: Vec<Seg>,
+}
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> PyResult<Pattern> {
+        let normalized = pattern.replace("[*]", ".*").replace('[', ".").replace(']', "");
+        let segs: Vec<Seg> = normalized.split('.').map(|s| match s {
+            "*" => Seg::Any,
+            name => Seg::Name(name.to_string()),
+        }).collect();
+        if segs.iter().any(|s| matches!(s, Seg::Name(n) if n.is_empty())) || !matches!(segs[0], Seg::Name(_)) {
+            return Err(PyValueError::new_err(format!("Invalid pattern '{pattern}' (expected 'zone.field[*].leaf')")));
+        }
+        if crate::zones::resolve_zone(&normalized) == crate::zones::ContextZone::Private {
+            return Err(PyValueError::new_err(format!("Pattern '{pattern}' is in the Private zone")));
+        }
+        Ok(Pattern { text: normalized, segs })
+    }
+
+    /// Does the `zone.field` path `root` hold values this pattern selects?
+    fn covers(&self, root: &str) -> bool {
+        let mut parts = root.split('.');
+        self.segs.iter().take(2).all(|seg| match (seg, parts.next()) {
+            (Seg::Any, Some(_)) => true,
+            (Seg::Name(n), Some(p)) => n == p,
+            (_, None) => true,
+        })
+    }
+}
+
+fn join(path: &str, key: &str, list: bool) -> String {
+    match (path.is_empty(), list) {
+        (true, _) => key.to_string(),
+        (false, true) => format!("{path}[{key}]"),
+        (false, false) => format!("{path}.{key}"),
+    }
+}
+
+fn load(value: Bound<'_, PyAny>) -> PyResult<Bound<'_, PyAny>> {
+    if crate::spill::is_placeholder(&value) {
+        let py = value.py();
+        return Ok(crate::spill::fault_in(py, value.unbind())?.into_bound(py));
+    }
+    Ok(value)
+}
+
+/// `(key, child)` pairs of a dict or list (list keys are indexes).
+fn entries<'py>(value: &Bound<'py, PyAny>) -> PyResult<Vec<(String, Bound<'py, PyAny>)>> {
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut out = Vec::with_capacity(dict.len());
+        for (k, v) in dict.iter() {
+            out.push((k.str()?.to_string(), load(v)?));
+        }
+        return Ok(out);
+    }
+    if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        return value.iter()?.enumerate().map(|(i, v)| Ok((i.to_string(), load(v?)?))).collect();
+    }
+    Ok(Vec::new())
+}
+
+fn get<'py>(value: &Bound<'py, PyAny>, key: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        return dict.get_item(key)?.map(load).transpose();
+    }
+    if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        if let Ok(i) = key.parse::<usize>() {
+            return if i < value.len()? { value.get_item(i).and_then(load).map(Some) } else { Ok(None) };
+        }
+    }
+    Ok(None)
+}
+
+fn is_list(value: Option<&Bound<'_, PyAny>>) -> bool {
+    value.is_some_and(|v| v.is_instance_of::<PyList>() || v.is_instance_of::<PyTuple>())
+}
+
+/// A leaf of a pattern that differs between two states: `(path, old, new)` (None = absent).
+pub type Change<'py> = (String, Option<Bound<'py, PyAny>>, Option<Bound<'py, PyAny>>);
+
+/// Leaves under `segs` that differ between `old` and `new`.
+fn diff_walk<'py>(segs: &[Seg], path: &str, old: Option<&Bound<'py, PyAny>>, new: Option<&Bound<'py, PyAny>>, out: &mut Vec<Change<'py>>) -> PyResult<()> {
+    if let (Some(o), Some(n)) = (old, new) {
+        if o.is(n) {
+            return Ok(());
+        }
+    }
+    let Some((seg, rest)) = segs.split_first() else {
+        out.push((path.to_string(), old.cloned(), new.cloned()));
+        return Ok(());
+    };
+    let list = is_list(new) || (new.is_none() && is_list(old));
+    match seg {
+        Seg::Name(key) => {
+            let o = old.map(|o| get(o, key)).transpose()?.flatten();
+            let n = new.map(|n| get(n, key)).transpose()?.flatten();
+            if o.is_some() || n.is_some() {
+                diff_walk(rest, &join(path, key, list), o.as_ref(), n.as_ref(), out)?;
+            }
+        }
+        Seg::Any => {
+            let old_entries = old.map(entries).transpose()?.unwrap_or_default();
+            let new_entries = new.map(entries).transpose()?.unwrap_or_default();
+            let mut old_map: HashMap<String, Bound<PyAny>> = old_entries.into_iter().collect();
+            for (key, n) in new_entries {
+                let o = old_map.remove(&key);
+                diff_walk(rest, &join(path, &key, list), o.as_ref(), Some(&n), out)?;
+            }
+            for (key, o) in old_map {
+                diff_walk(rest, &join(path, &key, is_list(old)), Some(&o), None, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Leaves of `pattern` that changed between two committed states, given the `zone.field`
+/// paths lineage reported as changed (`None` = compare everything).
+pub fn diff<'py>(py: Python<'py>, pattern: &Pattern, old: Option<&State>, new: &State, changed: Option<&[String]>) -> PyResult<Vec<Change<'py>>> {
+    let Seg::Name(zone) = &pattern.segs[0] else { return Ok(Vec::new()) };
+    let mut out = Vec::new();
+    if let Some(changed) = changed {
+        if !changed.iter().any(|root| pattern.covers(root)) {
+            return Ok(out);
+        }
+    }
+    let old_zone = old.and_then(|s| s.data.get(zone)).map(|v| v.bind(py).clone());
+    let new_zone = new.data.get(zone).map(|v| v.bind(py).clone());
+    diff_walk(&pattern.segs[1..], zone, old_zone.as_ref(), new_zone.as_ref(), &mut out)?;
+    Ok(out)
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Key {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(u64),
+    Str(String),
+}
+
+impl Key {
+    /// The index key of `value`, None for values that are not indexed.
+    fn of(value: &Bound<'_, PyAny>) -> Option<Key> {
+        if value.is_none() {
+            Some(Key::None)
+        } else if let Ok(b) = value.downcast::<PyBool>() {
+            Some(Key::Bool(b.is_true()))
+        } else if value.is_instance_of::<PyLong>() {
+            value.extract().ok().map(Key::Int)
+        } else if let Ok(f) = value.downcast::<PyFloat>() {
+            // 1.0 finds the same paths as 1, as it would with ==.
+            let f = f.value();
+            if f.fract() == 0.0 && f.abs() < 9.0e15 {
+                #[allow(clippy::cast_possible_truncation)]
+                return Some(Key::Int(f as i64));
+            }
+            Some(Key::Float(f.to_bits()))
+        } else if let Ok(s) = value.downcast::<PyString>() {
+            s.to_str().ok().map(|s| Key::Str(s.to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+struct Index {
+    pattern: Pattern,
+    by_key: HashMap<Key, BTreeSet<String>>,
+    by_path: HashMap<String, Key>,
+}
+
+impl Index {
+    fn apply(&mut self, changes: Vec<(String, Option<Key>)>) {
+        for (path, key) in changes {
+            if let Some(old) = self.by_path.remove(&path) {
+                if let Some(paths) = self.by_key.get_mut(&old) {
+                    paths.remove(&path);
+                    if paths.is_empty() {
+                        self.by_key.remove(&old);
+                    }
+                }
+            }
+            if let Some(key) = key {
+                self.by_key.entry(key.clone()).or_default().insert(path.clone());
+                self.by_path.insert(path, key);
+            }
+        }
+    }
+}
+
+fn keyed(changes: Vec<Change<'_>>) -> Vec<(String, Option<Key>)> {
+    changes.into_iter().map(|(path, _, value)| (path, value.as_ref().and_then(Key::of))).collect()
+}
+
+#[derive(Default)]
+pub struct Indexes {
+    indexes: Mutex<HashMap<String, Index>>,
+}
+
+impl Indexes {
+    /// Builds (or rebuilds) index `name` from `state`.
+    pub fn create(&self, py: Python, name: &str, pattern: Pattern, state: &State) -> PyResult<()> {
+        // NOTE: Never hold the index lock while walking Python objects.
+        let changes = keyed(diff(py, &pattern, None, state, None)?);
+        let mut index = Index { pattern, by_key: HashMap::new(), by_path: HashMap::new() };
+        index.apply(changes);
+        self.indexes.relock().insert(name.to_string(), index);
+        Ok(())
+    }
+
+    pub fn drop_index(&self, name: &str) -> bool {
+        self.indexes.relock().remove(name).is_some()
+    }
+
+    /// Re-walks the `changed` roots of a commit from `old` to `new`.
+    #[allow(clippy::similar_names)]
+    pub fn committed(&self, py: Python, old: &State, new: &State, changed: &[String]) -> PyResult<()> {
+        let patterns: Vec<(String, Pattern)> = self.indexes.relock().iter().map(|(n, i)| (n.clone(), i.pattern.clone())).collect();
+        for (name, pattern) in patterns {
+            let changes = keyed(diff(py, &pattern, Some(old), new, Some(changed))?);
+            if let Some(index) = self.indexes.relock().get_mut(&name) {
+                index.apply(changes);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds every index from `state` (after it replaced the committed state wholesale).
+    pub fn rebuild(&self, py: Python, state: &State) -> PyResult<()> {
+        let patterns: Vec<(String, Pattern)> = self.indexes.relock().iter().map(|(n, i)| (n.clone(), i.pattern.clone())).collect();
+        for (name, pattern) in patterns {
+            self.create(py, &name, pattern, state)?;
+        }
+        Ok(())
+    }
+
+    pub fn lookup(&self, name: &str, value: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        let key = Key::of(value).ok_or_else(|| PyTypeError::new_err("Index keys are str, int, float, bool or None"))?;
+        let indexes = self.indexes.relock();
+        let index = indexes.get(name).ok_or_else(|| PyKeyError::new_err(format!("No index named '{name}'")))?;
+        Ok(index.by_key.get(&key).map(|paths| paths.iter().cloned().collect()).unwrap_or_default())
+    }
+
+    /// `{name: {pattern, keys, entries}}`.
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        for (name, index) in self.indexes.relock().iter() {
+            let row = PyDict::new_bound(py);
+            row.set_item("pattern", &index.pattern.text)?;
+            row.set_item("keys", index.by_key.len())?;
+            row.set_item("entries", index.by_path.len())?;
+            out.set_item(name, row)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+}
@@ -0,0 +1,192 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PySet, PyString, PyTuple};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+// [v3.6] Structural content hashing for the Data zone, only computed while escape tracking
+// (`engine.set_escape_tracking(True)`) is on.
+// NOTE: DefaultHasher::new() uses fixed keys, so hashes are stable within a process
+// (not across Rust toolchains) - they are integrity checks, not persisted digests.
+// Objects are memoized by id() so shared subtrees are hashed once; a reference back to an
+// object still being hashed (a cycle) hashes as a marker. Opaque objects (no `__dict__`)
+// hash by type name only - `repr()` is never called.
+
+/// Nesting depth beyond which values hash by type name only.
+const MAX_DEPTH: usize = 64;
+
+/// One traversal: finished hashes, the objects on the current path and (for
+/// `container_tags`) the containers already tagged, by `id()`.
+#[derive(Default)]
+struct Walk {
+    done: HashMap<usize, u64>,
+    active: HashSet<usize>,
+    tagged: HashSet<usize>,
+}
+
+/// Hash a Python value by content: dict/set entries are order-independent,
+/// list/tuple entries are positional, scalars hash their value.
+pub fn content_hash(value: &Bound<'_, PyAny>) -> PyResult<u64> {
+    Walk::default().hash(value, 0)
+}
+
+impl Walk {
+    fn hash(&mut self, value: &Bound<'_, PyAny>, depth: usize) -> PyResult<u64> {
+        let mut h = DefaultHasher::new();
+
+        if value.is_none() {
+            0u8.hash(&mut h);
+        } else if let Ok(b) = value.downcast::<PyBool>() {
+            1u8.hash(&mut h);
+            b.is_true().hash(&mut h);
+        } else if let Ok(i) = value.downcast::<PyInt>() {
+            2u8.hash(&mut h);
+            match i.extract::<i64>() {
+                Ok(n) => n.hash(&mut h),
+                Err(_) => i.str()?.to_str()?.hash(&mut h),
+            }
+        } else if let Ok(f) = value.downcast::<PyFloat>() {
+            3u8.hash(&mut h);
+            f.value().to_bits().hash(&mut h);
+        } else if let Ok(s) = value.downcast::<PyString>() {
+            4u8.hash(&mut h);
+            s.to_str()?.hash(&mut h);
+        } else if let Ok(b) = value.downcast::<PyBytes>() {
+            5u8.hash(&mut h);
+            b.as_bytes().hash(&mut h);
+        } else {
+            return self.container(value, depth);
+        }
+
+        Ok(h.finish())
+    }
+
+    fn container(&mut self, value: &Bound<'_, PyAny>, depth: usize) -> PyResult<u64> {
+        let id = value.as_ptr() as usize;
+        if let Some(done) = self.done.get(&id) {
+            return Ok(*done);
+        }
+        let mut h = DefaultHasher::new();
+        if self.active.contains(&id) {
+            6u8.hash(&mut h);
+            return Ok(h.finish());
+        }
+        if depth >= MAX_DEPTH {
+            13u8.hash(&mut h);
+            value.get_type().name()?.to_str()?.hash(&mut h);
+            return Ok(h.finish());
+        }
+        self.active.insert(id);
+        let hashed = self.children(value, depth, &mut h);
+        self.active.remove(&id);
+        hashed?;
+        let hash = h.finish();
+        self.done.insert(id, hash);
+        Ok(hash)
+    }
+
+    fn children(&mut self, value: &Bound<'_, PyAny>, depth: usize, h: &mut DefaultHasher) -> PyResult<()> {
+        if let Ok(d) = value.downcast::<PyDict>() {
+            7u8.hash(h);
+            let mut acc = 0u64;
+            for (k, v) in d {
+                let mut entry = DefaultHasher::new();
+                self.hash(&k, depth + 1)?.hash(&mut entry);
+                self.hash(&v, depth + 1)?.hash(&mut entry);
+                acc = acc.wrapping_add(entry.finish());
+            }
+            (d.len(), acc).hash(h);
+        } else if let Ok(l) = value.downcast::<PyList>() {
+            8u8.hash(h);
+            for item in l {
+                self.hash(&item, depth + 1)?.hash(h);
+            }
+        } else if let Ok(t) = value.downcast::<PyTuple>() {
+            9u8.hash(h);
+            for item in t {
+                self.hash(&item, depth + 1)?.hash(h);
+            }
+        } else if value.is_instance_of::<PySet>() || value.is_instance_of::<PyFrozenSet>() {
+            10u8.hash(h);
+            let mut acc = 0u64;
+            for item in value.iter()? {
+                acc = acc.wrapping_add(self.hash(&item?, depth + 1)?);
+            }
+            acc.hash(h);
+        } else if let Ok(attrs) = value.getattr("__dict__").and_then(|d| d.downcast_into::<PyDict>().map_err(PyErr::from)) {
+            // Plain objects: type name + instance attributes.
+            11u8.hash(h);
+            value.get_type().name()?.to_str()?.hash(h);
+            self.hash(attrs.as_any(), depth + 1)?.hash(h);
+        } else {
+            // Opaque objects: type only.
+            12u8.hash(h);
+            value.get_type().name()?.to_str()?.hash(h);
+        }
+        Ok(())
+    }
+}
+
+/// Fold per-key hashes into one state checksum (independent of map iteration order).
+pub fn combine<'a>(entries: impl IntoIterator<Item = (&'a String, &'a u64)>) -> u64 {
+    let mut sorted: Vec<_> = entries.into_iter().collect();
+    sorted.sort();
+    let mut h = DefaultHasher::new();
+    for (k, v) in sorted {
+        k.hash(&mut h);
+        v.hash(&mut h);
+    }
+    h.finish()
+}
+
+/// [v3.6] Snapshot of a committed container: its path, Python identity and content hash.
+#[derive(Clone)]
+pub struct ContainerTag {
+    pub path: String,
+    pub id: usize,
+    pub hash: u64,
+}
+
+/// Walk `value` (rooted at `path`) and tag every nested container (dict/list/tuple/set/object).
+/// A container reachable by several paths is tagged at the first one only.
+pub fn container_tags(path: &str, value: &Bound<'_, PyAny>) -> PyResult<Vec<ContainerTag>> {
+    let mut tags = Vec::new();
+    collect_tags(path, value, 0, &mut Walk::default(), &mut tags)?;
+    Ok(tags)
+}
+
+fn collect_tags(path: &str, value: &Bound<'_, PyAny>, depth: usize, walk: &mut Walk, tags: &mut Vec<ContainerTag>) -> PyResult<()> {
+    let id = value.as_ptr() as usize;
+    if walk.tagged.contains(&id) {
+        return Ok(());
+    }
+    let children: Vec<(String, Bound<'_, PyAny>)> = if let Ok(d) = value.downcast::<PyDict>() {
+        d.iter().map(|(k, v)| Ok((segment(&k)?, v))).collect::<PyResult<_>>()?
+    } else if let Ok(l) = value.downcast::<PyList>() {
+        l.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect()
+    } else if let Ok(t) = value.downcast::<PyTuple>() {
+        t.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect()
+    } else if value.is_instance_of::<PySet>() || value.is_instance_of::<PyFrozenSet>() {
+        Vec::new()
+    } else if let Ok(attrs) = value.getattr("__dict__").and_then(|d| d.downcast_into::<PyDict>().map_err(PyErr::from)) {
+        attrs.iter().map(|(k, v)| Ok((segment(&k)?, v))).collect::<PyResult<_>>()?
+    } else {
+        return Ok(()); // Scalar: folded into its parent's hash.
+    };
+
+    walk.tagged.insert(id);
+    tags.push(ContainerTag { path: path.to_string(), id, hash: walk.hash(value, depth)? });
+    if depth + 1 < MAX_DEPTH {
+        for (seg, child) in children {
+            collect_tags(&format!("{path}.{seg}"), &child, depth + 1, walk, tags)?;
+        }
+    }
+    Ok(())
+}
+
+fn segment(key: &Bound<'_, PyAny>) -> PyResult<String> {
+    match key.downcast::<PyString>() {
+        Ok(s) => Ok(s.to_str()?.to_string()),
+        Err(_) => Ok(key.repr()?.to_str()?.to_string()),
+    }
+}
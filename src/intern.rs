@@ -0,0 +1,67 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] Process-wide interner for path and key strings (delta paths / ops / keys,
+// `key_last_modified`, `key_last_writer`). Every State version clones those maps, so sharing
+// one allocation per distinct string keeps the copies to refcount bumps.
+// NOTE: Strings nobody else references are purged whenever the table doubles in size.
+
+/// Interned string; clones share the allocation.
+pub type Sym = Arc<str>;
+
+const MIN_PURGE_LEN: usize = 4096;
+
+struct Table {
+    strings: HashSet<Sym>,
+    purge_at: usize,
+    lookups: u64,
+    hits: u64,
+}
+
+static TABLE: LazyLock<Mutex<Table>> = LazyLock::new(|| {
+    Mutex::new(Table { strings: HashSet::new(), purge_at: MIN_PURGE_LEN, lookups: 0, hits: 0 })
+});
+
+impl Table {
+    fn purge(&mut self) -> usize {
+        let before = self.strings.len();
+        self.strings.retain(|s| Arc::strong_count(s) > 1);
+        self.purge_at = (self.strings.len() * 2).max(MIN_PURGE_LEN);
+        before - self.strings.len()
+    }
+}
+
+/// The shared copy of `s`.
+pub fn intern(s: &str) -> Sym {
+    let mut table = TABLE.relock();
+    table.lookups += 1;
+    if let Some(sym) = table.strings.get(s).cloned() {
+        table.hits += 1;
+        return sym;
+    }
+    if table.strings.len() >= table.purge_at {
+        table.purge();
+    }
+    let sym: Sym = Arc::from(s);
+    table.strings.insert(sym.clone());
+    sym
+}
+
+/// Interner occupancy: `{strings, bytes, lookups, hits, purged}`. `purge=True` first drops
+/// strings no longer referenced outside the table.
+#[pyfunction]
+#[pyo3(signature = (purge=false))]
+pub fn intern_stats(py: Python, purge: bool) -> PyResult<PyObject> {
+    let mut table = TABLE.relock();
+    let purged = if purge { table.purge() } else { 0 };
+    let out = PyDict::new_bound(py);
+    out.set_item("strings", table.strings.len())?;
+    out.set_item("bytes", table.strings.iter().map(|s| s.len()).sum::<usize>())?;
+    out.set_item("lookups", table.lookups)?;
+    out.set_item("hits", table.hits)?;
+    out.set_item("purged", purged)?;
+    Ok(out.into_any().unbind())
+}
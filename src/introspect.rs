@@ -0,0 +1,310 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::fmt::Write as _;
+use crate::proxy::SupervisorProxy;
+use crate::zones::{get_physics_override, get_zone_physics, resolve_zone, ContextZone, CAP_APPEND, CAP_UPDATE};
+
+// [v3.6] REPL / notebook introspection shared by ContextGuard and SupervisorProxy:
+// `dir()` lists the children the current contract can read, `describe()` summarizes the
+// readable subtree and `render_tree()` draws it (text for `_repr_pretty_`, HTML for
+// `_repr_html_`). Everything is size-capped so a huge state never floods a notebook.
+// NOTE: Walks the raw targets - no shadows are taken and no reads are profiled.
+
+/// What a guard/proxy grants on a path: `None` = hidden, `Some(writable)` otherwise.
+pub trait Access {
+    fn access(&self, path: &str) -> Option<bool>;
+}
+
+/// `base`, further narrowed by an optional Python `narrow(path, writable) -> bool | None`
+/// (used by the Python `ContextGuard` wrapper to apply its contract). Errors hide the path.
+pub struct Narrowed<'a, 'py> {
+    pub base: &'a dyn Access,
+    pub narrow: Option<&'a Bound<'py, PyAny>>,
+}
+
+impl Access for Narrowed<'_, '_> {
+    fn access(&self, path: &str) -> Option<bool> {
+        let writable = self.base.access(path)?;
+        let Some(narrow) = self.narrow else { return Some(writable) };
+        narrow.call1((path, writable)).ok()?.extract::<Option<bool>>().ok()?
+    }
+}
+
+pub fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() { name.to_string() } else { format!("{prefix}.{name}") }
+}
+
+/// Zone physics for `path` (explicit overrides win), as used by the proxies.
+pub fn physics(path: &str) -> u8 {
+    get_physics_override(path).unwrap_or_else(|| get_zone_physics(&resolve_zone(path)))
+}
+
+pub fn can_mutate(caps: u8) -> bool {
+    caps & (CAP_UPDATE | CAP_APPEND) != 0
+}
+
+fn unwrap_proxy(v: Bound<'_, PyAny>) -> Bound<'_, PyAny> {
+    match v.downcast::<SupervisorProxy>() {
+        Ok(p) => p.borrow().inner.bind(v.py()).clone(),
+        Err(_) => v,
+    }
+}
+
+/// Public children of `obj` with their values: dict keys, instance attributes, or - for
+/// native objects such as the process context (`by_dir`) - non-callable attributes.
+fn children<'py>(obj: &Bound<'py, PyAny>, by_dir: bool) -> PyResult<Vec<(String, Bound<'py, PyAny>)>> {
+    if let Ok(d) = obj.downcast::<PyDict>() {
+        return Ok(d.iter().filter_map(|(k, v)| Some((k.extract::<String>().ok()?, unwrap_proxy(v)))).collect());
+    }
+    let names: Vec<String> = match obj.getattr("__dict__").ok().and_then(|d| d.downcast_into::<PyDict>().ok()) {
+        Some(d) if !d.is_empty() => d.keys().iter().filter_map(|k| k.extract().ok()).collect(),
+        _ if by_dir => obj.dir()?.iter().filter_map(|k| k.extract().ok()).collect(),
+        _ => return Ok(Vec::new()),
+    };
+    Ok(names.into_iter()
+        .filter(|n| !n.starts_with('_'))
+        .filter_map(|n| {
+            let v = obj.getattr(n.as_str()).ok()?;
+            (!v.is_callable()).then(|| (n, unwrap_proxy(v)))
+        })
+        .collect())
+}
+
+/// Readable children of `obj` under `prefix`, with their (unwrapped) values.
+pub fn visible_children<'py>(obj: &Bound<'py, PyAny>, prefix: &str, by_dir: bool, access: &dyn Access) -> PyResult<Vec<(String, Bound<'py, PyAny>)>> {
+    Ok(children(obj, by_dir)?
+        .into_iter()
+        .filter(|(n, _)| access.access(&join(prefix, n)).is_some())
+        .collect())
+}
+
+/// Readable child names of `obj` under `prefix`.
+pub fn visible_names(obj: &Bound<PyAny>, prefix: &str, by_dir: bool, access: &dyn Access) -> PyResult<Vec<String>> {
+    Ok(visible_children(obj, prefix, by_dir, access)?.into_iter().map(|(n, _)| n).collect())
+}
+
+/// `sorted(public class members + extra)` for `__dir__`.
+pub fn dir_with(obj: &Bound<PyAny>, extra: Vec<String>) -> PyResult<Vec<String>> {
+    let mut names: Vec<String> = obj.get_type().dir()?.iter()
+        .filter_map(|n| n.extract::<String>().ok())
+        .filter(|n| !n.starts_with('_'))
+        .chain(extra)
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+fn zone_name(zone: &ContextZone) -> String {
+    format!("{zone:?}").to_lowercase()
+}
+
+// ============================================================================
+// Tree walk
+// ============================================================================
+
+#[allow(clippy::struct_field_names)]
+pub struct Limits {
+    pub max_depth: usize,
+    pub max_keys: usize,
+    pub max_nodes: usize,
+}
+
+/// Leaf values longer than this are cut in rendered trees.
+const MAX_VALUE_CHARS: usize = 60;
+
+enum Row {
+    Field {
+        depth: usize,
+        name: String,
+        path: String,
+        zone: ContextZone,
+        writable: bool,
+        type_name: String,
+        len: Option<usize>,
+        value: Option<String>,
+    },
+    /// `count` readable siblings not shown (over `max_keys`).
+    More { depth: usize, count: usize },
+}
+
+struct Tree {
+    rows: Vec<Row>,
+    /// Stopped at `max_nodes`.
+    truncated: bool,
+}
+
+fn short_repr(value: &Bound<PyAny>) -> String {
+    let Ok(repr) = value.repr() else { return "<repr failed>".to_string() };
+    let repr = repr.to_string_lossy().into_owned();
+    match repr.char_indices().nth(MAX_VALUE_CHARS) {
+        Some((cut, _)) => format!("{}...", &repr[..cut]),
+        None => repr,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk(tree: &mut Tree, obj: &Bound<PyAny>, prefix: &str, by_dir: bool, depth: usize, limits: &Limits, access: &dyn Access, with_values: bool) -> PyResult<()> {
+    let visible: Vec<(String, Bound<PyAny>, String, bool)> = children(obj, by_dir)?
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let path = join(prefix, &name);
+            let writable = access.access(&path)?;
+            Some((name, value, path, writable))
+        })
+        .collect();
+    let total = visible.len();
+    for (i, (name, value, path, writable)) in visible.into_iter().enumerate() {
+        if i == limits.max_keys {
+            tree.rows.push(Row::More { depth, count: total - i });
+            break;
+        }
+        if tree.rows.len() >= limits.max_nodes {
+            tree.truncated = true;
+            return Ok(());
+        }
+        let zone = resolve_zone(&path);
+        let is_container = value.is_instance_of::<PyDict>() || value.is_instance_of::<PyList>();
+        // NOTE: Heavy values (tensors, frames) are never repr'd - too costly for a preview.
+        let leaf_value = with_values && !is_container && zone != ContextZone::Heavy && children(&value, false)?.is_empty();
+        tree.rows.push(Row::Field {
+            depth,
+            name,
+            path: path.clone(),
+            type_name: value.get_type().name()?.to_string(),
+            len: if is_container { Some(value.len()?) } else { None },
+            value: leaf_value.then(|| short_repr(&value)),
+            zone,
+            writable,
+        });
+        if depth + 1 < limits.max_depth {
+            walk(tree, &value, &path, false, depth + 1, limits, access, with_values)?;
+        }
+    }
+    Ok(())
+}
+
+fn collect(root: &Bound<PyAny>, prefix: &str, by_dir: bool, limits: &Limits, access: &dyn Access, with_values: bool) -> PyResult<Tree> {
+    let mut tree = Tree { rows: Vec::new(), truncated: false };
+    if limits.max_depth > 0 {
+        walk(&mut tree, &unwrap_proxy(root.clone()), prefix, by_dir, 0, limits, access, with_values)?;
+    }
+    Ok(tree)
+}
+
+/// `{path: {zone, access, type, len?}}` for every readable path below `root`, depth-first.
+/// Containers list at most `max_keys` readable children (`len` still reports the full size).
+pub fn describe(py: Python, root: &Bound<PyAny>, prefix: &str, by_dir: bool, max_depth: usize, max_keys: usize, access: &dyn Access) -> PyResult<PyObject> {
+    let limits = Limits { max_depth, max_keys, max_nodes: usize::MAX };
+    let out = PyDict::new_bound(py);
+    for row in collect(root, prefix, by_dir, &limits, access, false)?.rows {
+        let Row::Field { path, zone, writable, type_name, len, .. } = row else { continue };
+        let entry = PyDict::new_bound(py);
+        entry.set_item("zone", zone_name(&zone))?;
+        entry.set_item("access", if writable { "rw" } else { "ro" })?;
+        entry.set_item("type", type_name)?;
+        if let Some(n) = len {
+            entry.set_item("len", n)?;
+        }
+        out.set_item(path, entry)?;
+    }
+    Ok(out.into_any().unbind())
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn badge_color(zone: &ContextZone) -> &'static str {
+    match zone {
+        ContextZone::Data => "#dbeafe",
+        ContextZone::Signal => "#fef3c7",
+        ContextZone::Meta => "#ede9fe",
+        ContextZone::Heavy => "#fee2e2",
+        ContextZone::Log => "#e5e7eb",
+        ContextZone::Constant => "#dcfce7",
+        ContextZone::Private => "#f3f4f6",
+    }
+}
+
+fn summary(type_name: &str, len: Option<usize>, value: Option<&str>) -> String {
+    match (len, value) {
+        (Some(n), _) => format!("{type_name}({n})"),
+        (None, Some(v)) => format!("{type_name} = {v}"),
+        (None, None) => type_name.to_string(),
+    }
+}
+
+fn render_text(title: &str, tree: &Tree, max_nodes: usize) -> String {
+    let mut out = title.to_string();
+    for row in &tree.rows {
+        out.push('\n');
+        match row {
+            Row::Field { depth, name, zone, writable, type_name, len, value, .. } => {
+                let _ = write!(
+                    out, "{:indent$}{name}: {}  [{} {}]",
+                    "", summary(type_name, *len, value.as_deref()), zone_name(zone), if *writable { "rw" } else { "ro" },
+                    indent = 2 * (depth + 1),
+                );
+            }
+            Row::More { depth, count } => {
+                let _ = write!(out, "{:indent$}... {count} more", "", indent = 2 * (depth + 1));
+            }
+        }
+    }
+    if tree.truncated {
+        let _ = write!(out, "\n... truncated at {max_nodes} entries");
+    }
+    out
+}
+
+fn render_html(title: &str, tree: &Tree, max_nodes: usize) -> String {
+    const UL: &str = "<ul style=\"list-style:none;margin:0;padding-left:1.2em\">";
+    let mut out = format!("<div class=\"theus-tree\" style=\"font-family:monospace\"><div><b>{}</b></div>{UL}", escape(title));
+    let mut open = 1; // currently open <ul> levels
+    for row in &tree.rows {
+        let depth = match row {
+            Row::Field { depth, .. } | Row::More { depth, .. } => *depth,
+        };
+        while open > depth + 1 {
+            out.push_str("</ul>");
+            open -= 1;
+        }
+        while open < depth + 1 {
+            out.push_str(UL);
+            open += 1;
+        }
+        match row {
+            Row::Field { name, path, zone, writable, type_name, len, value, .. } => {
+                let _ = write!(
+                    out,
+                    "<li title=\"{}\"><code>{}</code>: {} <span class=\"theus-zone\" style=\"background:{};border-radius:3px;padding:0 4px;font-size:85%\">{}</span> <span style=\"opacity:.6;font-size:85%\">{}</span></li>",
+                    escape(path), escape(name), escape(&summary(type_name, *len, value.as_deref())),
+                    badge_color(zone), zone_name(zone), if *writable { "rw" } else { "ro" },
+                );
+            }
+            Row::More { count, .. } => {
+                let _ = write!(out, "<li style=\"opacity:.6\">&hellip; {count} more</li>");
+            }
+        }
+    }
+    for _ in 0..open {
+        out.push_str("</ul>");
+    }
+    if tree.truncated {
+        let _ = write!(out, "<div style=\"opacity:.6\">&hellip; truncated at {max_nodes} entries</div>");
+    }
+    out.push_str("</div>");
+    out
+}
+
+/// Draw the readable subtree below `root` as text or HTML under `title`.
+#[allow(clippy::too_many_arguments)]
+pub fn render(root: &Bound<PyAny>, prefix: &str, by_dir: bool, title: &str, html: bool, limits: &Limits, access: &dyn Access) -> PyResult<String> {
+    let tree = collect(root, prefix, by_dir, limits, access, true)?;
+    Ok(if html { render_html(title, &tree, limits.max_nodes) } else { render_text(title, &tree, limits.max_nodes) })
+}
@@ -14,8 +14,70 @@ mod guards;
 mod zones;
 mod signals;
 mod shm;
+mod state_codec;
+#[cfg(feature = "state-server")]
+mod state_server;
 mod shm_registry;
 mod conflict;
+mod outbox;
+mod integrity;
+mod rules;
+mod metrics;
+mod profiler;
+mod clock;
+mod determinism;
+mod faults;
+mod recorder;
+mod introspect;
+mod approvals;
+mod lineage;
+mod tags;
+mod heavy_store;
+mod dlpack;
+mod spill;
+mod compress;
+mod intern;
+mod shadow_compare;
+mod seeding;
+mod computed;
+mod query;
+mod indexes;
+mod search;
+mod cdc;
+mod tenancy;
+mod break_glass;
+mod copiers;
+mod schema_fields;
+mod cap_tokens;
+mod signal_queue;
+mod meta_watch;
+mod testing;
+mod pure_io;
+mod heartbeat;
+mod cancellation;
+mod pins;
+mod group_commit;
+mod op_ids;
+mod errors;
+mod messages;
+mod trace;
+mod checkpoints;
+mod timers;
+mod state_machines;
+mod provisional;
+mod observer;
+mod middleware;
+mod field_crypto;
+mod coercion;
+mod units;
+mod blobs;
+mod snapshots;
+mod snapshot_format;
+mod stubs;
+mod reentrancy;
+mod locks;
+mod fsck;
+mod shadow_budget;
 
 mod supervisor;
 mod proxy;
@@ -23,6 +85,7 @@ mod proxy;
 
 /// Theus Core Rust Extension
 #[pymodule]
+#[allow(clippy::too_many_lines)]
 fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     eprintln!("[THEUS-CORE] Loaded Version: 3.0.26(Target Env Build)");
     // v3.1 Supervisor/Proxy
@@ -34,7 +97,34 @@ fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<engine::TheusEngine>()?;
     m.add_class::<engine::Transaction>()?;
     m.add_class::<engine::OutboxCollector>()?;
+    m.add_class::<heavy_store::HeavyHandle>()?;
+    m.add_class::<spill::SpilledValue>()?;
+    m.add_class::<compress::CompressedValue>()?;
     m.add("WriteTimeoutError", py.get_type_bound::<engine::WriteTimeoutError>())?;
+    m.add("TransactionCancelledError", py.get_type_bound::<engine::TransactionCancelledError>())?;
+    m.add("TransactionLimitError", py.get_type_bound::<engine::TransactionLimitError>())?;
+    m.add("ProcessCancelledError", py.get_type_bound::<cancellation::ProcessCancelledError>())?;
+    m.add("MaintenanceModeError", py.get_type_bound::<engine::MaintenanceModeError>())?;
+    m.add("EngineShutdownError", py.get_type_bound::<engine::EngineShutdownError>())?;
+    m.add("IntegrityError", py.get_type_bound::<engine::IntegrityError>())?;
+    errors::register(py, m)?;
+    m.add("SnapshotFormatError", py.get_type_bound::<snapshot_format::SnapshotFormatError>())?;
+    m.add("SnapshotIncompatibleError", py.get_type_bound::<snapshot_format::SnapshotIncompatibleError>())?;
+    m.add("DecryptionError", py.get_type_bound::<field_crypto::DecryptionError>())?;
+    m.add("MiddlewareVetoError", py.get_type_bound::<middleware::MiddlewareVetoError>())?;
+    m.add("ReentrancyError", py.get_type_bound::<reentrancy::ReentrancyError>())?;
+    m.add("LockPoisonedError", py.get_type_bound::<locks::LockPoisonedError>())?;
+
+    // Rule Sandbox (v3.6)
+    m.add("RuleError", py.get_type_bound::<rules::RuleError>())?;
+    m.add("RuleLimitError", py.get_type_bound::<rules::RuleLimitError>())?;
+
+    // Remote State Reads (v3.6, feature "state-server")
+    #[cfg(feature = "state-server")]
+    {
+        m.add_class::<state_server::StateServer>()?;
+        m.add_class::<state_server::StateClient>()?;
+    }
     
     // Workflow
     m.add_class::<fsm::WorkflowEngine>()?;
@@ -43,6 +133,7 @@ fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Signals (v3.1)
     m.add_class::<signals::SignalHub>()?;
     m.add_class::<signals::SignalReceiver>()?;
+    m.add_class::<signal_queue::SignalQueue>()?;
 
     // Managed Memory (v3.2)
     // Managed Memory (v3.2) - Moved to shm submodule
@@ -56,12 +147,84 @@ fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<structures::MetaLogEntry>()?;
     m.add("ContextError", py.get_type_bound::<structures::ContextError>())?;
     
+    // Outbox Serializers (v3.6)
+    m.add_function(wrap_pyfunction!(clock::use_global_test_clock, m)?)?;
+    m.add_function(wrap_pyfunction!(clock::advance_global_clock_ms, m)?)?;
+    m.add_function(wrap_pyfunction!(clock::use_global_logical_clock, m)?)?;
+    m.add_function(wrap_pyfunction!(clock::use_global_system_clock, m)?)?;
+    m.add_function(wrap_pyfunction!(clock::global_clock, m)?)?;
+    m.add_function(wrap_pyfunction!(outbox::register_serializer, m)?)?;
+    m.add_function(wrap_pyfunction!(outbox::unregister_serializer, m)?)?;
+    m.add_function(wrap_pyfunction!(outbox::allow_pickle_serializer, m)?)?;
+    m.add_function(wrap_pyfunction!(outbox::list_serializers, m)?)?;
+    m.add_class::<outbox::DeadLetter>()?;
+    m.add("ADMIN_CAPS", zones::ADMIN_CAPS)?;
+    m.add("SerializationError", py.get_type_bound::<outbox::SerializationError>())?;
+
     // Guards
     m.add_class::<guards::ContextGuard>()?;
     
     // Zones
     m.add_function(wrap_pyfunction!(zones::register_physics_override, m)?)?;
     m.add_function(wrap_pyfunction!(zones::clear_physics_overrides, m)?)?;
+    m.add_function(wrap_pyfunction!(zones::physics_overrides, m)?)?;
+    m.add_function(wrap_pyfunction!(zones::effective_physics, m)?)?;
+    m.add_class::<zones::Capability>()?;
+    m.add_function(wrap_pyfunction!(cap_tokens::verify_capability_token, m)?)?;
+    m.add_function(wrap_pyfunction!(cap_tokens::guard_from_token, m)?)?;
+
+    // Deltas (v3.6)
+    m.add_class::<delta::DeltaOp>()?;
+
+    // PURE I/O sandbox (v3.6)
+    m.add_class::<pure_io::PureScope>()?;
+    m.add_function(wrap_pyfunction!(pure_io::check_effect, m)?)?;
+
+    // Cooperative cancellation (v3.6)
+    m.add_class::<cancellation::CancellationToken>()?;
+
+    // Version pinning (v3.6)
+    m.add_class::<pins::PinnedView>()?;
+    m.add_class::<observer::ObserverEngine>()?;
+    m.add_class::<blobs::BlobHandle>()?;
+
+    // State seeding (v3.6)
+    m.add_class::<seeding::EnvProvider>()?;
+    m.add_class::<seeding::CallbackProvider>()?;
+
+    // Multi-tenant isolation (v3.6)
+    m.add_class::<tenancy::TenantHandle>()?;
+
+    // Group commit (v3.6)
+    m.add_class::<group_commit::CommitGroup>()?;
+
+    // Error message catalog (v3.6)
+    m.add_function(wrap_pyfunction!(messages::register_error_formatter, m)?)?;
+    m.add_function(wrap_pyfunction!(messages::unregister_error_formatter, m)?)?;
+    m.add_function(wrap_pyfunction!(messages::error_catalog, m)?)?;
+
+    // Engine Metrics (v3.6)
+    m.add_function(wrap_pyfunction!(metrics::engine_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::set_metrics_enabled, m)?)?;
+    m.add_function(wrap_pyfunction!(profiler::set_profiling_enabled, m)?)?;
+    m.add_function(wrap_pyfunction!(profiler::profile_report, m)?)?;
+    m.add_function(wrap_pyfunction!(profiler::reset_profile, m)?)?;
+
+    // String Interner (v3.6)
+    m.add_function(wrap_pyfunction!(intern::intern_stats, m)?)?;
+
+    // Transaction Replay (v3.6)
+    m.add_function(wrap_pyfunction!(recorder::replay_recording, m)?)?;
+
+    // Proxy re-entrancy guard (v3.6)
+    m.add_function(wrap_pyfunction!(reentrancy::set_reentrancy_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(reentrancy::reentrancy_stats, m)?)?;
+
+    // Poisoned-lock recovery (v3.6)
+    m.add_function(wrap_pyfunction!(locks::lock_recoveries, m)?)?;
+
+    // Python stubs (v3.6)
+    m.add_function(wrap_pyfunction!(stubs::generate_stubs, m)?)?;
 
     // Config
     m.add_class::<config::ConfigLoader>()?;
@@ -80,6 +243,7 @@ fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Conflict (v3.3)
     m.add_class::<conflict::ConflictManager>()?;
     m.add_class::<conflict::RetryDecision>()?;
+    m.add_class::<conflict::RetryReason>()?;
 
 
     // Sub-module for SHM (v3.1)
@@ -88,5 +252,12 @@ fn theus_core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     shm_mod.add_class::<shm_registry::MemoryRegistry>()?;
     m.add_submodule(&shm_mod)?;
 
+    // Sub-module for test harnesses (v3.6)
+    let testing_mod = PyModule::new_bound(py, "testing")?;
+    testing::theus_testing(&testing_mod)?;
+    m.add_submodule(&testing_mod)?;
+    // Importable as `theus_core.testing` (e.g. from a conftest.py).
+    py.import("sys")?.getattr("modules")?.set_item("theus_core.testing", &testing_mod)?;
+
     Ok(())
 }
@@ -0,0 +1,151 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Data lineage: who wrote each path. Every commit stamps the `zone.field` paths whose
+// value actually changed with its writer (actor = process name / CAS requester, tx id) in
+// `State.key_last_writer`, and appends them to a bounded ring that `engine.blame()` reads.
+// NOTE: Granularity matches `key_last_modified` (zone.field); the Heavy zone is not tracked.
+
+/// Default number of commits kept for `blame()`.
+pub const DEFAULT_RETENTION: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct Writer {
+    pub actor: Option<String>,
+    pub tx: Option<u64>,
+    pub version: u64,
+}
+
+impl Writer {
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new_bound(py);
+        d.set_item("version", self.version)?;
+        d.set_item("tx", self.tx)?;
+        d.set_item("actor", self.actor.as_deref())?;
+        Ok(d)
+    }
+}
+
+struct Commit {
+    writer: Writer,
+    tags: Option<crate::tags::Tags>,
+    paths: Vec<String>,
+    ts_ms: u64,
+}
+
+pub struct Lineage {
+    commits: Mutex<VecDeque<Commit>>,
+    retention: Mutex<usize>,
+}
+
+impl Default for Lineage {
+    fn default() -> Self {
+        Lineage { commits: Mutex::new(VecDeque::new()), retention: Mutex::new(DEFAULT_RETENTION) }
+    }
+}
+
+/// `a` and `b` name the same value or one contains the other.
+fn overlaps(a: &str, b: &str) -> bool {
+    a == b
+        || a.strip_prefix(b).is_some_and(|rest| rest.starts_with('.'))
+        || b.strip_prefix(a).is_some_and(|rest| rest.starts_with('.'))
+}
+
+impl Lineage {
+    pub fn set_retention(&self, commits: usize) {
+        *self.retention.relock() = commits;
+        let mut log = self.commits.relock();
+        while log.len() > commits {
+            log.pop_front();
+        }
+    }
+
+    pub fn record(&self, writer: Writer, tags: Option<crate::tags::Tags>, paths: Vec<String>) {
+        let retention = *self.retention.relock();
+        if paths.is_empty() || retention == 0 {
+            return;
+        }
+        let mut log = self.commits.relock();
+        if log.len() >= retention {
+            log.pop_front();
+        }
+        log.push_back(Commit { writer, tags, paths, ts_ms: crate::clock::now_ms() });
+    }
+
+    /// Newest-first writers of `path` (or anything below / above it), at most `depth`,
+    /// restricted to commits matching the `tags` filter.
+    pub fn blame(&self, py: Python, path: &str, depth: usize, tags: Option<&crate::tags::Tags>) -> PyResult<Vec<PyObject>> {
+        let path = path.replace('[', ".").replace(']', "");
+        let log = self.commits.relock();
+        log.iter()
+            .rev()
+            .filter(|c| crate::tags::matches(c.tags.as_ref(), tags))
+            .filter_map(|c| {
+                let paths: Vec<&String> = c.paths.iter().filter(|p| overlaps(p, &path)).collect();
+                (!paths.is_empty()).then_some((c, paths))
+            })
+            .take(depth)
+            .map(|(c, paths)| {
+                let d = c.writer.to_dict(py)?;
+                d.set_item("tags", crate::tags::to_dict(py, c.tags.as_ref())?)?;
+                d.set_item("paths", paths)?;
+                d.set_item("ts_ms", c.ts_ms)?;
+                Ok(d.into_any().unbind())
+            })
+            .collect()
+    }
+}
+
+fn field<'py>(zone: Option<&Bound<'py, PyAny>>, key: &Bound<'py, PyAny>) -> Option<Bound<'py, PyAny>> {
+    zone?.downcast::<PyDict>().ok()?.get_item(key).ok()?
+}
+
+/// `zone.field` paths (or `zone` for non-dict zones) in `written` whose committed value
+/// differs between `old` and `new`.
+pub fn changed_paths(py: Python, old: &State, new: &State, written: &Bound<PyDict>) -> PyResult<Vec<String>> {
+    let differs = |a: Option<Bound<PyAny>>, b: Option<Bound<PyAny>>| match (a, b) {
+        (Some(a), Some(b)) => !a.eq(&b).unwrap_or(false),
+        (a, b) => a.is_some() || b.is_some(),
+    };
+    let mut paths = Vec::new();
+    for (zone_k, zone_v) in written.iter() {
+        let zone = zone_k.extract::<String>()?;
+        let old_zone = old.data.get(&zone).map(|v| v.bind(py).clone());
+        let new_zone = new.data.get(&zone).map(|v| v.bind(py).clone());
+        let Ok(fields) = zone_v.downcast::<PyDict>() else {
+            if differs(old_zone, new_zone) {
+                paths.push(zone);
+            }
+            continue;
+        };
+        for (k, _) in fields.iter() {
+            if differs(field(old_zone.as_ref(), &k), field(new_zone.as_ref(), &k)) {
+                paths.push(format!("{zone}.{}", k.str()?));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Stamps the paths `written` changed from `old` to `new` (State objects), logs the commit
+/// and returns those paths.
+#[allow(clippy::too_many_arguments)]
+pub fn stamp(
+    py: Python, lineage: &Lineage, old: &Bound<PyAny>, new: &Bound<PyAny>,
+    written: Option<&Bound<PyAny>>, actor: Option<String>, tx: Option<u64>, tags: Option<crate::tags::Tags>,
+) -> PyResult<Vec<String>> {
+    let Some(written) = written.and_then(|w| w.downcast::<PyDict>().ok()) else { return Ok(Vec::new()) };
+    let old = old.downcast::<State>()?.borrow();
+    let mut new = new.downcast::<State>()?.borrow_mut();
+    let paths = changed_paths(py, &old, &new, written)?;
+    let writer = Writer { actor, tx, version: new.version };
+    for path in &paths {
+        new.key_last_writer.insert(crate::intern::intern(path), writer.clone());
+    }
+    lineage.record(writer, tags, paths.clone());
+    Ok(paths)
+}
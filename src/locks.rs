@@ -0,0 +1,222 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::cell::RefCell;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::messages;
+use crate::structures::ContextError;
+
+// [v3.6] Poisoning-aware locking. A panic while one of the engine's mutexes was held used to
+// poison it, and every later `lock().unwrap()` on it panicked again: one bug bricked the
+// engine for the rest of the process. Locks are taken through `Relock` / `RelockRw` instead:
+// - `relock()` (and the RwLock variants) keep the guarded data as the panicking thread left
+//   it: committed state, history, outbox and audit records are never thrown away.
+// - `relock_reset()` rebuilds it from `Default`: caches and scratch indexes that may be
+//   half-updated and are cheap to rebuild.
+// Either way the poison is cleared and the recovery is recorded: a LOCK_POISONED audit event,
+// an entry in `lock_recoveries()`, and a bump of the recovery epoch. Transactions that were
+// open across a recovery fail their commit with LockPoisonedError (code TH801) instead of
+// publishing writes made around the panic; new transactions proceed normally.
+// Each engine keeps its own epoch (`LockEpoch`). A recovery made while one of its calls runs
+// on the thread (`LockEpoch::enter`) only bumps that engine's epoch; one made outside any
+// engine call (a process-wide lock, a background thread) bumps the shared epoch that every
+// engine's transactions also watch.
+
+pyo3::create_exception!(theus_core, LockPoisonedError, ContextError);
+
+const MAX_RECOVERIES: usize = 100;
+
+static SHARED_EPOCH: AtomicU64 = AtomicU64::new(0);
+static SEQ: AtomicU64 = AtomicU64::new(0);
+static RECOVERIES: Mutex<Vec<Recovery>> = Mutex::new(Vec::new());
+
+thread_local! {
+    // Engines whose calls are running on this thread, innermost last.
+    static SCOPE: RefCell<Vec<Arc<AtomicU64>>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Recovery {
+    seq: u64,
+    owner: Option<usize>, // `LockEpoch::id` of the engine charged, `None` if shared
+    site: &'static Location<'static>,
+    action: &'static str,
+    timestamp: f64,
+}
+
+/// An engine's recovery epoch. Clones share the counter.
+#[derive(Clone, Default)]
+pub struct LockEpoch(Arc<AtomicU64>);
+
+/// Epochs seen when a transaction opened (see `LockEpoch::check`).
+#[derive(Clone, Copy)]
+pub struct EpochMark {
+    own: u64,
+    shared: u64,
+    seq: u64,
+}
+
+/// Pops the engine pushed by `LockEpoch::enter`.
+pub struct ScopeGuard(());
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPE.with(|scope| scope.borrow_mut().pop());
+    }
+}
+
+impl LockEpoch {
+    fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+
+    pub fn mark(&self) -> EpochMark {
+        EpochMark {
+            own: self.0.load(Ordering::SeqCst),
+            shared: SHARED_EPOCH.load(Ordering::SeqCst),
+            seq: SEQ.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Charge recoveries on this thread to this engine until the guard drops.
+    #[must_use]
+    pub fn enter(&self) -> ScopeGuard {
+        SCOPE.with(|scope| scope.borrow_mut().push(self.0.clone()));
+        ScopeGuard(())
+    }
+
+    /// `Err(LockPoisonedError)` if this engine (or a shared lock) recovered since `since`.
+    pub fn check(&self, py: Python, since: EpochMark) -> PyResult<()> {
+        let now = self.mark();
+        if now.own == since.own && now.shared == since.shared {
+            return Ok(());
+        }
+        let id = self.id();
+        let sites: Vec<String> = RECOVERIES.lock().unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|r| r.seq > since.seq && r.owner.is_none_or(|o| o == id))
+            .map(|r| r.site.to_string())
+            .collect();
+        let first = sites.first().cloned().unwrap_or_else(|| "<unknown>".to_string());
+        let message = messages::render(messages::LOCK_POISONED, &[("count", &sites.len()), ("site", &first)]);
+        Err(crate::errors::with_fields(py, crate::errors::coded::<LockPoisonedError>(py, message), &[
+            ("sites", sites.into_py(py)),
+        ]))
+    }
+}
+
+fn record(site: &'static Location<'static>, action: &'static str) {
+    let owner = SCOPE.with(|scope| scope.borrow().last().cloned());
+    let timestamp = crate::clock::now_secs();
+    {
+        let mut recoveries = RECOVERIES.lock().unwrap_or_else(PoisonError::into_inner);
+        let seq = SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+        match &owner {
+            Some(epoch) => epoch.fetch_add(1, Ordering::SeqCst),
+            None => SHARED_EPOCH.fetch_add(1, Ordering::SeqCst),
+        };
+        if recoveries.len() >= MAX_RECOVERIES {
+            recoveries.remove(0);
+        }
+        let owner = owner.map(|epoch| Arc::as_ptr(&epoch) as usize);
+        recoveries.push(Recovery { seq, owner, site, action, timestamp });
+    }
+    crate::audit::log_global("LOCK_POISONED", &format!("Recovered lock poisoned by a panic at {site} (data {action})"));
+}
+
+pub trait Relock<T> {
+    /// Lock, keeping the data of a poisoned mutex.
+    fn relock(&self) -> MutexGuard<'_, T>;
+    /// Lock, resetting the data of a poisoned mutex to `T::default()`.
+    fn relock_reset(&self) -> MutexGuard<'_, T> where T: Default;
+}
+
+impl<T> Relock<T> for Mutex<T> {
+    #[track_caller]
+    fn relock(&self) -> MutexGuard<'_, T> {
+        let site = Location::caller();
+        loop {
+            match self.lock() {
+                Ok(guard) => return guard,
+                Err(poisoned) => {
+                    drop(poisoned.into_inner());
+                    self.clear_poison();
+                    record(site, "kept");
+                }
+            }
+        }
+    }
+
+    #[track_caller]
+    fn relock_reset(&self) -> MutexGuard<'_, T> where T: Default {
+        let site = Location::caller();
+        loop {
+            match self.lock() {
+                Ok(guard) => return guard,
+                Err(poisoned) => {
+                    *poisoned.into_inner() = T::default();
+                    self.clear_poison();
+                    record(site, "reset");
+                }
+            }
+        }
+    }
+}
+
+pub trait RelockRw<T> {
+    /// Read lock, keeping the data of a poisoned lock.
+    fn relock_read(&self) -> RwLockReadGuard<'_, T>;
+    /// Write lock, keeping the data of a poisoned lock.
+    fn relock_write(&self) -> RwLockWriteGuard<'_, T>;
+}
+
+impl<T> RelockRw<T> for RwLock<T> {
+    #[track_caller]
+    fn relock_read(&self) -> RwLockReadGuard<'_, T> {
+        let site = Location::caller();
+        loop {
+            match self.read() {
+                Ok(guard) => return guard,
+                Err(poisoned) => {
+                    drop(poisoned.into_inner());
+                    self.clear_poison();
+                    record(site, "kept");
+                }
+            }
+        }
+    }
+
+    #[track_caller]
+    fn relock_write(&self) -> RwLockWriteGuard<'_, T> {
+        let site = Location::caller();
+        loop {
+            match self.write() {
+                Ok(guard) => return guard,
+                Err(poisoned) => {
+                    drop(poisoned.into_inner());
+                    self.clear_poison();
+                    record(site, "kept");
+                }
+            }
+        }
+    }
+}
+
+/// [v3.6] Locks recovered after a panic poisoned them (most recent last, up to 100):
+/// `[{site, action, timestamp}]` with `action` "kept" or "reset". `reset=True` clears the list.
+#[pyfunction]
+#[pyo3(signature = (reset=false))]
+pub fn lock_recoveries(py: Python, reset: bool) -> PyResult<Vec<PyObject>> {
+    let mut recoveries = RECOVERIES.lock().unwrap_or_else(PoisonError::into_inner);
+    let out = recoveries.iter().map(|r| {
+        let d = PyDict::new_bound(py);
+        d.set_item("site", r.site.to_string())?;
+        d.set_item("action", r.action)?;
+        d.set_item("timestamp", r.timestamp)?;
+        Ok(d.into_any().unbind())
+    }).collect::<PyResult<Vec<_>>>()?;
+    if reset {
+        recoveries.clear();
+    }
+    Ok(out)
+}
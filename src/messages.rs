@@ -0,0 +1,148 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{LazyLock, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] Message catalog for the structured errors (conflicts, permissions, quotas, schema).
+// Every entry has a stable code that is set as `err.code` on the raised exception, and a
+// default English template with `{param}` placeholders. Products that want their own phrasing
+// register a formatter per code:
+//   theus_core.register_error_formatter("TH203", lambda code, params: f"No {params['capability']}")
+// The formatter receives the code and the template parameters (as strings) and returns the
+// message; if it raises or returns a non-string the default template is used.
+// NOTE: Codes are append-only: never renumber or reuse one. Formatters run on the raising
+// thread (often inside a guard), so they must not call back into the engine.
+
+/// One catalog entry.
+pub struct Entry {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub template: &'static str,
+}
+
+pub const CAS_MISMATCH: &str = "TH101";
+pub const STRICT_CAS_MISMATCH: &str = "TH102";
+pub const INJECTED_CONFLICT: &str = "TH103";
+pub const SYSTEM_BUSY: &str = "TH110";
+pub const ILLEGAL_ACCESS: &str = "TH201";
+pub const PURE_WRITE: &str = "TH202";
+pub const CAPABILITY_REQUIRED: &str = "TH203";
+pub const NO_TRANSACTION: &str = "TH204";
+pub const CONTROL_ZONE_INPUT: &str = "TH205";
+pub const PRIVATE_ATTRIBUTE: &str = "TH206";
+pub const EFFECT_BUDGET: &str = "TH207";
+pub const PURE_IO: &str = "TH208";
+pub const ZONE_PHYSICS: &str = "TH209";
+pub const TENANT_ISOLATION: &str = "TH210";
+pub const HEAVY_QUOTA: &str = "TH301";
+pub const TENANT_QUOTA: &str = "TH302";
+pub const SCHEMA_VIOLATION: &str = "TH401";
+pub const SCHEMA_VIOLATION_CAS: &str = "TH402";
+pub const SCHEMA_VIOLATION_LOAD: &str = "TH403";
+pub const INJECTED_SCHEMA_VIOLATION: &str = "TH404";
+pub const ILLEGAL_TRANSITION: &str = "TH501";
+pub const SNAPSHOT_INVALID: &str = "TH601";
+pub const SNAPSHOT_VERSION: &str = "TH602";
+pub const SNAPSHOT_CORRUPT: &str = "TH603";
+pub const SNAPSHOT_INCOMPATIBLE: &str = "TH604";
+pub const REENTRANT_ACCESS: &str = "TH701";
+pub const LOCK_POISONED: &str = "TH801";
+
+pub const CATALOG: &[Entry] = &[
+    Entry { code: CAS_MISMATCH, name: "cas_mismatch", template: "CAS Version Mismatch (Conflict Detected): Expected {expected}, Found {actual} (Keys Changed)" },
+    Entry { code: STRICT_CAS_MISMATCH, name: "strict_cas_mismatch", template: "Strict CAS Mismatch: Expected {expected}, Found {actual} (Strict CAS Enabled)" },
+    Entry { code: INJECTED_CONFLICT, name: "injected_conflict", template: "CAS Version Mismatch (Injected): {detail}" },
+    Entry { code: SYSTEM_BUSY, name: "system_busy", template: "System Busy (VIP Access Only)" },
+    Entry { code: ILLEGAL_ACCESS, name: "illegal_access", template: "Illegal {op}: '{path}'" },
+    Entry { code: PURE_WRITE, name: "pure_write", template: "PURE process cannot write to '{path}'" },
+    Entry { code: CAPABILITY_REQUIRED, name: "capability_required", template: "Permission Denied: {capability} capability required for {operation} at '{path}'. (Current Lens: {lens})" },
+    Entry { code: NO_TRANSACTION, name: "no_transaction", template: "Supervisor blocked mutation to '{path}': No active transaction found. State is Immutable outside processes." },
+    Entry { code: CONTROL_ZONE_INPUT, name: "control_zone_input", template: "SECURITY VIOLATION: Input '{path}' belongs to restricted Control Zone {zone}." },
+    Entry { code: PRIVATE_ATTRIBUTE, name: "private_attribute", template: "Access to private attribute '{name}' denied in Strict Mode" },
+    Entry { code: EFFECT_BUDGET, name: "effect_budget", template: "Process '{process}' attempted {category} effect ({effect}) outside its effect budget {budget} (contract: {contract})" },
+    Entry { code: PURE_IO, name: "pure_io", template: "PURE process '{process}' attempted {category} I/O ({effect})" },
+    Entry { code: ZONE_PHYSICS, name: "zone_physics", template: "Permission Denied: {capability} capability required for '{path}' (Zone Physics blocked it)." },
+    Entry { code: TENANT_ISOLATION, name: "tenant_isolation", template: "Tenant isolation: {scope} cannot access '{path}' ({reason})" },
+    Entry { code: HEAVY_QUOTA, name: "heavy_quota", template: "Heavy quota exceeded: '{path}' needs {requested} bytes, {used} of {limit} in use" },
+    Entry { code: TENANT_QUOTA, name: "tenant_quota", template: "Tenant quota exceeded: '{tenant}' needs {requested} {resource} (limit {limit})" },
+    Entry { code: SCHEMA_VIOLATION, name: "schema_violation", template: "Schema Violation: {error}" },
+    Entry { code: SCHEMA_VIOLATION_CAS, name: "schema_violation_cas", template: "Schema Violation (CAS): {error}" },
+    Entry { code: SCHEMA_VIOLATION_LOAD, name: "schema_violation_load", template: "Schema Violation (load_state): {error}" },
+    Entry { code: INJECTED_SCHEMA_VIOLATION, name: "injected_schema_violation", template: "Schema Violation (Injected): {detail}" },
+    Entry { code: ILLEGAL_TRANSITION, name: "illegal_transition", template: "Illegal state transition at '{path}': {from} -> {to} (machine '{pattern}' allows: {allowed})" },
+    Entry { code: SNAPSHOT_INVALID, name: "snapshot_invalid", template: "Not a Theus snapshot: {detail}" },
+    Entry { code: SNAPSHOT_VERSION, name: "snapshot_version", template: "Snapshot container version {found} is not supported (this build reads version {supported})" },
+    Entry { code: SNAPSHOT_CORRUPT, name: "snapshot_corrupt", template: "Snapshot corrupted: {detail}" },
+    Entry { code: SNAPSHOT_INCOMPATIBLE, name: "snapshot_incompatible", template: "Snapshot incompatible with this engine: {detail}" },
+    Entry { code: REENTRANT_ACCESS, name: "reentrant_access", template: "Re-entrant proxy {access} of '{path}': a hook running inside a proxy operation (__eq__, __deepcopy__, copier, middleware, transition rule) touched the context again" },
+    Entry { code: LOCK_POISONED, name: "lock_poisoned", template: "Commit refused: {count} engine lock(s) were poisoned by a panic while this transaction was open (first at {site}); retry on a fresh transaction" },
+];
+
+static FORMATTERS: LazyLock<Mutex<HashMap<&'static str, PyObject>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A rendered catalog message.
+pub struct Message {
+    pub code: &'static str,
+    pub text: String,
+}
+
+fn entry(code: &str) -> Option<&'static Entry> {
+    CATALOG.iter().find(|e| e.code == code)
+}
+
+/// Renders `code` with `params`, through its registered formatter if there is one.
+pub fn render(code: &'static str, params: &[(&str, &dyn Display)]) -> Message {
+    let params: Vec<(&str, String)> = params.iter().map(|(k, v)| (*k, v.to_string())).collect();
+    let formatter = Python::with_gil(|py| FORMATTERS.relock().get(code).map(|f| f.clone_ref(py)));
+    if let Some(formatter) = formatter {
+        let custom = Python::with_gil(|py| -> PyResult<String> {
+            let kwargs = PyDict::new_bound(py);
+            for (k, v) in &params {
+                kwargs.set_item(*k, v)?;
+            }
+            formatter.call1(py, (code, kwargs))?.extract(py)
+        });
+        if let Ok(text) = custom {
+            return Message { code, text };
+        }
+    }
+    let mut text = entry(code).map_or(code, |e| e.template).to_string();
+    for (k, v) in &params {
+        text = text.replace(&format!("{{{k}}}"), v);
+    }
+    Message { code, text }
+}
+
+/// Use `formatter(code, params) -> str` for messages with `code`.
+#[pyfunction]
+pub fn register_error_formatter(py: Python, code: &str, formatter: PyObject) -> PyResult<()> {
+    let entry = entry(code).ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Unknown error code '{code}'")))?;
+    if !formatter.bind(py).is_callable() {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!("Formatter for '{code}' must be callable")));
+    }
+    FORMATTERS.relock().insert(entry.code, formatter);
+    Ok(())
+}
+
+/// Restore the default template for `code`. Returns True if a formatter was registered.
+#[pyfunction]
+pub fn unregister_error_formatter(code: &str) -> bool {
+    FORMATTERS.relock().remove(code).is_some()
+}
+
+/// `{code: {"name", "template", "custom"}}` for every catalog entry.
+#[pyfunction]
+pub fn error_catalog(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let formatters = FORMATTERS.relock();
+    let out = PyDict::new_bound(py);
+    for e in CATALOG {
+        let item = PyDict::new_bound(py);
+        item.set_item("name", e.name)?;
+        item.set_item("template", e.template)?;
+        item.set_item("custom", formatters.contains_key(e.code))?;
+        out.set_item(e.code, item)?;
+    }
+    Ok(out)
+}
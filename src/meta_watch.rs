@@ -0,0 +1,51 @@
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use crate::zones::{resolve_zone, ContextZone};
+use crate::locks::Relock;
+
+// [v3.6] Meta zone hot-reload. Each commit that changes a Meta path (`meta`, `meta_*`
+// segments) bumps the engine's config epoch and calls the `on_meta_change` listeners with
+// `(paths, epoch)`, so processes caching configuration can compare epochs instead of polling
+// the zone.
+// NOTE: Changes are detected at `zone.field` granularity (the paths lineage records), so a
+// `meta_*` key nested deeper inside a changed field is not recognised on its own.
+
+#[derive(Default)]
+pub struct MetaWatch {
+    epoch: AtomicU64,
+    listeners: Mutex<Vec<PyObject>>,
+}
+
+impl MetaWatch {
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::SeqCst)
+    }
+
+    pub fn add(&self, callback: PyObject) {
+        self.listeners.relock().push(callback);
+    }
+
+    pub fn remove(&self, py: Python, callback: &Bound<'_, PyAny>) -> bool {
+        let mut listeners = self.listeners.relock();
+        let before = listeners.len();
+        listeners.retain(|l| !l.bind(py).is(callback));
+        listeners.len() != before
+    }
+
+    /// Bumps the epoch and notifies listeners if any of the committed `paths` is a Meta path.
+    /// NOTE: The commit already happened, so listener errors go to sys.unraisablehook.
+    pub fn committed(&self, py: Python, paths: &[String]) {
+        let meta: Vec<&String> = paths.iter().filter(|p| resolve_zone(p) == ContextZone::Meta).collect();
+        if meta.is_empty() {
+            return;
+        }
+        let epoch = self.epoch.fetch_add(1, Ordering::SeqCst) + 1;
+        let listeners: Vec<PyObject> = self.listeners.relock().iter().map(|l| l.clone_ref(py)).collect();
+        for listener in listeners {
+            if let Err(e) = listener.call1(py, (meta.clone(), epoch)) {
+                e.write_unraisable_bound(py, Some(listener.bind(py)));
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PySet, PyTuple};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::Instant;
+
+// [v3.6] Low-level engine histograms (process-wide, lock-free).
+// Buckets are HDR-style: log2 ranges split into 4 linear sub-buckets (<= 25% relative error),
+// so recording is a couple of atomic adds and never allocates.
+
+const SUB_BITS: u32 = 2;
+const SUB_COUNT: usize = 1 << SUB_BITS;
+const BUCKETS: usize = (64 - SUB_BITS as usize + 1) * SUB_COUNT;
+
+/// Shadow-size estimation stops descending past this depth.
+const SIZE_MAX_DEPTH: usize = 32;
+
+#[allow(clippy::cast_possible_truncation)]
+fn bucket_index(v: u64) -> usize {
+    if v < SUB_COUNT as u64 {
+        return v as usize;
+    }
+    let exp = v.ilog2();
+    let sub = ((v >> (exp - SUB_BITS)) as usize) & (SUB_COUNT - 1);
+    (exp - SUB_BITS + 1) as usize * SUB_COUNT + sub
+}
+
+/// Inclusive upper bound of bucket `idx`.
+#[allow(clippy::cast_possible_truncation)]
+fn bucket_upper(idx: usize) -> u64 {
+    if idx < SUB_COUNT {
+        return idx as u64;
+    }
+    let shift = (idx / SUB_COUNT) as u32 - 1;
+    let sub = (idx % SUB_COUNT) as u64;
+    let upper = ((SUB_COUNT as u128 + u128::from(sub) + 1) << shift) - 1;
+    u64::try_from(upper).unwrap_or(u64::MAX)
+}
+
+pub struct Histogram {
+    unit: &'static str,
+    buckets: [AtomicU64; BUCKETS],
+    sum: AtomicU64,
+    min: AtomicU64,
+    max: AtomicU64,
+}
+
+impl Histogram {
+    fn new(unit: &'static str) -> Self {
+        Histogram {
+            unit,
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum: AtomicU64::new(0),
+            min: AtomicU64::new(u64::MAX),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, v: u64) {
+        if !METRICS_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        self.buckets[bucket_index(v)].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(v, Ordering::Relaxed);
+        self.min.fetch_min(v, Ordering::Relaxed);
+        self.max.fetch_max(v, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for b in &self.buckets {
+            b.store(0, Ordering::Relaxed);
+        }
+        self.sum.store(0, Ordering::Relaxed);
+        self.min.store(u64::MAX, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+    }
+
+    /// Summary dict: count/sum/min/max/mean, p50/p90/p99 (bucket upper bounds, clamped to max)
+    /// and cumulative `buckets` as `[(le, count), ...]` ready for a Prometheus histogram.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let counts: Vec<u64> = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        let count: u64 = counts.iter().sum();
+        let sum = self.sum.load(Ordering::Relaxed);
+        let max = self.max.load(Ordering::Relaxed);
+
+        let d = PyDict::new_bound(py);
+        d.set_item("unit", self.unit)?;
+        d.set_item("count", count)?;
+        d.set_item("sum", sum)?;
+        d.set_item("min", if count == 0 { 0 } else { self.min.load(Ordering::Relaxed) })?;
+        d.set_item("max", max)?;
+        #[allow(clippy::cast_precision_loss)]
+        d.set_item("mean", if count == 0 { 0.0 } else { sum as f64 / count as f64 })?;
+
+        for (name, q) in [("p50", 0.50), ("p90", 0.90), ("p99", 0.99)] {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+            let rank = ((count as f64) * q).ceil().max(1.0) as u64;
+            let mut seen = 0;
+            let mut value = 0;
+            if count > 0 {
+                for (i, c) in counts.iter().enumerate() {
+                    seen += c;
+                    if seen >= rank {
+                        value = bucket_upper(i).min(max);
+                        break;
+                    }
+                }
+            }
+            d.set_item(name, value)?;
+        }
+
+        let buckets = PyList::empty_bound(py);
+        let mut cumulative = 0;
+        for (i, c) in counts.iter().enumerate() {
+            if *c > 0 {
+                cumulative += c;
+                buckets.append((bucket_upper(i), cumulative))?;
+            }
+        }
+        d.set_item("buckets", buckets)?;
+        Ok(d)
+    }
+}
+
+pub struct EngineMetrics {
+    /// Transaction / CAS commit, from commit start to the new state being installed.
+    pub commit_latency: Histogram,
+    /// Delta entries (tx) or top-level keys (CAS) per successful commit.
+    pub deltas_per_commit: Histogram,
+    /// Estimated size of each shadow deepcopy taken by a transaction.
+    pub shadow_copy_bytes: Histogram,
+    /// `SupervisorProxy` attribute/item reads.
+    pub proxy_read_latency: Histogram,
+}
+
+impl EngineMetrics {
+    fn all(&self) -> [(&'static str, &Histogram); 4] {
+        [
+            ("commit_latency_us", &self.commit_latency),
+            ("deltas_per_commit", &self.deltas_per_commit),
+            ("shadow_copy_bytes", &self.shadow_copy_bytes),
+            ("proxy_read_latency_ns", &self.proxy_read_latency),
+        ]
+    }
+}
+
+pub static METRICS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub static ENGINE_METRICS: LazyLock<EngineMetrics> = LazyLock::new(|| EngineMetrics {
+    commit_latency: Histogram::new("us"),
+    deltas_per_commit: Histogram::new("count"),
+    shadow_copy_bytes: Histogram::new("bytes"),
+    proxy_read_latency: Histogram::new("ns"),
+});
+
+pub fn enabled() -> bool {
+    METRICS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records elapsed nanoseconds into the proxy read histogram when dropped.
+pub struct ProxyReadTimer(Option<Instant>);
+
+impl ProxyReadTimer {
+    pub fn start() -> Self {
+        ProxyReadTimer(enabled().then(Instant::now))
+    }
+}
+
+impl Drop for ProxyReadTimer {
+    fn drop(&mut self) {
+        if let Some(start) = self.0 {
+            #[allow(clippy::cast_possible_truncation)]
+            ENGINE_METRICS.proxy_read_latency.record(start.elapsed().as_nanos() as u64);
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub fn record_commit(started: Instant, deltas: usize) {
+    ENGINE_METRICS.commit_latency.record(started.elapsed().as_micros() as u64);
+    ENGINE_METRICS.deltas_per_commit.record(deltas as u64);
+}
+
+/// Approximate deep size (`__sizeof__` summed over dict/list/tuple/set trees).
+pub fn estimate_size(obj: &Bound<PyAny>, depth: usize) -> u64 {
+    let own = obj.call_method0("__sizeof__").and_then(|s| s.extract::<u64>()).unwrap_or(0);
+    if depth >= SIZE_MAX_DEPTH {
+        return own;
+    }
+    let children: u64 = if let Ok(d) = obj.downcast::<PyDict>() {
+        d.iter().map(|(k, v)| estimate_size(&k, depth + 1) + estimate_size(&v, depth + 1)).sum()
+    } else if let Ok(l) = obj.downcast::<PyList>() {
+        l.iter().map(|v| estimate_size(&v, depth + 1)).sum()
+    } else if let Ok(t) = obj.downcast::<PyTuple>() {
+        t.iter().map(|v| estimate_size(&v, depth + 1)).sum()
+    } else if let Ok(s) = obj.downcast::<PySet>() {
+        s.iter().map(|v| estimate_size(&v, depth + 1)).sum()
+    } else if let Ok(attrs) = obj.getattr("__dict__") {
+        if attrs.is_instance_of::<PyDict>() { estimate_size(&attrs, depth + 1) } else { 0 }
+    } else {
+        0
+    };
+    own + children
+}
+
+/// [v3.6] Engine histograms as plain dicts (`reset=True` zeroes them after reading).
+/// Process-wide: every engine in the process records into the same histograms.
+#[pyfunction]
+#[pyo3(signature = (reset=false))]
+pub fn engine_metrics(py: Python, reset: bool) -> PyResult<PyObject> {
+    let out = PyDict::new_bound(py);
+    out.set_item("enabled", enabled())?;
+    for (name, h) in ENGINE_METRICS.all() {
+        out.set_item(name, h.to_dict(py)?)?;
+        if reset {
+            h.reset();
+        }
+    }
+    Ok(out.into_any().unbind())
+}
+
+/// [v3.6] Toggle histogram recording (on by default).
+#[pyfunction]
+pub fn set_metrics_enabled(enabled: bool) {
+    METRICS_ENABLED.store(enabled, Ordering::Relaxed);
+}
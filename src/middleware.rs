@@ -0,0 +1,231 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+use crate::structures::ContextError;
+use crate::locks::{Relock, RelockRw};
+
+// [v3.6] Middleware chain for proxy operations. `engine.add_middleware(name, handler)`
+// registers `handler(path, value, op) -> value`; op is "get" for leaf reads and "set" /
+// "append" / "insert" for writes (the written item, before it is logged). The returned value
+// replaces the original; raising MiddlewareVetoError (or any exception) rejects the
+// operation. Rust features plug in through the `Middleware` trait.
+// Order: writes run by ascending `priority` (ties: registration order), reads in the reverse
+// order, so a middleware that encodes on write decodes on read around the others.
+// `paths` limits a middleware to path prefixes (`*` matches one segment), `ops` to op names.
+// NOTE: Only proxy operations inside a transaction are intercepted; container reads are not
+// (the proxy must keep wrapping the real object). `tx.update()` / `update_many()` payloads
+// only pass through middlewares that opt in with `Middleware::seals_updates` (field
+// encryption), as "set" at each matching path of the payload.
+
+pyo3::create_exception!(theus_core, MiddlewareVetoError, ContextError);
+
+pub const OPS: &[&str] = &["get", "set", "append", "insert"];
+
+/// An interceptor. `value` is the value being read or written at `path`.
+pub trait Middleware: Send + Sync {
+    fn intercept(&self, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject>;
+
+    /// Also run on matching values of `tx.update()` / `update_many()` payloads.
+    fn seals_updates(&self) -> bool {
+        false
+    }
+}
+
+/// A Python callable `handler(path, value, op)`.
+struct PyHandler(PyObject);
+
+impl Middleware for PyHandler {
+    fn intercept(&self, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+        self.0.call1(py, (path, value, op))
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+struct Stats {
+    calls: u64,
+    vetoes: u64,
+    errors: u64,
+    total_ns: u64,
+}
+
+struct Entry {
+    name: String,
+    priority: i32,
+    seq: u64,
+    paths: Option<Vec<Vec<String>>>,
+    ops: Option<Vec<String>>,
+    handler: Arc<dyn Middleware>,
+    stats: Mutex<Stats>,
+}
+
+#[derive(Default)]
+pub struct Pipeline {
+    entries: RwLock<Vec<Arc<Entry>>>,
+    seq: AtomicU64,
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.replace('[', ".").replace(']', "").split('.').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+impl Entry {
+    fn applies(&self, path: &[String], op: &str) -> bool {
+        self.ops.as_ref().is_none_or(|ops| ops.iter().any(|o| o == op))
+            && self.paths.as_ref().is_none_or(|patterns| patterns.iter().any(|p| {
+                p.len() <= path.len() && p.iter().zip(path).all(|(p, s)| p == "*" || p == s)
+            }))
+    }
+}
+
+impl Pipeline {
+    pub fn is_empty(&self) -> bool {
+        self.entries.relock_read().is_empty()
+    }
+
+    /// Registers `handler` under `name` (replacing a middleware with that name).
+    pub fn add(&self, name: &str, handler: Arc<dyn Middleware>, priority: i32, paths: Option<Vec<String>>, ops: Option<Vec<String>>) -> PyResult<()> {
+        if let Some(unknown) = ops.iter().flatten().find(|o| !OPS.contains(&o.as_str())) {
+            return Err(PyValueError::new_err(format!("Unknown middleware op '{unknown}' (expected one of {OPS:?})")));
+        }
+        let entry = Entry {
+            name: name.to_string(),
+            priority,
+            seq: self.seq.fetch_add(1, Ordering::Relaxed),
+            paths: paths.map(|p| p.iter().map(|p| segments(p)).collect()),
+            ops,
+            handler,
+            stats: Mutex::new(Stats::default()),
+        };
+        let mut entries = self.entries.relock_write();
+        entries.retain(|e| e.name != name);
+        entries.push(Arc::new(entry));
+        entries.sort_by_key(|e| (e.priority, e.seq));
+        Ok(())
+    }
+
+    pub fn add_py(&self, name: &str, handler: PyObject, priority: i32, paths: Option<Vec<String>>, ops: Option<Vec<String>>) -> PyResult<()> {
+        self.add(name, Arc::new(PyHandler(handler)), priority, paths, ops)
+    }
+
+    pub fn remove(&self, name: &str) -> bool {
+        let mut entries = self.entries.relock_write();
+        let before = entries.len();
+        entries.retain(|e| e.name != name);
+        entries.len() != before
+    }
+
+    /// Registered names in write order.
+    pub fn names(&self) -> Vec<String> {
+        self.entries.relock_read().iter().map(|e| e.name.clone()).collect()
+    }
+
+    /// Runs the chain for `op` at `path` and returns the final value.
+    pub fn run(&self, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+        // NOTE: Handlers run Python code, so they run on a snapshot without the lock held.
+        let entries: Vec<Arc<Entry>> = self.entries.relock_read().clone();
+        let segments = segments(path);
+        let ordered: Box<dyn Iterator<Item = &Arc<Entry>>> = if op == "get" { Box::new(entries.iter().rev()) } else { Box::new(entries.iter()) };
+        chain(ordered.filter(|e| e.applies(&segments, op)), py, path, value, op)
+    }
+
+    /// `value` written at `path` by `tx.update()` / `update_many()`, with the middlewares that
+    /// seal updates applied wherever one matches inside it. Dicts and lists are walked and
+    /// copied only where something changed, so the caller's payload is left untouched.
+    pub fn seal(&self, py: Python, path: &str, value: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let entries: Vec<Arc<Entry>> = self.entries.relock_read().iter()
+            .filter(|e| e.handler.seals_updates() && e.ops.as_ref().is_none_or(|ops| ops.iter().any(|o| o == "set")))
+            .cloned()
+            .collect();
+        if entries.is_empty() {
+            return Ok(value.clone().unbind());
+        }
+        // No pattern can first match below the longest one, which also bounds the walk.
+        let depth = entries.iter().map(|e| e.paths.as_ref().map_or(0, |p| p.iter().map(Vec::len).max().unwrap_or(0))).max().unwrap_or(0);
+        let mut path = segments(path);
+        Ok(seal_value(py, &entries, &mut path, depth, value)?.unwrap_or_else(|| value.clone().unbind()))
+    }
+
+    /// `{name: {priority, calls, vetoes, errors, total_ms, avg_us}}` in write order.
+    pub fn stats<'py>(&self, py: Python<'py>, reset: bool) -> PyResult<Bound<'py, PyDict>> {
+        let out = PyDict::new_bound(py);
+        for e in self.entries.relock_read().iter() {
+            let mut stats = e.stats.relock();
+            let s = if reset { std::mem::take(&mut *stats) } else { *stats };
+            let row = PyDict::new_bound(py);
+            row.set_item("priority", e.priority)?;
+            row.set_item("calls", s.calls)?;
+            row.set_item("vetoes", s.vetoes)?;
+            row.set_item("errors", s.errors)?;
+            #[allow(clippy::cast_precision_loss)]
+            {
+                row.set_item("total_ms", s.total_ns as f64 / 1e6)?;
+                row.set_item("avg_us", if s.calls == 0 { 0.0 } else { s.total_ns as f64 / s.calls as f64 / 1e3 })?;
+            }
+            out.set_item(&e.name, row)?;
+        }
+        Ok(out)
+    }
+}
+
+fn chain<'a>(entries: impl Iterator<Item = &'a Arc<Entry>>, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+    let mut value = value;
+    for entry in entries {
+        let started = Instant::now();
+        let result = entry.handler.intercept(py, path, value, op);
+        let mut stats = entry.stats.relock();
+        stats.calls += 1;
+        stats.total_ns += u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        match result {
+            Ok(v) => value = v,
+            Err(e) => {
+                if e.is_instance_of::<MiddlewareVetoError>(py) {
+                    stats.vetoes += 1;
+                } else {
+                    stats.errors += 1;
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// The sealed replacement for `value` at `path`, or None when nothing in it matched.
+fn seal_value(py: Python, entries: &[Arc<Entry>], path: &mut Vec<String>, depth: usize, value: &Bound<'_, PyAny>) -> PyResult<Option<PyObject>> {
+    if entries.iter().any(|e| e.applies(path, "set")) {
+        let joined = path.join(".");
+        let matching = entries.iter().filter(|e| e.applies(path, "set"));
+        return chain(matching, py, &joined, value.clone().unbind(), "set").map(Some);
+    }
+    if path.len() >= depth {
+        return Ok(None);
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut sealed: Option<Bound<'_, PyDict>> = None;
+        for (key, item) in dict.iter() {
+            path.push(key.str()?.to_string());
+            let replaced = seal_value(py, entries, path, depth, &item);
+            path.pop();
+            if let Some(new) = replaced? {
+                sealed.get_or_insert(dict.copy()?).set_item(key, new)?;
+            }
+        }
+        return Ok(sealed.map(|d| d.into_any().unbind()));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut sealed: Option<Bound<'_, PyList>> = None;
+        for (i, item) in list.iter().enumerate() {
+            path.push(i.to_string());
+            let replaced = seal_value(py, entries, path, depth, &item);
+            path.pop();
+            if let Some(new) = replaced? {
+                sealed.get_or_insert_with(|| PyList::new_bound(py, list.iter())).set_item(i, new)?;
+            }
+        }
+        return Ok(sealed.map(|l| l.into_any().unbind()));
+    }
+    Ok(None)
+}
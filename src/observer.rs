@@ -0,0 +1,144 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyKeyError;
+use pyo3::types::PyDict;
+use crate::engine::TheusEngine;
+use crate::proxy::ExportFilter;
+use crate::structures::State;
+use crate::zones::{resolve_zone, ContextZone};
+use crate::locks::Relock;
+
+// [v3.6] Plugin-safe engine handle. `engine.observer()` returns an ObserverEngine that can
+// read committed state (current or retained versions), watch events / Meta changes and query
+// commit history - nothing else. It has no transaction constructor and never hands out the
+// engine, State or a live container: reads are redacted copies with Private-zone
+// (`internal_*`) and `_underscore` keys dropped, and a Private path reads as missing.
+// NOTE: Watcher callbacks run with the same payloads as on the engine; `tx.emit()` topics
+// are the emitter's responsibility.
+
+/// Private-zone or `_underscore` path: hidden from observers.
+fn is_redacted(path: &str) -> bool {
+    resolve_zone(path) == ContextZone::Private
+        || path.replace('[', ".").replace(']', "").split('.').any(|s| s.starts_with('_'))
+}
+
+#[pyclass(module = "theus_core", frozen)]
+pub struct ObserverEngine {
+    engine: Py<TheusEngine>,
+}
+
+impl ObserverEngine {
+    pub fn new(engine: Py<TheusEngine>) -> Self {
+        ObserverEngine { engine }
+    }
+
+    /// Committed state of `version` (None = current); older versions must be retained.
+    fn state_at(&self, py: Python, version: Option<u64>) -> PyResult<Py<State>> {
+        let engine = self.engine.borrow(py);
+        let current = engine.committed_state(py);
+        let current_version = current.borrow(py).version;
+        match version {
+            None => Ok(current),
+            Some(v) if v == current_version => Ok(current),
+            Some(v) => engine.history.relock().find(py, v).ok_or_else(|| PyKeyError::new_err(format!(
+                "Version {v} is not retained (current {current_version})"
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl ObserverEngine {
+    /// Current committed version.
+    #[getter]
+    fn version(&self, py: Python) -> u64 {
+        self.engine.borrow(py).committed_state(py).borrow(py).version
+    }
+
+    /// Redacted copy of the value at `path` ("domain.cfg.x"); `default` if missing or
+    /// Private. `version` reads a retained older version.
+    #[pyo3(signature = (path, default=None, version=None))]
+    fn read(&self, py: Python, path: &str, default: Option<PyObject>, version: Option<u64>) -> PyResult<PyObject> {
+        let default = || default.unwrap_or_else(|| py.None());
+        if is_redacted(path) {
+            return Ok(default());
+        }
+        let state = self.state_at(py, version)?;
+        let state = state.bind(py).borrow();
+        match crate::pins::lookup(py, &state, path)? {
+            Some(value) => Ok(ExportFilter::redacted().export(&value, path, 0)?.unwrap_or_else(|| py.None())),
+            None => Ok(default()),
+        }
+    }
+
+    /// Redacted copy of the whole Data zone (`{root: value}`).
+    #[pyo3(signature = (version=None))]
+    fn snapshot<'py>(&self, py: Python<'py>, version: Option<u64>) -> PyResult<Bound<'py, PyDict>> {
+        let state = self.state_at(py, version)?;
+        let state = state.bind(py).borrow();
+        let out = PyDict::new_bound(py);
+        let filter = ExportFilter::redacted();
+        for (key, value) in &state.data {
+            if is_redacted(key) {
+                continue;
+            }
+            if let Some(v) = filter.export(value.bind(py), key, 0)? {
+                out.set_item(key, v)?;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Commits that wrote `path` (see `TheusEngine.blame`), newest first; Private paths are
+    /// dropped from every entry.
+    #[pyo3(signature = (path, depth=10))]
+    fn history(&self, py: Python, path: &str, depth: usize) -> PyResult<Vec<PyObject>> {
+        if is_redacted(path) {
+            return Ok(Vec::new());
+        }
+        let entries = self.engine.borrow(py).lineage.blame(py, path, depth, None)?;
+        for entry in &entries {
+            let entry = entry.bind(py);
+            let paths: Vec<String> = entry.get_item("paths")?.extract()?;
+            entry.set_item("paths", paths.into_iter().filter(|p| !is_redacted(p)).collect::<Vec<_>>())?;
+        }
+        Ok(entries)
+    }
+
+    /// Register `watcher(topic, payload)` for events emitted via `tx.emit()`.
+    fn add_event_watcher(&self, py: Python, watcher: PyObject) {
+        self.engine.borrow(py).event_watchers.relock().push(watcher);
+    }
+
+    fn remove_event_watcher(&self, py: Python, watcher: &Bound<'_, PyAny>) -> bool {
+        let engine = self.engine.borrow(py);
+        let mut watchers = engine.event_watchers.relock();
+        let before = watchers.len();
+        watchers.retain(|w| !w.bind(py).is(watcher));
+        watchers.len() != before
+    }
+
+    /// Call `callback(paths, epoch)` after each commit changing Meta paths.
+    fn on_meta_change(&self, py: Python, callback: PyObject) -> PyObject {
+        self.engine.borrow(py).meta_watch.add(callback.clone_ref(py));
+        callback
+    }
+
+    fn remove_meta_listener(&self, py: Python, callback: &Bound<'_, PyAny>) -> bool {
+        self.engine.borrow(py).meta_watch.remove(py, callback)
+    }
+
+    #[getter]
+    fn meta_epoch(&self, py: Python) -> u64 {
+        self.engine.borrow(py).meta_watch.epoch()
+    }
+
+    /// Observers cannot be pickled (they are bound to a live engine).
+    #[allow(clippy::unused_self)]
+    fn __reduce__(&self) -> PyResult<()> {
+        Err(pyo3::exceptions::PyTypeError::new_err("ObserverEngine objects cannot be pickled"))
+    }
+
+    fn __repr__(&self, py: Python) -> String {
+        format!("ObserverEngine(version={})", self.version(py))
+    }
+}
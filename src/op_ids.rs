@@ -0,0 +1,63 @@
+use std::collections::{HashMap, VecDeque};
+
+// [v3.6] Idempotent transactions. `engine.transaction(op_id="job-42")` remembers the version
+// the op committed in; a later transaction with the same op_id (a retried job) commits as a
+// no-op reporting that original version instead of applying its writes again. The map is
+// bounded (oldest op ids are forgotten first) and travels with `dumps_state()` blobs.
+// NOTE: Only committed ops are remembered: a failed or rolled-back attempt can be retried.
+
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+pub struct CommittedOps {
+    order: VecDeque<String>,
+    versions: HashMap<String, u64>,
+    capacity: usize,
+}
+
+impl Default for CommittedOps {
+    fn default() -> Self {
+        CommittedOps { order: VecDeque::new(), versions: HashMap::new(), capacity: DEFAULT_CAPACITY }
+    }
+}
+
+impl CommittedOps {
+    pub fn get(&self, op_id: &str) -> Option<u64> {
+        self.versions.get(op_id).copied()
+    }
+
+    /// Remembers `op_id` as committed in `version` (the first commit wins).
+    pub fn record(&mut self, op_id: &str, version: u64) {
+        if self.versions.contains_key(op_id) {
+            return;
+        }
+        self.versions.insert(op_id.to_string(), version);
+        self.order.push_back(op_id.to_string());
+        self.prune();
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.prune();
+    }
+
+    fn prune(&mut self) {
+        while self.order.len() > self.capacity {
+            if let Some(op) = self.order.pop_front() {
+                self.versions.remove(&op);
+            }
+        }
+    }
+
+    /// `(op_id, version)` oldest first (snapshot order).
+    pub fn entries(&self) -> Vec<(String, u64)> {
+        self.order.iter().map(|op| (op.clone(), self.versions[op])).collect()
+    }
+
+    pub fn replace(&mut self, entries: Vec<(String, u64)>) {
+        self.order.clear();
+        self.versions.clear();
+        for (op, version) in entries {
+            self.record(&op, version);
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use pyo3::prelude::*;
+use pyo3::create_exception;
+use pyo3::types::PyBytes;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use crate::locks::Relock;
+
+create_exception!(theus_core, SerializationError, pyo3::exceptions::PyValueError);
+
+/// Default wire format used when an `OutboxMsg` carries no `content_type`.
+pub const DEFAULT_CONTENT_TYPE: &str = "json";
+
+/// Payload codec registered under a content-type name.
+enum Serializer {
+    Json,
+    Msgpack,
+    Pickle,
+    Custom { encode: PyObject, decode: PyObject },
+}
+
+// [v3.6] Process-wide serializer registry.
+// NOTE: pickle is NOT registered by default - unpickling worker input is arbitrary code
+// execution, so it must be enabled explicitly via `allow_pickle_serializer(True)`.
+static SERIALIZERS: std::sync::LazyLock<Mutex<HashMap<String, Serializer>>> = std::sync::LazyLock::new(|| {
+    let mut map = HashMap::new();
+    map.insert("json".to_string(), Serializer::Json);
+    map.insert("msgpack".to_string(), Serializer::Msgpack);
+    Mutex::new(map)
+});
+
+/// Register a user codec. `encode(payload) -> bytes`, `decode(bytes) -> payload`.
+#[pyfunction]
+pub fn register_serializer(py: Python, name: String, encode: PyObject, decode: PyObject) -> PyResult<()> {
+    if !encode.bind(py).is_callable() || !decode.bind(py).is_callable() {
+        return Err(pyo3::exceptions::PyTypeError::new_err(
+            format!("Serializer '{name}': encode and decode must be callable"),
+        ));
+    }
+    SERIALIZERS.relock().insert(name, Serializer::Custom { encode, decode });
+    Ok(())
+}
+
+/// Remove a codec. Returns True if it was registered.
+#[pyfunction]
+pub fn unregister_serializer(name: &str) -> bool {
+    SERIALIZERS.relock().remove(name).is_some()
+}
+
+/// Opt-in (or out) of the built-in `pickle` codec.
+#[pyfunction]
+pub fn allow_pickle_serializer(enabled: bool) {
+    let mut map = SERIALIZERS.relock();
+    if enabled {
+        map.insert("pickle".to_string(), Serializer::Pickle);
+    } else if matches!(map.get("pickle"), Some(Serializer::Pickle)) {
+        map.remove("pickle");
+    }
+}
+
+/// Names of all registered codecs (sorted).
+#[pyfunction]
+pub fn list_serializers() -> Vec<String> {
+    let mut names: Vec<String> = SERIALIZERS.relock().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+// NOTE: Takes the already-locked map; calling `list_serializers()` here would deadlock.
+fn unknown(name: &str, map: &HashMap<String, Serializer>) -> PyErr {
+    let mut names: Vec<&String> = map.keys().collect();
+    names.sort();
+    SerializationError::new_err(format!(
+        "No serializer registered for content type '{name}'. Available: {names:?}"
+    ))
+}
+
+/// How a content type is encoded / decoded, resolved without holding the registry lock
+/// across Python calls.
+enum Codec {
+    /// json.dumps plus a str -> bytes step.
+    JsonEncode,
+    /// The crate's own msgpack codec (`state_codec.rs`), no Python module needed.
+    Msgpack,
+    Call(PyObject),
+}
+
+fn resolve(py: Python, name: &str, encode: bool) -> PyResult<Codec> {
+    let builtin = {
+        let map = SERIALIZERS.relock();
+        match map.get(name) {
+            None => return Err(unknown(name, &map)),
+            Some(Serializer::Json) if encode => return Ok(Codec::JsonEncode),
+            Some(Serializer::Json) => ("json", "loads"),
+            Some(Serializer::Msgpack) => return Ok(Codec::Msgpack),
+            Some(Serializer::Pickle) => ("pickle", if encode { "dumps" } else { "loads" }),
+            Some(Serializer::Custom { encode: e, decode: d }) => {
+                return Ok(Codec::Call(if encode { e.clone_ref(py) } else { d.clone_ref(py) }));
+            }
+        }
+    };
+    Ok(Codec::Call(py.import_bound(builtin.0)?.getattr(builtin.1)?.unbind()))
+}
+
+/// Encode `payload` with the codec registered under `name`.
+pub fn encode_payload(py: Python, name: &str, payload: &Bound<'_, PyAny>) -> PyResult<Py<PyBytes>> {
+    let out = match resolve(py, name, true)? {
+        Codec::Call(f) => f.bind(py).call1((payload,))?,
+        Codec::JsonEncode => {
+            let text: String = py.import_bound("json")?.call_method1("dumps", (payload,))?.extract()?;
+            PyBytes::new_bound(py, text.as_bytes()).into_any()
+        }
+        Codec::Msgpack => PyBytes::new_bound(py, &crate::state_codec::encode_value(py, payload)?).into_any(),
+    };
+
+    out.downcast_into::<PyBytes>()
+        .map(Bound::unbind)
+        .map_err(|_| SerializationError::new_err(format!("Serializer '{name}' encode() must return bytes")))
+}
+
+/// Decode `data` with the codec registered under `name`.
+pub fn decode_payload(py: Python, name: &str, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    match resolve(py, name, false)? {
+        Codec::Call(f) => Ok(f.bind(py).call1((data,))?.unbind()),
+        Codec::Msgpack => {
+            let bytes = data.downcast::<PyBytes>()
+                .map_err(|_| SerializationError::new_err(format!("Serializer '{name}' decodes bytes only")))?;
+            Ok(crate::state_codec::decode_value(py, bytes.as_bytes())?.unbind())
+        }
+        Codec::JsonEncode => unreachable!("decoders never resolve to the json encoder"),
+    }
+}
+
+/// [v3.6] Delivery order: higher `priority` first (stable), except that messages sharing an
+/// `ordering_key` keep their enqueue order. A later urgent message therefore promotes its
+/// same-key predecessors instead of overtaking them.
+pub fn delivery_order(msgs: Vec<crate::structures::OutboxMsg>) -> Vec<crate::structures::OutboxMsg> {
+    let mut idx: Vec<usize> = (0..msgs.len()).collect();
+    idx.sort_by_key(|&i| std::cmp::Reverse(msgs[i].priority));
+
+    // Re-seat each ordering group into its sorted slots, in original order.
+    let mut groups: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (slot, &i) in idx.iter().enumerate() {
+        if let Some(k) = msgs[i].ordering_key.as_deref() {
+            groups.entry(k).or_default().push(slot);
+        }
+    }
+    for slots in groups.values() {
+        let mut members: Vec<usize> = slots.iter().map(|&s| idx[s]).collect();
+        members.sort_unstable();
+        for (&slot, member) in slots.iter().zip(members) {
+            idx[slot] = member;
+        }
+    }
+
+    let mut cells: Vec<Option<crate::structures::OutboxMsg>> = msgs.into_iter().map(Some).collect();
+    idx.into_iter().filter_map(|i| cells[i].take()).collect()
+}
+
+/// Wall-clock milliseconds (persistable across restarts, unlike `Instant`).
+pub fn now_ms() -> u64 {
+    crate::clock::now_ms()
+}
+
+/// [v3.6] Sliding window of idempotency keys whose side effects already completed.
+/// Entries expire after `retention_ms` or when `max_entries` is exceeded (oldest first).
+pub struct ProcessedIdWindow {
+    order: VecDeque<(String, u64)>,
+    ids: HashMap<String, u64>,
+    pub retention_ms: u64,
+    pub max_entries: usize,
+}
+
+impl ProcessedIdWindow {
+    pub fn new(retention_ms: u64, max_entries: usize) -> Self {
+        ProcessedIdWindow { order: VecDeque::new(), ids: HashMap::new(), retention_ms, max_entries }
+    }
+
+    fn prune(&mut self, now: u64) {
+        while let Some((key, ts)) = self.order.front() {
+            let expired = now.saturating_sub(*ts) > self.retention_ms;
+            if !expired && self.order.len() <= self.max_entries {
+                break;
+            }
+            // NOTE: Only drop the index entry if it was not re-marked later.
+            if self.ids.get(key) == Some(ts) {
+                self.ids.remove(key);
+            }
+            self.order.pop_front();
+        }
+    }
+
+    pub fn contains(&mut self, key: &str, now: u64) -> bool {
+        self.prune(now);
+        self.ids.contains_key(key)
+    }
+
+    /// Record completion at `ts`. Returns false if the key was already recorded.
+    pub fn mark(&mut self, key: String, ts: u64) -> bool {
+        self.prune(ts);
+        if self.ids.contains_key(&key) {
+            return false;
+        }
+        self.ids.insert(key.clone(), ts);
+        self.order.push_back((key, ts));
+        self.prune(ts);
+        true
+    }
+
+    pub fn entries(&self) -> Vec<(String, u64)> {
+        self.order.iter().filter(|(k, ts)| self.ids.get(k) == Some(ts)).cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.order.clear();
+        self.ids.clear();
+    }
+}
+
+/// [v3.6] fsck "outbox" check: queued messages already delivered or queued twice (dropped on
+/// `repair`), and ones that also sit in the dead-letter store.
+pub fn fsck(queue: &mut Vec<crate::structures::OutboxMsg>, processed: &mut ProcessedIdWindow, dead: &[DeadLetter], repair: bool) -> Vec<crate::fsck::Issue> {
+    use crate::fsck::Issue;
+    let now = now_ms();
+    let dead: std::collections::HashSet<&str> = dead.iter().map(|d| d.msg.idempotency_key.as_str()).collect();
+    let mut seen = std::collections::HashSet::new();
+    let mut issues = Vec::new();
+    let mut keep = Vec::with_capacity(queue.len());
+    for msg in queue.iter() {
+        let key = msg.idempotency_key.as_str();
+        let orphan = if processed.contains(key, now) {
+            Some("already delivered")
+        } else if !seen.insert(key) {
+            Some("queued twice")
+        } else {
+            None
+        };
+        if let Some(why) = orphan {
+            issues.push(Issue::warning("outbox", format!("'{}' ({key}) {why}", msg.topic)).repairable(repair));
+            keep.push(!repair);
+            continue;
+        }
+        if dead.contains(key) {
+            issues.push(Issue::error("outbox", format!("'{}' ({key}) is both queued and dead-lettered", msg.topic)));
+        }
+        keep.push(true);
+    }
+    let mut keep = keep.into_iter();
+    queue.retain(|_| keep.next().unwrap_or(true));
+    issues
+}
+
+/// [v3.6] Inbox bookkeeping: consumed event ids + per-key ordering.
+/// Events arriving ahead of their sequence are parked until the gap is filled.
+pub struct InboxState {
+    pub consumed: ProcessedIdWindow,
+    pub last_seq: HashMap<String, u64>,
+    pub parked: HashMap<String, std::collections::BTreeMap<u64, (PyObject, Option<String>)>>,
+}
+
+impl InboxState {
+    pub fn new() -> Self {
+        InboxState {
+            consumed: ProcessedIdWindow::new(86_400_000, 100_000),
+            last_seq: HashMap::new(),
+            parked: HashMap::new(),
+        }
+    }
+}
+
+/// [v3.6] A message that exhausted its delivery attempts.
+#[pyclass(module = "theus_core")]
+#[derive(Clone)]
+pub struct DeadLetter {
+    #[pyo3(get)]
+    pub msg: crate::structures::OutboxMsg,
+    #[pyo3(get)]
+    pub error: String,
+    #[pyo3(get)]
+    pub failed_at_ms: u64,
+}
+
+#[pymethods]
+impl DeadLetter {
+    fn __repr__(&self) -> String {
+        format!("DeadLetter(topic='{}', attempts={}, error='{}')", self.msg.topic, self.msg.attempts, self.error)
+    }
+}
+
+/// [v3.6] Delivery counters + dead-letter store shared between the engine and its collectors.
+pub struct OutboxMetrics {
+    pub in_flight: usize,
+    pub delivered: u64,
+    pub failed: u64,
+    pub max_attempts: u32,
+    pub dead_letters: Vec<DeadLetter>,
+}
+
+impl OutboxMetrics {
+    pub fn new(max_attempts: u32) -> Self {
+        OutboxMetrics { in_flight: 0, delivered: 0, failed: 0, max_attempts, dead_letters: Vec::new() }
+    }
+
+    /// Remove dead letters matching `keys` (all when None).
+    pub fn take_dead(&mut self, keys: Option<&[String]>) -> Vec<DeadLetter> {
+        let (taken, kept) = std::mem::take(&mut self.dead_letters)
+            .into_iter()
+            .partition(|d| keys.is_none_or(|ks| ks.contains(&d.msg.idempotency_key)));
+        self.dead_letters = kept;
+        taken
+    }
+
+    /// Ordering keys of dead letters: their successors wait until the dead letter is resolved.
+    pub fn blocked_keys(&self) -> HashSet<String> {
+        self.dead_letters.iter().filter_map(|d| d.msg.ordering_key.clone()).collect()
+    }
+}
@@ -0,0 +1,226 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyKeyError;
+use pyo3::types::{PyDict, PyList};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Version pinning for readers. The engine keeps the last `retain` committed states
+// (`set_version_retention`, default 0 = current only); `engine.pin(version)` returns a
+// PinnedView that keeps that state alive and resolves reads against it while writers move
+// on. Pinned versions are exempt from pruning until every view on them is unpinned, closed
+// or garbage-collected.
+// NOTE: States share unchanged subtrees, so retaining versions costs only what changed.
+
+#[derive(Default)]
+pub struct VersionHistory {
+    retain: usize,
+    recent: VecDeque<(u64, Py<State>)>,
+    /// version -> (state, open views)
+    pinned: BTreeMap<u64, (Py<State>, usize)>,
+}
+
+impl VersionHistory {
+    /// Records a newly committed state, pruning unpinned versions beyond `retain`.
+    pub fn record(&mut self, py: Python, state: &Py<State>) {
+        if self.retain == 0 {
+            return;
+        }
+        let version = state.borrow(py).version;
+        self.recent.retain(|(v, _)| *v != version);
+        self.recent.push_back((version, state.clone_ref(py)));
+        while self.recent.len() > self.retain {
+            self.recent.pop_front();
+        }
+    }
+
+    pub fn set_retention(&mut self, retain: usize) {
+        self.retain = retain;
+        while self.recent.len() > retain {
+            self.recent.pop_front();
+        }
+    }
+
+    /// The retained or pinned state of `version`, if still held.
+    pub fn find(&self, py: Python, version: u64) -> Option<Py<State>> {
+        self.pinned.get(&version).map(|(s, _)| s)
+            .or_else(|| self.recent.iter().find(|(v, _)| *v == version).map(|(_, s)| s))
+            .map(|s| s.clone_ref(py))
+    }
+
+    /// Every retained or pinned state.
+    pub fn states(&self, py: Python) -> Vec<Py<State>> {
+        self.recent.iter().map(|(_, s)| s).chain(self.pinned.values().map(|(s, _)| s))
+            .map(|s| s.clone_ref(py)).collect()
+    }
+
+    fn pin(&mut self, py: Python, version: u64, state: &Py<State>) {
+        self.pinned.entry(version).or_insert_with(|| (state.clone_ref(py), 0)).1 += 1;
+    }
+
+    fn unpin(&mut self, version: u64) {
+        if let Some(entry) = self.pinned.get_mut(&version) {
+            entry.1 -= 1;
+            if entry.1 == 0 {
+                self.pinned.remove(&version);
+            }
+        }
+    }
+
+    /// `{"retention": n, "retained": [versions], "pinned": {version: views}}`.
+    pub fn info(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        out.set_item("retention", self.retain)?;
+        out.set_item("retained", self.recent.iter().map(|(v, _)| *v).collect::<Vec<_>>())?;
+        let pinned = PyDict::new_bound(py);
+        for (v, (_, n)) in &self.pinned {
+            pinned.set_item(v, n)?;
+        }
+        out.set_item("pinned", pinned)?;
+        Ok(out.into_any().unbind())
+    }
+}
+
+/// Pins `version` (None = `current`) and returns a view on it.
+pub fn pin(py: Python, history: &Arc<Mutex<VersionHistory>>, current: &Py<State>, version: Option<u64>) -> PyResult<PinnedView> {
+    let state = state_at(py, history, current, version)?;
+    let version = state.borrow(py).version;
+    history.relock().pin(py, version, &state);
+    Ok(PinnedView { history: history.clone(), state, version, open: Mutex::new(true) })
+}
+
+/// The committed state of `version` (None = `current`), if still retained.
+pub fn state_at(py: Python, history: &Arc<Mutex<VersionHistory>>, current: &Py<State>, version: Option<u64>) -> PyResult<Py<State>> {
+    let current_version = current.borrow(py).version;
+    let version = version.unwrap_or(current_version);
+    if version == current_version {
+        return Ok(current.clone_ref(py));
+    }
+    history.relock().find(py, version).ok_or_else(|| PyKeyError::new_err(format!(
+        "Version {version} is not retained (current {current_version}); raise set_version_retention() to pin older versions"
+    )))
+}
+
+/// Value at `path` ("domain.cfg.x", "heavy.frame") in `state`, None if missing.
+pub fn lookup<'py>(py: Python<'py>, state: &State, path: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let mut segments = path.split('.');
+    let root = segments.next().unwrap_or_default();
+    let top = if root == "heavy" {
+        segments.next().and_then(|k| state.heavy.get(k))
+    } else {
+        state.data.get(root)
+    };
+    let Some(mut value) = top.map(|v| v.bind(py).clone()) else { return Ok(None) };
+    for segment in segments {
+        let next = match value.downcast::<PyDict>() {
+            Ok(d) => d.get_item(segment)?,
+            Err(_) => value.getattr(segment).ok(),
+        };
+        match next {
+            Some(v) => value = v,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(value))
+}
+
+/// Value at `path` in `state`; containers come back as read-only proxies, missing paths
+/// as `default`.
+pub fn read_path(py: Python, state: &State, path: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+    let Some(value) = lookup(py, state, path)? else {
+        return Ok(default.unwrap_or_else(|| py.None()));
+    };
+    if value.is_instance_of::<PyDict>() || value.is_instance_of::<PyList>() {
+        let proxy = crate::proxy::SupervisorProxy::new(py, value.unbind(), path.to_string(), true, None, false, crate::zones::CAP_READ);
+        return Ok(Py::new(py, proxy)?.into_any());
+    }
+    Ok(value.unbind())
+}
+
+/// Read handle on a pinned version: `view.domain.cfg.x`, `view.read("domain.a")`.
+#[pyclass(module = "theus_core")]
+pub struct PinnedView {
+    history: Arc<Mutex<VersionHistory>>,
+    state: Py<State>,
+    version: u64,
+    open: Mutex<bool>,
+}
+
+impl PinnedView {
+    fn release(&self) {
+        let mut open = self.open.relock();
+        if std::mem::replace(&mut *open, false) {
+            self.history.relock().unpin(self.version);
+        }
+    }
+}
+
+impl Drop for PinnedView {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[pymethods]
+impl PinnedView {
+    #[getter]
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    #[getter]
+    fn state(&self, py: Python) -> Py<State> {
+        self.state.clone_ref(py)
+    }
+
+    #[getter]
+    fn pinned(&self) -> bool {
+        *self.open.relock()
+    }
+
+    #[pyo3(signature = (path, default=None))]
+    fn read(&self, py: Python, path: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+        read_path(py, &self.state.bind(py).borrow(), path, default)
+    }
+
+    /// [v3.6] JSONPath-style query over this version (see query.rs).
+    #[pyo3(signature = (expr, with_paths=false))]
+    fn query(&self, py: Python, expr: &str, with_paths: bool) -> PyResult<PyObject> {
+        crate::query::Query::parse(expr)?.run(py, &self.state.bind(py).borrow(), with_paths)
+    }
+
+    /// Releases the pin (the view stays readable; its version may now be pruned).
+    fn unpin(&self) {
+        self.release();
+    }
+
+    fn __getattr__(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        let state = self.state.bind(py).borrow();
+        if name.starts_with('_') || (!state.data.contains_key(name) && name != "heavy") {
+            return Err(pyo3::exceptions::PyAttributeError::new_err(format!("'PinnedView' object has no attribute '{name}'")));
+        }
+        if name == "heavy" {
+            let heavy = PyDict::new_bound(py);
+            for (k, v) in &state.heavy {
+                heavy.set_item(k, v.as_ref())?;
+            }
+            let proxy = crate::proxy::SupervisorProxy::new(py, heavy.into_any().unbind(), "heavy".to_string(), true, None, false, crate::zones::CAP_READ);
+            return Ok(Py::new(py, proxy)?.into_any());
+        }
+        read_path(py, &state, name, None)
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&self, _args: &Bound<'_, pyo3::types::PyTuple>) {
+        self.release();
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PinnedView(version={}, pinned={})", self.version, *self.open.relock())
+    }
+}
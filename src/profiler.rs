@@ -0,0 +1,127 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+// [v3.6] Opt-in per-path access profiler (process-wide heat map).
+// Proxies record reads without knowing their engine, so the switch and the counts belong to
+// the process and are exposed as module functions, not engine methods.
+// Disabled cost is one relaxed atomic load per access; enabled, counts go to a
+// sharded map so concurrent readers rarely contend on the same lock.
+
+const SHARDS: usize = 16;
+
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default, Clone, Copy)]
+struct Counts {
+    reads: u64,
+    writes: u64,
+}
+
+static HEAT: LazyLock<[Mutex<HashMap<String, Counts>>; SHARDS]> =
+    LazyLock::new(|| std::array::from_fn(|_| Mutex::new(HashMap::new())));
+
+/// `domain.orders[3].price` / `domain.orders.3.price` -> `domain.orders.*.price`
+/// (list indices collapse so hot collections show up as one path).
+pub fn canonical_path(path: &str) -> String {
+    path.split(['.', '[', ']'])
+        .filter(|s| !s.is_empty())
+        .map(|s| if s.bytes().all(|b| b.is_ascii_digit()) { "*" } else { s })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+pub fn enabled() -> bool {
+    PROFILING.load(Ordering::Relaxed)
+}
+
+fn bump(path: &str, write: bool) {
+    let path = canonical_path(path);
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    #[allow(clippy::cast_possible_truncation)]
+    let shard = &HEAT[hasher.finish() as usize % SHARDS];
+    let mut map = shard.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let counts = map.entry(path).or_default();
+    if write {
+        counts.writes += 1;
+    } else {
+        counts.reads += 1;
+    }
+}
+
+pub fn record_read(path: &str) {
+    if enabled() {
+        bump(path, false);
+    }
+}
+
+pub fn record_write(path: &str) {
+    if enabled() {
+        bump(path, true);
+    }
+}
+
+/// Record a write for every leaf path of a nested update dict (`tx.update(data=...)`).
+pub fn record_update(prefix: &str, update: &Bound<PyDict>) {
+    if !enabled() {
+        return;
+    }
+    for (k, v) in update {
+        let Ok(key) = k.str() else { continue };
+        let path = if prefix.is_empty() { key.to_string() } else { format!("{prefix}.{key}") };
+        match v.downcast::<PyDict>() {
+            Ok(nested) if !nested.is_empty() => record_update(&path, nested),
+            _ => bump(&path, true),
+        }
+    }
+}
+
+/// [v3.6] Toggle path profiling for every engine in the process (off by default; counts
+/// survive toggling).
+#[pyfunction]
+pub fn set_profiling_enabled(enabled: bool) {
+    PROFILING.store(enabled, Ordering::Relaxed);
+}
+
+/// [v3.6] Drop all path counts.
+#[pyfunction]
+pub fn reset_profile() {
+    for shard in HEAT.iter() {
+        shard.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clear();
+    }
+}
+
+/// [v3.6] Hottest canonical paths first (reads + writes), as
+/// `[{path, reads, writes, total}, ...]`. `reset=True` clears the counts after reading.
+#[pyfunction]
+#[pyo3(signature = (top_n=None, reset=false))]
+pub fn profile_report(py: Python, top_n: Option<usize>, reset: bool) -> PyResult<PyObject> {
+    let mut rows: Vec<(String, Counts)> = HEAT.iter()
+        .flat_map(|shard| {
+            let map = shard.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+            map.iter().map(|(p, c)| (p.clone(), *c)).collect::<Vec<_>>()
+        })
+        .collect();
+    rows.sort_by(|(pa, a), (pb, b)| (b.reads + b.writes).cmp(&(a.reads + a.writes)).then_with(|| pa.cmp(pb)));
+    if reset {
+        reset_profile();
+    }
+    if let Some(n) = top_n {
+        rows.truncate(n);
+    }
+
+    let out = pyo3::types::PyList::empty_bound(py);
+    for (path, c) in rows {
+        let row = PyDict::new_bound(py);
+        row.set_item("path", path)?;
+        row.set_item("reads", c.reads)?;
+        row.set_item("writes", c.writes)?;
+        row.set_item("total", c.reads + c.writes)?;
+        out.append(row)?;
+    }
+    Ok(out.into_any().unbind())
+}
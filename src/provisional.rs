@@ -0,0 +1,82 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::sync::{Arc, Mutex};
+use crate::delta::DeltaEntry;
+use crate::locks::Relock;
+
+// [v3.6] Provisional reads for optimistic UIs. A transaction opened with `provisional=True`
+// exposes its uncommitted writes to `engine.peek_pending(path)`: proxy deltas and
+// `tx.update()` payloads that overlap `path`, as rows marked `"provisional": True`.
+// Nothing changes for commits - other transactions still read committed state only, and the
+// rows disappear once the transaction exits (committed or rolled back).
+// NOTE: Values are deep copies, so a UI cannot mutate the writer's shadows.
+
+/// What an open provisional transaction shares with the engine.
+pub struct Provisional {
+    pub actor: Option<String>,
+    pub delta_log: Arc<Mutex<Vec<DeltaEntry>>>,
+    pub pending_data: Py<PyDict>,
+}
+
+/// `a.b[c]` -> `["a", "b", "c"]`, the form delta paths are matched in.
+pub fn segments(path: &str) -> Vec<String> {
+    path.replace('[', ".").replace(']', "").split('.').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+/// `value` followed down `rest` (dict keys, then attributes); None if a step is missing.
+fn descend<'py>(value: &Bound<'py, PyAny>, rest: &[String]) -> Option<Bound<'py, PyAny>> {
+    let mut current = value.clone();
+    for key in rest {
+        current = match current.downcast::<PyDict>() {
+            Ok(d) => d.get_item(key).ok().flatten()?,
+            Err(_) => current.getattr(key.as_str()).ok()?,
+        };
+    }
+    Some(current)
+}
+
+impl Provisional {
+    pub fn clone_ref(&self, py: Python) -> Self {
+        Provisional { actor: self.actor.clone(), delta_log: self.delta_log.clone(), pending_data: self.pending_data.clone_ref(py) }
+    }
+
+    /// Appends the rows of this transaction that overlap `target` to `out`, in write order.
+    pub fn collect(&self, py: Python, tx_id: u64, target: &[String], out: &Bound<'_, PyList>) -> PyResult<()> {
+        let deepcopy = py.import_bound("copy")?.getattr("deepcopy")?;
+        let push = |path: &[String], value: &Bound<'_, PyAny>, op: &str| -> PyResult<()> {
+            let row = PyDict::new_bound(py);
+            row.set_item("tx_id", tx_id)?;
+            row.set_item("actor", &self.actor)?;
+            row.set_item("path", path.join("."))?;
+            row.set_item("op", op)?;
+            row.set_item("value", deepcopy.call1((value,))?)?;
+            row.set_item("provisional", true)?;
+            out.append(row)
+        };
+        // A write overlaps when it is at / under `target`, or replaces a parent holding it.
+        let visit = |path: Vec<String>, value: &Bound<'_, PyAny>, op: &str| -> PyResult<()> {
+            if path.len() >= target.len() {
+                if path.starts_with(target) {
+                    push(&path, value, op)?;
+                }
+            } else if target.starts_with(&path) {
+                if let Some(inner) = descend(value, &target[path.len()..]) {
+                    push(target, &inner, op)?;
+                }
+            }
+            Ok(())
+        };
+        let pending = self.pending_data.bind(py);
+        if !pending.is_empty() {
+            visit(Vec::new(), pending.as_any(), "update")?;
+        }
+        let entries: Vec<(String, String, PyObject)> = self.delta_log.relock().iter()
+            .filter_map(|e| e.value.as_ref().map(|v| (e.path.to_string(), e.op.to_string(), v.clone_ref(py))))
+            .collect();
+        for (path, op, value) in entries {
+            visit(segments(&path), value.bind(py), &op)?;
+        }
+        Ok(())
+    }
+}
+
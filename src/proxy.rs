@@ -1,6 +1,9 @@
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyTuple, PyAny, PyModule};
 use crate::zones::{CAP_APPEND, CAP_UPDATE, CAP_DELETE};
+use crate::errors::Denial;
+use crate::messages::{self, Message};
+use crate::locks::Relock;
 
 // use crate::engine::Transaction;
 
@@ -15,6 +18,30 @@ use crate::zones::{CAP_APPEND, CAP_UPDATE, CAP_DELETE};
 
 // SafePyRef Removed (Unused)
 
+fn capability_required(capability: &str, operation: &str, path: &str, caps: u8) -> Message {
+    messages::render(messages::CAPABILITY_REQUIRED, &[
+        ("capability", &capability), ("operation", &operation), ("path", &path), ("lens", &format!("{caps:04b}")),
+    ])
+}
+
+fn pure_write(path: &str) -> Message {
+    messages::render(messages::PURE_WRITE, &[("path", &path)])
+}
+
+fn no_transaction(path: &str) -> Message {
+    messages::render(messages::NO_TRANSACTION, &[("path", &path)])
+}
+
+/// [v3.6] Trace span for an access by the current transaction (no-op unless tracing).
+fn trace(py: Python, path: impl FnOnce() -> String, access: &'static str) -> crate::trace::Span {
+    let tx_id = || {
+        let tx = get_current_tx(py)?;
+        let tx = tx.bind(py).downcast::<crate::engine::Transaction>().ok()?.try_borrow().ok()?;
+        Some(tx.id)
+    };
+    crate::trace::span(tx_id, path, access)
+}
+
 /// `SupervisorProxy` - The Gatekeeper for Python object access
 /// 
 /// Unlike `FrozenDict` which returns copies, `SupervisorProxy` returns
@@ -46,6 +73,12 @@ pub struct SupervisorProxy {
     pub capabilities: u8,
 }
 
+/// [v3.6] Attribute names defined by the `SupervisorProxy` class (methods, getters).
+fn proxy_attributes(py: Python) -> PyResult<&'static std::collections::HashSet<String>> {
+    static ATTRIBUTES: pyo3::sync::GILOnceCell<std::collections::HashSet<String>> = pyo3::sync::GILOnceCell::new();
+    ATTRIBUTES.get_or_try_init(py, || py.get_type_bound::<SupervisorProxy>().dir()?.iter().map(|n| n.extract()).collect())
+}
+
 // Thread-local storage for active Transaction PyObject.
 // This avoids needing to import theus.guards during SupervisorProxy construction.
 thread_local! {
@@ -90,6 +123,146 @@ fn get_current_tx(py: Python) -> Option<PyObject> {
     }
 }
 
+/// [v3.6] Caps a break-glass grant of the current transaction's engine gives `path`, if any.
+fn glass_caps(py: Python, path: &str) -> Option<u8> {
+    if !crate::break_glass::any_open() {
+        return None;
+    }
+    let tx = get_current_tx(py)?;
+    let tx = tx.bind(py).downcast::<crate::engine::Transaction>().ok()?.try_borrow().ok()?;
+    tx.glass_caps(py, path)
+}
+
+/// [v3.6] `zones::path_physics`, widened by a break-glass grant (see `break_glass.rs`).
+fn physics(py: Python, path: &str) -> (crate::zones::ContextZone, u8) {
+    let (zone, caps) = crate::zones::path_physics(path);
+    (zone, glass_caps(py, path).unwrap_or(caps))
+}
+
+/// [v3.6] Replace the thread-local transaction, returning the previous one so dry runs
+/// (`ContextGuard.plan`) can restore it.
+pub(crate) fn swap_thread_tx(tx: Option<PyObject>) -> Option<PyObject> {
+    THREAD_LOCAL_TX.with(|cell| cell.replace(tx))
+}
+
+/// [v3.6] `" Did you mean 'x'?"` when the registered schema declares a field close to `name`
+/// at `path` (empty otherwise).
+fn did_you_mean(py: Python, path: &str, name: &str) -> String {
+    let Some(tx) = get_current_tx(py) else { return String::new() };
+    let Ok(tx) = tx.bind(py).downcast::<crate::engine::Transaction>().map(|t| t.borrow().schema_fields.clone()) else { return String::new() };
+    tx.suggest(path, name).map_or_else(String::new, |f| format!(" Did you mean '{f}'?"))
+}
+
+/// [v3.6] Runs the current transaction's middleware chain for `op` on `value` at `path`
+/// (unchanged when no middleware is registered or outside a transaction).
+fn intercept(py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+    let pipeline = get_current_tx(py).and_then(|tx| {
+        let tx = tx.bind(py).downcast::<crate::engine::Transaction>().ok()?.try_borrow().ok()?;
+        (!tx.middleware.is_empty()).then(|| tx.middleware.clone())
+    });
+    match pipeline {
+        Some(pipeline) => pipeline.run(py, path, value, op),
+        None => Ok(value),
+    }
+}
+
+/// [v3.6] Leaf read through the middleware chain (methods are returned as-is).
+fn read_leaf(py: Python, path: &str, value: PyObject) -> PyResult<PyObject> {
+    if value.bind(py).is_callable() {
+        return Ok(value);
+    }
+    intercept(py, path, value, "get")
+}
+
+/// [v3.6] Write gate: fail fast on maintenance mode or once the active transaction is cancelled.
+/// NOTE: The thread-local tx may outlive its `with` block, so closed transactions are ignored.
+fn ensure_tx_active(py: Python) -> PyResult<()> {
+    if let Some(tx) = get_current_tx(py) {
+        if let Ok(tx) = tx.bind(py).downcast::<crate::engine::Transaction>() {
+            let tx = tx.borrow();
+            if !tx.closed.load(std::sync::atomic::Ordering::SeqCst) {
+                tx.ensure_writable(py)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// [v3.6] Selective `SupervisorProxy.to_dict()` export.
+pub(crate) struct ExportFilter {
+    zones: Option<Vec<crate::zones::ContextZone>>,
+    include_private: bool,
+    max_depth: Option<usize>,
+}
+
+impl ExportFilter {
+    /// Every zone, with Private-zone and `_underscore` keys dropped.
+    pub(crate) fn redacted() -> Self {
+        ExportFilter { zones: None, include_private: false, max_depth: None }
+    }
+
+    pub(crate) fn keeps(&self, path: &str, key: &str) -> bool {
+        let zone = crate::zones::resolve_zone(path);
+        if !self.include_private && (key.starts_with('_') || zone == crate::zones::ContextZone::Private) {
+            return false;
+        }
+        self.zones.as_ref().is_none_or(|zones| zones.contains(&zone))
+    }
+
+    /// Copy of `value` without filtered keys; `None` for a container nested past `max_depth`.
+    pub(crate) fn export(&self, value: &Bound<'_, PyAny>, path: &str, depth: usize) -> PyResult<Option<PyObject>> {
+        let py = value.py();
+        if let Ok(dict) = value.downcast::<PyDict>() {
+            if self.max_depth.is_some_and(|max| depth > max) {
+                return Ok(None);
+            }
+            let out = PyDict::new_bound(py);
+            for (k, v) in dict.iter() {
+                let key = k.str()?.to_string();
+                let child = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                if !self.keeps(&child, &key) {
+                    continue;
+                }
+                if let Some(v) = self.export(&v, &child, depth + 1)? {
+                    out.set_item(k, v)?;
+                }
+            }
+            return Ok(Some(out.into_any().unbind()));
+        }
+        if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+            if self.max_depth.is_some_and(|max| depth > max) {
+                return Ok(None);
+            }
+            let mut items = Vec::new();
+            for item in value.iter()? {
+                if let Some(v) = self.export(&item?, path, depth + 1)? {
+                    items.push(v);
+                }
+            }
+            return Ok(Some(PyList::new_bound(py, items).into_any().unbind()));
+        }
+        if value.hasattr("model_dump")? {
+            return self.export(&value.call_method0("model_dump")?, path, depth);
+        }
+        Ok(Some(value.clone().unbind()))
+    }
+}
+
+impl crate::introspect::Access for SupervisorProxy {
+    fn access(&self, path: &str) -> Option<bool> {
+        let zone = crate::zones::resolve_zone(path);
+        let caps = if (self.capabilities & crate::zones::CAP_ADMIN) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
+            crate::zones::ADMIN_CAPS
+        } else {
+            self.capabilities & crate::introspect::physics(path)
+        };
+        if caps & crate::zones::CAP_READ == 0 {
+            return None;
+        }
+        Some(!self.read_only && self.is_mutable && crate::introspect::can_mutate(caps))
+    }
+}
+
 #[pymethods]
 impl SupervisorProxy {
     #[new]
@@ -125,8 +298,15 @@ impl SupervisorProxy {
     /// [RFC-001 §10] Intercept ALL attribute access at C level.
     /// __getattribute__ runs BEFORE `getset_descriptors` (including __dict__).
     /// Without this, `PyO3`'s auto-generated __dict__ descriptor bypasses __getattr__.
-    fn __getattribute__(slf: &Bound<'_, Self>, name: &str) -> PyResult<PyObject> {
-        if name == "__dict__" {
+    fn __getattribute__(slf: &Bound<'_, Self>, name: &Bound<'_, pyo3::types::PyString>) -> PyResult<PyObject> {
+        let py = slf.py();
+        let name_str = name.to_str()?;
+        // [v3.6] Fast path: a public name the proxy class does not define is a data field, so
+        // skip the generic lookup (and the AttributeError it would raise).
+        if !name_str.starts_with('_') && !proxy_attributes(py)?.contains(name_str) {
+            return slf.borrow().__getattr__(py, name_str);
+        }
+        if name_str == "__dict__" {
             // NOTE: Return empty dict instead of PermissionError.
             // Blocking with PermissionError breaks deepcopy() and any internal Python 
             // mechanism that probes __dict__ (AdminTransaction, pickle, etc.).
@@ -140,12 +320,10 @@ impl SupervisorProxy {
         // NOTE: Delegate to default tp_getattro for all other attributes.
         // This preserves normal attribute resolution (methods, properties, etc.)
         // and falls through to __getattr__ for dynamic lookups.
-        let py = slf.py();
-        let name_obj = pyo3::types::PyString::new(py, name);
         unsafe {
             let result = pyo3::ffi::PyObject_GenericGetAttr(
                 slf.as_ptr(),
-                name_obj.as_ptr(),
+                name.as_ptr(),
             );
             if result.is_null() {
                 Err(pyo3::PyErr::fetch(py))
@@ -158,6 +336,7 @@ impl SupervisorProxy {
     /// Get attribute - Returns original object (or nested Proxy)
     /// v3.1: Supports Dict dot-access (d.key) fallback
     fn __getattr__(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        let _timer = crate::metrics::ProxyReadTimer::start();
         // Skip internal attributes, but intercept __dict__ with PermissionError
         // [RFC-001 §10] Block __dict__ to prevent bypassing Zone Physics
         if name == "__dict__" {
@@ -176,21 +355,22 @@ impl SupervisorProxy {
         } else {
             format!("{}.{}", self.path, name)
         };
+        crate::profiler::record_read(&nested_path);
+        let _trace = trace(py, || nested_path.clone(), "read");
 
         // [RFC-001] Check field-specific Zone Physics (Read Access)
-        let zone = crate::zones::resolve_zone(&nested_path);
-        let override_caps = crate::zones::get_physics_override(&nested_path);
-        let zone_physics = override_caps.unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
+        let (zone, zone_physics) = physics(py, &nested_path);
         let mut access_caps = self.capabilities & zone_physics;
         
-        if (self.capabilities & 16) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
-            access_caps = 31u8;
+        if (self.capabilities & crate::zones::CAP_ADMIN) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
+            access_caps = crate::zones::ADMIN_CAPS;
         }
 
         if (access_caps & crate::zones::CAP_READ) == 0 {
             // [RFC-001 Handbook §1.1] Return None silently for non-admin reading PRIVATE 
             return Ok(py.None());
         }
+        let op = crate::reentrancy::read(py, || nested_path.clone())?;
 
         // 1. Try generic getattr (methods, object fields)
         let val_result = self.inner.getattr(py, name);
@@ -205,8 +385,8 @@ impl SupervisorProxy {
                             // If key missing, return original error but enriched
                             return Err(pyo3::exceptions::PyAttributeError::new_err(
                                 format!(
-                                    "'SupervisorProxy[dict]' object has no attribute '{}'. (Hint: Key '{}' missing in wrapped dict at path '{}')", 
-                                    name, name, self.path
+                                    "'SupervisorProxy[dict]' object has no attribute '{}'. (Hint: Key '{}' missing in wrapped dict at path '{}'){}", 
+                                    name, name, self.path, did_you_mean(py, &self.path, name)
                                 )
                             ));
                         }, 
@@ -214,20 +394,21 @@ impl SupervisorProxy {
                 } else {
                      return Err(pyo3::exceptions::PyAttributeError::new_err(
                         format!(
-                            "'SupervisorProxy[{}]' object has no attribute '{}'. (Path: '{}')", 
-                            self.inner.bind(py).get_type().name()?, name, self.path
+                            "'SupervisorProxy[{}]' object has no attribute '{}'. (Path: '{}'){}", 
+                            self.inner.bind(py).get_type().name()?, name, self.path, did_you_mean(py, &self.path, name)
                         )
                     ));
                 }
             }
         };
 
-        // Build nested path
-        let nested_path = if self.path.is_empty() {
-            name.to_string()
-        } else {
-            format!("{}.{}", self.path, name)
-        };
+        if op.nested {
+            return self.raw_child(py, val, nested_path);
+        }
+
+        // [v3.6] Spilled / compressed values fault in; inside a shadow tree the loaded copy replaces the
+        // placeholder so in-place mutations are tracked like any other shadow write.
+        let val = self.fault_in(py, val, &name.into_py(py))?;
 
         // Wrap nested dicts/objects in Proxy for continued tracking
         let is_dict = val.bind(py).is_instance_of::<PyDict>();
@@ -261,29 +442,19 @@ impl SupervisorProxy {
             };
 
             // Recalculate capabilities for nested path
-            let child_caps = if (self.capabilities & 16) != 0 {
-                31u8 // Preserve Admin Bypass
+            let child_caps = if (self.capabilities & crate::zones::CAP_ADMIN) != 0 {
+                crate::zones::ADMIN_CAPS // Preserve Admin Bypass
             } else {
-                let zone = crate::zones::resolve_zone(&nested_path);
-                let override_caps = crate::zones::get_physics_override(&nested_path);
-                let zone_physics = override_caps.unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
+                let (_, zone_physics) = physics(py, &nested_path);
                 self.capabilities & zone_physics
             };
 
             // [RFC-001] Feature 6: Block Direct Context __dict__ Mutation (Attack Surface §10)
             let is_read_only = self.read_only || name == "__dict__";
 
-            Ok(SupervisorProxy::new(
-                py,
-                val_shadow,
-                nested_path,
-                is_read_only,
-                tx_for_child,
-                is_child_shadow,
-                child_caps,
-            ).into_py(py))
+            pooled_proxy(py, val_shadow, nested_path, is_read_only, tx_for_child, is_child_shadow, child_caps)
         } else {
-            Ok(val)
+            read_leaf(py, &nested_path, val)
         }
     }
 
@@ -295,10 +466,13 @@ impl SupervisorProxy {
     /// Set attribute - Intercept for logging and permission check
     /// v3.1: Supports Dict dot-access (d.key = val -> d[`key`] = val)
     fn __setattr__(&self, py: Python, name: &str, value: PyObject) -> PyResult<()> {
+        let _trace = trace(py, || if self.path.is_empty() { name.to_string() } else { format!("{}.{}", self.path, name) }, "write");
+        let _op = crate::reentrancy::write(py, || if self.path.is_empty() { name.to_string() } else { format!("{}.{}", self.path, name) })?;
+        ensure_tx_active(py)?;
         // Block writes on read-only proxy (PURE processes)
         if self.read_only {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}.{}'", self.path, name)
+            return Err(crate::testing::denied(Denial::new(&format!("{}.{}", self.path, name), "pure").needs(CAP_UPDATE, self.capabilities),
+                pure_write(&format!("{}.{}", self.path, name))
             ));
         }
 
@@ -309,22 +483,21 @@ impl SupervisorProxy {
             format!("{}.{}", self.path, name)
         };
         
-        let zone = crate::zones::resolve_zone(&full_path);
-        let override_caps = crate::zones::get_physics_override(&full_path);
-        let zone_physics = override_caps.unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
+        let (zone, zone_physics) = physics(py, &full_path);
         let mut mutation_caps = self.capabilities & zone_physics;
         
         // Admin exception flag is bit 4 (16).
-        if (self.capabilities & 16) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
-            mutation_caps = 31u8;
+        if (self.capabilities & crate::zones::CAP_ADMIN) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
+            mutation_caps = crate::zones::ADMIN_CAPS;
         }
 
         if (mutation_caps & crate::zones::CAP_UPDATE) == 0 {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for '{full_path}'. (Current Lens: {mutation_caps:04b})")
+             return Err(crate::testing::denied(Denial::new(&full_path, "zone_physics").needs(crate::zones::CAP_UPDATE, mutation_caps),
+                capability_required("UPDATE", "attribute write", &full_path, mutation_caps)
             ));
         }
 
+        let value = intercept(py, &full_path, value, "set")?;
         let is_dict = self.inner.bind(py).is_instance_of::<PyDict>();
 
         // Log mutation via contextvars Transaction (not stored in self)
@@ -345,10 +518,8 @@ impl SupervisorProxy {
             // Call transaction.log_delta(path, old, new)
             match tx_obj.bind(py).getattr("log_delta") {
                 Ok(tx_bound) => {
-                     if let Err(e) = tx_bound.call1((full_path, old_val, value.clone_ref(py))) {
-                         eprintln!("ERROR: log_delta failed!");
-                         e.print(py);
-                     }
+                     // [v3.6] Propagate transaction limit breaches (TransactionLimitError)
+                     tx_bound.call1((full_path, old_val, value.clone_ref(py)))?;
                 },
                 Err(e) => {
                     eprintln!("ERROR: Transaction object missing log_delta!");
@@ -368,35 +539,43 @@ impl SupervisorProxy {
             }
             Ok(())
         } else {
-            Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Supervisor blocked mutation to '{}.{}': No active transaction found. State is Immutable outside processes.", self.path, name)
+            Err(crate::testing::denied(Denial::new(&format!("{}.{}", self.path, name), "no_transaction"),
+                no_transaction(&format!("{}.{}", self.path, name))
             ))
         }
     }
 
     #[allow(clippy::needless_pass_by_value)]
     fn __getitem__(&self, py: Python, key: PyObject) -> PyResult<PyObject> {
+        let _timer = crate::metrics::ProxyReadTimer::start();
         let key_str = key.bind(py).str()?.to_string();
         let nested_path = if self.path.is_empty() {
             key_str.clone()
         } else {
             format!("{}[{}]", self.path, key_str)
         };
+        crate::profiler::record_read(&nested_path);
+        let _trace = trace(py, || nested_path.clone(), "read");
 
         // [RFC-001] Check field-specific Zone Physics (Read Access)
         let zone = crate::zones::resolve_zone(&nested_path);
-        let zone_physics = crate::zones::get_zone_physics(&zone);
+        let zone_physics = glass_caps(py, &nested_path).unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
         let mut access_caps = self.capabilities & zone_physics;
         
-        if (self.capabilities & 16) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
-            access_caps = 31u8;
+        if (self.capabilities & crate::zones::CAP_ADMIN) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
+            access_caps = crate::zones::ADMIN_CAPS;
         }
 
         if (access_caps & crate::zones::CAP_READ) == 0 {
              return Ok(py.None());
         }
+        let op = crate::reentrancy::read(py, || nested_path.clone())?;
 
         let val = self.inner.call_method1(py, "__getitem__", (key.clone_ref(py),))?;
+        if op.nested {
+            return self.raw_child(py, val, nested_path);
+        }
+        let val = self.fault_in(py, val, &key)?;
 
         // Check if value is a container (Dict/List/Object)
 
@@ -432,35 +611,31 @@ impl SupervisorProxy {
             };
 
             // Recalculate capabilities for nested path
-            let child_caps = if (self.capabilities & 16) != 0 {
-                31u8 // Preserve Admin Bypass
+            let child_caps = if (self.capabilities & crate::zones::CAP_ADMIN) != 0 {
+                crate::zones::ADMIN_CAPS // Preserve Admin Bypass
             } else {
-                let zone = crate::zones::resolve_zone(&nested_path);
-                let override_caps = crate::zones::get_physics_override(&nested_path);
-                let zone_physics = override_caps.unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
+                let (_, zone_physics) = physics(py, &nested_path);
                 self.capabilities & zone_physics
             };
 
-            let proxy = SupervisorProxy::new(
-                py,
-                val_shadow,
-                nested_path,
-                self.read_only,
-                tx_for_child,
-                is_child_shadow,
-                child_caps,
-            );
-            Ok(Py::new(py, proxy)?.into_any())
+            pooled_proxy(py, val_shadow, nested_path, self.read_only, tx_for_child, is_child_shadow, child_caps)
         } else {
-            Ok(val)
+            read_leaf(py, &nested_path, val)
         }
     }
 
     /// Set item - For dict-like access ctx.domain[`key`] = value
     fn __setitem__(&self, py: Python, key: PyObject, value: PyObject) -> PyResult<()> {
+        let item_path = || {
+            let key = key.bind(py).str().map(|k| k.to_string()).unwrap_or_default();
+            if self.path.is_empty() { key } else { format!("{}[{}]", self.path, key) }
+        };
+        let _trace = trace(py, item_path, "write");
+        let _op = crate::reentrancy::write(py, item_path)?;
+        ensure_tx_active(py)?;
         if self.read_only {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(
-                "PURE process cannot write"
+            return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_UPDATE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
@@ -472,25 +647,23 @@ impl SupervisorProxy {
         };
 
         // [RFC-001] Check field-specific Zone Physics
-        let zone = crate::zones::resolve_zone(&full_path_tmp);
-        let override_caps = crate::zones::get_physics_override(&full_path_tmp);
-        let zone_physics = override_caps.unwrap_or_else(|| crate::zones::get_zone_physics(&zone));
+        let (zone, zone_physics) = physics(py, &full_path_tmp);
         let mut mutation_caps = self.capabilities & zone_physics;
         
-        if (self.capabilities & 16) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
-            mutation_caps = 31u8;
+        if (self.capabilities & crate::zones::CAP_ADMIN) != 0 && !crate::zones::is_absolute_ceiling(&zone) {
+            mutation_caps = crate::zones::ADMIN_CAPS;
         }
 
         if (mutation_caps & CAP_UPDATE) == 0 {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for item assignment at '{full_path_tmp}'. (Current Lens: {mutation_caps:04b})")
+             return Err(crate::testing::denied(Denial::new(&full_path_tmp, "zone_physics").needs(CAP_UPDATE, mutation_caps),
+                capability_required("UPDATE", "item assignment", &full_path_tmp, mutation_caps)
             ));
         }
 
         // [v3.1.3 SECURITY FIX] Block mutations if not mutable!
         if !self.is_mutable {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Supervisor blocked mutation to path '{}': No active transaction found.", self.path)
+             return Err(crate::testing::denied(Denial::new(&self.path, "no_transaction"),
+                no_transaction(&self.path)
             ));
         }
 
@@ -503,10 +676,11 @@ impl SupervisorProxy {
         };
         
         let old_val = self.inner.call_method1(py, "get", (key.clone_ref(py),)).ok();
+        let value = intercept(py, &full_path, value, "set")?;
         
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((full_path, old_val, value.clone_ref(py)));
+                tx_bound.call1((full_path, old_val, value.clone_ref(py)))?;
             }
         }
 
@@ -525,6 +699,47 @@ impl SupervisorProxy {
         self.__repr__(py)
     }
 
+    /// [v3.6] `dir(proxy)`: proxy methods plus the readable keys/attributes of the target.
+    fn __dir__(slf: &Bound<'_, Self>) -> PyResult<Vec<String>> {
+        let proxy = slf.borrow();
+        let fields = crate::introspect::visible_names(proxy.inner.bind(slf.py()), &proxy.path, false, &*proxy)?;
+        crate::introspect::dir_with(slf.as_any(), fields)
+    }
+
+    /// [v3.6] Readable subtree as `{path: {zone, access, type, len?}}`, at most `max_depth`
+    /// levels and `max_keys` children per level. `narrow(path, writable) -> bool | None`
+    /// can restrict it further.
+    #[pyo3(signature = (max_depth=3, max_keys=50, narrow=None))]
+    fn describe(&self, py: Python, max_depth: usize, max_keys: usize, narrow: Option<&Bound<'_, PyAny>>) -> PyResult<PyObject> {
+        let access = crate::introspect::Narrowed { base: self, narrow };
+        crate::introspect::describe(py, self.inner.bind(py), &self.path, false, max_depth, max_keys, &access)
+    }
+
+    /// [v3.6] Readable subtree drawn as indented text (or HTML) with zone badges; leaf
+    /// values are truncated and at most `max_nodes` entries are shown.
+    #[pyo3(signature = (html=false, max_depth=3, max_keys=20, max_nodes=200, narrow=None))]
+    fn render_tree(&self, py: Python, html: bool, max_depth: usize, max_keys: usize, max_nodes: usize, narrow: Option<&Bound<'_, PyAny>>) -> PyResult<String> {
+        let title = format!(
+            "SupervisorProxy[{}] '{}' ({})",
+            self.inner.bind(py).get_type().name()?, self.path, if self.read_only || !self.is_mutable { "ro" } else { "rw" }
+        );
+        let limits = crate::introspect::Limits { max_depth, max_keys, max_nodes };
+        let access = crate::introspect::Narrowed { base: self, narrow };
+        crate::introspect::render(self.inner.bind(py), &self.path, false, &title, html, &limits, &access)
+    }
+
+    /// [v3.6] Jupyter rich display.
+    fn _repr_html_(&self, py: Python) -> PyResult<String> {
+        self.render_tree(py, true, 3, 20, 200, None)
+    }
+
+    /// [v3.6] `IPython` pretty printer.
+    fn _repr_pretty_(&self, py: Python, p: &Bound<'_, PyAny>, cycle: bool) -> PyResult<()> {
+        let text = if cycle { self.__repr__(py)? } else { self.render_tree(py, false, 3, 20, 200, None)? };
+        p.call_method1("text", (text,))?;
+        Ok(())
+    }
+
     /// Helper for users confused by type checks
     /// "isinstance(proxy, dict)" fails, so we provide this hint.
     #[allow(clippy::unused_self)]
@@ -543,124 +758,148 @@ impl SupervisorProxy {
     }
 
     /// Conversion to dict (Delegates to target or returns None)
-    fn to_dict(&self, py: Python) -> PyResult<PyObject> {
-        let inner = self.inner.bind(py);
-        if inner.hasattr("model_dump")? {
-            inner.call_method0("model_dump").map(pyo3::Bound::unbind)
-        } else if inner.hasattr("dict")? {
-            inner.call_method0("dict").map(pyo3::Bound::unbind)
-        } else if inner.hasattr("to_dict")? {
-            inner.call_method0("to_dict").map(pyo3::Bound::unbind)
-        } else if inner.is_instance_of::<PyDict>() {
-            // It is already a dict, but target is PyAny. Return clone as dict.
-            // Actually, usually we want a copy.
-            inner.call_method0("copy").map(pyo3::Bound::unbind)
-        } else {
-             Err(pyo3::exceptions::PyAttributeError::new_err("Wrapped object has no to_dict/model_dump"))
+    /// [v3.6] Any keyword switches to a selective export walked here: only keys whose full
+    /// path resolves to one of `zones` (names or `ContextZone`) are kept, `internal_*` and
+    /// `_underscore` keys are dropped unless `include_private`, and containers nested deeper
+    /// than `max_depth` levels are omitted.
+    #[pyo3(signature = (*, zones=None, include_private=None, max_depth=None))]
+    fn to_dict(&self, py: Python, zones: Option<Vec<Bound<'_, PyAny>>>, include_private: Option<bool>, max_depth: Option<usize>) -> PyResult<PyObject> {
+        if zones.is_some() || include_private.is_some() || max_depth.is_some() {
+            let filter = ExportFilter {
+                zones: zones.map(|z| z.iter().map(crate::zones::zone_arg).collect::<PyResult<Vec<_>>>()).transpose()?,
+                include_private: include_private.unwrap_or(false),
+                max_depth,
+            };
+            let root = self.plain_dict(py)?;
+            return filter.export(root.bind(py), &self.path, 0).map(|v| v.unwrap_or_else(|| PyDict::new_bound(py).into_any().unbind()));
         }
+        self.plain_dict(py)
     }
 
     // === List Methods (Guarded) ===
 
     fn append(&self, py: Python, item: PyObject) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_APPEND == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: APPEND capability required for .append() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_APPEND, self.capabilities), capability_required("APPEND", ".append()", &self.path, self.capabilities)));
         }
+        let item = intercept(py, &self.path, item, "append")?;
         self.inner.call_method1(py, "append", (item,))?;
         
         // Log Delta (Explicit SET for engine compatibility)
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn extend(&self, py: Python, iterable: PyObject) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_APPEND == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: APPEND capability required for .extend() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_APPEND, self.capabilities), capability_required("APPEND", ".extend()", &self.path, self.capabilities)));
         }
         self.inner.call_method1(py, "extend", (iterable,))?;
         
         // Log Delta
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn insert(&self, py: Python, index: PyObject, item: PyObject) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_APPEND == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: APPEND capability required for .insert() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_APPEND, self.capabilities), capability_required("APPEND", ".insert()", &self.path, self.capabilities)));
         }
+        let item = intercept(py, &self.path, item, "insert")?;
         self.inner.call_method1(py, "insert", (index, item))?;
         
         // Log Delta
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn remove(&self, py: Python, value: PyObject) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_DELETE == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: DELETE capability required for .remove() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_DELETE, self.capabilities), capability_required("DELETE", ".remove()", &self.path, self.capabilities)));
         }
         self.inner.call_method1(py, "remove", (value,))?;
         
         // Log Delta
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn sort(&self, py: Python, kwargs: Option<&Bound<PyDict>>) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_UPDATE == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: UPDATE capability required for .sort() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_UPDATE, self.capabilities), capability_required("UPDATE", ".sort()", &self.path, self.capabilities)));
         }
         self.inner.call_method(py, "sort", (), kwargs)?;
         
         // Log Delta
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn reverse(&self, py: Python) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.capabilities & CAP_UPDATE == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: UPDATE capability required for .reverse() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_UPDATE, self.capabilities), capability_required("UPDATE", ".reverse()", &self.path, self.capabilities)));
         }
         self.inner.call_method0(py, "reverse")?;
         
         // Log Delta
         if let Some(tx_obj) = get_current_tx(py) {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
             }
         }
         Ok(())
     }
 
     fn clear(&self, py: Python) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.read_only {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}'", self.path)
+             return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_DELETE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
         if (self.capabilities & CAP_DELETE) == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(format!("Permission Denied: DELETE capability required for .clear() at '{}'", self.path)));
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_DELETE, self.capabilities), capability_required("DELETE", ".clear()", &self.path, self.capabilities)));
         }
 
         let is_list = self.inner.bind(py).is_instance_of::<PyList>();
@@ -673,11 +912,11 @@ impl SupervisorProxy {
             if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
                 if is_list {
                     // For lists, log whole empty list
-                    let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                    tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
                 } else {
                     // For dicts, we could log all keys being removed, or just use the Shadow Inference in commit.
                     // But to be safe and explicit:
-                    let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                    tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
                 }
             }
         }
@@ -689,7 +928,7 @@ impl SupervisorProxy {
     /// Expose internals as dict for Pydantic/Standard Library compatibility
     #[getter]
     fn __dict__(&self, py: Python) -> PyResult<PyObject> {
-        self.to_dict(py)
+        self.plain_dict(py)
     }
 
     // === Mapping Protocol Implementation ===
@@ -714,9 +953,10 @@ impl SupervisorProxy {
     #[allow(clippy::needless_pass_by_value)]
     fn get(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
         // Safe get that wraps result
+        let op = crate::reentrancy::read(py, || self.path.clone())?;
         let val_res = self.inner.call_method1(py, "get", (key.clone_ref(py), default));
         match val_res {
-            Ok(val) => self.wrap_result(py, key.bind(py).str()?.to_string(), val),
+            Ok(val) => self.wrap_or_raw(py, op.nested, key.bind(py).str()?.to_string(), val),
             Err(e) => Err(e),
         }
     }
@@ -726,6 +966,7 @@ impl SupervisorProxy {
     }
 
     fn values(&self, py: Python) -> PyResult<PyObject> {
+        let op = crate::reentrancy::read(py, || self.path.clone())?;
         let values_view = self.inner.call_method0(py, "values")?;
         // Robustness: Convert view to list via builtins to handle any iterable safely
         let builtins = py.import_bound("builtins")?;
@@ -734,13 +975,14 @@ impl SupervisorProxy {
         
         let mut wrapped_list = Vec::new();
         for item in values_py_list.iter() {
-             let wrapped = self.wrap_result(py, "?".to_string(), item.unbind())?;
+             let wrapped = self.wrap_or_raw(py, op.nested, "?".to_string(), item.unbind())?;
              wrapped_list.push(wrapped);
         }
         Ok(PyList::new_bound(py, wrapped_list).into())
     }
 
     fn items(&self, py: Python) -> PyResult<PyObject> {
+        let op = crate::reentrancy::read(py, || self.path.clone())?;
         let items_view = self.inner.call_method0(py, "items")?;
         // Robustness: Convert view to list via builtins
         let builtins = py.import_bound("builtins")?;
@@ -756,7 +998,7 @@ impl SupervisorProxy {
                      let k = tuple.get_item(0)?;
                      let v = tuple.get_item(1)?;
                      let k_str = k.str()?.to_string();
-                     let wrapped_v = self.wrap_result(py, k_str, v.unbind())?;
+                     let wrapped_v = self.wrap_or_raw(py, op.nested, k_str, v.unbind())?;
                      
                      // Safe Tuple Creation
                      let elements = vec![k.unbind(), wrapped_v];
@@ -772,9 +1014,12 @@ impl SupervisorProxy {
 
     #[allow(clippy::needless_pass_by_value)]
     fn update(&self, py: Python, other: Option<PyObject>, kwargs: Option<PyObject>) -> PyResult<()> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.read_only {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}'", self.path)
+             return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_UPDATE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
@@ -797,20 +1042,23 @@ impl SupervisorProxy {
         
         // [RFC-001] Check UPDATE Capability
         if (self.capabilities & CAP_UPDATE) == 0 {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for .update() at '{}'. (Current Lens: {:04b})", self.path, self.capabilities)
+             return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_UPDATE, self.capabilities),
+                capability_required("UPDATE", ".update()", &self.path, self.capabilities)
             ));
         }
 
         // 2. Iterate and log each change
         if let Some(tx_obj) = get_current_tx(py) {
-             for (k, v) in updates_dict.iter() {
+             let items: Vec<(Bound<PyAny>, Bound<PyAny>)> = updates_dict.iter().collect();
+             for (k, v) in items {
                  let key_str = k.str()?.to_string();
                  let full_path = if self.path.is_empty() {
                     key_str.clone()
                  } else {
                     format!("{}.{}", self.path, key_str)
                  };
+                 let v = intercept(py, &full_path, v.unbind(), "set")?;
+                 updates_dict.set_item(&k, &v)?;
 
                  // Get old value
                  // Get old value
@@ -818,7 +1066,7 @@ impl SupervisorProxy {
                  
                  // Log delta
                  if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                    let _ = tx_bound.call1((full_path, old_val, v));
+                    tx_bound.call1((full_path, old_val, v))?;
                  }
              }
         }
@@ -830,15 +1078,18 @@ impl SupervisorProxy {
 
     #[pyo3(signature = (key_or_index=None, default=None))]
     fn pop(&self, py: Python, key_or_index: Option<PyObject>, default: Option<PyObject>) -> PyResult<PyObject> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.read_only {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}'", self.path)
+            return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_DELETE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
         if (self.capabilities & CAP_DELETE) == 0 {
-            return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: DELETE capability required for .pop() at '{}'. (Current Lens: {:04b})", self.path, self.capabilities)
+            return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_DELETE, self.capabilities),
+                capability_required("DELETE", ".pop()", &self.path, self.capabilities)
             ));
         }
 
@@ -849,7 +1100,7 @@ impl SupervisorProxy {
             if is_list {
                 // For lists, log the whole list path since indices shift
                 if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                    let _ = tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)));
+                    tx_bound.call1((self.path.clone(), py.None(), self.inner.clone_ref(py)))?;
                 }
             } else if let Some(ref koi) = key_or_index {
                 // For dicts, log specific key
@@ -863,7 +1114,7 @@ impl SupervisorProxy {
                 if self.inner.call_method1(py, "__contains__", (koi.clone_ref(py),))?.extract(py)? {
                      let old_val = self.inner.call_method1(py, "get", (koi.clone_ref(py),)).ok();
                      if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                        let _ = tx_bound.call1((full_path, old_val, py.None()));
+                        tx_bound.call1((full_path, old_val, py.None()))?;
                      }
                 }
             }
@@ -881,15 +1132,18 @@ impl SupervisorProxy {
     }
 
     fn popitem(&self, py: Python) -> PyResult<PyObject> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.read_only {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}'", self.path)
+             return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_DELETE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
         if (self.capabilities & CAP_DELETE) == 0 {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: DELETE capability required for .popitem() at '{}'. (Current Lens: {:04b})", self.path, self.capabilities)
+             return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_DELETE, self.capabilities),
+                capability_required("DELETE", ".popitem()", &self.path, self.capabilities)
             ));
         }
 
@@ -915,7 +1169,7 @@ impl SupervisorProxy {
 
                     if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
                         // Log deletion: old=v, new=None
-                        let _ = tx_bound.call1((full_path, v, py.None())); 
+                        tx_bound.call1((full_path, v, py.None()))?; 
                     }
                  }
              }
@@ -927,15 +1181,18 @@ impl SupervisorProxy {
 
     #[allow(clippy::needless_pass_by_value)]
     fn setdefault(&self, py: Python, key: PyObject, default: Option<PyObject>) -> PyResult<PyObject> {
+        let _trace = trace(py, || self.path.clone(), "write");
+        let _op = crate::reentrancy::write(py, || self.path.clone())?;
+        ensure_tx_active(py)?;
         if self.read_only {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("PURE process cannot write to '{}'", self.path)
+             return Err(crate::testing::denied(Denial::new(&self.path, "pure").needs(CAP_UPDATE, self.capabilities),
+                pure_write(&self.path)
             ));
         }
 
         if (self.capabilities & CAP_UPDATE) == 0 {
-             return Err(pyo3::exceptions::PyPermissionError::new_err(
-                format!("Permission Denied: UPDATE capability required for .setdefault() at '{}'. (Current Lens: {:04b})", self.path, self.capabilities)
+             return Err(crate::testing::denied(Denial::new(&self.path, "capability").needs(CAP_UPDATE, self.capabilities),
+                capability_required("UPDATE", ".setdefault()", &self.path, self.capabilities)
             ));
         }
         
@@ -955,7 +1212,7 @@ impl SupervisorProxy {
                 let default_val = default.as_ref().map(|o| o.clone_ref(py)).unwrap_or(py.None());
                 
                 if let Ok(tx_bound) = tx_obj.bind(py).getattr("log_delta") {
-                    let _ = tx_bound.call1((full_path, py.None(), default_val));
+                    tx_bound.call1((full_path, py.None(), default_val))?;
                 }
              }
         }
@@ -967,6 +1224,7 @@ impl SupervisorProxy {
         self.wrap_result(py, key_str, res)
     }
     fn wrap_result(&self, py: Python, key_or_path: String, val: PyObject) -> PyResult<PyObject> {
+        let val = crate::spill::fault_in(py, val)?;
          let nested_path = if self.path.is_empty() {
             key_or_path
         } else {
@@ -1004,15 +1262,7 @@ impl SupervisorProxy {
                 val
             };
 
-            return Ok(SupervisorProxy::new(
-                py,
-                val_shadow,
-                nested_path,
-                self.read_only,
-                tx_for_child,
-                is_child_shadow,
-                self.capabilities, // Inherit
-            ).into_py(py));
+            return pooled_proxy(py, val_shadow, nested_path, self.read_only, tx_for_child, is_child_shadow, self.capabilities); // Inherit caps
         }
         
         // 2. [NEW] Handle Lists (Passive Inference Registration)
@@ -1028,12 +1278,20 @@ impl SupervisorProxy {
              }
         }
 
-        Ok(val)
+        read_leaf(py, &nested_path, val)
     }
     fn path(&self) -> &str {
         &self.path
     }
 
+    /// [v3.6] Field names the registered schema declares at this path (`[]` when unknown or
+    /// outside a transaction).
+    fn schema_fields(&self, py: Python) -> Vec<String> {
+        get_current_tx(py)
+            .and_then(|tx| tx.bind(py).downcast::<crate::engine::Transaction>().ok().map(|t| t.borrow().schema_fields.fields(&self.path)))
+            .unwrap_or_default()
+    }
+
     #[getter]
     fn read_only(&self) -> bool {
         self.read_only
@@ -1102,6 +1360,111 @@ impl SupervisorProxy {
     }
 }
 
+impl SupervisorProxy {
+    /// [v3.6] Re-entrant read (see reentrancy.rs): containers come back as detached read-only
+    /// proxies and leaves as they are, without shadowing, middleware or delta bookkeeping.
+    fn raw_child(&self, py: Python, val: PyObject, path: String) -> PyResult<PyObject> {
+        let val = crate::spill::fault_in(py, val)?;
+        let capabilities = if (self.capabilities & crate::zones::CAP_ADMIN) != 0 {
+            crate::zones::ADMIN_CAPS
+        } else {
+            self.capabilities & physics(py, &path).1
+        };
+        raw_view(py, val, path, capabilities)
+    }
+
+    /// `wrap_result`, or `raw_child` for a re-entrant read.
+    fn wrap_or_raw(&self, py: Python, raw: bool, key_or_path: String, val: PyObject) -> PyResult<PyObject> {
+        if !raw {
+            return self.wrap_result(py, key_or_path, val);
+        }
+        let path = if self.path.is_empty() { key_or_path } else { format!("{}.{}", self.path, key_or_path) };
+        self.raw_child(py, val, path)
+    }
+
+    /// Unfiltered `to_dict()`: the target's own serializer or a shallow dict copy.
+    fn plain_dict(&self, py: Python) -> PyResult<PyObject> {
+        let inner = self.inner.bind(py);
+        if inner.hasattr("model_dump")? {
+            inner.call_method0("model_dump").map(pyo3::Bound::unbind)
+        } else if inner.hasattr("dict")? {
+            inner.call_method0("dict").map(pyo3::Bound::unbind)
+        } else if inner.hasattr("to_dict")? {
+            inner.call_method0("to_dict").map(pyo3::Bound::unbind)
+        } else if inner.is_instance_of::<PyDict>() {
+            // It is already a dict, but target is PyAny. Return clone as dict.
+            // Actually, usually we want a copy.
+            inner.call_method0("copy").map(pyo3::Bound::unbind)
+        } else {
+             Err(pyo3::exceptions::PyAttributeError::new_err("Wrapped object has no to_dict/model_dump"))
+        }
+    }
+
+    /// [v3.6] Load a spilled / compressed value read from `inner[key]`; shadows keep the
+    /// loaded copy.
+    fn fault_in(&self, py: Python, val: PyObject, key: &PyObject) -> PyResult<PyObject> {
+        if !crate::spill::is_placeholder(val.bind(py)) {
+            return Ok(val);
+        }
+        let loaded = crate::spill::fault_in(py, val)?;
+        if self.is_shadow && !self.read_only {
+            let inner = self.inner.bind(py);
+            if inner.is_instance_of::<PyDict>() {
+                inner.set_item(key, &loaded)?;
+            } else if let Ok(name) = key.extract::<String>(py) {
+                inner.setattr(name.as_str(), &loaded)?;
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+/// [v3.6] `SupervisorProxy::new` through the transaction's proxy pool: within one transaction,
+/// repeated accesses to the same (target, path) under the same lens return the same proxy
+/// (`ctx.domain.a is ctx.domain.a`). Without an open transaction a fresh proxy is built.
+/// [v3.6] Re-entrant read result: containers and objects as a detached read-only proxy
+/// (no shadow, no transaction), leaves as-is.
+pub(crate) fn raw_view(py: Python, val: PyObject, path: String, capabilities: u8) -> PyResult<PyObject> {
+    let bound = val.bind(py);
+    if !(bound.is_instance_of::<PyDict>() || bound.is_instance_of::<PyList>() || bound.hasattr("__dict__")?) {
+        return Ok(val);
+    }
+    let proxy = SupervisorProxy { inner: val, path, read_only: true, is_mutable: false, is_shadow: false, capabilities };
+    Ok(Py::new(py, proxy)?.into_any())
+}
+
+pub(crate) fn pooled_proxy(
+    py: Python,
+    target: PyObject,
+    path: String,
+    read_only: bool,
+    transaction: Option<PyObject>,
+    is_shadow: bool,
+    capabilities: u8,
+) -> PyResult<PyObject> {
+    let pool_tx = transaction.as_ref()
+        .and_then(|t| t.bind(py).downcast::<crate::engine::Transaction>().ok().cloned())
+        .filter(|t| t.try_borrow().is_ok_and(|t| !t.closed.load(std::sync::atomic::Ordering::SeqCst)));
+    let Some(pool_tx) = pool_tx else {
+        return Ok(Py::new(py, SupervisorProxy::new(py, target, path, read_only, transaction, is_shadow, capabilities))?.into_any());
+    };
+    let key = (target.bind(py).as_ptr() as usize, path);
+    let hit = pool_tx.borrow().proxy_pool.relock_reset().get(&key).map(|p| p.clone_ref(py));
+    if let Some(hit) = hit {
+        // A pooled proxy whose lens was changed since (e.g. elevated) is not handed out again.
+        let same_lens = hit.try_borrow(py).is_ok_and(|p| {
+            p.read_only == read_only && p.is_shadow == is_shadow && p.capabilities == capabilities && p.is_mutable
+        });
+        if same_lens {
+            THREAD_LOCAL_TX.with(|cell| *cell.borrow_mut() = transaction);
+            return Ok(hit.into_any());
+        }
+    }
+    let proxy = Py::new(py, SupervisorProxy::new(py, target, key.1.clone(), read_only, transaction, is_shadow, capabilities))?;
+    pool_tx.borrow().proxy_pool.relock_reset().insert(key, proxy.clone_ref(py));
+    Ok(proxy.into_any())
+}
+
 // =============================================================================
 // Module Registration
 // =============================================================================
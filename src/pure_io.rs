@@ -0,0 +1,306 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyTuple};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] I/O sandbox for PURE processes. While a PURE process runs inside
+// `engine.pure_scope(name)`, a process-wide `sys.addaudithook` hook classifies audit events
+// as file_read / file_write / network / subprocess and, per `set_pure_io_policy`, records
+// them (`pure_io_violations()`, audit key PURE_IO) or rejects them with PermissionError.
+// The active scope lives in a ContextVar, so concurrent async processes do not leak into
+// each other, and the hook returns immediately while no scope is open.
+// [v3.6] A process contract may also declare an effect budget (`effects=["outbox", ...]`):
+// its scope then rejects every effect class outside the budget, whatever the engine policy,
+// and `check_effect` lets the outbox and logging ask the same question.
+// NOTE: Audit hooks cannot be removed: once installed, the hook stays for the life of the
+// interpreter (idle when no sandbox is active). First-time imports read source files, so
+// PURE processes importing lazily need "file_read" in `allow`.
+
+pub const CATEGORIES: [&str; 4] = ["file_read", "file_write", "network", "subprocess"];
+/// Effect classes a contract budget may name: the I/O categories plus engine-mediated ones.
+pub const EFFECTS: [&str; 7] = ["state", "outbox", "log", "file_read", "file_write", "network", "subprocess"];
+const MAX_VIOLATIONS: usize = 1000;
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: GILOnceCell<PyObject> = GILOnceCell::new();
+static WRITE_FLAGS: GILOnceCell<i64> = GILOnceCell::new();
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Record,
+    Reject,
+}
+
+impl Mode {
+    fn as_str(self) -> &'static str {
+        match self {
+            Mode::Off => "off",
+            Mode::Record => "record",
+            Mode::Reject => "reject",
+        }
+    }
+}
+
+struct Violation {
+    process: String,
+    category: &'static str,
+    event: String,
+    detail: String,
+    rejected: bool,
+    ts_ms: u64,
+}
+
+struct Settings {
+    mode: Mode,
+    allow: Vec<String>,
+}
+
+/// Per-engine PURE I/O policy and the violations seen under it.
+pub struct PurePolicy {
+    settings: Mutex<Settings>,
+    violations: Mutex<VecDeque<Violation>>,
+}
+
+impl Default for PurePolicy {
+    fn default() -> Self {
+        PurePolicy {
+            settings: Mutex::new(Settings { mode: Mode::Off, allow: Vec::new() }),
+            violations: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+fn current(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    CURRENT.get_or_try_init(py, || {
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("default", py.None())?;
+        Ok::<_, PyErr>(py.import("contextvars")?.getattr("ContextVar")?.call(("theus_pure_io",), Some(&kwargs))?.unbind())
+    }).map(|v| v.bind(py))
+}
+
+fn install(py: Python) -> PyResult<()> {
+    if INSTALLED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    WRITE_FLAGS.get_or_try_init(py, || {
+        let os = py.import("os")?;
+        let mut flags = 0i64;
+        for name in ["O_WRONLY", "O_RDWR", "O_CREAT", "O_APPEND", "O_TRUNC"] {
+            flags |= os.getattr(name)?.extract::<i64>()?;
+        }
+        Ok::<_, PyErr>(flags)
+    })?;
+    py.import("sys")?.call_method1("addaudithook", (Py::new(py, IoHook)?,))?;
+    Ok(())
+}
+
+/// Category of an audit event, if it is I/O this sandbox cares about.
+fn classify(py: Python, event: &str, args: &Bound<'_, PyTuple>) -> Option<&'static str> {
+    match event {
+        "open" => {
+            let mode = args.get_item(1).ok();
+            let writes = if let Some(m) = mode.as_ref().and_then(|m| m.extract::<String>().ok()) { m.contains(['w', 'a', 'x', '+']) } else {
+                let flags = args.get_item(2).ok().and_then(|f| f.extract::<i64>().ok()).unwrap_or(0);
+                flags & WRITE_FLAGS.get(py).copied().unwrap_or(0) != 0
+            };
+            Some(if writes { "file_write" } else { "file_read" })
+        }
+        "os.listdir" | "os.scandir" | "glob.glob" => Some("file_read"),
+        "os.remove" | "os.rename" | "os.mkdir" | "os.rmdir" | "os.truncate" | "os.chmod" | "os.chown"
+        | "os.link" | "os.symlink" | "os.utime" => Some("file_write"),
+        _ if event.starts_with("shutil.") => Some("file_write"),
+        _ if event.starts_with("socket.") || event.starts_with("http.client.") || event == "urllib.Request" => Some("network"),
+        "subprocess.Popen" | "os.system" | "os.fork" | "os.forkpty" | "os.startfile" | "pty.spawn" => Some("subprocess"),
+        _ if event.starts_with("os.exec") || event.starts_with("os.spawn") || event.starts_with("os.posix_spawn") => Some("subprocess"),
+        _ => None,
+    }
+}
+
+/// `sys.addaudithook` callback shared by every engine.
+#[pyclass(module = "theus_core")]
+struct IoHook;
+
+#[pymethods]
+impl IoHook {
+    #[allow(clippy::unused_self)]
+    fn __call__(&self, py: Python, event: &str, args: &Bound<'_, PyTuple>) -> PyResult<()> {
+        if ACTIVE.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        let Some(category) = classify(py, event, args) else { return Ok(()) };
+        let scope = current(py)?.call_method0("get")?;
+        let Ok(scope) = scope.downcast::<PureScope>() else { return Ok(()) };
+        let detail = || args.get_item(0).ok().map(|a| a.str().map(|s| s.to_string()).unwrap_or_default()).unwrap_or_default();
+        scope.borrow().observe(category, event, detail)
+    }
+}
+
+/// Rejects `effect` ("outbox", "log", ...) when the running process's budget excludes it.
+pub fn check(py: Python, effect: &'static str, event: &str) -> PyResult<()> {
+    if ACTIVE.load(Ordering::Relaxed) == 0 {
+        return Ok(());
+    }
+    let scope = current(py)?.call_method0("get")?;
+    let Ok(scope) = scope.downcast::<PureScope>() else { return Ok(()) };
+    let scope = scope.borrow();
+    if scope.budget.is_none() {
+        return Ok(());
+    }
+    scope.observe(effect, event, String::new)
+}
+
+/// `check_effect("log")`: `PermissionError` if the running process's effect budget excludes
+/// `effect` (no-op outside budgeted processes).
+#[pyfunction]
+pub fn check_effect(py: Python, effect: &str) -> PyResult<()> {
+    let Some(effect) = EFFECTS.iter().find(|e| **e == effect) else {
+        return Err(PyValueError::new_err(format!("Unknown effect class '{effect}' (expected one of {EFFECTS:?})")));
+    };
+    check(py, effect, effect)
+}
+
+/// `with engine.pure_scope(name):` marks the enclosed code as the PURE process `name`.
+#[pyclass(module = "theus_core")]
+pub struct PureScope {
+    policy: Arc<PurePolicy>,
+    process: String,
+    /// Effect classes declared by the contract; `None` defers to the engine policy.
+    budget: Option<Vec<String>>,
+    /// `module.qualname` of the contract, quoted in budget violations.
+    contract: Option<String>,
+    token: Option<PyObject>,
+}
+
+impl PureScope {
+    /// Records `category` I/O (`event` on `detail`) and rejects it if the budget or the
+    /// engine policy says so.
+    fn observe(&self, category: &'static str, event: &str, detail: impl FnOnce() -> String) -> PyResult<()> {
+        let rejected = if let Some(budget) = &self.budget { !budget.iter().any(|e| e == category) } else {
+            let settings = self.policy.settings.relock();
+            if settings.mode == Mode::Off || settings.allow.iter().any(|a| a == category) {
+                return Ok(());
+            }
+            settings.mode == Mode::Reject
+        };
+        if self.budget.is_some() && !rejected {
+            return Ok(());
+        }
+        let detail = detail();
+        crate::audit::log_global("PURE_IO", &format!("{}: {category} ({event} {detail})", self.process));
+        {
+            let mut violations = self.policy.violations.relock();
+            if violations.len() >= MAX_VIOLATIONS {
+                violations.pop_front();
+            }
+            violations.push_back(Violation {
+                process: self.process.clone(),
+                category,
+                event: event.to_string(),
+                detail: detail.clone(),
+                rejected,
+                ts_ms: crate::clock::now_ms(),
+            });
+        }
+        if !rejected {
+            return Ok(());
+        }
+        let what = if detail.is_empty() { event.to_string() } else { format!("{event}: {detail}") };
+        let message = match &self.budget {
+            Some(budget) => crate::messages::render(crate::messages::EFFECT_BUDGET, &[
+                ("process", &self.process), ("category", &category), ("effect", &what),
+                ("budget", &format!("{budget:?}")), ("contract", &self.contract.as_deref().unwrap_or(&self.process)),
+            ]),
+            None => crate::messages::render(crate::messages::PURE_IO, &[("process", &self.process), ("category", &category), ("effect", &what)]),
+        };
+        let rule = if self.budget.is_some() { "effect_budget" } else { "pure" };
+        Err(Python::with_gil(|py| {
+            crate::errors::permission_denied(py, message, &crate::errors::Denial { path: None, rule, required: None, caps: None })
+        }))
+    }
+}
+
+#[pymethods]
+impl PureScope {
+    fn __enter__(slf: &Bound<'_, Self>) -> PyResult<()> {
+        let py = slf.py();
+        {
+            let scope = slf.borrow();
+            if scope.budget.is_none() && scope.policy.settings.relock().mode == Mode::Off {
+                return Ok(());
+            }
+        }
+        let token = current(py)?.call_method1("set", (slf,))?.unbind();
+        slf.borrow_mut().token = Some(token);
+        ACTIVE.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, py: Python, _args: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        if let Some(token) = self.token.take() {
+            ACTIVE.fetch_sub(1, Ordering::SeqCst);
+            current(py)?.call_method1("reset", (token,))?;
+        }
+        Ok(false)
+    }
+}
+
+impl PurePolicy {
+    pub fn set(&self, py: Python, mode: Option<&str>, allow: Option<Vec<String>>) -> PyResult<()> {
+        let mode = match mode.unwrap_or("off") {
+            "off" => Mode::Off,
+            "record" => Mode::Record,
+            "reject" => Mode::Reject,
+            other => return Err(PyValueError::new_err(format!("Unknown PURE I/O mode '{other}' (expected off, record or reject)"))),
+        };
+        let allow = allow.unwrap_or_default();
+        if let Some(bad) = allow.iter().find(|a| !CATEGORIES.contains(&a.as_str())) {
+            return Err(PyValueError::new_err(format!("Unknown I/O category '{bad}' (expected one of {CATEGORIES:?})")));
+        }
+        if mode != Mode::Off {
+            install(py)?;
+        }
+        *self.settings.relock() = Settings { mode, allow };
+        Ok(())
+    }
+
+    pub fn get(&self) -> (&'static str, Vec<String>) {
+        let settings = self.settings.relock();
+        (settings.mode.as_str(), settings.allow.clone())
+    }
+
+    pub fn scope(self: &Arc<Self>, py: Python, process: String, effects: Option<Vec<String>>, contract: Option<String>) -> PyResult<PureScope> {
+        if let Some(effects) = &effects {
+            if let Some(bad) = effects.iter().find(|e| !EFFECTS.contains(&e.as_str())) {
+                return Err(PyValueError::new_err(format!("Unknown effect class '{bad}' (expected one of {EFFECTS:?})")));
+            }
+            install(py)?;
+        }
+        Ok(PureScope { policy: self.clone(), process, budget: effects, contract, token: None })
+    }
+
+    /// `[{process, category, event, detail, rejected, ts_ms}]`, oldest first.
+    pub fn violations(&self, py: Python, clear: bool) -> PyResult<Vec<PyObject>> {
+        let mut violations = self.violations.relock();
+        let out = violations.iter().map(|v| {
+            let d = PyDict::new_bound(py);
+            d.set_item("process", &v.process)?;
+            d.set_item("category", v.category)?;
+            d.set_item("event", &v.event)?;
+            d.set_item("detail", &v.detail)?;
+            d.set_item("rejected", v.rejected)?;
+            d.set_item("ts_ms", v.ts_ms)?;
+            Ok(d.into_any().unbind())
+        }).collect::<PyResult<Vec<_>>>()?;
+        if clear {
+            violations.clear();
+        }
+        Ok(out)
+    }
+}
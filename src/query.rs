@@ -0,0 +1,436 @@
+use pyo3::prelude::*;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList, PyTuple};
+use crate::proxy::ExportFilter;
+use crate::structures::State;
+
+// [v3.6] JSONPath-style queries over the Data zones, evaluated in Rust against one committed
+// state (`engine.query(expr, version=None)`, `view.query(expr)`). Supported syntax:
+//   $                      root (optional: "domain.orders" == "$.domain.orders")
+//   .name  ['name', ...]   child / union of children
+//   [n]                    list index (negative counts from the end)
+//   .*  [*]                every child of a dict or list
+//   ..name  ..*  ..[...]   recursive descent
+//   [?(@.a.b OP literal)]  children for which the filter holds; OP is == != < <= > >=,
+//                          combined with && || ! and parentheses; `@.a` alone tests existence
+//   literals               'str' "str" 12 -1.5 true false null
+// Comparisons use Python semantics; a missing operand or incomparable types never match.
+// Results are plain copies (dicts / lists, pydantic models dumped). Private-zone and
+// `_underscore` keys are invisible to traversal, filters and results alike, as in
+// `SupervisorProxy.to_dict()`. The Heavy zone is not queryable; wildcards skip the legacy
+// `domain_ctx` / `global_ctx` aliases.
+
+enum Step {
+    Children(Vec<String>),
+    Index(i64),
+    Wildcard,
+    Descend(Box<Step>),
+    Filter(Filter),
+}
+
+enum Operand {
+    Current(Vec<Key>),
+    Literal(Literal),
+}
+
+enum Key {
+    Name(String),
+    Index(i64),
+}
+
+enum Literal {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+}
+
+enum Filter {
+    Or(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Exists(Operand),
+    Compare(Operand, CompareOp, Operand),
+}
+
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> PyErr {
+        PyValueError::new_err(format!("Invalid query {:?} at offset {}: {msg}", self.expr, self.pos))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        let end = self.pos + token.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().copied().eq(token.chars()) {
+            self.pos = end;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: &str) -> PyResult<()> {
+        if self.eat(token) { Ok(()) } else { Err(self.error(&format!("expected '{token}'"))) }
+    }
+
+    fn name(&mut self) -> PyResult<String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.error("expected a name"));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn quoted(&mut self) -> PyResult<Option<String>> {
+        self.skip_ws();
+        let Some(quote) = self.peek().filter(|c| *c == '\'' || *c == '"') else { return Ok(None) };
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(Some(out));
+                }
+                Some('\\') => {
+                    out.extend(self.chars.get(self.pos + 1));
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn number(&mut self) -> PyResult<Option<Literal>> {
+        self.skip_ws();
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if text.is_empty() || text == "-" {
+            self.pos = start;
+            return Ok(None);
+        }
+        if let Ok(i) = text.parse::<i64>() {
+            return Ok(Some(Literal::Int(i)));
+        }
+        text.parse::<f64>().map(|f| Some(Literal::Float(f))).map_err(|_| self.error(&format!("bad number '{text}'")))
+    }
+
+    fn query(&mut self) -> PyResult<Vec<Step>> {
+        let mut steps = Vec::new();
+        self.skip_ws();
+        if !self.eat("$") && self.peek().is_some() && self.peek() != Some('[') && self.peek() != Some('.') {
+            steps.push(self.dotted()?);
+        }
+        loop {
+            self.skip_ws();
+            if self.peek().is_none() {
+                return Ok(steps);
+            }
+            if self.eat("..") {
+                let step = if self.peek() == Some('[') { self.bracket()? } else { self.dotted()? };
+                steps.push(Step::Descend(Box::new(step)));
+            } else if self.eat(".") {
+                steps.push(self.dotted()?);
+            } else if self.peek() == Some('[') {
+                steps.push(self.bracket()?);
+            } else {
+                return Err(self.error("expected '.', '..' or '['"));
+            }
+        }
+    }
+
+    fn dotted(&mut self) -> PyResult<Step> {
+        if self.eat("*") { Ok(Step::Wildcard) } else { Ok(Step::Children(vec![self.name()?])) }
+    }
+
+    fn bracket(&mut self) -> PyResult<Step> {
+        self.expect("[")?;
+        let step = if self.eat("*") {
+            Step::Wildcard
+        } else if self.eat("?") {
+            Step::Filter(self.or()?)
+        } else if let Some(first) = self.quoted()? {
+            let mut names = vec![first];
+            while self.eat(",") {
+                names.push(self.quoted()?.ok_or_else(|| self.error("expected a quoted name"))?);
+            }
+            Step::Children(names)
+        } else {
+            match self.number()? {
+                Some(Literal::Int(i)) => Step::Index(i),
+                _ => return Err(self.error("expected *, ?(...), a quoted name or an index")),
+            }
+        };
+        self.expect("]")?;
+        Ok(step)
+    }
+
+    fn or(&mut self) -> PyResult<Filter> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            left = Filter::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> PyResult<Filter> {
+        let mut left = self.unary()?;
+        while self.eat("&&") {
+            left = Filter::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> PyResult<Filter> {
+        if self.eat("!") {
+            return Ok(Filter::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let inner = self.or()?;
+            self.expect(")")?;
+            return Ok(inner);
+        }
+        let left = self.operand()?;
+        let op = [("==", CompareOp::Eq), ("!=", CompareOp::Ne), ("<=", CompareOp::Le), (">=", CompareOp::Ge), ("<", CompareOp::Lt), (">", CompareOp::Gt)]
+            .into_iter().find(|(token, _)| self.eat(token)).map(|(_, op)| op);
+        match op {
+            Some(op) => Ok(Filter::Compare(left, op, self.operand()?)),
+            None => Ok(Filter::Exists(left)),
+        }
+    }
+
+    fn operand(&mut self) -> PyResult<Operand> {
+        if self.eat("@") {
+            let mut keys = Vec::new();
+            loop {
+                if self.eat(".") {
+                    keys.push(Key::Name(self.name()?));
+                } else if self.eat("[") {
+                    match (self.quoted()?, self.number()?) {
+                        (Some(name), _) => keys.push(Key::Name(name)),
+                        (None, Some(Literal::Int(i))) => keys.push(Key::Index(i)),
+                        _ => return Err(self.error("expected a quoted name or an index")),
+                    }
+                    self.expect("]")?;
+                } else {
+                    return Ok(Operand::Current(keys));
+                }
+            }
+        }
+        if let Some(s) = self.quoted()? {
+            return Ok(Operand::Literal(Literal::Str(s)));
+        }
+        for (token, literal) in [("true", Literal::Bool(true)), ("false", Literal::Bool(false)), ("null", Literal::Null)] {
+            if self.eat(token) {
+                return Ok(Operand::Literal(literal));
+            }
+        }
+        match self.number()? {
+            Some(n) => Ok(Operand::Literal(n)),
+            None => Err(self.error("expected @, a string, a number, true, false or null")),
+        }
+    }
+}
+
+type Node<'py> = (String, Bound<'py, PyAny>);
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() { key.to_string() } else { format!("{path}.{key}") }
+}
+
+/// Visible child `key` of a dict node (spilled values are faulted in).
+fn child<'py>(filter: &ExportFilter, path: &str, value: &Bound<'py, PyAny>, key: &str) -> PyResult<Option<Node<'py>>> {
+    let Ok(dict) = value.downcast::<PyDict>() else { return Ok(None) };
+    let child_path = join(path, key);
+    if !filter.keeps(&child_path, key) {
+        return Ok(None);
+    }
+    match dict.get_item(key)? {
+        Some(v) if crate::spill::is_placeholder(&v) => {
+            let loaded = crate::spill::fault_in(value.py(), v.unbind())?;
+            Ok(Some((child_path, loaded.into_bound(value.py()))))
+        }
+        Some(v) => Ok(Some((child_path, v))),
+        None => Ok(None),
+    }
+}
+
+fn index<'py>(path: &str, value: &Bound<'py, PyAny>, i: i64) -> PyResult<Option<Node<'py>>> {
+    if !(value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>()) {
+        return Ok(None);
+    }
+    let len = i64::try_from(value.len()?).unwrap_or(i64::MAX);
+    let i = if i < 0 { i + len } else { i };
+    if !(0..len).contains(&i) {
+        return Ok(None);
+    }
+    Ok(Some((format!("{path}[{i}]"), value.get_item(i)?)))
+}
+
+/// Legacy root aliases mirroring another zone (see `theus/context.py`); wildcards skip them.
+const ALIASES: [(&str, &str); 2] = [("domain_ctx", "domain"), ("global_ctx", "global")];
+
+fn children<'py>(filter: &ExportFilter, path: &str, value: &Bound<'py, PyAny>) -> PyResult<Vec<Node<'py>>> {
+    let mut out = Vec::new();
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        for k in dict.keys() {
+            let key = k.str()?.to_string();
+            if path.is_empty() && ALIASES.iter().any(|(alias, zone)| key == *alias && dict.contains(*zone).unwrap_or(false)) {
+                continue;
+            }
+            if let Some(node) = child(filter, path, value, &key)? {
+                out.push(node);
+            }
+        }
+    } else if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        for (i, item) in value.iter()?.enumerate() {
+            out.push((format!("{path}[{i}]"), item?));
+        }
+    }
+    Ok(out)
+}
+
+fn descendants<'py>(filter: &ExportFilter, node: Node<'py>, out: &mut Vec<Node<'py>>) -> PyResult<()> {
+    let kids = children(filter, &node.0, &node.1)?;
+    out.push(node);
+    for kid in kids {
+        descendants(filter, kid, out)?;
+    }
+    Ok(())
+}
+
+fn resolve<'py>(filter: &ExportFilter, node: &Node<'py>, operand: &Operand) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = node.1.py();
+    let keys = match operand {
+        Operand::Literal(literal) => return Ok(Some(match literal {
+            Literal::Str(s) => s.into_py(py).into_bound(py),
+            Literal::Int(i) => i.into_py(py).into_bound(py),
+            Literal::Float(f) => f.into_py(py).into_bound(py),
+            Literal::Bool(b) => b.into_py(py).into_bound(py),
+            Literal::Null => py.None().into_bound(py),
+        })),
+        Operand::Current(keys) => keys,
+    };
+    let mut current = (node.0.clone(), node.1.clone());
+    for key in keys {
+        let next = match key {
+            Key::Name(name) => child(filter, &current.0, &current.1, name)?,
+            Key::Index(i) => index(&current.0, &current.1, *i)?,
+        };
+        match next {
+            Some(n) => current = n,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(current.1))
+}
+
+fn matches(filter: &ExportFilter, node: &Node, predicate: &Filter) -> PyResult<bool> {
+    Ok(match predicate {
+        Filter::Or(a, b) => matches(filter, node, a)? || matches(filter, node, b)?,
+        Filter::And(a, b) => matches(filter, node, a)? && matches(filter, node, b)?,
+        Filter::Not(inner) => !matches(filter, node, inner)?,
+        // `@.a` tests existence; a bare literal stands for its truth value.
+        Filter::Exists(operand @ Operand::Literal(_)) => resolve(filter, node, operand)?.is_some_and(|v| v.is_truthy().unwrap_or(false)),
+        Filter::Exists(operand) => resolve(filter, node, operand)?.is_some(),
+        Filter::Compare(left, op, right) => match (resolve(filter, node, left)?, resolve(filter, node, right)?) {
+            (Some(l), Some(r)) => l.rich_compare(r, *op).and_then(|b| b.is_truthy()).unwrap_or(false),
+            _ => false,
+        },
+    })
+}
+
+fn apply<'py>(filter: &ExportFilter, step: &Step, node: Node<'py>, out: &mut Vec<Node<'py>>) -> PyResult<()> {
+    match step {
+        Step::Children(names) => {
+            for name in names {
+                out.extend(child(filter, &node.0, &node.1, name)?);
+            }
+        }
+        Step::Index(i) => out.extend(index(&node.0, &node.1, *i)?),
+        Step::Wildcard => out.extend(children(filter, &node.0, &node.1)?),
+        Step::Filter(predicate) => {
+            for kid in children(filter, &node.0, &node.1)? {
+                if matches(filter, &kid, predicate)? {
+                    out.push(kid);
+                }
+            }
+        }
+        Step::Descend(inner) => {
+            let mut all = Vec::new();
+            descendants(filter, node, &mut all)?;
+            for n in all {
+                apply(filter, inner, n, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Query {
+    pub fn parse(expr: &str) -> PyResult<Query> {
+        let mut parser = Parser { expr, chars: expr.chars().collect(), pos: 0 };
+        Ok(Query { steps: parser.query()? })
+    }
+
+    /// Matches in `state` as plain values, or `(path, value)` tuples with `with_paths`.
+    pub fn run(&self, py: Python, state: &State, with_paths: bool) -> PyResult<PyObject> {
+        let filter = ExportFilter::redacted();
+        let root = PyDict::new_bound(py);
+        for (zone, value) in &state.data {
+            root.set_item(zone, value.as_ref())?;
+        }
+        let mut nodes: Vec<Node> = vec![(String::new(), root.into_any())];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for node in nodes {
+                apply(&filter, step, node, &mut next)?;
+            }
+            nodes = next;
+        }
+        let out = PyList::empty_bound(py);
+        for (path, value) in nodes {
+            let value = filter.export(&value, &path, 0)?.unwrap_or_else(|| py.None());
+            if with_paths {
+                out.append((path, value))?;
+            } else {
+                out.append(value)?;
+            }
+        }
+        Ok(out.into_any().unbind())
+    }
+}
@@ -0,0 +1,243 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use crate::engine::{TheusEngine, Transaction};
+use crate::state_codec::{decode_value, encode_value};
+use crate::locks::Relock;
+
+// [v3.6] Transaction recorder for bug reproduction.
+// While recording, every transaction appends its ordered operations to a file of
+// u32 big-endian length-prefixed msgpack frames (same codec as `dumps_state`): one header
+// frame, then one `{tx, seq, op, ...}` frame per operation:
+//   begin    {start_version, write_timeout_ms}
+//   update   {data?, heavy?, signal?}      - tx.update()
+//   set      {path, value, inferred}       - proxy writes (inferred = found by shadow diff)
+//   commit   {version} / rollback {error}
+// `replay_recording` re-applies the committed transactions, in commit order, to an engine.
+// NOTE: Values are encoded when the op happens, so later in-place mutation of the same
+// object does not rewrite history. Values the codec can't encode are stored as their
+// repr and listed under `lossy`.
+
+pub const FORMAT: &str = "theus-recording/1";
+
+pub struct Recorder {
+    path: String,
+    out: Mutex<Option<BufWriter<File>>>,
+    records: AtomicU64,
+    error: Mutex<Option<String>>,
+}
+
+fn write_frame(out: &mut impl Write, body: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(body.len())
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "recorded op too large"))?;
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(body)
+}
+
+/// Encode `record`; fields the codec rejects are replaced by their repr.
+fn encode_record(py: Python, record: &Bound<PyDict>) -> PyResult<Vec<u8>> {
+    if let Ok(bytes) = encode_value(py, record.as_any()) {
+        return Ok(bytes);
+    }
+    let lossy = PyList::empty_bound(py);
+    for (k, v) in record.iter() {
+        if encode_value(py, &v).is_err() {
+            record.set_item(&k, v.repr()?)?;
+            lossy.append(k)?;
+        }
+    }
+    record.set_item("lossy", lossy)?;
+    encode_value(py, record.as_any())
+}
+
+impl Recorder {
+    pub fn create(py: Python, path: &str) -> PyResult<Self> {
+        let file = File::create(path)?;
+        let header = PyDict::new_bound(py);
+        header.set_item("format", FORMAT)?;
+        header.set_item("started_ms", crate::clock::now_ms())?;
+        let mut out = BufWriter::new(file);
+        write_frame(&mut out, &encode_value(py, header.as_any())?)?;
+        Ok(Recorder {
+            path: path.to_string(),
+            out: Mutex::new(Some(out)),
+            records: AtomicU64::new(0),
+            error: Mutex::new(None),
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Append one op. Never fails the transaction: the first I/O or encoding error is kept
+    /// and raised by `finish()`.
+    pub fn record(&self, py: Python, tx: u64, op: &str, fields: &[(&str, Bound<PyAny>)]) {
+        let result = (|| -> PyResult<()> {
+            let record = PyDict::new_bound(py);
+            record.set_item("tx", tx)?;
+            record.set_item("op", op)?;
+            for (k, v) in fields {
+                record.set_item(*k, v)?;
+            }
+            // NOTE: seq is assigned under the writer lock so file order == seq order.
+            let mut out = self.out.relock();
+            let Some(out) = out.as_mut() else { return Ok(()) };
+            record.set_item("seq", self.records.fetch_add(1, Ordering::SeqCst))?;
+            write_frame(out, &encode_record(py, &record)?)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            self.error.relock().get_or_insert_with(|| e.to_string());
+        }
+    }
+
+    /// Close the file. Returns the number of recorded ops.
+    pub fn finish(&self) -> PyResult<u64> {
+        if let Some(mut out) = self.out.relock().take() {
+            out.flush()?;
+        }
+        match self.error.relock().take() {
+            Some(e) => Err(pyo3::exceptions::PyIOError::new_err(format!("Recording to '{}' failed: {e}", self.path))),
+            None => Ok(self.records.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+/// Split a recording into frames; `true` if the file ends in a partial frame (crash mid-write).
+fn split_frames(buf: &[u8]) -> (Vec<&[u8]>, bool) {
+    let mut frames = Vec::new();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let Some(header) = buf.get(pos..pos + 4) else { return (frames, true) };
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let Some(body) = buf.get(pos + 4..pos + 4 + len) else { return (frames, true) };
+        frames.push(body);
+        pos += 4 + len;
+    }
+    (frames, false)
+}
+
+struct RecordedTx<'py> {
+    write_timeout_ms: u64,
+    ops: Vec<Bound<'py, PyDict>>,
+}
+
+fn apply_op(tx: &Bound<Transaction>, op: &Bound<PyDict>) -> PyResult<()> {
+    let py = tx.py();
+    let kind: String = op.get_item("op")?.map_or(Ok(String::new()), |v| v.extract())?;
+    match kind.as_str() {
+        "update" => {
+            let kwargs = PyDict::new_bound(py);
+            for zone in ["data", "heavy", "signal"] {
+                if let Some(v) = op.get_item(zone)? {
+                    kwargs.set_item(zone, v)?;
+                }
+            }
+            tx.call_method("update", (), Some(&kwargs))?;
+        }
+        "set" => {
+            let path = op.get_item("path")?.ok_or_else(|| pyo3::exceptions::PyValueError::new_err("set op without path"))?;
+            let value = op.get_item("value")?;
+            tx.call_method1("log_delta", (path, py.None(), value))?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Re-run one recorded transaction; on failure it is rolled back and the error returned.
+fn replay_tx(py: Python, engine: &Py<TheusEngine>, recorded: &RecordedTx) -> PyResult<u64> {
+    let tx = Bound::new(py, Transaction::fresh(py, engine.clone_ref(py), recorded.write_timeout_ms)?)?;
+    tx.call_method0("__enter__")?;
+    if let Err(e) = recorded.ops.iter().try_for_each(|op| apply_op(&tx, op)) {
+        tx.call_method1("__exit__", (e.get_type_bound(py), e.value_bound(py), py.None()))?;
+        return Err(e);
+    }
+    tx.call_method1("__exit__", (py.None(), py.None(), py.None()))?;
+    engine.bind(py).getattr("state")?.getattr("version")?.extract()
+}
+
+/// [v3.6] Re-execute the transactions captured by `engine.start_recording(file)` against
+/// `engine` (a core engine or a `theus.TheusEngine`), in commit order. Transactions that
+/// rolled back - or never finished - while recording are reported but not replayed.
+/// With `stop_on_error=False`, replay failures are reported instead of raised.
+#[pyfunction]
+#[pyo3(signature = (engine, file, stop_on_error=true))]
+pub fn replay_recording(py: Python, engine: &Bound<PyAny>, file: &str, stop_on_error: bool) -> PyResult<PyObject> {
+    let engine: Py<TheusEngine> = match engine.extract() {
+        Ok(core) => core,
+        Err(_) => engine.getattr("_core")?.extract()?,
+    };
+    let buf = std::fs::read(file)?;
+    let (frames, truncated) = split_frames(&buf);
+    let corrupt = |msg: &str| pyo3::exceptions::PyValueError::new_err(format!("Corrupt recording '{file}': {msg}"));
+
+    let mut frames = frames.into_iter();
+    let header = decode_value(py, frames.next().ok_or_else(|| corrupt("missing header"))?)?;
+    let format: String = header.get_item("format").and_then(|f| f.extract()).map_err(|_| corrupt("bad header"))?;
+    if format != FORMAT {
+        return Err(corrupt(&format!("unsupported format '{format}' (expected '{FORMAT}')")));
+    }
+
+    // tx id -> ops; outcomes in the order they were recorded (= commit order).
+    let mut txs: HashMap<u64, RecordedTx> = HashMap::new();
+    let mut outcomes: Vec<(u64, Bound<PyDict>)> = Vec::new();
+    for frame in frames {
+        let record = decode_value(py, frame)?.downcast_into::<PyDict>().map_err(|_| corrupt("op is not a map"))?;
+        let tx_id: u64 = record.get_item("tx")?.ok_or_else(|| corrupt("op without tx"))?.extract()?;
+        let op: String = record.get_item("op")?.ok_or_else(|| corrupt("op without name"))?.extract()?;
+        match op.as_str() {
+            "begin" => {
+                let write_timeout_ms = record.get_item("write_timeout_ms")?.map_or(Ok(5000), |v| v.extract())?;
+                txs.insert(tx_id, RecordedTx { write_timeout_ms, ops: Vec::new() });
+            }
+            "commit" | "rollback" => outcomes.push((tx_id, record)),
+            _ => txs.entry(tx_id).or_insert(RecordedTx { write_timeout_ms: 5000, ops: Vec::new() }).ops.push(record),
+        }
+    }
+
+    let report = PyList::empty_bound(py);
+    let mut replayed = 0u64;
+    for (tx_id, outcome) in &outcomes {
+        let Some(recorded) = txs.remove(tx_id) else { continue };
+        let row = PyDict::new_bound(py);
+        row.set_item("tx", tx_id)?;
+        row.set_item("ops", recorded.ops.len())?;
+        row.set_item("recorded", outcome.get_item("op")?)?;
+        if let Some(err) = outcome.get_item("error")? {
+            row.set_item("recorded_error", err)?;
+        }
+        if outcome.get_item("op")?.is_some_and(|o| o.eq("commit").unwrap_or(false)) {
+            match replay_tx(py, &engine, &recorded) {
+                Ok(version) => {
+                    replayed += 1;
+                    row.set_item("replayed", "commit")?;
+                    row.set_item("version", version)?;
+                }
+                Err(e) if stop_on_error => return Err(e),
+                Err(e) => {
+                    row.set_item("replayed", "rollback")?;
+                    row.set_item("error", e.to_string())?;
+                }
+            }
+        } else {
+            row.set_item("replayed", "skipped")?;
+        }
+        report.append(row)?;
+    }
+    // Opened but never finished while recording (recording stopped or process died).
+    let mut unfinished: Vec<u64> = txs.into_keys().collect();
+    unfinished.sort_unstable();
+
+    let out = PyDict::new_bound(py);
+    out.set_item("transactions", report)?;
+    out.set_item("replayed", replayed)?;
+    out.set_item("unfinished", unfinished)?;
+    out.set_item("truncated", truncated)?;
+    Ok(out.into_any().unbind())
+}
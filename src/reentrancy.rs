@@ -0,0 +1,121 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::messages;
+use crate::structures::ContextError;
+
+// [v3.6] Per-thread re-entrancy guard for SupervisorProxy operations.
+// User code runs in the middle of proxy operations: __deepcopy__ hooks and copiers while
+// get_shadow holds the shadow cache, middleware and transition rules around log_delta,
+// __eq__ during shadow inference. If that code touches a proxy again it re-enters
+// get_shadow / log_delta from inside themselves (deadlocking on the shadow cache, or logging
+// deltas about the very write being logged).
+// Proxy reads and writes, and the engine steps that call user hooks, bump a thread-local depth
+// for their duration; a proxy operation that starts at depth > 0 is re-entrant:
+// - Writes raise ReentrancyError (code TH701).
+// - Reads follow `set_reentrancy_policy`: "raw" (default) reads the wrapped object directly -
+//   zone read checks still apply, but there is no shadowing, middleware or delta bookkeeping,
+//   and containers come back as read-only proxies - while "raise" raises ReentrancyError too.
+// `reentrancy_stats()` counts both outcomes (process-wide).
+
+pyo3::create_exception!(theus_core, ReentrancyError, ContextError);
+
+thread_local! {
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+static RAISE_ON_READ: AtomicBool = AtomicBool::new(false);
+static RAW_READS: AtomicU64 = AtomicU64::new(0);
+static BLOCKED: AtomicU64 = AtomicU64::new(0);
+
+/// Holds one level of the current thread's depth until dropped.
+pub struct Entered {
+    /// Another proxy operation or hook step was already running on this thread.
+    pub nested: bool,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+    }
+}
+
+fn enter() -> Entered {
+    DEPTH.with(|d| {
+        let depth = d.get();
+        d.set(depth + 1);
+        Entered { nested: depth > 0 }
+    })
+}
+
+/// Engine step that runs user hooks; proxy operations inside it are re-entrant.
+pub fn hooks() -> Entered {
+    enter()
+}
+
+/// Proxy read of `path`; `nested` reads must take the raw path.
+pub fn read(py: Python, path: impl FnOnce() -> String) -> PyResult<Entered> {
+    let op = enter();
+    if op.nested {
+        if RAISE_ON_READ.load(Ordering::Relaxed) {
+            BLOCKED.fetch_add(1, Ordering::Relaxed);
+            return Err(reentrant(py, &path(), "read"));
+        }
+        RAW_READS.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(op)
+}
+
+/// Proxy write of `path`; refused when nested.
+pub fn write(py: Python, path: impl FnOnce() -> String) -> PyResult<Entered> {
+    let op = enter();
+    if op.nested {
+        BLOCKED.fetch_add(1, Ordering::Relaxed);
+        return Err(reentrant(py, &path(), "write"));
+    }
+    Ok(op)
+}
+
+fn reentrant(py: Python, path: &str, access: &str) -> PyErr {
+    let message = messages::render(messages::REENTRANT_ACCESS, &[("access", &access), ("path", &path)]);
+    crate::errors::with_fields(py, crate::errors::coded::<ReentrancyError>(py, message), &[
+        ("path", path.into_py(py)),
+        ("access", access.into_py(py)),
+    ])
+}
+
+fn current_policy() -> &'static str {
+    if RAISE_ON_READ.load(Ordering::Relaxed) { "raise" } else { "raw" }
+}
+
+/// [v3.6] How re-entrant proxy reads are handled: "raw" (default) or "raise". Returns the
+/// previous policy. Re-entrant writes always raise `ReentrancyError`.
+#[pyfunction]
+pub fn set_reentrancy_policy(policy: &str) -> PyResult<String> {
+    let previous = current_policy().to_string();
+    match policy {
+        "raw" => RAISE_ON_READ.store(false, Ordering::Relaxed),
+        "raise" => RAISE_ON_READ.store(true, Ordering::Relaxed),
+        other => return Err(PyValueError::new_err(format!("Unknown re-entrancy policy '{other}' (expected 'raw' or 'raise')"))),
+    }
+    Ok(previous)
+}
+
+/// [v3.6] `{policy, raw_reads, blocked}`: re-entrant proxy reads served raw and re-entrant
+/// operations refused since start-up (`reset=True` zeroes the counters after reading).
+#[pyfunction]
+#[pyo3(signature = (reset=false))]
+pub fn reentrancy_stats(py: Python, reset: bool) -> PyResult<PyObject> {
+    let out = PyDict::new_bound(py);
+    out.set_item("policy", current_policy())?;
+    let (raw_reads, blocked) = if reset {
+        (RAW_READS.swap(0, Ordering::Relaxed), BLOCKED.swap(0, Ordering::Relaxed))
+    } else {
+        (RAW_READS.load(Ordering::Relaxed), BLOCKED.load(Ordering::Relaxed))
+    };
+    out.set_item("raw_reads", raw_reads)?;
+    out.set_item("blocked", blocked)?;
+    Ok(out.into_any().unbind())
+}
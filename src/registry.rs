@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 use pyo3::prelude::*;
+use crate::locks::Relock;
 
 // Thread-safe global registry
 // Stores process_name -> Python Function
@@ -11,11 +12,11 @@ fn get_registry() -> &'static Mutex<HashMap<String, PyObject>> {
 }
 
 pub fn register_process(name: String, process: PyObject) {
-    let mut registry = get_registry().lock().unwrap();
+    let mut registry = get_registry().relock();
     registry.insert(name, process);
 }
 
 pub fn get_process(py: Python, name: &str) -> Option<PyObject> {
-    let registry = get_registry().lock().unwrap();
+    let registry = get_registry().relock();
     registry.get(name).map(|obj| obj.clone_ref(py))
 }
@@ -0,0 +1,929 @@
+use pyo3::prelude::*;
+use pyo3::create_exception;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+create_exception!(theus_core, RuleError, pyo3::exceptions::PyValueError);
+create_exception!(theus_core, RuleLimitError, RuleError);
+
+// [v3.6] Sandboxed rule language.
+// Rules are plain expressions evaluated over a Rust copy of a state subtree; no Python object
+// is reachable from a rule, so evaluation cannot call into user code or mutate state.
+//
+//   expr    := or ("if" or "else" expr)?
+//   or      := and ("or" and)*        and := not ("and" not)*      not := "not" not | cmp
+//   cmp     := add (("=="|"!="|"<"|"<="|">"|">="|"in"|"not in") add)?
+//   add     := mul (("+"|"-") mul)*    mul := unary (("*"|"/"|"%") unary)*
+//   unary   := "-" unary | postfix     postfix := atom ("." name | "[" expr "]" | "(" args ")")*
+//   atom    := number | string | true/false/null | name | "(" expr ")" | "[" items "]"
+//
+// Names resolve against the subtree (a mapping); `_` is the subtree itself.
+
+/// Maximum nesting while parsing (guards the Rust stack). Every link of an operator chain
+/// (`a + b + c`, `x.y.z`) counts as one level, since it deepens the tree just the same.
+const MAX_PARSE_DEPTH: usize = 64;
+
+/// Longest rule source accepted, in bytes.
+const MAX_RULE_LEN: usize = 4096;
+
+/// Evaluation budget for one rule.
+pub struct RuleLimits {
+    pub max_steps: u64,
+    pub timeout: Duration,
+    pub max_memory: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+    /// A value the sandbox cannot see into (callables, arbitrary objects).
+    Opaque(String),
+}
+
+impl Value {
+    fn type_name(&self) -> &str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Str(_) => "str",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Opaque(t) => t,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::Float(f) => *f != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::List(l) => !l.is_empty(),
+            Value::Map(m) => !m.is_empty(),
+            Value::Opaque(_) => true,
+        }
+    }
+
+    /// Rough heap footprint, used for the memory budget.
+    fn size(&self) -> usize {
+        match self {
+            Value::Str(s) | Value::Opaque(s) => 16 + s.len(),
+            Value::List(l) => 16 + l.iter().map(Value::size).sum::<usize>(),
+            Value::Map(m) => 16 + m.iter().map(|(k, v)| k.len() + v.size()).sum::<usize>(),
+            _ => 16,
+        }
+    }
+
+    /// Convert a Python value, charging its `size()` against `max_memory` as it goes so an
+    /// oversized input fails before it is fully copied.
+    pub fn from_py(value: &Bound<'_, PyAny>, max_memory: usize) -> PyResult<Value> {
+        let mut left = max_memory;
+        Value::convert(value, 0, &mut left).map_err(|e| match e {
+            Some(err) => err,
+            None => RuleLimitError::new_err(format!("Rule input exceeds {max_memory} byte memory limit")),
+        })
+    }
+
+    /// `Err(None)` means the memory budget ran out.
+    fn convert(value: &Bound<'_, PyAny>, depth: usize, left: &mut usize) -> Result<Value, Option<PyErr>> {
+        if depth > MAX_PARSE_DEPTH {
+            return Err(Some(RuleLimitError::new_err("Rule input nested too deeply")));
+        }
+        // Read through proxies to the committed value.
+        if let Ok(inner) = value.getattr("supervisor_target") {
+            return Value::convert(&inner, depth, left);
+        }
+        charge(left, 16)?;
+        Ok(if value.is_none() {
+            Value::Null
+        } else if let Ok(b) = value.downcast::<PyBool>() {
+            Value::Bool(b.is_true())
+        } else if let Ok(i) = value.downcast::<PyInt>() {
+            i.extract::<i64>().map_or_else(|_| Value::Float(i.extract::<f64>().unwrap_or(f64::NAN)), Value::Int)
+        } else if let Ok(f) = value.downcast::<PyFloat>() {
+            Value::Float(f.value())
+        } else if let Ok(s) = value.downcast::<PyString>() {
+            let s = s.to_str().map_err(Some)?;
+            charge(left, s.len())?;
+            Value::Str(s.to_string())
+        } else if let Ok(l) = value.downcast::<PyList>() {
+            Value::List(l.iter().map(|v| Value::convert(&v, depth + 1, left)).collect::<Result<_, _>>()?)
+        } else if let Ok(t) = value.downcast::<PyTuple>() {
+            Value::List(t.iter().map(|v| Value::convert(&v, depth + 1, left)).collect::<Result<_, _>>()?)
+        } else if let Ok(d) = value.downcast::<PyDict>() {
+            let mut map = BTreeMap::new();
+            for (k, v) in d {
+                let key = k.str().and_then(|k| Ok(k.to_str()?.to_string())).map_err(Some)?;
+                charge(left, key.len())?;
+                map.insert(key, Value::convert(&v, depth + 1, left)?);
+            }
+            Value::Map(map)
+        } else {
+            let name = value.get_type().name().map_err(Some)?.to_string();
+            charge(left, name.len())?;
+            Value::Opaque(name)
+        })
+    }
+
+    pub fn into_py(self, py: Python) -> PyResult<PyObject> {
+        Ok(match self {
+            Value::Null => py.None(),
+            Value::Bool(b) => b.into_py(py),
+            Value::Int(i) => i.into_py(py),
+            Value::Float(f) => f.into_py(py),
+            Value::Str(s) => s.into_py(py),
+            Value::List(l) => {
+                let items = l.into_iter().map(|v| v.into_py(py)).collect::<PyResult<Vec<_>>>()?;
+                PyList::new_bound(py, items).into_any().unbind()
+            }
+            Value::Map(m) => {
+                let dict = PyDict::new_bound(py);
+                for (k, v) in m {
+                    dict.set_item(k, v.into_py(py)?)?;
+                }
+                dict.into_any().unbind()
+            }
+            Value::Opaque(t) => return Err(RuleError::new_err(format!("Rule cannot return opaque '{t}' value"))),
+        })
+    }
+}
+
+/// Parse or evaluation failure. Plain Rust inside the sandbox; it becomes a `RuleError` or
+/// `RuleLimitError` once it leaves `evaluate`.
+#[derive(Debug)]
+enum Fail {
+    Rule(String),
+    Limit(String),
+}
+
+impl Fail {
+    fn rule(msg: impl Into<String>) -> Self {
+        Fail::Rule(msg.into())
+    }
+
+    fn limit(msg: impl Into<String>) -> Self {
+        Fail::Limit(msg.into())
+    }
+}
+
+impl From<Fail> for PyErr {
+    fn from(fail: Fail) -> PyErr {
+        match fail {
+            Fail::Rule(msg) => RuleError::new_err(msg),
+            Fail::Limit(msg) => RuleLimitError::new_err(msg),
+        }
+    }
+}
+
+type Res<T> = Result<T, Fail>;
+
+/// Take `bytes` from the input budget (`Err(None)` once it runs out).
+fn charge(left: &mut usize, bytes: usize) -> Result<(), Option<PyErr>> {
+    *left = left.checked_sub(bytes).ok_or(None)?;
+    Ok(())
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(Value),
+    Str(String),
+    Name(String),
+    Op(&'static str),
+}
+
+fn tokenize(src: &str) -> Res<Vec<Token>> {
+    const OPS: [&str; 18] = ["==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "(", ")", "[", "]", ".", ",", "!"];
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            let is_float = i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit();
+            if is_float {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            let num = if is_float {
+                text.parse::<f64>().map(Value::Float).ok()
+            } else {
+                text.parse::<i64>().map(Value::Int).ok()
+            };
+            tokens.push(Token::Num(num.ok_or_else(|| Fail::rule(format!("Invalid number '{text}'")))?));
+        } else if c == '"' || c == '\'' {
+            let mut s = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(Fail::rule("Unterminated string literal")),
+                    Some(&q) if q == c => break,
+                    Some('\\') => {
+                        i += 1;
+                        match chars.get(i) {
+                            Some('n') => s.push('\n'),
+                            Some('t') => s.push('\t'),
+                            Some(&e) => s.push(e),
+                            None => return Err(Fail::rule("Unterminated string literal")),
+                        }
+                    }
+                    Some(&ch) => s.push(ch),
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Name(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPS.iter().find(|op| rest.starts_with(**op))
+                .ok_or_else(|| Fail::rule(format!("Unexpected character '{c}' at {i}")))?;
+            i += op.len();
+            tokens.push(Token::Op(op));
+        }
+    }
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+#[derive(Debug)]
+enum Expr {
+    Lit(Value),
+    Name(String),
+    List(Vec<Expr>),
+    Attr(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Cond(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is_op(&self, op: &str) -> bool {
+        matches!(self.peek(), Some(Token::Op(o)) if *o == op)
+    }
+
+    fn is_kw(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(n)) if n == kw)
+    }
+
+    fn expect_op(&mut self, op: &str) -> Res<()> {
+        if self.is_op(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(Fail::rule(format!("Expected '{op}' at token {}", self.pos)))
+        }
+    }
+
+    fn deepen(&mut self) -> Res<()> {
+        self.depth += 1;
+        if self.depth > MAX_PARSE_DEPTH {
+            return Err(Fail::limit("Rule nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Res<T>) -> Res<T> {
+        self.deepen()?;
+        let out = f(self);
+        self.depth -= 1;
+        out
+    }
+
+    fn expr(&mut self) -> Res<Expr> {
+        self.nested(|p| {
+            let body = p.or()?;
+            if !p.is_kw("if") {
+                return Ok(body);
+            }
+            p.pos += 1;
+            let cond = p.or()?;
+            if !p.is_kw("else") {
+                return Err(Fail::rule("Expected 'else' in conditional expression"));
+            }
+            p.pos += 1;
+            let other = p.expr()?;
+            Ok(Expr::Cond(Box::new(cond), Box::new(body), Box::new(other)))
+        })
+    }
+
+    fn or(&mut self) -> Res<Expr> {
+        let base = self.depth;
+        let mut lhs = self.and()?;
+        while self.is_kw("or") {
+            self.deepen()?;
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        self.depth = base;
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Res<Expr> {
+        let base = self.depth;
+        let mut lhs = self.not()?;
+        while self.is_kw("and") {
+            self.deepen()?;
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.not()?));
+        }
+        self.depth = base;
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Res<Expr> {
+        if self.is_kw("not") || self.is_op("!") {
+            self.pos += 1;
+            return self.nested(|p| Ok(Expr::Unary("not", Box::new(p.not()?))));
+        }
+        self.cmp()
+    }
+
+    fn cmp(&mut self) -> Res<Expr> {
+        let lhs = self.add()?;
+        let op = match self.peek() {
+            Some(Token::Op(o)) if ["==", "!=", "<", "<=", ">", ">="].contains(o) => *o,
+            Some(Token::Name(n)) if n == "in" => "in",
+            Some(Token::Name(n)) if n == "not" && matches!(self.tokens.get(self.pos + 1), Some(Token::Name(m)) if m == "in") => {
+                self.pos += 1;
+                "not in"
+            }
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        Ok(Expr::Binary(op, Box::new(lhs), Box::new(self.add()?)))
+    }
+
+    fn add(&mut self) -> Res<Expr> {
+        let base = self.depth;
+        let mut lhs = self.mul()?;
+        while let Some(Token::Op(op @ ("+" | "-"))) = self.peek() {
+            let op = *op;
+            self.deepen()?;
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.mul()?));
+        }
+        self.depth = base;
+        Ok(lhs)
+    }
+
+    fn mul(&mut self) -> Res<Expr> {
+        let base = self.depth;
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(op @ ("*" | "/" | "%"))) = self.peek() {
+            let op = *op;
+            self.deepen()?;
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        self.depth = base;
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Res<Expr> {
+        if self.is_op("-") {
+            self.pos += 1;
+            return self.nested(|p| Ok(Expr::Unary("-", Box::new(p.unary()?))));
+        }
+        self.postfix()
+    }
+
+    fn args(&mut self, close: &str) -> Res<Vec<Expr>> {
+        let mut items = Vec::new();
+        while !self.is_op(close) {
+            items.push(self.expr()?);
+            if !self.is_op(close) {
+                self.expect_op(",")?;
+            }
+        }
+        self.expect_op(close)?;
+        Ok(items)
+    }
+
+    fn postfix(&mut self) -> Res<Expr> {
+        let base = self.depth;
+        let mut e = self.atom()?;
+        loop {
+            if self.is_op(".") || self.is_op("[") || self.is_op("(") {
+                self.deepen()?;
+            }
+            if self.is_op(".") {
+                self.pos += 1;
+                match self.peek().cloned() {
+                    Some(Token::Name(n)) => {
+                        self.pos += 1;
+                        e = Expr::Attr(Box::new(e), n);
+                    }
+                    _ => return Err(Fail::rule("Expected field name after '.'")),
+                }
+            } else if self.is_op("[") {
+                self.pos += 1;
+                let idx = self.expr()?;
+                self.expect_op("]")?;
+                e = Expr::Index(Box::new(e), Box::new(idx));
+            } else if self.is_op("(") {
+                let Expr::Name(name) = e else {
+                    return Err(Fail::rule("Only built-in functions can be called"));
+                };
+                self.pos += 1;
+                e = Expr::Call(name, self.args(")")?);
+            } else {
+                self.depth = base;
+                return Ok(e);
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Res<Expr> {
+        let tok = self.peek().cloned().ok_or_else(|| Fail::rule("Unexpected end of rule"))?;
+        self.pos += 1;
+        Ok(match tok {
+            Token::Num(v) => Expr::Lit(v),
+            Token::Str(s) => Expr::Lit(Value::Str(s)),
+            Token::Name(n) => match n.as_str() {
+                "true" | "True" => Expr::Lit(Value::Bool(true)),
+                "false" | "False" => Expr::Lit(Value::Bool(false)),
+                "null" | "None" => Expr::Lit(Value::Null),
+                _ => Expr::Name(n),
+            },
+            Token::Op("(") => {
+                let e = self.expr()?;
+                self.expect_op(")")?;
+                e
+            }
+            Token::Op("[") => Expr::List(self.nested(|p| p.args("]"))?),
+            Token::Op(op) => return Err(Fail::rule(format!("Unexpected '{op}'"))),
+        })
+    }
+}
+
+/// Parse a rule into an expression tree (syntax errors raise `RuleError`).
+fn parse(src: &str) -> Res<Expr> {
+    if src.len() > MAX_RULE_LEN {
+        return Err(Fail::limit(format!("Rule longer than {MAX_RULE_LEN} bytes")));
+    }
+    let mut p = Parser { tokens: tokenize(src)?, pos: 0, depth: 0 };
+    let e = p.expr()?;
+    if p.pos != p.tokens.len() {
+        return Err(Fail::rule(format!("Unexpected trailing input at token {}", p.pos)));
+    }
+    Ok(e)
+}
+
+// ============================================================================
+// Evaluator
+// ============================================================================
+
+/// Values are borrowed from the input or the rule wherever possible: reading `_`, a name or a
+/// field never copies the subtree. Anything the rule builds (lists, strings, `keys()`, and
+/// copies made to build them) is charged to the memory budget.
+type Val<'a> = Cow<'a, Value>;
+
+struct Vm<'a> {
+    root: &'a Value,
+    limits: &'a RuleLimits,
+    started: Instant,
+    steps: u64,
+    memory: usize,
+}
+
+fn type_err(op: &str, a: &Value, b: &Value) -> Fail {
+    Fail::rule(format!("Unsupported operand types for '{op}': {} and {}", a.type_name(), b.type_name()))
+}
+
+/// Child `key` of a borrowed or owned map, borrowed or moved out accordingly.
+fn child<'a>(obj: Val<'a>, key: &str) -> Option<Val<'a>> {
+    match obj {
+        Cow::Borrowed(Value::Map(m)) => m.get(key).map(Cow::Borrowed),
+        Cow::Owned(Value::Map(mut m)) => m.remove(key).map(Cow::Owned),
+        _ => None,
+    }
+}
+
+/// Item `i` of a borrowed or owned list.
+fn item(obj: Val<'_>, i: usize) -> Option<Val<'_>> {
+    match obj {
+        Cow::Borrowed(Value::List(l)) => l.get(i).map(Cow::Borrowed),
+        Cow::Owned(Value::List(mut l)) => (i < l.len()).then(|| Cow::Owned(l.swap_remove(i))),
+        _ => None,
+    }
+}
+
+impl<'a> Vm<'a> {
+    fn tick(&mut self) -> Res<()> {
+        self.steps += 1;
+        if self.steps > self.limits.max_steps {
+            return Err(Fail::limit(format!("Rule exceeded {} evaluation steps", self.limits.max_steps)));
+        }
+        if self.started.elapsed() > self.limits.timeout {
+            return Err(Fail::limit(format!("Rule exceeded {}ms time limit", self.limits.timeout.as_millis())));
+        }
+        Ok(())
+    }
+
+    fn alloc(&mut self, v: Value) -> Res<Val<'a>> {
+        self.memory += v.size();
+        if self.memory > self.limits.max_memory {
+            return Err(Fail::limit(format!("Rule exceeded {} byte memory limit", self.limits.max_memory)));
+        }
+        Ok(Cow::Owned(v))
+    }
+
+    /// An owned copy, charged to the memory budget if it had to be cloned.
+    fn owned(&mut self, v: Val<'a>) -> Res<Value> {
+        match v {
+            Cow::Borrowed(v) => Ok(self.alloc(v.clone())?.into_owned()),
+            Cow::Owned(v) => Ok(v),
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn eval(&mut self, e: &'a Expr) -> Res<Val<'a>> {
+        self.tick()?;
+        match e {
+            Expr::Lit(v) => Ok(Cow::Borrowed(v)),
+            Expr::Name(n) if n == "_" => Ok(Cow::Borrowed(self.root)),
+            Expr::Name(n) => match self.root {
+                Value::Map(m) => m.get(n).map(Cow::Borrowed).ok_or_else(|| Fail::rule(format!("Unknown name '{n}'"))),
+                _ => Err(Fail::rule(format!("Unknown name '{n}' (subtree is not a mapping; use '_')"))),
+            },
+            Expr::List(items) => {
+                let mut vals = Vec::with_capacity(items.len());
+                for i in items {
+                    let v = self.eval(i)?;
+                    vals.push(self.owned(v)?);
+                }
+                // The items were charged as they were copied; only the list itself is new.
+                self.alloc(Value::List(Vec::new()))?;
+                Ok(Cow::Owned(Value::List(vals)))
+            }
+            Expr::Attr(obj, name) => {
+                let obj = self.eval(obj)?;
+                let type_name = obj.type_name().to_string();
+                if !matches!(*obj, Value::Map(_)) {
+                    return Err(Fail::rule(format!("Cannot read field '{name}' of {type_name}")));
+                }
+                child(obj, name).ok_or_else(|| Fail::rule(format!("Unknown field '{name}'")))
+            }
+            Expr::Index(obj, idx) => {
+                let (obj, idx) = (self.eval(obj)?, self.eval(idx)?);
+                match (&*obj, &*idx) {
+                    (Value::List(l), Value::Int(i)) => {
+                        let i = if *i < 0 { *i + l.len() as i64 } else { *i };
+                        usize::try_from(i).ok().and_then(|i| item(obj, i))
+                            .ok_or_else(|| Fail::rule("List index out of range"))
+                    }
+                    (Value::Map(_), Value::Str(k)) => {
+                        let k = k.clone();
+                        child(obj, &k).ok_or_else(|| Fail::rule(format!("Unknown key '{k}'")))
+                    }
+                    (o, i) => Err(type_err("[]", o, i)),
+                }
+            }
+            Expr::Call(name, args) => {
+                let vals = args.iter().map(|a| self.eval(a)).collect::<Res<Vec<_>>>()?;
+                self.call(name, vals)
+            }
+            Expr::Unary(op, inner) => {
+                let v = self.eval(inner)?;
+                match (*op, &*v) {
+                    ("not", v) => Ok(Cow::Owned(Value::Bool(!v.truthy()))),
+                    ("-", Value::Int(i)) => i.checked_neg().map(|i| Cow::Owned(Value::Int(i))).ok_or_else(|| Fail::rule("Integer overflow")),
+                    ("-", Value::Float(f)) => Ok(Cow::Owned(Value::Float(-f))),
+                    (_, v) => Err(Fail::rule(format!("Bad operand type for unary '{op}': {}", v.type_name()))),
+                }
+            }
+            Expr::And(a, b) => {
+                let lhs = self.eval(a)?;
+                if lhs.truthy() { self.eval(b) } else { Ok(lhs) }
+            }
+            Expr::Or(a, b) => {
+                let lhs = self.eval(a)?;
+                if lhs.truthy() { Ok(lhs) } else { self.eval(b) }
+            }
+            Expr::Cond(cond, body, other) => {
+                if self.eval(cond)?.truthy() { self.eval(body) } else { self.eval(other) }
+            }
+            Expr::Binary(op, a, b) => {
+                let (lhs, rhs) = (self.eval(a)?, self.eval(b)?);
+                self.binary(op, lhs, rhs)
+            }
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn binary(&mut self, op: &str, a: Val<'a>, b: Val<'a>) -> Res<Val<'a>> {
+        use Value::{Bool, Float, Int, List, Map, Str};
+        let num = |v: &Value| match v {
+            Int(i) => Some(*i as f64),
+            Float(f) => Some(*f),
+            _ => None,
+        };
+        let scalar = |v: Value| Ok(Cow::Owned(v));
+        match op {
+            "==" => scalar(Bool(values_eq(&a, &b))),
+            "!=" => scalar(Bool(!values_eq(&a, &b))),
+            "in" | "not in" => {
+                let found = match (&*a, &*b) {
+                    (_, List(l)) => l.iter().any(|v| values_eq(v, &a)),
+                    (Str(k), Map(m)) => m.contains_key(k),
+                    (Str(s), Str(hay)) => hay.contains(s.as_str()),
+                    _ => return Err(type_err(op, &a, &b)),
+                };
+                scalar(Bool(found == (op == "in")))
+            }
+            "<" | "<=" | ">" | ">=" => scalar(Bool(compare(op, &a, &b)?)),
+            "+" => match (&*a, &*b) {
+                (Int(x), Int(y)) => x.checked_add(*y).map(|i| Cow::Owned(Int(i))).ok_or_else(|| Fail::rule("Integer overflow")),
+                (Str(x), Str(y)) => {
+                    let joined = format!("{x}{y}");
+                    self.alloc(Str(joined))
+                }
+                (List(_), List(_)) => {
+                    let (List(mut x), List(y)) = (a.into_owned(), b.into_owned()) else { unreachable!() };
+                    x.extend(y);
+                    self.alloc(List(x))
+                }
+                (x, y) => match (num(x), num(y)) {
+                    (Some(x), Some(y)) => scalar(Float(x + y)),
+                    _ => Err(type_err(op, x, y)),
+                },
+            },
+            "-" | "*" | "/" | "%" => {
+                if let (Int(x), Int(y)) = (&*a, &*b) {
+                    let r = match op {
+                        "-" => x.checked_sub(*y),
+                        "*" => x.checked_mul(*y),
+                        "%" if *y != 0 => Some(x.rem_euclid(*y)),
+                        "/" if *y != 0 => return scalar(Float(*x as f64 / *y as f64)),
+                        _ => return Err(Fail::rule("Division by zero")),
+                    };
+                    return r.map(|i| Cow::Owned(Int(i))).ok_or_else(|| Fail::rule("Integer overflow"));
+                }
+                let (Some(x), Some(y)) = (num(&a), num(&b)) else { return Err(type_err(op, &a, &b)) };
+                if (op == "/" || op == "%") && y == 0.0 {
+                    return Err(Fail::rule("Division by zero"));
+                }
+                scalar(Float(match op {
+                    "-" => x - y,
+                    "*" => x * y,
+                    "/" => x / y,
+                    _ => x.rem_euclid(y),
+                }))
+            }
+            _ => Err(Fail::rule(format!("Unknown operator '{op}'"))),
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn call(&mut self, name: &str, args: Vec<Val<'a>>) -> Res<Val<'a>> {
+        use Value::{Bool, Float, Int, List, Map, Str};
+        let arity = |n: usize| {
+            if args.len() == n { Ok(()) } else { Err(Fail::rule(format!("{name}() takes {n} argument(s), got {}", args.len()))) }
+        };
+        // min/max/sum accept either one list or several values.
+        let items = |args: Vec<Val<'a>>| -> Vec<Val<'a>> {
+            match <[Val<'a>; 1]>::try_from(args) {
+                Ok([Cow::Borrowed(List(l))]) => l.iter().map(Cow::Borrowed).collect(),
+                Ok([Cow::Owned(List(l))]) => l.into_iter().map(Cow::Owned).collect(),
+                Ok([v]) => vec![v],
+                Err(args) => args,
+            }
+        };
+        let scalar = |v: Value| Ok(Cow::Owned(v));
+        match name {
+            "len" => {
+                arity(1)?;
+                match &*args[0] {
+                    Str(s) => scalar(Int(s.chars().count() as i64)),
+                    List(l) => scalar(Int(l.len() as i64)),
+                    Map(m) => scalar(Int(m.len() as i64)),
+                    v => Err(Fail::rule(format!("len() of {}", v.type_name()))),
+                }
+            }
+            "abs" => {
+                arity(1)?;
+                match &*args[0] {
+                    Int(i) => i.checked_abs().map(|i| Cow::Owned(Int(i))).ok_or_else(|| Fail::rule("Integer overflow")),
+                    Float(f) => scalar(Float(f.abs())),
+                    v => Err(Fail::rule(format!("abs() of {}", v.type_name()))),
+                }
+            }
+            "min" | "max" => {
+                let mut best: Option<Val<'a>> = None;
+                for v in items(args) {
+                    self.tick()?;
+                    best = Some(match best {
+                        None => v,
+                        Some(b) => {
+                            let less = compare("<", &v, &b)?;
+                            if less == (name == "min") { v } else { b }
+                        }
+                    });
+                }
+                best.ok_or_else(|| Fail::rule(format!("{name}() of empty sequence")))
+            }
+            "sum" => {
+                let mut total = Cow::Owned(Int(0));
+                for v in items(args) {
+                    self.tick()?;
+                    total = self.binary("+", total, v)?;
+                }
+                Ok(total)
+            }
+            "lower" | "upper" => {
+                arity(1)?;
+                match &*args[0] {
+                    Str(s) => self.alloc(Str(if name == "lower" { s.to_lowercase() } else { s.to_uppercase() })),
+                    v => Err(Fail::rule(format!("{name}() of {}", v.type_name()))),
+                }
+            }
+            "startswith" | "endswith" => {
+                arity(2)?;
+                match (&*args[0], &*args[1]) {
+                    (Str(s), Str(p)) => scalar(Bool(if name == "startswith" { s.starts_with(p.as_str()) } else { s.ends_with(p.as_str()) })),
+                    (a, b) => Err(type_err(name, a, b)),
+                }
+            }
+            "keys" => {
+                arity(1)?;
+                match &*args[0] {
+                    Map(m) => self.alloc(List(m.keys().cloned().map(Str).collect())),
+                    v => Err(Fail::rule(format!("keys() of {}", v.type_name()))),
+                }
+            }
+            _ => Err(Fail::rule(format!("Unknown function '{name}'"))),
+        }
+    }
+}
+
+/// `a <op> b` for the ordering operators.
+#[allow(clippy::cast_precision_loss)]
+fn compare(op: &str, a: &Value, b: &Value) -> Res<bool> {
+    let ord = match (a, b) {
+        (Value::Str(x), Value::Str(y)) => x.partial_cmp(y),
+        (Value::Int(x), Value::Int(y)) => Some(x.cmp(y)),
+        (Value::Int(_) | Value::Float(_), Value::Int(_) | Value::Float(_)) => {
+            let num = |v: &Value| match v {
+                Value::Int(i) => *i as f64,
+                Value::Float(f) => *f,
+                _ => unreachable!(),
+            };
+            num(a).partial_cmp(&num(b))
+        }
+        _ => return Err(type_err(op, a, b)),
+    };
+    let Some(ord) = ord else { return Ok(false) };
+    Ok(match op {
+        "<" => ord.is_lt(),
+        "<=" => ord.is_le(),
+        ">" => ord.is_gt(),
+        _ => ord.is_ge(),
+    })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn values_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int(x), Value::Float(y)) | (Value::Float(y), Value::Int(x)) => (*x as f64) == *y,
+        (Value::Opaque(_), _) | (_, Value::Opaque(_)) => false,
+        _ => a == b,
+    }
+}
+
+/// Evaluate `rule` against `root` within `limits`.
+pub fn evaluate(rule: &str, root: &Value, limits: &RuleLimits) -> PyResult<Value> {
+    let expr = parse(rule)?;
+    let mut vm = Vm { root, limits, started: Instant::now(), steps: 0, memory: root.size() };
+    if vm.memory > limits.max_memory {
+        return Err(RuleLimitError::new_err(format!("Rule input exceeds {} byte memory limit", limits.max_memory)));
+    }
+    let result = vm.eval(&expr)?;
+    Ok(vm.owned(result)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_steps: u64, timeout: Duration, max_memory: usize) -> RuleLimits {
+        RuleLimits { max_steps, timeout, max_memory }
+    }
+
+    fn expr(src: &str) -> Expr {
+        parse(src).expect("rule parses")
+    }
+
+    fn new_vm<'a>(root: &'a Value, limits: &'a RuleLimits) -> Vm<'a> {
+        Vm { root, limits, started: Instant::now(), steps: 0, memory: root.size() }
+    }
+
+    /// 2000 keys of 100 bytes each (~230 KB).
+    fn wide_map() -> Value {
+        Value::Map((0..2000).map(|i| (format!("{i:0>100}"), Value::Int(i))).collect())
+    }
+
+    #[test]
+    fn test_evaluates_expressions() {
+        let root = Value::Map(BTreeMap::from([("n".to_string(), Value::Int(4)), ("tags".to_string(), Value::List(vec![Value::Str("a".into())]))]));
+        let limits = limits(1000, Duration::from_secs(5), 1 << 20);
+        let e = expr("n * 2 + len(tags) if 'a' in tags else 0");
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e).as_deref(), Ok(Value::Int(9))));
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let root = Value::Null;
+        let limits = limits(10, Duration::from_secs(5), 1 << 20);
+        let e = expr("1 + 1 + 1 + 1 + 1 + 1");
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e), Err(Fail::Limit(_))));
+        assert_eq!(vm.steps, 11);
+    }
+
+    #[test]
+    fn test_time_limit_is_checked_on_every_step() {
+        let root = Value::Null;
+        let limits = limits(1000, Duration::from_millis(50), 1 << 20);
+        let e = expr("1 + 1");
+        let mut vm = new_vm(&root, &limits);
+        vm.started -= Duration::from_millis(100);
+        assert!(matches!(vm.eval(&e), Err(Fail::Limit(_))));
+        assert_eq!(vm.steps, 1);
+    }
+
+    #[test]
+    fn test_reading_the_input_does_not_copy_it() {
+        let root = wide_map();
+        let limits = limits(10_000, Duration::from_secs(5), root.size() + 4096);
+        let e = expr(&format!("sum([{}])", vec!["len(_)"; 200].join(", ")));
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e).as_deref(), Ok(Value::Int(400_000))));
+        assert!(vm.memory < limits.max_memory);
+
+        // keys() builds a new list, which is charged.
+        let e = expr("len(keys(_))");
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e), Err(Fail::Limit(_))));
+    }
+
+    #[test]
+    fn test_memory_limit_charges_copies() {
+        let root = wide_map();
+        let limits = limits(10_000, Duration::from_secs(5), root.size() * 2 + 64);
+        let e = expr("[_, _]");
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e), Err(Fail::Limit(_))));
+        assert!(vm.memory > limits.max_memory);
+
+        let e = expr("len([_])");
+        let mut vm = new_vm(&root, &limits);
+        assert!(matches!(vm.eval(&e).as_deref(), Ok(Value::Int(1))));
+    }
+}
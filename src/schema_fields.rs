@@ -0,0 +1,135 @@
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use crate::locks::Relock;
+
+// [v3.6] Field names declared by the registered Pydantic schema, indexed by context path
+// ("" -> zones, "domain" -> fields of the domain model, "domain.users[*]" -> fields of the
+// model held in a list/dict). Built once in `set_schema`; proxies read it for
+// `schema_fields()` and to suggest "did you mean ...?" on a missing attribute.
+// NOTE: Only Pydantic v2 models (`model_fields`) are walked; Optional/Union members, list items
+// and dict values are followed, any other annotation ends the walk.
+
+/// Models nested deeper than this are not indexed.
+const MAX_DEPTH: usize = 32;
+
+#[derive(Default)]
+pub struct SchemaFields {
+    fields: Mutex<HashMap<String, Vec<String>>>,
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() { name.to_string() } else { format!("{path}.{name}") }
+}
+
+fn walk(typing: &Bound<'_, PyModule>, ann: &Bound<'_, PyAny>, path: &str, out: &mut HashMap<String, Vec<String>>, depth: usize) -> PyResult<()> {
+    if depth > MAX_DEPTH || out.contains_key(path) {
+        return Ok(());
+    }
+    if let Ok(model_fields) = ann.getattr("model_fields") {
+        if let Ok(model_fields) = model_fields.downcast::<pyo3::types::PyDict>() {
+            let mut names = Vec::with_capacity(model_fields.len());
+            let mut nested = Vec::new();
+            for (name, info) in model_fields.iter() {
+                let name: String = name.extract()?;
+                if let Ok(inner) = info.getattr("annotation") {
+                    nested.push((join(path, &name), inner));
+                }
+                names.push(name);
+            }
+            out.insert(path.to_string(), names);
+            for (child, inner) in nested {
+                walk(typing, &inner, &child, out, depth + 1)?;
+            }
+            return Ok(());
+        }
+    }
+    let origin = typing.call_method1("get_origin", (ann,))?;
+    if origin.is_none() {
+        return Ok(());
+    }
+    let args = typing.call_method1("get_args", (ann,))?;
+    let args: Vec<Bound<'_, PyAny>> = args.extract()?;
+    let builtins = ann.py().import("builtins")?;
+    if origin.is(&builtins.getattr("list")?) || origin.is(&builtins.getattr("set")?) || origin.is(&builtins.getattr("tuple")?) {
+        for arg in &args {
+            walk(typing, arg, &format!("{path}[*]"), out, depth + 1)?;
+        }
+    } else if origin.is(&builtins.getattr("dict")?) {
+        if let Some(value) = args.get(1) {
+            walk(typing, value, &format!("{path}[*]"), out, depth + 1)?;
+        }
+    } else {
+        // Union / Optional / Annotated: the first model-bearing member wins.
+        for arg in &args {
+            walk(typing, arg, path, out, depth + 1)?;
+        }
+    }
+    Ok(())
+}
+
+/// `domain.users[u1].name` -> `domain.users[*].name`.
+fn normalize(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                if depth == 0 {
+                    out.push_str("[*]");
+                }
+                depth += 1;
+            }
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb { prev } else { 1 + prev.min(cur).min(row[j]) };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+impl SchemaFields {
+    /// Rebuilds the index from `schema` (`None` clears it).
+    pub fn rebuild(&self, py: Python, schema: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        let mut out = HashMap::new();
+        if let Some(schema) = schema {
+            walk(&py.import("typing")?, schema, "", &mut out, 0)?;
+        }
+        *self.fields.relock() = out;
+        Ok(())
+    }
+
+    /// Declared field names at `path` (empty when the schema does not describe it).
+    pub fn fields(&self, path: &str) -> Vec<String> {
+        self.fields.relock().get(&normalize(path)).cloned().unwrap_or_default()
+    }
+
+    /// The declared field at `path` closest to `name`, if any is a plausible typo.
+    pub fn suggest(&self, path: &str, name: &str) -> Option<String> {
+        let fields = self.fields.relock();
+        let candidates = fields.get(&normalize(path))?;
+        let wanted = name.to_lowercase();
+        let limit = (name.chars().count() / 3).max(1);
+        candidates.iter()
+            .filter(|f| f.as_str() != name)
+            .map(|f| (distance(&wanted, &f.to_lowercase()), f))
+            .filter(|(d, _)| *d <= limit)
+            .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+            .map(|(_, f)| f.clone())
+    }
+}
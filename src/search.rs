@@ -0,0 +1,196 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
+use crate::indexes::{diff, Pattern};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Full-text search over designated string fields, for operational tooling ("where does
+// this id appear?"). `engine.enable_search(["domain.tickets.*.body", "domain.notes"])` keeps an
+// inverted index of the strings at (and nested under) those patterns; `engine.search("disk
+// full")` returns `(path, snippet, version)` for the strings containing every term, version
+// being the commit that last changed that string.
+// Terms are case-insensitive alphanumeric words. The index follows commits incrementally the
+// same way secondary indexes do (see indexes.rs); `_underscore` keys are skipped and
+// Private-zone patterns rejected, so redacted data never becomes searchable.
+
+const SNIPPET: usize = 60;
+
+struct Doc {
+    text: String,
+    version: u64,
+    terms: Vec<String>,
+}
+
+#[derive(Default)]
+struct Inverted {
+    patterns: Vec<Pattern>,
+    docs: BTreeMap<String, Doc>,
+    postings: HashMap<String, BTreeSet<String>>,
+}
+
+fn terms(text: &str) -> Vec<String> {
+    let mut out: Vec<String> = text.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()).map(str::to_lowercase).collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// Strings at or below `value`: `(path, text)`.
+fn strings(path: &str, value: &Bound<'_, PyAny>, out: &mut Vec<(String, String)>) -> PyResult<()> {
+    if let Ok(s) = value.downcast::<PyString>() {
+        out.push((path.to_string(), s.to_str()?.to_string()));
+    } else if let Ok(dict) = value.downcast::<PyDict>() {
+        for (k, v) in dict.iter() {
+            let key = k.str()?.to_string();
+            if !key.starts_with('_') {
+                strings(&format!("{path}.{key}"), &v, out)?;
+            }
+        }
+    } else if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        for (i, item) in value.iter()?.enumerate() {
+            strings(&format!("{path}[{i}]"), &item?, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Up to SNIPPET characters of `text` around the first occurrence of `term`.
+fn snippet(text: &str, term: &str) -> String {
+    let lower = text.to_lowercase();
+    let chars: Vec<char> = text.chars().collect();
+    // Lowercasing can change lengths, so the char offset is only approximate (and clamped).
+    let at = lower.find(term).map_or(0, |byte| lower[..byte].chars().count()).min(chars.len());
+    let start = at.saturating_sub(SNIPPET / 3).min(chars.len().saturating_sub(SNIPPET));
+    let end = (start + SNIPPET).min(chars.len());
+    let mut out: String = chars[start..end].iter().collect();
+    if start > 0 {
+        out.insert(0, '…');
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// `(replaced subtree path, strings now under it)` per changed leaf of each pattern.
+type Changes = Vec<(String, Vec<(String, String)>)>;
+
+impl Inverted {
+    fn remove_under(&mut self, prefix: &str) {
+        let stale: Vec<String> = self.docs.range(prefix.to_string()..)
+            .take_while(|(p, _)| p.starts_with(prefix))
+            .filter(|(p, _)| p.len() == prefix.len() || matches!(p.as_bytes()[prefix.len()], b'.' | b'['))
+            .map(|(p, _)| p.clone())
+            .collect();
+        for path in stale {
+            if let Some(doc) = self.docs.remove(&path) {
+                for term in &doc.terms {
+                    if let Some(paths) = self.postings.get_mut(term) {
+                        paths.remove(&path);
+                        if paths.is_empty() {
+                            self.postings.remove(term);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, changes: Changes, version: impl Fn(&str) -> u64) {
+        for (prefix, docs) in changes {
+            self.remove_under(&prefix);
+            for (path, text) in docs {
+                let terms = terms(&text);
+                for term in &terms {
+                    self.postings.entry(term.clone()).or_default().insert(path.clone());
+                }
+                let version = version(&path);
+                self.docs.insert(path, Doc { text, version, terms });
+            }
+        }
+    }
+}
+
+#[allow(clippy::similar_names)]
+fn collect(py: Python, patterns: &[Pattern], old: Option<&State>, new: &State, changed: Option<&[String]>) -> PyResult<Changes> {
+    let mut changes = Vec::new();
+    for pattern in patterns {
+        for (path, _, value) in diff(py, pattern, old, new, changed)? {
+            let mut docs = Vec::new();
+            if let Some(value) = value {
+                strings(&path, &value, &mut docs)?;
+            }
+            changes.push((path, docs));
+        }
+    }
+    Ok(changes)
+}
+
+/// Commit that last touched the `zone.field` holding `path` (the state version if unknown).
+fn version_of(state: &State, path: &str) -> u64 {
+    let root: String = path.split(['.', '[']).take(2).collect::<Vec<_>>().join(".");
+    state.key_last_modified.get(root.as_str()).copied().unwrap_or(state.version)
+}
+
+#[derive(Default)]
+pub struct TextIndex {
+    inner: Mutex<Inverted>,
+}
+
+impl TextIndex {
+    /// Index the strings at `patterns` (replacing the previous set) from `state`.
+    pub fn configure(&self, py: Python, patterns: Vec<Pattern>, state: &State) -> PyResult<()> {
+        // NOTE: Never hold the index lock while walking Python objects.
+        let changes = collect(py, &patterns, None, state, None)?;
+        let mut inverted = Inverted { patterns, ..Inverted::default() };
+        inverted.apply(changes, |path| version_of(state, path));
+        *self.inner.relock() = inverted;
+        Ok(())
+    }
+
+    #[allow(clippy::similar_names)]
+    pub fn committed(&self, py: Python, old: &State, new: &State, changed: &[String]) -> PyResult<()> {
+        let patterns = self.inner.relock().patterns.clone();
+        if patterns.is_empty() {
+            return Ok(());
+        }
+        let changes = collect(py, &patterns, Some(old), new, Some(changed))?;
+        self.inner.relock().apply(changes, |_| new.version);
+        Ok(())
+    }
+
+    pub fn rebuild(&self, py: Python, state: &State) -> PyResult<()> {
+        let patterns = self.inner.relock().patterns.clone();
+        if patterns.is_empty() {
+            return Ok(());
+        }
+        self.configure(py, patterns, state)
+    }
+
+    /// `(path, snippet, version)` of the strings containing every term of `query`.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(String, String, u64)> {
+        let wanted = terms(query);
+        // Snippets centre on the first word as typed.
+        let lead = query.split(|c: char| !c.is_alphanumeric()).find(|t| !t.is_empty()).map(str::to_lowercase).unwrap_or_default();
+        let inverted = self.inner.relock();
+        let Some(first) = wanted.first() else { return Vec::new() };
+        let Some(candidates) = inverted.postings.get(first) else { return Vec::new() };
+        candidates.iter()
+            .filter(|path| wanted[1..].iter().all(|t| inverted.postings.get(t).is_some_and(|p| p.contains(*path))))
+            .take(limit)
+            .filter_map(|path| inverted.docs.get(path).map(|doc| (path.clone(), snippet(&doc.text, &lead), doc.version)))
+            .collect()
+    }
+
+    /// `{patterns, documents, terms}`.
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let inverted = self.inner.relock();
+        let out = PyDict::new_bound(py);
+        out.set_item("patterns", inverted.patterns.iter().map(|p| p.text.clone()).collect::<Vec<_>>())?;
+        out.set_item("documents", inverted.docs.len())?;
+        out.set_item("terms", inverted.postings.len())?;
+        Ok(out.into_any().unbind())
+    }
+}
@@ -0,0 +1,118 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
+use pyo3::types::{PyDict, PyMapping};
+use std::collections::BTreeMap;
+
+// [v3.6] Bootstrapping state from outside the process: `engine.seed_from(provider)` asks a
+// provider for `{path: value}` and writes it in one admin transaction (actor "seed:<name>"), so
+// Meta (`meta_*`) and Private (`internal_*`) fields can be filled before processes run. A
+// provider is anything with `fetch() -> mapping` and an optional `name`; built-ins:
+//   EnvProvider(mapping={"meta_db.url": "DB_URL"})   named variables, missing ones skipped
+//                                                    (`required=True` raises instead)
+//   EnvProvider(prefix="APP__")                      every APP__* variable; the rest of the
+//                                                    name split on "__" and lowercased is the
+//                                                    path (APP__META_DB__URL -> meta_db.url)
+//   CallbackProvider(fn, name="vault")               `fn() -> mapping`, e.g. a secret manager
+// The audit log records which paths were injected ("STATE_SEEDED"), never their values.
+
+/// [v3.6] Reads `os.environ` (so changes made from Python are seen).
+#[pyclass(module = "theus_core", frozen)]
+pub struct EnvProvider {
+    mapping: BTreeMap<String, String>,
+    prefix: Option<String>,
+    required: bool,
+}
+
+#[pymethods]
+impl EnvProvider {
+    #[new]
+    #[pyo3(signature = (mapping=None, prefix=None, required=false))]
+    fn new(mapping: Option<BTreeMap<String, String>>, prefix: Option<String>, required: bool) -> PyResult<Self> {
+        if mapping.is_none() && prefix.as_deref().is_none_or(str::is_empty) {
+            return Err(PyValueError::new_err("EnvProvider needs a mapping {path: VAR} or a non-empty prefix"));
+        }
+        Ok(EnvProvider { mapping: mapping.unwrap_or_default(), prefix, required })
+    }
+
+    #[getter]
+    #[allow(clippy::unused_self)]
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn fetch<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let environ = py.import_bound("os")?.getattr("environ")?;
+        let out = PyDict::new_bound(py);
+        if let Some(prefix) = &self.prefix {
+            for item in environ.call_method0("items")?.iter()? {
+                let (var, value): (String, String) = item?.extract()?;
+                if let Some(rest) = var.strip_prefix(prefix.as_str()) {
+                    out.set_item(rest.split("__").map(str::to_lowercase).collect::<Vec<_>>().join("."), value)?;
+                }
+            }
+        }
+        let mut missing = Vec::new();
+        for (path, var) in &self.mapping {
+            match environ.call_method1("get", (var,))? {
+                value if value.is_none() => missing.push(var.as_str()),
+                value => out.set_item(path, value)?,
+            }
+        }
+        if self.required && !missing.is_empty() {
+            return Err(PyKeyError::new_err(format!("Missing environment variables: {}", missing.join(", "))));
+        }
+        Ok(out)
+    }
+}
+
+/// [v3.6] Seeds whatever `callback()` returns.
+#[pyclass(module = "theus_core", frozen)]
+pub struct CallbackProvider {
+    callback: PyObject,
+    name: String,
+}
+
+#[pymethods]
+impl CallbackProvider {
+    #[new]
+    #[pyo3(signature = (callback, name="callback".to_string()))]
+    fn new(callback: &Bound<'_, PyAny>, name: String) -> PyResult<Self> {
+        if !callback.is_callable() {
+            return Err(PyTypeError::new_err("CallbackProvider needs a callable returning {path: value}"));
+        }
+        Ok(CallbackProvider { callback: callback.clone().unbind(), name })
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.callback.bind(py).call0()
+    }
+}
+
+/// Ask `provider` for its values: its name and `(path, value)` pairs sorted by path.
+pub fn fetch(provider: &Bound<'_, PyAny>) -> PyResult<(String, Vec<(String, PyObject)>)> {
+    let name = match provider.getattr("name") {
+        Ok(name) if !name.is_none() => name.str()?.to_string(),
+        _ => provider.get_type().name()?.to_string(),
+    };
+    let values = provider.call_method0("fetch")?;
+    let values = values.downcast::<PyMapping>()
+        .map_err(|_| PyTypeError::new_err(format!("Seed provider '{name}' must return a mapping {{path: value}}")))?;
+    let mut pairs = BTreeMap::new();
+    for item in values.items()?.iter() {
+        let (path, value): (String, PyObject) = item.extract()?;
+        pairs.insert(path, value);
+    }
+    // A value and a field below it would overwrite each other depending on order.
+    for path in pairs.keys() {
+        let mut ancestors = path.match_indices('.').map(|(i, _)| &path[..i]);
+        if let Some(parent) = ancestors.find(|p| pairs.contains_key(*p)) {
+            return Err(PyValueError::new_err(format!("Seed provider '{name}' returned both '{parent}' and '{path}'")));
+        }
+    }
+    Ok((name, pairs.into_iter().collect()))
+}
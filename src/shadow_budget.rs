@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+// [v3.6] Memory bound for a transaction's shadow cache. Every deepcopied shadow is admitted
+// with its estimated size and a use tick (bumped on each cache hit). Once the total exceeds
+// `engine.set_shadow_cache_limit(max_bytes)`, the least recently used entries are offered for
+// eviction. The transaction only drops an entry that is provably disposable:
+// - not pinned: no logged delta overlaps its path,
+// - untouched: the working copy still equals the committed value,
+// - unreachable: nothing but the cache (and its pooled proxy) references the working copy.
+// An evicted path is simply shadowed again on its next access.
+
+struct Entry {
+    path: String,
+    bytes: u64,
+    last_used: u64,
+}
+
+#[derive(Default)]
+pub struct ShadowBudget {
+    limit: Option<u64>,
+    entries: HashMap<usize, Entry>,
+    bytes: u64,
+    tick: u64,
+    evicted: u64,
+}
+
+impl ShadowBudget {
+    pub fn new(limit: Option<u64>) -> Self {
+        ShadowBudget { limit, ..Self::default() }
+    }
+
+    /// Sizes are only estimated when a limit is set.
+    pub fn bounded(&self) -> bool {
+        self.limit.is_some()
+    }
+
+    pub fn admit(&mut self, id: usize, path: String, bytes: u64) {
+        self.tick += 1;
+        if let Some(old) = self.entries.insert(id, Entry { path, bytes, last_used: self.tick }) {
+            self.bytes -= old.bytes;
+        }
+        self.bytes += bytes;
+    }
+
+    pub fn touch(&mut self, id: usize) {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.last_used = self.tick;
+        }
+    }
+
+    pub fn over(&self) -> bool {
+        self.limit.is_some_and(|limit| self.bytes > limit)
+    }
+
+    /// `(id, path)` of every entry but `keep`, least recently used first.
+    pub fn candidates(&self, keep: usize) -> Vec<(usize, String)> {
+        let mut out: Vec<(u64, usize, &String)> = self.entries.iter()
+            .filter(|(id, _)| **id != keep)
+            .map(|(id, e)| (e.last_used, *id, &e.path))
+            .collect();
+        out.sort_unstable();
+        out.into_iter().map(|(_, id, path)| (id, path.clone())).collect()
+    }
+
+    pub fn evict(&mut self, id: usize) {
+        if let Some(entry) = self.entries.remove(&id) {
+            self.bytes -= entry.bytes;
+            self.evicted += 1;
+        }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+}
+
+/// `a` and `b` name the same value or one contains the other (`.` or `[` separated).
+pub fn overlaps(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.starts_with(short) && matches!(long.as_bytes().get(short.len()), None | Some(b'.' | b'['))
+}
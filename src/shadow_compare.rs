@@ -0,0 +1,135 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use rayon::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// [v3.6] Parallel shadow comparison for `infer_shadow_deltas`. Plain-data values (None, bool,
+// int, float, str, bytes, list, tuple, dict - exact types only) are serialized under the GIL
+// into a canonical form whose byte equality is exactly Python `==` (1 == 1.0 == True, dict
+// order ignored). The buffers are then hashed and compared on rayon workers with the GIL
+// released. Anything else (custom `__eq__`, sets, NaN, big ints, ...) is ambiguous and left
+// to Python `==`.
+// `fingerprint` is the opt-in shortcut (`engine.set_shadow_hashing(True)`): an XXH3 hash of
+// the shadow recorded when it is created and recomputed at inference; a match skips the
+// comparison entirely. Non-plain values are hashed from their pickle, unpicklable ones get none.
+
+/// Containers nested deeper than this are left to Python `==`.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Equal,
+    Different,
+    Ambiguous,
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+/// Appends the canonical encoding of `value`; false when it is not plain data.
+fn canonical(value: &Bound<'_, PyAny>, out: &mut Vec<u8>, depth: usize) -> bool {
+    if depth > MAX_DEPTH {
+        return false;
+    }
+    if value.is_none() {
+        out.push(0);
+    } else if let Ok(b) = value.downcast_exact::<PyBool>() {
+        out.push(1);
+        out.extend_from_slice(&i64::from(b.is_true()).to_le_bytes());
+    } else if let Ok(i) = value.downcast_exact::<PyInt>() {
+        let Ok(n) = i.extract::<i64>() else { return false };
+        out.push(1);
+        out.extend_from_slice(&n.to_le_bytes());
+    } else if let Ok(f) = value.downcast_exact::<PyFloat>() {
+        let v = f.value();
+        if v.is_nan() {
+            return false;
+        }
+        // Integral floats share the int encoding (1.0 == 1, -0.0 == 0).
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        if v.fract() == 0.0 && v.abs() < i64::MAX as f64 {
+            out.push(1);
+            out.extend_from_slice(&(v as i64).to_le_bytes());
+        } else {
+            out.push(2);
+            out.extend_from_slice(&v.to_bits().to_le_bytes());
+        }
+    } else if let Ok(s) = value.downcast_exact::<PyString>() {
+        let Ok(s) = s.to_str() else { return false };
+        out.push(3);
+        put_len(out, s.len());
+        out.extend_from_slice(s.as_bytes());
+    } else if let Ok(b) = value.downcast_exact::<PyBytes>() {
+        out.push(4);
+        put_len(out, b.as_bytes().len());
+        out.extend_from_slice(b.as_bytes());
+    } else if let Ok(l) = value.downcast_exact::<PyList>() {
+        out.push(5);
+        put_len(out, l.len());
+        return l.iter().all(|item| canonical(&item, out, depth + 1));
+    } else if let Ok(t) = value.downcast_exact::<PyTuple>() {
+        out.push(6);
+        put_len(out, t.len());
+        return t.iter().all(|item| canonical(&item, out, depth + 1));
+    } else if let Ok(d) = value.downcast_exact::<PyDict>() {
+        let mut entries = Vec::with_capacity(d.len());
+        for (k, v) in d.iter() {
+            let (mut key, mut val) = (Vec::new(), Vec::new());
+            if !canonical(&k, &mut key, depth + 1) || !canonical(&v, &mut val, depth + 1) {
+                return false;
+            }
+            entries.push((key, val));
+        }
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        out.push(7);
+        put_len(out, entries.len());
+        for (key, val) in entries {
+            out.extend_from_slice(&key);
+            out.extend_from_slice(&val);
+        }
+    } else {
+        return false;
+    }
+    true
+}
+
+fn digest(bytes: &[u8]) -> u64 {
+    let mut h = DefaultHasher::new();
+    bytes.hash(&mut h);
+    h.finish()
+}
+
+/// Equality verdict for each `(original, current)` pair.
+pub fn compare_all(py: Python, pairs: &[(PyObject, PyObject)]) -> Vec<Verdict> {
+    let encoded: Vec<Option<(Vec<u8>, Vec<u8>)>> = pairs.iter()
+        .map(|(a, b)| {
+            let (mut ea, mut eb) = (Vec::new(), Vec::new());
+            (canonical(a.bind(py), &mut ea, 0) && canonical(b.bind(py), &mut eb, 0)).then_some((ea, eb))
+        })
+        .collect();
+    py.allow_threads(|| {
+        encoded.par_iter()
+            .map(|pair| match pair {
+                None => Verdict::Ambiguous,
+                // Equal digests are confirmed byte-for-byte (collisions must not hide a write).
+                Some((a, b)) if a.len() == b.len() && digest(a) == digest(b) && a == b => Verdict::Equal,
+                Some(_) => Verdict::Different,
+            })
+            .collect()
+    })
+}
+
+/// [v3.6] XXH3 content hash of `value`: its canonical encoding, or its pickle when it is not
+/// plain data. None when it cannot be serialized.
+pub fn fingerprint(value: &Bound<'_, PyAny>) -> Option<u64> {
+    let mut out = vec![b'c'];
+    if !canonical(value, &mut out, 0) {
+        let pickled = value.py().import("pickle").and_then(|m| m.call_method1("dumps", (value,))).ok()?;
+        out.clear();
+        out.push(b'p');
+        out.extend_from_slice(pickled.downcast::<PyBytes>().ok()?.as_bytes());
+    }
+    Some(twox_hash::XxHash3_64::oneshot(&out))
+}
@@ -0,0 +1,248 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use crate::locks::Relock;
+
+// [v3.6] At-most-once consumption of committed signals (`engine.signals.claim(n, consumer)`).
+// Every signal published by a commit is also queued here. `claim` atomically leases the
+// oldest unclaimed entries to one consumer; each lease bumps the entry's `claim_version`,
+// and `ack` / `renew` / `release` only act on the current version, so a consumer whose
+// lease lapsed (and was reclaimed by someone else) cannot ack twice.
+// NOTE: The queue is bounded; when full, the oldest *unclaimed* entry is dropped (counted in
+// `stats()["dropped"]`). Lease deadlines use the monotonic engine clock.
+
+pub const DEFAULT_CAPACITY: usize = 10_000;
+pub const DEFAULT_LEASE_MS: u64 = 30_000;
+
+struct Lease {
+    consumer: String,
+    deadline_ms: u64,
+}
+
+struct Entry {
+    seq: u64,
+    topic: String,
+    payload: PyObject,
+    version: u64,
+    claim_version: u64,
+    attempts: u32,
+    lease: Option<Lease>,
+}
+
+struct Inner {
+    entries: VecDeque<Entry>,
+    next_seq: u64,
+    capacity: usize,
+    lease_ms: u64,
+    enqueued: u64,
+    acked: u64,
+    released: u64,
+    dropped: u64,
+    reclaimed: u64,
+    /// Leases lost per consumer (crashed or too slow).
+    reclaimed_from: BTreeMap<String, u64>,
+}
+
+impl Inner {
+    /// Returns expired leases to the pool.
+    fn reclaim(&mut self, now: u64) {
+        for entry in &mut self.entries {
+            if entry.lease.as_ref().is_some_and(|l| l.deadline_ms <= now) {
+                let lease = entry.lease.take().unwrap();
+                self.reclaimed += 1;
+                *self.reclaimed_from.entry(lease.consumer).or_default() += 1;
+            }
+        }
+    }
+
+    fn position(&self, seq: u64, claim_version: u64) -> Option<usize> {
+        self.entries.iter().position(|e| e.seq == seq && e.claim_version == claim_version && e.lease.is_some())
+    }
+}
+
+/// `engine.signals`: claimable queue of committed signals.
+#[pyclass(module = "theus_core")]
+#[derive(Clone)]
+pub struct SignalQueue {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Default for SignalQueue {
+    fn default() -> Self {
+        SignalQueue {
+            inner: Arc::new(Mutex::new(Inner {
+                entries: VecDeque::new(),
+                next_seq: 0,
+                capacity: DEFAULT_CAPACITY,
+                lease_ms: DEFAULT_LEASE_MS,
+                enqueued: 0,
+                acked: 0,
+                released: 0,
+                dropped: 0,
+                reclaimed: 0,
+                reclaimed_from: BTreeMap::new(),
+            })),
+        }
+    }
+}
+
+fn lease_ms(seconds: f64) -> PyResult<u64> {
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Err(PyValueError::new_err("lease_s must be positive"));
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok((seconds * 1000.0) as u64)
+}
+
+impl SignalQueue {
+    /// Queues the signals of a commit at state `version` (a `{topic: payload}` dict or a list of them).
+    pub fn enqueue(&self, py: Python, signal: &Bound<'_, PyAny>, version: u64) -> PyResult<()> {
+        let mut batch = Vec::new();
+        let dicts: Vec<Bound<'_, PyAny>> = match signal.downcast::<PyList>() {
+            Ok(list) => list.iter().collect(),
+            Err(_) => vec![signal.clone()],
+        };
+        for item in dicts {
+            let Ok(d) = item.downcast::<PyDict>() else { continue };
+            for (k, v) in d {
+                batch.push((k.extract::<String>()?, v.unbind()));
+            }
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let mut inner = self.inner.relock();
+        for (topic, payload) in batch {
+            if inner.entries.len() >= inner.capacity {
+                if let Some(idx) = inner.entries.iter().position(|e| e.lease.is_none()) {
+                    inner.entries.remove(idx);
+                    inner.dropped += 1;
+                } else {
+                    inner.dropped += 1;
+                    continue;
+                }
+            }
+            inner.next_seq += 1;
+            let seq = inner.next_seq;
+            inner.entries.push_back(Entry { seq, topic, payload: payload.clone_ref(py), version, claim_version: 0, attempts: 0, lease: None });
+            inner.enqueued += 1;
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl SignalQueue {
+    /// Lease up to `n` unclaimed signals (oldest first, optionally only `topic`) to
+    /// `consumer_id` for `lease_s` seconds (default: `set_lease`). Returns
+    /// `[{seq, topic, payload, version, claim_version, attempts}]`; pass `seq` and
+    /// `claim_version` back to `ack` / `renew` / `release`.
+    #[pyo3(signature = (n, consumer_id, lease_s=None, topic=None))]
+    fn claim(&self, py: Python, n: usize, consumer_id: &str, lease_s: Option<f64>, topic: Option<&str>) -> PyResult<Vec<PyObject>> {
+        let lease = lease_s.map(lease_ms).transpose()?;
+        let now = crate::clock::monotonic_ms();
+        let mut inner = self.inner.relock();
+        inner.reclaim(now);
+        let deadline_ms = now + lease.unwrap_or(inner.lease_ms);
+        let mut out = Vec::new();
+        for entry in &mut inner.entries {
+            if out.len() >= n {
+                break;
+            }
+            if entry.lease.is_some() || topic.is_some_and(|t| t != entry.topic) {
+                continue;
+            }
+            entry.claim_version += 1;
+            entry.attempts += 1;
+            entry.lease = Some(Lease { consumer: consumer_id.to_string(), deadline_ms });
+            let d = PyDict::new_bound(py);
+            d.set_item("seq", entry.seq)?;
+            d.set_item("topic", &entry.topic)?;
+            d.set_item("payload", &entry.payload)?;
+            d.set_item("version", entry.version)?;
+            d.set_item("claim_version", entry.claim_version)?;
+            d.set_item("attempts", entry.attempts)?;
+            out.push(d.into_any().unbind());
+        }
+        Ok(out)
+    }
+
+    /// Mark a claimed signal processed (removes it). False if the lease was lost.
+    fn ack(&self, seq: u64, claim_version: u64) -> bool {
+        let mut inner = self.inner.relock();
+        inner.reclaim(crate::clock::monotonic_ms());
+        let Some(idx) = inner.position(seq, claim_version) else { return false };
+        inner.entries.remove(idx);
+        inner.acked += 1;
+        true
+    }
+
+    /// Extend a lease by `lease_s` seconds from now. False if the lease was lost.
+    #[pyo3(signature = (seq, claim_version, lease_s=None))]
+    fn renew(&self, seq: u64, claim_version: u64, lease_s: Option<f64>) -> PyResult<bool> {
+        let lease = lease_s.map(lease_ms).transpose()?;
+        let now = crate::clock::monotonic_ms();
+        let mut inner = self.inner.relock();
+        inner.reclaim(now);
+        let Some(idx) = inner.position(seq, claim_version) else { return Ok(false) };
+        let deadline_ms = now + lease.unwrap_or(inner.lease_ms);
+        if let Some(l) = inner.entries[idx].lease.as_mut() {
+            l.deadline_ms = deadline_ms;
+        }
+        Ok(true)
+    }
+
+    /// Give a claimed signal back without processing it. False if the lease was lost.
+    fn release(&self, seq: u64, claim_version: u64) -> bool {
+        let mut inner = self.inner.relock();
+        inner.reclaim(crate::clock::monotonic_ms());
+        let Some(idx) = inner.position(seq, claim_version) else { return false };
+        inner.entries[idx].lease = None;
+        inner.released += 1;
+        true
+    }
+
+    /// Default lease for `claim` / `renew`.
+    fn set_lease(&self, lease_s: f64) -> PyResult<()> {
+        self.inner.relock().lease_ms = lease_ms(lease_s)?;
+        Ok(())
+    }
+
+    fn set_capacity(&self, capacity: usize) -> PyResult<()> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("capacity must be positive"));
+        }
+        self.inner.relock().capacity = capacity;
+        Ok(())
+    }
+
+    /// Unclaimed signals.
+    fn __len__(&self) -> usize {
+        let mut inner = self.inner.relock();
+        inner.reclaim(crate::clock::monotonic_ms());
+        inner.entries.iter().filter(|e| e.lease.is_none()).count()
+    }
+
+    /// `{pending, claimed, enqueued, acked, released, dropped, reclaimed, reclaimed_from,
+    /// capacity, lease_s}`; `reclaimed_from` counts expired leases per consumer.
+    fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let mut inner = self.inner.relock();
+        inner.reclaim(crate::clock::monotonic_ms());
+        let claimed = inner.entries.iter().filter(|e| e.lease.is_some()).count();
+        let d = PyDict::new_bound(py);
+        d.set_item("pending", inner.entries.len() - claimed)?;
+        d.set_item("claimed", claimed)?;
+        d.set_item("enqueued", inner.enqueued)?;
+        d.set_item("acked", inner.acked)?;
+        d.set_item("released", inner.released)?;
+        d.set_item("dropped", inner.dropped)?;
+        d.set_item("reclaimed", inner.reclaimed)?;
+        d.set_item("reclaimed_from", inner.reclaimed_from.clone().into_py(py))?;
+        d.set_item("capacity", inner.capacity)?;
+        #[allow(clippy::cast_precision_loss)]
+        d.set_item("lease_s", inner.lease_ms as f64 / 1000.0)?;
+        Ok(d.into_any().unbind())
+    }
+}
@@ -0,0 +1,212 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use crate::errors::{coded, with_fields};
+use crate::messages;
+use crate::outbox::SerializationError;
+
+// [v3.6] Stable snapshot container for exchanging state between services and across
+// upgrades (`engine.export_snapshot()` / `engine.import_snapshot(blob)`). Layout, all integers
+// little-endian:
+//   offset  size  field
+//   0       8     magic b"THEUSSNP"
+//   8       2     container version (u16, currently 1)
+//   10      2     flags (u16, reserved, 0)
+//   12      4     header length H (u32)
+//   16      H     header (msgpack map, below)
+//   16+H    ...   sections, concatenated in header order
+//   end-32  32    SHA-256 of every preceding byte
+// Header: { "producer": "theus-core <version>", "created_ms", "state_format" (state_codec
+// FORMAT), "state_version", "config": {strict_guards, strict_cas, isolation}, "config_digest",
+// "schema_fingerprint" (SHA-256 of the sorted-key JSON schema, or None), "zone_policies"
+// ({path: caps} physics overrides), "sections": [{name, length, sha256}] }.
+// Sections: "state" (a `dumps_state()` payload) and, with a schema, "schema" (its JSON schema).
+// Readers must reject unknown container versions and may ignore unknown header keys and
+// sections; writers only ever add keys and sections within a container version.
+// Loading validates magic, version, checksums and section layout (SnapshotFormatError), then -
+// with `strict=True` - the schema fingerprint and zone policies against this engine
+// (SnapshotIncompatibleError). The config digest is informational.
+
+pyo3::create_exception!(theus_core, SnapshotFormatError, SerializationError);
+pyo3::create_exception!(theus_core, SnapshotIncompatibleError, SerializationError);
+
+const MAGIC: &[u8; 8] = b"THEUSSNP";
+pub const CONTAINER_VERSION: u16 = 1;
+const PREAMBLE: usize = 16;
+const TRAILER: usize = 32;
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    crate::cap_tokens::hex(&Sha256::digest(bytes))
+}
+
+fn invalid(py: Python, code: &'static str, detail: &str) -> PyErr {
+    with_fields(py, coded::<SnapshotFormatError>(py, messages::render(code, &[("detail", &detail)])), &[
+        ("reason", detail.into_py(py)),
+    ])
+}
+
+fn incompatible(py: Python, detail: &str, expected: PyObject, found: PyObject) -> PyErr {
+    let message = messages::render(messages::SNAPSHOT_INCOMPATIBLE, &[("detail", &detail)]);
+    with_fields(py, coded::<SnapshotIncompatibleError>(py, message), &[
+        ("reason", detail.into_py(py)),
+        ("expected", expected),
+        ("found", found),
+    ])
+}
+
+/// What the engine contributes to a container besides the state payload.
+pub struct EngineProfile {
+    pub config: BTreeMap<&'static str, String>,
+    pub schema_json: Option<String>,
+    pub zone_policies: BTreeMap<String, u8>,
+}
+
+impl EngineProfile {
+    pub fn config_digest(&self) -> String {
+        let canonical: Vec<String> = self.config.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        sha256_hex(canonical.join(";").as_bytes())
+    }
+
+    pub fn schema_fingerprint(&self) -> Option<String> {
+        self.schema_json.as_deref().map(|s| sha256_hex(s.as_bytes()))
+    }
+}
+
+/// Sorted-key JSON schema of a pydantic model (v2 `model_json_schema`, v1 `schema`), else
+/// its qualified name.
+pub fn schema_json(py: Python, schema: &Bound<'_, PyAny>) -> PyResult<String> {
+    let json = py.import_bound("json")?;
+    let kwargs = PyDict::new_bound(py);
+    kwargs.set_item("sort_keys", true)?;
+    for method in ["model_json_schema", "schema"] {
+        if let Ok(f) = schema.getattr(method) {
+            if let Ok(doc) = f.call0() {
+                return json.call_method("dumps", (doc,), Some(&kwargs))?.extract();
+            }
+        }
+    }
+    let module: String = schema.getattr("__module__").and_then(|m| m.extract()).unwrap_or_default();
+    let name: String = schema.getattr("__qualname__").and_then(|m| m.extract()).unwrap_or_else(|_| schema.to_string());
+    json.call_method("dumps", (format!("{module}.{name}"),), Some(&kwargs))?.extract()
+}
+
+pub fn encode(py: Python, profile: &EngineProfile, state_version: u64, state: &[u8]) -> PyResult<Vec<u8>> {
+    let mut sections: Vec<(&str, &[u8])> = vec![("state", state)];
+    if let Some(schema) = &profile.schema_json {
+        sections.push(("schema", schema.as_bytes()));
+    }
+    let header = PyDict::new_bound(py);
+    header.set_item("producer", format!("theus-core {}", env!("CARGO_PKG_VERSION")))?;
+    header.set_item("created_ms", crate::outbox::now_ms())?;
+    header.set_item("state_format", crate::state_codec::FORMAT)?;
+    header.set_item("state_version", state_version)?;
+    let config = PyDict::new_bound(py);
+    for (k, v) in &profile.config {
+        config.set_item(k, v)?;
+    }
+    header.set_item("config", config)?;
+    header.set_item("config_digest", profile.config_digest())?;
+    header.set_item("schema_fingerprint", profile.schema_fingerprint())?;
+    header.set_item("zone_policies", profile.zone_policies.clone())?;
+    let rows = PyList::empty_bound(py);
+    for (name, bytes) in &sections {
+        let row = PyDict::new_bound(py);
+        row.set_item("name", *name)?;
+        row.set_item("length", bytes.len())?;
+        row.set_item("sha256", sha256_hex(bytes))?;
+        rows.append(row)?;
+    }
+    header.set_item("sections", rows)?;
+    let header = crate::state_codec::encode_value(py, header.as_any())?;
+    let header_len = u32::try_from(header.len()).map_err(|_| SerializationError::new_err("Snapshot header too large"))?;
+
+    let mut out = Vec::with_capacity(PREAMBLE + header.len() + sections.iter().map(|s| s.1.len()).sum::<usize>() + TRAILER);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&CONTAINER_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&header_len.to_le_bytes());
+    out.extend_from_slice(&header);
+    for (_, bytes) in &sections {
+        out.extend_from_slice(bytes);
+    }
+    let checksum = Sha256::digest(&out);
+    out.extend_from_slice(&checksum);
+    Ok(out)
+}
+
+/// A structurally valid container: its header and sections by name.
+pub struct Container<'a, 'py> {
+    pub header: Bound<'py, PyDict>,
+    pub sections: BTreeMap<String, &'a [u8]>,
+}
+
+pub fn decode<'a, 'py>(py: Python<'py>, blob: &'a [u8]) -> PyResult<Container<'a, 'py>> {
+    if blob.len() < PREAMBLE + TRAILER || &blob[..8] != MAGIC {
+        return Err(invalid(py, messages::SNAPSHOT_INVALID, "missing THEUSSNP magic or truncated preamble"));
+    }
+    let version = u16::from_le_bytes([blob[8], blob[9]]);
+    if version != CONTAINER_VERSION {
+        let message = messages::render(messages::SNAPSHOT_VERSION, &[("found", &version), ("supported", &CONTAINER_VERSION)]);
+        return Err(with_fields(py, coded::<SnapshotFormatError>(py, message), &[
+            ("reason", "unsupported container version".into_py(py)),
+        ]));
+    }
+    let (body, trailer) = blob.split_at(blob.len() - TRAILER);
+    if Sha256::digest(body).as_slice() != trailer {
+        return Err(invalid(py, messages::SNAPSHOT_CORRUPT, "checksum mismatch (truncated or modified)"));
+    }
+    let header_len = u32::from_le_bytes([blob[12], blob[13], blob[14], blob[15]]) as usize;
+    let Some(header_bytes) = body.get(PREAMBLE..PREAMBLE + header_len) else {
+        return Err(invalid(py, messages::SNAPSHOT_CORRUPT, "header length exceeds the container"));
+    };
+    let header = crate::state_codec::decode_value(py, header_bytes)
+        .and_then(|h| h.downcast_into::<PyDict>().map_err(|_| SerializationError::new_err("not a map")))
+        .map_err(|e| invalid(py, messages::SNAPSHOT_CORRUPT, &format!("unreadable header ({e})")))?;
+    let rows = header.get_item("sections")?
+        .ok_or_else(|| invalid(py, messages::SNAPSHOT_CORRUPT, "header has no 'sections'"))?;
+    let mut sections = BTreeMap::new();
+    let mut offset = PREAMBLE + header_len;
+    for row in rows.iter()? {
+        let row = row?;
+        let name: String = row.get_item("name")?.extract()?;
+        let length: usize = row.get_item("length")?.extract()?;
+        let expected: String = row.get_item("sha256")?.extract()?;
+        let Some(bytes) = body.get(offset..offset + length) else {
+            return Err(invalid(py, messages::SNAPSHOT_CORRUPT, &format!("section '{name}' exceeds the container")));
+        };
+        if sha256_hex(bytes) != expected {
+            return Err(invalid(py, messages::SNAPSHOT_CORRUPT, &format!("section '{name}' checksum mismatch")));
+        }
+        sections.insert(name, bytes);
+        offset += length;
+    }
+    if offset != body.len() {
+        return Err(invalid(py, messages::SNAPSHOT_CORRUPT, "trailing bytes after the last section"));
+    }
+    let format: Option<String> = header.get_item("state_format")?.map(|f| f.extract()).transpose()?;
+    if format.as_deref() != Some(crate::state_codec::FORMAT) || !sections.contains_key("state") {
+        return Err(invalid(py, messages::SNAPSHOT_INVALID, &format!(
+            "state payload format {format:?} is not '{}'", crate::state_codec::FORMAT
+        )));
+    }
+    Ok(Container { header, sections })
+}
+
+/// Strict checks of a container against this engine's schema and zone policies.
+pub fn check_compatible(py: Python, container: &Container, profile: &EngineProfile) -> PyResult<()> {
+    let found: Option<String> = container.header.get_item("schema_fingerprint")?.map(|f| f.extract()).transpose()?.flatten();
+    if let Some(expected) = profile.schema_fingerprint() {
+        if found.as_deref() != Some(expected.as_str()) {
+            let detail = if found.is_some() { "schema fingerprint differs from this engine's schema" } else { "snapshot embeds no schema but this engine has one" };
+            return Err(incompatible(py, detail, expected.into_py(py), found.into_py(py)));
+        }
+    }
+    let policies: BTreeMap<String, u8> = container.header.get_item("zone_policies")?.map(|p| p.extract()).transpose()?.unwrap_or_default();
+    for (path, caps) in &policies {
+        if let Some(mine) = profile.zone_policies.get(path).filter(|mine| *mine != caps) {
+            return Err(incompatible(py, &format!("zone policy of '{path}' differs (caps {mine} here, {caps} in snapshot)"), mine.into_py(py), caps.into_py(py)));
+        }
+    }
+    Ok(())
+}
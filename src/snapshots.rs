@@ -0,0 +1,148 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use crate::outbox::SerializationError;
+use crate::state_codec::DecodedState;
+use crate::structures::State;
+
+// [v3.6] Incremental snapshots. `engine.dumps_state()` takes a full snapshot; each
+// `engine.dumps_incremental()` afterwards records only what changed since the previous
+// snapshot of the chain, using the per-key versions every commit already tracks
+// (`key_last_modified`, zone.field granularity): changed fields of dict zones, whole values
+// of other zones, and the current key lists so deletions replay too.
+// `engine.load_snapshot(full, *incrementals)` replays the chain. Every incremental names its
+// base by version and by the SHA-256 of the base snapshot's bytes, so a missing, reordered or
+// altered link fails the load instead of producing a silently wrong state.
+// Layout (msgpack map via state_codec): { "format", "base_version", "base_digest", "version",
+// "zones", "data", "heavy_zones", "heavy", "key_last_modified", "op_ids", "timers" }.
+// NOTE: The Heavy zone is recorded per zone (its fields are not versioned separately).
+
+pub const FORMAT: &str = "theus-state-delta/1";
+
+/// SHA-256 of a snapshot blob (full or incremental), hex.
+pub fn digest(blob: &[u8]) -> String {
+    crate::cap_tokens::hex(&Sha256::digest(blob))
+}
+
+fn changed(state: &State, key: &str, base: u64) -> bool {
+    state.key_last_modified.get(key).is_some_and(|v| *v > base)
+}
+
+/// Encode the changes of `state` since the snapshot at `base_version` with `base_digest`.
+pub fn encode_incremental(py: Python, state: &State, base_version: u64, base_digest: &str, op_ids: &[(String, u64)], timers: &Bound<'_, PyList>) -> PyResult<Vec<u8>> {
+    let root = PyDict::new_bound(py);
+    root.set_item("format", FORMAT)?;
+    root.set_item("base_version", base_version)?;
+    root.set_item("base_digest", base_digest)?;
+    root.set_item("version", state.version)?;
+
+    let mut zones: Vec<&String> = state.data.keys().collect();
+    zones.sort();
+    root.set_item("zones", &zones)?;
+    let data = PyDict::new_bound(py);
+    for zone in zones.into_iter().filter(|z| changed(state, z, base_version)) {
+        let value = state.data[zone].bind(py);
+        let entry = PyDict::new_bound(py);
+        if let Ok(fields) = value.downcast::<PyDict>() {
+            let written = PyDict::new_bound(py);
+            let mut keys = Vec::with_capacity(fields.len());
+            for (k, v) in fields.iter() {
+                let key: String = k.extract()?;
+                if changed(state, &format!("{zone}.{key}"), base_version) {
+                    written.set_item(&key, v)?;
+                }
+                keys.push(key);
+            }
+            entry.set_item("fields", written)?;
+            entry.set_item("keys", keys)?;
+        } else {
+            entry.set_item("value", value)?;
+        }
+        data.set_item(zone, entry)?;
+    }
+    root.set_item("data", data)?;
+
+    let mut heavy_zones: Vec<&String> = state.heavy.keys().collect();
+    heavy_zones.sort();
+    root.set_item("heavy_zones", &heavy_zones)?;
+    let heavy = PyDict::new_bound(py);
+    for zone in heavy_zones.into_iter().filter(|z| changed(state, z, base_version)) {
+        heavy.set_item(zone, state.heavy[zone].bind(py))?;
+    }
+    root.set_item("heavy", heavy)?;
+
+    let klm = PyDict::new_bound(py);
+    for (k, v) in state.key_last_modified.iter().filter(|(_, v)| **v > base_version) {
+        klm.set_item(k.as_ref(), *v)?;
+    }
+    root.set_item("key_last_modified", klm)?;
+    root.set_item("op_ids", op_ids.iter().map(|(op, v)| (op.as_str(), *v)).collect::<Vec<_>>())?;
+    root.set_item("timers", timers)?;
+    crate::state_codec::encode_value(py, root.as_any())
+}
+
+/// Apply one incremental `blob` on top of `state`, whose own snapshot bytes hashed to
+/// `base_digest`.
+pub fn apply_incremental<'py>(py: Python<'py>, state: &mut DecodedState<'py>, base_digest: &str, blob: &[u8]) -> PyResult<()> {
+    let root = crate::state_codec::decode_value(py, blob)?.downcast_into::<PyDict>()
+        .map_err(|_| SerializationError::new_err("Incremental snapshot is not a msgpack map"))?;
+    let field = |name: &str| root.get_item(name)?.ok_or_else(|| SerializationError::new_err(format!("Incremental snapshot missing '{name}'")));
+    let format: String = field("format")?.extract()?;
+    if format != FORMAT {
+        return Err(SerializationError::new_err(format!("Unsupported incremental format '{format}' (expected '{FORMAT}')")));
+    }
+    let base_version: u64 = field("base_version")?.extract()?;
+    let expected: String = field("base_digest")?.extract()?;
+    if base_version != state.version || expected != base_digest {
+        return Err(SerializationError::new_err(format!(
+            "Snapshot chain broken: incremental expects base version {base_version} ({}), got version {} ({})",
+            &expected[..12.min(expected.len())], state.version, &base_digest[..12.min(base_digest.len())]
+        )));
+    }
+
+    let zones: Vec<String> = field("zones")?.extract()?;
+    for key in state.data.keys().iter() {
+        if !zones.contains(&key.extract::<String>()?) {
+            state.data.del_item(key)?;
+        }
+    }
+    for (zone, entry) in field("data")?.downcast::<PyDict>()?.iter() {
+        let entry = entry.downcast::<PyDict>()?;
+        if let Some(value) = entry.get_item("value")? {
+            state.data.set_item(zone, value)?;
+            continue;
+        }
+        let fields = entry.get_item("fields")?.ok_or_else(|| SerializationError::new_err("Zone entry missing 'fields'"))?;
+        let fields = fields.downcast::<PyDict>()?;
+        let previous = state.data.get_item(&zone)?.and_then(|v| v.downcast_into::<PyDict>().ok());
+        let rebuilt = PyDict::new_bound(py);
+        for key in entry.get_item("keys")?.ok_or_else(|| SerializationError::new_err("Zone entry missing 'keys'"))?.iter()? {
+            let key = key?;
+            let value = match fields.get_item(&key)? {
+                Some(v) => v,
+                None => previous.as_ref().and_then(|p| p.get_item(&key).transpose()).transpose()?.ok_or_else(|| SerializationError::new_err(format!(
+                    "Snapshot chain broken: field '{zone}.{key}' is neither in the incremental nor in its base"
+                )))?,
+            };
+            rebuilt.set_item(key, value)?;
+        }
+        state.data.set_item(zone, rebuilt)?;
+    }
+
+    let heavy_zones: Vec<String> = field("heavy_zones")?.extract()?;
+    for key in state.heavy.keys().iter() {
+        if !heavy_zones.contains(&key.extract::<String>()?) {
+            state.heavy.del_item(key)?;
+        }
+    }
+    state.heavy.update(field("heavy")?.downcast::<PyDict>()?.as_mapping())?;
+
+    let mut klm: HashMap<String, u64> = std::mem::take(&mut state.key_last_modified).into_iter().collect();
+    klm.extend(field("key_last_modified")?.extract::<HashMap<String, u64>>()?);
+    state.key_last_modified = klm.into_iter().collect();
+    state.op_ids = field("op_ids")?.extract()?;
+    state.timers = Some(field("timers")?);
+    state.version = field("version")?.extract()?;
+    Ok(())
+}
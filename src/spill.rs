@@ -0,0 +1,334 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::types::PyDict;
+use memmap2::Mmap;
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Spilling for Data subtrees larger than RAM (`engine.spill_to_disk(["domain.lookup"])`).
+// On commit, a newly written value at a spill prefix is msgpack-encoded into a page-aligned
+// record of an append-only file and replaced in the State by a `SpilledValue` placeholder.
+// Proxy/guard reads fault the value back in (a fresh decode, so it can be mutated like any
+// shadow); the most recently read records stay cached in memory up to `cache_bytes`.
+// NOTE: Records are never reclaimed - rewriting a spilled value appends a new record. The
+// file is deleted with the engine unless the caller supplied its path. Values already held
+// compressed (`compress.rs`) are left in memory.
+
+const PAGE: u64 = 4096;
+
+struct Inner {
+    prefixes: Vec<Vec<String>>,
+    file: File,
+    path: PathBuf,
+    owned: bool,
+    map: Option<Mmap>,
+    end: u64,
+    records: HashMap<u64, (u64, usize)>,
+    next_id: u64,
+    cache: HotCache,
+    spilled_bytes: u64,
+}
+
+/// Byte-bounded LRU of decoded payloads keyed by record id (also used for compressed values).
+pub(crate) struct HotCache {
+    // front = least recently used
+    lru: VecDeque<u64>,
+    hot: HashMap<u64, Arc<Vec<u8>>>,
+    pub bytes: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl HotCache {
+    pub fn new(capacity: usize) -> Self {
+        HotCache { lru: VecDeque::new(), hot: HashMap::new(), bytes: 0, capacity, hits: 0, misses: 0 }
+    }
+
+    fn touch(&mut self, id: u64) {
+        if let Some(pos) = self.lru.iter().position(|&k| k == id) {
+            self.lru.remove(pos);
+        }
+        self.lru.push_back(id);
+    }
+
+    /// Cached payload of `id`, counting the hit or miss.
+    pub fn get(&mut self, id: u64) -> Option<Arc<Vec<u8>>> {
+        let Some(bytes) = self.hot.get(&id).cloned() else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        self.touch(id);
+        Some(bytes)
+    }
+
+    pub fn insert(&mut self, id: u64, bytes: Arc<Vec<u8>>) {
+        self.bytes += bytes.len();
+        if let Some(old) = self.hot.insert(id, bytes) {
+            self.bytes -= old.len();
+        }
+        self.touch(id);
+        self.evict();
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.capacity {
+            let Some(victim) = self.lru.pop_front() else { break };
+            if let Some(b) = self.hot.remove(&victim) {
+                self.bytes -= b.len();
+            }
+        }
+    }
+}
+
+pub struct SpillStore {
+    inner: Mutex<Inner>,
+}
+
+impl Drop for SpillStore {
+    fn drop(&mut self) {
+        let inner = self.inner.get_mut().unwrap_or_else(std::sync::PoisonError::into_inner);
+        inner.map = None;
+        if inner.owned {
+            let _ = std::fs::remove_file(&inner.path);
+        }
+    }
+}
+
+fn io_err(e: &std::io::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("Spill file I/O failed: {e}"))
+}
+
+impl Inner {
+    fn read(&mut self, id: u64) -> PyResult<Arc<Vec<u8>>> {
+        if let Some(bytes) = self.cache.get(id) {
+            return Ok(bytes);
+        }
+        let &(offset, len) = self.records.get(&id)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown spilled record #{id}")))?;
+        let end = usize::try_from(offset).unwrap_or(usize::MAX).saturating_add(len);
+        if self.map.as_ref().is_none_or(|m| m.len() < end) {
+            // SAFETY: the file is private to this store and only ever appended to, so the
+            // mapped range is never truncated while mapped.
+            self.map = Some(unsafe { Mmap::map(&self.file) }.map_err(|e| io_err(&e))?);
+        }
+        let map = self.map.as_ref().expect("mapped above");
+        let bytes = Arc::new(map[end - len..end].to_vec());
+        self.cache.insert(id, bytes.clone());
+        Ok(bytes)
+    }
+}
+
+impl SpillStore {
+    pub fn open(prefixes: Vec<String>, path: Option<String>, cache_bytes: usize) -> PyResult<Arc<Self>> {
+        let mut parsed = Vec::with_capacity(prefixes.len());
+        for prefix in prefixes {
+            parsed.push(parse_prefix("Spill", &prefix)?);
+        }
+        let owned = path.is_none();
+        let path = path.map_or_else(
+            || std::env::temp_dir().join(format!("theus_spill_{}_{}.bin", std::process::id(), uuid::Uuid::new_v4())),
+            PathBuf::from,
+        );
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)
+            .map_err(|e| io_err(&e))?;
+        Ok(Arc::new(SpillStore { inner: Mutex::new(Inner {
+            prefixes: parsed, file, path, owned, map: None, end: 0,
+            records: HashMap::new(), next_id: 0, cache: HotCache::new(cache_bytes), spilled_bytes: 0,
+        }) }))
+    }
+
+    pub fn prefixes(&self) -> Vec<Vec<String>> {
+        self.inner.relock().prefixes.clone()
+    }
+
+    fn write(&self, bytes: &[u8]) -> PyResult<u64> {
+        let mut inner = self.inner.relock();
+        let offset = inner.end;
+        inner.file.seek(SeekFrom::Start(offset)).map_err(|e| io_err(&e))?;
+        inner.file.write_all(bytes).map_err(|e| io_err(&e))?;
+        let len = bytes.len() as u64;
+        let padded = len.div_ceil(PAGE).max(1) * PAGE;
+        inner.file.set_len(offset + padded).map_err(|e| io_err(&e))?;
+        inner.end = offset + padded;
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.records.insert(id, (offset, bytes.len()));
+        inner.spilled_bytes += len;
+        Ok(id)
+    }
+
+    pub fn stats(&self, py: Python) -> PyResult<PyObject> {
+        let inner = self.inner.relock();
+        let d = PyDict::new_bound(py);
+        d.set_item("path", inner.path.to_string_lossy())?;
+        d.set_item("records", inner.records.len())?;
+        d.set_item("file_bytes", inner.end)?;
+        d.set_item("spilled_bytes", inner.spilled_bytes)?;
+        d.set_item("cached_bytes", inner.cache.bytes)?;
+        d.set_item("cache_bytes", inner.cache.capacity)?;
+        d.set_item("hits", inner.cache.hits)?;
+        d.set_item("misses", inner.cache.misses)?;
+        Ok(d.into_any().unbind())
+    }
+
+    /// [v3.6] fsck "registry" check of the record table against the spill file.
+    pub fn fsck(&self) -> Vec<crate::fsck::Issue> {
+        use crate::fsck::Issue;
+        let mut issues = Vec::new();
+        let inner = self.inner.relock();
+        match inner.file.metadata() {
+            Ok(meta) if meta.len() < inner.end => issues.push(Issue::error("registry", format!(
+                "spill file {} is {} bytes, records end at {}", inner.path.display(), meta.len(), inner.end
+            ))),
+            Ok(_) => {}
+            Err(e) => issues.push(Issue::error("registry", format!("spill file {} unreadable: {e}", inner.path.display()))),
+        }
+        let mut ids: Vec<u64> = inner.records.iter()
+            .filter(|(_, (offset, len))| offset + *len as u64 > inner.end)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        for id in ids {
+            issues.push(Issue::error("registry", format!("spilled record #{id} extends past the end of the spill file")));
+        }
+        issues
+    }
+
+    /// Replaces newly written values at spill prefixes in `state` with placeholders.
+    pub fn apply(self: &Arc<Self>, py: Python, state: &Bound<'_, PyAny>) -> PyResult<()> {
+        let prefixes = self.inner.relock().prefixes.clone();
+        let mut state = state.downcast::<State>()?.borrow_mut();
+        for segments in &prefixes {
+            let Some(zone) = state.data.get(&segments[0]).map(|z| z.bind(py).clone()) else { continue };
+            let Some(value) = lookup(&zone, &segments[1..]) else { continue };
+            if is_placeholder(&value) {
+                continue;
+            }
+            let bytes = crate::state_codec::encode_value(py, &value)?;
+            let id = self.write(&bytes)?;
+            let placeholder = Py::new(py, SpilledValue { store: self.clone(), id, nbytes: bytes.len() })?;
+            let new_zone = replace_cow(&zone, &segments[1..], placeholder.bind(py).as_any())?;
+            state.data.insert(segments[0].clone(), Arc::new(new_zone.unbind()));
+            state.rehash(py, &segments[0])?;
+        }
+        Ok(())
+    }
+}
+
+/// `zone.field[.sub...]` -> segments; `kind` names the feature in the error.
+pub(crate) fn parse_prefix(kind: &str, prefix: &str) -> PyResult<Vec<String>> {
+    let segments: Vec<String> = prefix.replace('[', ".").replace(']', "").split('.').map(str::to_string).collect();
+    if segments.len() < 2 || segments.iter().any(String::is_empty) {
+        return Err(PyValueError::new_err(format!(
+            "{kind} prefix '{prefix}' must name a zone and a field (e.g. 'domain.lookup')"
+        )));
+    }
+    Ok(segments)
+}
+
+pub(crate) fn lookup<'py>(root: &Bound<'py, PyAny>, segments: &[String]) -> Option<Bound<'py, PyAny>> {
+    let mut current = root.clone();
+    for seg in segments {
+        current = current.downcast::<PyDict>().ok()?.get_item(seg).ok()??;
+    }
+    Some(current)
+}
+
+/// Copy of `root` with `value` at `segments`, copying every dict along the path.
+pub(crate) fn replace_cow<'py>(root: &Bound<'py, PyAny>, segments: &[String], value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Some((first, rest)) = segments.split_first() else { return Ok(value.clone()) };
+    let copy = root.downcast::<PyDict>()?.copy()?;
+    let child = copy.get_item(first)?.expect("looked up before");
+    copy.set_item(first, replace_cow(&child, rest, value)?)?;
+    Ok(copy.into_any())
+}
+
+/// Spilled or compressed stand-in for a committed value.
+pub fn is_placeholder(value: &Bound<'_, PyAny>) -> bool {
+    value.is_instance_of::<SpilledValue>() || value.is_instance_of::<crate::compress::CompressedValue>()
+}
+
+/// Materialized value for a placeholder, the value itself otherwise.
+pub fn fault_in(py: Python, value: PyObject) -> PyResult<PyObject> {
+    if let Ok(spilled) = value.downcast_bound::<SpilledValue>(py) {
+        return spilled.borrow().load(py);
+    }
+    match value.downcast_bound::<crate::compress::CompressedValue>(py) {
+        Ok(compressed) => compressed.borrow().load(py),
+        Err(_) => Ok(value),
+    }
+}
+
+/// Copy of a Data-zone dict with placeholders under `prefixes` loaded (schema checks).
+pub fn materialize<'py>(py: Python<'py>, prefixes: &[Vec<String>], data: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let mut data = data.clone();
+    for segments in prefixes {
+        if let Some(value) = lookup(&data, segments) {
+            if is_placeholder(&value) {
+                let loaded = fault_in(py, value.unbind())?;
+                data = replace_cow(&data, segments, loaded.bind(py))?;
+            }
+        }
+    }
+    Ok(data)
+}
+
+/// Placeholder for a Data value stored in the spill file. Reads through proxies load it
+/// transparently; `load()` does so explicitly.
+#[pyclass(module = "theus_core")]
+pub struct SpilledValue {
+    store: Arc<SpillStore>,
+    id: u64,
+    nbytes: usize,
+}
+
+#[pymethods]
+impl SpilledValue {
+    /// Decode the value (a fresh object on every call).
+    pub fn load(&self, py: Python) -> PyResult<PyObject> {
+        let bytes = self.store.inner.relock().read(self.id)?;
+        Ok(crate::state_codec::decode_value(py, &bytes)?.unbind())
+    }
+
+    /// Encoded size on disk.
+    #[getter]
+    fn nbytes(&self) -> usize {
+        self.nbytes
+    }
+
+    fn __eq__(&self, py: Python, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if let Ok(o) = other.downcast::<SpilledValue>() {
+            let o = o.borrow();
+            if Arc::ptr_eq(&self.store, &o.store) && self.id == o.id {
+                return Ok(true);
+            }
+            return self.load(py)?.bind(py).eq(o.load(py)?);
+        }
+        self.load(py)?.bind(py).eq(other)
+    }
+
+    /// Placeholders are immutable references: copies share the record.
+    fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __deepcopy__(slf: Py<Self>, _memo: PyObject) -> Py<Self> {
+        slf
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SpilledValue(#{}, {} bytes)", self.id, self.nbytes)
+    }
+}
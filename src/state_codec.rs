@@ -0,0 +1,464 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use rmp::Marker;
+use crate::outbox::SerializationError;
+use crate::shm::BufferDescriptor;
+use crate::structures::State;
+
+// [v3.6] Native msgpack codec for State transfer between processes (no pickle).
+// Layout: map { "format", "version", "data", "heavy", "key_last_modified", "op_ids", "timers" }
+// ("op_ids": [[op_id, version]] oldest first; "timers": pending timer rows as exported by
+// `timers::Timers::export`; both optional on decode).
+// Values map onto msgpack natively; the rest use extension types:
+const EXT_TUPLE: i8 = 1; // array payload
+const EXT_NDARRAY: i8 = 2; // [dtype.str, shape, raw C-order bytes]
+const EXT_SHM_NDARRAY: i8 = 3; // [shm name, dtype.str, shape] - zero-copy, re-attached on load
+const EXT_BUFFER_DESCRIPTOR: i8 = 4; // [name, size, shape, dtype]
+
+pub const FORMAT: &str = "theus-state/1";
+
+/// Nesting limit on both sides (guards the Rust stack against hostile input).
+const MAX_DEPTH: usize = 128;
+
+fn werr<T, E: std::fmt::Debug>(r: Result<T, E>) -> PyResult<()> {
+    r.map(|_| ()).map_err(|e| SerializationError::new_err(format!("msgpack write failed: {e:?}")))
+}
+
+fn len32(n: usize) -> PyResult<u32> {
+    u32::try_from(n).map_err(|_| SerializationError::new_err("Container too large for msgpack"))
+}
+
+// ============================================================================
+// Encoder
+// ============================================================================
+
+pub(crate) struct Encoder<'py> {
+    numpy: Option<Bound<'py, PyAny>>,
+    out: Vec<u8>,
+}
+
+impl<'py> Encoder<'py> {
+    pub(crate) fn new(py: Python<'py>) -> Self {
+        // NOTE: Only handle numpy if the caller already imported it.
+        let numpy = py.import_bound("sys").ok()
+            .and_then(|sys| sys.getattr("modules").ok())
+            .and_then(|m| m.get_item("numpy").ok());
+        Encoder { numpy, out: Vec::new() }
+    }
+
+    fn nested(&mut self, f: impl FnOnce(&mut Self) -> PyResult<()>) -> PyResult<Vec<u8>> {
+        let outer = std::mem::take(&mut self.out);
+        let res = f(self);
+        let inner = std::mem::replace(&mut self.out, outer);
+        res.map(|()| inner)
+    }
+
+    fn ext(&mut self, ty: i8, payload: &[u8]) -> PyResult<()> {
+        werr(rmp::encode::write_ext_meta(&mut self.out, len32(payload.len())?, ty))?;
+        self.out.extend_from_slice(payload);
+        Ok(())
+    }
+
+    pub(crate) fn str(&mut self, s: &str) -> PyResult<()> {
+        werr(rmp::encode::write_str(&mut self.out, s))
+    }
+
+    fn shape(&mut self, shape: &[usize]) -> PyResult<()> {
+        werr(rmp::encode::write_array_len(&mut self.out, len32(shape.len())?))?;
+        for d in shape {
+            werr(rmp::encode::write_uint(&mut self.out, *d as u64))?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn value(&mut self, v: &Bound<'py, PyAny>, depth: usize) -> PyResult<()> {
+        if depth > MAX_DEPTH {
+            return Err(SerializationError::new_err("State nested too deeply to serialize"));
+        }
+        if v.is_none() {
+            return werr(rmp::encode::write_nil(&mut self.out));
+        }
+        if crate::spill::is_placeholder(v) {
+            let loaded = crate::spill::fault_in(v.py(), v.clone().unbind())?;
+            return self.value(loaded.bind(v.py()), depth);
+        }
+        if let Ok(b) = v.downcast::<PyBool>() {
+            return werr(rmp::encode::write_bool(&mut self.out, b.is_true()));
+        }
+        if let Ok(i) = v.downcast::<PyInt>() {
+            if let Ok(n) = i.extract::<i64>() {
+                return werr(rmp::encode::write_sint(&mut self.out, n));
+            }
+            let n: u64 = i.extract().map_err(|_| SerializationError::new_err("Integer out of msgpack range"))?;
+            return werr(rmp::encode::write_uint(&mut self.out, n));
+        }
+        if let Ok(f) = v.downcast::<PyFloat>() {
+            return werr(rmp::encode::write_f64(&mut self.out, f.value()));
+        }
+        if let Ok(s) = v.downcast::<PyString>() {
+            return self.str(s.to_str()?);
+        }
+        if let Ok(b) = v.downcast::<PyBytes>() {
+            return werr(rmp::encode::write_bin(&mut self.out, b.as_bytes()));
+        }
+        if let Ok(b) = v.downcast::<PyByteArray>() {
+            return werr(rmp::encode::write_bin(&mut self.out, &b.to_vec()));
+        }
+        if let Ok(d) = v.downcast::<PyDict>() {
+            werr(rmp::encode::write_map_len(&mut self.out, len32(d.len())?))?;
+            for (k, item) in d {
+                self.value(&k, depth + 1)?;
+                self.value(&item, depth + 1)?;
+            }
+            return Ok(());
+        }
+        if let Ok(l) = v.downcast::<PyList>() {
+            werr(rmp::encode::write_array_len(&mut self.out, len32(l.len())?))?;
+            for item in l {
+                self.value(&item, depth + 1)?;
+            }
+            return Ok(());
+        }
+        if let Ok(t) = v.downcast::<PyTuple>() {
+            let payload = self.nested(|e| {
+                werr(rmp::encode::write_array_len(&mut e.out, len32(t.len())?))?;
+                t.iter().try_for_each(|item| e.value(&item, depth + 1))
+            })?;
+            return self.ext(EXT_TUPLE, &payload);
+        }
+        if let Ok(desc) = v.downcast::<BufferDescriptor>() {
+            let desc = desc.borrow().clone();
+            let payload = self.nested(|e| {
+                werr(rmp::encode::write_array_len(&mut e.out, 4))?;
+                e.str(&desc.name)?;
+                werr(rmp::encode::write_uint(&mut e.out, desc.size as u64))?;
+                e.shape(&desc.shape)?;
+                e.str(&desc.dtype)
+            })?;
+            return self.ext(EXT_BUFFER_DESCRIPTOR, &payload);
+        }
+        if let Some(np) = self.numpy.clone() {
+            if v.is_instance(&np.getattr("ndarray")?)? {
+                return self.ndarray(v);
+            }
+            if v.is_instance(&np.getattr("generic")?)? {
+                return self.value(&v.call_method0("item")?, depth + 1);
+            }
+        }
+        // Proxies serialize as the value they supervise.
+        if let Ok(inner) = v.getattr("supervisor_target") {
+            return self.value(&inner, depth + 1);
+        }
+        Err(SerializationError::new_err(format!(
+            "Cannot serialize value of type '{}' (no pickle fallback)", v.get_type().name()?
+        )))
+    }
+
+    fn ndarray(&mut self, arr: &Bound<'py, PyAny>) -> PyResult<()> {
+        let dtype: String = arr.getattr("dtype")?.getattr("str")?.extract()?;
+        if dtype.contains('O') {
+            return Err(SerializationError::new_err("Cannot serialize object-dtype ndarray"));
+        }
+        let shape: Vec<usize> = arr.getattr("shape")?.extract()?;
+
+        // Shared-memory backed (ManagedAllocator): send the handle, not the bytes.
+        let shm = ["_shm_ref", "_shm"].iter().find_map(|a| arr.getattr(*a).ok().filter(|s| !s.is_none()));
+        if let Some(shm) = shm {
+            let name: String = shm.getattr("name")?.extract()?;
+            let payload = self.nested(|e| {
+                werr(rmp::encode::write_array_len(&mut e.out, 3))?;
+                e.str(&name)?;
+                e.str(&dtype)?;
+                e.shape(&shape)
+            })?;
+            return self.ext(EXT_SHM_NDARRAY, &payload);
+        }
+
+        let raw = arr.call_method1("tobytes", ("C",))?;
+        let raw = raw.downcast::<PyBytes>()?;
+        let payload = self.nested(|e| {
+            werr(rmp::encode::write_array_len(&mut e.out, 3))?;
+            e.str(&dtype)?;
+            e.shape(&shape)?;
+            werr(rmp::encode::write_bin(&mut e.out, raw.as_bytes()))
+        })?;
+        self.ext(EXT_NDARRAY, &payload)
+    }
+}
+
+/// Serialize `state` (Data + Heavy zones, version, key versions) to msgpack bytes.
+pub fn encode_state(py: Python, state: &State, op_ids: &[(String, u64)], timers: &Bound<'_, PyList>) -> PyResult<Vec<u8>> {
+    let mut enc = Encoder::new(py);
+    werr(rmp::encode::write_map_len(&mut enc.out, 7))?;
+    enc.str("format")?;
+    enc.str(FORMAT)?;
+    enc.str("version")?;
+    werr(rmp::encode::write_uint(&mut enc.out, state.version))?;
+    for (name, zone) in [("data", &state.data), ("heavy", &state.heavy)] {
+        enc.str(name)?;
+        werr(rmp::encode::write_map_len(&mut enc.out, len32(zone.len())?))?;
+        for (k, v) in zone {
+            enc.str(k)?;
+            enc.value(v.bind(py), 1)?;
+        }
+    }
+    enc.str("key_last_modified")?;
+    werr(rmp::encode::write_map_len(&mut enc.out, len32(state.key_last_modified.len())?))?;
+    for (k, ver) in &state.key_last_modified {
+        enc.str(k)?;
+        werr(rmp::encode::write_uint(&mut enc.out, *ver))?;
+    }
+    enc.str("op_ids")?;
+    werr(rmp::encode::write_array_len(&mut enc.out, len32(op_ids.len())?))?;
+    for (op, ver) in op_ids {
+        werr(rmp::encode::write_array_len(&mut enc.out, 2))?;
+        enc.str(op)?;
+        werr(rmp::encode::write_uint(&mut enc.out, *ver))?;
+    }
+    enc.str("timers")?;
+    enc.value(timers.as_any(), 1)?;
+    Ok(enc.out)
+}
+
+// ============================================================================
+// Decoder
+// ============================================================================
+
+struct Decoder<'a, 'py> {
+    py: Python<'py>,
+    buf: &'a [u8],
+    pos: usize,
+}
+
+fn truncated() -> PyErr {
+    SerializationError::new_err("Truncated msgpack state")
+}
+
+impl<'a, 'py> Decoder<'a, 'py> {
+    fn take(&mut self, n: usize) -> PyResult<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|e| *e <= self.buf.len()).ok_or_else(truncated)?;
+        let out = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn be<const N: usize>(&mut self) -> PyResult<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+
+    fn len(&mut self, bytes: usize) -> PyResult<usize> {
+        Ok(match bytes {
+            1 => self.be::<1>()?[0] as usize,
+            2 => u16::from_be_bytes(self.be()?) as usize,
+            _ => u32::from_be_bytes(self.be()?) as usize,
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> PyResult<Bound<'py, PyAny>> {
+        if depth > MAX_DEPTH {
+            return Err(SerializationError::new_err("State nested too deeply to deserialize"));
+        }
+        let py = self.py;
+        let marker = Marker::from_u8(self.be::<1>()?[0]);
+        Ok(match marker {
+            Marker::Null => py.None().into_bound(py),
+            Marker::True => PyBool::new_bound(py, true).to_owned().into_any(),
+            Marker::False => PyBool::new_bound(py, false).to_owned().into_any(),
+            Marker::FixPos(n) => n.into_py(py).into_bound(py),
+            Marker::FixNeg(n) => n.into_py(py).into_bound(py),
+            Marker::U8 => self.be::<1>()?[0].into_py(py).into_bound(py),
+            Marker::U16 => u16::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::U32 => u32::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::U64 => u64::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::I8 => i8::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::I16 => i16::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::I32 => i32::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::I64 => i64::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::F32 => f32::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::F64 => f64::from_be_bytes(self.be()?).into_py(py).into_bound(py),
+            Marker::FixStr(n) => self.string(n as usize)?,
+            Marker::Str8 => { let n = self.len(1)?; self.string(n)? }
+            Marker::Str16 => { let n = self.len(2)?; self.string(n)? }
+            Marker::Str32 => { let n = self.len(4)?; self.string(n)? }
+            Marker::Bin8 => { let n = self.len(1)?; PyBytes::new_bound(py, self.take(n)?).into_any() }
+            Marker::Bin16 => { let n = self.len(2)?; PyBytes::new_bound(py, self.take(n)?).into_any() }
+            Marker::Bin32 => { let n = self.len(4)?; PyBytes::new_bound(py, self.take(n)?).into_any() }
+            Marker::FixArray(n) => self.list(n as usize, depth)?.into_any(),
+            Marker::Array16 => { let n = self.len(2)?; self.list(n, depth)?.into_any() }
+            Marker::Array32 => { let n = self.len(4)?; self.list(n, depth)?.into_any() }
+            Marker::FixMap(n) => self.map(n as usize, depth)?.into_any(),
+            Marker::Map16 => { let n = self.len(2)?; self.map(n, depth)?.into_any() }
+            Marker::Map32 => { let n = self.len(4)?; self.map(n, depth)?.into_any() }
+            Marker::FixExt1 => self.ext(1, depth)?,
+            Marker::FixExt2 => self.ext(2, depth)?,
+            Marker::FixExt4 => self.ext(4, depth)?,
+            Marker::FixExt8 => self.ext(8, depth)?,
+            Marker::FixExt16 => self.ext(16, depth)?,
+            Marker::Ext8 => { let n = self.len(1)?; self.ext(n, depth)? }
+            Marker::Ext16 => { let n = self.len(2)?; self.ext(n, depth)? }
+            Marker::Ext32 => { let n = self.len(4)?; self.ext(n, depth)? }
+            Marker::Reserved => return Err(SerializationError::new_err("Invalid msgpack marker 0xc1")),
+        })
+    }
+
+    fn string(&mut self, n: usize) -> PyResult<Bound<'py, PyAny>> {
+        let raw = self.take(n)?;
+        let s = std::str::from_utf8(raw).map_err(|_| SerializationError::new_err("Invalid UTF-8 in msgpack string"))?;
+        Ok(PyString::new_bound(self.py, s).into_any())
+    }
+
+    fn list(&mut self, n: usize, depth: usize) -> PyResult<Bound<'py, PyList>> {
+        let list = PyList::empty_bound(self.py);
+        for _ in 0..n {
+            list.append(self.value(depth + 1)?)?;
+        }
+        Ok(list)
+    }
+
+    fn map(&mut self, n: usize, depth: usize) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(self.py);
+        for _ in 0..n {
+            let k = self.value(depth + 1)?;
+            dict.set_item(k, self.value(depth + 1)?)?;
+        }
+        Ok(dict)
+    }
+
+    fn ext(&mut self, len: usize, depth: usize) -> PyResult<Bound<'py, PyAny>> {
+        let py = self.py;
+        let ty = i8::from_be_bytes(self.be()?);
+        let start = self.pos;
+        let fields = self.value(depth + 1)?;
+        if self.pos - start != len {
+            return Err(SerializationError::new_err(format!("Malformed msgpack extension {ty}")));
+        }
+        let fields = fields.downcast_into::<PyList>()
+            .map_err(|_| SerializationError::new_err(format!("Malformed msgpack extension {ty}")))?;
+        match ty {
+            EXT_TUPLE => Ok(PyTuple::new_bound(py, fields.iter()).into_any()),
+            EXT_NDARRAY => {
+                let (dtype, shape, raw) = (fields.get_item(0)?, fields.get_item(1)?, fields.get_item(2)?);
+                let np = py.import_bound("numpy")?;
+                let shape = PyTuple::new_bound(py, shape.downcast::<PyList>()?.iter());
+                np.call_method1("frombuffer", (raw, dtype))?
+                    .call_method1("reshape", (shape,))?
+                    .call_method0("copy")
+            }
+            EXT_SHM_NDARRAY => {
+                let (name, dtype, shape) = (fields.get_item(0)?, fields.get_item(1)?, fields.get_item(2)?);
+                let kwargs = PyDict::new_bound(py);
+                kwargs.set_item("name", name)?;
+                kwargs.set_item("create", false)?;
+                let shm = py.import_bound("multiprocessing.shared_memory")?
+                    .getattr("SharedMemory")?
+                    .call((), Some(&kwargs))?;
+                // ShmArray (unlike plain ndarray) can carry the SharedMemory reference that keeps the mapping alive.
+                let cls = py.import_bound("theus.structures")?.getattr("ShmArray")?;
+                let shape = PyTuple::new_bound(py, shape.downcast::<PyList>()?.iter());
+                let kwargs = PyDict::new_bound(py);
+                kwargs.set_item("dtype", dtype)?;
+                kwargs.set_item("buffer", shm.getattr("buf")?)?;
+                let arr = cls.call((shape,), Some(&kwargs))?;
+                arr.setattr("_shm_ref", shm)?;
+                Ok(arr)
+            }
+            EXT_BUFFER_DESCRIPTOR => {
+                let desc = BufferDescriptor {
+                    name: fields.get_item(0)?.extract()?,
+                    size: fields.get_item(1)?.extract()?,
+                    shape: fields.get_item(2)?.extract()?,
+                    dtype: fields.get_item(3)?.extract()?,
+                };
+                Ok(Py::new(py, desc)?.into_bound(py).into_any())
+            }
+            _ => Err(SerializationError::new_err(format!("Unknown msgpack extension type {ty}"))),
+        }
+    }
+}
+
+/// Fields recovered from `encode_state` output.
+pub struct DecodedState<'py> {
+    pub version: u64,
+    pub data: Bound<'py, PyDict>,
+    pub heavy: Bound<'py, PyDict>,
+    pub key_last_modified: Vec<(String, u64)>,
+    pub op_ids: Vec<(String, u64)>,
+    pub timers: Option<Bound<'py, PyAny>>,
+}
+
+pub fn decode_state<'py>(py: Python<'py>, buf: &[u8]) -> PyResult<DecodedState<'py>> {
+    let mut dec = Decoder { py, buf, pos: 0 };
+    let root = dec.value(0)?;
+    if dec.pos != buf.len() {
+        return Err(SerializationError::new_err("Trailing bytes after msgpack state"));
+    }
+    let root = root.downcast_into::<PyDict>().map_err(|_| SerializationError::new_err("State blob is not a msgpack map"))?;
+    let field = |name: &str| root.get_item(name)?.ok_or_else(|| SerializationError::new_err(format!("State blob missing '{name}'")));
+
+    let format: String = field("format")?.extract()?;
+    if format != FORMAT {
+        return Err(SerializationError::new_err(format!("Unsupported state format '{format}' (expected '{FORMAT}')")));
+    }
+    let as_dict = |v: Bound<'py, PyAny>| v.downcast_into::<PyDict>().map_err(|_| SerializationError::new_err("State zone is not a map"));
+    Ok(DecodedState {
+        version: field("version")?.extract()?,
+        data: as_dict(field("data")?)?,
+        heavy: as_dict(field("heavy")?)?,
+        key_last_modified: field("key_last_modified")?.extract::<std::collections::HashMap<String, u64>>()?.into_iter().collect(),
+        op_ids: match root.get_item("op_ids")? {
+            Some(ops) => ops.iter()?.map(|pair| {
+                let pair = pair?.downcast_into::<PyList>().map_err(|_| SerializationError::new_err("op_ids entry is not a pair"))?;
+                Ok((pair.get_item(0)?.extract()?, pair.get_item(1)?.extract()?))
+            }).collect::<PyResult<_>>()?,
+            None => Vec::new(),
+        },
+        timers: root.get_item("timers")?,
+    })
+}
+
+// Framing helpers for the remote state endpoint (`crate::state_server`).
+#[cfg(feature = "state-server")]
+impl<'py> Encoder<'py> {
+    pub(crate) fn map_len(&mut self, n: usize) -> PyResult<()> {
+        werr(rmp::encode::write_map_len(&mut self.out, len32(n)?))
+    }
+
+    pub(crate) fn uint(&mut self, n: u64) -> PyResult<()> {
+        werr(rmp::encode::write_uint(&mut self.out, n))
+    }
+
+    pub(crate) fn bool(&mut self, b: bool) -> PyResult<()> {
+        werr(rmp::encode::write_bool(&mut self.out, b))
+    }
+
+    /// Encode `v`, or `fallback` if `v` is not serializable (the partial output is discarded).
+    pub(crate) fn value_or_str(&mut self, v: &Bound<'py, PyAny>, fallback: &str) -> PyResult<()> {
+        let mark = self.out.len();
+        if self.value(v, 0).is_err() {
+            self.out.truncate(mark);
+            self.str(fallback)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.out
+    }
+}
+
+/// Encode a single Python value with the same rules (and extension types) as `encode_state`.
+pub fn encode_value(py: Python, value: &Bound<PyAny>) -> PyResult<Vec<u8>> {
+    let mut enc = Encoder::new(py);
+    enc.value(value, 0)?;
+    Ok(enc.out)
+}
+
+/// Inverse of `encode_value`; the whole buffer must be consumed.
+pub fn decode_value<'py>(py: Python<'py>, buf: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    let mut dec = Decoder { py, buf, pos: 0 };
+    let value = dec.value(0)?;
+    if dec.pos != buf.len() {
+        return Err(SerializationError::new_err("Trailing bytes after msgpack value"));
+    }
+    Ok(value)
+}
@@ -0,0 +1,173 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::RwLock;
+use crate::locks::RelockRw;
+
+// [v3.6] Lifecycle fields. `engine.define_state_machine("domain.orders.*.status",
+// {"new": ["paid", "cancelled"], "paid": ["shipped"]})` makes proxy writes to matching paths
+// check the old -> new transition: an undeclared jump raises IllegalTransitionError (code
+// TH501) and is audited as ILLEGAL_TRANSITION. Patterns are dotted paths where `*` matches one
+// segment (`orders[o1]` is matched as `orders.o1`). Writing the same state again is always
+// allowed; a first value (no old state) must be one of `initial` (default: any declared state).
+// Replacing a parent dict checks the machine paths inside it.
+// NOTE: States are compared as strings (an Enum by its `.value`). Only proxy writes are
+// checked - `tx.update()` / `compare_and_swap()` payloads are trusted like other admin paths.
+
+/// Nested dicts deeper than this are not searched for machine paths.
+const MAX_DEPTH: usize = 16;
+
+struct Machine {
+    pattern: String,
+    segments: Vec<String>,
+    transitions: BTreeMap<String, BTreeSet<String>>,
+    initial: Option<BTreeSet<String>>,
+}
+
+/// A rejected write: the machine's pattern and the states reachable from `from`.
+pub struct Violation {
+    pub pattern: String,
+    pub from: Option<String>,
+    pub to: String,
+    pub allowed: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct StateMachines {
+    machines: RwLock<Vec<Machine>>,
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.replace('[', ".").replace(']', "").split('.').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    pattern == "*" || pattern == segment
+}
+
+/// State name of a written value (None for None).
+fn state_name(value: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(Some(s));
+    }
+    if value.is_instance(&value.py().import_bound("enum")?.getattr("Enum")?)? {
+        return Ok(Some(value.getattr("value")?.str()?.to_string()));
+    }
+    Ok(Some(value.str()?.to_string()))
+}
+
+impl Machine {
+    fn states(&self) -> BTreeSet<&String> {
+        self.transitions.iter().flat_map(|(from, to)| std::iter::once(from).chain(to)).collect()
+    }
+
+    fn check(&self, from: Option<String>, to: String) -> Result<(), Violation> {
+        let allowed: Vec<String> = match &from {
+            Some(f) if *f == to => return Ok(()),
+            Some(f) => self.transitions.get(f).map(|t| t.iter().cloned().collect()).unwrap_or_default(),
+            None => match &self.initial {
+                Some(initial) => initial.iter().cloned().collect(),
+                None => self.states().into_iter().cloned().collect(),
+            },
+        };
+        if allowed.contains(&to) {
+            return Ok(());
+        }
+        Err(Violation { pattern: self.pattern.clone(), from, to, allowed })
+    }
+}
+
+impl StateMachines {
+    pub fn is_empty(&self) -> bool {
+        self.machines.relock_read().is_empty()
+    }
+
+    /// Adds (or replaces) the machine for `pattern`.
+    pub fn define(&self, pattern: &str, transitions: &Bound<'_, PyDict>, initial: Option<Vec<String>>) -> PyResult<()> {
+        let segments = segments(pattern);
+        if segments.is_empty() {
+            return Err(PyValueError::new_err("State machine pattern must not be empty"));
+        }
+        let mut table = BTreeMap::new();
+        for (from, to) in transitions.iter() {
+            let to: Vec<String> = to.extract().map_err(|_| PyValueError::new_err(format!("Transitions from '{from}' must be a list of state names")))?;
+            table.insert(from.extract::<String>()?, to.into_iter().collect::<BTreeSet<_>>());
+        }
+        let machine = Machine { pattern: pattern.to_string(), segments, transitions: table, initial: initial.map(|i| i.into_iter().collect()) };
+        if let Some(initial) = &machine.initial {
+            let states = machine.states();
+            if let Some(unknown) = initial.iter().find(|s| !states.contains(s)) {
+                return Err(PyValueError::new_err(format!("Initial state '{unknown}' is not part of the '{pattern}' machine")));
+            }
+        }
+        let mut machines = self.machines.relock_write();
+        machines.retain(|m| m.pattern != pattern);
+        machines.push(machine);
+        Ok(())
+    }
+
+    pub fn remove(&self, pattern: &str) -> bool {
+        let mut machines = self.machines.relock_write();
+        let before = machines.len();
+        machines.retain(|m| m.pattern != pattern);
+        machines.len() != before
+    }
+
+    /// `{pattern: {"transitions": {from: [to]}, "initial": [..] | None}}`.
+    pub fn export<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let out = PyDict::new_bound(py);
+        for m in self.machines.relock_read().iter() {
+            let transitions = PyDict::new_bound(py);
+            for (from, to) in &m.transitions {
+                transitions.set_item(from, to.iter().collect::<Vec<_>>())?;
+            }
+            let entry = PyDict::new_bound(py);
+            entry.set_item("transitions", transitions)?;
+            entry.set_item("initial", m.initial.as_ref().map(|i| i.iter().collect::<Vec<_>>()))?;
+            out.set_item(&m.pattern, entry)?;
+        }
+        Ok(out)
+    }
+
+    /// Checks writing `new` over `old` at `path`, including machine paths inside a written dict.
+    pub fn check(&self, path: &str, old: Option<&Bound<'_, PyAny>>, new: &Bound<'_, PyAny>) -> PyResult<Result<(), Violation>> {
+        let machines = self.machines.relock_read();
+        check_value(&machines, &segments(path), old, new, 0)
+    }
+}
+
+fn check_value(machines: &[Machine], path: &[String], old: Option<&Bound<'_, PyAny>>, new: &Bound<'_, PyAny>, depth: usize) -> PyResult<Result<(), Violation>> {
+    let mut below = false;
+    for m in machines {
+        if m.segments.len() < path.len() || !m.segments.iter().zip(path).all(|(p, s)| segment_matches(p, s)) {
+            continue;
+        }
+        if m.segments.len() == path.len() {
+            let from = old.map(state_name).transpose()?.flatten();
+            let Some(to) = state_name(new)? else { continue };
+            if let Err(v) = m.check(from, to) {
+                return Ok(Err(v));
+            }
+        } else {
+            below = true;
+        }
+    }
+    if !below || depth >= MAX_DEPTH {
+        return Ok(Ok(()));
+    }
+    let Ok(new) = new.downcast::<PyDict>() else { return Ok(Ok(())) };
+    let old = old.and_then(|o| o.downcast::<PyDict>().ok());
+    for (key, value) in new.iter() {
+        let mut child = path.to_vec();
+        child.push(key.str()?.to_string());
+        let old_value = old.map(|o| o.get_item(&key)).transpose()?.flatten();
+        if let Err(v) = check_value(machines, &child, old_value.as_ref(), &value, depth + 1)? {
+            return Ok(Err(v));
+        }
+    }
+    Ok(Ok(()))
+}
@@ -0,0 +1,437 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::engine::TheusEngine;
+use crate::outbox::SerializationError;
+use crate::state_codec::{decode_value, Encoder};
+use crate::zones::{parse_zone, resolve_zone, ContextZone};
+
+// [v3.6] Read-only state endpoint (feature "state-server").
+// Wire format: every frame is a u32 big-endian length followed by one msgpack value.
+//   request : {"token": str, "op": "read" | "version", "prefix": str (optional, "read" only)}
+//   response: {"ok": true, "version": int, "state": {dotted.path: value}}      (read)
+//             {"ok": true, "version": int, "key_versions": {key: int}}         (version)
+//             {"ok": false, "error": str}
+// A failed token check answers once and closes the connection. At most `MAX_CONNECTIONS`
+// are served at once (extra ones are closed on accept), and a connection that has not sent an
+// authorized request within `AUTH_TIMEOUT` is dropped.
+
+/// Requests are tiny; anything bigger is a protocol error.
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// Containers nested deeper than this are served as a marker (also breaks reference cycles).
+const MAX_FLATTEN_DEPTH: usize = 32;
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+/// Concurrent connections (one thread each).
+const MAX_CONNECTIONS: usize = 64;
+/// Time a new connection gets to send its first authorized request.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Constant-time token comparison (length still leaks, which is acceptable for random tokens).
+#[allow(clippy::needless_bitwise_bool)]
+fn token_matches(tokens: &[String], given: &str) -> bool {
+    tokens.iter().fold(false, |found, t| {
+        let same_len = t.len() == given.len();
+        let diff = t.bytes().zip(given.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        found | (same_len & (diff == 0))
+    })
+}
+
+struct ServerConfig {
+    engine: Py<TheusEngine>,
+    tokens: Vec<String>,
+    redacted: Vec<ContextZone>,
+    stop: Arc<AtomicBool>,
+    served: Arc<AtomicU64>,
+    connections: AtomicUsize,
+}
+
+/// One of the `MAX_CONNECTIONS` slots, released on drop.
+struct ConnectionSlot(Arc<ServerConfig>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ServerConfig {
+    /// Any segment in a redacted zone hides the path (`meta.internal_x` is Private too).
+    fn is_redacted(&self, path: &str) -> bool {
+        path.replace('[', ".").replace(']', "").split('.').any(|segment| self.redacted.contains(&resolve_zone(segment)))
+    }
+}
+
+/// [v3.6] Handle returned by `engine.serve_state()`. Stops on `stop()` or when dropped.
+#[pyclass(module = "theus_core")]
+pub struct StateServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    served: Arc<AtomicU64>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl StateServer {
+    pub fn start(py: Python, engine: Py<TheusEngine>, host: &str, port: u16, tokens: Vec<String>, redact_zones: Option<Vec<String>>) -> PyResult<Self> {
+        if tokens.is_empty() || tokens.iter().any(String::is_empty) {
+            return Err(pyo3::exceptions::PyValueError::new_err("serve_state() requires at least one non-empty read token"));
+        }
+        // Private is always redacted; callers can only widen the set.
+        let mut redacted = vec![ContextZone::Private];
+        for name in redact_zones.unwrap_or_default() {
+            let zone = parse_zone(&name)?;
+            if !redacted.contains(&zone) {
+                redacted.push(zone);
+            }
+        }
+
+        let listener = TcpListener::bind((host, port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let served = Arc::new(AtomicU64::new(0));
+        let config = Arc::new(ServerConfig { engine, tokens, redacted, stop: stop.clone(), served: served.clone(), connections: AtomicUsize::new(0) });
+
+        let thread = py.allow_threads(|| {
+            std::thread::Builder::new()
+                .name(format!("theus-state-server-{}", addr.port()))
+                .spawn(move || accept_loop(&listener, &config))
+        })?;
+        crate::audit::log_global("STATE_SERVER", &format!("listening on {addr}"));
+        Ok(StateServer { addr, stop, served, thread: Some(thread) })
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(t) = self.thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+#[pymethods]
+impl StateServer {
+    /// `(host, port)` actually bound (useful with `port=0`).
+    #[getter]
+    fn address(&self) -> (String, u16) {
+        (self.addr.ip().to_string(), self.addr.port())
+    }
+
+    #[getter]
+    fn requests_served(&self) -> u64 {
+        self.served.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn running(&self) -> bool {
+        self.thread.is_some()
+    }
+
+    fn stop(&mut self, py: Python) {
+        py.allow_threads(|| self.shutdown());
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, py: Python, _args: &Bound<'_, pyo3::types::PyTuple>) {
+        self.stop(py);
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<StateServer {} running={}>", self.addr, self.thread.is_some())
+    }
+}
+
+impl Drop for StateServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn accept_loop(listener: &TcpListener, config: &Arc<ServerConfig>) {
+    while !config.stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if config.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    config.connections.fetch_sub(1, Ordering::SeqCst);
+                    crate::audit::log_global("STATE_SERVER_BUSY", &format!("refused {peer}: {MAX_CONNECTIONS} connections open"));
+                    continue;
+                }
+                let slot = ConnectionSlot(config.clone());
+                let spawned = std::thread::Builder::new()
+                    .name("theus-state-conn".into())
+                    .spawn(move || serve_connection(stream, &slot.0));
+                if let Err(e) = spawned {
+                    crate::audit::log_global("STATE_SERVER_BUSY", &format!("refused {peer}: {e}"));
+                }
+            }
+            // WouldBlock (idle) or a transient accept error
+            Err(_) => std::thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Read one frame; `Ok(None)` on clean EOF or server stop, `Err(TimedOut)` past `deadline`.
+fn read_frame(stream: &mut TcpStream, stop: &AtomicBool, deadline: Option<Instant>) -> std::io::Result<Option<Vec<u8>>> {
+    let mut header = [0u8; 4];
+    let mut got = 0;
+    while got < header.len() {
+        if stop.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+        match stream.read(&mut header[got..]) {
+            Ok(0) => return Ok(None),
+            Ok(n) => got += n,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    return Err(e);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let len = u32::from_be_bytes(header) as usize;
+    if len > MAX_REQUEST_BYTES {
+        return Err(std::io::Error::new(ErrorKind::InvalidData, "request frame too large"));
+    }
+    let mut body = vec![0u8; len];
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.read_exact(&mut body)?;
+    stream.set_read_timeout(Some(POLL_INTERVAL * 4))?;
+    Ok(Some(body))
+}
+
+fn write_frame(stream: &mut TcpStream, body: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(body.len()).map_err(|_| std::io::Error::new(ErrorKind::InvalidData, "response too large"))?;
+    stream.write_all(&len.to_be_bytes())?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn serve_connection(mut stream: TcpStream, config: &ServerConfig) {
+    let peer = stream.peer_addr().map_or_else(|_| "?".to_string(), |a| a.to_string());
+    if stream.set_nonblocking(false).is_err() || stream.set_read_timeout(Some(POLL_INTERVAL * 4)).is_err() {
+        return;
+    }
+    let mut deadline = Some(Instant::now() + AUTH_TIMEOUT);
+    while let Ok(Some(frame)) = read_frame(&mut stream, &config.stop, deadline) {
+        let (response, authorized) = Python::with_gil(|py| handle_request(py, config, &frame));
+        let response = match response {
+            Ok(bytes) => bytes,
+            Err(e) => Python::with_gil(|py| error_response(py, &e.to_string())),
+        };
+        config.served.fetch_add(1, Ordering::Relaxed);
+        if write_frame(&mut stream, &response).is_err() {
+            break;
+        }
+        match authorized {
+            Some(false) => {
+                crate::audit::log_global("STATE_SERVER_AUTH_FAILED", &format!("rejected read from {peer}"));
+                break;
+            }
+            Some(true) => deadline = None,
+            None => {}
+        }
+    }
+}
+
+fn error_response(py: Python, msg: &str) -> Vec<u8> {
+    let mut enc = Encoder::new(py);
+    let _ = enc.map_len(2).and_then(|()| enc.str("ok")).and_then(|()| enc.bool(false))
+        .and_then(|()| enc.str("error")).and_then(|()| enc.str(msg));
+    enc.into_bytes()
+}
+
+/// Returns the encoded response and whether the token was accepted (None for a malformed
+/// request, which carries no token to check).
+fn handle_request(py: Python, config: &ServerConfig, frame: &[u8]) -> (PyResult<Vec<u8>>, Option<bool>) {
+    let request = match decode_value(py, frame).and_then(|v| {
+        v.downcast_into::<PyDict>().map_err(|_| SerializationError::new_err("Request must be a msgpack map"))
+    }) {
+        Ok(r) => r,
+        Err(e) => return (Err(e), None),
+    };
+    let field = |name: &str| -> PyResult<Option<String>> {
+        request.get_item(name)?.map(|v| v.extract::<String>()).transpose()
+    };
+
+    let token = field("token").ok().flatten().unwrap_or_default();
+    if !token_matches(&config.tokens, &token) {
+        return (Ok(error_response(py, "unauthorized")), Some(false));
+    }
+    let result = (|| {
+        let op = field("op")?.unwrap_or_else(|| "read".to_string());
+        let prefix = field("prefix")?.unwrap_or_default();
+        let state = config.engine.bind(py).try_borrow()
+            .map_err(|_| pyo3::exceptions::PyRuntimeError::new_err("engine busy, retry"))?
+            .committed_state(py);
+        let state = state.bind(py).borrow();
+
+        let mut enc = Encoder::new(py);
+        enc.map_len(3)?;
+        enc.str("ok")?;
+        enc.bool(true)?;
+        enc.str("version")?;
+        enc.uint(state.version)?;
+        match op.as_str() {
+            "version" => {
+                let mut keys: Vec<(&crate::intern::Sym, &u64)> = state.key_last_modified.iter()
+                    .filter(|(k, _)| !config.is_redacted(k))
+                    .collect();
+                keys.sort();
+                enc.str("key_versions")?;
+                enc.map_len(keys.len())?;
+                for (k, v) in keys {
+                    enc.str(k)?;
+                    enc.uint(*v)?;
+                }
+            }
+            "read" => {
+                let mut leaves = Vec::new();
+                let mut keys: Vec<&String> = state.data.keys().collect();
+                keys.sort();
+                for k in keys {
+                    flatten(config, k.clone(), state.data[k].bind(py), 0, &mut leaves)?;
+                }
+                leaves.retain(|(path, _)| {
+                    prefix.is_empty() || *path == prefix || path.starts_with(&format!("{prefix}."))
+                });
+                enc.str("state")?;
+                enc.map_len(leaves.len())?;
+                for (path, value) in leaves {
+                    enc.str(&path)?;
+                    let fallback = format!("<unserializable {}>", value.get_type().name()?);
+                    enc.value_or_str(&value, &fallback)?;
+                }
+            }
+            other => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown op '{other}'"))),
+        }
+        Ok(enc.into_bytes())
+    })();
+    (result, Some(true))
+}
+
+/// Flatten nested dicts, lists and tuples into dotted leaf paths (list items by index),
+/// dropping redacted zones. Leaves are never containers, so nothing is served unchecked.
+fn flatten<'py>(config: &ServerConfig, path: String, value: &Bound<'py, PyAny>, depth: usize, out: &mut Vec<(String, Bound<'py, PyAny>)>) -> PyResult<()> {
+    if config.is_redacted(&path) {
+        return Ok(());
+    }
+    let value = value.getattr("supervisor_target").unwrap_or_else(|_| value.clone());
+    let children: Vec<(String, Bound<'py, PyAny>)> = if let Ok(d) = value.downcast::<PyDict>() {
+        d.iter().map(|(k, v)| Ok((k.str()?.to_string(), v))).collect::<PyResult<_>>()?
+    } else if let Ok(l) = value.downcast::<PyList>() {
+        l.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect()
+    } else if let Ok(t) = value.downcast::<PyTuple>() {
+        t.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect()
+    } else {
+        out.push((path, value));
+        return Ok(());
+    };
+    if children.is_empty() {
+        out.push((path, value));
+    } else if depth >= MAX_FLATTEN_DEPTH {
+        out.push((path, PyString::new_bound(value.py(), "<nested too deeply>").into_any()));
+    } else {
+        for (segment, child) in children {
+            flatten(config, format!("{path}.{segment}"), &child, depth + 1, out)?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// [v3.6] Minimal blocking client for `StateServer` (dashboards in other processes/hosts).
+#[pyclass(module = "theus_core")]
+pub struct StateClient {
+    stream: Option<TcpStream>,
+    token: String,
+}
+
+impl StateClient {
+    fn call(&mut self, py: Python, op: &str, prefix: Option<&str>) -> PyResult<PyObject> {
+        let stream = self.stream.as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyConnectionError::new_err("StateClient is closed"))?;
+
+        let request = PyDict::new_bound(py);
+        request.set_item("token", &self.token)?;
+        request.set_item("op", op)?;
+        if let Some(p) = prefix {
+            request.set_item("prefix", p)?;
+        }
+        let body = crate::state_codec::encode_value(py, &request)?;
+        let reply = py.allow_threads(|| -> std::io::Result<Vec<u8>> {
+            write_frame(stream, &body)?;
+            let mut header = [0u8; 4];
+            stream.read_exact(&mut header)?;
+            let mut reply = vec![0u8; u32::from_be_bytes(header) as usize];
+            stream.read_exact(&mut reply)?;
+            Ok(reply)
+        })?;
+
+        let response = decode_value(py, &reply)?.downcast_into::<PyDict>()
+            .map_err(|_| SerializationError::new_err("Malformed state server response"))?;
+        let ok = response.get_item("ok")?.is_some_and(|v| v.is_truthy().unwrap_or(false));
+        if !ok {
+            let msg: String = response.get_item("error")?.map_or_else(|| Ok("unknown error".to_string()), |v| v.extract())?;
+            if msg == "unauthorized" {
+                self.stream = None;
+                return Err(pyo3::exceptions::PyPermissionError::new_err("State server rejected the read token"));
+            }
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(format!("State server error: {msg}")));
+        }
+        response.del_item("ok")?;
+        Ok(response.into_any().unbind())
+    }
+}
+
+#[pymethods]
+impl StateClient {
+    #[new]
+    #[pyo3(signature = (host, port, token, timeout_ms=5000))]
+    fn new(py: Python, host: &str, port: u16, token: String, timeout_ms: u64) -> PyResult<Self> {
+        let timeout = Duration::from_millis(timeout_ms);
+        let host = host.to_string();
+        let stream = py.allow_threads(|| -> std::io::Result<TcpStream> {
+            let stream = TcpStream::connect((host.as_str(), port))?;
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+            stream.set_nodelay(true)?;
+            Ok(stream)
+        })?;
+        Ok(StateClient { stream: Some(stream), token })
+    }
+
+    /// Flattened Data zone: `{"version": int, "state": {dotted.path: value}}`.
+    #[pyo3(signature = (prefix=None))]
+    fn read(&mut self, py: Python, prefix: Option<&str>) -> PyResult<PyObject> {
+        self.call(py, "read", prefix)
+    }
+
+    /// Version metadata only: `{"version": int, "key_versions": {key: int}}`.
+    fn version(&mut self, py: Python) -> PyResult<PyObject> {
+        self.call(py, "version", None)
+    }
+
+    fn close(&mut self) {
+        self.stream = None;
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) {
+        self.close();
+    }
+}
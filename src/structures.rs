@@ -1,14 +1,16 @@
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyBytes, PyDict, PyList};
 use pyo3::create_exception;
 use crate::proxy::SupervisorProxy;
 use im::HashMap;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
 use std::collections::VecDeque;
-use std::time::{SystemTime, UNIX_EPOCH};
 use crate::signals::SignalHub;
 use crate::engine::Transaction;
+use crate::cancellation::CancellationToken;
 use crate::zones::{CAP_READ, CAP_UPDATE, CAP_APPEND, CAP_DELETE};
+use crate::locks::Relock;
 
 create_exception!(theus.structures, ContextError, pyo3::exceptions::PyException);
 
@@ -204,9 +206,16 @@ pub struct State {
     pub meta_capacity: usize,
     pub version: u64,
     // v3.3: Key-Level Versioning for Smart CAS
-    pub key_last_modified: HashMap<String, u64>,
+    // [v3.6] Keys are interned (`intern.rs`), so cloning per version only bumps refcounts.
+    pub key_last_modified: HashMap<crate::intern::Sym, u64>,
     // v3.3: Signal Latch for Flux (Snapshot of signals in this version)
     pub last_signals: HashMap<String, String>,
+    // [v3.6] Per-key content hashes of the Data zone, recorded at commit time while
+    // `integrity` (escape tracking) is on; empty otherwise.
+    pub data_hashes: HashMap<String, u64>,
+    pub integrity: bool,
+    // [v3.6] Last writer (actor, tx, version) per changed path, same granularity as key_last_modified.
+    pub key_last_writer: HashMap<crate::intern::Sym, crate::lineage::Writer>,
 }
 
 /// Helper: Deep Merge (Copy-on-Write) for State Updates
@@ -255,7 +264,7 @@ impl State {
             for (k, v) in d_dict {
                 let key = k.extract::<String>()?;
                 state_data.insert(key.clone(), Arc::new(v.into_py(py)));
-                key_last_mod.insert(key, version);
+                key_last_mod.insert(crate::intern::intern(&key), version);
             }
         }
 
@@ -264,7 +273,7 @@ impl State {
             for (k, v) in h_dict {
                  let key = k.extract::<String>()?;
                  state_heavy.insert(key.clone(), Arc::new(v.into_py(py)));
-                 key_last_mod.insert(key, version);
+                 key_last_mod.insert(crate::intern::intern(&key), version);
             }
         }
         
@@ -279,6 +288,9 @@ impl State {
             version,
             key_last_modified: key_last_mod,
             last_signals: last_sig,
+            data_hashes: HashMap::new(),
+            integrity: false,
+            key_last_writer: HashMap::new(),
         })
     }
 
@@ -296,6 +308,9 @@ impl State {
             version: self.version + 1,
             key_last_modified: self.key_last_modified.clone(),
             last_signals: HashMap::new(), // Reset latch for new tick
+            data_hashes: self.data_hashes.clone(),
+            integrity: self.integrity,
+            key_last_writer: self.key_last_writer.clone(),
         };
 
         // Auto-log update event (Meta Zone)
@@ -319,25 +334,28 @@ impl State {
                     for (ik, _iv) in inner_dict {
                         let inner_key = ik.extract::<String>()?;
                         let field_path = format!("{zone_key}.{inner_key}");  // "domain.counter"
-                        new_state.key_last_modified.insert(field_path, new_state.version);
+                        new_state.key_last_modified.insert(crate::intern::intern(&field_path), new_state.version);
                     }
                 }
                 
                 // Keep zone-level tracking for backwards compatibility
-                new_state.key_last_modified.insert(zone_key.clone(), new_state.version);
+                new_state.key_last_modified.insert(crate::intern::intern(&zone_key), new_state.version);
                 
                 // [FIX v3.1] Deep Merge CoW Policy
                 if let Ok(inner_dict) = v.downcast::<PyDict>() {
                     if let Some(existing_arc) = self.data.get(&zone_key) {
                         let existing_obj = existing_arc.clone_ref(py);
                         let merged = deep_merge_cow(py, existing_obj, inner_dict)?;
-                        new_state.data.insert(zone_key, Arc::new(merged));
+                        new_state.data.insert(zone_key.clone(), Arc::new(merged));
                     } else {
-                        new_state.data.insert(zone_key, Arc::new(v.into_py(py)));
+                        new_state.data.insert(zone_key.clone(), Arc::new(v.into_py(py)));
                     }
                 } else {
-                    new_state.data.insert(zone_key, Arc::new(v.into_py(py)));
+                    new_state.data.insert(zone_key.clone(), Arc::new(v.into_py(py)));
                 }
+
+                // [v3.6] Re-hash only the zones this update touched.
+                new_state.rehash(py, &zone_key)?;
             }
         }
         
@@ -359,12 +377,12 @@ impl State {
                     for (ik, _iv) in inner_dict {
                         let inner_key = ik.extract::<String>()?;
                         let field_path = format!("{zone_key}.{inner_key}");  // "heavy.buffer"
-                        new_state.key_last_modified.insert(field_path, new_state.version);
+                        new_state.key_last_modified.insert(crate::intern::intern(&field_path), new_state.version);
                     }
                 }
                 
                 // Keep zone-level tracking for backwards compatibility
-                new_state.key_last_modified.insert(zone_key.clone(), new_state.version);
+                new_state.key_last_modified.insert(crate::intern::intern(&zone_key), new_state.version);
                 
                 // [FIX v3.1] Deep Merge CoW Policy for Heavy Zone
                 if let Ok(inner_dict) = v.downcast::<PyDict>() {
@@ -438,10 +456,7 @@ impl State {
 
     /// Log a system event to the Meta Zone Ring Buffer.
     fn log_meta(&self, key: &str, message: &str) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs_f64();
+        let now = crate::clock::now_secs();
 
         let entry = MetaLogEntry {
             timestamp: now,
@@ -449,7 +464,7 @@ impl State {
             message: message.to_string(),
         };
 
-        let mut logs = self.meta_logs.lock().unwrap();
+        let mut logs = self.meta_logs.relock();
         if logs.len() >= self.meta_capacity && self.meta_capacity > 0 {
             logs.pop_front();
         }
@@ -460,7 +475,7 @@ impl State {
 
     /// Retrieve persistent meta logs (shared across state versions).
     fn get_meta_logs(&self) -> Vec<MetaLogEntry> {
-        self.meta_logs.lock().unwrap().iter().cloned().collect()
+        self.meta_logs.relock().iter().cloned().collect()
     }
 
     fn restrict_view(&self) -> State {
@@ -473,6 +488,31 @@ impl State {
             version: self.version,
             key_last_modified: self.key_last_modified.clone(),
             last_signals: self.last_signals.clone(),
+            data_hashes: self.data_hashes.clone(),
+            integrity: self.integrity,
+            key_last_writer: self.key_last_writer.clone(),
+        }
+    }
+
+    /// [v3.6] Checksum of the Data zone as recorded at commit time; None unless escape
+    /// tracking is on.
+    #[getter]
+    fn integrity_hash(&self) -> Option<u64> {
+        self.integrity.then(|| crate::integrity::combine(&self.data_hashes))
+    }
+
+    /// [v3.6] Who last changed `path` (or the nearest tracked ancestor):
+    /// `{"version", "tx", "actor"}`, or None if no commit has written it.
+    fn last_writer<'py>(&self, py: Python<'py>, path: &str) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let mut path = path.replace('[', ".").replace(']', "");
+        loop {
+            if let Some(writer) = self.key_last_writer.get(path.as_str()) {
+                return writer.to_dict(py).map(Some);
+            }
+            match path.rfind('.') {
+                Some(idx) => path.truncate(idx),
+                None => return Ok(None),
+            }
         }
     }
 
@@ -592,6 +632,55 @@ impl State {
     }
 }
 
+impl State {
+    /// [v3.6] Install zones rebuilt by a streaming commit. `fields` are the touched top-level
+    /// keys of each zone; key-level versions and content hashes follow `update()`.
+    pub fn replace_zones(&mut self, py: Python, zones: Vec<(String, PyObject, Vec<String>)>) -> PyResult<()> {
+        for (zone, value, fields) in zones {
+            for field in fields {
+                self.key_last_modified.insert(crate::intern::intern(&format!("{zone}.{field}")), self.version);
+            }
+            self.key_last_modified.insert(crate::intern::intern(&zone), self.version);
+            self.data.insert(zone.clone(), Arc::new(value));
+            self.rehash(py, &zone)?;
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Record the content hash of Data key `zone` (integrity tracking only).
+    pub fn rehash(&mut self, py: Python, zone: &str) -> PyResult<()> {
+        if !self.integrity {
+            return Ok(());
+        }
+        match self.data.get(zone) {
+            Some(value) => {
+                let hash = crate::integrity::content_hash(value.bind(py))?;
+                self.data_hashes.insert(zone.to_string(), hash);
+            }
+            None => {
+                self.data_hashes.remove(zone);
+            }
+        }
+        Ok(())
+    }
+
+    /// [v3.6] Turn integrity tracking on (hashing every Data key now) or off.
+    pub fn set_integrity(&mut self, py: Python, enabled: bool) -> PyResult<()> {
+        self.integrity = enabled;
+        self.data_hashes = if enabled { self.recompute_data_hashes(py)? } else { HashMap::new() };
+        Ok(())
+    }
+
+    /// [v3.6] Re-hash the Data zone as it is now (compare against `data_hashes`).
+    pub fn recompute_data_hashes(&self, py: Python) -> PyResult<HashMap<String, u64>> {
+        let mut hashes = HashMap::new();
+        for (k, v) in &self.data {
+            hashes.insert(k.clone(), crate::integrity::content_hash(v.bind(py))?);
+        }
+        Ok(hashes)
+    }
+}
+
 #[pyclass(module = "theus_core")]
 #[derive(Clone)]
 pub struct Outbox {
@@ -608,14 +697,17 @@ impl Outbox {
     }
 
     #[pyo3(signature = (msg))]
-    fn add(&mut self, msg: OutboxMsg) {
+    fn add(&mut self, py: Python, msg: OutboxMsg) -> PyResult<()> {
         eprintln!("DEBUG: Outbox::add topic={}", msg.topic);
-        self.messages.lock().unwrap().push(msg);
+        // [v3.6] Processes with an effect budget must declare "outbox".
+        crate::pure_io::check(py, "outbox", &format!("outbox.add: {}", msg.topic))?;
+        self.messages.relock().push(msg);
+        Ok(())
     }
     
     #[getter]
     fn get_messages(&self) -> Vec<OutboxMsg> {
-        self.messages.lock().unwrap().clone()
+        self.messages.relock().clone()
     }
 }
 
@@ -630,18 +722,53 @@ pub struct ProcessContext {
     pub outbox: Outbox,
     #[pyo3(get)]
     pub tx: Option<Py<Transaction>>, // v3.1: Expose active transaction
+    #[pyo3(get)]
+    pub token: Py<CancellationToken>, // [v3.6] Cooperative cancellation for this run
+}
+
+impl ProcessContext {
+    /// [v3.6] Same state bound to another transaction, with a copied `local` scope and an
+    /// empty outbox (dry runs must not leak into the caller's context).
+    pub(crate) fn rebind(&self, py: Python, tx: Py<Transaction>) -> PyResult<Self> {
+        Ok(ProcessContext {
+            state: self.state.clone_ref(py),
+            local: self.local.bind(py).copy()?.unbind(),
+            outbox: Outbox { messages: Arc::new(Mutex::new(Vec::new())) },
+            tx: Some(tx),
+            token: Py::new(py, CancellationToken::new("", Arc::new(AtomicBool::new(false))))?,
+        })
+    }
 }
 
 #[pymethods]
 impl ProcessContext {
     #[new]
-    fn new(state: Py<State>, local: Py<PyDict>, tx: Option<Py<Transaction>>) -> Self {
-        ProcessContext { 
+    fn new(py: Python, state: Py<State>, local: Py<PyDict>, tx: Option<Py<Transaction>>) -> PyResult<Self> {
+        Ok(ProcessContext { 
             state, 
             local,
             outbox: Outbox::new(), 
             tx,
-        }
+            token: Py::new(py, CancellationToken::new("", Arc::new(AtomicBool::new(false))))?,
+        })
+    }
+
+    /// [v3.6] True once the run was cancelled (engine.cancel, tx.cancel, stuck-run abort).
+    #[getter]
+    fn cancelled(&self) -> bool {
+        self.token.get().is_cancelled()
+    }
+
+    /// [v3.6] Raises `ProcessCancelledError` if the run was cancelled.
+    fn raise_if_cancelled(&self) -> PyResult<()> {
+        self.token.get().check()
+    }
+
+    /// [v3.6] Blob store of the run's transaction (see blobs.rs).
+    #[getter]
+    fn blobs(&self, py: Python) -> PyResult<crate::blobs::BlobHandle> {
+        let tx = self.tx.as_ref().ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("ctx.blobs requires a transaction"))?;
+        Ok(tx.bind(py).try_borrow()?.blobs())
     }
 
     #[getter]
@@ -732,17 +859,93 @@ pub struct OutboxMsg {
     #[pyo3(get)]
     pub topic: String,
     pub payload: Arc<PyObject>,
+    /// [v3.6] Optional schema tag so workers can dispatch/validate without sniffing payloads.
+    #[pyo3(get)]
+    pub schema: Option<String>,
+    /// [v3.6] Registered serializer name used for the wire format (default: json).
+    #[pyo3(get)]
+    pub content_type: Option<String>,
+    /// [v3.6] Dedup key for exactly-once delivery. Auto-generated (uuid4) when not supplied.
+    #[pyo3(get)]
+    pub idempotency_key: String,
+    /// [v3.6] Priority band: higher drains first (e.g. alerts). Default 0.
+    #[pyo3(get)]
+    pub priority: i32,
+    /// [v3.6] Messages sharing an ordering key are always delivered in enqueue order.
+    #[pyo3(get)]
+    pub ordering_key: Option<String>,
+    /// [v3.6] Failed delivery attempts so far.
+    #[pyo3(get)]
+    pub attempts: u32,
+    #[pyo3(get)]
+    pub created_at_ms: u64,
+    /// [v3.6] Tags of the transaction that enqueued the message.
+    pub tags: Option<crate::tags::Tags>,
+    /// [v3.6] `idempotency_key` was generated, so a seeded engine may re-draw it on commit.
+    pub generated_key: bool,
 }
 
 #[pymethods]
 impl OutboxMsg {
     #[new]
-    fn new(topic: String, payload: PyObject) -> Self {
-        OutboxMsg { topic, payload: Arc::new(payload) }
+    #[pyo3(signature = (topic, payload, schema=None, content_type=None, idempotency_key=None, priority=0, ordering_key=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        topic: String,
+        payload: PyObject,
+        schema: Option<String>,
+        content_type: Option<String>,
+        idempotency_key: Option<String>,
+        priority: i32,
+        ordering_key: Option<String>,
+    ) -> Self {
+        let generated_key = idempotency_key.is_none();
+        let idempotency_key = idempotency_key.unwrap_or_else(crate::determinism::uuid4);
+        OutboxMsg {
+            topic,
+            payload: Arc::new(payload),
+            schema,
+            content_type,
+            idempotency_key,
+            priority,
+            ordering_key,
+            attempts: 0,
+            created_at_ms: crate::outbox::now_ms(),
+            tags: None,
+            generated_key,
+        }
     }
 
     #[getter]
     fn payload(&self, py: Python) -> PyObject {
         self.payload.as_ref().clone_ref(py)
     }
+
+    #[getter]
+    fn tags<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        crate::tags::to_dict(py, self.tags.as_ref())
+    }
+
+    /// Serialize the payload using `content_type` (or the default json codec).
+    fn encode(&self, py: Python) -> PyResult<Py<PyBytes>> {
+        let name = self.content_type.as_deref().unwrap_or(crate::outbox::DEFAULT_CONTENT_TYPE);
+        crate::outbox::encode_payload(py, name, self.payload.bind(py))
+    }
+
+    /// Rebuild a message from wire bytes produced by `encode()`.
+    #[staticmethod]
+    #[pyo3(signature = (topic, data, schema=None, content_type=None, idempotency_key=None))]
+    fn decode(py: Python, topic: String, data: &Bound<'_, PyAny>, schema: Option<String>, content_type: Option<String>, idempotency_key: Option<String>) -> PyResult<Self> {
+        let name = content_type.as_deref().unwrap_or(crate::outbox::DEFAULT_CONTENT_TYPE);
+        let payload = crate::outbox::decode_payload(py, name, data)?;
+        Ok(Self::new(topic, payload, schema, content_type, idempotency_key, 0, None))
+    }
+
+    fn __repr__(&self) -> String {
+        let opt = |v: &Option<String>| v.as_ref().map_or("None".to_string(), |s| format!("'{s}'"));
+        format!(
+            "OutboxMsg(topic='{}', schema={}, content_type={}, idempotency_key='{}')",
+            self.topic, opt(&self.schema), opt(&self.content_type), self.idempotency_key
+        )
+    }
 }
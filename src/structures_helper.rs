@@ -362,6 +362,65 @@ fn deep_update_at_path(py: Python, root: &Bound<'_, PyDict>, path: &str, value:
     Ok(())
 }
 
+/// [v3.6] Copy-on-write set used by streaming commits: every container along `path`
+/// (below `root`) that is not already in `owned` (object ids) is shallow-copied and relinked
+/// before descending, so the committed tree is never mutated. Placeholders (spilled /
+/// compressed values) are loaded; missing keys get fresh dicts. `value` becomes owned.
+#[allow(clippy::implicit_hasher)]
+pub fn set_nested_value_cow(py: Python, root: &Bound<'_, PyAny>, path: &str, value: &PyObject, owned: &mut std::collections::HashSet<usize>) -> PyResult<()> {
+    let segments = parse_path_segments(path);
+    let Some((last, parents)) = segments.split_last() else { return Ok(()) };
+    let mut current = root.clone();
+    for segment in parents {
+        let child = match get_child(&current, segment) {
+            Some(c) if owned.contains(&(c.as_ptr() as usize)) => c,
+            Some(c) if crate::spill::is_placeholder(&c) => crate::spill::fault_in(py, c.unbind())?.into_bound(py),
+            Some(c) => shallow_copy(py, &c)?,
+            None => PyDict::new_bound(py).into_any(),
+        };
+        if !owned.contains(&(child.as_ptr() as usize)) {
+            set_child(&current, segment, &child)?;
+            owned.insert(child.as_ptr() as usize);
+        }
+        current = child;
+    }
+    let value = value.bind(py).getattr("supervisor_target").unwrap_or_else(|_| value.bind(py).clone());
+    set_child(&current, last, &value)?;
+    owned.insert(value.as_ptr() as usize);
+    Ok(())
+}
+
+/// Shallow copy of a dict / list / object (`copy.copy`).
+pub fn shallow_copy<'py>(py: Python<'py>, value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if let Ok(d) = value.downcast::<PyDict>() {
+        return Ok(d.copy()?.into_any());
+    }
+    if let Ok(l) = value.downcast::<PyList>() {
+        return Ok(PyList::new_bound(py, l.iter()).into_any());
+    }
+    py.import_bound("copy")?.call_method1("copy", (value,))
+}
+
+fn get_child<'py>(container: &Bound<'py, PyAny>, segment: &PathSegment) -> Option<Bound<'py, PyAny>> {
+    match segment {
+        PathSegment::Key(key) => match container.downcast::<PyDict>() {
+            Ok(d) => d.get_item(key).ok()?,
+            Err(_) => container.getattr(key.as_str()).ok(),
+        },
+        PathSegment::Index(idx) => container.get_item(*idx).ok(),
+    }
+}
+
+fn set_child(container: &Bound<'_, PyAny>, segment: &PathSegment, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    match segment {
+        PathSegment::Key(key) => match container.downcast::<PyDict>() {
+            Ok(d) => d.set_item(key, value),
+            Err(_) => container.setattr(key.as_str(), value),
+        },
+        PathSegment::Index(idx) => container.set_item(*idx, value),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
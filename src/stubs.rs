@@ -0,0 +1,23 @@
+use pyo3::prelude::*;
+use std::path::PathBuf;
+
+// [v3.6] Python type stubs for this module, generated by build.rs from the #[pyclass] /
+// #[pymethods] / #[pyfunction] signatures of the build that produced it, so IDE support
+// cannot drift from the Rust surface. `scripts/gen_stubs.py` refreshes theus/theus_core.pyi
+// with it.
+
+const STUBS: &str = include_str!(concat!(env!("OUT_DIR"), "/theus_core.pyi"));
+
+/// [v3.6] The generated `.pyi` text; also written to `path` when given (a directory gets
+/// `theus_core.pyi` inside it).
+#[pyfunction]
+#[pyo3(signature = (path=None))]
+pub fn generate_stubs(path: Option<PathBuf>) -> PyResult<String> {
+    if let Some(mut path) = path {
+        if path.is_dir() {
+            path.push("theus_core.pyi");
+        }
+        std::fs::write(&path, STUBS)?;
+    }
+    Ok(STUBS.to_string())
+}
@@ -0,0 +1,35 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+// [v3.6] Transaction tags (correlation ids): `engine.transaction(tags={"request_id": ...})`.
+// The map is frozen when the transaction opens and shared (not copied) by every delta,
+// outbox message, audit event and lineage entry the transaction produces.
+
+pub type Tags = Arc<BTreeMap<String, String>>;
+
+/// Tags from a Python mapping; values are stored as `str(value)`. Empty maps yield None.
+pub fn from_py(tags: Option<&Bound<'_, PyDict>>) -> PyResult<Option<Tags>> {
+    let Some(tags) = tags else { return Ok(None) };
+    let mut map = BTreeMap::new();
+    for (k, v) in tags.iter() {
+        map.insert(k.extract::<String>()?, v.str()?.to_string());
+    }
+    Ok((!map.is_empty()).then(|| Arc::new(map)))
+}
+
+/// Python dict view (empty when untagged).
+pub fn to_dict<'py>(py: Python<'py>, tags: Option<&Tags>) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new_bound(py);
+    for (k, v) in tags.into_iter().flat_map(|t| t.iter()) {
+        d.set_item(k, v)?;
+    }
+    Ok(d)
+}
+
+/// True when every `filter` entry is present in `tags` (an empty filter matches all).
+pub fn matches(tags: Option<&Tags>, filter: Option<&Tags>) -> bool {
+    let Some(filter) = filter else { return true };
+    filter.iter().all(|(k, v)| tags.and_then(|t| t.get(k)) == Some(v))
+}
@@ -0,0 +1,400 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use crate::engine::{TheusEngine, Transaction};
+use crate::errors::Denial;
+use crate::messages;
+use crate::structures::State;
+use crate::locks::Relock;
+
+// [v3.6] Multi-tenant isolation inside one engine. `engine.tenant("acme")` registers the tenant
+// and returns a handle whose paths are rooted under `tenants.acme`:
+//   with engine.tenant("acme").transaction() as tx:
+//       tx.update(data={"domain": {"plan": "pro"}})     # -> tenants.acme.domain.plan
+//       tx.update_many([("domain.seats", 5)])           # -> tenants.acme.domain.seats
+//   engine.tenant("acme").read("domain.plan"), .query("$.domain.*")
+// Zones still resolve per segment, so `tenants.acme.meta_cfg` is Meta and
+// `tenants.acme.internal_keys` Private. Isolation is enforced at commit on the written
+// `tenants.<name>` roots: a tenant transaction may only write its own root, and unscoped
+// transactions (admin included) and `compare_and_swap` may not write under `tenants` at all
+// once a tenant is registered, unless a break-glass grant covers the root (see break_glass.rs).
+// `validate_contract(inputs, outputs)` roots a process contract under the tenant and refuses
+// paths escaping it. Refusals raise PermissionDeniedError (rule "tenant"); commit-time ones are
+// also audited.
+// Reads are isolated for tenant principals only: handle `read()` / `query()` and the tenant
+// transaction's `read()` resolve under the root (so `tenants.globex...` cannot be named), its
+// `snapshot` is withheld, and its process guards refuse other tenants' roots whatever the
+// contract says. Unscoped access is
+// operator access and sees every tenant: `engine.state`, plain and admin transactions,
+// `engine.query()`, the state server and snapshots / `dumps_state()`.
+// Usage per tenant (`engine.tenant_usage()`): keys (dict keys under the root, nested ones
+// included), bytes (approximate deep size), commits and outbox messages. Only the roots a
+// commit replaced are re-measured - untouched tenants keep their copy-on-write subtree and
+// their numbers. `engine.set_tenant_quota("acme", max_bytes=...)` caps any of the four; a
+// commit that would exceed one raises TenantQuotaExceededError (a QuotaExceededError with
+// `tenant` and `resource`). Keys / bytes only count against a quota when they grow, so a
+// tenant over a lowered quota can still delete.
+// NOTE: Snapshot restores (`load_state`) replace the whole state and are not checked; they
+// re-measure keys and bytes but keep the commit / outbox counters.
+
+pub const ROOT: &str = "tenants";
+
+#[derive(Default, Clone, Copy)]
+struct Usage {
+    keys: u64,
+    bytes: u64,
+    commits: u64,
+    outbox: u64,
+}
+
+#[derive(Default, Clone, Copy)]
+#[allow(clippy::struct_field_names)]
+pub struct Quota {
+    pub max_keys: Option<u64>,
+    pub max_bytes: Option<u64>,
+    pub max_commits: Option<u64>,
+    pub max_outbox: Option<u64>,
+}
+
+#[derive(Default)]
+struct Account {
+    usage: Usage,
+    quota: Quota,
+}
+
+/// `(tenant, keys, bytes)` of the roots a commit replaced, measured before it is installed.
+pub type Measured = Vec<(String, u64, u64)>;
+
+#[derive(Default)]
+pub struct Tenants {
+    accounts: Mutex<BTreeMap<String, Account>>,
+}
+
+fn validate_name(name: &str) -> PyResult<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        && crate::zones::resolve_zone(name) == crate::zones::ContextZone::Data;
+    if !valid {
+        return Err(PyValueError::new_err(format!(
+            "Invalid tenant name '{name}' (letters, digits, '_' and '-'; no leading '_' or zone prefix)"
+        )));
+    }
+    Ok(())
+}
+
+/// `tenants.<name>`.
+pub fn root(name: &str) -> String {
+    format!("{ROOT}.{name}")
+}
+
+/// `path` (relative to the tenant) rooted under `tenants.<name>`.
+pub fn scoped(name: &str, path: &str) -> PyResult<String> {
+    if path.is_empty() || path.starts_with(['.', '[', '$']) || path.split('.').any(str::is_empty) {
+        return Err(PyValueError::new_err(format!("Invalid tenant path '{path}'")));
+    }
+    Ok(format!("{}.{path}", root(name)))
+}
+
+/// `path` lies under the tenant roots but outside `tenants.<name>` (`tenants` itself, the way
+/// down to the root, does not count).
+pub fn outside(name: &str, path: &str) -> bool {
+    let own = root(name);
+    path.split(['.', '[']).next() == Some(ROOT)
+        && path != ROOT
+        && !path.strip_prefix(&own).is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// Refusal for a tenant transaction's guard touching another tenant's `path`.
+pub fn guard_denied(py: Python, name: &str, path: &str) -> PyErr {
+    let scope = format!("tenant '{name}'");
+    let reason = format!("outside tenant root '{}'", root(name));
+    let message = messages::render(messages::TENANT_ISOLATION, &[("scope", &scope), ("path", &path), ("reason", &reason)]);
+    crate::errors::permission_denied(py, message, &Denial::new(path, "tenant"))
+}
+
+/// Tenant owning the written `zone.field` path, if it lies under the tenant roots.
+/// `Some(None)` is a write replacing every root at once.
+#[allow(clippy::option_option)]
+fn owner(path: &str) -> Option<Option<&str>> {
+    match path.split_once('.') {
+        Some((ROOT, rest)) => Some(Some(rest.split(['.', '[']).next().unwrap_or(rest))),
+        None if path == ROOT => Some(None),
+        _ => None,
+    }
+}
+
+/// Subtree of tenant `name` in `state`.
+fn subtree<'py>(py: Python<'py>, state: &State, name: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Some(zone) = state.data.get(ROOT) else { return Ok(None) };
+    match zone.bind(py).downcast::<PyDict>() {
+        Ok(zone) => zone.get_item(name),
+        Err(_) => Ok(None),
+    }
+}
+
+fn count_keys(value: &Bound<'_, PyAny>) -> u64 {
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        dict.iter().map(|(_, v)| 1 + count_keys(&v)).sum()
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        list.iter().map(|v| count_keys(&v)).sum()
+    } else {
+        0
+    }
+}
+
+/// `(keys, bytes)` under tenant `name`.
+fn measure(py: Python, state: &State, name: &str) -> PyResult<(u64, u64)> {
+    Ok(subtree(py, state, name)?.map_or((0, 0), |v| (count_keys(&v), crate::metrics::estimate_size(&v, 0))))
+}
+
+/// `requested` units of `resource` for `tenant` over `limit`.
+fn over(py: Python, tenant: &str, resource: &str, limit: Option<u64>, requested: u64) -> PyResult<()> {
+    match limit {
+        Some(limit) if requested > limit => Err(crate::errors::tenant_quota_exceeded(py, tenant, resource, limit, requested)),
+        _ => Ok(()),
+    }
+}
+
+impl Tenants {
+    /// Adds tenant `name` (measured from `state`); registering it again is a no-op.
+    pub fn register(&self, py: Python, name: &str, state: &State) -> PyResult<()> {
+        validate_name(name)?;
+        if self.accounts.relock().contains_key(name) {
+            return Ok(());
+        }
+        let (keys, bytes) = measure(py, state, name)?;
+        let usage = Usage { keys, bytes, ..Usage::default() };
+        self.accounts.relock().entry(name.to_string()).or_insert(Account { usage, quota: Quota::default() });
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.accounts.relock().keys().cloned().collect()
+    }
+
+    pub fn set_quota(&self, name: &str, quota: Quota) -> PyResult<()> {
+        let mut accounts = self.accounts.relock();
+        let account = accounts.get_mut(name).ok_or_else(|| PyKeyError::new_err(format!("Unknown tenant '{name}'")))?;
+        account.quota = quota;
+        Ok(())
+    }
+
+    /// Measures the tenant roots replaced between `old` and `new` and enforces the key, byte
+    /// and commit quotas on them.
+    pub fn prepare(&self, py: Python, old: &State, new: &State) -> PyResult<Measured> {
+        let accounts: Vec<(String, Usage, Quota)> = self.accounts.relock().iter().map(|(n, a)| (n.clone(), a.usage, a.quota)).collect();
+        let mut measured = Vec::new();
+        for (name, usage, quota) in accounts {
+            let (before, after) = (subtree(py, old, &name)?, subtree(py, new, &name)?);
+            let same = match (&before, &after) {
+                (Some(b), Some(a)) => b.is(a),
+                (None, None) => true,
+                _ => false,
+            };
+            if same {
+                continue;
+            }
+            let (keys, bytes) = measure(py, new, &name)?;
+            if keys > usage.keys {
+                over(py, &name, "keys", quota.max_keys, keys)?;
+            }
+            if bytes > usage.bytes {
+                over(py, &name, "bytes", quota.max_bytes, bytes)?;
+            }
+            over(py, &name, "commits", quota.max_commits, usage.commits + 1)?;
+            measured.push((name, keys, bytes));
+        }
+        Ok(measured)
+    }
+
+    /// Books a commit installed with the `measured` roots.
+    pub fn committed(&self, measured: Measured) {
+        let mut accounts = self.accounts.relock();
+        for (name, keys, bytes) in measured {
+            if let Some(account) = accounts.get_mut(&name) {
+                account.usage = Usage { keys, bytes, commits: account.usage.commits + 1, ..account.usage };
+            }
+        }
+    }
+
+    pub fn sent(&self, name: &str, messages: usize) {
+        if let Some(account) = self.accounts.relock().get_mut(name) {
+            account.usage.outbox += messages as u64;
+        }
+    }
+
+    /// Re-measures keys and bytes of every tenant from `state` (after a restore).
+    pub fn rebuild(&self, py: Python, state: &State) -> PyResult<()> {
+        for name in self.names() {
+            let (keys, bytes) = measure(py, state, &name)?;
+            if let Some(account) = self.accounts.relock().get_mut(&name) {
+                (account.usage.keys, account.usage.bytes) = (keys, bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// `{tenant: {keys, bytes, commits, outbox, quota: {max_keys, ...}}}`.
+    pub fn usage(&self, py: Python) -> PyResult<PyObject> {
+        let out = PyDict::new_bound(py);
+        for (name, account) in self.accounts.relock().iter() {
+            let Account { usage, quota } = account;
+            let row = PyDict::new_bound(py);
+            row.set_item("keys", usage.keys)?;
+            row.set_item("bytes", usage.bytes)?;
+            row.set_item("commits", usage.commits)?;
+            row.set_item("outbox", usage.outbox)?;
+            let limits = PyDict::new_bound(py);
+            limits.set_item("max_keys", quota.max_keys)?;
+            limits.set_item("max_bytes", quota.max_bytes)?;
+            limits.set_item("max_commits", quota.max_commits)?;
+            limits.set_item("max_outbox", quota.max_outbox)?;
+            row.set_item("quota", limits)?;
+            out.set_item(name, row)?;
+        }
+        Ok(out.into_any().unbind())
+    }
+
+    /// Refuses a commit by `tenant` (None = unscoped) writing the `zone.field` paths `written`
+    /// and sending `outbox` messages, unless a break-glass grant covers the path.
+    pub fn check(&self, py: Python, tenant: Option<&str>, written: &[String], outbox: usize, glass: &crate::break_glass::BreakGlass) -> PyResult<()> {
+        let accounts = self.accounts.relock();
+        if tenant.is_none() && accounts.is_empty() {
+            return Ok(());
+        }
+        if let Some((t, account)) = tenant.and_then(|t| Some((t, accounts.get(t)?))).filter(|_| outbox > 0) {
+            over(py, t, "outbox", account.quota.max_outbox, account.usage.outbox + outbox as u64)?;
+        }
+        // A bare `tenants` entry next to `tenants.<name>` ones is just the zone of those writes.
+        let nested = written.iter().any(|p| matches!(owner(p), Some(Some(_))));
+        for path in written {
+            let reason = match (tenant, owner(path)) {
+                (_, Some(None)) if nested => continue,
+                (Some(t), Some(Some(o))) if t == o => continue,
+                (Some(_), Some(Some(o))) => format!("owned by tenant '{o}'"),
+                (Some(t), _) => format!("outside tenant root '{}'", root(t)),
+                (None, Some(_)) => "tenant data is only writable through tenant handles".to_string(),
+                (None, None) => continue,
+            };
+            let scope = tenant.map_or_else(|| "unscoped transaction".to_string(), |t| format!("tenant '{t}'"));
+            if let Some(id) = glass.covering(path) {
+                crate::audit::log_global("BREAK_GLASS_USED", &format!("Break-glass #{id}: {scope} wrote '{path}' ({reason})"));
+                continue;
+            }
+            let message = messages::render(messages::TENANT_ISOLATION, &[("scope", &scope), ("path", &path), ("reason", &reason)]);
+            crate::audit::log_global("TENANT_ISOLATION_DENIED", &message.text);
+            return Err(crate::errors::permission_denied(py, message, &Denial::new(path, "tenant")));
+        }
+        Ok(())
+    }
+}
+
+/// Handle on one tenant's slice of the engine (`engine.tenant(name)`).
+#[pyclass(module = "theus_core")]
+pub struct TenantHandle {
+    engine: Py<TheusEngine>,
+    name: String,
+}
+
+impl TenantHandle {
+    pub fn new(engine: Py<TheusEngine>, name: String) -> Self {
+        TenantHandle { engine, name }
+    }
+}
+
+#[pymethods]
+impl TenantHandle {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// `tenants.<name>`.
+    #[getter]
+    fn root(&self) -> String {
+        root(&self.name)
+    }
+
+    /// Absolute path of the tenant-relative `path`.
+    fn path(&self, path: &str) -> PyResult<String> {
+        scoped(&self.name, path)
+    }
+
+    /// Latest committed value at the tenant-relative `path` (containers as read-only proxies).
+    #[pyo3(signature = (path, default=None))]
+    fn read(&self, py: Python, path: &str, default: Option<PyObject>) -> PyResult<PyObject> {
+        let path = scoped(&self.name, path)?;
+        let state = self.engine.borrow(py).state.clone_ref(py);
+        let state = state.bind(py).borrow();
+        crate::pins::read_path(py, &state, &path, default)
+    }
+
+    /// Transaction whose writes and reads are rooted under this tenant (same options as
+    /// `engine.transaction()`, minus `admin`).
+    #[pyo3(signature = (write_timeout_ms=5000, actor=None, tags=None, isolation=None, op_id=None, partial_commit=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn transaction<'py>(&self, py: Python<'py>, write_timeout_ms: u64, actor: Option<String>, tags: Option<&Bound<'py, PyDict>>, isolation: Option<&str>, op_id: Option<String>, partial_commit: bool) -> PyResult<Bound<'py, Transaction>> {
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("write_timeout_ms", write_timeout_ms)?;
+        kwargs.set_item("actor", actor)?;
+        kwargs.set_item("tags", tags)?;
+        kwargs.set_item("isolation", isolation)?;
+        kwargs.set_item("op_id", op_id)?;
+        kwargs.set_item("partial_commit", partial_commit)?;
+        let tx = self.engine.bind(py).call_method("transaction", (), Some(&kwargs))?.downcast_into::<Transaction>()?;
+        tx.borrow_mut().tenant = Some(self.name.clone());
+        Ok(tx)
+    }
+
+    /// `engine.query()` with `$` at the tenant root; result paths are tenant-relative.
+    #[pyo3(signature = (expr, with_paths=false))]
+    fn query(&self, py: Python, expr: &str, with_paths: bool) -> PyResult<PyObject> {
+        let rest = expr.trim().strip_prefix('$').unwrap_or(expr.trim());
+        let sep = if rest.is_empty() || rest.starts_with(['.', '[']) { "" } else { "." };
+        let query = crate::query::Query::parse(&format!("$.{}{sep}{rest}", root(&self.name)))?;
+        let state = self.engine.borrow(py).state.clone_ref(py);
+        let found = query.run(py, &state.bind(py).borrow(), with_paths)?;
+        if !with_paths {
+            return Ok(found);
+        }
+        let prefix = format!("{}.", root(&self.name));
+        let out = PyList::empty_bound(py);
+        for item in found.bind(py).iter()? {
+            let (path, value): (String, PyObject) = item?.extract()?;
+            let path = path.strip_prefix(&prefix).map_or(path.clone(), str::to_string);
+            out.append(PyTuple::new_bound(py, [PyString::new_bound(py, &path).into_any(), value.into_bound(py)]))?;
+        }
+        Ok(out.into_any().unbind())
+    }
+
+    /// Contract paths (tenant-relative, or absolute under this tenant's root) rooted under the
+    /// tenant: `(inputs, outputs)`. Raises `PermissionDeniedError` (rule "tenant") for the first
+    /// path that escapes the root.
+    #[pyo3(signature = (inputs=None, outputs=None))]
+    #[allow(clippy::needless_pass_by_value)]
+    fn validate_contract(&self, py: Python, inputs: Option<Vec<String>>, outputs: Option<Vec<String>>) -> PyResult<(Vec<String>, Vec<String>)> {
+        let own = root(&self.name);
+        let rooted = |path: &String| -> PyResult<String> {
+            let escapes = path.starts_with(['.', '$', '['])
+                || path.contains("..")
+                || (path.split(['.', '[']).next() == Some(ROOT)
+                    && !path.strip_prefix(&own).is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '['])));
+            if escapes {
+                let scope = format!("tenant '{}'", self.name);
+                let reason = format!("contract path outside tenant root '{own}'");
+                let message = messages::render(messages::TENANT_ISOLATION, &[("scope", &scope), ("path", path), ("reason", &reason)]);
+                return Err(crate::errors::permission_denied(py, message, &Denial::new(path, "tenant")));
+            }
+            if path.split(['.', '[']).next() == Some(ROOT) { Ok(path.clone()) } else { scoped(&self.name, path) }
+        };
+        let inputs = inputs.iter().flatten().map(&rooted).collect::<PyResult<_>>()?;
+        Ok((inputs, outputs.iter().flatten().map(&rooted).collect::<PyResult<_>>()?))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<TenantHandle '{}' at {}>", self.name, root(&self.name))
+    }
+}
@@ -55,7 +55,7 @@ impl TheusTensorGuard {
              let mut tx_ref = tx.bind(py).borrow_mut();
              tx_ref.log_internal(
                 self.path.clone(),
-                "TENSOR_MUTATION".to_string(),
+                crate::delta::DeltaOp::TensorMutation.as_str().to_string(),
                 None, 
                 None,
                 Some(self.inner.clone_ref(py)),
@@ -0,0 +1,340 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyAssertionError;
+use pyo3::types::{PyDict, PyList, PyTuple};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::audit::AuditLogEntry;
+use crate::engine::TheusEngine;
+use crate::structures::OutboxMsg;
+use crate::locks::Relock;
+
+// [v3.6] `theus_core.testing`: an ephemeral engine for test suites. `Harness(data)` builds a
+// fresh TheusEngine on the test clock, seeded with a fixed determinism seed, and, while open,
+// captures audit events, the outbox messages its transactions commit and the accesses that
+// guards / proxies refuse. `assert_delta(path)` / `assert_denied(path)` check those captures,
+// so tests stop hand-rolling the same plumbing over the engine internals.
+// `engine.testing.simulate_conflict(paths, at_version)` arms a one-shot write conflict, so
+// retry paths can be unit-tested without racing real writers.
+// NOTE: The clock is process-wide, as are audit events and denials: they are captured from
+// every engine while a harness is open. The seed and outbox capture are per engine.
+
+static OPEN: AtomicUsize = AtomicUsize::new(0);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static CAPTURES: Mutex<Vec<(u64, Arc<Capture>)>> = Mutex::new(Vec::new());
+
+#[derive(Default)]
+struct Capture {
+    /// `TheusEngine::outbox_key` of the harness engine.
+    engine: usize,
+    audit: Mutex<Vec<AuditLogEntry>>,
+    outbox: Mutex<Vec<OutboxMsg>>,
+    denials: Mutex<Vec<(String, String)>>,
+}
+
+fn each_capture(f: impl Fn(&Capture)) {
+    if OPEN.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    for (_, capture) in CAPTURES.relock().iter() {
+        f(capture);
+    }
+}
+
+/// Records an audit event for open harnesses.
+pub fn on_audit(entry: &AuditLogEntry) {
+    each_capture(|c| c.audit.relock().push(entry.clone()));
+}
+
+/// Records outbox messages committed to the engine identified by `engine` (`outbox_key`).
+pub fn on_outbox(engine: usize, msgs: &[OutboxMsg]) {
+    each_capture(|c| {
+        if c.engine == engine {
+            c.outbox.relock().extend(msgs.iter().cloned());
+        }
+    });
+}
+
+/// `PermissionDeniedError` for a refused access (`denial`), recorded for open harnesses.
+#[allow(clippy::needless_pass_by_value)]
+pub fn denied(denial: crate::errors::Denial, message: crate::messages::Message) -> PyErr {
+    let path = denial.path.unwrap_or_default();
+    each_capture(|c| c.denials.relock().push((path.to_string(), message.text.clone())));
+    Python::with_gil(|py| crate::errors::permission_denied(py, message, &denial))
+}
+
+/// `path` equals `other` or one lies below the other (`a.b` vs `a.b.c` / `a.b[0]`).
+fn overlaps(path: &str, other: &str) -> bool {
+    let below = |a: &str, b: &str| a.strip_prefix(b).is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['));
+    path == other || below(path, other) || below(other, path)
+}
+
+/// Committed value at `path` (`zone.field.key`, `[i]` indexes sequences).
+fn resolve<'py>(root: &Bound<'py, PyAny>, path: &str) -> PyResult<Bound<'py, PyAny>> {
+    let mut value = root.clone();
+    for segment in path.split('.') {
+        let (name, indexes) = segment.split_once('[').map_or((segment, ""), |(n, rest)| (n, rest));
+        if !name.is_empty() {
+            value = match value.downcast::<PyDict>() {
+                Ok(d) => d.get_item(name)?.ok_or_else(|| PyAssertionError::new_err(format!("'{path}' does not exist")))?,
+                Err(_) => value.getattr(name)?,
+            };
+        }
+        for index in indexes.split('[').map(|i| i.trim_end_matches(']')).filter(|i| !i.is_empty()) {
+            value = match index.parse::<isize>() {
+                Ok(i) if value.downcast::<PyDict>().is_err() => value.get_item(i)?,
+                _ => value.get_item(index)?,
+            };
+        }
+    }
+    Ok(value)
+}
+
+struct Armed {
+    paths: Vec<String>,
+    at_version: Option<u64>,
+}
+
+/// Per-engine one-shot conflicts armed by `simulate_conflict`.
+#[derive(Default)]
+pub struct ConflictSim {
+    armed: Mutex<Vec<Armed>>,
+}
+
+impl ConflictSim {
+    /// Consumes the first armed conflict overlapping a path in `written` (`zone.field`)
+    /// and returns the version the phantom writer reached.
+    pub fn take(&self, written: &[String], current_version: u64) -> Option<u64> {
+        let mut armed = self.armed.relock();
+        if armed.is_empty() {
+            return None;
+        }
+        let idx = armed.iter().position(|a| a.paths.iter().any(|p| written.iter().any(|w| overlaps(p, w))))?;
+        let hit = armed.remove(idx);
+        Some(hit.at_version.unwrap_or(current_version + 1))
+    }
+}
+
+/// `zone.field` paths written by a `{zone: {field: value}}` update (`zone` for non-dict zones).
+pub fn written_paths(data: &Bound<'_, PyDict>) -> PyResult<Vec<String>> {
+    let mut paths = Vec::new();
+    for (zone, fields) in data.iter() {
+        let zone = zone.extract::<String>()?;
+        match fields.downcast::<PyDict>() {
+            Ok(fields) => {
+                for (k, _) in fields.iter() {
+                    paths.push(format!("{zone}.{}", k.str()?));
+                }
+            }
+            Err(_) => paths.push(zone),
+        }
+    }
+    Ok(paths)
+}
+
+/// `engine.testing`: fault helpers for the engine's own tests.
+#[pyclass(module = "theus_core.testing")]
+pub struct EngineTesting {
+    conflicts: Arc<ConflictSim>,
+}
+
+impl EngineTesting {
+    pub fn new(conflicts: Arc<ConflictSim>) -> Self {
+        EngineTesting { conflicts }
+    }
+}
+
+#[pymethods]
+impl EngineTesting {
+    /// Make the next CAS / commit writing any of `paths` (or a path above / below one)
+    /// fail once with the regular CAS conflict error, as if another writer had reached
+    /// `at_version` (default: current version + 1). Arming twice queues two conflicts.
+    #[pyo3(signature = (paths, at_version=None))]
+    fn simulate_conflict(&self, paths: Vec<String>, at_version: Option<u64>) -> PyResult<()> {
+        if paths.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err("simulate_conflict needs at least one path"));
+        }
+        self.conflicts.armed.relock().push(Armed { paths, at_version });
+        Ok(())
+    }
+
+    /// Armed conflicts not yet observed, as `(paths, at_version)`.
+    #[getter]
+    fn pending_conflicts(&self) -> Vec<(Vec<String>, Option<u64>)> {
+        self.conflicts.armed.relock().iter().map(|a| (a.paths.clone(), a.at_version)).collect()
+    }
+
+    fn clear_conflicts(&self) {
+        self.conflicts.armed.relock().clear();
+    }
+}
+
+/// Ephemeral engine with deterministic time and captured side effects.
+#[pyclass(module = "theus_core.testing")]
+pub struct Harness {
+    id: u64,
+    /// The engine handed to `Harness(engine=...)` (e.g. a `theus.engine.TheusEngine`), if any.
+    wrapper: Option<PyObject>,
+    engine: Py<TheusEngine>,
+    capture: Arc<Capture>,
+    /// Deltas committed at or below this version predate the harness (or `clear()`).
+    baseline: u64,
+    open: bool,
+}
+
+fn version(py: Python, engine: &Py<TheusEngine>) -> PyResult<u64> {
+    engine.bind(py).getattr("state")?.getattr("version")?.extract()
+}
+
+#[pymethods]
+impl Harness {
+    /// Fresh engine hydrated with `data`, clock at `start_ms`, determinism `seed`. Pass
+    /// `engine` (a core engine or a wrapper exposing `_core`) to capture an existing one;
+    /// `strict_guards` only configures a fresh engine.
+    #[new]
+    #[pyo3(signature = (data=None, seed=0, start_ms=0, strict_guards=false, engine=None))]
+    fn new(py: Python, data: Option<&Bound<'_, PyDict>>, seed: u64, start_ms: u64, strict_guards: bool, engine: Option<PyObject>) -> PyResult<Self> {
+        crate::clock::use_test(Some(start_ms));
+        let core: Py<TheusEngine> = match &engine {
+            Some(e) => {
+                let e = e.bind(py);
+                match e.getattr("_core") {
+                    Ok(core) => core.extract()?,
+                    Err(_) => e.extract()?,
+                }
+            }
+            None => py.get_type_bound::<TheusEngine>().call0()?.extract()?,
+        };
+        let bound = core.bind(py);
+        bound.borrow().determinism.set_seed(Some(seed));
+        if engine.is_none() {
+            bound.call_method1("set_strict_guards", (strict_guards,))?;
+        }
+        if let Some(data) = data {
+            bound.call_method1("compare_and_swap", (bound.getattr("state")?.getattr("version")?, data))?;
+        }
+        let capture = Arc::new(Capture { engine: bound.borrow().outbox_key(), ..Capture::default() });
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        CAPTURES.relock().push((id, capture.clone()));
+        OPEN.fetch_add(1, Ordering::Relaxed);
+        let baseline = version(py, &core)?;
+        Ok(Harness { id, wrapper: engine, engine: core, capture, baseline, open: true })
+    }
+
+    /// The engine under test (as passed in, or the fresh core engine).
+    #[getter]
+    fn engine(&self, py: Python) -> PyObject {
+        self.wrapper.as_ref().map_or_else(|| self.engine.clone_ref(py).into_any(), |w| w.clone_ref(py))
+    }
+
+    /// Advance the (process-wide) test clock; returns the new wall time in ms.
+    #[allow(clippy::unused_self)]
+    fn advance_ms(&self, ms: u64) -> Option<u64> {
+        crate::clock::advance(ms)
+    }
+
+    /// Captured audit events, optionally only those with `key`.
+    #[pyo3(signature = (key=None))]
+    fn audit_events(&self, key: Option<&str>) -> Vec<AuditLogEntry> {
+        self.capture.audit.relock().iter().filter(|e| key.is_none_or(|k| e.key == k)).cloned().collect()
+    }
+
+    /// Outbox messages committed by this engine, optionally only those on `topic`.
+    #[pyo3(signature = (topic=None))]
+    fn outbox(&self, topic: Option<&str>) -> Vec<OutboxMsg> {
+        self.capture.outbox.relock().iter().filter(|m| topic.is_none_or(|t| m.topic == t)).cloned().collect()
+    }
+
+    /// Refused accesses as `(path, message)`.
+    #[getter]
+    fn denials(&self) -> Vec<(String, String)> {
+        self.capture.denials.relock().clone()
+    }
+
+    /// Assert a commit since the harness opened changed `path` (or a path above / below
+    /// it) and, if given, that its committed value now equals `value`. Returns the newest
+    /// matching `blame()` record.
+    #[pyo3(signature = (path, *args))]
+    fn assert_delta(&self, py: Python, path: &str, args: &Bound<'_, PyTuple>) -> PyResult<PyObject> {
+        let engine = self.engine.bind(py);
+        let records = engine.call_method1("blame", (path, usize::MAX))?;
+        let records = records.downcast::<PyList>()?;
+        let mut newest = None;
+        for record in records.iter() {
+            if record.get_item("version")?.extract::<u64>()? > self.baseline {
+                newest = Some(record);
+                break;
+            }
+        }
+        let Some(newest) = newest else {
+            return Err(PyAssertionError::new_err(format!("No committed delta touched '{path}'")));
+        };
+        if let Some(expected) = args.iter().next() {
+            let actual = resolve(&engine.getattr("state")?.getattr("data")?, path)?;
+            if !actual.eq(&expected)? {
+                return Err(PyAssertionError::new_err(format!(
+                    "'{path}' is {} after commit, expected {}", actual.repr()?, expected.repr()?
+                )));
+            }
+        }
+        Ok(newest.unbind())
+    }
+
+    /// Assert an access to `path` (or a path above / below it) was refused; returns the
+    /// `PermissionError` message.
+    fn assert_denied(&self, path: &str) -> PyResult<String> {
+        let denials = self.capture.denials.relock();
+        if let Some((_, message)) = denials.iter().find(|(p, _)| overlaps(p, path)) {
+            return Ok(message.clone());
+        }
+        let seen: Vec<&str> = denials.iter().map(|(p, _)| p.as_str()).collect();
+        Err(PyAssertionError::new_err(format!("No access to '{path}' was denied (denied: {seen:?})")))
+    }
+
+    /// Forget captured events and deltas committed so far.
+    fn clear(&mut self, py: Python) -> PyResult<()> {
+        self.capture.audit.relock().clear();
+        self.capture.outbox.relock().clear();
+        self.capture.denials.relock().clear();
+        self.baseline = version(py, &self.engine)?;
+        Ok(())
+    }
+
+    /// Stop capturing and restore the system clock and the engine's real randomness.
+    fn close(&mut self) {
+        if !self.open {
+            return;
+        }
+        self.open = false;
+        CAPTURES.relock().retain(|(id, _)| *id != self.id);
+        Python::with_gil(|py| {
+            if let Ok(engine) = self.engine.bind(py).try_borrow() {
+                engine.determinism.set_seed(None);
+            }
+        });
+        if OPEN.fetch_sub(1, Ordering::Relaxed) == 1 {
+            crate::clock::use_system();
+        }
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        self.close();
+        false
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+pub fn theus_testing(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Harness>()?;
+    m.add_class::<EngineTesting>()?;
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyDict, PyList};
+use std::collections::BTreeMap;
+
+// [v3.6] Timer service. `engine.schedule_signal(key, value, ...)` and
+// `engine.schedule_process(name, ...)` register one-shot (`delay_ms` / `at_ms`) or recurring
+// (`every_ms`) timers on the engine clock. `take_due_timers()` hands out the timers that are
+// due (advancing recurring ones) and the Python engine fires them: `fire_timers()` commits the
+// signal / executes the process, `run_timers()` loops over it.
+// Pending timers travel with `dumps_state()` blobs, so a restarted engine resumes them.
+// NOTE: A recurring timer that missed several periods (engine down, nobody firing) fires once
+// and moves to its next future slot - no burst of catch-up runs. Times are wall-clock
+// (`clock::now_ms`) so they stay meaningful across restarts.
+
+pub enum Action {
+    /// Commit `{key: value}` to the Signal zone.
+    Signal { key: String, value: PyObject },
+    /// `execute(process, **kwargs)`.
+    Process { name: String, kwargs: Option<Py<PyDict>> },
+}
+
+pub struct Timer {
+    fire_at_ms: u64,
+    every_ms: Option<u64>,
+    fired: u64,
+    action: Action,
+}
+
+#[derive(Default)]
+pub struct Timers {
+    timers: BTreeMap<String, Timer>,
+    next_id: u64,
+}
+
+impl Action {
+    fn kind(&self) -> &'static str {
+        match self {
+            Action::Signal { .. } => "signal",
+            Action::Process { .. } => "process",
+        }
+    }
+
+    fn target(&self) -> &str {
+        match self {
+            Action::Signal { key, .. } => key,
+            Action::Process { name, .. } => name,
+        }
+    }
+
+    fn payload(&self, py: Python) -> PyObject {
+        match self {
+            Action::Signal { value, .. } => value.clone_ref(py),
+            Action::Process { kwargs, .. } => kwargs.as_ref().map_or_else(|| py.None(), |k| k.clone_ref(py).into_any()),
+        }
+    }
+}
+
+impl Timers {
+    /// Registers a timer (replacing one with the same id); returns its id.
+    pub fn schedule(&mut self, id: Option<String>, action: Action, delay_ms: u64, at_ms: Option<u64>, every_ms: Option<u64>) -> PyResult<String> {
+        if every_ms == Some(0) {
+            return Err(PyValueError::new_err("every_ms must be positive"));
+        }
+        let id = id.unwrap_or_else(|| loop {
+            self.next_id += 1;
+            let id = format!("timer-{}", self.next_id);
+            if !self.timers.contains_key(&id) {
+                break id;
+            }
+        });
+        // A recurring timer without an explicit start first fires one period from now.
+        let fire_at_ms = match (at_ms, every_ms) {
+            (Some(at), _) => at,
+            (None, Some(every)) if delay_ms == 0 => crate::clock::now_ms() + every,
+            (None, _) => crate::clock::now_ms() + delay_ms,
+        };
+        self.timers.insert(id.clone(), Timer { fire_at_ms, every_ms, fired: 0, action });
+        Ok(id)
+    }
+
+    pub fn cancel(&mut self, id: &str) -> bool {
+        self.timers.remove(id).is_some()
+    }
+
+    /// Removes due one-shot timers and reschedules due recurring ones; returns what to fire
+    /// as `(id, kind, target, payload)`, earliest first.
+    pub fn take_due(&mut self, py: Python, now_ms: u64) -> Vec<(String, &'static str, String, PyObject)> {
+        let mut due: Vec<(u64, String)> = self.timers.iter().filter(|(_, t)| t.fire_at_ms <= now_ms).map(|(id, t)| (t.fire_at_ms, id.clone())).collect();
+        due.sort();
+        let mut out = Vec::with_capacity(due.len());
+        for (_, id) in due {
+            let timer = self.timers.get_mut(&id).expect("due timer");
+            out.push((id.clone(), timer.action.kind(), timer.action.target().to_string(), timer.action.payload(py)));
+            match timer.every_ms {
+                Some(every) => {
+                    timer.fired += 1;
+                    let missed = (now_ms - timer.fire_at_ms) / every;
+                    timer.fire_at_ms += (missed + 1) * every;
+                }
+                None => {
+                    self.timers.remove(&id);
+                }
+            }
+        }
+        out
+    }
+
+    /// `[{id, kind, target, payload, fire_at_ms, every_ms, fired}]` ordered by id.
+    pub fn export<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let out = PyList::empty_bound(py);
+        for (id, t) in &self.timers {
+            let row = PyDict::new_bound(py);
+            row.set_item("id", id)?;
+            row.set_item("kind", t.action.kind())?;
+            row.set_item("target", t.action.target())?;
+            row.set_item("payload", t.action.payload(py))?;
+            row.set_item("fire_at_ms", t.fire_at_ms)?;
+            row.set_item("every_ms", t.every_ms)?;
+            row.set_item("fired", t.fired)?;
+            out.append(row)?;
+        }
+        Ok(out)
+    }
+
+    /// Replaces all timers with rows produced by `export`.
+    pub fn restore(&mut self, rows: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut timers = BTreeMap::new();
+        for row in rows.iter()? {
+            let row = row?;
+            let field = |k: &str| row.get_item(k);
+            let target: String = field("target")?.extract()?;
+            let payload = field("payload")?;
+            let action = match field("kind")?.extract::<String>()?.as_str() {
+                "signal" => Action::Signal { key: target, value: payload.unbind() },
+                "process" => Action::Process { name: target, kwargs: (!payload.is_none()).then(|| payload.downcast_into::<PyDict>().map(Bound::unbind)).transpose()? },
+                other => return Err(PyValueError::new_err(format!("Unknown timer kind '{other}'"))),
+            };
+            let timer = Timer {
+                fire_at_ms: field("fire_at_ms")?.extract()?,
+                every_ms: field("every_ms")?.extract()?,
+                fired: field("fired")?.extract()?,
+                action,
+            };
+            timers.insert(field("id")?.extract()?, timer);
+        }
+        self.timers = timers;
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use crate::locks::Relock;
+
+// [v3.6] Trace mode for debugging one misbehaving process. `engine.trace("name")` arms a
+// one-shot access log for that process's next run: while the run is in flight every guard /
+// proxy read and write under its transaction is recorded as {path, access, allowed, rule,
+// at_us, duration_us}. When the run's task finishes the report is stored on the engine and
+// `engine.trace_report("name")` returns it.
+//
+// The same sessions feed contract drift tracking (`track_contract_drift(window)`): every run
+// then keeps the set of allowed (path, access) pairs, and the last `window` sets per process
+// are what `observed_access(process)` aggregates.
+// NOTE: Untraced cost is one relaxed atomic load per access. Events are capped per run
+// (`max_events`); the report counts the ones dropped. Commit-time checks (after the process
+// body returns) are not part of the run and are not traced.
+
+pub const DEFAULT_MAX_EVENTS: usize = 10_000;
+
+/// Traced runs in flight (fast path: zero means nothing to record).
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static SESSIONS: LazyLock<Mutex<Vec<Arc<Session>>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+thread_local! {
+    /// Rule of the last denial raised on this thread (read by the enclosing span).
+    static DENIED: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+struct Event {
+    path: String,
+    access: &'static str,
+    rule: Option<&'static str>,
+    at: Duration,
+    duration: Duration,
+}
+
+/// Allowed accesses of one run.
+type Observed = BTreeSet<(String, &'static str)>;
+
+/// Engine side: armed process names, the last report per process and the drift windows.
+#[derive(Default)]
+pub struct Traces {
+    armed: HashMap<String, usize>,
+    reports: HashMap<String, PyObject>,
+    /// Runs kept per process for drift tracking (0 = off).
+    window: usize,
+    observed: HashMap<String, VecDeque<Observed>>,
+}
+
+impl Traces {
+    pub fn arm(&mut self, process: String, max_events: usize) {
+        self.armed.insert(process, max_events);
+    }
+
+    /// Consumes the armed trace for `process`, if any.
+    pub fn take(&mut self, process: &str) -> Option<usize> {
+        self.armed.remove(process)
+    }
+
+    pub fn report(&self, py: Python, process: &str) -> Option<PyObject> {
+        self.reports.get(process).map(|r| r.clone_ref(py))
+    }
+
+    pub fn pending(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.armed.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Keep the access sets of the last `window` runs per process (0 stops and forgets).
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window;
+        self.observed.retain(|_, runs| {
+            while runs.len() > window {
+                runs.pop_front();
+            }
+            !runs.is_empty()
+        });
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    fn observe(&mut self, process: &str, seen: Observed) {
+        if self.window == 0 {
+            return;
+        }
+        let runs = self.observed.entry(process.to_string()).or_default();
+        if runs.len() == self.window {
+            runs.pop_front();
+        }
+        runs.push_back(seen);
+    }
+
+    /// `(runs in the window, read paths, written paths)` observed for `process`.
+    pub fn observed(&self, process: &str) -> (usize, Vec<String>, Vec<String>) {
+        let Some(runs) = self.observed.get(process) else { return (0, Vec::new(), Vec::new()) };
+        let (mut reads, mut writes) = (BTreeSet::new(), BTreeSet::new());
+        for (path, access) in runs.iter().flatten() {
+            match *access {
+                "read" => reads.insert(path.clone()),
+                _ => writes.insert(path.clone()),
+            };
+        }
+        (runs.len(), reads.into_iter().collect(), writes.into_iter().collect())
+    }
+}
+
+/// One traced and/or observed run.
+pub struct Session {
+    process: String,
+    tx_id: u64,
+    started: Instant,
+    started_ms: u64,
+    /// Event cap of a traced run; None when the run is only observed for drift.
+    max_events: Option<usize>,
+    events: Mutex<Vec<Event>>,
+    dropped: AtomicUsize,
+    observed: Option<Mutex<Observed>>,
+    traces: Arc<Mutex<Traces>>,
+}
+
+impl Session {
+    fn push(&self, event: Event) {
+        if let (Some(observed), None) = (&self.observed, event.rule) {
+            observed.relock().insert((event.path.clone(), event.access));
+        }
+        let Some(max_events) = self.max_events else { return };
+        let mut events = self.events.relock();
+        if events.len() < max_events {
+            events.push(event);
+        } else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+pub fn enabled() -> bool {
+    ACTIVE.load(Ordering::Relaxed) > 0
+}
+
+fn session(tx_id: u64) -> Option<Arc<Session>> {
+    SESSIONS.relock().iter().find(|s| s.tx_id == tx_id).cloned()
+}
+
+/// Starts recording the run of `process` under transaction `tx_id`, if it is traced (armed
+/// by `engine.trace`) or drift tracking is on.
+pub fn open(process: &str, tx_id: u64, traces: &Arc<Mutex<Traces>>) -> Option<Arc<Session>> {
+    let (max_events, observe) = {
+        let mut t = traces.relock();
+        (t.take(process), t.window > 0)
+    };
+    if max_events.is_none() && !observe {
+        return None;
+    }
+    let session = Arc::new(Session {
+        process: process.to_string(),
+        tx_id,
+        started: Instant::now(),
+        started_ms: crate::clock::now_ms(),
+        max_events,
+        events: Mutex::new(Vec::new()),
+        dropped: AtomicUsize::new(0),
+        observed: observe.then(Mutex::default),
+        traces: traces.clone(),
+    });
+    SESSIONS.relock().push(session.clone());
+    ACTIVE.fetch_add(1, Ordering::Relaxed);
+    Some(session)
+}
+
+/// Marks the access in progress on this thread as denied by `rule`.
+pub fn denied(rule: &'static str) {
+    if enabled() {
+        DENIED.with(|d| d.set(Some(rule)));
+    }
+}
+
+/// Records one access for the duration of its scope; denied if a denial is raised meanwhile.
+pub struct Span(Option<(Arc<Session>, String, &'static str, Instant)>);
+
+/// `access` ("read" / "write") of `path` by the transaction `tx_id`. Both closures only run
+/// while some run is traced.
+pub fn span(tx_id: impl FnOnce() -> Option<u64>, path: impl FnOnce() -> String, access: &'static str) -> Span {
+    if !enabled() {
+        return Span(None);
+    }
+    let Some(session) = tx_id().and_then(session) else { return Span(None) };
+    DENIED.with(|d| d.set(None));
+    Span(Some((session, path(), access, Instant::now())))
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((session, path, access, start)) = self.0.take() {
+            let rule = DENIED.with(Cell::get);
+            session.push(Event { path, access, rule, at: start - session.started, duration: start.elapsed() });
+        }
+    }
+}
+
+/// A denial decided outside a span (Python-side contract checks).
+pub fn record_denied(tx_id: u64, path: String, access: &'static str, rule: &'static str) {
+    if let Some(session) = enabled().then(|| session(tx_id)).flatten() {
+        session.push(Event { path, access, rule: Some(rule), at: session.started.elapsed(), duration: Duration::ZERO });
+    }
+}
+
+fn build_report(py: Python, session: &Session, status: &str, error: Option<String>) -> PyResult<PyObject> {
+    let events = session.events.relock();
+    let rows = PyList::empty_bound(py);
+    let (mut reads, mut writes, mut denied) = (0usize, 0usize, 0usize);
+    for e in events.iter() {
+        match e.access {
+            "read" => reads += 1,
+            _ => writes += 1,
+        }
+        denied += usize::from(e.rule.is_some());
+        let row = PyDict::new_bound(py);
+        row.set_item("path", &e.path)?;
+        row.set_item("access", e.access)?;
+        row.set_item("allowed", e.rule.is_none())?;
+        row.set_item("rule", e.rule)?;
+        row.set_item("at_us", e.at.as_micros())?;
+        row.set_item("duration_us", e.duration.as_micros())?;
+        rows.append(row)?;
+    }
+    let out = PyDict::new_bound(py);
+    out.set_item("process", &session.process)?;
+    out.set_item("tx_id", session.tx_id)?;
+    out.set_item("started_ms", session.started_ms)?;
+    out.set_item("duration_ms", session.started.elapsed().as_secs_f64() * 1000.0)?;
+    out.set_item("status", status)?;
+    out.set_item("error", error)?;
+    out.set_item("reads", reads)?;
+    out.set_item("writes", writes)?;
+    out.set_item("denied", denied)?;
+    out.set_item("dropped", session.dropped.load(Ordering::Relaxed))?;
+    out.set_item("events", rows)?;
+    Ok(out.into_any().unbind())
+}
+
+/// End of a recorded run (`status` is "ok", "error" or "cancelled"): stops recording, stores
+/// the trace report and feeds the drift window.
+pub fn close(py: Python, session: &Arc<Session>, status: &str, error: Option<String>) -> PyResult<()> {
+    {
+        let mut sessions = SESSIONS.relock();
+        sessions.retain(|s| !Arc::ptr_eq(s, session));
+    }
+    ACTIVE.fetch_sub(1, Ordering::Relaxed);
+    if let Some(observed) = &session.observed {
+        let seen = std::mem::take(&mut *observed.relock());
+        session.traces.relock().observe(&session.process, seen);
+    }
+    if session.max_events.is_none() {
+        return Ok(());
+    }
+    let report = build_report(py, session, status, error)?;
+    session.traces.relock().reports.insert(session.process.clone(), report);
+    Ok(())
+}
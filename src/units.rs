@@ -0,0 +1,134 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyBool, PyFloat, PyLong, PyString, PyTuple};
+use crate::middleware::MiddlewareVetoError;
+
+// [v3.6] Unit-of-measure tagging. `engine.set_units({"domain.job.timeout": "ms"})` (the mapping
+// a config file provides) makes a path canonical in a unit: proxy writes of tagged quantities -
+// `(value, "s")` tuples or pint-like objects (`.magnitude` / `.units`, converted with `.to()`
+// when available) - are converted to the canonical unit, bare numbers are taken as already
+// canonical, and a unit of another dimension (or an unknown one) vetoes the write. With
+// `annotate_reads=True` leaf reads return `(value, unit)`.
+// Conversions are linear (no offsets such as °C/°F); integers stay integers when the factor
+// between the units is a whole number (2 s -> 2000 ms), otherwise the result is a float.
+// Units run at the lowest middleware priority (i32::MIN), so coercions see the converted number.
+
+/// (unit, dimension, size in the dimension's base unit)
+const UNITS: &[(&str, &str, f64)] = &[
+    ("ns", "time", 1e-9), ("us", "time", 1e-6), ("ms", "time", 1e-3), ("s", "time", 1.0),
+    ("min", "time", 60.0), ("h", "time", 3600.0), ("d", "time", 86400.0),
+    ("B", "data", 1.0), ("KB", "data", 1e3), ("MB", "data", 1e6), ("GB", "data", 1e9), ("TB", "data", 1e12),
+    ("KiB", "data", 1024.0), ("MiB", "data", 1_048_576.0), ("GiB", "data", 1_073_741_824.0),
+    ("mm", "length", 1e-3), ("cm", "length", 1e-2), ("m", "length", 1.0), ("km", "length", 1e3),
+    ("mg", "mass", 1e-6), ("g", "mass", 1e-3), ("kg", "mass", 1.0), ("t", "mass", 1e3),
+];
+
+/// Aliases as spelled by pint and in configs.
+fn canonical_name(unit: &str) -> &str {
+    match unit {
+        "nanosecond" | "nanoseconds" => "ns",
+        "microsecond" | "microseconds" | "µs" => "us",
+        "millisecond" | "milliseconds" => "ms",
+        "second" | "seconds" | "sec" => "s",
+        "minute" | "minutes" => "min",
+        "hour" | "hours" => "h",
+        "day" | "days" => "d",
+        "byte" | "bytes" => "B",
+        "kilobyte" | "kB" => "KB",
+        "megabyte" => "MB",
+        "gigabyte" => "GB",
+        "terabyte" => "TB",
+        "kibibyte" => "KiB",
+        "mebibyte" => "MiB",
+        "gibibyte" => "GiB",
+        "millimeter" | "millimetre" => "mm",
+        "centimeter" | "centimetre" => "cm",
+        "meter" | "metre" => "m",
+        "kilometer" | "kilometre" => "km",
+        "milligram" => "mg",
+        "gram" => "g",
+        "kilogram" => "kg",
+        "tonne" => "t",
+        other => other,
+    }
+}
+
+fn lookup(unit: &str) -> Option<(&'static str, &'static str, f64)> {
+    let unit = canonical_name(unit.trim());
+    UNITS.iter().find(|(name, _, _)| *name == unit).copied()
+}
+
+pub struct UnitTag {
+    unit: &'static str,
+    dimension: &'static str,
+    size: f64,
+    annotate: bool,
+}
+
+impl UnitTag {
+    pub fn new(unit: &str, annotate: bool) -> PyResult<Self> {
+        let (unit, dimension, size) = lookup(unit).ok_or_else(|| PyValueError::new_err(format!(
+            "Unknown unit '{unit}' (known: {:?})", UNITS.iter().map(|u| u.0).collect::<Vec<_>>()
+        )))?;
+        Ok(UnitTag { unit, dimension, size, annotate })
+    }
+
+    /// `(magnitude, unit)` of a tagged quantity, or None for an untagged value.
+    fn split<'py>(value: &Bound<'py, PyAny>) -> PyResult<Option<(Bound<'py, PyAny>, String)>> {
+        if let Ok(t) = value.downcast::<PyTuple>() {
+            if t.len() == 2 && t.get_item(1)?.is_instance_of::<PyString>() {
+                return Ok(Some((t.get_item(0)?, t.get_item(1)?.extract()?)));
+            }
+            return Ok(None);
+        }
+        if value.hasattr("magnitude")? && value.hasattr("units")? {
+            return Ok(Some((value.getattr("magnitude")?, value.getattr("units")?.str()?.to_string())));
+        }
+        Ok(None)
+    }
+
+    fn convert(&self, py: Python, path: &str, value: PyObject) -> PyResult<PyObject> {
+        let bound = value.bind(py);
+        // pint knows more units than the table; let it convert when it can.
+        if bound.hasattr("to")? && bound.hasattr("magnitude")? {
+            if let Ok(q) = bound.call_method1("to", (self.unit,)) {
+                return Ok(q.getattr("magnitude")?.unbind());
+            }
+        }
+        let Some((magnitude, unit)) = Self::split(bound)? else {
+            return Ok(value);
+        };
+        let Some((_, dimension, size)) = lookup(&unit) else {
+            return Err(MiddlewareVetoError::new_err(format!("Unknown unit '{unit}' written to '{path}' ({})", self.unit)));
+        };
+        if dimension != self.dimension {
+            return Err(MiddlewareVetoError::new_err(format!(
+                "'{path}' is measured in {} ({}); got {unit} ({dimension})", self.unit, self.dimension
+            )));
+        }
+        let is_int = magnitude.is_instance_of::<PyLong>() && !magnitude.is_instance_of::<PyBool>();
+        if !is_int && !magnitude.is_instance_of::<PyFloat>() {
+            return Err(MiddlewareVetoError::new_err(format!("Quantity written to '{path}' is not a number")));
+        }
+        let ratio = size / self.size;
+        let whole = (ratio.round() - ratio).abs() < 1e-9 * ratio.max(1.0);
+        #[allow(clippy::cast_possible_truncation)]
+        if is_int && whole {
+            return Ok(magnitude.mul(ratio.round() as i64)?.unbind());
+        }
+        let magnitude: f64 = magnitude.extract()?;
+        Ok((magnitude * ratio).into_py(py))
+    }
+}
+
+impl crate::middleware::Middleware for UnitTag {
+    fn intercept(&self, py: Python, path: &str, value: PyObject, op: &str) -> PyResult<PyObject> {
+        if op != "get" {
+            return self.convert(py, path, value);
+        }
+        if self.annotate && !value.bind(py).is_none() {
+            return Ok((value, self.unit).into_py(py));
+        }
+        Ok(value)
+    }
+}
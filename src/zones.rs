@@ -1,14 +1,29 @@
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
 use pyo3::prelude::*;
+use crate::intern::Sym;
+use crate::locks::RelockRw;
 
 static PHYSICS_OVERRIDES: std::sync::LazyLock<Mutex<HashMap<String, u8>>> = std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 
+// [v3.6] Resolved (zone, physics) per interned path, so hot proxy reads skip the segment
+// scans and the override lock. Dropped whenever the overrides change.
+static PATH_PHYSICS: std::sync::LazyLock<RwLock<HashMap<Sym, (ContextZone, u8)>>> = std::sync::LazyLock::new(|| RwLock::new(HashMap::new()));
+static OVERRIDES_GENERATION: AtomicU64 = AtomicU64::new(0);
+const PATH_PHYSICS_LIMIT: usize = 1 << 16;
+
+fn overrides_changed() {
+    OVERRIDES_GENERATION.fetch_add(1, Ordering::SeqCst);
+    PATH_PHYSICS.relock_write().clear();
+}
+
 #[pyfunction]
 pub fn register_physics_override(path: String, caps: u8) {
     if let Ok(mut map) = PHYSICS_OVERRIDES.lock() {
         map.insert(path, caps);
     }
+    overrides_changed();
 }
 
 #[pyfunction]
@@ -16,6 +31,44 @@ pub fn clear_physics_overrides() {
     if let Ok(mut map) = PHYSICS_OVERRIDES.lock() {
         map.clear();
     }
+    overrides_changed();
+}
+
+/// [v3.6] Registered overrides as `{path: caps}` (for reviews / `engine.security_report()`).
+#[pyfunction]
+pub fn physics_overrides() -> std::collections::BTreeMap<String, u8> {
+    PHYSICS_OVERRIDES.lock().map(|map| map.iter().map(|(k, v)| (k.clone(), *v)).collect()).unwrap_or_default()
+}
+
+/// [v3.6] `(zone name, capabilities)` the proxies apply to `path` (overrides included).
+#[pyfunction]
+pub fn effective_physics(path: &str) -> (String, Capability) {
+    let (zone, caps) = path_physics(path);
+    (format!("{zone:?}").to_lowercase(), Capability::from_bits(caps))
+}
+
+/// [v3.6] Bumped whenever physics overrides change (cache invalidation).
+pub fn overrides_generation() -> u64 {
+    OVERRIDES_GENERATION.load(Ordering::SeqCst)
+}
+
+/// [v3.6] `(zone, override or zone physics)` of `path`, cached.
+pub fn path_physics(path: &str) -> (ContextZone, u8) {
+    if let Some(hit) = PATH_PHYSICS.relock_read().get(path) {
+        return hit.clone();
+    }
+    let generation = OVERRIDES_GENERATION.load(Ordering::SeqCst);
+    let zone = resolve_zone(path);
+    let caps = get_physics_override(path).unwrap_or_else(|| get_zone_physics(&zone));
+    let mut cache = PATH_PHYSICS.relock_write();
+    // An override registered meanwhile may not be reflected in `caps`: do not cache it.
+    if OVERRIDES_GENERATION.load(Ordering::SeqCst) == generation {
+        if cache.len() >= PATH_PHYSICS_LIMIT {
+            cache.clear();
+        }
+        cache.insert(crate::intern::intern(path), (zone.clone(), caps));
+    }
+    (zone, caps)
 }
 
 pub fn get_physics_override(path: &str) -> Option<u8> {
@@ -57,12 +110,177 @@ pub enum ContextZone {
     Private,
 }
 
+/// Zone by name ("data", "meta", ...; case-insensitive).
+pub fn parse_zone(name: &str) -> PyResult<ContextZone> {
+    Ok(match name.to_ascii_lowercase().as_str() {
+        "data" => ContextZone::Data,
+        "signal" => ContextZone::Signal,
+        "meta" => ContextZone::Meta,
+        "heavy" => ContextZone::Heavy,
+        "log" => ContextZone::Log,
+        "constant" => ContextZone::Constant,
+        "private" => ContextZone::Private,
+        other => return Err(pyo3::exceptions::PyValueError::new_err(format!("Unknown zone '{other}'"))),
+    })
+}
+
+/// [v3.6] Zone argument from Python: a `theus_core.ContextZone`, a `theus.zones.ContextZone`
+/// enum member (its string value) or a plain name.
+pub fn zone_arg(value: &Bound<'_, PyAny>) -> PyResult<ContextZone> {
+    if let Ok(zone) = value.downcast::<ContextZone>() {
+        return Ok(zone.borrow().clone());
+    }
+    if let Ok(name) = value.extract::<String>() {
+        return parse_zone(&name);
+    }
+    parse_zone(&value.getattr("value")?.extract::<String>()?)
+}
+
 pub const CAP_READ: u8   = 1 << 0; // 1
 pub const CAP_APPEND: u8 = 1 << 1; // 2
 pub const CAP_UPDATE: u8 = 1 << 2; // 4
 pub const CAP_DELETE: u8 = 1 << 3; // 8
+pub const CAP_EXECUTE: u8 = 1 << 4; // 16 - [v3.6] Invoke stored callables via ctx.run()
 pub const CAP_NONE: u8   = 0;      // 0 - Completely private
 
+const CAP_NAMES: [(u8, &str); 5] = [
+    (CAP_READ, "READ"), (CAP_APPEND, "APPEND"), (CAP_UPDATE, "UPDATE"), (CAP_DELETE, "DELETE"), (CAP_EXECUTE, "EXECUTE"),
+];
+pub const CAP_ALL: u8 = CAP_READ | CAP_APPEND | CAP_UPDATE | CAP_DELETE | CAP_EXECUTE;
+
+/// Admin bypass marker in a proxy's capability mask. Not a zone capability (outside
+/// `CAP_ALL`, so `Capability` never grants it): a proxy carrying it gets every capability
+/// on all but the absolute-ceiling zones, and passes it on to its children.
+pub const CAP_ADMIN: u8 = 1 << 7; // 128
+/// Mask handed to proxies elevated by an admin guard.
+pub const ADMIN_CAPS: u8 = CAP_ALL | CAP_ADMIN;
+
+/// [v3.6] Capability bits as flags (`Capability.READ | Capability.UPDATE`). Behaves as an int
+/// (`__index__`), so it is accepted wherever a capability mask is.
+#[pyclass(module = "theus_core", frozen)]
+#[derive(Clone, Copy)]
+pub struct Capability {
+    bits: u8,
+}
+
+fn cap_bits(value: &Bound<'_, PyAny>) -> PyResult<u8> {
+    if let Ok(c) = value.downcast::<Capability>() {
+        return Ok(c.get().bits);
+    }
+    let bits: u8 = value.extract()?;
+    if bits & !CAP_ALL != 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!("Invalid capability mask {bits}")));
+    }
+    Ok(bits)
+}
+
+impl Capability {
+    pub fn from_bits(bits: u8) -> Self {
+        Capability { bits }
+    }
+}
+
+#[pymethods]
+#[allow(clippy::trivially_copy_pass_by_ref)]
+impl Capability {
+    #[new]
+    fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Capability { bits: cap_bits(value)? })
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn NONE() -> Self { Capability { bits: CAP_NONE } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn READ() -> Self { Capability { bits: CAP_READ } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn APPEND() -> Self { Capability { bits: CAP_APPEND } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn UPDATE() -> Self { Capability { bits: CAP_UPDATE } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn DELETE() -> Self { Capability { bits: CAP_DELETE } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn EXECUTE() -> Self { Capability { bits: CAP_EXECUTE } }
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn ALL() -> Self { Capability { bits: CAP_ALL } }
+
+    /// Names of the set bits (`["READ", "UPDATE"]`).
+    #[getter]
+    fn names(&self) -> Vec<&'static str> {
+        CAP_NAMES.iter().filter(|(bit, _)| self.bits & bit != 0).map(|(_, name)| *name).collect()
+    }
+
+    fn __index__(&self) -> u8 {
+        self.bits
+    }
+
+    fn __int__(&self) -> u8 {
+        self.bits
+    }
+
+    fn __bool__(&self) -> bool {
+        self.bits != 0
+    }
+
+    fn __hash__(&self) -> u64 {
+        u64::from(self.bits)
+    }
+
+    fn __or__(&self, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Capability { bits: self.bits | cap_bits(other)? })
+    }
+
+    fn __ror__(&self, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        self.__or__(other)
+    }
+
+    fn __and__(&self, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Capability { bits: self.bits & cap_bits(other)? })
+    }
+
+    fn __rand__(&self, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        self.__and__(other)
+    }
+
+    fn __xor__(&self, other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Capability { bits: self.bits ^ cap_bits(other)? })
+    }
+
+    fn __invert__(&self) -> Self {
+        Capability { bits: !self.bits & CAP_ALL }
+    }
+
+    /// `Capability.READ in caps`: every bit of `other` is set.
+    fn __contains__(&self, other: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let bits = cap_bits(other)?;
+        Ok(self.bits & bits == bits)
+    }
+
+    fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: pyo3::basic::CompareOp) -> PyObject {
+        let py = other.py();
+        let Ok(bits) = cap_bits(other) else { return py.NotImplemented() };
+        match op {
+            pyo3::basic::CompareOp::Eq => (self.bits == bits).into_py(py),
+            pyo3::basic::CompareOp::Ne => (self.bits != bits).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        match self.bits {
+            CAP_NONE => "Capability.NONE".to_string(),
+            CAP_ALL => "Capability.ALL".to_string(),
+            _ => format!("Capability.{}", self.names().join("|")),
+        }
+    }
+}
+
 pub fn resolve_zone(key: &str) -> ContextZone {
     // Structural Support: Check all segments (handle both dot and bracket notation)
     let normalized = key.replace('[', ".").replace(']', "");
@@ -98,7 +316,7 @@ pub fn resolve_zone(key: &str) -> ContextZone {
 
 pub fn get_zone_physics(zone: &ContextZone) -> u8 {
     match zone {
-        ContextZone::Data     => CAP_READ | CAP_APPEND | CAP_UPDATE | CAP_DELETE,
+        ContextZone::Data     => CAP_READ | CAP_APPEND | CAP_UPDATE | CAP_DELETE | CAP_EXECUTE,
         ContextZone::Signal   => CAP_READ | CAP_APPEND, // River: Flow only
         ContextZone::Meta     => CAP_READ | CAP_UPDATE, // Config: Tune, don't delete
         ContextZone::Heavy    => CAP_READ | CAP_UPDATE, // Ref Swap only
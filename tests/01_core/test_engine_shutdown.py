@@ -0,0 +1,52 @@
+import threading
+import time
+
+import pytest
+from theus_core import EngineShutdownError
+
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def test_shutdown_flushes_outbox_and_rejects_new_work():
+    engine = TheusEngine()
+    sent = []
+    engine.attach_worker(lambda m: sent.append(m.topic))
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("bye", None))
+
+    report = engine.shutdown(timeout_ms=100)
+    assert report["clean"] is True
+    assert sent == ["bye"]
+    assert engine.is_shutdown
+
+    with pytest.raises(EngineShutdownError):
+        with engine.transaction():
+            pass
+
+
+def test_shutdown_waits_for_in_flight_commit():
+    engine = TheusEngine()
+    entered = threading.Event()
+
+    def slow_writer():
+        with engine.transaction() as tx:
+            entered.set()
+            time.sleep(0.05)
+            tx.update(data={"domain": {"done": True}})
+
+    t = threading.Thread(target=slow_writer)
+    t.start()
+    entered.wait()
+    report = engine.shutdown(timeout_ms=2000)
+    t.join()
+    assert report["abandoned_transactions"] == []
+    assert engine.state.data["domain"]["done"] is True
+
+
+def test_shutdown_reports_abandoned_transactions():
+    engine = TheusEngine()
+    stuck = engine._core.transaction()
+    report = engine.shutdown(timeout_ms=20)
+    assert report["clean"] is False
+    assert [t["id"] for t in report["abandoned_transactions"]] == [stuck.id]
@@ -0,0 +1,39 @@
+import pytest
+from theus_core import ContextError, MaintenanceModeError, SupervisorProxy
+
+from theus.engine import TheusEngine
+
+
+def test_maintenance_blocks_commit_cas_and_proxy_writes():
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 1}})
+    version = engine.state.version
+
+    engine.enter_maintenance("schema migration v7")
+    assert engine.maintenance_reason == "schema migration v7"
+
+    with pytest.raises(MaintenanceModeError, match="schema migration v7"):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": 2}})
+
+    with pytest.raises(MaintenanceModeError):
+        engine.compare_and_swap(version, data={"domain": {"n": 3}})
+
+    with pytest.raises(MaintenanceModeError):
+        with engine.transaction() as tx:
+            SupervisorProxy({"n": 1}, "domain", False, tx)["n"] = 4
+
+    # Reads keep working, nothing changed
+    assert engine.state.data["domain"]["n"] == 1
+    assert issubclass(MaintenanceModeError, ContextError)
+
+
+def test_exit_maintenance_restores_writes():
+    engine = TheusEngine()
+    engine.enter_maintenance("snapshot")
+    engine.exit_maintenance()
+    assert engine.maintenance_reason is None
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 5}})
+    assert engine.state.data["domain"]["n"] == 5
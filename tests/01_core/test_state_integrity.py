@@ -0,0 +1,113 @@
+import pytest
+from theus_core import ContextError, IntegrityError, State
+
+from theus.engine import TheusEngine
+
+
+def _engine(tracking=True):
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"items": [1, 2], "meta": {"owner": "a"}}, "global": {"mode": "x"}})
+    engine.set_escape_tracking(tracking)
+    return engine
+
+
+def test_committed_state_verifies_clean():
+    engine = _engine()
+    report = engine.verify_integrity()
+    assert report["ok"] is True
+    assert report["changed_keys"] == []
+    assert report["expected"] == report["actual"] == engine.state.integrity_hash
+    assert report["version"] == engine.state.version
+
+
+def test_out_of_band_mutation_is_flagged():
+    engine = _engine()
+    # Escaped raw reference to committed state
+    engine.state.data["domain"]["items"].append(3)
+
+    report = engine.verify_integrity()
+    assert report["ok"] is False
+    assert report["changed_keys"] == ["domain"]
+    assert report["expected"] != report["actual"]
+
+    with pytest.raises(IntegrityError, match="domain"):
+        engine.verify_integrity(raise_on_mismatch=True)
+    assert issubclass(IntegrityError, ContextError)
+
+
+def test_transactional_updates_keep_checksum_in_sync():
+    engine = _engine()
+    before = engine.state.integrity_hash
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"meta": {"owner": "b"}}})
+    assert engine.state.integrity_hash != before
+    assert engine.verify_integrity()["ok"] is True
+
+    engine.compare_and_swap(engine.state.version, data={"global": {"mode": "y"}})
+    assert engine.verify_integrity()["ok"] is True
+
+
+def test_hash_is_content_based():
+    engine_a, engine_b = _engine(), _engine()
+    assert engine_a.state.integrity_hash == engine_b.state.integrity_hash
+
+
+def test_escape_tracking_reports_changed_path():
+    engine = _engine()
+    engine.state.data["domain"]["items"].append(3)
+
+    report = engine.verify_integrity()
+    assert report["changed_paths"] == [{"path": "domain.items", "kind": "mutated"}]
+
+
+def test_escape_tracking_reports_replaced_and_added():
+    engine = _engine()
+    domain = engine.state.data["domain"]
+    domain["meta"] = {"owner": "a"}  # same content, different object
+    domain["extra"] = []
+
+    paths = {p["path"]: p["kind"] for p in engine.verify_integrity()["changed_paths"]}
+    assert paths == {"domain.meta": "replaced", "domain.extra": "added"}
+
+
+def test_escape_tracking_survives_commits_to_other_zones():
+    engine = _engine()
+    engine.state.data["domain"]["meta"]["owner"] = "mallory"
+    with engine.transaction() as tx:
+        tx.update(data={"global": {"mode": "z"}})
+
+    report = engine.verify_integrity()
+    assert report["changed_keys"] == ["domain"]
+    assert report["changed_paths"] == [{"path": "domain.meta", "kind": "mutated"}]
+
+
+def test_no_checksums_without_tracking():
+    engine = _engine(tracking=False)
+    assert engine.state.integrity_hash is None
+    with pytest.raises(ContextError, match="set_escape_tracking"):
+        engine.verify_integrity()
+
+
+class Node:
+    pass
+
+
+def test_cycles_and_opaque_objects_are_hashed():
+    node = Node()
+    node.a = node.b = node
+    # No tracking: committing the cycle never walks it.
+    assert State(data={"domain": {"node": node}}).integrity_hash is None
+
+    class Opaque:
+        __slots__ = ()
+
+        def __repr__(self):
+            raise RuntimeError("repr must not be called")
+
+    engine = _engine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"node": node, "opaque": Opaque()}})
+    assert engine.verify_integrity()["ok"] is True
+    node.c = 1
+    assert engine.verify_integrity()["changed_keys"] == ["domain"]
@@ -0,0 +1,39 @@
+import gc
+import time
+
+from theus_core import AuditSystem
+
+from theus.engine import TheusEngine
+
+
+def test_open_transactions_tracks_until_exit():
+    engine = TheusEngine()
+    assert engine.open_transactions() == []
+
+    with engine.transaction() as tx:
+        open_txs = engine.open_transactions()
+        assert [t["id"] for t in open_txs] == [tx.id]
+        assert open_txs[0]["age_ms"] >= 0
+        assert open_txs[0]["stack"] is None
+
+    assert engine.open_transactions() == []
+
+
+def test_leaked_transaction_is_reported_once_with_stack():
+    engine = TheusEngine()
+    leaks = []
+    engine.set_leak_detection(5, capture_stack=True, on_leak=leaks.append)
+
+    leaked = engine._core.transaction()  # created, never entered/exited
+    time.sleep(0.02)
+    engine.open_transactions()
+    engine.open_transactions()
+
+    assert [l["id"] for l in leaks] == [leaked.id]
+    assert "test_leaked_transaction_is_reported_once_with_stack" in leaks[0]["stack"]
+    assert any(e.key == "TX_LEAK" and f"#{leaked.id}" in e.message for e in AuditSystem().get_logs())
+
+    # Garbage collection releases the entry
+    del leaked
+    gc.collect()
+    assert engine.open_transactions() == []
@@ -0,0 +1,60 @@
+import time
+import warnings
+
+import pytest
+from theus_core import SupervisorProxy, TransactionCancelledError
+
+from theus.engine import TheusEngine
+
+
+def test_abort_cancels_writes_and_commit():
+    engine = TheusEngine()
+    fired = []
+    engine.set_transaction_watchdog(5, callback=fired.append, action="abort")
+
+    with pytest.raises(TransactionCancelledError):
+        with engine.transaction() as tx:
+            proxy = SupervisorProxy({"n": 0}, "domain", False, tx)
+            proxy["n"] = 1  # within deadline
+            time.sleep(0.02)
+            assert tx.cancelled
+            with pytest.raises(TransactionCancelledError):
+                proxy["n"] = 2
+            tx.update(data={"domain": {"n": 3}})
+
+    assert fired[0]["action"] == "abort"
+    assert fired[0]["elapsed_ms"] > 5
+    assert engine.state.data.get("domain") is None
+
+
+def test_warn_fires_once_and_still_commits():
+    engine = TheusEngine()
+    engine.set_transaction_watchdog(1)
+
+    with warnings.catch_warnings(record=True) as caught:
+        warnings.simplefilter("always")
+        with engine.transaction() as tx:
+            time.sleep(0.01)
+            tx.update(data={"domain": {"n": 1}})
+            tx.update(data={"domain": {"n": 2}})
+            assert not tx.cancelled
+
+    assert len([w for w in caught if "soft deadline" in str(w.message)]) == 1
+    assert engine.state.data["domain"]["n"] == 2
+
+
+def test_manual_cancel_and_disable():
+    engine = TheusEngine()
+    with pytest.raises(TransactionCancelledError):
+        with engine.transaction() as tx:
+            tx.cancel()
+
+    engine.set_transaction_watchdog(1, action="abort")
+    engine.set_transaction_watchdog(None)
+    with engine.transaction() as tx:
+        time.sleep(0.01)
+        tx.update(data={"domain": {"ok": True}})
+    assert engine.state.data["domain"]["ok"] is True
+
+    with pytest.raises(ValueError):
+        engine.set_transaction_watchdog(1, action="explode")
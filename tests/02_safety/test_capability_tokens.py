@@ -0,0 +1,79 @@
+import time
+
+import pytest
+import theus_core
+from theus_core import Capability, ContextGuard
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "b": 2}, "global": {"limit": 10}})
+
+
+def _target(engine):
+    data = engine._core.state.data
+    return {"domain": data["domain"], "global": data["global"]}
+
+
+def test_token_round_trip_and_worker_guard():
+    engine = _engine()
+    token = engine.issue_capability_token(["global"], ["domain"], caps=Capability.READ | Capability.UPDATE)
+    grant = theus_core.verify_capability_token(token, engine.capability_key())
+    assert (grant["inputs"], grant["outputs"], grant["caps"]) == (["global"], ["domain"], 5)
+    assert engine.verify_capability_token(token) == grant
+
+    with engine._core.transaction() as tx:
+        ctx = theus_core.guard_from_token(_target(engine), token, engine.capability_key(), tx)
+        assert ctx["global"]["limit"] == 10
+        ctx["domain"]["b"] = 5
+        with pytest.raises(PermissionError):
+            ctx["global"] = {}
+    assert engine._core.state.data["domain"]["b"] == 5
+
+
+def test_capability_ceiling_is_enforced():
+    engine = _engine()
+    token = engine.issue_capability_token(["global"], ["domain"], caps=Capability.READ)
+    with engine._core.transaction() as tx:
+        ctx = theus_core.guard_from_token(_target(engine), token, engine.capability_key(), tx)
+        assert ctx["domain"]["a"] == 1
+        with pytest.raises(PermissionError):
+            ctx["domain"]["b"] = 5
+    assert engine._core.state.data["domain"]["b"] == 2
+
+
+def test_forged_expired_and_foreign_tokens_are_rejected():
+    engine = _engine()
+    token = engine.issue_capability_token(["domain"], [])
+    prefix, payload, mac = token.split(".")
+    forged = ".".join([prefix, payload.replace("646f6d61696e", "676c6f62616c"), mac])
+    for bad in (forged, token + "00", "garbage"):
+        with pytest.raises(PermissionError, match="Invalid capability token"):
+            engine.verify_capability_token(bad)
+    with pytest.raises(PermissionError, match="Invalid"):
+        theus_core.verify_capability_token(token, TheusEngine(context={}).capability_key())
+
+    short = engine.issue_capability_token(["domain"], [], ttl_s=0.001)
+    time.sleep(0.01)
+    with pytest.raises(PermissionError, match="expired"):
+        engine.verify_capability_token(short)
+
+
+def test_shared_key_and_guard_delegation():
+    parent, worker = _engine(), _engine()
+    with pytest.raises(ValueError):
+        worker.set_capability_key(b"short")
+    worker.set_capability_key(parent.capability_key())
+
+    with parent._core.transaction() as tx:
+        guard = ContextGuard(_target(parent), ["global"], ["domain"], tx=tx, strict_guards=True)
+        token = guard.delegation_token(caps=Capability.READ | Capability.APPEND)
+        with pytest.raises(PermissionError, match="Admin"):
+            ContextGuard(_target(parent), [], [], tx=tx, is_admin=True).delegation_token()
+    with pytest.raises(PermissionError, match="transaction"):
+        ContextGuard(_target(parent), ["global"], []).delegation_token()
+
+    grant = worker.verify_capability_token(token)
+    assert grant["inputs"] == ["global"] and grant["outputs"] == ["domain"]
+    assert grant["caps"] == 3 and grant["strict_guards"] is True
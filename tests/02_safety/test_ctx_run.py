@@ -0,0 +1,95 @@
+import pytest
+from theus.engine import TheusEngine
+from theus.contracts import process
+from theus_core import AuditSystem
+
+
+def pricing(ctx, qty, discount=0):
+    return ctx.domain.base * qty - discount
+
+
+def tamper(ctx):
+    ctx.domain["base"] = 0
+
+
+def _engine():
+    return TheusEngine(
+        context={"domain": {"base": 10, "strategies": {"pricing": pricing, "tamper": tamper}, "quotes": []}},
+        strict_guards=True,
+    )
+
+
+@process(inputs=["domain.strategies", "domain.base"], outputs=["domain.quotes"])
+def quote(ctx):
+    ctx.domain.quotes.append(ctx.run("domain.strategies.pricing", 3, discount=5))
+
+
+@process(inputs=["domain.strategies", "domain.base"], outputs=["domain"])
+def run_tamper(ctx):
+    ctx.run("domain.strategies.tamper")
+
+
+@process(inputs=["domain.base"], outputs=[])
+def run_undeclared(ctx):
+    ctx.run("domain.strategies.pricing", 1)
+
+
+@process(inputs=["domain.base"], outputs=[])
+def run_scalar(ctx):
+    ctx.run("domain.base")
+
+
+@pytest.mark.asyncio
+async def test_run_invokes_callable_and_audits():
+    engine = _engine()
+    engine.register(quote)
+    await engine.execute("quote")
+    assert engine.state.data["domain"]["quotes"] == [25]
+
+    logs = [e for e in AuditSystem().get_logs() if e.key == "EXECUTE"]
+    assert logs and logs[-1].message == "domain.strategies.pricing ok"
+
+
+@pytest.mark.asyncio
+async def test_callable_gets_read_only_guard():
+    engine = _engine()
+    engine.register(run_tamper)
+    with pytest.raises(PermissionError):
+        await engine.execute("run_tamper")
+    assert engine.state.data["domain"]["base"] == 10
+
+
+@pytest.mark.asyncio
+async def test_run_requires_declared_path():
+    engine = _engine()
+    engine.register(run_undeclared)
+    with pytest.raises(PermissionError):
+        await engine.execute("run_undeclared")
+
+
+@pytest.mark.asyncio
+async def test_run_rejects_non_callable():
+    engine = _engine()
+    engine.register(run_scalar)
+    with pytest.raises(TypeError, match="not callable"):
+        await engine.execute("run_scalar")
+
+
+def test_execute_capability_follows_zone_physics():
+    from theus_core import ContextGuard
+    guard = ContextGuard({"meta_hooks": {"f": lambda ctx: 1}}, ["meta_hooks"], [])
+    with pytest.raises(PermissionError, match="EXECUTE"):
+        guard.run("meta_hooks.f")
+
+
+def test_execute_bit_is_not_admin_and_constants_stay_read_only():
+    from theus_core import ADMIN_CAPS, Capability, ContextGuard, SupervisorProxy
+    # A full, non-admin mask (EXECUTE included) is still clamped by Signal zone physics.
+    proxy = SupervisorProxy({"sig_queue": {"a": 1}}, "domain", capabilities=Capability.ALL)
+    assert proxy.sig_queue.capabilities == Capability.READ | Capability.APPEND
+    elevated = SupervisorProxy({"sig_queue": {"a": 1}}, "domain", capabilities=ADMIN_CAPS)
+    assert elevated.sig_queue.capabilities == ADMIN_CAPS
+
+    guard = ContextGuard({"const_hooks": {"f": lambda ctx: 1}}, ["const_hooks"], [])
+    with pytest.raises(PermissionError, match="EXECUTE"):
+        guard.run("const_hooks.f")
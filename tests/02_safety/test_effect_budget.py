@@ -0,0 +1,83 @@
+import asyncio
+import logging
+import socket
+
+import pytest
+
+from theus.contracts import ContractViolationError, OutboxMsg, SemanticType, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.a"], outputs=[], effects=["outbox"])
+def notify(ctx):
+    ctx.outbox.add(OutboxMsg("notified", {"a": ctx.domain.a}))
+
+
+@process(inputs=["domain.a"], outputs=[], effects=["log"])
+def log_only(ctx):
+    logging.getLogger("theus.tests.budget").warning("a=%s", ctx.domain.a)
+    ctx.outbox.add(OutboxMsg("sneaky", {}))
+
+
+@process(inputs=["domain.a"], outputs=[], effects=["outbox"])
+def chatty(ctx):
+    logging.getLogger("theus.tests.budget").warning("not budgeted")
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"], effects=["state"])
+def writes_then_calls_out(ctx):
+    ctx.domain.a = 5
+    socket.socket().close()
+
+
+@process(inputs=["domain.a"], outputs=[], semantic=SemanticType.PURE, effects=["network"])
+def pure_with_network(ctx):
+    socket.socket().close()
+    return ctx.domain.a
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    for p in (notify, log_only, chatty, writes_then_calls_out, pure_with_network):
+        engine.register(p)
+    return engine
+
+
+def test_declared_effects_are_allowed():
+    engine = _engine()
+    asyncio.run(engine.execute("notify"))
+    assert engine._core.outbox.len() == 1
+    assert asyncio.run(engine.execute("pure_with_network")) == 1
+
+
+def test_undeclared_effects_raise_with_contract_reference():
+    engine = _engine()
+    with pytest.raises(PermissionError, match=r"'log_only' attempted outbox effect .*contract: .*log_only"):
+        asyncio.run(engine.execute("log_only"))
+    with pytest.raises(PermissionError, match=r"attempted log effect .*\[\"outbox\"\]"):
+        asyncio.run(engine.execute("chatty"))
+    with pytest.raises(PermissionError, match="attempted network effect"):
+        asyncio.run(engine.execute("writes_then_calls_out"))
+    assert engine._core.state.data["domain"]["a"] == 1
+    assert engine._core.outbox.len() == 0
+    assert {v["category"] for v in engine.pure_io_violations()} == {"outbox", "log", "network"}
+
+    # Outside budgeted processes logging is untouched.
+    logging.getLogger("theus.tests.budget").warning("outside")
+
+
+def test_budget_is_validated_at_registration():
+    engine = TheusEngine()
+
+    @process(inputs=[], outputs=["domain.a"], effects=["outbox"])
+    def writes_without_state(ctx):
+        pass
+
+    @process(inputs=[], outputs=[], effects=["disk"])
+    def unknown_class(ctx):
+        pass
+
+    with pytest.raises(ContractViolationError, match="excludes 'state'"):
+        engine.register(writes_without_state)
+    with pytest.raises(ContractViolationError, match="Unknown effect class 'disk'"):
+        engine.register(unknown_class)
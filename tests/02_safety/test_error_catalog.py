@@ -0,0 +1,62 @@
+import pytest
+import theus_core
+from theus_core import Capability, PermissionDeniedError, QuotaExceededError, VersionMismatchError
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "b": 2}, "global": {"limit": 10}})
+
+
+def _conflict(engine):
+    start = engine.state.version
+    engine.compare_and_swap(start, data={"domain": {"a": 5}})
+    with pytest.raises(VersionMismatchError) as info:
+        engine.compare_and_swap(start, data={"domain": {"a": 6}})
+    return info.value
+
+
+def test_catalog_lists_stable_codes_and_errors_carry_them():
+    catalog = theus_core.error_catalog()
+    assert catalog["TH101"]["name"] == "cas_mismatch"
+    assert "{expected}" in catalog["TH101"]["template"]
+    assert theus_core.ContextError.code is None
+
+    engine = _engine()
+    err = _conflict(engine)
+    assert err.code == "TH101" and "CAS Version Mismatch" in str(err)
+
+    token = engine.issue_capability_token(["global"], ["domain"], caps=Capability.READ)
+    with engine._core.transaction() as tx:
+        data = engine._core.state.data
+        ctx = theus_core.guard_from_token({"domain": data["domain"], "global": data["global"]}, token, engine.capability_key(), tx)
+        with pytest.raises(PermissionDeniedError) as info:
+            ctx["global"] = {}
+    assert info.value.code == "TH201"
+
+    engine.set_heavy_quota(10)
+    with pytest.raises(QuotaExceededError) as info:
+        engine.alloc_heavy("frame", b"x", nbytes=50)
+    assert info.value.code == "TH301"
+
+
+def test_registered_formatter_controls_the_message():
+    theus_core.register_error_formatter("TH101", lambda code, p: f"[{code}] stale write: {p['expected']} -> {p['actual']}")
+    try:
+        assert theus_core.error_catalog()["TH101"]["custom"] is True
+        err = _conflict(_engine())
+        assert str(err) == f"[TH101] stale write: {err.expected_version} -> {err.actual_version}"
+        assert err.code == "TH101"
+
+        # A broken formatter never masks the real error.
+        theus_core.register_error_formatter("TH101", lambda code, p: 1 / 0)
+        assert "CAS Version Mismatch" in str(_conflict(_engine()))
+    finally:
+        assert theus_core.unregister_error_formatter("TH101")
+    assert not theus_core.unregister_error_formatter("TH101")
+
+    with pytest.raises(ValueError, match="Unknown error code"):
+        theus_core.register_error_formatter("TH999", str)
+    with pytest.raises(TypeError, match="callable"):
+        theus_core.register_error_formatter("TH101", "not callable")
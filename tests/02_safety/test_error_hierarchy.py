@@ -0,0 +1,55 @@
+import pytest
+import theus_core
+from theus_core import (
+    BusyError, ConflictError, ContextError, PermissionDeniedError, QuotaExceededError,
+    SchemaViolationError, VersionMismatchError,
+)
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "b": 2}, "global": {"limit": 10}})
+
+
+def test_hierarchy_keeps_builtin_bases():
+    for exc in (ConflictError, BusyError, SchemaViolationError, PermissionDeniedError, QuotaExceededError):
+        assert issubclass(exc, ContextError)
+    assert issubclass(VersionMismatchError, ConflictError)
+    assert issubclass(PermissionDeniedError, PermissionError)
+    assert issubclass(QuotaExceededError, MemoryError)
+    assert ConflictError.path is None and PermissionDeniedError.caps is None
+
+
+def test_version_mismatch_carries_path_and_versions():
+    engine = _engine()
+    start = engine.state.version
+    engine.compare_and_swap(start, data={"domain": {"a": 5}})
+    with pytest.raises(VersionMismatchError, match="CAS Version Mismatch") as info:
+        engine.compare_and_swap(start, data={"domain": {"a": 6}})
+    err = info.value
+    assert (err.path, err.expected_version, err.actual_version) == ("domain.a", start, start + 1)
+
+    with pytest.raises(ConflictError) as info:
+        with engine._core.transaction() as tx:
+            engine.compare_and_swap(engine.state.version, data={"domain": {"b": 3}})
+            tx.update(data={"domain": {"b": 4}})
+    assert info.value.path == "domain.b"
+
+
+def test_permission_and_quota_errors_are_structured():
+    engine = _engine()
+    token = engine.issue_capability_token(["global"], ["domain"], caps=theus_core.Capability.READ | theus_core.Capability.UPDATE)
+    with engine._core.transaction() as tx:
+        ctx = theus_core.guard_from_token(
+            {"domain": engine._core.state.data["domain"], "global": engine._core.state.data["global"]},
+            token, engine.capability_key(), tx,
+        )
+        with pytest.raises(PermissionDeniedError, match="Illegal Write") as info:
+            ctx["global"] = {}
+    assert info.value.path == "global"
+
+    engine.set_heavy_quota(10)
+    with pytest.raises(QuotaExceededError) as info:
+        engine.alloc_heavy("frame", b"x", nbytes=50)
+    assert (info.value.path, info.value.limit, info.value.requested) == ("frame", 10, 50)
@@ -0,0 +1,42 @@
+import theus_core
+
+from theus.engine import TheusEngine
+
+
+class Obj:
+    pass
+
+
+def _target():
+    o = Obj()
+    o.names = [1, 2]
+    o.internal_secret = {"k": 1}
+    o.log_lines = []
+    return o
+
+
+def test_repeated_reads_keep_their_guard_decisions():
+    engine = TheusEngine(context={"domain": {}})
+    o = _target()
+    with engine._core.transaction() as tx:
+        guard = theus_core.ContextGuard(o, ["names", "internal_secret", "log_lines"], [], tx=tx)
+        for _ in range(3):
+            names = guard.names
+            assert isinstance(names, theus_core.SupervisorProxy)
+            assert names.read_only and names.capabilities == 1
+            assert guard.internal_secret is None
+            assert guard.log_lines is o.log_lines
+
+
+def test_elevation_invalidates_cached_decisions():
+    engine = TheusEngine(context={"domain": {}})
+    with engine._core.transaction() as tx:
+        guard = theus_core.ContextGuard(_target(), ["names", "internal_secret"], [], tx=tx)
+        assert guard.names.read_only and guard.internal_secret is None
+
+        guard._elevate(True)
+        assert not guard.names.read_only and guard.names.capabilities == 15
+        assert guard.internal_secret == {"k": 1}
+
+        guard._elevate(False)
+        assert guard.names.read_only and guard.internal_secret is None
@@ -0,0 +1,52 @@
+import pytest
+from theus_core import SupervisorProxy
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.n", "domain.cfg"], outputs=["domain.n"])
+def explore(ctx):
+    seen["root"] = dir(ctx)
+    seen["domain"] = dir(ctx.domain)
+    seen["describe"] = ctx.describe()
+    seen["domain_describe"] = ctx.domain.describe(max_depth=1)
+
+
+@pytest.mark.asyncio
+async def test_dir_and_describe_follow_the_contract():
+    engine = TheusEngine(context={"domain": {
+        "n": 0, "cfg": {"a": 1, "internal_key": "s3cret"}, "secret": 5,
+    }})
+    engine.register(explore)
+    await engine.execute("explore")
+
+    assert "domain" in seen["root"] and "describe" in seen["root"] and "outbox" in seen["root"]
+    assert {"n", "cfg"} <= set(seen["domain"])
+    assert "secret" not in seen["domain"] and "internal_key" not in seen["domain"]
+
+    rows = seen["describe"]
+    assert rows["domain.n"] == {"zone": "data", "access": "rw", "type": "int"}
+    assert rows["domain.cfg"] == {"zone": "data", "access": "ro", "type": "dict", "len": 2}
+    assert rows["domain.cfg.a"]["access"] == "ro"
+    assert "domain.secret" not in rows and "domain.cfg.internal_key" not in rows
+
+    assert list(seen["domain_describe"]) == ["domain.n", "domain.cfg"]
+
+
+def test_supervisor_proxy_introspection():
+    proxy = SupervisorProxy(
+        {"a": 1, "internal_x": 2, "sig_ready": True, "sub": {"b": [1, 2]}},
+        path="domain", read_only=True,
+    )
+    names = dir(proxy)
+    assert {"a", "sub", "sig_ready", "describe", "keys"} <= set(names)
+    assert "internal_x" not in names and names == sorted(names)
+
+    rows = proxy.describe()
+    assert rows["domain.sig_ready"]["zone"] == "signal"
+    assert rows["domain.sub.b"] == {"zone": "data", "access": "ro", "type": "list", "len": 2}
+    assert "domain.internal_x" not in rows
+    assert list(proxy.describe(max_depth=1, max_keys=1)) == ["domain.a"]
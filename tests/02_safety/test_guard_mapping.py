@@ -0,0 +1,53 @@
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.cfg", "domain.n"], outputs=[])
+def browse(ctx):
+    seen["root_keys"] = ctx.keys()
+    seen["has"] = ("cfg" in ctx.domain, "secret" in ctx.domain, "internal_token" in ctx.domain)
+    seen["domain_get"] = ctx.get("domain").get("n")
+    seen["cfg_type"] = type(ctx.domain.get("cfg")).__name__
+    seen["items"] = [k for k, _ in ctx.domain.items()]
+
+
+@pytest.mark.asyncio
+async def test_mapping_surface_follows_the_contract():
+    engine = TheusEngine(context={"domain": {"cfg": {"a": 1}, "n": 3, "secret": 1, "internal_token": "t"}})
+    engine.register(browse)
+    await engine.execute("browse")
+
+    assert "domain" in seen["root_keys"]
+    assert seen["has"] == (True, False, False)
+    assert seen["domain_get"] == 3
+    assert seen["cfg_type"] == "ContextGuard"
+    assert seen["items"] == ["cfg", "n"]
+
+
+def test_native_guard_filters_keys_and_checks_reads():
+    target = {"cfg": {"a": 1}, "tags": [1], "secret": 2, "internal_x": 3}
+    guard = theus_core.ContextGuard(target, ["domain.cfg", "domain.tags"], [], path_prefix="domain")
+
+    assert guard.keys() == ["cfg", "tags"]
+    assert [k for k, _ in guard.items()] == ["cfg", "tags"]
+    assert guard.values() == [guard["cfg"], guard["tags"]]
+    assert guard.get("cfg") == {"a": 1}
+
+    assert "cfg" in guard and "secret" not in guard and "internal_x" not in guard
+    whole = theus_core.ContextGuard(target, ["domain"], [], path_prefix="domain")
+    assert whole.get("missing") is None and whole.get("missing", 7) == 7
+    assert whole.keys() == ["cfg", "tags", "secret"]
+    with pytest.raises(PermissionError, match="Illegal Read"):
+        guard.get("secret")
+
+
+def test_native_guard_mapping_needs_read_access_on_itself():
+    denied = theus_core.ContextGuard({"a": 1}, ["other"], [], path_prefix="cfg")
+    with pytest.raises(PermissionError):
+        denied.keys()
+    assert "a" not in denied
@@ -0,0 +1,63 @@
+import pytest
+from theus_core import SupervisorProxy
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+class FakePrinter:
+    """Stand-in for IPython's RepresentationPrinter."""
+
+    def __init__(self):
+        self.out = ""
+
+    def text(self, s):
+        self.out += s
+
+
+seen = {}
+
+
+@process(inputs=["domain.n"], outputs=["domain.n"])
+def peek(ctx):
+    printer = FakePrinter()
+    ctx._repr_pretty_(printer, False)
+    seen["pretty"] = printer.out
+    seen["html"] = ctx.domain._repr_html_()
+
+
+@pytest.mark.asyncio
+async def test_guard_repr_is_permission_filtered():
+    engine = TheusEngine(context={"domain": {"n": 7, "secret": "hunter2", "internal_token": "t"}})
+    engine.register(peek)
+    await engine.execute("peek")
+
+    assert seen["pretty"].startswith("ContextGuard (root)")
+    assert "n: int = 7  [data rw]" in seen["pretty"]
+    for hidden in ("secret", "hunter2", "internal_token"):
+        assert hidden not in seen["pretty"] and hidden not in seen["html"]
+    assert "theus-zone" in seen["html"] and "<code>n</code>" in seen["html"]
+
+
+def test_proxy_repr_is_size_capped_and_escaped():
+    data = {f"k{i}": i for i in range(30)}
+    data["<b>"] = "x" * 500
+    data["heavy_frame"] = list(range(1000))
+    proxy = SupervisorProxy({"domain": data}, path="")
+
+    text = proxy.render_tree(max_keys=5)
+    assert "... 27 more" in text and "k5" not in text
+
+    full = proxy.render_tree(max_keys=100)
+    assert "x" * 61 not in full and "'" + "x" * 59 + "..." in full
+    assert "heavy_frame: list(1000)  [heavy ro]" in full
+
+    assert proxy.render_tree(max_nodes=3).endswith("... truncated at 3 entries")
+
+    html = proxy.render_tree(html=True, max_keys=100)
+    assert "&lt;b&gt;" in html and "<b><b>" not in html
+    assert html.count("<ul") == html.count("</ul>")
+
+    printer = FakePrinter()
+    proxy._repr_pretty_(printer, True)
+    assert printer.out == repr(proxy)
@@ -0,0 +1,36 @@
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.tags", "domain.queue", "domain.cfg"], outputs=[])
+def inspect(ctx):
+    seen["root"] = bool(ctx)
+    seen["tags"] = (bool(ctx.domain.tags), len(ctx.domain.tags))
+    seen["queue"] = (bool(ctx.domain.queue), len(ctx.domain.queue))
+    seen["cfg"] = "empty" if not ctx.domain.cfg else "set"
+
+
+@pytest.mark.asyncio
+async def test_truthiness_of_guarded_containers():
+    engine = TheusEngine(context={"domain": {"tags": ["a", "b"], "queue": [], "cfg": {}}})
+    engine.register(inspect)
+    await engine.execute("inspect")
+    assert seen == {"root": True, "tags": (True, 2), "queue": (False, 0), "cfg": "empty"}
+
+
+def test_native_guard_len_and_bool_need_read_access():
+    target = {"jobs": []}
+    allowed = theus_core.ContextGuard(target, ["cfg"], [], path_prefix="cfg")
+    assert len(allowed) == 1 and bool(allowed)
+    assert not theus_core.ContextGuard({}, ["cfg"], [], path_prefix="cfg")
+
+    denied = theus_core.ContextGuard(target, ["other"], [], path_prefix="cfg")
+    with pytest.raises(PermissionError, match="Illegal Read: 'cfg'"):
+        len(denied)
+    with pytest.raises(PermissionError):
+        bool(denied)
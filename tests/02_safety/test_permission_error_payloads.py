@@ -0,0 +1,57 @@
+import asyncio
+import logging
+
+import pytest
+import theus_core
+from theus_core import Capability, PermissionDeniedError
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "b": 2}, "global": {"limit": 10}})
+
+
+def _guard(engine, tx, caps):
+    token = engine.issue_capability_token(["global"], ["domain"], caps=caps)
+    data = engine._core.state.data
+    target = {"domain": data["domain"], "global": data["global"]}
+    return theus_core.guard_from_token(target, token, engine.capability_key(), tx)
+
+
+def test_capability_denial_carries_required_and_current_caps():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        ctx = _guard(engine, tx, Capability.READ)
+        with pytest.raises(PermissionDeniedError) as info:
+            ctx["domain"]["b"] = 5
+    err = info.value
+    assert (err.path, err.zone) == ("domain", "data")
+    assert err.required == Capability.UPDATE and err.caps == Capability.READ
+    assert err.details == {"path": "domain", "zone": "data", "required": 4, "caps": 1, "rule": err.rule}
+
+
+def test_contract_denial_names_the_rule():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        ctx = _guard(engine, tx, Capability.READ | Capability.UPDATE)
+        with pytest.raises(PermissionDeniedError, match="Illegal Write") as info:
+            ctx["global"] = {}
+        assert (info.value.rule, info.value.path, info.value.required) == ("contract", "global", Capability.UPDATE)
+        with pytest.raises(PermissionDeniedError, match="Illegal Read") as info:
+            ctx["other"]
+        assert (info.value.rule, info.value.required) == ("contract", Capability.READ)
+
+
+def test_effect_budget_denial_has_no_path():
+    @process(inputs=["domain.a"], outputs=[], effects=["outbox"])
+    def chatty(ctx):
+        logging.getLogger("theus.tests.payloads").warning("not budgeted")
+
+    engine = _engine()
+    engine.register(chatty)
+    with pytest.raises(PermissionDeniedError) as info:
+        asyncio.run(engine.execute("chatty"))
+    assert info.value.rule == "effect_budget"
+    assert info.value.path is None and info.value.details["path"] is None
@@ -0,0 +1,79 @@
+import asyncio
+import os
+import socket
+
+import pytest
+
+from theus.contracts import SemanticType, process
+from theus.engine import TheusEngine
+
+
+def _engine(tmp_path):
+    @process(inputs=["domain.a"], outputs=[], semantic=SemanticType.PURE)
+    def writes_file(ctx):
+        with open(os.path.join(tmp_path, "leak.txt"), "w") as f:
+            f.write("x")
+        return ctx.domain.a
+
+    @process(inputs=["domain.a"], outputs=[], semantic=SemanticType.PURE)
+    async def opens_socket(ctx):
+        s = socket.socket()
+        try:
+            s.connect(("127.0.0.1", 9))
+        except OSError:
+            pass
+        finally:
+            s.close()
+
+    @process(inputs=["domain.a"], outputs=["domain.a"])
+    def effect_writes(ctx):
+        with open(os.path.join(tmp_path, "effect.txt"), "w") as f:
+            f.write("ok")
+
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    for p in (writes_file, opens_socket, effect_writes):
+        engine.register(p)
+    return engine
+
+
+def test_off_by_default(tmp_path):
+    engine = _engine(tmp_path)
+    assert engine.pure_io_policy == ("off", [])
+    asyncio.run(engine.execute("writes_file"))
+    assert engine.pure_io_violations() == []
+
+
+def test_record_mode_logs_io_of_pure_processes_only(tmp_path):
+    engine = _engine(tmp_path)
+    engine.set_pure_io_policy("record")
+    assert asyncio.run(engine.execute("writes_file")) == 1
+    asyncio.run(engine.execute("opens_socket"))
+    asyncio.run(engine.execute("effect_writes"))
+    # Outside any process scope nothing is recorded either.
+    open(os.path.join(tmp_path, "outside.txt"), "w").close()
+
+    seen = engine.pure_io_violations()
+    assert {(v["process"], v["category"]) for v in seen} == {("writes_file", "file_write"), ("opens_socket", "network")}
+    assert any(v["event"] == "socket.connect" for v in seen)
+    assert not any(v["rejected"] for v in seen)
+    assert engine.pure_io_violations(clear=True) and engine.pure_io_violations() == []
+
+
+def test_reject_mode_fails_the_call_unless_allowed(tmp_path):
+    engine = _engine(tmp_path)
+    engine.set_pure_io_policy("reject", allow=["network"])
+    with pytest.raises(PermissionError, match="'writes_file' attempted file_write"):
+        asyncio.run(engine.execute("writes_file"))
+    assert not os.path.exists(os.path.join(tmp_path, "leak.txt"))
+    asyncio.run(engine.execute("opens_socket"))
+
+    [violation] = engine.pure_io_violations()
+    assert violation["rejected"] and violation["category"] == "file_write"
+
+
+def test_invalid_policy():
+    engine = TheusEngine()
+    with pytest.raises(ValueError):
+        engine.set_pure_io_policy("block")
+    with pytest.raises(ValueError):
+        engine.set_pure_io_policy("record", allow=["disk"])
@@ -0,0 +1,59 @@
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+def bump(ctx):
+    pass
+
+
+@process(inputs=["domain"], outputs=["domain", "domain.const_rate"])
+def rewrite(ctx):
+    pass
+
+
+@process(inputs=[], outputs=["domain.b"])
+def fill(ctx):
+    pass
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"a": 1, "b": 2, "const_rate": 3, "internal_key": "k", "cfg": {"const_max": 9}}})
+    for func in (bump, rewrite, fill):
+        engine.register(func)
+    return engine
+
+
+def test_report_flags_shared_writes_seals_and_exposure():
+    engine = _engine()
+    theus_core.register_physics_override("domain.internal_key", int(theus_core.Capability.READ))
+    theus_core.register_physics_override("domain.cfg", int(theus_core.Capability.READ))
+    theus_core.register_physics_override("domain.cfg.const_max", 15)
+    engine.require_approval(["domain.a"])
+    engine.grant_approver("alice")
+    try:
+        report = engine.security_report()
+    finally:
+        theus_core.clear_physics_overrides()
+
+    assert report["processes"] == ["bump", "fill", "rewrite"]
+    shared = {s["path"]: s["processes"] for s in report["shared_writes"]}
+    assert shared["domain.a"] == ["bump", "rewrite"] and shared["domain.b"] == ["fill", "rewrite"]
+    assert report["private_exposed"] == [{"path": "domain.internal_key", "caps": ["READ"]}]
+
+    constants = {c["path"]: c for c in report["constants"]}
+    assert constants["domain.const_rate"]["sealed"] and constants["domain.const_rate"]["writers"] == ["rewrite"]
+    assert not constants["domain.cfg.const_max"]["sealed"]
+
+    kinds = {(s["kind"], s["path"]) for s in report["shadowed"]}
+    assert ("override", "domain.cfg") in kinds and ("physics", "domain.const_rate") in kinds
+    assert report["admin"]["approval_paths"] == ["domain.a"] and report["admin"]["approvers"] == ["alice"]
+
+
+def test_clean_engine_has_nothing_to_flag():
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    engine.register(bump)
+    report = engine.security_report()
+    assert report["shared_writes"] == report["private_exposed"] == report["constants"] == report["shadowed"] == []
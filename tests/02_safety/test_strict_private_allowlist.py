@@ -0,0 +1,54 @@
+import collections
+
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+Point = collections.namedtuple("Point", "x y")
+
+seen = {}
+
+
+@process(inputs=["domain.n"], outputs=[])
+def probe(ctx):
+    for name in ("_fields", "_secret"):
+        try:
+            getattr(ctx, name)
+        except Exception as e:
+            seen[name] = type(e).__name__
+
+
+def test_allowlisted_private_names_pass_strict_mode():
+    guard = theus_core.ContextGuard(
+        Point(1, 2), ["pt"], [], path_prefix="pt", strict_guards=True,
+        private_allowlist=["_fields", "_asdict", "_fields"],
+    )
+    assert guard.private_allowlist == ["_asdict", "_fields"]
+    assert guard._asdict() == {"x": 1, "y": 2}
+    assert guard._fields == ("x", "y")
+    with pytest.raises(PermissionError, match="'_make' denied in Strict Mode"):
+        guard._make
+
+    plain = theus_core.ContextGuard(Point(1, 2), ["pt"], [], path_prefix="pt", strict_guards=True)
+    with pytest.raises(PermissionError):
+        plain._asdict
+
+
+def test_allowlist_rejects_public_names_and_dict():
+    with pytest.raises(ValueError, match="not a private attribute"):
+        theus_core.ContextGuard(Point(1, 2), [], [], private_allowlist=["x"])
+    engine = TheusEngine()
+    with pytest.raises(PermissionError, match="__dict__"):
+        engine.private_allowlist = ["__dict__"]
+
+
+@pytest.mark.asyncio
+async def test_engine_allowlist_reaches_process_guards():
+    engine = TheusEngine(context={"domain": {"n": 1}}, private_allowlist=["_fields"])
+    assert engine.private_allowlist == ["_fields"]
+    engine.register(probe)
+    await engine.execute("probe")
+    # Allowlisted: resolved on the target (which has no such attribute) instead of denied.
+    assert seen == {"_fields": "AttributeError", "_secret": "PermissionDeniedError"}
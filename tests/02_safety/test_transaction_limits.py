@@ -0,0 +1,48 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import TransactionCancelledError, TransactionLimitError
+from theus_core.testing import Harness
+
+
+@process(inputs=["domain.rows"], outputs=["domain.rows"])
+def runaway(ctx):
+    for i in range(10_000):
+        ctx.domain.rows[f"k{i}"] = i
+
+
+def test_max_deltas_aborts_runaway_process():
+    with Harness() as h:
+        engine = TheusEngine(context={"domain": {"rows": {}}})
+        engine.register(runaway)
+        engine.set_transaction_limits(max_deltas=5)
+        assert engine.transaction_limits == {"max_deltas": 5, "max_paths": None}
+
+        with pytest.raises(TransactionLimitError, match=r"max_deltas=5 exceeded \(6 deltas logged\)"):
+            asyncio.run(engine.execute("runaway"))
+        assert engine.state.data["domain"]["rows"] == {}
+        assert h.audit_events("TX_LIMIT_EXCEEDED")
+
+
+def test_max_paths_counts_distinct_paths_and_cancels_tx():
+    engine = TheusEngine(context={"domain": {}})
+    engine.set_transaction_limits(max_paths=2)
+    with pytest.raises(TransactionLimitError, match="last 'domain.c'"):
+        with engine.transaction() as tx:
+            tx.update_many([("domain.a", 1), ("domain.b", 2), ("domain.a", 3)])
+            tx.update_many([("domain.c", 1)])
+    assert engine.state.data["domain"] == {}
+
+    tx = engine._core.transaction()
+    with pytest.raises(TransactionLimitError):
+        tx.update_many([("domain.x", 1), ("domain.y", 1), ("domain.z", 1)])
+    with pytest.raises(TransactionCancelledError):
+        tx.update_many([("domain.x", 2)])
+
+    engine.set_transaction_limits()
+    with engine.transaction() as tx:
+        tx.update_many([(f"domain.f{i}", i) for i in range(10)])
+    assert len(engine.state.data["domain"]) == 10
@@ -0,0 +1,67 @@
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.payroll", "domain.n"], outputs=["domain.payroll", "domain.n"])
+def raise_salary(ctx):
+    ctx.domain.payroll["ann"] = 200
+    ctx.domain.n = 1
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"payroll": {"ann": 100}, "n": 0}})
+    engine.require_approval(["domain.payroll"])
+    engine.grant_approver("carol")
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_privileged_writes_wait_for_a_second_approver():
+    engine = _engine()
+    engine.register(raise_salary)
+    await engine.execute("raise_salary")
+
+    # The unguarded part of the commit went through; the payroll change is parked.
+    assert engine.state.data["domain"]["n"] == 1
+    assert engine.state.data["domain"]["payroll"] == {"ann": 100}
+    [proposal] = engine.proposals()
+    assert proposal["proposer"] == "raise_salary"
+    assert proposal["writes"] == {"domain.payroll": {"ann": 200}}
+
+    with pytest.raises(PermissionError, match="GRANT"):
+        engine.approve(proposal["id"], "mallory")
+    engine.grant_approver("raise_salary")
+    with pytest.raises(PermissionError, match="Two-person rule"):
+        engine.approve(proposal["id"], "raise_salary")
+
+    version = engine.approve(proposal["id"], "carol")
+    assert engine.state.version == version
+    assert engine.state.data["domain"]["payroll"] == {"ann": 200}
+    assert engine.proposals() == []
+
+    keys = [e.key for e in theus_core.AuditSystem().get_logs() if e.key.startswith("APPROVAL_")]
+    assert keys[-2:] == ["APPROVAL_PROPOSED", "APPROVAL_GRANTED"]
+
+
+def test_reject_and_admin_bypass():
+    engine = _engine()
+    with engine.transaction(actor="bob") as tx:
+        tx.update(data={"domain": {"payroll": {"ann": 1}}})
+    assert tx.proposal is not None and tx.actor == "bob"
+    engine.reject(tx.proposal, "carol", reason="not budgeted")
+    assert engine.proposals() == []
+    with pytest.raises(KeyError):
+        engine.approve(tx.proposal, "carol")
+
+    with engine.transaction(admin=True) as tx:
+        tx.update(data={"domain": {"payroll": {"ann": 300}}})
+    assert tx.proposal is None
+    assert engine.state.data["domain"]["payroll"] == {"ann": 300}
+
+    # Unchanged values are not parked.
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"payroll": {"ann": 300}, "n": 5}})
+    assert tx.proposal is None and engine.state.data["domain"]["n"] == 5
@@ -0,0 +1,66 @@
+import asyncio
+import os
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+PAYLOAD = b"\x89PNG" + bytes(range(256)) * 64
+
+
+@process(inputs=["domain.doc"], outputs=["domain.doc"])
+def attach(ctx, data=PAYLOAD):
+    ctx.domain.doc["image"] = ctx.blobs.put(data)
+    return ctx.domain.doc["image"]
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"doc": {"image": None}}})
+    engine.register(attach)
+    return engine
+
+
+def test_state_holds_the_content_hash_and_bytes_are_deduplicated():
+    engine = _engine()
+    blob_id = asyncio.run(engine.execute("attach"))
+    assert blob_id.startswith("sha256:") and len(blob_id) == 71
+    assert engine.state.data["domain"]["doc"]["image"] == blob_id
+    assert engine.blobs.get(blob_id) == PAYLOAD
+    assert len(engine.dumps_state()) < len(PAYLOAD)
+
+    assert asyncio.run(engine.execute("attach")) == blob_id
+    stats = engine.blob_stats()
+    assert stats["blobs"] == 1 and stats["bytes"] == len(PAYLOAD)
+    assert os.listdir(stats["dir"]) == [blob_id[len("sha256:"):]]
+
+
+def test_gc_deletes_blobs_no_retained_version_references():
+    engine = _engine()
+    engine.set_version_retention(2)
+    old = asyncio.run(engine.execute("attach"))
+    new = asyncio.run(engine.execute("attach", data=b"v2"))
+
+    # The previous version is still retained and references `old`.
+    assert engine.gc_blobs() == 0
+    assert engine.blob_stats()["referenced"] == 2
+
+    asyncio.run(engine.execute("attach", data=b"v3"))
+    assert engine.gc_blobs() == 1
+    assert old not in engine.blobs and new in engine.blobs
+    with pytest.raises(KeyError):
+        engine.blobs.get(old)
+
+
+def test_blobs_put_by_open_transactions_survive_gc():
+    engine = _engine()
+    with engine.transaction() as tx:
+        pending = tx.blobs.put(b"draft")
+        assert engine.gc_blobs() == 0
+        tx.update(data={"domain": {"doc": {"image": pending}}})
+    assert engine.gc_blobs() == 0
+    assert engine.blobs.get(pending) == b"draft"
+
+    stray = engine.blobs.put(b"stray")
+    assert engine.gc_blobs() == 1
+    assert stray not in engine.blobs
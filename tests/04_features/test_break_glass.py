@@ -0,0 +1,106 @@
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import PermissionDeniedError
+from theus_core.testing import Harness
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"cfg": 1}})
+    with engine.tenant("acme").transaction() as tx:
+        tx.update(data={"domain": {"plan": "pro"}})
+    return engine
+
+
+def _write_private(engine, value):
+    with engine.transaction(actor="oncall") as tx:
+        tx.update_many([("domain.internal_notes", value)])
+
+
+def test_grant_lifts_private_and_tenancy_until_expiry():
+    engine = _engine()
+    with Harness(engine=engine, start_ms=1_000_000) as h:
+        with pytest.raises(PermissionError):
+            _write_private(engine, "before")
+
+        grant = engine.break_glass(["domain.internal_notes", "tenants.acme"], "INC-42: corrupt plan", 60, actor="oncall")
+        _write_private(engine, "fixed")
+        with engine.transaction(admin=True) as tx:
+            tx.update(data={"tenants": {"acme": {"domain": {"plan": "free"}}}})
+        assert engine.tenant("acme").read("domain.plan") == "free"
+        assert [g["id"] for g in engine.break_glass_grants()] == [grant]
+
+        h.advance_ms(60_000)
+        with pytest.raises(PermissionError):
+            _write_private(engine, "after")
+        with pytest.raises(PermissionDeniedError, match="tenant"):
+            with engine.transaction(admin=True) as tx:
+                tx.update(data={"tenants": {"acme": {"domain": {"plan": "pro"}}}})
+        assert engine.break_glass_grants() == []
+
+        events = [m.payload["event"] for m in h.outbox("theus.break_glass")]
+        assert events == ["granted", "expired"]
+        assert h.outbox("theus.break_glass")[0].payload["reason"] == "INC-42: corrupt plan"
+        audit = [e.message for e in h.audit_events("BREAK_GLASS")]
+        assert len(audit) == 2 and "GRANTED" in audit[0] and "EXPIRED" in audit[1]
+        assert len(h.audit_events("BREAK_GLASS_USED")) == 1
+
+
+def test_revoke_restores_previous_caps_and_rejects_bad_grants():
+    engine = _engine()
+    with Harness(engine=engine, start_ms=1_000_000) as h:
+        first = engine.break_glass(["domain.internal_notes"], "INC-1", 60)
+        second = engine.break_glass(["domain.internal_notes"], "INC-2", 600)
+        assert engine.revoke_break_glass(first) and not engine.revoke_break_glass(first)
+        _write_private(engine, "still open")
+        assert engine.revoke_break_glass(second)
+        with pytest.raises(PermissionError):
+            _write_private(engine, "closed")
+        assert [m.payload["event"] for m in h.outbox("theus.break_glass")] == ["granted", "granted", "revoked", "revoked"]
+
+        with pytest.raises(ValueError, match="reason"):
+            engine.break_glass(["domain.internal_notes"], " ", 60)
+        with pytest.raises(ValueError, match="Constant"):
+            engine.break_glass(["domain.const_limits"], "INC-3", 60)
+        with pytest.raises(ValueError):
+            engine.break_glass(["domain.internal_notes"], "INC-4", 0)
+
+
+SEEN = []
+
+
+@process(inputs=["domain.internal_notes"], outputs=["domain.internal_notes"])
+async def fix_notes(ctx):
+    SEEN.append(ctx.domain.internal_notes)
+    if SEEN[-1] is not None:
+        ctx.domain.internal_notes = "fixed"
+
+
+@pytest.mark.asyncio
+async def test_grant_reaches_processes_of_its_own_engine_only():
+    engines = [TheusEngine(context={"domain": {"internal_notes": "bad"}}) for _ in range(2)]
+    for engine in engines:
+        engine.register(fix_notes)
+    SEEN.clear()
+    with Harness(start_ms=1_000_000):
+        await engines[0].execute("fix_notes")
+        assert SEEN == [None]
+
+        engines[0].break_glass(["domain.internal_notes"], "INC-7: repair notes", 60)
+        await engines[0].execute("fix_notes")
+        await engines[1].execute("fix_notes")
+        assert SEEN == [None, "bad", None]
+        assert engines[0].state.data["domain"]["internal_notes"] == "fixed"
+        assert engines[1].state.data["domain"]["internal_notes"] == "bad"
+
+
+def test_access_ends_at_expiry_inside_an_open_transaction():
+    engine = _engine()
+    with Harness(engine=engine, start_ms=1_000_000) as h:
+        engine.break_glass(["domain.internal_notes"], "INC-8", 60)
+        with pytest.raises(PermissionError):
+            with engine.transaction() as tx:
+                tx.update_many([("domain.internal_notes", "early")])
+                h.advance_ms(60_000)
+                tx.update_many([("domain.internal_notes", "late")])
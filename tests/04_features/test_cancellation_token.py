@@ -0,0 +1,86 @@
+import asyncio
+import threading
+
+import pytest
+
+from theus.contracts import SemanticType, process
+from theus.engine import TheusEngine
+from theus_core import CancellationToken, ProcessCancelledError, TransactionCancelledError
+
+SEEN = {}
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+async def long_async(ctx):
+    SEEN["token"] = ctx.token
+    SEEN["started"].set()
+    await asyncio.sleep(30)
+    ctx.domain.a = 99
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+def cooperative_sync(ctx):
+    SEEN["token"] = ctx.token
+    SEEN["loop"].call_soon_threadsafe(SEEN["started"].set)
+    SEEN["go"].wait(5)
+    assert ctx.cancelled
+    ctx.raise_if_cancelled()
+    ctx.domain.a = 99
+
+
+@process(inputs=["domain.a"], outputs=[], semantic=SemanticType.PURE)
+def pure_reader(ctx):
+    ctx.raise_if_cancelled()
+    return (ctx.cancelled, ctx.domain.a)
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    for p in (long_async, cooperative_sync, pure_reader):
+        engine.register(p)
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_cancel_by_name_cancels_async_task():
+    engine = _engine()
+    SEEN["started"] = asyncio.Event()
+    task = asyncio.ensure_future(engine.execute("long_async"))
+    await SEEN["started"].wait()
+
+    assert engine.cancel("long_async", reason="shutdown") == 1
+    with pytest.raises(asyncio.CancelledError):
+        await task
+    token = SEEN["token"]
+    assert token.cancelled and token.reason == "shutdown" and token.process == "long_async"
+    assert engine.state.data["domain"]["a"] == 1
+    assert engine.cancel("long_async") == 0
+
+
+@pytest.mark.asyncio
+async def test_cancel_by_token_is_cooperative_for_sync_processes():
+    engine = _engine()
+    SEEN.update(started=asyncio.Event(), go=threading.Event(), loop=asyncio.get_running_loop())
+    task = asyncio.ensure_future(engine.execute("cooperative_sync"))
+    await SEEN["started"].wait()
+
+    assert engine.cancel(SEEN["token"]) == 1
+    assert engine.cancel(SEEN["token"]) == 0
+    SEEN["go"].set()
+    with pytest.raises((asyncio.CancelledError, TransactionCancelledError)):
+        await task
+    await asyncio.sleep(0.05)
+    assert engine.state.data["domain"]["a"] == 1
+
+
+def test_token_api_and_pure_processes():
+    token = CancellationToken("manual")
+    token.raise_if_cancelled()
+    assert token.cancel("stop") is True
+    with pytest.raises(ProcessCancelledError, match="'manual' cancelled: stop"):
+        token.raise_if_cancelled()
+    assert issubclass(ProcessCancelledError, TransactionCancelledError)
+
+    assert asyncio.run(_engine().execute("pure_reader")) == (False, 1)
+    with pytest.raises(TypeError):
+        _engine().cancel(42)
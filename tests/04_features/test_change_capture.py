@@ -0,0 +1,61 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {
+        "orders": {"o1": {"status": "new", "total": 5, "customer": {"id": 1, "name": "Ann"}, "lines": [1, 2, 3]}},
+        "noise": 0,
+    }})
+
+
+def _write(engine, actor=None, **fields):
+    with engine.transaction(actor=actor) as tx:
+        tx.update(data={"domain": fields})
+
+
+def test_filtered_projected_events_per_commit():
+    engine = _engine()
+    batches = []
+    sub = engine.subscribe_changes(batches.append, ["domain.orders.*"], fields=["status", "customer.id"], include_old=True)
+
+    _write(engine, noise=1)
+    assert batches == []
+
+    _write(engine, actor="api", orders={"o1": {"status": "paid"}, "o2": {"status": "new", "total": 1}})
+    (events,) = batches
+    version = engine.state.version
+    assert events == [
+        {"path": "domain.orders.o1", "op": "update", "version": version, "actor": "api",
+         "value": {"status": "paid", "customer": {"id": 1}}, "old_value": {"status": "new", "customer": {"id": 1}}},
+        {"path": "domain.orders.o2", "op": "insert", "version": version, "actor": "api",
+         "value": {"status": "new"}, "old_value": None},
+    ]
+    assert engine.change_subscriptions()[sub]["events"] == 2
+    assert engine.unsubscribe_changes(sub) and not engine.unsubscribe_changes(sub)
+
+
+def test_ops_filter_deletes_and_sink_errors():
+    engine = _engine()
+    deletes, statuses = [], []
+    engine.subscribe_changes(deletes.append, ["domain.orders.*"], ops=["delete"])
+    engine.subscribe_changes(statuses.append, ["domain.orders.*.status"])
+
+    def broken(events):
+        raise RuntimeError("sink down")
+
+    bad = engine.subscribe_changes(broken, ["domain.orders.*"])
+    _write(engine, orders={"o1": {"total": 6}})
+    assert deletes == [] and statuses == []
+    assert engine.change_subscriptions()[bad]["errors"] == 1
+
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"orders": None}})
+    assert [e["path"] for e in deletes[0]] == ["domain.orders.o1"] and deletes[0][0]["value"] is None
+    assert [(e["path"], e["op"]) for e in statuses[0]] == [("domain.orders.o1.status", "delete")]
+
+    with pytest.raises(ValueError, match="Unknown change op"):
+        engine.subscribe_changes(print, ["domain.orders.*"], ops=["upsert"])
+    with pytest.raises(ValueError, match="Private"):
+        engine.subscribe_changes(print, ["internal_keys.*"])
@@ -0,0 +1,69 @@
+import asyncio
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.order"], outputs=["domain.order"])
+def write_order(ctx, total=None, placed=None, note=None):
+    if total is not None:
+        ctx.domain.order["total"] = total
+    if placed is not None:
+        ctx.domain.order["placed"] = placed
+    if note is not None:
+        ctx.domain.order["note"] = note
+    return "ok"
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"order": {"total": Decimal("0"), "placed": None, "note": ""}}})
+    engine.register(write_order)
+    return engine
+
+
+def _order(engine):
+    return engine.state.data["domain"]["order"]
+
+
+def test_builtin_coercions_normalize_writes():
+    engine = _engine()
+    engine.add_coercion("domain.order.total", "to_decimal")
+    engine.add_coercion("domain.order.placed", "to_utc")
+    engine.add_coercion("domain.order.note", "strip_strings")
+
+    asyncio.run(engine.execute("write_order", total=0.1, placed=datetime(2024, 1, 2, 3, 4), note="  hi  "))
+    order = _order(engine)
+    assert order["total"] == Decimal("0.1") and isinstance(order["total"], Decimal)
+    assert order["placed"] == datetime(2024, 1, 2, 3, 4, tzinfo=timezone.utc)
+    assert order["note"] == "hi"
+
+    plus_two = timezone(timedelta(hours=2))
+    asyncio.run(engine.execute("write_order", total="12.50", placed=datetime(2024, 1, 2, 5, 0, tzinfo=plus_two)))
+    assert _order(engine)["total"] == Decimal("12.50")
+    assert _order(engine)["placed"].utcoffset() == timedelta(0)
+    assert _order(engine)["placed"].hour == 3
+
+    # Unconvertible values pass through for validation to report.
+    asyncio.run(engine.execute("write_order", total="n/a"))
+    assert _order(engine)["total"] == "n/a"
+
+
+def test_custom_rule_chains_and_removal():
+    engine = _engine()
+    engine.add_coercion("domain.order.note", ["strip_strings", str.upper])
+    asyncio.run(engine.execute("write_order", note="  rush "))
+    assert _order(engine)["note"] == "RUSH"
+
+    assert engine.remove_coercion("domain.order.note")
+    assert not engine.remove_coercion("domain.order.note")
+    asyncio.run(engine.execute("write_order", note=" as-is "))
+    assert _order(engine)["note"] == " as-is "
+
+    with pytest.raises(ValueError):
+        engine.add_coercion("domain.order.note", "to_roman")
+    with pytest.raises(TypeError):
+        engine.add_coercion("domain.order.note", 42)
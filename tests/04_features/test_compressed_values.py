@@ -0,0 +1,63 @@
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.reports"], outputs=[])
+def read_report(ctx):
+    seen["q1"] = ctx.domain.reports["q1"]
+
+
+@process(inputs=["domain.reports"], outputs=["domain.reports"])
+def append_report(ctx):
+    ctx.domain.reports["q2"] = "flat " * 2000
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"reports": {"q1": "growth " * 5000}, "n": 0}})
+    engine.register(read_report)
+    engine.register(append_report)
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_values_stay_compressed_and_decompress_on_read():
+    engine = _engine()
+    engine.set_compression("domain.reports")
+    stored = engine._core.state.data["domain"]["reports"]
+    assert isinstance(stored, theus_core.CompressedValue)
+    assert stored.codec == "zstd" and stored.nbytes < stored.raw_nbytes
+
+    await engine.execute("read_report")
+    await engine.execute("read_report")
+    assert seen["q1"] == "growth " * 5000
+
+    await engine.execute("append_report")
+    stored = engine._core.state.data["domain"]["reports"]
+    assert isinstance(stored, theus_core.CompressedValue)
+    assert stored.load()["q2"].startswith("flat") and stored == stored.load()
+
+    stats = engine.compression_stats()
+    path = stats["paths"]["domain.reports"]
+    assert path["codec"] == "zstd" and path["values"] == 2
+    assert stats["saved_bytes"] == path["raw_bytes"] - path["stored_bytes"] > 0
+    assert stats["hits"] >= 1 and stats["misses"] >= 1
+    assert stats["hit_rate"] == stats["hits"] / stats["reads"]
+
+
+def test_lz4_policy_removal_and_validation():
+    engine = _engine()
+    engine.set_compression("domain.reports", codec="lz4")
+    stored = engine._core.state.data["domain"]["reports"]
+    assert stored.codec == "lz4" and stored.load() == {"q1": "growth " * 5000}
+
+    engine.set_compression("domain.reports", codec=None)
+    assert engine.compression_stats()["paths"] == {}
+    with pytest.raises(ValueError, match="Unknown codec"):
+        engine.set_compression("domain.reports", codec="gzip")
+    with pytest.raises(ValueError, match="zone and a field"):
+        engine.set_compression("domain")
@@ -0,0 +1,76 @@
+import threading
+import time
+
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _write(engine, **fields):
+    with engine.transaction() as tx:
+        tx.update(data={"domain": fields})
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"orders": {"o1": 5, "o2": 7}, "other": 0}})
+
+
+def test_cached_per_dependency_version():
+    engine = _engine()
+    calls = []
+
+    def total(state):
+        calls.append(state.version)
+        return sum(state.data["domain"]["orders"].values())
+
+    engine.define_computed("derived.total", total, deps=["domain.orders"])
+    assert engine.computed("derived.total") == 12
+    assert engine.computed("derived.total") == 12
+    _write(engine, other=1)  # unrelated commit keeps the entry
+    assert engine.computed("derived.total") == 12
+    assert len(calls) == 1
+
+    _write(engine, orders={"o3": 1})
+    assert engine.computed("derived.total") == 13
+    assert len(calls) == 2
+    stats = engine.computed_stats()["derived.total"]
+    assert (stats["hits"], stats["misses"], stats["errors"]) == (2, 2, 0)
+    assert stats["cached_version"] == engine.state.version
+
+    engine.invalidate_computed()
+    assert engine.computed("derived.total") == 13 and len(calls) == 3
+    assert engine.remove_computed("derived.total")
+    with pytest.raises(KeyError):
+        engine.computed("derived.total")
+
+
+def test_concurrent_readers_share_one_computation_and_its_error():
+    engine = _engine()
+    calls = []
+
+    def slow(state):
+        calls.append(1)
+        time.sleep(0.2)
+        if len(calls) == 1:
+            raise RuntimeError("backend down")
+        return "ok"
+
+    engine.define_computed("derived.slow", slow, deps=["domain.orders"])
+    results = []
+
+    def read():
+        try:
+            results.append(engine.computed("derived.slow"))
+        except RuntimeError as e:
+            results.append(str(e))
+
+    threads = [threading.Thread(target=read) for _ in range(4)]
+    for t in threads:
+        t.start()
+    for t in threads:
+        t.join()
+    assert results == ["backend down"] * 4 and len(calls) == 1
+    assert engine.computed_stats()["derived.slow"]["waits"] == 3
+
+    # Errors are not cached.
+    assert engine.computed("derived.slow") == "ok" and len(calls) == 2
@@ -104,3 +104,32 @@ class TestConflictManager:
         assert decision_p2.wait_ms == 50, (
             "Blocked process should endure polite wait time"
         )
+
+
+class TestRetryDecisionGuidance:
+    def test_attempts_remaining_and_reason(self):
+        from theus_core import RetryReason
+
+        cm = ConflictManager(max_retries=2, base_backoff_ms=10)
+        d1 = cm.report_conflict("p")
+        assert (d1.reason, d1.attempts_remaining) == (RetryReason.Backoff, 1)
+        d2 = cm.report_conflict("p")
+        assert (d2.reason, d2.attempts_remaining) == (RetryReason.Backoff, 0)
+        assert cm.report_conflict("p").reason == RetryReason.VipGranted
+        assert cm.report_conflict("p").reason == RetryReason.VipRetry
+
+        other = cm.report_conflict("q")
+        assert other.reason == RetryReason.BlockedByVip
+        assert other.should_retry
+
+    def test_suggested_backoff_scales_with_contention(self):
+        cm = ConflictManager(max_retries=10, base_backoff_ms=100)
+        solo = cm.report_conflict("a")
+        assert 80 <= solo.suggested_backoff_ms <= 120
+
+        for key in ("b", "c", "d"):
+            cm.report_conflict(key)
+        busy = cm.report_conflict("e")
+        # 5 contending keys -> ~5x the raw wait (capped at 1000ms)
+        assert busy.suggested_backoff_ms > busy.wait_ms
+        assert busy.suggested_backoff_ms <= 1000
@@ -0,0 +1,63 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import ContractViolationError, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.a", "domain.unused"], outputs=["domain.a", "domain.never"])
+def sloppy(ctx):
+    ctx.domain.a = ctx.domain.a + ctx.domain.secret
+    ctx.domain.b = 5
+
+
+@process(inputs=["domain.a", "domain.flag"], outputs=["domain.a"])
+def branchy(ctx):
+    if ctx.domain.a == 1:
+        ctx.domain.flag
+    ctx.domain.a = ctx.domain.a + 1
+
+
+def _engine(**kwargs):
+    engine = TheusEngine(context={"domain": {"a": 1, "b": 0, "unused": 0, "never": 0, "secret": 2, "flag": True}}, **kwargs)
+    engine.register(sloppy)
+    engine.register(branchy)
+    engine.track_contract_drift(window=5)
+    return engine
+
+
+def test_warn_mode_commits_and_reports_both_directions():
+    engine = _engine(contract_mode="warn")
+    asyncio.run(engine.execute("sloppy"))
+    assert engine.state.data["domain"]["b"] == 5
+
+    drift = engine.contract_drift("sloppy")
+    assert (drift["runs"], drift["window"]) == (1, 5)
+    assert drift["unused_inputs"] == ["domain.unused"]
+    assert drift["unused_outputs"] == ["domain.never"]
+    assert drift["undeclared_reads"] == ["domain.secret"]
+    assert drift["undeclared_writes"] == ["domain.b"]
+
+    enforcing = _engine()
+    with pytest.raises(ContractViolationError, match="domain.b"):
+        asyncio.run(enforcing.execute("sloppy"))
+    with pytest.raises(ValueError, match="contract_mode"):
+        enforcing.contract_mode = "loose"
+
+
+def test_window_slides_and_can_be_disabled():
+    engine = _engine()
+    engine.track_contract_drift(window=1)
+    asyncio.run(engine.execute("branchy"))
+    assert engine.contract_drift("branchy")["unused_inputs"] == []
+
+    # Only the last run is kept: it no longer reads domain.flag.
+    asyncio.run(engine.execute("branchy"))
+    drift = engine.contract_drift("branchy")
+    assert drift["runs"] == 1 and drift["unused_inputs"] == ["domain.flag"]
+
+    engine.track_contract_drift(window=0)
+    assert engine.contract_drift("branchy")["runs"] == 0
+    with pytest.raises(KeyError):
+        engine.contract_drift("missing")
@@ -0,0 +1,64 @@
+import copy
+import pickle
+
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+class FakeCudaTensor:
+    """Minimal DLPack producer on a CUDA device that refuses to be copied."""
+
+    nbytes = 256
+
+    def __dlpack__(self, stream=None):
+        return ("capsule", stream)
+
+    def __dlpack_device__(self):
+        return (2, 1)
+
+    def __deepcopy__(self, memo):
+        raise RuntimeError("device tensors cannot be deep-copied")
+
+    def __eq__(self, other):
+        return self is other
+
+
+def test_handle_exposes_dlpack_device_and_forbids_copies():
+    engine = TheusEngine()
+    tensor = FakeCudaTensor()
+    handle = engine.alloc_heavy("weights", tensor)
+    assert handle.device == ("cuda", 1) and handle.nbytes == 256
+    assert handle.__dlpack_device__() == (2, 1)
+    assert handle.__dlpack__(stream=7) == ("capsule", 7)
+    assert copy.deepcopy(handle) is handle and copy.copy(handle) is handle
+    with pytest.raises(TypeError, match="ipc_export"):
+        pickle.dumps(handle)
+    with pytest.raises(TypeError, match="No CUDA IPC route"):
+        handle.ipc_export()
+
+    plain = engine.alloc_heavy("blob", b"abc")
+    assert plain.device is None
+    with pytest.raises(TypeError, match="DLPack"):
+        plain.__dlpack_device__()
+    with pytest.raises(TypeError, match="Unknown IPC payload"):
+        theus_core.HeavyHandle.ipc_import({"kind": "mystery"})
+
+
+seen = {}
+
+
+@process(inputs=["domain.model"], outputs=["domain.n"])
+def infer(ctx):
+    seen["same"] = ctx.domain.model["weights"] is not None
+    ctx.domain.n = 1
+
+
+@pytest.mark.asyncio
+async def test_device_tensors_in_data_are_not_shadow_copied():
+    engine = TheusEngine(context={"domain": {"model": {"weights": FakeCudaTensor()}, "n": 0}})
+    engine.register(infer)
+    await engine.execute("infer")
+    assert seen["same"] and engine.state.data["domain"]["n"] == 1
@@ -0,0 +1,72 @@
+import pytest
+import theus_core
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+HISTOGRAMS = {"commit_latency_us", "deltas_per_commit", "shadow_copy_bytes", "proxy_read_latency_ns"}
+
+
+@process(inputs=["domain.counter"], outputs=["domain.counter"])
+def bump(ctx):
+    ctx.domain.counter = ctx.domain.counter + 1
+
+
+def _fresh():
+    theus_core.set_metrics_enabled(True)
+    theus_core.engine_metrics(reset=True)
+
+
+def test_commits_are_recorded():
+    _fresh()
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"a": 1}})
+    engine.compare_and_swap(engine.state.version, data={"domain": {"a": 2}, "global": {"b": 1}})
+
+    m = theus_core.engine_metrics()
+    assert HISTOGRAMS <= set(m)
+    commits = m["commit_latency_us"]
+    assert commits["count"] == 2 and commits["unit"] == "us"
+    assert commits["min"] <= commits["p50"] <= commits["p99"] <= commits["max"]
+    assert m["deltas_per_commit"]["max"] == 2
+
+
+@pytest.mark.asyncio
+async def test_process_run_records_shadow_and_proxy_reads():
+    _fresh()
+    engine = TheusEngine(context={"domain": {"counter": 0}})
+    engine.register(bump)
+    await engine.execute("bump")
+
+    m = theus_core.engine_metrics()
+    assert m["shadow_copy_bytes"]["count"] >= 1
+    assert m["shadow_copy_bytes"]["sum"] > 0
+    assert m["proxy_read_latency_ns"]["count"] >= 1
+
+
+def test_buckets_are_cumulative_and_reset_clears():
+    _fresh()
+    engine = TheusEngine()
+    for i in range(5):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": i}})
+
+    buckets = theus_core.engine_metrics(reset=True)["commit_latency_us"]["buckets"]
+    counts = [c for _, c in buckets]
+    assert counts == sorted(counts) and counts[-1] == 5
+    assert [le for le, _ in buckets] == sorted(le for le, _ in buckets)
+    assert theus_core.engine_metrics()["commit_latency_us"]["count"] == 0
+
+
+def test_disabled_metrics_record_nothing():
+    _fresh()
+    theus_core.set_metrics_enabled(False)
+    try:
+        engine = TheusEngine()
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"a": 1}})
+        m = theus_core.engine_metrics()
+        assert m["enabled"] is False
+        assert m["commit_latency_us"]["count"] == 0
+    finally:
+        theus_core.set_metrics_enabled(True)
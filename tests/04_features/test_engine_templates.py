@@ -0,0 +1,67 @@
+import pytest
+from pydantic import BaseModel
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+TEMPLATE = {"domain": {"counter": 0, "items": []}, "global": {"mode": "test"}}
+
+
+class Domain(BaseModel):
+    counter: int
+    items: list
+    const_limit: int = 0
+
+
+class Schema(BaseModel):
+    domain: Domain
+
+
+@process(inputs=["domain.counter"], outputs=["domain.counter", "domain.const_limit"])
+def bump(ctx):
+    ctx.domain.counter += 1
+
+
+@process(inputs=["domain.const_limit"], outputs=["domain.const_limit"])
+def tamper(ctx):
+    ctx.domain.const_limit = 99
+
+
+@pytest.mark.asyncio
+async def test_template_seeds_and_resets():
+    engine = TheusEngine.from_template(TEMPLATE, schema=Schema, constants={"domain": {"const_limit": 3}})
+    assert engine.state.data["domain"] == {"counter": 0, "items": [], "const_limit": 3}
+    seeded = engine.state.version
+
+    engine.register(bump)
+    await engine.execute("bump")
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"items": ["x"], "scratch": 1}})
+    assert engine.state.data["domain"]["counter"] == 1
+
+    engine.reset_to_template()
+    assert engine.state.version == seeded
+    assert engine.state.data["domain"] == {"counter": 0, "items": [], "const_limit": 3}
+    assert engine._core.state.data["global"] == {"mode": "test"}
+    # The template itself was copied, not shared.
+    assert TEMPLATE["domain"]["items"] == []
+
+
+@pytest.mark.asyncio
+async def test_constants_are_sealed():
+    engine = TheusEngine.from_template(TEMPLATE, constants={"domain": {"const_limit": 3}})
+    engine.register(tamper)
+    with pytest.raises(PermissionError, match="CONSTANT"):
+        await engine.execute("tamper")
+    assert engine._core.state.data["domain"]["const_limit"] == 3
+
+
+def test_template_validation():
+    with pytest.raises(Exception, match="Schema Violation"):
+        TheusEngine.from_template({"domain": {"counter": "x", "items": []}}, schema=Schema)
+    with pytest.raises(ValueError, match="const_"):
+        TheusEngine.from_template(TEMPLATE, constants={"domain": {"limit": 3}})
+    with pytest.raises(ValueError, match="both"):
+        TheusEngine.from_template({"domain": {"const_limit": 1}}, constants={"domain": {"const_limit": 3}})
+    with pytest.raises(RuntimeError, match="from_template"):
+        TheusEngine(context={"domain": {}}).reset_to_template()
@@ -0,0 +1,77 @@
+import pytest
+from theus_core import RuleError, RuleLimitError
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {
+            "order": {"total": 120.5, "items": [{"sku": "A", "qty": 2}, {"sku": "B", "qty": 5}], "country": "VN"},
+            "tiers": [10, 50, 100],
+            "hook": print,
+        }})
+    return engine
+
+
+def test_rules_evaluate_against_subtree():
+    engine = _engine()
+    assert engine.eval_rule("domain.order", "total > 100 and country in ['VN', 'TH']") is True
+    assert engine.eval_rule("domain.order", "sum([items[0].qty, items[1].qty]) * 2") == 14
+    assert engine.eval_rule("domain.order", "'gold' if total >= 100 else 'std'") == "gold"
+    assert engine.eval_rule("domain.order", "lower(country) + '-' + items[-1].sku") == "vn-B"
+    assert engine.eval_rule("domain.tiers", "max(_) - min(_)") == 90
+    assert engine.eval_rule("domain.tiers.1", "_ == 50") is True
+    assert engine.eval_rule("", "len(keys(domain))") == 3
+
+
+def test_rules_cannot_reach_python_objects():
+    engine = _engine()
+    with pytest.raises(RuleError, match="Unknown function"):
+        engine.eval_rule("domain", "print('x')")
+    with pytest.raises(RuleError, match="Cannot read field"):
+        engine.eval_rule("domain", "hook.__self__")
+    with pytest.raises(RuleError, match="opaque"):
+        engine.eval_rule("domain", "hook")
+    with pytest.raises(RuleError, match="Unexpected character"):
+        engine.eval_rule("domain", "tiers; import os")
+
+
+def test_rule_errors():
+    engine = _engine()
+    with pytest.raises(RuleError, match="Division by zero"):
+        engine.eval_rule("domain.order", "total / 0")
+    with pytest.raises(RuleError, match="not found"):
+        engine.eval_rule("domain.missing", "true")
+    with pytest.raises(RuleError, match="Unknown name"):
+        engine.eval_rule("domain.order", "discount > 0")
+
+
+def test_rule_limits():
+    engine = _engine()
+    assert issubclass(RuleLimitError, RuleError)
+    with pytest.raises(RuleLimitError, match="steps"):
+        engine.eval_rule("domain.order", "total + total + total + total", max_steps=3)
+    with pytest.raises(RuleLimitError, match="memory"):
+        engine.eval_rule("domain.tiers", "_ + _ + _ + _", max_memory=200)
+    with pytest.raises(RuleLimitError, match="nested"):
+        engine.eval_rule("domain.order", "(" * 200 + "1" + ")" * 200)
+
+
+def test_rule_does_not_mutate_state():
+    engine = _engine()
+    engine.eval_rule("domain.order", "items + items")
+    assert len(engine.state.data["domain"]["order"]["items"]) == 2
+
+
+def test_long_rules_and_large_inputs_are_bounded():
+    engine = _engine()
+    with pytest.raises(RuleLimitError, match="nested"):
+        engine.eval_rule("domain.tiers", "_[0]" + " + _[0]" * 100)
+    with pytest.raises(RuleLimitError, match="longer"):
+        engine.eval_rule("domain", "x" + "+x" * 20000)
+    with engine.transaction() as tx:
+        tx.update(data={"big": ["x" * 1000] * 2000})
+    with pytest.raises(RuleLimitError, match="memory"):
+        engine.eval_rule("big", "len(_)", max_memory=10_000)
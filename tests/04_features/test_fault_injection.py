@@ -0,0 +1,96 @@
+import pytest
+from theus_core import ContextError, SchemaViolationError
+
+from theus.contracts import OutboxMsg, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.n"], outputs=["domain.n"])
+def incr(ctx):
+    ctx.domain.n = ctx.domain.n + 1
+
+
+@process(inputs=["domain.tags"], outputs=["domain.tags"])
+def tag(ctx):
+    ctx.domain.tags.append("x")
+
+
+@pytest.mark.asyncio
+async def test_commit_fault_is_retried_by_execute():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    engine.register(incr)
+    engine.inject_fault("commit", times=1)
+    await engine.execute("incr")
+    assert engine.state.data["domain"]["n"] == 1
+    assert engine.fault_stats()["commit"] == {"probability": 1.0, "remaining": 0, "fired": 1}
+
+
+def test_cas_and_schema_faults_leave_state_untouched():
+    engine = TheusEngine()
+    engine.inject_fault("cas", times=1)
+    with pytest.raises(ContextError, match="Injected"):
+        engine.compare_and_swap(engine.state.version, data={"domain": {"a": 1}})
+
+    engine.inject_fault("schema", message="chaos")
+    with pytest.raises(SchemaViolationError, match="chaos"):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"a": 1}})
+    assert engine.state.version == 0
+
+    engine.clear_faults()
+    assert engine.fault_stats() == {}
+    engine.compare_and_swap(engine.state.version, data={"domain": {"a": 1}})
+    assert engine.state.version == 1
+
+
+@pytest.mark.asyncio
+async def test_shadow_fault_rolls_back_process():
+    engine = TheusEngine(context={"domain": {"tags": []}})
+    engine.register(tag)
+    engine.inject_fault("shadow")
+    with pytest.raises(RuntimeError, match="isolation failure"):
+        await engine.execute("tag")
+    assert engine.state.data["domain"]["tags"] == []
+
+
+def test_outbox_fault_counts_as_failed_delivery():
+    engine = TheusEngine()
+    delivered = []
+    engine.attach_worker(lambda msg: delivered.append(msg.topic))
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("a", 1))
+
+    engine.inject_fault("outbox", times=1)
+    with pytest.raises(RuntimeError, match="Injected"):
+        engine.process_outbox()
+    assert delivered == [] and engine.outbox.stats()["failed"] == 1
+
+    engine.process_outbox()
+    assert delivered == ["a"]
+
+
+def test_probability_is_seedable_and_validated():
+    def fired(seed):
+        engine = TheusEngine()
+        engine.set_deterministic(seed)
+        try:
+            engine.inject_fault("cas", probability=0.5)
+            out = []
+            for _ in range(16):
+                try:
+                    engine.compare_and_swap(engine.state.version, data={"domain": {"a": 1}})
+                    out.append(False)
+                except ContextError:
+                    out.append(True)
+            return out
+        finally:
+            engine.set_deterministic(None)
+
+    assert fired(3) == fired(3)
+    assert 0 < sum(fired(3)) < 16
+
+    engine = TheusEngine()
+    with pytest.raises(ValueError, match="Unknown fault point"):
+        engine.inject_fault("disk")
+    with pytest.raises(ValueError):
+        engine.inject_fault("cas", probability=1.5)
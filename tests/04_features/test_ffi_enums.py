@@ -0,0 +1,52 @@
+import pytest
+import theus_core
+from theus_core import Capability, DeltaOp, SupervisorProxy
+
+from theus.engine import TheusEngine
+
+
+def test_capability_flags_behave_like_masks():
+    rw = Capability.READ | Capability.UPDATE
+    assert int(rw) == 5 and rw == 5 and rw == Capability(5)
+    assert rw.names == ["READ", "UPDATE"]
+    assert repr(rw) == "Capability.READ|UPDATE"
+    assert Capability.READ in rw and Capability.DELETE not in rw
+    assert (rw & Capability.READ) == Capability.READ
+    assert (2 | Capability.READ) == 3
+    assert ~Capability.NONE == Capability.ALL == 31
+    assert not Capability.NONE
+    assert {rw: "x"}[Capability(5)] == "x"
+    with pytest.raises(ValueError):
+        Capability(64)
+
+
+def test_capability_accepted_where_ints_are():
+    proxy = SupervisorProxy({"limits": {"max": 5}}, "domain", capabilities=Capability.READ)
+    assert proxy.capabilities == 1
+    proxy._set_capabilities(Capability.READ | Capability.UPDATE)
+    assert proxy.capabilities == 5
+
+    theus_core.register_physics_override("domain.limits", Capability.NONE)
+    try:
+        assert proxy.limits is None
+    finally:
+        theus_core.clear_physics_overrides()
+    assert proxy.limits.max == 5
+
+
+def test_delta_op_matches_string_codes():
+    assert DeltaOp.SET == "SET" and "SET_ITEM" == DeltaOp.SET_ITEM
+    assert DeltaOp.SET != DeltaOp.APPEND and DeltaOp.SET == DeltaOp.SET
+    assert str(DeltaOp.TENSOR_MUTATION) == "TENSOR_MUTATION" and DeltaOp.POP.code == "POP"
+    assert DeltaOp.SET in {"SET"} and "SET" in {DeltaOp.SET}
+
+
+def test_delta_op_accepted_where_op_strings_are():
+    engine = TheusEngine(context={"domain": {"counter": 0}})
+    with engine._core.transaction() as tx:
+        tx.log_internal("domain.counter", DeltaOp.SET, 1, 0, None, None)
+        shadow = tx.get_shadow(engine._core.state.data["domain"], "domain")
+        shadow["counter"] = 1
+    assert [e.op for e in tx.deltas] == [DeltaOp.SET]
+    with pytest.raises(TypeError):
+        tx.log_internal("domain.counter", 3, 1, 0, None, None)
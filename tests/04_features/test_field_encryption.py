@@ -0,0 +1,80 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import DecryptionError
+
+KEYS = {"k1": b"\x01" * 32, "k2": b"\x02" * 32}
+
+
+@process(inputs=["domain.users"], outputs=["domain.users"])
+def set_token(ctx, token="s3cret"):
+    ctx.domain.users["ada"]["token"] = token
+    return ctx.domain.users["ada"]["token"]
+
+
+@process(inputs=["domain.users"], outputs=[])
+def read_token(ctx):
+    return ctx.domain.users["ada"]["token"]
+
+
+def _engine(key_id="k1"):
+    engine = TheusEngine(context={"domain": {"users": {"ada": {"name": "Ada", "token": None}}}})
+    engine.register(set_token)
+    engine.register(read_token)
+    engine.encrypt_fields(["domain.users.*.token"], KEYS.__getitem__, key_id=key_id)
+    return engine
+
+
+def test_encrypted_field_is_ciphertext_at_rest_and_plaintext_to_processes():
+    engine = _engine()
+    assert asyncio.run(engine.execute("set_token")) == "s3cret"
+
+    stored = engine.state.data["domain"]["users"]["ada"]["token"]
+    assert stored.startswith("tenc1:k1:") and "s3cret" not in stored
+    assert engine.state.data["domain"]["users"]["ada"]["name"] == "Ada"
+    assert b"s3cret" not in engine.dumps_state()
+
+    # Each write gets a fresh nonce.
+    asyncio.run(engine.execute("set_token"))
+    assert engine.state.data["domain"]["users"]["ada"]["token"] != stored
+
+
+def test_rotated_keys_still_decrypt_and_wrong_keys_fail():
+    engine = _engine()
+    asyncio.run(engine.execute("set_token", token={"v": [1, 2]}))
+    engine.encrypt_fields(["domain.users.*.token"], KEYS.__getitem__, key_id="k2")
+
+    assert asyncio.run(engine.execute("read_token")) == {"v": [1, 2]}
+
+    engine.encrypt_fields(["domain.users.*.token"], lambda key_id: b"\x09" * 32)
+    with pytest.raises(DecryptionError, match="authentication failed"):
+        asyncio.run(engine.execute("read_token"))
+
+    with pytest.raises(ValueError):
+        engine.encrypt_fields(["domain.users.*.token"], lambda key_id: b"short")
+        asyncio.run(engine.execute("set_token"))
+
+
+def test_update_payloads_are_sealed_and_ciphertext_is_bound_to_its_path():
+    engine = _engine()
+    payload = {"domain": {"users": {"ada": {"token": "tenc1:looks-encrypted"}, "bob": {"token": "b0b"}}}}
+    with engine.transaction() as tx:
+        tx.update(data=payload)
+        tx.update_many([("domain.users.cy.token", "cy")])
+    # The caller's payload is not rewritten in place.
+    assert payload["domain"]["users"]["bob"]["token"] == "b0b"
+
+    users = engine.state.data["domain"]["users"]
+    assert all(users[u]["token"].startswith("tenc1:k1:") for u in ("ada", "bob", "cy"))
+    # Plaintext that merely starts with the prefix is encrypted, not trusted.
+    assert asyncio.run(engine.execute("read_token")) == "tenc1:looks-encrypted"
+
+    # Bob's ciphertext copied onto Ada's field does not authenticate there, so it is stored
+    # as an opaque string rather than read back as Bob's token.
+    bob = users["bob"]["token"]
+    with engine.transaction() as tx:
+        tx.update_many([("domain.users.ada.token", bob)])
+    assert asyncio.run(engine.execute("read_token")) == bob
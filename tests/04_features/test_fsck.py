@@ -0,0 +1,72 @@
+import hashlib
+
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+from theus_core.testing import Harness
+
+
+def _issues(report, check):
+    return [i for i in report["issues"] if i["check"] == check]
+
+
+def test_fsck_reports_then_repairs_outbox_stamps_and_blob_registry(tmp_path):
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    engine.set_blob_dir(str(tmp_path))
+    clean = engine.fsck()
+    assert clean["ok"] and clean["issues"] == [] and set(clean["checks"]) == {
+        "shadows", "key_last_modified", "outbox", "audit", "registry",
+    }
+
+    # Stamps left behind by a zone that is no longer a dict.
+    with engine.transaction() as tx:
+        tx.update(data={"domain": None})
+    # Outbox entries that would never be delivered again.
+    engine.mark_processed("sent")
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("mail", 1, idempotency_key="sent"))
+        tx.outbox.add(OutboxMsg("mail", 2, idempotency_key="k"))
+        tx.outbox.add(OutboxMsg("mail", 3, idempotency_key="k"))
+    # Blob registry out of step with its directory.
+    lost = engine.blobs.put(b"lost")
+    (tmp_path / lost.split(":")[1]).unlink()
+    (tmp_path / hashlib.sha256(b"stray").hexdigest()).write_bytes(b"stray")
+
+    report = engine.fsck()
+    assert not report["ok"] and report["repaired"] == 0
+    assert report["checks"] == {"shadows": 0, "key_last_modified": 1, "outbox": 2, "audit": 0, "registry": 2}
+    assert [i["detail"] for i in _issues(report, "outbox")] == [
+        "'mail' (sent) already delivered", "'mail' (k) queued twice",
+    ]
+    assert any("file is missing" in i["detail"] and i["severity"] == "error" for i in _issues(report, "registry"))
+    assert all(i["repairable"] and not i["repaired"] for i in report["issues"])
+
+    with Harness(engine=engine) as h:
+        repaired = engine.fsck(repair=True)
+        assert repaired["ok"] and repaired["repaired"] == 5
+        (event,) = h.audit_events("FSCK_REPAIR")
+        assert "repaired 5 issue(s)" in event.message
+
+    assert [m["idempotency_key"] for m in engine.outbox_snapshot()["pending"]] == ["k"]
+    assert engine.blob_stats()["blobs"] == 1 and lost not in engine.blobs
+    assert engine.fsck() == {**clean, "version": engine.state.version}
+
+
+def test_stamps_of_deleted_keys_are_kept_while_a_transaction_is_open():
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    with engine.transaction() as tx:
+        tx.update(data={"domain": None})
+
+    with engine.transaction() as open_tx:
+        open_tx.update(data={"other": {"b": 1}})
+        report = engine.fsck(repair=True)
+        (issue,) = _issues(report, "key_last_modified")
+        assert issue == {
+            "check": "key_last_modified", "severity": "warning",
+            "detail": "stamp for missing key 'domain.a'", "repairable": False, "repaired": False,
+        }
+        # Warnings do not fail the check.
+        assert report["ok"] and report["repaired"] == 0
+
+    report = engine.fsck(repair=True)
+    assert report["repaired"] == 1
+    assert engine.fsck()["issues"] == []
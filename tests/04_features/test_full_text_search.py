@@ -0,0 +1,53 @@
+from theus.engine import TheusEngine
+
+
+LONG = "Checked the node: " + "lorem ipsum " * 10 + "the DISK is full on db-7, paging on-call. " + "dolor " * 10
+
+
+def _engine():
+    return TheusEngine(context={
+        "domain": {
+            "tickets": {
+                "t1": {"title": "Disk full", "body": LONG, "_internal": "disk"},
+                "t2": {"title": "Login broken", "body": "SSO returns 500", "tags": ["auth", "disk"]},
+            },
+            "other": "disk",
+        },
+    })
+
+
+def test_search_finds_terms_with_snippets_and_versions():
+    engine = _engine()
+    engine.enable_search(["domain.tickets.*"])
+    version = engine.state.version
+
+    hits = engine.search("disk")
+    assert [(p, v) for p, _, v in hits] == [
+        ("domain.tickets.t1.body", version),
+        ("domain.tickets.t1.title", version),
+        ("domain.tickets.t2.tags[1]", version),
+    ]
+    body_snippet = hits[0][1]
+    assert "DISK is full" in body_snippet and body_snippet.startswith("…") and len(body_snippet) <= 62
+    assert [p for p, _, _ in engine.search("full DB")] == ["domain.tickets.t1.body"]
+    assert engine.search("disk", limit=1) == hits[:1]
+    assert engine.search("") == [] and engine.search("nothing") == []
+    assert engine.search_stats()["patterns"] == ["domain.tickets.*"]
+
+
+def test_index_follows_commits():
+    engine = _engine()
+    engine.enable_search(["domain.tickets.*"])
+    before = engine.state.version
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"tickets": {"t2": {"title": "Login fixed", "tags": ["auth"]}, "t3": {"title": "Disk alarm"}}}})
+    version = engine.state.version
+    assert ("domain.tickets.t3.title", "Disk alarm", version) in engine.search("disk")
+    assert [p for p, _, _ in engine.search("login")] == ["domain.tickets.t2.title"]
+    assert engine.search("broken") == []
+    assert "domain.tickets.t2.tags[1]" not in [p for p, _, _ in engine.search("disk")]
+    # Untouched tickets keep the version they were indexed at.
+    assert engine.search("paging")[0][2] == before
+
+    engine.enable_search([])
+    assert engine.search("disk") == []
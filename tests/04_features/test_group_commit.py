@@ -0,0 +1,74 @@
+import pytest
+from pydantic import BaseModel
+
+from theus.engine import TheusEngine
+from theus_core import ContextError
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 0, "b": 0, "c": 0, "d": 0}})
+
+
+def test_batch_commits_as_one_version_with_per_tx_status():
+    engine = _engine()
+    start = engine.state.version
+    txs = []
+    with engine.group_commit() as group:
+        for key in ("a", "b", "c"):
+            with engine.transaction() as tx:
+                tx.update(data={"domain": {key: 1}})
+            txs.append(tx)
+            assert tx.commit_status == "staged"
+        assert group.pending == 3
+        assert engine.state.version == start
+
+    assert engine.state.version == start + 1
+    assert engine.state.domain["a"] == engine.state.domain["c"] == 1
+    assert [t.commit_status for t in txs] == ["committed"] * 3
+    assert {r["version"] for r in group.results} == {start + 1}
+    assert [r["tx_id"] for r in group.results] == [t.id for t in txs]
+
+
+def test_max_batch_flushes_and_failures_stay_per_transaction():
+    class Domain(BaseModel):
+        d: int
+
+    class Schema(BaseModel):
+        domain: Domain
+
+    engine = _engine()
+    start = engine.state.version
+    with engine.group_commit(max_batch=2) as group:
+        with engine.transaction() as first:
+            first.update(data={"domain": {"a": 1}})
+        with engine.transaction() as second:
+            second.update(data={"domain": {"b": 1}})
+        assert group.pending == 0 and engine.state.version == start + 1
+
+        engine.set_schema(Schema)
+        with engine.transaction() as good:
+            good.update(data={"domain": {"c": 1}})
+        with engine.transaction() as bad:
+            bad.update(data={"domain": {"d": "not a number"}})
+
+    assert good.commit_status == "committed" and good.committed_version == start + 2
+    assert bad.commit_status == "failed" and "Schema Violation" in bad.commit_error
+    assert [r["status"] for r in group.results] == ["committed", "committed", "committed", "failed"]
+    assert engine.state.domain["c"] == 1
+
+
+def test_overlapping_write_flushes_and_conflicts():
+    engine = _engine()
+    start = engine.state.version
+    with engine.group_commit():
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"a": 1}})
+        with pytest.raises(ContextError, match="CAS Version Mismatch"):
+            with engine.transaction() as tx:
+                tx.update(data={"domain": {"a": 2}})
+        assert engine.state.version == start + 1
+        with pytest.raises(ContextError, match="already open"):
+            with engine.group_commit():
+                pass
+
+    assert engine.state.domain["a"] == 1
@@ -0,0 +1,40 @@
+import pytest
+import theus_core
+from theus_core import ContextError
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.users", "domain.n"], outputs=["domain.users", "domain.n"])
+def review(ctx):
+    seen["plan"] = ctx.plan(lambda c: c.domain.users.clear())
+    seen["during"] = list(ctx.domain.users)
+    seen["plan_set"] = ctx.plan(lambda c: setattr(c.domain, "secret", 1))
+    ctx.domain.n = 2
+
+
+@pytest.mark.asyncio
+async def test_plan_reports_deltas_without_committing():
+    engine = TheusEngine(context={"domain": {"users": ["ann", "bob"], "n": 1, "secret": 0}})
+    engine.register(review)
+    await engine.execute("review")
+
+    cleared, parent = seen["plan"]
+    assert (cleared["path"], cleared["op"], cleared["value"]) == ("domain.users", "SET", [])
+    assert parent["path"] == "domain" and parent["old_value"]["users"] == ["ann", "bob"]
+    assert seen["during"] == ["ann", "bob"]
+    assert {k: seen["plan_set"][0][k] for k in ("path", "value", "old_value")} == {
+        "path": "domain.secret", "value": 1, "old_value": 0,
+    }
+
+    # The dry run left the real transaction intact: its own write still commits.
+    assert engine.state.data["domain"] == {"users": ["ann", "bob"], "n": 2, "secret": 0}
+
+
+def test_plan_needs_a_transaction():
+    guard = theus_core.ContextGuard({"a": 1}, ["a"], ["a"])
+    with pytest.raises(ContextError, match="transaction-bound"):
+        guard.plan(lambda c: None)
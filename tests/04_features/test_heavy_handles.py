@@ -0,0 +1,44 @@
+import gc
+
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def test_handle_finalizes_after_last_reference():
+    engine = TheusEngine()
+    freed = []
+    handle = engine.alloc_heavy("frames", bytearray(64), nbytes=64, on_release=freed.append)
+    with engine.transaction() as tx:
+        tx.update(heavy={"frames": handle})
+    assert engine.heavy_usage() == {"used_bytes": 64, "quota_bytes": None, "handles": 1}
+
+    reader = engine.transaction()
+    reader.__enter__()  # pins the committed handle
+    assert handle.refcount == 2
+
+    handle.release()
+    handle.release()  # idempotent
+    assert freed == [] and handle.get() == bytearray(64)
+    reader.__exit__(None, None, None)
+
+    assert freed == [bytearray(64)] and handle.refcount == 0
+    with pytest.raises(ValueError, match="released"):
+        handle.get()
+    assert engine.heavy_usage()["used_bytes"] == 0
+
+
+def test_quota_and_garbage_collected_handles():
+    engine = TheusEngine()
+    engine.set_heavy_quota(100)
+    freed = []
+    with engine.alloc_heavy("a", b"x", nbytes=60, on_release=freed.append) as a:
+        extra = a.acquire()
+        with pytest.raises(MemoryError, match="Heavy quota exceeded"):
+            engine.alloc_heavy("b", b"y", nbytes=50)
+    assert freed == [] and extra.refcount == 1
+
+    del extra
+    gc.collect()
+    assert freed == [b"x"]
+    engine.alloc_heavy("b", b"y", nbytes=50).release()
@@ -0,0 +1,44 @@
+from theus.engine import TheusEngine
+from theus_core import OutboxMsg
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"count": 0}})
+
+
+def _bump(engine, op_id):
+    with engine.transaction(op_id=op_id) as tx:
+        tx.update(data={"domain": {"count": engine.state.domain["count"] + 1}})
+        tx.outbox.add(OutboxMsg("billing", {"charge": 1}))
+    return tx
+
+
+def test_retried_op_is_a_noop_reporting_the_original_version():
+    engine = _engine()
+    first = _bump(engine, "job-1")
+    version = engine.state.version
+    assert first.commit_status == "committed" and first.committed_version == version
+    assert engine.committed_op("job-1") == version
+
+    retry = _bump(engine, "job-1")
+    assert retry.commit_status == "duplicate" and retry.committed_version == version
+    assert engine.state.version == version and engine.state.domain["count"] == 1
+    assert len(engine._core.outbox_snapshot()["pending"]) == 1
+
+    _bump(engine, "job-2")
+    assert engine.state.domain["count"] == 2
+    assert engine.committed_op("job-3") is None
+
+
+def test_op_ids_are_bounded_and_travel_with_snapshots():
+    engine = _engine()
+    engine.set_op_id_retention(2)
+    for op in ("a", "b", "c"):
+        _bump(engine, op)
+    assert engine.committed_op("a") is None and engine.committed_op("c") is not None
+
+    restored = TheusEngine(context={"domain": {"count": 0}})
+    restored.load_state(engine.dumps_state())
+    assert restored.committed_op("b") == engine.committed_op("b")
+    assert _bump(restored, "c").commit_status == "duplicate"
+    assert restored.state.domain["count"] == 3
@@ -0,0 +1,84 @@
+import pytest
+
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def _engine_with_log():
+    engine = TheusEngine()
+    seen = []
+
+    def handler(tx, event):
+        seen.append(event["n"])
+        tx.update(data={"domain": {"last": event["n"]}})
+
+    engine.attach_inbox_handler(handler)
+    return engine, seen
+
+
+def test_ingest_commits_and_dedups():
+    engine, seen = _engine_with_log()
+    assert engine.ingest({"n": 1}, dedup_key="evt-1") == 1
+    assert engine.ingest({"n": 1}, dedup_key="evt-1") == 0
+    assert seen == [1]
+    assert engine.state.data["domain"]["last"] == 1
+
+
+def test_ingest_uses_outbox_idempotency_key():
+    engine = TheusEngine()
+    seen = []
+    engine.attach_inbox_handler(lambda tx, msg: seen.append(msg.topic))
+    msg = OutboxMsg("order.created", {}, idempotency_key="o-1")
+    engine.ingest(msg)
+    engine.ingest(msg)
+    assert seen == ["order.created"]
+
+
+def test_failed_handler_is_not_marked_consumed():
+    engine = TheusEngine()
+    attempts = []
+
+    def handler(tx, event):
+        attempts.append(event)
+        if len(attempts) == 1:
+            raise RuntimeError("transient")
+
+    engine.attach_inbox_handler(handler)
+    with pytest.raises(RuntimeError):
+        engine.ingest("e", dedup_key="k")
+    assert engine.ingest("e", dedup_key="k") == 1
+    assert len(attempts) == 2
+
+
+def test_per_key_ordering_parks_early_events():
+    engine, seen = _engine_with_log()
+    assert engine.ingest({"n": 2}, order_key="acct", sequence=2) == 0
+    assert engine.ingest({"n": 3}, order_key="acct", sequence=3) == 0
+    assert seen == []
+    assert engine.ingest({"n": 1}, order_key="acct", sequence=1) == 3
+    assert seen == [1, 2, 3]
+    # Stale redelivery is ignored
+    assert engine.ingest({"n": 2}, order_key="acct", sequence=2) == 0
+    assert engine.state.data["domain"]["last"] == 3
+
+
+def test_failed_parked_event_stays_parked_and_is_retried():
+    engine, seen = _engine_with_log()
+    failures = [2]
+
+    def handler(tx, event):
+        if event["n"] in failures:
+            failures.remove(event["n"])
+            raise RuntimeError("transient")
+        seen.append(event["n"])
+        tx.update(data={"domain": {"last": event["n"]}})
+
+    engine.attach_inbox_handler(handler)
+    assert engine.ingest({"n": 2}, order_key="acct", sequence=2) == 0
+    assert engine.ingest({"n": 3}, order_key="acct", sequence=3) == 0
+    # Event 1 commits; parked event 2 fails and keeps blocking 3.
+    assert engine.ingest({"n": 1}, order_key="acct", sequence=1) == 1
+    assert seen == [1]
+    # The next ingest for the key retries the parked events in order.
+    assert engine.ingest({"n": 4}, order_key="acct", sequence=4) == 3
+    assert seen == [1, 2, 3, 4]
@@ -0,0 +1,60 @@
+import pytest
+
+from theus.engine import TheusEngine
+from theus_core import SerializationError
+
+
+def _engine():
+    users = {f"u{i}": {"score": i} for i in range(200)}
+    return TheusEngine(context={"domain": {"users": users, "cfg": {"mode": "a"}, "tmp": 1}})
+
+
+def _write(engine, **fields):
+    with engine.transaction() as tx:
+        tx.update(data={"domain": fields})
+
+
+def test_incrementals_record_only_changes_and_replay_onto_the_full_snapshot():
+    engine = _engine()
+    full = engine.dumps_state()
+    _write(engine, cfg={"mode": "b"})
+    first = engine.dumps_incremental()
+    _write(engine, cfg={"mode": "c", "level": 2})
+    second = engine.dumps_incremental()
+    assert len(first) < len(full) // 10 and len(second) < len(full) // 10
+
+    restored = TheusEngine()
+    restored.load_snapshot(full, first, second)
+    assert restored.state.version == engine.state.version
+    assert restored.state.data["domain"] == engine.state.data["domain"]
+    assert restored.state.data["domain"]["cfg"] == {"mode": "c", "level": 2}
+
+    # The chain continues from the loaded snapshot.
+    _write(restored, tmp=2)
+    third = restored.dumps_incremental()
+    again = TheusEngine()
+    again.load_snapshot(full, first, second, third)
+    assert again.state.data["domain"]["tmp"] == 2
+
+
+def test_broken_chains_are_rejected():
+    engine = _engine()
+    full = engine.dumps_state()
+    _write(engine, tmp=2)
+    first = engine.dumps_incremental()
+    _write(engine, tmp=3)
+    second = engine.dumps_incremental()
+
+    restored = TheusEngine()
+    with pytest.raises(SerializationError, match="chain broken"):
+        restored.load_snapshot(full, second)
+    with pytest.raises(SerializationError, match="chain broken"):
+        restored.load_snapshot(full, second, first)
+
+    # A later full snapshot is not a valid base for an older incremental.
+    _write(engine, tmp=4)
+    with pytest.raises(SerializationError, match="chain broken"):
+        restored.load_snapshot(engine.dumps_state(), first)
+
+    with pytest.raises(ValueError, match="dumps_state"):
+        TheusEngine().dumps_incremental()
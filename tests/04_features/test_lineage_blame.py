@@ -0,0 +1,44 @@
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.config"], outputs=["domain.config"])
+def tune(ctx):
+    ctx.domain.config["retries"] = 5
+
+
+@process(inputs=["domain.config", "domain.n"], outputs=["domain.config", "domain.n"])
+def bump(ctx):
+    ctx.domain.n = ctx.domain.n + 1
+
+
+@pytest.mark.asyncio
+async def test_blame_reports_last_writers_per_path():
+    engine = TheusEngine(context={"domain": {"config": {"retries": 1}, "n": 0}})
+    engine.register(tune)
+    engine.register(bump)
+    await engine.execute("tune")
+    await engine.execute("bump")
+
+    # bump declared domain.config as an output but left it unchanged: not a writer.
+    last = engine.state.last_writer("domain.config.retries")
+    assert last["actor"] == "tune" and last["tx"] is not None
+    assert engine.state.last_writer("domain.n")["actor"] == "bump"
+    assert engine.state.last_writer("domain.unknown") is None
+
+    # The oldest entry is the engine's initial context load.
+    history = engine.blame("domain.config")
+    assert [(h["actor"], h["paths"]) for h in history] == [("tune", ["domain.config"]), (None, ["domain.config"])]
+    assert [h["actor"] for h in engine.blame("domain")] == ["bump", "tune", None]
+    assert engine.blame("domain", depth=1)[0]["paths"] == ["domain.n"]
+
+
+def test_cas_writes_are_attributed_to_the_requester():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    engine.set_lineage_retention(2)
+    for i in range(1, 4):
+        engine.compare_and_swap(engine.state.version, data={"domain": {"n": i}}, requester=f"w{i}")
+    assert [h["actor"] for h in engine.blame("domain.n", depth=5)] == ["w3", "w2"]
+    assert engine.state.last_writer("domain.n") == {"version": engine.state.version, "tx": None, "actor": "w3"}
@@ -0,0 +1,85 @@
+import asyncio
+
+import pytest
+from theus_core import LockPoisonedError, lock_recoveries
+from theus_core.testing import Harness
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.n"], outputs=["domain.n"])
+def incr(ctx):
+    ctx.domain.n = ctx.domain.n + 1
+
+
+def _panic_commit(engine):
+    engine.inject_fault("panic", times=1)
+    with pytest.raises(BaseException, match="Injected panic") as err:
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": 100}})
+    assert type(err.value).__name__ == "PanicException"
+
+
+def test_panic_under_lock_is_recovered_and_engine_keeps_working():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    engine.register(incr)
+    lock_recoveries(reset=True)
+    with Harness(engine=engine) as h:
+        _panic_commit(engine)
+        assert engine.state.data["domain"]["n"] == 0
+
+        # The next commit takes the poisoned history lock: recovered, not a second panic.
+        asyncio.run(engine.execute("incr"))
+        asyncio.run(engine.execute("incr"))
+        assert engine.state.data["domain"]["n"] == 2
+        assert "retention" in engine.pinned_versions()
+
+        (recovery,) = lock_recoveries()
+        assert recovery["action"] == "kept" and "engine.rs" in recovery["site"]
+        (event,) = h.audit_events("LOCK_POISONED")
+        assert recovery["site"] in event.message
+
+    assert lock_recoveries(reset=True) and lock_recoveries() == []
+
+
+def test_transactions_open_across_a_recovery_refuse_to_commit():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    lock_recoveries(reset=True)
+
+    bystander = engine._core.transaction()
+    bystander.__enter__()
+    bystander.update(data={"domain": {"n": 7}})
+
+    _panic_commit(engine)
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 1}})
+
+    with pytest.raises(LockPoisonedError) as err:
+        bystander.__exit__(None, None, None)
+    assert err.value.code == "TH801" and len(err.value.sites) == 1
+    assert bystander.commit_status == "failed"
+    assert engine.state.data["domain"]["n"] == 1
+
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 2}})
+    assert engine.state.data["domain"]["n"] == 2
+
+
+def test_recovery_only_fails_transactions_of_the_same_engine():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    other = TheusEngine(context={"domain": {"n": 0}})
+    lock_recoveries(reset=True)
+
+    bystander = other._core.transaction()
+    bystander.__enter__()
+    bystander.update(data={"domain": {"n": 7}})
+
+    _panic_commit(engine)
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 1}})
+    assert len(lock_recoveries()) == 1
+
+    bystander.__exit__(None, None, None)
+    assert bystander.commit_status == "committed"
+    assert other._core.state.data["domain"]["n"] == 7
@@ -0,0 +1,45 @@
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "meta_flags": {"beta": False}}, "meta": {"rate": 1}})
+
+
+def test_meta_commits_bump_epoch_and_notify():
+    engine = _engine()
+    seen = []
+
+    @engine.on_meta_change
+    def refresh(paths, epoch):
+        seen.append((paths, epoch, engine._core.state.data["meta"]["rate"]))
+
+    start = engine.meta_epoch
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"a": 2}})
+    assert engine.meta_epoch == start and seen == []
+
+    with engine.transaction() as tx:
+        tx.update(data={"meta": {"rate": 5}})
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"meta_flags": {"beta": True}}})
+    # Listeners run after the commit, so they already see the new value.
+    assert seen == [(["meta.rate"], start + 1, 5), (["domain.meta_flags"], start + 2, 5)]
+    assert engine.meta_epoch == start + 2
+
+    # Rewriting the same value is not a change.
+    with engine.transaction() as tx:
+        tx.update(data={"meta": {"rate": 5}})
+    assert engine.meta_epoch == start + 2
+
+    assert engine.remove_meta_listener(refresh)
+    assert not engine.remove_meta_listener(refresh)
+    engine._core.compare_and_swap(engine._core.state.version, data={"meta": {"rate": 6}})
+    assert engine.meta_epoch == start + 3 and len(seen) == 2
+
+
+def test_listener_errors_do_not_fail_the_commit():
+    engine = _engine()
+    engine.on_meta_change(lambda paths, epoch: 1 / 0)
+    with engine.transaction() as tx:
+        tx.update(data={"meta": {"rate": 2}})
+    assert engine._core.state.data["meta"]["rate"] == 2
@@ -0,0 +1,66 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import MiddlewareVetoError
+
+
+@process(inputs=["domain.user"], outputs=["domain.user"])
+def rename(ctx, name="  Ada  "):
+    ctx.domain.user["name"] = name
+    return ctx.domain.user["name"]
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"user": {"name": "bob", "age": 3}}})
+    engine.register(rename)
+    return engine
+
+
+def test_middlewares_transform_writes_in_priority_order_and_reads_in_reverse():
+    engine = _engine()
+    calls = []
+
+    def strip(path, value, op):
+        calls.append(("strip", op))
+        return value.strip() if isinstance(value, str) else value
+
+    def upper(path, value, op):
+        calls.append(("upper", op))
+        if op == "set":
+            return value.upper()
+        return value.lower()
+
+    engine.add_middleware("upper", upper, priority=10, paths=["domain.user.name"])
+    engine.add_middleware("strip", strip, paths=["domain.*.name"])
+    assert engine.middlewares() == ["strip", "upper"]
+
+    assert asyncio.run(engine.execute("rename")) == "ada"
+    assert engine.state.data["domain"]["user"]["name"] == "ADA"
+    assert calls == [("strip", "set"), ("upper", "set"), ("upper", "get"), ("strip", "get")]
+
+    stats = engine.middleware_stats(reset=True)
+    assert stats["strip"]["calls"] == 2 and stats["upper"]["calls"] == 2
+    assert engine.middleware_stats()["strip"]["calls"] == 0
+
+
+def test_middleware_veto_rejects_the_write():
+    engine = _engine()
+
+    def no_blank(path, value, op):
+        if op == "set" and not str(value).strip():
+            raise MiddlewareVetoError(f"blank value for {path}")
+        return value
+
+    engine.add_middleware("no_blank", no_blank, ops=["set"])
+    with pytest.raises(MiddlewareVetoError, match="blank value"):
+        asyncio.run(engine.execute("rename", name="   "))
+    assert engine.state.data["domain"]["user"]["name"] == "bob"
+    assert engine.middleware_stats()["no_blank"]["vetoes"] == 1
+
+    assert engine.remove_middleware("no_blank")
+    assert not engine.remove_middleware("no_blank")
+    with pytest.raises(ValueError):
+        engine.add_middleware("bad", no_blank, ops=["delete"])
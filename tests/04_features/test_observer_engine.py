@@ -0,0 +1,47 @@
+import pickle
+
+import pytest
+
+from theus.engine import TheusEngine
+from theus_core import ObserverEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"cfg": {"mode": "a", "internal_secret": "s3"}, "items": [1, 2]}})
+
+
+def test_observer_reads_redacted_copies():
+    engine = _engine()
+    obs = engine.observer()
+    assert isinstance(obs, ObserverEngine)
+    assert obs.read("domain.cfg") == {"mode": "a"}
+    assert obs.read("domain.cfg.internal_secret", default="hidden") == "hidden"
+    assert "internal_secret" not in obs.snapshot()["domain"]["cfg"]
+
+    # Copies: mutating what a plugin got never reaches committed state.
+    obs.read("domain.items").append(3)
+    assert engine.state.data["domain"]["items"] == [1, 2]
+
+
+def test_observer_follows_commits_and_history():
+    engine = _engine()
+    engine.set_version_retention(5)
+    obs = engine.observer()
+    before = obs.version
+    with engine.transaction(actor="ops") as tx:
+        tx.update(data={"domain": {"cfg": {"mode": "b", "internal_secret": "s4"}}})
+    assert obs.version == before + 1
+    assert obs.read("domain.cfg.mode") == "b"
+    assert obs.read("domain.cfg.mode", version=before) == "a"
+    entry = obs.history("domain.cfg")[0]
+    assert entry["actor"] == "ops"
+    assert all("internal_" not in p for p in entry["paths"])
+    assert obs.history("domain.cfg.internal_secret") == []
+
+
+def test_observer_has_no_write_paths():
+    obs = _engine().observer()
+    for name in ("transaction", "compare_and_swap", "execute", "state", "_core", "engine"):
+        assert not hasattr(obs, name)
+    with pytest.raises(TypeError):
+        pickle.dumps(obs)
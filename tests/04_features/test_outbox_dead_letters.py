@@ -0,0 +1,117 @@
+import pytest
+
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def _engine(fail_topics):
+    engine = TheusEngine()
+    delivered = []
+
+    def worker(msg):
+        if msg.topic in fail_topics:
+            raise RuntimeError(f"cannot deliver {msg.topic}")
+        delivered.append(msg.topic)
+
+    engine.attach_worker(worker)
+    return engine, delivered
+
+
+def test_stats_are_non_destructive():
+    engine, delivered = _engine(set())
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("a", 1))
+        tx.outbox.add(OutboxMsg("b", 2))
+
+    stats = engine.outbox.stats()
+    assert stats["queued"] == 2
+    assert stats["oldest_age_ms"] >= 0
+    assert engine.outbox.len() == 2  # still queued
+
+    engine.process_outbox()
+    stats = engine.outbox.stats()
+    assert stats == {**stats, "queued": 0, "in_flight": 0, "delivered": 2, "failed": 0, "dead": 0}
+    assert stats["oldest_age_ms"] is None
+
+
+def test_exhausted_message_is_dead_lettered_then_requeued():
+    fail = {"bad"}
+    engine, delivered = _engine(fail)
+    engine.set_outbox_max_attempts(2)
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("bad", 1, idempotency_key="bad-1"))
+        tx.outbox.add(OutboxMsg("good", 2))
+
+    for _ in range(2):
+        with pytest.raises(RuntimeError):
+            engine.process_outbox()
+
+    dead = engine.outbox.dead_letters()
+    assert [d.msg.idempotency_key for d in dead] == ["bad-1"]
+    assert dead[0].msg.attempts == 2
+    assert "cannot deliver bad" in dead[0].error
+    assert engine.outbox.stats()["failed"] == 2
+
+    engine.process_outbox()
+    assert delivered == ["good"]
+
+    fail.clear()
+    assert engine.outbox.requeue_dead_letters(["bad-1"]) == 1
+    engine.process_outbox()
+    assert delivered == ["good", "bad"]
+    assert engine.outbox.dead_letters() == []
+
+
+def test_purge_dead_letters():
+    engine, _ = _engine({"bad"})
+    engine.set_outbox_max_attempts(1)
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("bad", 1))
+    with pytest.raises(RuntimeError):
+        engine.process_outbox()
+    assert engine.outbox.purge_dead_letters() == 1
+    assert engine.outbox.stats()["dead"] == 0
+
+
+def test_dead_letter_holds_its_ordering_key_until_resolved():
+    fail = {"debit"}
+    engine, delivered = _engine(fail)
+    engine.set_outbox_max_attempts(1)
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("debit", 1, idempotency_key="debit-1", ordering_key="acct-1"))
+        tx.outbox.add(OutboxMsg("credit", 2, ordering_key="acct-1"))
+        tx.outbox.add(OutboxMsg("other", 3, ordering_key="acct-2"))
+
+    with pytest.raises(RuntimeError):
+        engine.process_outbox()
+    engine.process_outbox()
+    assert delivered == ["other"]
+    assert engine.outbox.stats()["queued"] == 1
+
+    # Later messages with the blocked key wait too.
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("close", 4, ordering_key="acct-1"))
+    engine.process_outbox()
+    assert delivered == ["other"]
+
+    fail.clear()
+    assert engine.outbox.requeue_dead_letters(["debit-1"]) == 1
+    engine.process_outbox()
+    assert delivered == ["other", "debit", "credit", "close"]
+
+
+def test_purging_a_dead_letter_releases_its_ordering_key():
+    engine, delivered = _engine({"debit"})
+    engine.set_outbox_max_attempts(1)
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("debit", 1, ordering_key="acct-1"))
+        tx.outbox.add(OutboxMsg("credit", 2, ordering_key="acct-1"))
+
+    with pytest.raises(RuntimeError):
+        engine.process_outbox()
+    engine.process_outbox()
+    assert delivered == []
+
+    assert engine.outbox.purge_dead_letters() == 1
+    engine.process_outbox()
+    assert delivered == ["credit"]
@@ -0,0 +1,68 @@
+import pytest
+
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def test_messages_get_idempotency_keys():
+    auto = OutboxMsg("t", 1)
+    assert auto.idempotency_key
+    assert OutboxMsg("t", 1).idempotency_key != auto.idempotency_key
+    assert OutboxMsg("t", 1, idempotency_key="order-7").idempotency_key == "order-7"
+
+
+def test_redelivered_message_is_skipped():
+    engine = TheusEngine()
+    received = []
+    engine.attach_worker(lambda m: received.append(m.idempotency_key))
+
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("mail", {"to": "a"}, idempotency_key="k1"))
+    engine.process_outbox()
+
+    # Same message redelivered (e.g. replayed after crash)
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("mail", {"to": "a"}, idempotency_key="k1"))
+    engine.process_outbox()
+
+    assert received == ["k1"]
+    assert engine.is_processed("k1")
+
+
+def test_failed_delivery_is_requeued_and_survives_snapshot():
+    engine = TheusEngine()
+    calls = []
+
+    def flaky(msg):
+        calls.append(msg.idempotency_key)
+        if msg.idempotency_key == "k2" and calls.count("k2") == 1:
+            raise RuntimeError("broker down")
+
+    engine.attach_worker(flaky)
+    with engine.transaction() as tx:
+        for key in ("k1", "k2", "k3"):
+            tx.outbox.add(OutboxMsg("mail", key, idempotency_key=key))
+
+    with pytest.raises(RuntimeError):
+        engine.process_outbox()
+
+    # Simulate restart: persist queue + processed window, load into a fresh engine
+    snap = engine.outbox_snapshot()
+    assert [m["idempotency_key"] for m in snap["pending"]] == ["k2", "k3"]
+    assert [k for k, _ in snap["processed"]] == ["k1"]
+
+    restarted = TheusEngine()
+    restarted.attach_worker(flaky)
+    restarted.restore_outbox(snap)
+    restarted.process_outbox()
+    assert calls == ["k1", "k2", "k2", "k3"]
+
+
+def test_retention_window_expires_keys():
+    engine = TheusEngine()
+    engine.set_outbox_retention(60_000, max_entries=2)
+    for key in ("a", "b", "c"):
+        assert engine.mark_processed(key)
+    assert not engine.mark_processed("c")
+    assert not engine.is_processed("a")
+    assert engine.is_processed("b") and engine.is_processed("c")
@@ -0,0 +1,37 @@
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def _deliver(msgs):
+    engine = TheusEngine()
+    out = []
+    engine.attach_worker(lambda m: out.append(m.topic))
+    with engine.transaction() as tx:
+        for m in msgs:
+            tx.outbox.add(m)
+    engine.process_outbox()
+    return out
+
+
+def test_default_is_fifo():
+    assert _deliver([OutboxMsg(t, None) for t in "abc"]) == ["a", "b", "c"]
+
+
+def test_urgent_jumps_ahead():
+    out = _deliver([
+        OutboxMsg("report", None),
+        OutboxMsg("email", None),
+        OutboxMsg("alert", None, priority=10),
+    ])
+    assert out == ["alert", "report", "email"]
+
+
+def test_ordering_key_is_never_overtaken():
+    out = _deliver([
+        OutboxMsg("acct.open", None, ordering_key="acct-1"),
+        OutboxMsg("other", None),
+        OutboxMsg("acct.freeze", None, priority=10, ordering_key="acct-1"),
+        OutboxMsg("alert", None, priority=5),
+    ])
+    # The urgent freeze promotes its predecessor instead of overtaking it.
+    assert out == ["acct.open", "alert", "acct.freeze", "other"]
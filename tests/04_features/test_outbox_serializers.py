@@ -0,0 +1,59 @@
+import pytest
+
+import theus_core
+from theus.contracts import OutboxMsg
+from theus.engine import TheusEngine
+
+
+def test_schema_tag_survives_commit_to_worker():
+    engine = TheusEngine()
+    received = []
+    engine.attach_worker(received.append)
+
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("orders", {"id": 7}, schema="OrderCreated.v1"))
+
+    engine.process_outbox()
+    assert received[0].schema == "OrderCreated.v1"
+    assert received[0].content_type is None
+
+
+def test_default_json_roundtrip():
+    msg = OutboxMsg("orders", {"id": 7})
+    wire = msg.encode()
+    assert wire == b'{"id": 7}'
+    assert OutboxMsg.decode("orders", wire).payload == {"id": 7}
+
+
+def test_msgpack_roundtrip_is_native():
+    # No Python msgpack module is needed: the crate's own codec handles it.
+    msg = OutboxMsg("orders", {"id": 7, "tags": ["a"], "at": (1, 2)}, content_type="msgpack")
+    wire = msg.encode()
+    assert isinstance(wire, bytes)
+    assert OutboxMsg.decode("orders", wire, content_type="msgpack").payload == {"id": 7, "tags": ["a"], "at": (1, 2)}
+
+
+def test_pickle_requires_opt_in():
+    msg = OutboxMsg("orders", {1, 2}, content_type="pickle")
+    with pytest.raises(theus_core.SerializationError):
+        msg.encode()
+
+    theus_core.allow_pickle_serializer(True)
+    try:
+        wire = msg.encode()
+        assert OutboxMsg.decode("orders", wire, content_type="pickle").payload == {1, 2}
+    finally:
+        theus_core.allow_pickle_serializer(False)
+    assert "pickle" not in theus_core.list_serializers()
+
+
+def test_user_serializer():
+    theus_core.register_serializer("csv", lambda p: ",".join(p).encode(), lambda b: b.decode().split(","))
+    try:
+        msg = OutboxMsg("rows", ["a", "b"], schema="Row", content_type="csv")
+        assert msg.encode() == b"a,b"
+        back = OutboxMsg.decode("rows", b"x,y", schema="Row", content_type="csv")
+        assert back.payload == ["x", "y"]
+        assert back.schema == "Row"
+    finally:
+        assert theus_core.unregister_serializer("csv")
@@ -0,0 +1,45 @@
+from theus.engine import TheusEngine
+
+
+class Box:
+    def __init__(self, v):
+        self.v = v
+
+    def __eq__(self, other):
+        return isinstance(other, Box) and self.v == other.v
+
+    def __deepcopy__(self, memo):
+        return Box(self.v)
+
+
+def _engine():
+    users = {f"u{i}": {"score": i, "tags": ["a", "b"], "meta": {"x": 1.0, "y": None}} for i in range(200)}
+    return TheusEngine(context={"domain": {"users": users, "boxes": {"same": Box(1), "moved": Box(1)}}})
+
+
+def test_only_mutated_shadows_become_deltas():
+    engine = _engine()
+    users = engine._core.state.data["domain"]["users"]
+    with engine._core.transaction() as tx:
+        shadows = {k: tx.get_shadow(v, f"domain.users.{k}") for k, v in users.items()}
+        shadows["u7"]["score"] = 70
+        shadows["u150"]["tags"].append("c")
+        # Value-equal rewrites are not changes: reordered dict, 1.0 -> 1, list -> fresh list.
+        shadows["u9"]["meta"] = {"y": None, "x": 1}
+        shadows["u10"]["tags"] = ["a", "b"]
+        shadows["u11"]["score"] = float(shadows["u11"]["score"])
+        # Same repr but a different type is a change.
+        shadows["u12"]["tags"] = ("a", "b")
+    assert sorted(d.path for d in tx.deltas) == ["domain.users.u12", "domain.users.u150", "domain.users.u7"]
+
+
+def test_custom_eq_and_nan_fall_back_to_python():
+    engine = _engine()
+    data = engine._core.state.data["domain"]
+    with engine._core.transaction() as tx:
+        tx.get_shadow(data["boxes"]["same"], "domain.boxes.same").v = 1
+        tx.get_shadow(data["boxes"]["moved"], "domain.boxes.moved").v = 2
+        tx.get_shadow(data["users"]["u1"], "domain.users.u1")["meta"]["x"] = float("nan")
+        tx.get_shadow(data["users"]["u2"], "domain.users.u2")["big"] = 1 << 80
+        tx.get_shadow(data["users"]["u3"], "domain.users.u3")
+    assert sorted(d.path for d in tx.deltas) == ["domain.boxes.moved", "domain.users.u1", "domain.users.u2"]
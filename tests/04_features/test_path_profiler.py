@@ -0,0 +1,60 @@
+import pytest
+import theus_core
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.orders", "domain.total"], outputs=["domain.total"])
+def tally(ctx):
+    orders = ctx.domain.orders
+    ctx.domain.total = orders[0]["price"] + orders[1]["price"]
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"orders": [{"price": 2}, {"price": 3}], "total": 0}})
+    engine.register(tally)
+    theus_core.reset_profile()
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_disabled_by_default_records_nothing():
+    engine = _engine()
+    theus_core.set_profiling_enabled(False)
+    await engine.execute("tally")
+    assert theus_core.profile_report() == []
+
+
+@pytest.mark.asyncio
+async def test_reads_and_writes_are_counted_per_canonical_path():
+    engine = _engine()
+    theus_core.set_profiling_enabled(True)
+    try:
+        await engine.execute("tally")
+        with engine.transaction() as tx:
+            tx.update(data={"global": {"mode": "x"}})
+        rows = {r["path"]: r for r in theus_core.profile_report()}
+    finally:
+        theus_core.set_profiling_enabled(False)
+
+    assert rows["domain.total"]["writes"] >= 1
+    assert rows["domain.orders"]["reads"] >= 1
+    # List indices collapse into one entry
+    assert rows["domain.orders.*.price"]["reads"] == 2
+    assert rows["global.mode"] == {"path": "global.mode", "reads": 0, "writes": 1, "total": 1}
+
+
+@pytest.mark.asyncio
+async def test_top_n_orders_by_total_and_reset():
+    engine = _engine()
+    theus_core.set_profiling_enabled(True)
+    try:
+        await engine.execute("tally")
+        report = theus_core.profile_report(top_n=2, reset=True)
+    finally:
+        theus_core.set_profiling_enabled(False)
+
+    assert len(report) == 2
+    assert report[0]["total"] >= report[1]["total"]
+    assert theus_core.profile_report() == []
+
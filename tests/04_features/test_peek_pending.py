@@ -0,0 +1,46 @@
+import asyncio
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"cart": {"items": 1, "total": 10}}})
+
+
+def test_peek_pending_shows_provisional_writes_until_exit():
+    engine = _engine()
+    with engine.transaction(actor="editor", provisional=True) as tx:
+        tx.update(data={"domain": {"cart": {"items": 2}}})
+        [row] = engine.peek_pending("domain.cart.items")
+        assert row["provisional"] is True
+        assert (row["tx_id"], row["actor"], row["path"], row["value"]) == (tx.id, "editor", "domain.cart.items", 2)
+        assert engine.peek_pending("domain.cart")[0]["value"] == {"items": 2}
+        assert engine.peek_pending("domain.other") == []
+        # Committed reads are untouched.
+        assert engine.state.data["domain"]["cart"]["items"] == 1
+    assert engine.peek_pending("domain.cart.items") == []
+    assert engine.state.data["domain"]["cart"]["items"] == 2
+
+
+def test_non_provisional_transactions_stay_hidden():
+    engine = _engine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"cart": {"items": 5}}})
+        assert engine.peek_pending("domain.cart") == []
+
+
+def test_process_deltas_are_visible_while_running():
+    engine = _engine()
+    seen = []
+
+    @process(inputs=["domain.cart"], outputs=["domain.cart"])
+    def add_item(ctx):
+        ctx.domain.cart["items"] = 3
+        seen.extend(engine.peek_pending("domain.cart.items"))
+        seen[-1]["value"] = "mutated by reader"
+
+    engine.register(add_item)
+    asyncio.run(engine.execute("add_item", provisional=True))
+    assert [(r["path"], r["op"]) for r in seen] == [("domain.cart.items", "SET")]
+    assert engine.state.data["domain"]["cart"]["items"] == 3
@@ -0,0 +1,91 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core.testing import Harness
+
+GATE = {}
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+async def slow_writer(ctx):
+    await GATE["release"].wait()
+    ctx.domain.a = 2
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"a": 1}})
+    engine.register(slow_writer)
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_health_reports_stuck_run_until_heartbeat():
+    with Harness(start_ms=1000) as h:
+        engine = _engine()
+        engine.set_expected_duration(50, "slow_writer")
+        GATE["release"] = asyncio.Event()
+        task = asyncio.ensure_future(engine.execute("slow_writer"))
+        await asyncio.sleep(0)
+
+        health = engine.health()
+        assert health["status"] == "ok"
+        assert [r["process"] for r in health["running"]] == ["slow_writer"]
+
+        h.advance_ms(80)
+        health = engine.health()
+        assert health["status"] == "degraded" and health["stuck"] == 1
+        assert health["running"][0]["since_heartbeat_ms"] == 80
+
+        assert engine.heartbeat("slow_writer") == 1
+        assert engine.health()["status"] == "ok"
+
+        GATE["release"].set()
+        await task
+        assert engine.health()["running"] == []
+        assert engine.state.data["domain"]["a"] == 2
+
+
+@pytest.mark.asyncio
+async def test_abort_policy_cancels_stuck_transaction():
+    with Harness(start_ms=1000) as h:
+        engine = _engine()
+        engine.set_expected_duration(50)
+        engine.set_stuck_policy("abort", check_interval_ms=60_000)
+        GATE["release"] = asyncio.Event()
+        task = asyncio.ensure_future(engine.execute("slow_writer"))
+        await asyncio.sleep(0)
+
+        h.advance_ms(100)
+        health = engine.health()
+        assert health["policy"] == "abort" and health["aborted_total"] == 1
+        assert health["running"][0]["aborted"] is True
+
+        GATE["release"].set()
+        with pytest.raises(Exception, match="(?i)cancel"):
+            await task
+        assert engine.state.data["domain"]["a"] == 1
+        assert h.audit_events("PROCESS_STUCK")
+
+
+@pytest.mark.asyncio
+async def test_run_is_awaited_inline_and_tracked_only_while_awaited():
+    engine = _engine()
+
+    async def probe(ctx):
+        return [r["process"] for r in engine.health()["running"]], asyncio.current_task()
+
+    awaitable = engine._core.execute_process_async("probe", probe)
+    assert asyncio.iscoroutine(awaitable)
+    assert engine.health()["running"] == []
+    running, task = await awaitable
+    # No extra task: the process runs in the caller's task.
+    assert running == ["probe"] and task is asyncio.current_task()
+    assert engine.health()["running"] == []
+
+
+def test_unknown_policy_is_rejected():
+    with pytest.raises(ValueError, match="Unknown stuck policy"):
+        _engine().set_stuck_policy("explode")
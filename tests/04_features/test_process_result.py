@@ -0,0 +1,49 @@
+import pytest
+
+from theus import ProcessResult
+from theus.contracts import OutboxMsg, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.n", "domain.log"], outputs=["domain.n", "domain.log"])
+def step(ctx):
+    ctx.domain.n = ctx.domain.n + 1
+    ctx.domain.log.append(ctx.domain.n)
+    ctx.outbox.add(OutboxMsg(topic="stepped", payload={"n": ctx.domain.n}))
+
+
+@process(inputs=["domain.n"], outputs=[])
+def peek(ctx):
+    return ctx.domain.n
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"n": 0, "log": []}})
+    engine.register(step)
+    engine.register(peek)
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_execute_result_carries_commit_metadata():
+    engine = _engine()
+    res = await engine.execute_result("step")
+    assert isinstance(res, ProcessResult)
+    assert (res.process, res.value, res.status, res.retries, res.outbox) == ("step", None, "committed", 0, 1)
+    assert res.version == engine.state.version
+    assert {"domain.n", "domain.log"} <= set(res.changed_paths) and res.deltas >= 2
+    assert res.duration_ms > 0
+
+    read = await engine.execute_result("peek")
+    assert (read.value, read.version, read.deltas, read.changed_paths) == (1, None, 0, ())
+    # execute() keeps returning the bare value.
+    assert await engine.execute("peek") == 1
+
+
+@pytest.mark.asyncio
+async def test_execute_result_counts_retries():
+    engine = _engine()
+    engine.inject_fault("commit", times=1)
+    res = await engine.execute_result("step", retries=2)
+    assert res.retries == 1 and res.status == "committed"
+    assert engine.state.data["domain"]["n"] == 1
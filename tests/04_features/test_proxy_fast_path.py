@@ -0,0 +1,29 @@
+import pytest
+import theus_core
+from theus_core import SupervisorProxy
+
+
+def test_fields_methods_and_missing_names():
+    proxy = SupervisorProxy({"counter": 3, "nested": {"x": 1}, "keys": "shadowed"}, "domain")
+    assert proxy.counter == 3
+    assert proxy.nested.x == 1 and proxy.nested.path() == "domain.nested"
+    # Proxy methods win over same-named keys, as before.
+    assert sorted(proxy.keys()) == ["counter", "keys", "nested"]
+    assert proxy["keys"] == "shadowed"
+    assert proxy.is_proxy() and proxy.path() == "domain"
+    with pytest.raises(AttributeError, match="missing"):
+        proxy.missing
+    with pytest.raises(AttributeError):
+        proxy._private
+    assert proxy.__dict__ == {}
+
+
+def test_physics_overrides_apply_to_cached_paths():
+    proxy = SupervisorProxy({"limits": {"max": 5}}, "domain", capabilities=15)
+    assert proxy.limits.max == 5
+    theus_core.register_physics_override("domain.limits", 0)
+    try:
+        assert proxy.limits is None
+    finally:
+        theus_core.clear_physics_overrides()
+    assert proxy.limits.max == 5
@@ -0,0 +1,34 @@
+from theus.engine import TheusEngine
+from theus_core import SupervisorProxy
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"cfg": {"inner": {"x": 1}}, "names": ["a"]}})
+
+
+def test_repeated_access_returns_the_same_proxy():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        domain = SupervisorProxy(engine._core.state.data["domain"], "domain", transaction=tx)
+        assert domain.cfg is domain.cfg
+        assert domain["cfg"] is domain["cfg"]
+        assert domain.cfg.inner is domain.cfg.inner
+        domain.cfg.inner.x = 2
+        domain.names.append("b")
+        assert domain.cfg.inner.x == 2
+    assert engine._core.state.data["domain"]["cfg"]["inner"]["x"] == 2
+    assert engine._core.state.data["domain"]["names"] == ["a", "b"]
+
+
+def test_pool_respects_lens_and_transaction_scope():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        domain = SupervisorProxy(engine._core.state.data["domain"], "domain", transaction=tx)
+        cfg = domain.cfg
+        cfg._set_capabilities(1)
+        assert domain.cfg is not cfg and domain.cfg.capabilities != 1
+    # Closed transaction: no pooling (and the pool was dropped on exit).
+    assert domain.cfg is not domain.cfg
+
+    detached = SupervisorProxy({"cfg": {}}, "domain")
+    assert detached.cfg is not detached.cfg
@@ -0,0 +1,47 @@
+import json
+
+import pytest
+
+from theus.zones import ContextZone
+from theus_core import SupervisorProxy
+
+
+def _proxy():
+    target = {
+        "name": "acme",
+        "meta_stats": {"hits": 3},
+        "internal_secret": "s3cr3t",
+        "_scratch": 1,
+        "sig_ping": True,
+        "orders": [{"id": 1, "internal_cost": 5, "lines": [{"sku": "a"}]}],
+    }
+    return target, SupervisorProxy(target, "domain", True, None)
+
+
+def test_plain_to_dict_is_unchanged():
+    target, proxy = _proxy()
+    assert proxy.to_dict() == target
+
+
+def test_zone_and_private_filters_apply_at_every_level():
+    _, proxy = _proxy()
+    exported = proxy.to_dict(zones=["data"])
+    assert exported == {"name": "acme", "orders": [{"id": 1, "lines": [{"sku": "a"}]}]}
+    json.dumps(exported)
+
+    with_meta = proxy.to_dict(zones=[ContextZone.DATA, ContextZone.META])
+    assert with_meta["meta_stats"] == {"hits": 3} and "sig_ping" not in with_meta
+
+    everything = proxy.to_dict(include_private=True)
+    assert everything["internal_secret"] == "s3cr3t" and everything["_scratch"] == 1
+    assert "internal_secret" not in proxy.to_dict(include_private=False)
+
+
+def test_max_depth_omits_deeper_containers():
+    _, proxy = _proxy()
+    assert proxy.to_dict(max_depth=0) == {"name": "acme", "sig_ping": True}
+    shallow = proxy.to_dict(max_depth=1)
+    assert shallow["meta_stats"] == {"hits": 3} and shallow["orders"] == []
+
+    with pytest.raises(ValueError, match="Unknown zone 'vault'"):
+        proxy.to_dict(zones=["vault"])
@@ -0,0 +1,50 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={
+        "domain": {
+            "orders": {
+                "o1": {"status": "failed", "total": 30, "items": [{"sku": "a"}, {"sku": "b"}]},
+                "o2": {"status": "paid", "total": 10, "items": []},
+                "o3": {"status": "failed", "total": 5, "items": [{"sku": "c"}], "_trace": "x"},
+            },
+        },
+        "internal_secrets": {"orders": {"status": "failed"}},
+    })
+
+
+def test_filters_wildcards_and_recursive_descent():
+    engine = _engine()
+    failed = engine.query("$.domain.orders[?(@.status == 'failed')]", with_paths=True)
+    assert [p for p, _ in failed] == ["domain.orders.o1", "domain.orders.o3"]
+    assert "_trace" not in failed[1][1]
+
+    assert engine.query("domain.orders[?(@.status == 'failed' && @.total > 10)].total") == [30]
+    assert engine.query("domain.orders[?(@.status != 'paid') && !(@.total < 10)].total") == [30]
+    assert sorted(engine.query("$..sku")) == ["a", "b", "c"]
+    assert engine.query("$.domain.orders.o1.items[-1].sku") == ["b"]
+    assert engine.query("$.domain.orders['o2', 'missing'].total") == [10]
+    assert engine.query("$.domain.orders[?(@.items[0])].total") == [30, 5]
+    assert engine.query("$.domain.orders.*.total") == [30, 10, 5]
+
+
+def test_private_zone_is_redacted_and_versions_are_queryable():
+    engine = _engine()
+    assert engine.query("$.internal_secrets.orders") == []
+    assert all(not p.startswith("internal_") for p, _ in engine.query("$..status", with_paths=True))
+    assert engine.query("$..o3._trace") == []
+
+    engine.set_version_retention(2)
+    old = engine.state.version
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"orders": {"o2": {"status": "failed"}}}})
+    assert len(engine.query("domain.orders[?(@.status == 'failed')]")) == 3
+    assert len(engine.query("domain.orders[?(@.status == 'failed')]", version=old)) == 2
+    with engine.pin(old) as view:
+        assert view.query("domain.orders.o2.status") == ["paid"]
+
+    with pytest.raises(ValueError, match="offset"):
+        engine.query("$.domain[?(@.a ==)]")
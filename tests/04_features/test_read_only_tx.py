@@ -0,0 +1,63 @@
+import pytest
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.names"], outputs=[])
+def count_names(ctx):
+    return len(ctx.domain.names)
+
+
+@process(inputs=["domain.names"], outputs=["domain.names"])
+def add_item(ctx):
+    ctx.domain.names.append("c")
+
+
+def test_transaction_without_writes_keeps_the_version():
+    engine = TheusEngine(context={"domain": {"names": ["a", "b"], "n": 0}})
+    before = engine.state
+    seen = []
+    engine.add_event_watcher(lambda topic, payload: seen.append(topic))
+
+    with engine._core.transaction() as tx:
+        tx.update(data={"domain": {}})
+        tx.emit("looked", None)
+        assert engine.state.data["domain"]["n"] == 0
+    assert tx.read_only
+    assert engine.state.version == before.version
+    assert seen == ["looked"]
+
+    with engine._core.transaction() as tx:
+        tx.update(data={"domain": {"n": 1}})
+    assert not tx.read_only
+    assert engine.state.version == before.version + 1
+
+
+def test_untouched_shadows_are_read_only_but_mutated_ones_commit():
+    engine = TheusEngine(context={"domain": {"names": ["a", "b"]}})
+    version = engine.state.version
+    names = engine._core.state.data["domain"]["names"]
+
+    with engine._core.transaction() as tx:
+        tx.get_shadow(names, "domain.names")
+    assert tx.read_only and engine.state.version == version
+
+    with engine._core.transaction() as tx:
+        tx.get_shadow(names, "domain.names").append("c")
+    assert not tx.read_only
+    assert engine.state.version == version + 1
+    assert engine._core.state.data["domain"]["names"] == ["a", "b", "c"]
+
+
+@pytest.mark.asyncio
+async def test_read_only_process_skips_the_commit():
+    engine = TheusEngine(context={"domain": {"names": ["a", "b"]}})
+    engine.register(count_names)
+    engine.register(add_item)
+    version = engine.state.version
+
+    assert await engine.execute("count_names") == 2
+    assert engine.state.version == version
+
+    await engine.execute("add_item")
+    assert engine.state.version == version + 1
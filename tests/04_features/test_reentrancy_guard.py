@@ -0,0 +1,99 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import ReentrancyError, reentrancy_stats, set_reentrancy_policy
+
+HOLDER = {}
+
+
+class Frame:
+    def __init__(self, rows):
+        self.rows = rows
+
+    def copy(self):
+        return Frame(list(self.rows))
+
+    def __eq__(self, other):
+        return isinstance(other, Frame) and self.rows == other.rows
+
+
+@process(inputs=["domain.user", "domain.cfg", "domain.frame"], outputs=["domain.user"])
+def rename(ctx, name="ada"):
+    HOLDER["ctx"] = ctx
+    ctx.domain.user["name"] = name
+    return ctx.domain.user["name"]
+
+
+@process(inputs=["domain.user", "domain.cfg", "domain.frame"], outputs=["domain.user"])
+def count_rows(ctx):
+    HOLDER["ctx"] = ctx
+    ctx.domain.user["rows"] = len(ctx.domain.frame.rows)
+    return ctx.domain.user["rows"]
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {
+        "user": {"name": "bob", "age": 3},
+        "cfg": {"suffix": "!"},
+        "frame": Frame([1, 2]),
+    }})
+    engine.register(rename)
+    engine.register(count_rows)
+    return engine
+
+
+def test_reentrant_reads_go_raw_and_reentrant_writes_raise():
+    engine = _engine()
+    reentrancy_stats(reset=True)
+
+    # A copier that reads the context while get_shadow holds the shadow cache.
+    def copier(frame):
+        assert HOLDER["ctx"].domain.cfg["suffix"] == "!"
+        return frame.copy()
+
+    engine.set_copier(Frame, copier)
+    assert asyncio.run(engine.execute("count_rows")) == 2
+
+    def suffix(path, value, op):
+        return value + HOLDER["ctx"].domain.cfg["suffix"] if op == "set" else value
+
+    engine.add_middleware("suffix", suffix, paths=["domain.user.name"])
+    assert asyncio.run(engine.execute("rename")) == "ada!"
+    assert engine.state.data["domain"]["user"]["name"] == "ada!"
+    assert engine.state.data["domain"]["cfg"] == {"suffix": "!"}
+    assert reentrancy_stats()["raw_reads"] >= 4
+
+    def sneaky(path, value, op):
+        HOLDER["ctx"].domain.user["age"] = 99
+        return value
+
+    engine.add_middleware("sneaky", sneaky, paths=["domain.user.name"], ops=["set"])
+    with pytest.raises(ReentrancyError) as err:
+        asyncio.run(engine.execute("rename", name="eve"))
+    assert err.value.code == "TH701" and err.value.access == "write" and err.value.path == "domain.user[age]"
+    assert engine.state.data["domain"]["user"] == {"name": "ada!", "age": 3, "rows": 2}
+    assert reentrancy_stats()["blocked"] == 1
+
+
+def test_raise_policy_refuses_reentrant_reads():
+    engine = _engine()
+
+    def peek(path, value, op):
+        HOLDER["ctx"].domain.cfg["suffix"]
+        return value
+
+    engine.add_middleware("peek", peek, paths=["domain.user.name"], ops=["set"])
+    assert set_reentrancy_policy("raise") == "raw"
+    try:
+        with pytest.raises(ReentrancyError, match="Re-entrant proxy read of 'domain'"):
+            asyncio.run(engine.execute("rename"))
+    finally:
+        assert set_reentrancy_policy("raw") == "raise"
+    assert reentrancy_stats()["policy"] == "raw"
+
+    assert asyncio.run(engine.execute("rename")) == "ada"
+    with pytest.raises(ValueError):
+        set_reentrancy_policy("ignore")
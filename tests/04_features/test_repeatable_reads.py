@@ -0,0 +1,68 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+READS = []
+
+
+@process(inputs=["domain.a", "domain.cfg"], outputs=["domain.seen"])
+async def reader(ctx):
+    READS.append((ctx.domain.a, ctx.domain.cfg.x))
+    await asyncio.sleep(0)
+    await READS_GATE.wait()
+    READS.append((ctx.domain.a, ctx.domain.cfg.x))
+    ctx.domain.seen = True
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "cfg": {"x": 1}}})
+
+
+def _commit_elsewhere(engine, value):
+    with engine.transaction() as tx:
+        tx.update_many([("domain.a", value), ("domain.cfg.x", value)])
+
+
+def test_repeatable_read_transaction_reads_pinned_snapshot():
+    engine = _engine()
+    with engine.transaction(isolation="repeatable_read") as tx:
+        assert tx.isolation == "repeatable_read"
+        assert tx.snapshot.version == engine._core.state.version
+        _commit_elsewhere(engine, 2)
+        assert tx.read("domain.a") == 1
+        assert tx.read("domain.cfg.x") == 1
+        assert dict(tx.read("domain.cfg")) == {"x": 1}
+        assert tx.read("domain.missing", "dflt") == "dflt"
+    assert tx.snapshot is None
+
+    with engine.transaction() as tx:
+        assert tx.isolation == "read_committed" and tx.snapshot is None
+        _commit_elsewhere(engine, 3)
+        assert tx.read("domain.a") == 3
+
+
+@pytest.mark.asyncio
+async def test_engine_default_applies_to_processes():
+    global READS_GATE
+    engine = _engine()
+    engine.register(reader)
+    engine.set_isolation("repeatable_read")
+    assert engine.isolation == "repeatable_read"
+
+    READS.clear()
+    READS_GATE = asyncio.Event()
+    task = asyncio.ensure_future(engine.execute("reader", retries=1))
+    while not READS:
+        await asyncio.sleep(0)
+    _commit_elsewhere(engine, 5)
+    READS_GATE.set()
+    await task
+    # First attempt stays on its snapshot; the retry after the conflict sees the new commit.
+    assert READS[:2] == [(1, 1), (1, 1)]
+    assert READS[2:] == [(5, 5), (5, 5)]
+
+    with pytest.raises(ValueError, match="Unknown isolation level"):
+        engine.set_isolation("serializable")
@@ -0,0 +1,59 @@
+import pytest
+
+from theus.contracts import OutboxMsg, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.n", "domain.log", "domain.cfg"], outputs=["domain.n", "domain.log", "domain.cfg"])
+def half_done(ctx):
+    ctx.domain.n = 5
+    ctx.domain.log.append("x")
+    ctx.domain.cfg["k"] = 2
+    ctx.outbox.add(OutboxMsg(topic="t", payload={}))
+    raise RuntimeError("boom")
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"n": 0, "log": [], "cfg": {"k": 1}}})
+
+
+@pytest.mark.asyncio
+async def test_failed_process_exception_carries_rollback_report():
+    engine = _engine()
+    engine.register(half_done)
+    with pytest.raises(RuntimeError, match="boom") as info:
+        await engine.execute("half_done")
+
+    report = info.value.rollback_report
+    assert (report["actor"], report["reason"], report["outbox"]) == ("half_done", "exception", 1)
+    assert "boom" in report["error"]
+    changes = {d["path"]: (d["old_value"], d["value"]) for d in report["deltas"]}
+    assert changes["domain.n"] == (0, 5) and changes["domain.cfg[k]"] == (1, 2)
+    assert {"domain.cfg", "domain.log"} <= set(report["shadows"])
+    assert engine.state.data["domain"]["n"] == 0
+
+
+def test_transaction_keeps_last_rollback_report():
+    engine = _engine()
+    tx = engine._core.transaction()
+    assert tx.last_rollback_report() is None
+    with pytest.raises(KeyError):
+        with tx:
+            tx.update(data={"domain": {"n": 3}})
+            raise KeyError("x")
+    report = tx.last_rollback_report()
+    assert report["updates"] == ["domain"] and report["deltas"] == []
+    assert report["start_version"] == engine.state.version
+
+    committed = engine._core.transaction()
+    with committed:
+        committed.update(data={"domain": {"n": 4}})
+    assert committed.last_rollback_report() is None
+
+    engine.inject_fault("commit", times=1)
+    conflicted = engine._core.transaction()
+    with pytest.raises(Exception) as info:
+        with conflicted:
+            conflicted.update(data={"domain": {"n": 5}})
+    assert info.value.rollback_report["reason"] == "commit_failed"
+    assert conflicted.last_rollback_report() is info.value.rollback_report
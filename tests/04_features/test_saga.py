@@ -0,0 +1,75 @@
+from theus.engine import TheusEngine
+from theus.orchestrator import SagaCoordinator, SagaStep
+from theus_core import OutboxMsg
+
+
+def _checkout(calls, fail_ship=False):
+    def step(name, result=None, fail=False):
+        def forward(data, key):
+            calls.append(("forward", name, key))
+            if fail:
+                raise RuntimeError(f"{name} unavailable")
+            return result
+
+        def compensate(data, key):
+            calls.append(("compensate", name, data.get("reservation")))
+
+        return forward, compensate
+
+    reserve, release = step("reserve", {"reservation": "r-1"})
+    charge, refund = step("charge")
+    ship, _ = step("ship", fail=fail_ship)
+    return [SagaStep("reserve", reserve, release), SagaStep("charge", charge, refund), SagaStep("ship", ship)]
+
+
+def test_saga_runs_forward_and_compensates_in_reverse():
+    engine = TheusEngine(context={"domain": {}})
+    relayed = []
+    calls = []
+    coordinator = SagaCoordinator(engine, worker=lambda m: relayed.append(m.topic))
+    coordinator.register("checkout", _checkout(calls))
+    coordinator.register("checkout_fail", _checkout(calls, fail_ship=True))
+
+    ok = coordinator.start("checkout", {"order": 7}, saga_id="order-7")
+    with engine.transaction() as tx:
+        tx.outbox.add(OutboxMsg("mail", {"to": "a"}))
+    assert coordinator.run_until_idle()
+    record = coordinator.status(ok)
+    assert (record["status"], record["completed"]) == ("completed", ["reserve", "charge", "ship"])
+    assert record["data"] == {"order": 7, "reservation": "r-1"}
+    assert [c[2] for c in calls] == ["order-7:0:forward", "order-7:1:forward", "order-7:2:forward"]
+    assert relayed == ["mail"]
+
+    calls.clear()
+    failed = coordinator.start("checkout_fail", {"order": 8})
+    assert coordinator.run_until_idle()
+    record = coordinator.status(failed)
+    assert record["status"] == "compensated" and "ship unavailable" in record["error"]
+    assert calls[3:] == [("compensate", "charge", "r-1"), ("compensate", "reserve", "r-1")]
+    assert set(coordinator.sagas("completed")) == {"order-7"}
+
+
+def test_saga_survives_restart_from_the_durable_outbox():
+    calls = []
+    engine = TheusEngine(context={"domain": {}})
+    coordinator = SagaCoordinator(engine)
+    coordinator.register("checkout", _checkout(calls))
+    saga_id = coordinator.start("checkout")
+    engine.process_outbox()  # reserve only; charge is now queued
+    assert coordinator.status(saga_id)["step"] == 1
+
+    blob, queue = engine.dumps_state(), engine.outbox_snapshot()
+    restarted = TheusEngine.loads_state(blob, context={"domain": {}})
+    restarted.restore_outbox(queue)
+    resumed = SagaCoordinator(restarted)
+    resumed.register("checkout", _checkout(calls))
+    assert resumed.recover() == 0  # the charge message came back with the queue
+    assert resumed.run_until_idle()
+    assert resumed.status(saga_id)["status"] == "completed"
+
+    # Queue lost: recover() re-enqueues the current step.
+    lost = TheusEngine.loads_state(blob, context={"domain": {}})
+    again = SagaCoordinator(lost)
+    again.register("checkout", _checkout(calls))
+    assert again.recover() == 1 and again.run_until_idle()
+    assert again.status(saga_id)["status"] == "completed"
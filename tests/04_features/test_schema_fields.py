@@ -0,0 +1,69 @@
+from typing import Dict, List, Optional
+
+import pytest
+from pydantic import BaseModel
+from theus_core import SupervisorProxy
+
+from theus.engine import TheusEngine
+
+
+class User(BaseModel):
+    name: str = ""
+    email: str = ""
+
+
+class Settings(BaseModel):
+    threshold: int = 0
+    retries: int = 0
+
+
+class Domain(BaseModel):
+    counter: int = 0
+    settings: Optional[Settings] = None
+    users: Dict[str, User] = {}
+    history: List[User] = []
+
+
+class Schema(BaseModel):
+    domain: Domain
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"counter": 1, "settings": {"threshold": 3, "retries": 1},
+                                             "users": {"u1": {"name": "a", "email": ""}}}})
+    engine.set_schema(Schema)
+    return engine
+
+
+def test_schema_fields_follow_nested_models():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        domain = SupervisorProxy(engine._core.state.data["domain"], "domain", transaction=tx)
+        assert domain.schema_fields() == ["counter", "settings", "users", "history"]
+        assert domain.settings.schema_fields() == ["threshold", "retries"]
+        assert domain.users["u1"].schema_fields() == ["name", "email"]
+        # Plain values and undeclared paths have no metadata.
+        assert domain.users.schema_fields() == []
+
+
+def test_missing_attribute_suggests_schema_field():
+    engine = _engine()
+    with engine._core.transaction() as tx:
+        domain = SupervisorProxy(engine._core.state.data["domain"], "domain", transaction=tx)
+        with pytest.raises(AttributeError, match="Did you mean 'counter'"):
+            domain.countr
+        with pytest.raises(AttributeError, match="Did you mean 'threshold'"):
+            domain.settings.Treshold
+        with pytest.raises(AttributeError) as exc:
+            domain.unrelated_name
+        assert "Did you mean" not in str(exc.value)
+
+
+def test_no_schema_means_no_metadata():
+    engine = TheusEngine(context={"domain": {"counter": 1}})
+    with engine._core.transaction() as tx:
+        domain = SupervisorProxy(engine._core.state.data["domain"], "domain", transaction=tx)
+        assert domain.schema_fields() == []
+        with pytest.raises(AttributeError) as exc:
+            domain.countr
+        assert "Did you mean" not in str(exc.value)
@@ -0,0 +1,67 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.orders"], outputs=["domain.orders"])
+def reassign(ctx, index=0, customer=9):
+    orders = [dict(o) for o in ctx.domain.orders]
+    orders[index]["customer_id"] = customer
+    ctx.domain.orders = orders
+
+
+@process(inputs=["domain.orders"], outputs=["domain.orders"])
+def add_order(ctx, customer=7):
+    ctx.domain.orders.append({"id": "new", "customer_id": customer})
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {
+        "orders": [{"id": "a", "customer_id": 7}, {"id": "b", "customer_id": 8}, {"id": "c", "customer_id": 7}],
+        "users": {"u1": {"email": "x@y"}, "u2": {"email": "z@y"}},
+    }})
+    engine.register(reassign)
+    engine.register(add_order)
+    return engine
+
+
+def test_index_is_built_and_maintained_from_commits():
+    engine = _engine()
+    name = engine.create_index("domain.orders[*].customer_id")
+    assert name == "domain.orders.*.customer_id"
+    assert engine.lookup(name, 7) == ["domain.orders[0].customer_id", "domain.orders[2].customer_id"]
+
+    asyncio.run(engine.execute("reassign", index=0, customer=9))
+    assert engine.lookup(name, 7) == ["domain.orders[2].customer_id"]
+    assert engine.lookup(name, 9) == ["domain.orders[0].customer_id"]
+
+    asyncio.run(engine.execute("add_order", customer=7))
+    assert engine.lookup(name, 7.0) == ["domain.orders[2].customer_id", "domain.orders[3].customer_id"]
+    assert engine.index_stats()[name] == {"pattern": name, "keys": 3, "entries": 4}
+
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"orders": [{"id": "z", "customer_id": 1}]}})
+    assert engine.lookup(name, 7) == [] and engine.lookup(name, 1) == ["domain.orders[0].customer_id"]
+
+
+def test_dict_patterns_named_indexes_and_errors():
+    engine = _engine()
+    engine.create_index("domain.users.*.email", name="email")
+    assert engine.lookup("email", "z@y") == ["domain.users.u2.email"]
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"users": {"u3": {"email": "z@y"}}}})
+    assert engine.lookup("email", "z@y") == ["domain.users.u2.email", "domain.users.u3.email"]
+
+    engine.load_state(_engine().dumps_state())
+    assert engine.lookup("email", "z@y") == ["domain.users.u2.email"]
+
+    with pytest.raises(TypeError):
+        engine.lookup("email", ["z@y"])
+    with pytest.raises(KeyError):
+        engine.lookup("missing", 1)
+    with pytest.raises(ValueError, match="Private"):
+        engine.create_index("internal_keys.*.id")
+    assert engine.drop_index("email") and not engine.drop_index("email")
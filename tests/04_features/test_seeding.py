@@ -0,0 +1,57 @@
+import os
+
+import pytest
+
+from theus.engine import TheusEngine
+from theus_core import CallbackProvider, EnvProvider
+from theus_core.testing import Harness
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"n": 0}})
+
+
+def test_env_provider_seeds_meta_and_private_fields_with_redacted_audit():
+    os.environ["SEED_TEST_DB_URL"] = "postgres://db/app"
+    os.environ["SEED_TEST_API_KEY"] = "s3cr3t"
+    try:
+        engine = _engine()
+        provider = EnvProvider({"meta_cfg.db_url": "SEED_TEST_DB_URL", "internal_secrets.api_key": "SEED_TEST_API_KEY", "meta_cfg.unset": "SEED_TEST_UNSET"})
+        with Harness() as h:
+            seeded = engine.seed_from(provider)
+        assert seeded == ["internal_secrets.api_key", "meta_cfg.db_url"]
+        data = engine.state.data
+        assert data["meta_cfg"] == {"db_url": "postgres://db/app"}
+        assert data["internal_secrets"] == {"api_key": "s3cr3t"}
+
+        (event,) = h.audit_events("STATE_SEEDED")
+        assert "internal_secrets.api_key=<redacted>" in event.message
+        assert "s3cr3t" not in event.message and "postgres" not in event.message
+
+        with pytest.raises(KeyError, match="SEED_TEST_UNSET"):
+            EnvProvider({"meta_cfg.unset": "SEED_TEST_UNSET"}, required=True).fetch()
+    finally:
+        del os.environ["SEED_TEST_DB_URL"], os.environ["SEED_TEST_API_KEY"]
+
+
+def test_env_prefix_and_callback_providers():
+    os.environ["SEEDPFX__META_APP__MODE"] = "prod"
+    try:
+        engine = _engine()
+        assert engine.seed_from(EnvProvider(prefix="SEEDPFX__")) == ["meta_app.mode"]
+        assert engine.state.data["meta_app"] == {"mode": "prod"}
+    finally:
+        del os.environ["SEEDPFX__META_APP__MODE"]
+
+    engine.seed_from(CallbackProvider(lambda: {"meta_app.replicas": 3}, name="vault"))
+    assert engine.state.data["meta_app"] == {"mode": "prod", "replicas": 3}
+    assert engine.seed_from(CallbackProvider(dict)) == []
+
+    class Custom:
+        def fetch(self):
+            return {"meta_app": 1, "meta_app.mode": "x"}
+
+    version = engine.state.version
+    with pytest.raises(ValueError, match="both 'meta_app' and 'meta_app.mode'"):
+        engine.seed_from(Custom())
+    assert engine.state.version == version
@@ -0,0 +1,64 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+class Frame:
+    copies = 0
+
+    def __init__(self, rows):
+        self.rows = rows
+
+    def __deepcopy__(self, memo):
+        raise TypeError("Frame cannot be deep-copied")
+
+    def copy(self):
+        Frame.copies += 1
+        return Frame(list(self.rows))
+
+
+class SubFrame(Frame):
+    pass
+
+
+def test_registered_copier_replaces_deepcopy():
+    engine = TheusEngine(context={"domain": {"frame": SubFrame([1, 2])}})
+    frame = engine._core.state.data["domain"]["frame"]
+
+    with pytest.raises(RuntimeError, match="cannot deepcopy"):
+        with engine._core.transaction() as tx:
+            tx.get_shadow(frame, "domain.frame")
+
+    engine.set_copier(Frame, lambda f: f.copy())
+    assert engine.copiers() == [f"{__name__}.Frame"]
+    with engine._core.transaction() as tx:
+        shadow = tx.get_shadow(frame, "domain.frame")
+        assert isinstance(shadow, Frame) and shadow is not frame
+        shadow.rows.append(3)
+    assert frame.rows == [1, 2] and Frame.copies == 1
+
+    engine.set_copier(Frame, None)
+    assert engine.copiers() == []
+
+
+def test_copier_errors_fail_fast():
+    engine = TheusEngine(context={"domain": {"frame": Frame([1])}})
+    frame = engine._core.state.data["domain"]["frame"]
+
+    engine.set_copier("Frame", lambda f: f)
+    with pytest.raises(RuntimeError, match="returned the original object"):
+        with engine._core.transaction() as tx:
+            tx.get_shadow(frame, "domain.frame")
+
+    def broken(f):
+        raise OSError("disk full")
+
+    engine.set_copier("Frame", broken)
+    with pytest.raises(RuntimeError, match="disk full"):
+        with engine._core.transaction() as tx:
+            tx.get_shadow(frame, "domain.frame")
+
+    with pytest.raises(TypeError):
+        engine.set_copier("Frame", 42)
+    with pytest.raises(TypeError):
+        engine.set_copier(42, broken)
@@ -0,0 +1,59 @@
+import asyncio
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+def _engine(limit):
+    engine = TheusEngine(context={"domain": {f"k{i}": {"payload": list(range(2000))} for i in range(6)}})
+    engine.set_shadow_cache_limit(limit)
+    seen = {}
+    engine.set_slow_commit_threshold(0.0, callback=seen.update)
+    return engine, seen
+
+
+@process(inputs=["domain"], outputs=["domain"])
+def scan(ctx):
+    held = ctx.domain.k1.payload
+    for i in range(2, 6):
+        assert getattr(ctx.domain, f"k{i}").payload[0] == 0
+    # Untouched shadows were evicted meanwhile; writes still land.
+    held.append(-1)
+    ctx.domain.k2.payload[0] = -2
+
+
+@process(inputs=["domain"], outputs=["domain"])
+def rewrite(ctx):
+    ctx.domain.k1.payload = list(range(2000))
+    for i in range(2, 6):
+        _ = getattr(ctx.domain, f"k{i}").payload
+    _ = ctx.domain.k1.payload
+
+
+def test_untouched_shadows_are_evicted_under_the_bound():
+    engine, seen = _engine(100_000)
+    engine.register(scan)
+    asyncio.run(engine.execute("scan"))
+
+    domain = engine.state.data["domain"]
+    assert domain["k1"]["payload"][-1] == -1 and domain["k2"]["payload"][0] == -2
+    assert seen["shadow_evictions"] == 4 and seen["shadow_bytes"] > 100_000
+    # k2 was copied again after its eviction.
+    assert seen["shadows"] == 7
+
+    unbounded, seen = _engine(None)
+    unbounded.register(scan)
+    asyncio.run(unbounded.execute("scan"))
+    assert seen["shadow_evictions"] == 0 and seen["shadow_bytes"] == 0 and seen["shadows"] == 6
+    assert unbounded.state.data["domain"] == engine.state.data["domain"]
+
+
+def test_shadows_with_logged_deltas_are_pinned():
+    engine, seen = _engine(1)
+    engine.register(rewrite)
+    asyncio.run(engine.execute("rewrite"))
+
+    # k1 equals its committed value again but its delta pins it (and the root): neither
+    # is copied twice, while the untouched siblings are evicted.
+    assert seen["shadows"] == 6 and seen["shadow_evictions"] > 0
+    assert engine.state.data["domain"]["k1"]["payload"] == list(range(2000))
@@ -0,0 +1,57 @@
+import asyncio
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+EQ_CALLS = []
+
+
+class Model:
+    def __init__(self, weights):
+        self.weights = weights
+
+    def __eq__(self, other):
+        EQ_CALLS.append(self)
+        return isinstance(other, Model) and self.weights == other.weights
+
+
+@process(inputs=["domain"], outputs=["domain"])
+def read(ctx):
+    assert len(ctx.domain.model.weights) == 1000
+
+
+@process(inputs=["domain"], outputs=["domain"])
+def train(ctx):
+    ctx.domain.model.weights[0] = -1.0
+
+
+def _engine(hashing):
+    engine = TheusEngine(context={"domain": {"model": Model([0.5] * 1000)}})
+    engine.set_shadow_hashing(hashing)
+    seen = {}
+    engine.set_slow_commit_threshold(0.0, callback=seen.update)
+    engine.register(read)
+    engine.register(train)
+    return engine, seen
+
+
+def test_unchanged_shadows_skip_python_eq():
+    engine, seen = _engine(False)
+    EQ_CALLS.clear()
+    asyncio.run(engine.execute("read"))
+    assert EQ_CALLS and seen["shadow_hash_skips"] == 0
+
+    engine, seen = _engine(True)
+    EQ_CALLS.clear()
+    asyncio.run(engine.execute("read"))
+    assert EQ_CALLS == [] and seen["shadow_hash_skips"] == seen["shadows"] > 0
+    assert seen["deltas"] == 0
+
+
+def test_changed_shadows_fall_back_to_comparison():
+    engine, seen = _engine(True)
+    asyncio.run(engine.execute("train"))
+
+    # The model shadow changed; only its untouched parent is skipped.
+    assert seen["shadow_hash_skips"] < seen["shadows"] and seen["deltas"] > 0
+    assert engine.state.data["domain"]["model"].weights[0] == -1.0
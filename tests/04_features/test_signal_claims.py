@@ -0,0 +1,83 @@
+import threading
+
+import pytest
+
+from theus_core import advance_global_clock_ms, use_global_system_clock, use_global_test_clock
+from theus.engine import TheusEngine
+
+
+def _engine_with_signals(count):
+    engine = TheusEngine(context={"domain": {}})
+    for i in range(count):
+        with engine.transaction() as tx:
+            tx.update(signal={"cmd_job": i})
+    return engine
+
+
+def test_claims_are_exclusive_across_consumers():
+    engine = _engine_with_signals(50)
+    seen, lock = [], threading.Lock()
+
+    def consume(name):
+        while batch := engine.signals.claim(3, name):
+            for entry in batch:
+                assert engine.signals.ack(entry["seq"], entry["claim_version"])
+                with lock:
+                    seen.append(entry["payload"])
+
+    workers = [threading.Thread(target=consume, args=(f"w{i}",)) for i in range(4)]
+    for w in workers:
+        w.start()
+    for w in workers:
+        w.join()
+    assert sorted(seen) == list(range(50))
+    stats = engine.signals.stats()
+    assert (stats["enqueued"], stats["acked"], stats["pending"], stats["claimed"]) == (50, 50, 0, 0)
+
+
+def test_rolled_back_signals_are_not_queued():
+    engine = TheusEngine(context={"domain": {}})
+    with pytest.raises(RuntimeError):
+        with engine.transaction() as tx:
+            tx.update(signal={"cmd_job": 1})
+            raise RuntimeError("abort")
+    assert len(engine.signals) == 0
+
+
+def test_expired_leases_are_reclaimed_and_stale_acks_fail():
+    engine = _engine_with_signals(2)
+    use_global_test_clock(start_ms=1_000_000)
+    try:
+        signals = engine.signals
+        [first, second] = signals.claim(2, "crashy", lease_s=5)
+        assert signals.claim(1, "other") == []
+        assert signals.renew(second["seq"], second["claim_version"], lease_s=20)
+
+        advance_global_clock_ms(6_000)
+        [retry] = signals.claim(5, "healthy")
+        assert (retry["seq"], retry["claim_version"], retry["attempts"]) == (first["seq"], 2, 2)
+        # The crashed consumer's lease is gone: its late ack is refused.
+        assert not signals.ack(first["seq"], first["claim_version"])
+        assert signals.ack(retry["seq"], retry["claim_version"])
+
+        assert signals.release(second["seq"], second["claim_version"])
+        assert signals.claim(5, "healthy", topic="cmd_other") == []
+        assert [e["payload"] for e in signals.claim(5, "healthy", topic="cmd_job")] == [1]
+
+        stats = signals.stats()
+        assert stats["reclaimed"] == 1 and stats["reclaimed_from"] == {"crashy": 1}
+        assert stats["acked"] == 1 and stats["released"] == 1
+    finally:
+        use_global_system_clock()
+
+
+def test_capacity_drops_oldest_unclaimed():
+    engine = TheusEngine(context={"domain": {}})
+    engine.signals.set_capacity(2)
+    for i in range(3):
+        with engine.transaction() as tx:
+            tx.update(signal={"cmd_job": i})
+    assert [e["payload"] for e in engine.signals.claim(5, "w")] == [1, 2]
+    assert engine.signals.stats()["dropped"] == 1
+    with pytest.raises(ValueError):
+        engine.signals.set_lease(0)
@@ -0,0 +1,64 @@
+import pytest
+from theus_core import ContextError
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+calls = []
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+def bump(ctx):
+    calls.append(ctx.domain.a)
+    ctx.domain.a = ctx.domain.a + 1
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "b": 0}})
+
+
+@pytest.mark.asyncio
+async def test_execute_retries_once_after_simulated_conflict():
+    engine = _engine()
+    engine.register(bump)
+    calls.clear()
+    engine.testing.simulate_conflict(["domain.a"], at_version=7)
+    assert engine.testing.pending_conflicts == [(["domain.a"], 7)]
+
+    await engine.execute("bump")
+    assert calls == [1, 1]
+    assert engine._core.state.data["domain"]["a"] == 2
+    assert engine.testing.pending_conflicts == []
+
+
+def test_cas_conflict_fires_exactly_once_on_overlapping_paths():
+    engine = _engine()
+    core = engine._core
+    version = core.state.version
+    engine.testing.simulate_conflict(["domain"], at_version=9)
+    with pytest.raises(ContextError, match=f"Expected {version}, Found 9"):
+        core.compare_and_swap(version, data={"domain": {"b": 2}})
+    assert core.state.version == version
+
+    core.compare_and_swap(version, data={"domain": {"b": 2}})
+    assert core.state.data["domain"]["b"] == 2
+
+
+def test_unrelated_commits_leave_conflict_armed():
+    engine = _engine()
+    engine.testing.simulate_conflict(["domain.c"])
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"b": 3}})
+    assert engine.testing.pending_conflicts == [(["domain.c"], None)]
+
+    with pytest.raises(ContextError, match="Conflict Detected"):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"c": 1}})
+    assert "c" not in engine._core.state.data["domain"]
+
+    engine.testing.simulate_conflict(["domain.b"])
+    engine.testing.clear_conflicts()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"b": 4}})
+    with pytest.raises(ValueError):
+        engine.testing.simulate_conflict([])
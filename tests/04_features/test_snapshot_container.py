@@ -0,0 +1,90 @@
+import hashlib
+import json
+import struct
+
+import pytest
+from pydantic import BaseModel
+
+from theus.engine import TheusEngine
+from theus_core import SnapshotFormatError, SnapshotIncompatibleError
+
+
+class Domain(BaseModel):
+    counter: int = 0
+
+
+class Schema(BaseModel):
+    domain: Domain
+
+
+class SchemaV2(BaseModel):
+    domain: Domain
+    extra: int = 0
+
+
+def _engine(schema=Schema):
+    engine = TheusEngine(context={"domain": {"counter": 7}})
+    if schema is not None:
+        engine.set_schema(schema)
+    return engine
+
+
+def _resign(blob):
+    """Recompute the trailer of a container whose body was edited."""
+    body = blob[:-32]
+    return body + hashlib.sha256(body).digest()
+
+
+def test_export_import_roundtrip_with_embedded_schema():
+    source = _engine()
+    blob = source.export_snapshot()
+    assert blob[:8] == b"THEUSSNP" and struct.unpack("<H", blob[8:10])[0] == 1
+
+    header = source.inspect_snapshot(blob)
+    assert header["state_format"] == "theus-state/1"
+    assert header["state_version"] == source.state.version
+    assert [s["name"] for s in header["sections"]] == ["state", "schema"]
+    assert json.loads(header["schema"]) == json.loads(json.dumps(Schema.model_json_schema(), sort_keys=True))
+    assert header["config"]["isolation"] == "read_committed"
+
+    target = _engine()
+    target.import_snapshot(blob)
+    assert target.state.data["domain"] == {"counter": 7}
+    assert target.state.version == source.state.version
+
+
+def test_damaged_or_foreign_containers_fail_with_clear_errors():
+    blob = _engine().export_snapshot()
+    target = _engine()
+
+    with pytest.raises(SnapshotFormatError, match="Not a Theus snapshot") as err:
+        target.import_snapshot(b"PK\x03\x04" + blob[4:])
+    assert err.value.code == "TH601"
+
+    newer = _resign(blob[:8] + struct.pack("<H", 2) + blob[10:])
+    with pytest.raises(SnapshotFormatError, match="version 2 is not supported") as err:
+        target.import_snapshot(newer)
+    assert err.value.code == "TH602"
+
+    flipped = bytearray(blob)
+    flipped[-40] ^= 0xFF
+    with pytest.raises(SnapshotFormatError, match="checksum mismatch") as err:
+        target.import_snapshot(bytes(flipped))
+    assert err.value.code == "TH603"
+    with pytest.raises(SnapshotFormatError, match="section 'schema' checksum"):
+        target.import_snapshot(_resign(bytes(flipped)))
+    assert target.state.data["domain"] == {"counter": 7}
+
+
+def test_strict_import_checks_the_schema_fingerprint():
+    blob = _engine(schema=Schema).export_snapshot()
+    upgraded = _engine(schema=SchemaV2)
+    with pytest.raises(SnapshotIncompatibleError, match="schema fingerprint") as err:
+        upgraded.import_snapshot(blob)
+    assert err.value.code == "TH604" and err.value.expected != err.value.found
+
+    upgraded.import_snapshot(blob, strict=False)
+    assert upgraded.state.data["domain"] == {"counter": 7}
+
+    with pytest.raises(SnapshotIncompatibleError, match="embeds no schema"):
+        _engine(schema=Schema).import_snapshot(_engine(schema=None).export_snapshot())
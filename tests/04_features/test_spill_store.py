@@ -0,0 +1,59 @@
+import os
+
+import pytest
+import theus_core
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+seen = {}
+
+
+@process(inputs=["domain.lookup"], outputs=[])
+def read_table(ctx):
+    seen["k7"] = ctx.domain.lookup["k7"]
+
+
+@process(inputs=["domain.lookup", "domain.n"], outputs=["domain.lookup", "domain.n"])
+def patch_table(ctx):
+    ctx.domain.lookup["k7"] = -1
+    ctx.domain.n = 1
+
+
+def _engine(tmp_path=None):
+    table = {f"k{i}": i for i in range(500)}
+    engine = TheusEngine(context={"domain": {"lookup": table, "n": 0}})
+    engine.spill_to_disk(["domain.lookup"], path=tmp_path, cache_bytes=1 << 20)
+    return engine
+
+
+@pytest.mark.asyncio
+async def test_spilled_values_fault_in_on_proxy_reads():
+    engine = _engine()
+    placeholder = engine._core.state.data["domain"]["lookup"]
+    assert isinstance(placeholder, theus_core.SpilledValue)
+    assert placeholder.load()["k499"] == 499
+
+    engine.register(read_table)
+    engine.register(patch_table)
+    await engine.execute("read_table")
+    assert seen["k7"] == 7
+
+    await engine.execute("patch_table")
+    spilled = engine._core.state.data["domain"]["lookup"]
+    assert isinstance(spilled, theus_core.SpilledValue) and spilled is not placeholder
+    assert spilled.load()["k7"] == -1 and placeholder.load()["k7"] == 7
+    assert engine._core.state.data["domain"]["n"] == 1
+
+    stats = engine.spill_stats()
+    assert stats["records"] == 2 and stats["file_bytes"] % 4096 == 0
+    assert stats["hits"] >= 1 and stats["misses"] >= 1
+
+
+def test_spill_file_and_prefix_validation(tmp_path):
+    path = str(tmp_path / "spill.bin")
+    engine = _engine(path)
+    assert os.path.getsize(path) == engine.spill_stats()["file_bytes"] > 0
+    with pytest.raises(ValueError, match="zone and a field"):
+        engine.spill_to_disk(["domain"])
+    assert TheusEngine().spill_stats() is None
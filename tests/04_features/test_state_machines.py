@@ -0,0 +1,77 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import IllegalTransitionError
+from theus_core.testing import Harness
+
+
+@process(inputs=["domain.orders"], outputs=["domain.orders"])
+def set_status(ctx, order="o1", status="paid"):
+    ctx.domain.orders[order]["status"] = status
+
+
+@process(inputs=["domain.orders"], outputs=["domain.orders"])
+def replace_order(ctx, order="o1", status="shipped"):
+    ctx.domain.orders[order] = {"status": status}
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"orders": {"o1": {"status": "new"}}}})
+    engine.register(set_status)
+    engine.register(replace_order)
+    engine.define_state_machine(
+        "domain.orders.*.status",
+        {"new": ["paid", "cancelled"], "paid": ["shipped"]},
+        initial=["new"],
+    )
+    return engine
+
+
+def _status(engine, order="o1"):
+    return engine.state.data["domain"]["orders"][order]["status"]
+
+
+def test_declared_transitions_commit():
+    engine = _engine()
+    asyncio.run(engine.execute("set_status", status="paid"))
+    asyncio.run(engine.execute("set_status", status="paid"))  # same state is always allowed
+    asyncio.run(engine.execute("set_status", status="shipped"))
+    assert _status(engine) == "shipped"
+
+
+def test_illegal_jump_is_rejected_and_audited():
+    engine = _engine()
+    with Harness() as h, pytest.raises(IllegalTransitionError) as info:
+        asyncio.run(engine.execute("set_status", status="shipped"))
+    err = info.value
+    assert err.code == "TH501"
+    assert (err.from_state, err.to_state, err.allowed) == ("new", "shipped", ["cancelled", "paid"])
+    assert err.pattern == "domain.orders.*.status"
+    assert "new -> shipped" in str(err)
+    assert _status(engine) == "new"
+    assert h.audit_events("ILLEGAL_TRANSITION")
+
+
+def test_replacing_parent_checks_nested_machine_paths():
+    engine = _engine()
+    with pytest.raises(IllegalTransitionError):
+        asyncio.run(engine.execute("replace_order", status="shipped"))
+    # New orders must start in an initial state.
+    with pytest.raises(IllegalTransitionError):
+        asyncio.run(engine.execute("replace_order", order="o2", status="paid"))
+    asyncio.run(engine.execute("replace_order", order="o2", status="new"))
+    assert _status(engine, "o2") == "new"
+
+
+def test_machines_can_be_listed_and_removed():
+    engine = _engine()
+    assert engine.state_machines()["domain.orders.*.status"]["initial"] == ["new"]
+    with pytest.raises(ValueError):
+        engine.define_state_machine("domain.x", {"a": ["b"]}, initial=["z"])
+    assert engine.remove_state_machine("domain.orders.*.status")
+    assert not engine.remove_state_machine("domain.orders.*.status")
+    asyncio.run(engine.execute("set_status", status="shipped"))
+    assert _status(engine) == "shipped"
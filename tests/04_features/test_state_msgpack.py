@@ -0,0 +1,73 @@
+import pytest
+from theus_core import SerializationError
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={
+            "domain": {"orders": [1, 2.5, None, True], "meta": {"owner": "a", "pair": (1, "x")}},
+            "global": {"blob": b"\x00\xff", "big": 2**63},
+        })
+    return engine
+
+
+def test_roundtrip_preserves_data_and_versions():
+    engine = _engine()
+    blob = engine.dumps_state()
+    assert isinstance(blob, bytes)
+
+    restored = TheusEngine.loads_state(blob)
+    assert restored.state.version == engine.state.version
+    assert restored.state.data["domain"] == engine.state.data["domain"]
+    assert restored.state.data["domain"]["meta"]["pair"] == (1, "x")
+    assert restored.state.data["global"]["blob"] == b"\x00\xff"
+    assert restored.state.data["global"]["big"] == 2**63
+    restored.set_escape_tracking(True)
+    assert restored.verify_integrity()["ok"] is True
+
+
+def test_load_state_replaces_state_and_accepts_commits():
+    source = _engine()
+    target = TheusEngine()
+    target.load_state(source.dumps_state())
+    assert target.state.data["domain"]["meta"]["owner"] == "a"
+
+    with target.transaction() as tx:
+        tx.update(data={"domain": {"meta": {"owner": "b"}}})
+    assert target.state.version == source.state.version + 1
+    assert target.state.data["domain"]["meta"]["owner"] == "b"
+
+
+def test_unsupported_values_do_not_fall_back_to_pickle():
+    class Opaque:
+        pass
+
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"obj": Opaque()}})
+    with pytest.raises(SerializationError, match="Opaque"):
+        engine.dumps_state()
+
+
+def test_rejects_garbage_blobs():
+    with pytest.raises(SerializationError):
+        TheusEngine.loads_state(b"\x93\x01")
+    with pytest.raises(SerializationError):
+        TheusEngine.loads_state(_engine().dumps_state()[:-3])
+
+
+def test_numpy_arrays_roundtrip():
+    np = pytest.importorskip("numpy")
+    engine = TheusEngine()
+    arr = np.arange(6, dtype=np.float32).reshape(2, 3)
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"weights": arr, "scale": np.float64(0.5)}})
+
+    restored = TheusEngine.loads_state(engine.dumps_state())
+    out = restored.state.data["domain"]["weights"]
+    assert out.dtype == np.float32 and out.shape == (2, 3)
+    assert np.array_equal(out, arr)
+    assert restored.state.data["domain"]["scale"] == 0.5
@@ -0,0 +1,112 @@
+import pytest
+import theus_core
+
+from theus.engine import TheusEngine
+
+pytestmark = pytest.mark.skipif(
+    not hasattr(theus_core, "StateServer"), reason="theus_core built without the state-server feature"
+)
+
+
+def _engine():
+    engine = TheusEngine()
+    with engine.transaction() as tx:
+        tx.update(data={
+            "domain": {
+                "orders": [1, 2], "meta": {"owner": "a"}, "internal_secret": "s3cr3t",
+                "users": [{"name": "ann", "internal_token": "s3cr3t"}],
+            },
+            "global": {"mode": "live"},
+            "log_events": ["boot"],
+        })
+    return engine
+
+
+def test_read_returns_flattened_state_with_private_redacted():
+    engine = _engine()
+    with engine.serve_state(tokens=["t0k"]) as server:
+        host, port = server.address
+        with theus_core.StateClient(host, port, "t0k") as client:
+            reply = client.read()
+    assert reply["version"] == engine.state.version
+    # Lists are walked item by item, so Private fields inside them are redacted too.
+    assert reply["state"] == {
+        "domain.orders.0": 1,
+        "domain.orders.1": 2,
+        "domain.meta.owner": "a",
+        "domain.users.0.name": "ann",
+        "global.mode": "live",
+        "log_events.0": "boot",
+    }
+
+
+def test_prefix_filter_and_extra_redacted_zones():
+    engine = _engine()
+    with engine.serve_state(tokens=["t0k"], redact_zones=["log"]) as server:
+        client = theus_core.StateClient(*server.address, "t0k")
+        assert client.read(prefix="domain.meta")["state"] == {"domain.meta.owner": "a"}
+        assert not any(k.startswith("log_") for k in client.read()["state"])
+
+        meta = client.version()
+        assert meta["version"] == engine.state.version
+        assert meta["key_versions"]["domain"] == engine.state.version
+        assert not any(k.startswith("log_") for k in meta["key_versions"])
+
+
+def test_reads_observe_new_commits():
+    engine = _engine()
+    with engine.serve_state(tokens=["t0k"]) as server:
+        client = theus_core.StateClient(*server.address, "t0k")
+        with engine.transaction() as tx:
+            tx.update(data={"global": {"mode": "drain"}})
+        reply = client.read(prefix="global")
+        assert reply == {"version": engine.state.version, "state": {"global.mode": "drain"}}
+        assert server.requests_served == 1
+
+
+def test_bad_token_is_rejected():
+    engine = _engine()
+    with engine.serve_state(tokens=["t0k"]) as server:
+        client = theus_core.StateClient(*server.address, "wrong")
+        with pytest.raises(PermissionError):
+            client.read()
+        with pytest.raises(ConnectionError):
+            client.read()
+
+    with pytest.raises(ValueError):
+        engine.serve_state(tokens=[])
+
+
+def test_stop_closes_listener():
+    engine = _engine()
+    server = engine.serve_state(tokens=["t0k"])
+    address = server.address
+    server.stop()
+    assert server.running is False
+    with pytest.raises(OSError):
+        theus_core.StateClient(*address, "t0k")
+
+
+def test_every_configured_token_is_accepted():
+    engine = _engine()
+    tokens = ["alpha-token", "beta-token", "gamma-tok"]
+    with engine.serve_state(tokens=tokens) as server:
+        for token in tokens:
+            with theus_core.StateClient(*server.address, token) as client:
+                assert client.version()["version"] == engine.state.version
+        with pytest.raises(PermissionError):
+            theus_core.StateClient(*server.address, "alpha-tokem").read()
+
+
+def test_silent_connection_is_dropped_before_authentication():
+    import socket
+    import time
+
+    engine = _engine()
+    with engine.serve_state(tokens=["t0k"]) as server:
+        sock = socket.create_connection(server.address)
+        sock.settimeout(10)
+        started = time.monotonic()
+        assert sock.recv(1) == b""  # closed by the server, nothing sent
+        assert time.monotonic() - started < 9
+        sock.close()
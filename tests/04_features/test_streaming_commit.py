@@ -0,0 +1,71 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _engine(streaming):
+    users = {f"u{i}": {"score": 0} for i in range(50)}
+    engine = TheusEngine(context={"domain": {"users": users, "items": [1, 2, 3], "total": 0}})
+    if streaming:
+        engine.set_streaming_commit(min_deltas=2)
+    return engine
+
+
+def _write(engine):
+    with engine.transaction() as tx:
+        for i in range(0, 50, 5):
+            tx.log_delta(f"domain.users.u{i}.score", 0, i)
+        tx.log_delta("domain.total", 0, 10)
+    return tx
+
+
+def test_streamed_commit_matches_regular_commit():
+    for streaming in (False, True):
+        engine = _engine(streaming)
+        before = engine._core.state.data["domain"]
+        before_users = before["users"]
+
+        tx = _write(engine)
+        assert tx.streamed is streaming
+
+        data = engine._core.state.data["domain"]
+        assert data["users"]["u5"] == {"score": 5} and data["users"]["u6"] == {"score": 0}
+        assert data["total"] == 10 and data["items"] is before["items"]
+        # The previous version is untouched (copy-on-write).
+        assert before_users["u5"]["score"] == 0 and before["total"] == 0
+        assert engine.state.last_writer("domain.users")["tx"] == tx.id
+
+
+def test_streamed_list_indices_copy_the_list():
+    engine = _engine(True)
+    before = engine._core.state.data["domain"]["items"]
+    with engine.transaction() as tx:
+        tx.log_delta("domain.items[1]", 2, 20)
+        tx.log_delta("domain.users[u1].score", 0, 1)
+    assert tx.streamed
+    assert engine._core.state.data["domain"]["items"] == [1, 20, 3] and before == [1, 2, 3]
+    assert engine._core.state.data["domain"]["users"]["u1"]["score"] == 1
+
+
+def test_small_or_explicit_transactions_take_the_regular_path():
+    engine = _engine(True)
+    with engine.transaction() as tx:
+        tx.log_delta("domain.total", 0, 1)
+    assert not tx.streamed
+
+    with engine.transaction() as tx:
+        tx.log_delta("domain.total", 1, 2)
+        tx.log_delta("domain.users.u1.score", 0, 1)
+        tx.update(data={"domain": {"total": 3}})
+    assert engine._core.state.data["domain"]["total"] == 3
+
+
+def test_stale_streamed_fields_conflict():
+    engine = _engine(True)
+    tx = engine._core.transaction()
+    tx.__enter__()
+    tx.log_delta("domain.users.u1.score", 0, 1)
+    tx.log_delta("domain.users.u2.score", 0, 2)
+    _write(engine)
+    with pytest.raises(Exception, match="CAS Version Mismatch"):
+        tx.__exit__(None, None, None)
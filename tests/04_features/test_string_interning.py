@@ -0,0 +1,30 @@
+import theus_core
+
+from theus.engine import TheusEngine
+
+
+def test_repeated_paths_share_interned_strings():
+    engine = TheusEngine(context={"domain": {"n": 0, "label": "a"}})
+    before = theus_core.intern_stats()
+
+    for i in range(20):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": i + 1}})
+            tx.log_delta("domain.n", i, i + 1)
+
+    [delta] = tx.deltas
+    assert (delta.path, delta.op, delta.key) == ("domain.n", "SET", None)
+    assert engine.state.last_writer("domain.n")["version"] == engine.state.version
+
+    after = theus_core.intern_stats()
+    assert after["lookups"] - before["lookups"] >= 60
+    assert after["hits"] - before["hits"] >= after["lookups"] - before["lookups"] - 4
+    assert after["strings"] >= 3 and after["bytes"] > 0
+
+
+def test_purge_drops_unreferenced_strings():
+    engine = TheusEngine(context={"scratch": {"unique_key_for_purge_test": 1}})
+    del engine
+    stats = theus_core.intern_stats(purge=True)
+    assert theus_core.intern_stats()["strings"] == stats["strings"]
+    assert theus_core.intern_stats(purge=True)["purged"] == 0
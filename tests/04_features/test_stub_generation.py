@@ -0,0 +1,48 @@
+import ast
+import inspect
+from pathlib import Path
+
+import theus_core
+
+SHIPPED = Path(__file__).resolve().parents[2] / "theus" / "theus_core.pyi"
+
+
+def _top_level(text):
+    tree = ast.parse(text)
+    return {node.name: node for node in tree.body if isinstance(node, (ast.ClassDef, ast.FunctionDef))}
+
+
+def test_stubs_cover_the_registered_surface_with_types():
+    text = theus_core.generate_stubs()
+    top = _top_level(text)
+
+    exported = {
+        name for name in dir(theus_core)
+        if not name.startswith("_") and (inspect.isclass(getattr(theus_core, name)) or callable(getattr(theus_core, name)))
+    }
+    assert exported == set(top)
+
+    assert "def break_glass(self, paths: list[str], reason: str, ttl_s: float, actor: str | None = None) -> int:" in text
+    assert "def tenant(self, name: str) -> TenantHandle:" in text
+    assert "def register_physics_override(path: str, caps: int) -> None:" in text
+    assert "def effective_physics(path: str) -> tuple[str, Capability]:" in text
+    assert "class PermissionDeniedError(ContextError, PermissionError):" in text
+
+    proxy = {n.name for n in top["SupervisorProxy"].body if isinstance(n, ast.FunctionDef)}
+    assert {"__getattr__", "__setattr__", "to_dict"} <= proxy
+    tx = {n.name for n in top["Transaction"].body if isinstance(n, ast.FunctionDef)}
+    assert {"update", "update_many", "__enter__", "__exit__"} <= tx
+
+
+def test_generate_stubs_writes_file_and_matches_shipped_copy(tmp_path):
+    text = theus_core.generate_stubs()
+
+    target = tmp_path / "core.pyi"
+    assert theus_core.generate_stubs(str(target)) == text
+    assert target.read_text() == text
+
+    theus_core.generate_stubs(str(tmp_path))
+    assert (tmp_path / "theus_core.pyi").read_text() == text
+
+    # Regenerate with `python scripts/gen_stubs.py` when this fails.
+    assert SHIPPED.read_text() == text
@@ -0,0 +1,75 @@
+import pytest
+
+from theus.engine import TheusEngine
+from theus_core import ContextGuard, PermissionDeniedError
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"shared": 1}})
+    for name, plan in [("acme", "pro"), ("globex", "free")]:
+        with engine.tenant(name).transaction() as tx:
+            tx.update(data={"domain": {"plan": plan, "seats": 1}})
+    return engine
+
+
+def test_handles_root_paths_under_the_tenant():
+    engine = _engine()
+    acme = engine.tenant("acme")
+    assert engine.tenants() == ["acme", "globex"]
+    assert acme.root == "tenants.acme" and acme.path("domain.plan") == "tenants.acme.domain.plan"
+
+    with acme.transaction(actor="api") as tx:
+        assert tx.read("domain.plan") == "pro"
+        tx.update_many([("domain.seats", 5)])
+    assert engine.state.data["tenants"]["acme"]["domain"] == {"plan": "pro", "seats": 5}
+    assert engine.state.data["tenants"]["globex"]["domain"]["seats"] == 1
+    assert acme.read("domain.seats") == 5 and acme.read("domain.missing", default=0) == 0
+    assert acme.query("$.domain.*", with_paths=True) == [("domain.plan", "pro"), ("domain.seats", 5)]
+    assert engine.tenant("globex").query("domain.plan") == ["free"]
+
+    with pytest.raises(ValueError, match="Invalid tenant name"):
+        engine.tenant("internal_ops")
+    with pytest.raises(ValueError, match="Invalid tenant path"):
+        acme.read("..domain")
+
+
+def test_cross_tenant_writes_raise_even_for_admin():
+    engine = _engine()
+    with pytest.raises(PermissionDeniedError, match="tenant data is only writable through tenant handles") as err:
+        with engine.transaction(admin=True) as tx:
+            tx.update(data={"tenants": {"globex": {"domain": {"plan": "pro"}}}})
+    assert err.value.code == "TH210" and err.value.rule == "tenant" and err.value.path == "tenants.globex"
+    with pytest.raises(PermissionDeniedError):
+        engine.compare_and_swap(engine.state.version, data={"tenants": {"acme": {"domain": {}}}})
+    assert engine.state.data["tenants"]["globex"]["domain"]["plan"] == "free"
+
+    acme = engine.tenant("acme")
+    with pytest.raises(Exception, match="engine-wide"):
+        with acme.transaction() as tx:
+            tx.update(heavy={"frame": 1})
+    inputs, outputs = acme.validate_contract(["domain.plan", "tenants.acme.domain.seats"], ["domain"])
+    assert inputs == ["tenants.acme.domain.plan", "tenants.acme.domain.seats"] and outputs == ["tenants.acme.domain"]
+    with pytest.raises(PermissionDeniedError, match="outside tenant root"):
+        acme.validate_contract(outputs=["tenants.globex.domain"])
+
+
+def test_tenant_reads_stay_under_the_root_while_unscoped_reads_see_all():
+    engine = _engine()
+    acme = engine.tenant("acme")
+    assert acme.read("tenants.globex.domain.plan", default="hidden") == "hidden"
+    assert acme.query("$..plan") == ["pro"]
+    with acme.transaction(isolation="repeatable_read") as tx:
+        assert tx.read("tenants.globex.domain.plan", default="hidden") == "hidden"
+        assert tx.snapshot is None
+        # Guards of a tenant transaction refuse other roots even when the contract allows them.
+        guard = ContextGuard({"tenants": engine.state.data["tenants"]}, ["tenants"], [], tx=tx)
+        assert guard.tenants.acme.domain.plan == "pro"
+        with pytest.raises(PermissionDeniedError, match="outside tenant root") as err:
+            guard["tenants"]["globex"]
+        assert err.value.rule == "tenant"
+    with engine.transaction(isolation="repeatable_read") as tx:
+        assert tx.snapshot is not None
+
+    # Unscoped access is operator access (documented in tenancy.rs).
+    assert sorted(engine.state.data["tenants"]) == ["acme", "globex"]
+    assert sorted(engine.query("$.tenants.*.domain.plan")) == ["free", "pro"]
@@ -0,0 +1,58 @@
+import pytest
+
+from theus.engine import TheusEngine
+from theus_core import OutboxMsg, QuotaExceededError, TenantQuotaExceededError
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {}})
+    for name in ("acme", "globex"):
+        with engine.tenant(name).transaction() as tx:
+            tx.update(data={"domain": {"users": {"u1": {"name": "Ann"}}}})
+    return engine
+
+
+def test_usage_is_metered_per_tenant():
+    engine = _engine()
+    acme = engine.tenant("acme")
+    before = engine.tenant_usage()
+    assert before["acme"]["keys"] == 4 and before["acme"]["commits"] == 1 and before["acme"]["bytes"] > 0
+
+    with acme.transaction() as tx:
+        tx.update(data={"domain": {"users": {"u2": {"name": "Bob", "email": "b@x"}}}})
+        tx.outbox.add(OutboxMsg("user_created", "u2"))
+    usage = engine.tenant_usage()
+    assert usage["acme"]["keys"] == 7 and usage["acme"]["commits"] == 2 and usage["acme"]["outbox"] == 1
+    assert usage["acme"]["bytes"] > before["acme"]["bytes"]
+    assert usage["globex"] == before["globex"]
+    assert usage["acme"]["quota"] == {"max_keys": None, "max_bytes": None, "max_commits": None, "max_outbox": None}
+
+    engine.load_state(engine.dumps_state())
+    assert engine.tenant_usage()["acme"]["keys"] == 7 and engine.tenant_usage()["acme"]["commits"] == 2
+
+
+def test_quotas_raise_tenant_specific_errors():
+    engine = _engine()
+    acme = engine.tenant("acme")
+    engine.set_tenant_quota("acme", max_keys=5, max_outbox=1)
+
+    with pytest.raises(TenantQuotaExceededError, match="'acme' needs 6 keys") as err:
+        with acme.transaction() as tx:
+            tx.update(data={"domain": {"users": {"u2": {"name": "Bob"}}}})
+    assert isinstance(err.value, QuotaExceededError) and err.value.code == "TH302"
+    assert (err.value.tenant, err.value.resource, err.value.limit, err.value.path) == ("acme", "keys", 5, "tenants.acme")
+    assert "u2" not in acme.read("domain.users")
+
+    # Other tenants are unaffected; shrinking stays allowed under a lowered quota.
+    with engine.tenant("globex").transaction() as tx:
+        tx.update(data={"domain": {"users": {"u2": {}, "u3": {}}}})
+    engine.set_tenant_quota("acme", max_keys=1, max_outbox=1)
+    with acme.transaction() as tx:
+        tx.update(data={"domain": {"users": {"u1": {"name": None}}}})
+
+    with pytest.raises(TenantQuotaExceededError, match="outbox"):
+        with acme.transaction() as tx:
+            tx.outbox.add(OutboxMsg("a", None))
+            tx.outbox.add(OutboxMsg("b", None))
+    with pytest.raises(KeyError):
+        engine.set_tenant_quota("initech", max_commits=1)
@@ -0,0 +1,70 @@
+import pytest
+from theus_core import (
+    AuditSystem, ContextError, Transaction, WriteTimeoutError,
+    advance_global_clock_ms, global_clock, use_global_system_clock, use_global_test_clock,
+)
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    engine = TheusEngine()
+    use_global_test_clock(start_ms=1_000_000)
+    return engine
+
+
+def test_advance_moves_wall_time():
+    engine = _engine()
+    try:
+        assert global_clock() == ("test", 1_000_000)
+        assert advance_global_clock_ms(5000) == 1_005_000
+        audit = AuditSystem()
+        audit.log("K", "x")
+        assert audit.get_logs()[-1].timestamp == 1005.0
+    finally:
+        use_global_system_clock()
+    assert global_clock()[0] == "system"
+    with pytest.raises(ContextError, match="test clock"):
+        advance_global_clock_ms(1)
+
+
+def test_write_timeout_without_sleeping():
+    engine = _engine()
+    try:
+        with pytest.raises(WriteTimeoutError, match="5001ms"):
+            with engine.transaction(write_timeout_ms=5000) as tx:
+                tx.update(data={"domain": {"a": 1}})
+                advance_global_clock_ms(5001)
+        assert engine.state.version == 0
+
+        with engine.transaction(write_timeout_ms=5000) as tx:
+            tx.update(data={"domain": {"a": 1}})
+            advance_global_clock_ms(5000)
+        assert engine.state.version == 1
+    finally:
+        use_global_system_clock()
+
+
+def test_idempotency_ttl_expires_on_advance():
+    engine = _engine()
+    try:
+        engine.set_outbox_retention(60_000)
+        engine.mark_processed("k1")
+        advance_global_clock_ms(59_000)
+        assert engine.is_processed("k1")
+        advance_global_clock_ms(2_000)
+        assert not engine.is_processed("k1")
+    finally:
+        use_global_system_clock()
+
+
+def test_leak_ages_follow_test_clock():
+    engine = _engine()
+    try:
+        tx = Transaction(engine._core)
+        tx.__enter__()
+        advance_global_clock_ms(250)
+        assert engine.open_transactions()[0]["age_ms"] == 250
+        tx.__exit__(None, None, None)
+    finally:
+        use_global_system_clock()
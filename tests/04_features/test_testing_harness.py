@@ -0,0 +1,66 @@
+import pytest
+from theus_core import global_clock
+from theus_core.testing import Harness
+
+from theus.contracts import OutboxMsg, process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+def bump(ctx):
+    ctx.domain.a = ctx.domain.a + 1
+    ctx.outbox.add(OutboxMsg("bumped", {"a": ctx.domain.a}))
+
+
+@process(inputs=["domain.a"], outputs=["domain.a"])
+def sneaky(ctx):
+    return ctx.constants
+
+
+def test_harness_captures_deltas_outbox_and_clock():
+    with Harness({"domain": {"a": 1, "items": []}}, start_ms=5_000) as h:
+        core = h.engine
+        assert global_clock() == ("test", 5_000)
+        with core.transaction() as tx:
+            tx.update(data={"domain": {"a": 2}})
+            tx.outbox.add(OutboxMsg("t", {"x": 1}))
+
+        record = h.assert_delta("domain.a", 2)
+        assert record["paths"] == ["domain.a"] and record["ts_ms"] == 5_000
+        with pytest.raises(AssertionError, match="expected 3"):
+            h.assert_delta("domain.a", 3)
+        # Hydration predates the harness baseline.
+        with pytest.raises(AssertionError, match="domain.items"):
+            h.assert_delta("domain.items")
+        assert [m.topic for m in h.outbox()] == ["t"] and h.outbox("other") == []
+
+        assert h.advance_ms(250) == 5_250
+        h.clear()
+        assert h.outbox() == []
+        with pytest.raises(AssertionError):
+            h.assert_delta("domain.a")
+    assert global_clock()[0] == "system"
+
+
+@pytest.mark.asyncio
+async def test_harness_wraps_engine_and_records_denials():
+    engine = TheusEngine(context={"domain": {"a": 1}}, strict_guards=True)
+    engine.register(bump)
+    engine.register(sneaky)
+    with Harness(engine=engine) as h:
+        assert h.engine is engine
+        await engine.execute("bump")
+        h.assert_delta("domain.a", 2)
+        assert [m.payload for m in h.outbox("bumped")] == [{"a": 2}]
+
+        with pytest.raises(AssertionError, match="No access"):
+            h.assert_denied("constants")
+        with pytest.raises(PermissionError):
+            await engine.execute("sneaky")
+        assert h.assert_denied("constants") == "Illegal Read: 'constants'"
+        assert h.denials
+
+    # Closed harnesses stop capturing.
+    with pytest.raises(PermissionError):
+        await engine.execute("sneaky")
+    assert len(h.denials) == 1
@@ -0,0 +1,61 @@
+import asyncio
+
+from theus_core import advance_global_clock_ms, use_global_system_clock, use_global_test_clock
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.runs"], outputs=["domain.runs"])
+def reconcile(ctx, step=1):
+    ctx.domain.runs = ctx.domain.runs + step
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"runs": 0}})
+    engine.register(reconcile)
+    use_global_test_clock(start_ms=1_000_000)
+    return engine
+
+
+def test_timers_fire_signals_and_processes_on_schedule():
+    engine = _engine()
+    try:
+        every = engine.schedule_process("reconcile", every_ms=300_000, kwargs={"step": 2})
+        once = engine.schedule_signal("cmd_saga_timeout", {"saga": "s1"}, delay_ms=3_600_000, timer_id="saga-s1")
+        assert once == "saga-s1"
+        assert asyncio.run(engine.fire_timers()) == []
+
+        advance_global_clock_ms(300_000)
+        assert asyncio.run(engine.fire_timers()) == [every]
+        assert engine.state.data["domain"]["runs"] == 2
+
+        # Missed periods collapse into one run.
+        advance_global_clock_ms(3_300_000)
+        assert asyncio.run(engine.fire_timers()) == [every, "saga-s1"]
+        assert engine.state.data["domain"]["runs"] == 4
+        [signal] = engine.signals.claim(5, "test")
+        assert signal["payload"] == {"saga": "s1"}
+
+        [pending] = engine.timers()
+        assert (pending["id"], pending["fired"], pending["fire_at_ms"]) == (every, 2, 1_000_000 + 13 * 300_000)
+        assert engine.cancel_timer(every) and not engine.cancel_timer(every)
+        assert engine.timers() == []
+    finally:
+        use_global_system_clock()
+
+
+def test_pending_timers_survive_a_state_snapshot():
+    engine = _engine()
+    try:
+        engine.schedule_signal("cmd_wake", delay_ms=10_000, timer_id="wake")
+        engine.schedule_process("reconcile", at_ms=1_020_000)
+        restarted = TheusEngine.loads_state(engine.dumps_state(), context={"domain": {"runs": 0}})
+        restarted.register(reconcile)
+        assert [t["id"] for t in restarted.timers()] == ["timer-1", "wake"]
+        assert restarted.schedule_signal("cmd_wake", delay_ms=60_000) == "timer-2"  # ids skip restored ones
+
+        advance_global_clock_ms(20_000)
+        assert asyncio.run(restarted.fire_timers()) == ["wake", "timer-1"]
+        assert restarted.state.data["domain"]["runs"] == 1
+    finally:
+        use_global_system_clock()
@@ -0,0 +1,68 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.a", "domain.log"], outputs=["domain.a", "domain.log"])
+def work(ctx):
+    ctx.domain.a = ctx.domain.a + 1
+    ctx.domain.log.append(ctx.domain.a)
+    try:
+        ctx.config
+    except PermissionError:
+        pass
+
+
+@process(inputs=["domain.a"], outputs=[])
+def broken(ctx):
+    ctx.domain.a
+    raise RuntimeError("boom")
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"a": 1, "log": []}, "config": {"k": 1}}, strict_guards=True)
+    engine.register(work)
+    engine.register(broken)
+    return engine
+
+
+def test_trace_records_the_next_run_only():
+    engine = _engine()
+    assert engine.trace_report("work") is None
+    engine.trace("work")
+    assert engine.pending_traces() == ["work"]
+    asyncio.run(engine.execute("work"))
+
+    report = engine.trace_report("work")
+    assert engine.pending_traces() == []
+    assert (report["process"], report["status"], report["dropped"]) == ("work", "ok", 0)
+    events = report["events"]
+    assert {"path": "domain.a", "access": "write", "allowed": True, "rule": None} in [
+        {k: e[k] for k in ("path", "access", "allowed", "rule")} for e in events
+    ]
+    assert ("domain.log", "write") in {(e["path"], e["access"]) for e in events}
+    denied = [e for e in events if not e["allowed"]]
+    assert [(e["path"], e["rule"]) for e in denied] == [("config", "contract")]
+    assert report["denied"] == 1 and report["reads"] + report["writes"] == len(events)
+    assert all(e["duration_us"] >= 0 for e in events)
+
+    asyncio.run(engine.execute("work"))
+    assert engine.trace_report("work")["tx_id"] == report["tx_id"]
+
+
+def test_trace_caps_events_and_reports_failures():
+    engine = _engine()
+    engine.trace("work", max_events=2)
+    engine.trace("broken")
+    asyncio.run(engine.execute("work"))
+    with pytest.raises(RuntimeError, match="boom"):
+        asyncio.run(engine.execute("broken"))
+
+    capped = engine.trace_report("work")
+    assert len(capped["events"]) == 2 and capped["dropped"] > 0
+    failed = engine.trace_report("broken")
+    assert failed["status"] == "error" and "boom" in failed["error"]
+    assert failed["events"][0]["path"] == "domain"
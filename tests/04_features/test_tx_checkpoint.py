@@ -0,0 +1,68 @@
+import asyncio
+
+import pytest
+from theus_core import AuditSystem
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.rows", "domain.stage"], outputs=["domain.rows", "domain.stage"])
+def import_batch(ctx):
+    ctx.domain.rows.append("a")
+    ctx.domain.stage = "loaded"
+    ctx.checkpoint()
+    ctx.domain.rows.append("b")
+    ctx.domain.stage = "done"
+    raise RuntimeError("late failure")
+
+
+def _engine():
+    engine = TheusEngine(context={"domain": {"rows": [], "stage": "new", "n": 0}})
+    engine.register(import_batch)
+    return engine
+
+
+def test_late_failure_commits_the_checkpointed_writes():
+    engine = _engine()
+    with pytest.raises(RuntimeError, match="late failure") as info:
+        asyncio.run(engine.execute("import_batch", partial_commit=True))
+
+    domain = engine.state.data["domain"]
+    assert (domain["rows"], domain["stage"]) == (["a"], "loaded")
+    checkpoint = info.value.rollback_report["checkpoint"]
+    assert checkpoint["index"] == 1 and checkpoint["error"] is None
+    assert checkpoint["version"] == engine.state.version
+
+    logs = [(e.key, e.message) for e in AuditSystem().get_logs()]
+    assert any(k == "TX_CHECKPOINT" and "checkpoint 1" in m for k, m in logs)
+    assert any(k == "TX_PARTIAL_COMMIT" and f"version {engine.state.version}" in m for k, m in logs)
+
+    # Without the policy the whole run rolls back.
+    fresh = _engine()
+    with pytest.raises(RuntimeError, match="partial_commit"):
+        asyncio.run(fresh.execute("import_batch"))
+    assert fresh.state.data["domain"]["rows"] == []
+
+
+def test_transaction_keeps_the_last_checkpoint_only():
+    engine = _engine()
+    with pytest.raises(ValueError):
+        with engine.transaction(partial_commit=True) as tx:
+            tx.update(data={"domain": {"n": 1}})
+            assert tx.checkpoint() == 1
+            tx.update(data={"domain": {"n": 2}})
+            tx.emit("progress", {"n": 2})
+            assert tx.checkpoint() == 2
+            tx.update(data={"domain": {"n": 3}})
+            raise ValueError("stop")
+
+    assert engine.state.data["domain"]["n"] == 2
+    assert tx.commit_status == "partial" and tx.committed_version == engine.state.version
+    assert tx.last_rollback_report()["checkpoint"]["index"] == 2
+
+    with pytest.raises(ValueError):
+        with engine.transaction(partial_commit=True) as tx:
+            tx.update(data={"domain": {"n": 9}})
+            raise ValueError("no checkpoint")
+    assert engine.state.data["domain"]["n"] == 2 and tx.commit_status is None
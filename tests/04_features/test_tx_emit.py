@@ -0,0 +1,46 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def test_emit_fires_only_after_commit():
+    engine = TheusEngine()
+    seen = []
+
+    def watcher(topic, payload):
+        # Committed state must already be visible
+        seen.append((topic, payload, engine.state.data["domain"]["n"]))
+
+    engine.add_event_watcher(watcher)
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 1}})
+        tx.emit("counter.changed", {"n": 1})
+        assert seen == []
+
+    assert seen == [("counter.changed", {"n": 1}, 1)]
+
+
+def test_emit_discarded_on_rollback():
+    engine = TheusEngine()
+    seen = []
+    engine.add_event_watcher(lambda t, p: seen.append(t))
+
+    with pytest.raises(ValueError):
+        with engine.transaction() as tx:
+            tx.emit("never", None)
+            raise ValueError("abort")
+
+    assert seen == []
+
+
+def test_remove_event_watcher():
+    engine = TheusEngine()
+    seen = []
+    watcher = lambda t, p: seen.append(t)  # noqa: E731
+    engine.add_event_watcher(watcher)
+    assert engine.remove_event_watcher(watcher)
+    assert not engine.remove_event_watcher(watcher)
+
+    with engine.transaction() as tx:
+        tx.emit("ignored", None)
+    assert seen == []
@@ -0,0 +1,110 @@
+import os
+import tempfile
+
+import pytest
+from theus_core import ContextError, replay_recording
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+
+
+@process(inputs=["domain.n"], outputs=["domain.n"])
+def incr(ctx):
+    ctx.domain.n = ctx.domain.n + 1
+
+
+@process(inputs=["domain.tags"], outputs=["domain.tags"])
+def tag(ctx):
+    ctx.domain.tags.append("x")
+
+
+def _recording_path():
+    fd, path = tempfile.mkstemp(suffix=".theusrec")
+    os.close(fd)
+    return path
+
+
+@pytest.mark.asyncio
+async def test_replay_reproduces_committed_state():
+    path = _recording_path()
+    engine = TheusEngine(context={"domain": {"n": 0, "tags": []}})
+    engine.register(incr)
+    engine.register(tag)
+
+    engine.start_recording(path)
+    assert engine.recording == path
+    await engine.execute("incr")
+    await engine.execute("tag")
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"label": ("a", 1)}})
+    with pytest.raises(RuntimeError):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": 99}})
+            raise RuntimeError("boom")
+    info = engine.stop_recording()
+    assert info["path"] == path and info["records"] > 0
+    assert engine.recording is None and engine.stop_recording() is None
+
+    replica = TheusEngine(context={"domain": {"n": 0, "tags": []}})
+    report = replica.replay_recording(path)
+    assert replica.state.data["domain"]["n"] == 1
+    assert replica.state.data["domain"]["tags"] == ["x"]
+    assert replica.state.data["domain"]["label"] == ("a", 1)
+    assert report["replayed"] == 3
+    assert [t["replayed"] for t in report["transactions"]] == ["commit", "commit", "commit", "skipped"]
+    assert report["transactions"][-1]["recorded_error"] == "RuntimeError: boom"
+    assert not report["truncated"] and report["unfinished"] == []
+    os.remove(path)
+
+
+def test_values_are_captured_when_recorded():
+    path = _recording_path()
+    engine = TheusEngine()
+    engine.start_recording(path)
+    payload = {"domain": {"items": [1]}}
+    with engine.transaction() as tx:
+        tx.update(data=payload)
+    payload["domain"]["items"].append(2)
+    engine.stop_recording()
+
+    replica = TheusEngine()
+    replay_recording(replica, path)
+    assert replica.state.data["domain"]["items"] == [1]
+    os.remove(path)
+
+
+def test_truncated_recording_and_replay_failures_are_reported():
+    path = _recording_path()
+    engine = TheusEngine()
+    engine.start_recording(path)
+    with pytest.raises(ContextError, match="Already recording"):
+        engine.start_recording(path)
+    for i in range(2):
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"i": i}})
+    engine.stop_recording()
+    with open(path, "ab") as f:
+        f.write(b"\x00\x00\x00\x10partial")
+
+    replica = TheusEngine()
+    replica.inject_fault("commit", times=1)
+    with pytest.raises(ContextError, match="Injected"):
+        replica.replay_recording(path)
+
+    replica = TheusEngine()
+    replica.inject_fault("commit", times=1)
+    report = replica.replay_recording(path, stop_on_error=False)
+    assert report["truncated"]
+    assert [t["replayed"] for t in report["transactions"]] == ["rollback", "commit"]
+    assert "Injected" in report["transactions"][0]["error"]
+    assert replica.state.data["domain"]["i"] == 1
+    os.remove(path)
+
+
+def test_rejects_foreign_files():
+    path = _recording_path()
+    with open(path, "wb") as f:
+        f.write(TheusEngine().dumps_state())
+    with pytest.raises(ValueError, match="Corrupt recording"):
+        replay_recording(TheusEngine(), path)
+    os.remove(path)
@@ -0,0 +1,65 @@
+import logging
+
+from theus.engine import TheusEngine
+from theus_core import AuditSystem
+
+KEYS = {"shadows", "shadow_copy_ms", "shadow_bytes", "shadow_evictions",
+        "shadow_hash_skips", "deltas", "inference_ms", "schema_ms", "commit_ms"}
+
+
+def test_stats_break_down_the_transaction():
+    engine = TheusEngine(context={"domain": {"a": {"x": 1}, "b": {"y": 2}}})
+    data = engine._core.state.data["domain"]
+    with engine._core.transaction() as tx:
+        tx.get_shadow(data["a"], "domain.a")["x"] = 5
+        tx.get_shadow(data["b"], "domain.b")
+        tx.update(data={"domain": {"c": 3}})
+        assert tx.stats()["commit_ms"] == 0.0
+
+    stats = tx.stats()
+    assert set(stats) == KEYS
+    assert stats["shadows"] == 2 and stats["deltas"] == 1
+    assert stats["shadow_copy_ms"] > 0 and stats["inference_ms"] > 0
+    assert stats["commit_ms"] >= stats["inference_ms"]
+
+
+def test_slow_commits_report_their_breakdown():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    seen = []
+    engine.set_slow_commit_threshold(0.0, callback=seen.append)
+    with engine.transaction(tags={"request_id": "r1"}) as tx:
+        tx.update(data={"domain": {"n": 1}})
+
+    assert len(seen) == 1
+    info = seen[0]
+    assert KEYS <= set(info) and info["threshold_ms"] == 0.0
+    assert info["tags"] == {"request_id": "r1"} and info["commit_ms"] > 0
+    assert any(e.key == "SLOW_COMMIT" and f"#{info['tx_id']}" in e.message for e in AuditSystem().get_logs())
+
+    engine.set_slow_commit_threshold(60_000.0, callback=seen.append)
+    with engine.transaction() as tx:
+        tx.update(data={"domain": {"n": 2}})
+    assert len(seen) == 1
+
+
+def test_slow_commit_without_callback_logs_a_warning():
+    records = []
+
+    class Capture(logging.Handler):
+        def emit(self, record):
+            records.append(record)
+
+    logger = logging.getLogger("theus.engine")
+    handler = Capture()
+    logger.addHandler(handler)
+    try:
+        engine = TheusEngine(context={"domain": {"n": 0}})
+        engine.set_slow_commit_threshold(0.0)
+        with engine.transaction() as tx:
+            tx.update(data={"domain": {"n": 1}})
+    finally:
+        logger.removeHandler(handler)
+
+    assert len(records) == 1 and records[0].levelno == logging.WARNING
+    assert "Slow commit" in records[0].getMessage()
+    assert KEYS <= set(records[0].tx_stats)
@@ -0,0 +1,38 @@
+import pytest
+import theus_core
+
+from theus.engine import TheusEngine
+
+
+def test_tags_reach_deltas_outbox_audit_and_blame():
+    engine = TheusEngine(context={"domain": {"n": 0, "payroll": {"ann": 1}}})
+    engine.require_approval(["domain.payroll"])
+    tags = {"request_id": "req-42", "attempt": 2}
+
+    with engine.transaction(actor="api", tags=tags) as tx:
+        tx.update(data={"domain": {"n": 1, "payroll": {"ann": 2}}})
+        tx.log_delta("domain.n", 0, 1)
+        tx.outbox.add(theus_core.OutboxMsg("billing", {"n": 1}))
+
+    assert tx.tags == {"request_id": "req-42", "attempt": "2"}
+    assert [d.tags for d in tx.deltas] == [tx.tags]
+    [msg] = engine.outbox.drain()
+    assert msg.tags == tx.tags
+
+    proposed = theus_core.AuditSystem().get_logs(tags={"request_id": "req-42"})
+    assert [e.key for e in proposed] == ["APPROVAL_PROPOSED"]
+
+    [entry] = engine.blame("domain.n", tags={"request_id": "req-42"})
+    assert entry["tags"] == tx.tags and entry["tx"] == tx.id
+    assert engine.blame("domain.n", tags={"request_id": "other"}) == []
+    assert engine.blame("domain.n")[-1]["tags"] == {}
+
+
+def test_untagged_transactions_have_empty_tags():
+    engine = TheusEngine(context={"domain": {"n": 0}})
+    with engine.transaction() as tx:
+        tx.outbox.add(theus_core.OutboxMsg("t", 1))
+    assert tx.tags == {}
+    assert engine.outbox.drain()[0].tags == {}
+    with pytest.raises(TypeError):
+        engine.transaction(tags={1: "x"}).__enter__()
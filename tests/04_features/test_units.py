@@ -0,0 +1,68 @@
+import asyncio
+
+import pytest
+
+from theus.contracts import process
+from theus.engine import TheusEngine
+from theus_core import MiddlewareVetoError
+
+
+@process(inputs=["domain.job"], outputs=["domain.job"])
+def configure(ctx, timeout=None, size=None):
+    if timeout is not None:
+        ctx.domain.job["timeout"] = timeout
+    if size is not None:
+        ctx.domain.job["size"] = size
+    return ctx.domain.job["timeout"]
+
+
+class Quantity:
+    """Minimal pint-like quantity without `.to()`."""
+
+    def __init__(self, magnitude, units):
+        self.magnitude, self.units = magnitude, units
+
+
+def _engine(**kwargs):
+    engine = TheusEngine(
+        context={"domain": {"job": {"timeout": 0, "size": 0}}},
+        units={"domain.job.timeout": "ms", "domain.job.size": "MB"},
+        **kwargs,
+    )
+    engine.register(configure)
+    return engine
+
+
+def _job(engine):
+    return engine.state.data["domain"]["job"]
+
+
+def test_tagged_quantities_convert_to_the_canonical_unit():
+    engine = _engine()
+    asyncio.run(engine.execute("configure", timeout=(2, "s"), size=Quantity(512, "KB")))
+    assert _job(engine) == {"timeout": 2000, "size": 0.512}
+    assert isinstance(_job(engine)["timeout"], int)
+
+    asyncio.run(engine.execute("configure", timeout=(1500, "us")))
+    assert abs(_job(engine)["timeout"] - 1.5) < 1e-9
+
+    # Bare numbers are already canonical.
+    asyncio.run(engine.execute("configure", timeout=250))
+    assert _job(engine)["timeout"] == 250
+
+
+def test_wrong_dimension_is_vetoed_and_reads_can_be_annotated():
+    engine = _engine()
+    with pytest.raises(MiddlewareVetoError, match="measured in ms"):
+        asyncio.run(engine.execute("configure", timeout=(3, "MB")))
+    with pytest.raises(MiddlewareVetoError, match="Unknown unit"):
+        asyncio.run(engine.execute("configure", timeout=(3, "fortnight")))
+    assert _job(engine)["timeout"] == 0
+
+    engine.set_units({"domain.job.timeout": "s"}, annotate_reads=True)
+    assert asyncio.run(engine.execute("configure", timeout=(90, "min"))) == (5400, "s")
+
+    engine.set_units({"domain.job.timeout": None, "domain.job.size": None})
+    assert asyncio.run(engine.execute("configure", timeout=(1, "h"))) == (1, "h")
+    with pytest.raises(ValueError):
+        engine.set_units({"domain.job.timeout": "parsec"})
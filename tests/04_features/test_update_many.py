@@ -0,0 +1,47 @@
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    users = {f"u{i}": {"score": 0, "name": f"user{i}"} for i in range(100)}
+    return TheusEngine(context={"domain": {"users": users, "total": 0}})
+
+
+def test_bulk_writes_merge_into_state_with_one_delta_per_path():
+    engine = _engine()
+    with engine.transaction(tags={"job": "rescore"}) as tx:
+        written = tx.update_many([(f"domain.users.u{i}.score", i) for i in range(0, 100, 3)] + [("domain.total", 99)])
+        assert written == 35
+
+    users = engine.state.data["domain"]["users"]
+    assert users["u3"] == {"score": 3, "name": "user3"}
+    assert users["u4"]["score"] == 0 and engine.state.data["domain"]["total"] == 99
+    assert len(tx.deltas) == 35
+    assert tx.deltas[0].path == "domain.users.u0.score" and tx.deltas[0].old_value is None
+    assert {d.tags["job"] for d in tx.deltas} == {"rescore"}
+
+
+def test_bracket_paths_and_later_writes_win():
+    engine = _engine()
+    with engine.transaction() as tx:
+        tx.update_many([("domain.users[u1].score", 1), ("domain.users.u1.score", 2)])
+    assert engine.state.data["domain"]["users"]["u1"]["score"] == 2
+
+
+def test_zone_checks_reject_the_whole_batch():
+    engine = _engine()
+    with pytest.raises(PermissionError, match="domain.const_limits"):
+        with engine.transaction() as tx:
+            tx.update_many([("domain.total", 1), ("domain.const_limits.max", 5)])
+    assert engine.state.data["domain"]["total"] == 0
+
+    with pytest.raises(PermissionError):
+        with engine.transaction(admin=True) as tx:
+            tx.update_many([("domain.const_limits.max", 5)])
+    with engine.transaction(admin=True) as tx:
+        tx.update_many([("domain.log_events.last", "x")])
+
+    with pytest.raises(ValueError, match="Invalid path"):
+        with engine.transaction() as tx:
+            tx.update_many([("domain..x", 1)])
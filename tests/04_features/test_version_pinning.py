@@ -0,0 +1,52 @@
+import gc
+
+import pytest
+
+from theus.engine import TheusEngine
+
+
+def _engine():
+    return TheusEngine(context={"domain": {"a": 1, "cfg": {"x": 1}}})
+
+
+def _commit(engine, value):
+    with engine.transaction() as tx:
+        tx.update_many([("domain.a", value), ("domain.cfg.x", value)])
+
+
+def test_pinned_view_reads_its_version_while_writers_continue():
+    engine = _engine()
+    view = engine.pin()
+    _commit(engine, 2)
+    _commit(engine, 3)
+    assert view.version == 1
+    assert view.domain.a == 1 and view.domain.cfg.x == 1
+    assert view.read("domain.cfg.x") == 1 and view.read("domain.nope", 0) == 0
+    assert engine.pinned_versions()["pinned"] == {1: 1}
+
+    del view
+    gc.collect()
+    assert engine.pinned_versions()["pinned"] == {}
+
+
+def test_retention_and_pruning_protection():
+    engine = _engine()
+    with pytest.raises(KeyError, match="not retained"):
+        _commit(engine, 2)
+        engine.pin(1)
+
+    engine.set_version_retention(2)
+    for v in (3, 4, 5):
+        _commit(engine, v)
+    assert engine.pinned_versions()["retained"] == [4, 5]
+
+    with engine.pin(4) as old:
+        for v in (6, 7, 8):
+            _commit(engine, v)
+        assert engine.pinned_versions()["retained"] == [7, 8]
+        assert engine.pin(4).domain.a == 4
+        assert old.domain.a == 4
+    assert not old.pinned
+    assert 4 not in engine.pinned_versions()["pinned"]
+    with pytest.raises(KeyError):
+        engine.pin(4)
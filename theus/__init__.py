@@ -1,6 +1,7 @@
 from .engine import TheusEngine
 from .contracts import process, ContractViolationError
 from .context import BaseSystemContext, BaseGlobalContext, BaseDomainContext
+from .structures import ProcessResult
 # context module might be broken too if I touched it? (I didn't).
 # But locks module?
 # Let's keep minimal valid imports.
@@ -37,6 +38,7 @@ __all__ = [
     "BaseSystemContext",
     "BaseGlobalContext",
     "BaseDomainContext",
+    "ProcessResult",
     "SignalHub",
     "SignalReceiver",
     "SchemaViolationError",
@@ -55,8 +55,9 @@ if TYPE_CHECKING:
             """Write a generic message to the Ring Buffer."""
             ...
 
-        def get_logs(self) -> List[AuditLogEntry]:
-            """Retrieve all logs from the Ring Buffer."""
+        def get_logs(self, tags: Optional[dict] = None) -> List[AuditLogEntry]:
+            """Retrieve all logs from the Ring Buffer (only events of transactions
+            carrying all of `tags`, if given)."""
             ...
 
         @property
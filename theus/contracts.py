@@ -1,4 +1,4 @@
-from typing import List, Callable
+from typing import List, Callable, Optional
 import functools
 import inspect
 from enum import Enum
@@ -8,7 +8,7 @@ try:
 except ImportError:
 
     class OutboxMsg:
-        def __init__(self, topic, payload):
+        def __init__(self, topic, payload, schema=None, content_type=None):
             pass
 
 
@@ -62,6 +62,7 @@ class ProcessContract:
         errors: List[str] = None,
         side_effects: List[str] = None,
         parallel: bool = False,
+        effects: Optional[List[str]] = None,
     ):
         self.inputs = inputs
         self.outputs = outputs
@@ -69,6 +70,9 @@ class ProcessContract:
         self.errors = errors or []
         self.side_effects = side_effects or []
         self.parallel = parallel
+        # [v3.6] Enforced effect budget ("state", "outbox", "log", "file_read",
+        # "file_write", "network", "subprocess"); None = not budgeted.
+        self.effects = list(effects) if effects is not None else None
 
 
 class AdminTransaction:
@@ -98,6 +102,7 @@ def process(
     errors: List[str] = None,
     side_effects: List[str] = None,
     parallel: bool = False,
+    effects: Optional[List[str]] = None,
 ):
     # Support bare decorator usage @process
     if callable(inputs):
@@ -159,7 +164,7 @@ def process(
 
     def decorator(func: Callable):
         func._pop_contract = ProcessContract(
-            inputs, outputs, semantic, errors, side_effects, parallel
+            inputs, outputs, semantic, errors, side_effects, parallel, effects
         )
 
         # Pre-compute signature parameters
@@ -1,7 +1,9 @@
 import os
 import sys
+import logging
+import random
 import dataclasses
-from contextlib import contextmanager
+from contextlib import contextmanager, nullcontext
 
 # Load Core Rust Module
 try:
@@ -21,15 +23,73 @@ except ImportError as e:
     print(f"WARNING: 'theus_core' not found. Reason: {e}")
     print("Running in Pure Python Fallback (Slower).")
 
-from theus.context import BaseSystemContext, TransactionError, NamespaceRegistry
+from theus.structures import ProcessResult
+from theus.context import BaseSystemContext, TransactionError, NamespaceRegistry, PYTHON_PHYSICS_OVERRIDES
 from theus.contracts import SemanticType, ContractViolationError
 from theus.guards import ContextGuard
 
+
+def _is_retryable_conflict(err):
+    """[v3.6] CAS/OCC conflicts and busy signals are retried by execute(). Typed errors
+    come from the Rust core; the message check covers plain ContextErrors raised by hand."""
+    if _HAS_RUST_CORE and isinstance(err, (theus_core.ConflictError, theus_core.BusyError)):
+        return True
+    err_msg = str(err)
+    return "CAS Version Mismatch" in err_msg or "System Busy" in err_msg
+
 # [v3.3 Compatibility] Export ContextGuard as SupervisorProxy for legacy/manual transactions
 SupervisorProxy = ContextGuard
 
 SecurityViolationError = ContractViolationError
 
+_LOG_BUDGET_INSTALLED = False
+
+
+def _install_log_budget():
+    """[v3.6] Route `logging` records through the effect budget of the running process."""
+    global _LOG_BUDGET_INSTALLED
+    if _LOG_BUDGET_INSTALLED:
+        return
+    import logging
+
+    previous = logging.getLogRecordFactory()
+
+    def budgeted_record(*args, **kwargs):
+        theus_core.check_effect("log")
+        return previous(*args, **kwargs)
+
+    logging.setLogRecordFactory(budgeted_record)
+    _LOG_BUDGET_INSTALLED = True
+
+
+def _path_covers(outer, inner):
+    """True if `inner` is `outer` or lies below it (segment-wise: "a.b" covers "a.b.c", not "a.bc")."""
+    return inner == outer or inner.startswith(outer + ".") or inner.startswith(outer + "[")
+
+
+def _contract_covers(pattern, path):
+    """Does a contract path (dotted, `[key]` or wildcard) cover `path`?"""
+    import fnmatch
+    pattern = pattern.replace("[", ".").replace("]", "")
+    path = path.replace("[", ".").replace("]", "")
+    return _path_covers(pattern, path) or fnmatch.fnmatch(path, pattern)
+
+
+def _const_paths(value, prefix="", depth=0):
+    """[v3.6] `const_*` paths in a state subtree (a sealed path's children are not listed)."""
+    if depth > 8 or not isinstance(value, dict):
+        return []
+    found = []
+    for key, child in value.items():
+        if not isinstance(key, str):
+            continue
+        path = f"{prefix}.{key}" if prefix else key
+        if key.startswith("const_"):
+            found.append(path)
+        else:
+            found.extend(_const_paths(child, path, depth + 1))
+    return found
+
 
 # NOTE: [v3.4] _strip_transaction_refs removed — Transaction no longer leaks
 # into data graph. SupervisorProxy stores is_mutable:bool, not Transaction ref.
@@ -44,19 +104,28 @@ class TheusEngine:
         context: Initial context data (optional)
         namespaces: List of Namespace configurations (optional, [RFC-002])
         strict_guards: Enable strict contract enforcement (default: True)
+        private_allowlist: Underscore attributes guards still resolve in strict mode,
+            e.g. ["_asdict", "_fields"] for namedtuples (optional)
         strict_cas: Enable Strict CAS mode (default: False)
+        contract_mode: "enforce" (default) denies accesses outside a process contract;
+            "warn" logs and allows them (for tightening contracts, see `contract_drift`)
         audit_recipe: Audit configuration (optional)
         write_timeout_ms: Transaction write timeout in milliseconds.
             Falls back to THEUS_WRITE_TIMEOUT_MS env var, then 300000ms (5 min).
+        units: Canonical unit per path, e.g. {"domain.job.timeout": "ms"} (optional,
+            see `set_units`)
     """
 
     def __init__(
         self, context=None, namespaces=None, strict_guards=True, strict_cas=False,
-        audit_recipe=None, write_timeout_ms=None
+        audit_recipe=None, write_timeout_ms=None, private_allowlist=None, contract_mode="enforce",
+        units=None
     ):
         self._namespaces = NamespaceRegistry()
         self._strict_guards = strict_guards # Renamed from strict_mode
+        self.contract_mode = contract_mode
         self._strict_cas = strict_cas  # v3.0.4: CAS mode control
+        self._private_allowlist = sorted(set(private_allowlist or ()))
         self._audit = None
         self._schema = None  # v3.1.2: Schema Validation
 
@@ -214,6 +283,10 @@ class TheusEngine:
             # [POP v3.1] Explicit Decoupling of Strictness Flags
             self._core.set_strict_guards(strict_guards)
             self._core.set_strict_cas(strict_cas)
+            if private_allowlist:
+                self.private_allowlist = private_allowlist
+            if units:
+                self._core.set_units(units)
 
             # Hydrate state via CAS (Version 0 -> Init)
             if init_data:
@@ -284,6 +357,30 @@ class TheusEngine:
         if hasattr(self._core, "set_strict_guards"):
             self._core.set_strict_guards(enabled)
 
+    @property
+    def private_allowlist(self):
+        """[v3.6] Private attribute names guards still resolve under strict mode."""
+        return list(self._private_allowlist)
+
+    @private_allowlist.setter
+    def private_allowlist(self, names):
+        if hasattr(self._core, "set_private_allowlist"):
+            self._core.set_private_allowlist(list(names))
+            self._private_allowlist = self._core.private_allowlist
+        else:
+            self._private_allowlist = sorted(set(names))
+
+    @property
+    def contract_mode(self):
+        """[v3.6] "enforce" or "warn" (out-of-contract accesses are logged, not denied)."""
+        return self._contract_mode
+
+    @contract_mode.setter
+    def contract_mode(self, mode):
+        if mode not in ("enforce", "warn"):
+            raise ValueError(f"Unknown contract_mode '{mode}' (expected 'enforce' or 'warn')")
+        self._contract_mode = mode
+
     @property
     def strict_cas(self):
         return self._strict_cas
@@ -498,20 +595,45 @@ class TheusEngine:
         """v3.1 Managed Memory Allocator"""
         return self._allocator
 
-    def transaction(self, write_timeout_ms=5000):
-        """v3.3 Returns a Transaction Context Manager (with Auto-Sync)."""
+    def transaction(self, write_timeout_ms=5000, actor=None, admin=False, tags=None, isolation=None, op_id=None, partial_commit=False, provisional=False):
+        """v3.3 Returns a Transaction Context Manager (with Auto-Sync).
+        [v3.6] `actor` names the writer (proposer for approval paths); `admin` transactions
+        bypass the approval queue. `tags` (e.g. {"request_id": ...}) are stamped onto the
+        transaction's deltas, outbox messages, audit events and `blame()` entries.
+        `isolation` ("read_committed" / "repeatable_read") overrides `set_isolation()`.
+        `op_id` makes the commit idempotent: if that op already committed, this one is a
+        no-op (`tx.commit_status == "duplicate"`, `tx.committed_version` = original).
+        `partial_commit` enables `tx.checkpoint()`: if the block raises, the writes made up to
+        the last checkpoint are still committed (`tx.commit_status == "partial"`).
+        `provisional` lets `peek_pending(path)` show this transaction's uncommitted writes."""
         instance = self
         
         @contextmanager
         def sync_transaction(core, timeout):
-            with theus_core.Transaction(core, write_timeout_ms=timeout) as tx:
-                yield tx
+            tx = theus_core.Transaction(core, write_timeout_ms=timeout, actor=actor, admin=admin, tags=tags, isolation=isolation, op_id=op_id, partial_commit=partial_commit, provisional=provisional)
+            try:
+                with tx:
+                    yield tx
+            except BaseException:
+                # [v3.6] The failed block's last checkpoint was committed.
+                if tx.commit_status == "partial":
+                    instance._sync_registry_from_core()
+                raise
             
             # Post-Commit Sync (Success only)
             instance._sync_registry_from_core()
             
         return sync_transaction(self._core, write_timeout_ms)
 
+    @contextmanager
+    def group_commit(self, max_batch=64, window_ms=None):
+        """[v3.6] Merge the commits of transactions closed inside the block into shared
+        versions (flushed every `max_batch` transactions / `window_ms`, and on exit).
+        Per-transaction outcomes: `group.results`, `tx.commit_status`."""
+        with self._core.group_commit(max_batch=max_batch, window_ms=window_ms) as group:
+            yield group
+        self._sync_registry_from_core()
+
     def compare_and_swap(self, expected_version, data=None, heavy=None, signal=None, requester=None):
         """
         Compare-And-Swap with configurable conflict detection.
@@ -539,6 +661,222 @@ class TheusEngine:
         
         return res
 
+    def set_deterministic(self, seed=None):
+        """Seed every randomized decision of this engine (Rust jitter/fault odds/outbox ids,
+        Python retry backoff); `None` restores real randomness. For reproducible timestamps
+        also call `theus_core.use_global_logical_clock()` (process-wide)."""
+        self._core.set_deterministic(seed)
+        self._rng = (seed, random.Random(seed)) if seed is not None else None
+
+    def _retry_rng(self):
+        seed = self._core.deterministic_seed
+        if seed is None:
+            return random
+        # The core seed may have been (re)set directly, e.g. by a testing Harness.
+        if getattr(self, "_rng", None) is None or self._rng[0] != seed:
+            self._rng = (seed, random.Random(seed))
+        return self._rng[1]
+
+    def load_state(self, blob):
+        """Replace the committed state with bytes produced by `dumps_state()` (msgpack, no pickle)."""
+        self._core.load_state(blob)
+        if hasattr(self._context, "_state"):
+            object.__setattr__(self._context, "_state", self._core.state)
+        self._sync_registry_from_core()
+
+    def load_snapshot(self, full, *incrementals):
+        """[v3.6] Replace the committed state with a `dumps_state()` snapshot plus the
+        `dumps_incremental()` snapshots taken after it, in order (continuity is verified)."""
+        self._core.load_snapshot(full, *incrementals)
+        if hasattr(self._context, "_state"):
+            object.__setattr__(self._context, "_state", self._core.state)
+        self._sync_registry_from_core()
+
+    def import_snapshot(self, blob, strict=True):
+        """[v3.6] Replace the committed state with an `export_snapshot()` container (validated;
+        `strict` also checks the schema fingerprint and zone policies)."""
+        self._core.import_snapshot(blob, strict)
+        if hasattr(self._context, "_state"):
+            object.__setattr__(self._context, "_state", self._core.state)
+        self._sync_registry_from_core()
+
+    @classmethod
+    def loads_state(cls, blob, **kwargs):
+        """Build an engine (constructed with `kwargs`) whose state is loaded from `dumps_state()` bytes."""
+        engine = cls(**kwargs)
+        engine.load_state(blob)
+        return engine
+
+    @classmethod
+    def from_template(cls, template, schema=None, constants=None, **kwargs):
+        """[v3.6] Build an engine (constructed with `kwargs`) seeded from `template`
+        (`{zone: {field: value}}`) plus `constants` (same shape; every field must be a `const_`
+        name, so the CONSTANT zone seals it) in one transaction validated against `schema`.
+        `reset_to_template()` brings the engine back to exactly that state."""
+        import copy
+
+        data = copy.deepcopy(dict(template or {}))
+        for zone, fields in (constants or {}).items():
+            target = data.setdefault(zone, {})
+            for name, value in fields.items():
+                if not str(name).startswith("const_"):
+                    raise ValueError(f"Constant '{zone}.{name}' must use a 'const_' name to be sealed")
+                if name in target:
+                    raise ValueError(f"'{zone}.{name}' is both a template field and a constant")
+                target[name] = copy.deepcopy(value)
+
+        engine = cls(**kwargs)
+        if schema is not None:
+            engine.set_schema(schema)
+        if data:
+            with engine.transaction(actor="template", admin=True) as tx:
+                tx.update(data=data)
+        engine._template_blob = engine._core.dumps_state()
+        return engine
+
+    def reset_to_template(self):
+        """[v3.6] Restore the state seeded by `from_template()` (test isolation)."""
+        blob = getattr(self, "_template_blob", None)
+        if blob is None:
+            raise RuntimeError("reset_to_template() requires an engine built with TheusEngine.from_template()")
+        self.load_state(blob)
+
+    def approve(self, proposal_id, approver):
+        """[v3.6] Commit a parked proposal (see `proposals()`) as `approver`."""
+        version = self._core.approve(proposal_id, approver)
+        self._sync_registry_from_core()
+        return version
+
+    def security_report(self):
+        """
+        [v3.6] Security posture summary for reviews. Walks the registered contracts, the zone
+        physics overrides, the zone of every declared path and the admin grants:
+          - shared_writes: paths written by more than one process (overlapping paths count)
+          - private_exposed: PRIVATE paths an override makes readable
+          - constants: every `const_*` path and whether an override breaks its seal
+          - shadowed: overrides narrowed by a more specific one, and declared outputs the
+            zone physics never lets the process write
+          - admin: approval paths, approvers, the private allowlist and strict mode
+        """
+        write_bits = int(theus_core.Capability.APPEND | theus_core.Capability.UPDATE | theus_core.Capability.DELETE)
+
+        def zone(path):
+            return theus_core.effective_physics(path)[0]
+
+        def caps(bits):
+            return theus_core.Capability(bits).names
+
+        overrides = dict(theus_core.physics_overrides())
+        for path, bits in PYTHON_PHYSICS_OVERRIDES.items():
+            overrides.setdefault(path, bits)
+
+        contracts = {}
+        for name, func in sorted(self._registry.items()):
+            contract = getattr(func, "_pop_contract", None)
+            if contract is not None:
+                contracts[name] = contract
+
+        writers = {}
+        for name, contract in contracts.items():
+            for path in contract.outputs or []:
+                writers.setdefault(path, set()).add(name)
+        shared_writes = []
+        for path in sorted(writers):
+            names = set()
+            for other, other_names in writers.items():
+                if _path_covers(other, path) or _path_covers(path, other):
+                    names |= other_names
+            if len(names) > 1:
+                shared_writes.append({"path": path, "processes": sorted(names)})
+
+        private_exposed = [
+            {"path": path, "caps": caps(bits)}
+            for path, bits in sorted(overrides.items())
+            if zone(path) == "private" and bits & int(theus_core.Capability.READ)
+        ]
+
+        const_paths = set(_const_paths(self._core.state.data.to_dict()))
+        const_paths |= {p for p in overrides if zone(p) == "constant"}
+        for contract in contracts.values():
+            const_paths |= {p for p in (contract.inputs or []) + (contract.outputs or []) if zone(p) == "constant"}
+        constants = []
+        for path in sorted(const_paths):
+            effective = int(theus_core.effective_physics(path)[1])
+            constants.append({
+                "path": path,
+                "sealed": not effective & write_bits,
+                "caps": caps(effective),
+                "writers": sorted(
+                    name for name, contract in contracts.items()
+                    if any(_path_covers(out, path) or _path_covers(path, out) for out in contract.outputs or [])
+                ),
+            })
+
+        shadowed = []
+        for broad, broad_bits in sorted(overrides.items()):
+            for narrow, narrow_bits in sorted(overrides.items()):
+                if narrow != broad and _path_covers(broad, narrow) and narrow_bits != broad_bits:
+                    shadowed.append({"kind": "override", "path": broad, "caps": caps(broad_bits), "shadowed_by": narrow, "by_caps": caps(narrow_bits)})
+        for name, contract in contracts.items():
+            for path in contract.outputs or []:
+                effective, bits = theus_core.effective_physics(path)
+                if not int(bits) & write_bits:
+                    shadowed.append({"kind": "physics", "path": path, "process": name, "zone": effective, "caps": caps(int(bits))})
+
+        return {
+            "processes": sorted(contracts),
+            "shared_writes": shared_writes,
+            "private_exposed": private_exposed,
+            "constants": constants,
+            "shadowed": shadowed,
+            "admin": {
+                "approval_paths": self._core.approval_paths,
+                "approvers": self._core.approvers,
+                "private_allowlist": self.private_allowlist,
+                "strict_guards": self.strict_guards,
+            },
+        }
+
+    def contract_drift(self, process):
+        """
+        [v3.6] Declared vs observed access of `process` over its last tracked runs (enable
+        with `track_contract_drift(window)`):
+          - unused_inputs / unused_outputs: declared paths never read or written / never written
+          - undeclared_reads / undeclared_writes: paths used outside the contract (undeclared
+            writes only commit under contract_mode="warn")
+        Reads of a declared path's parents (`ctx.domain` to reach `domain.a`) are not drift.
+        """
+        func = self._registry.get(process)
+        contract = getattr(func, "_pop_contract", None)
+        if contract is None:
+            raise KeyError(f"Process '{process}' is not registered with a contract")
+        observed = self._core.observed_access(process)
+        reads, writes = observed["reads"], observed["writes"]
+        inputs, outputs = list(contract.inputs or []), list(contract.outputs or [])
+        declared = inputs + outputs
+
+        def undeclared(paths, allowed):
+            return [
+                p for p in paths
+                if not any(_contract_covers(a, p) or _path_covers(p, a) for a in allowed)
+            ]
+
+        return {
+            "process": process,
+            "runs": observed["runs"],
+            "window": observed["window"],
+            "unused_inputs": [i for i in inputs if not any(_contract_covers(i, p) for p in reads + writes)],
+            "unused_outputs": [o for o in outputs if not any(_contract_covers(o, p) for p in writes)],
+            "undeclared_reads": undeclared(reads, declared),
+            "undeclared_writes": [p for p in writes if not any(_contract_covers(o, p) for o in outputs)],
+        }
+
+    def replay_recording(self, file, stop_on_error=True):
+        """Re-run the transactions captured by `start_recording(file)` on this engine."""
+        report = theus_core.replay_recording(self._core, file, stop_on_error=stop_on_error)
+        self._sync_registry_from_core()
+        return report
+
     def _sync_registry_from_core(self):
         """Syncs the current Rust Core state back to the NamespaceRegistry and Context object."""
         if not hasattr(self, "_core"): return
@@ -618,6 +956,20 @@ class TheusEngine:
                         raise ContractViolationError(
                             f"Pure process cannot take inputs from Zone: Signal/Meta (Found: {inp})"
                         )
+            # [v3.6] Effect budget: state writes need "state"; other classes are enforced per call.
+            if contract.effects is not None:
+                ref = f"{func.__module__}.{func.__qualname__}"
+                try:
+                    self._core.pure_scope(func.__name__, contract.effects, ref)
+                except ValueError as e:
+                    raise ContractViolationError(f"{e} (contract: {ref})") from None
+                if contract.outputs and "state" not in contract.effects:
+                    raise ContractViolationError(
+                        f"Process '{func.__name__}' declares outputs {contract.outputs} but its effect "
+                        f"budget {contract.effects} excludes 'state' (contract: {ref})"
+                    )
+                if "log" not in contract.effects:
+                    _install_log_budget()
 
         self._registry[func.__name__] = func
 
@@ -626,7 +978,19 @@ class TheusEngine:
         Executes a process and handles Transactional Commit logic and Safety Guard enforcement.
         Extended v3.3: Supports Automatic Retry (Backoff) for Conflict Resolution.
         """
+        return (await self.execute_result(func_or_name, *args, **kwargs)).value
+
+    async def execute_result(self, func_or_name, *args, **kwargs):
+        """
+        [v3.6] `execute()`, but awaiting yields a `ProcessResult`: the return value plus the
+        committed version, changed paths, outbox count, duration and retries of the run.
+        `partial_commit=True` lets the process call `ctx.checkpoint()` (see `transaction()`).
+        `provisional=True` shows the run's uncommitted writes to `peek_pending()`.
+        """
         import asyncio
+        import time
+
+        started = time.perf_counter()
 
         # Resolve function
         if isinstance(func_or_name, str):
@@ -639,16 +1003,20 @@ class TheusEngine:
         # [v3.3] Extract Retry Config
         # Fixes TypeError: func() got unexpected keyword argument 'retries'
         max_retries = kwargs.pop("retries", 0)
+        partial_commit = kwargs.pop("partial_commit", False)
+        provisional = kwargs.pop("provisional", False)
         current_retries = 0
+        attempts = 0
 
         # [v3.3 FIX] Hoist Transaction to preserve Outbox across CAS retries
         # Long-running simulation processes often exceed 5s, bumping to 30s.
         while True:
             # NOTE: Transaction captures start_version at __enter__ for OCC conflict detection.
-            # Read-Committed semantics (not MVCC snapshot): reads see latest committed data.
+            # Read-Committed by default: reads see latest committed data. [v3.6] Under
+            # set_isolation("repeatable_read") reads resolve against the state pinned at __enter__.
             # On field-level conflict, __exit__ raises CAS Version Mismatch -> triggers retry below.
             try:
-                _tx_ctx = theus_core.Transaction(self._core, write_timeout_ms=self._write_timeout_ms)
+                _tx_ctx = theus_core.Transaction(self._core, write_timeout_ms=self._write_timeout_ms, actor=func.__name__, partial_commit=partial_commit, provisional=provisional)
             except RuntimeError as tx_err:
                 if "cannot deepcopy" in str(tx_err) or "cannot pickle" in str(tx_err):
                     # Safety net: clean state and retry if Transaction refs still leak
@@ -661,7 +1029,7 @@ class TheusEngine:
                             self._core.compare_and_swap(
                                 self._core.state.version, data=cleaned
                             )
-                        _tx_ctx = theus_core.Transaction(self._core, write_timeout_ms=self._write_timeout_ms)
+                        _tx_ctx = theus_core.Transaction(self._core, write_timeout_ms=self._write_timeout_ms, actor=func.__name__, partial_commit=partial_commit, provisional=provisional)
                     except Exception:
                         raise tx_err
                 else:
@@ -670,7 +1038,9 @@ class TheusEngine:
             try:
                 with _tx_ctx as tx:
                     try:
+                        attempts += 1
                         result = await self._attempt_execute(func, tx, *args, **kwargs)
+                        outbox_count = tx.outbox.len()
 
                         # If success, clear conflict counter
                         if hasattr(self._core, "report_success"):
@@ -683,13 +1053,8 @@ class TheusEngine:
                         # Transaction commits when block exits
                         pass
                     except Exception as e:
-                        # Check for CAS Conflict (ContextError) raised by process body
-                        err_msg = str(e)
-                        is_cas_error = "CAS Version Mismatch" in err_msg
-                        is_busy_error = "System Busy" in err_msg
-
-                        if is_busy_error:
-                            is_cas_error = True 
+                        # Check for CAS Conflict / Busy (ConflictError, BusyError) raised by process body
+                        is_cas_error = _is_retryable_conflict(e)
 
                         should_retry = False
                         backoff_ms = 50
@@ -700,7 +1065,7 @@ class TheusEngine:
                                 decision = self._core.report_conflict(func.__name__)
                                 if decision.should_retry:
                                     should_retry = True
-                                    backoff_ms = decision.wait_ms
+                                    backoff_ms = decision.suggested_backoff_ms
                             
                             # 2. Fallback to Python Manual Retry with CAPPED Backoff + Full Jitter
                             if not should_retry and current_retries < max_retries:
@@ -710,8 +1075,7 @@ class TheusEngine:
                                 MAX_BACKOFF_MS = 1000
                                 raw_backoff = 50 * (2 ** (current_retries - 1))
                                 
-                                import random
-                                actual_backoff = random.uniform(0, min(MAX_BACKOFF_MS, raw_backoff))
+                                actual_backoff = self._retry_rng().uniform(0, min(MAX_BACKOFF_MS, raw_backoff))
                                 
                                 backoff_ms = actual_backoff
                                 print(f"[*] CAS/Busy Conflict for {func.__name__}. Retry {current_retries}/{max_retries} in {backoff_ms:.2f}ms...")
@@ -724,8 +1088,7 @@ class TheusEngine:
             except ContextError as commit_err:
                 # Transaction.__exit__ raised OCC conflict (field-level version mismatch at commit time).
                 # Apply the same retry policy as body-level CAS errors.
-                err_msg = str(commit_err)
-                if "CAS Version Mismatch" in err_msg or "System Busy" in err_msg:
+                if _is_retryable_conflict(commit_err):
                     should_retry = False
                     backoff_ms = 50
 
@@ -733,27 +1096,45 @@ class TheusEngine:
                         decision = self._core.report_conflict(func.__name__)
                         if decision.should_retry:
                             should_retry = True
-                            backoff_ms = decision.wait_ms
+                            backoff_ms = decision.suggested_backoff_ms
 
                     if not should_retry and current_retries < max_retries:
                         should_retry = True
                         current_retries += 1
                         MAX_BACKOFF_MS = 1000
                         raw_backoff = 50 * (2 ** (current_retries - 1))
-                        import random
-                        backoff_ms = random.uniform(0, min(MAX_BACKOFF_MS, raw_backoff))
+                        backoff_ms = self._retry_rng().uniform(0, min(MAX_BACKOFF_MS, raw_backoff))
                         print(f"[*] CAS Commit Conflict for {func.__name__}. Retry {current_retries}/{max_retries} in {backoff_ms:.2f}ms...")
 
                     if should_retry:
                         await asyncio.sleep(backoff_ms / 1000.0)
                         continue
 
+                if _tx_ctx.commit_status == "partial":
+                    self._sync_registry_from_core()
                 raise commit_err
+            except BaseException:
+                # [v3.6] partial_commit: the run failed but its last checkpoint was committed.
+                if _tx_ctx.commit_status == "partial":
+                    self._sync_registry_from_core()
+                raise
             
             # Successful COMMIT. Sync back to registry for legacy tests.
             self._sync_registry_from_core()
-            
-            return result
+
+            changed = tx.get_delta_log()
+            return ProcessResult(
+                value=result,
+                process=func.__name__,
+                tx_id=tx.id,
+                version=None if tx.read_only else tx.committed_version,
+                status=tx.commit_status,
+                deltas=len(changed),
+                changed_paths=tuple(sorted(set(changed))),
+                outbox=outbox_count,
+                retries=attempts - 1,
+                duration_ms=(time.perf_counter() - started) * 1000.0,
+            )
 
     async def _attempt_execute(self, func, tx, *args, **kwargs):
         # [v3.1.2] Input Gate: Active Validation
@@ -781,6 +1162,8 @@ class TheusEngine:
             
         if ran_locally:
             target_func = func
+            effects = contract.effects if contract else None
+            contract_ref = f"{func.__module__}.{func.__qualname__}"
             if contract and contract.semantic == SemanticType.PURE:
                 # Pure Wrapper Logic + Arg Capture
                 # [v3.0.4] Pass contract.inputs to create filtered restricted view
@@ -793,7 +1176,9 @@ class TheusEngine:
                         restricted = self._create_restricted_view(
                             ctx, allowed_paths=allowed_inputs
                         )
-                        return await func(restricted, *args, **kwargs)
+                        # [v3.6] I/O made by the process is checked against set_pure_io_policy
+                        with self._core.pure_scope(func.__name__, effects, contract_ref):
+                            return await func(restricted, *args, **kwargs)
 
                     safe_wrapper.__name__ = func.__name__
                     target_func = safe_wrapper
@@ -803,7 +1188,9 @@ class TheusEngine:
                         restricted = self._create_restricted_view(
                             ctx, allowed_paths=allowed_inputs
                         )
-                        return func(restricted, *args, **kwargs)
+                        # [v3.6] I/O made by the process is checked against set_pure_io_policy
+                        with self._core.pure_scope(func.__name__, effects, contract_ref):
+                            return func(restricted, *args, **kwargs)
 
                     safe_wrapper.__name__ = func.__name__
                     target_func = safe_wrapper
@@ -856,9 +1243,13 @@ class TheusEngine:
                             path_prefix="",
                             transaction=tx,
                             strict_guards=self._strict_guards,
+                            private_allowlist=self._private_allowlist,
+                            contract_mode=self._contract_mode,
                             process_name=func.__name__
                         )
-                        res = await func(native_guard, *args, **kwargs)
+                        # [v3.6] Declared effect budget (no-op for unbudgeted contracts)
+                        with self._core.pure_scope(func.__name__, effects, contract_ref) if effects is not None else nullcontext():
+                            res = await func(native_guard, *args, **kwargs)
                         return _deep_unwrap_res(res)
 
                     arg_binder.__name__ = func.__name__
@@ -905,9 +1296,13 @@ class TheusEngine:
                             path_prefix="",
                             transaction=tx,
                             strict_guards=self._strict_guards,
+                            private_allowlist=self._private_allowlist,
+                            contract_mode=self._contract_mode,
                             process_name=func.__name__
                         )
-                        res = func(native_guard, *args, **kwargs)
+                        # [v3.6] Declared effect budget (no-op for unbudgeted contracts)
+                        with self._core.pure_scope(func.__name__, effects, contract_ref) if effects is not None else nullcontext():
+                            res = func(native_guard, *args, **kwargs)
                         return _deep_unwrap_res(res)
 
                     arg_binder.__name__ = func.__name__
@@ -1309,6 +1704,12 @@ class TheusEngine:
                     break
 
             if not is_allowed:
+                if self._contract_mode == "warn":
+                    logging.getLogger("theus.engine").warning(
+                        f"Process '{func_name}' modified '{path}' which is NOT declared in outputs "
+                        f"(allowed: contract_mode='warn')"
+                    )
+                    continue
                 raise ContractViolationError(
                     f"Process '{func_name}' modified '{path}' which is NOT declared in outputs."
                     f"\nAllowed: {allowed_patterns}"
@@ -1318,7 +1719,9 @@ class TheusEngine:
     def _create_restricted_view(self, ctx, allowed_paths=None):
         # [v3.0.4] Create a restricted view with input filtering
         # The Proxy ensures AttributeError/ContractViolationError on unauthorized access
-        return RestrictedStateProxy(ctx.restrict_view(), allowed_paths=allowed_paths)
+        return RestrictedStateProxy(
+            ctx.restrict_view(), allowed_paths=allowed_paths, token=getattr(ctx, "token", None)
+        )
 
     def _check_output_permission(self, update, contract):
         # Check if update keys match contract.outputs glob patterns
@@ -1449,8 +1852,16 @@ class TheusEngine:
         future = self._parallel_pool.submit(func, ctx)
         return future.result()
 
-    def shutdown(self):
-        """Cleanly shuts down internal resources (Pools, Heavies)."""
+    def shutdown(self, timeout_ms=5000):
+        """
+        Cleanly shuts down the engine and internal resources (Pools, Heavies).
+        [v3.6] Stops accepting transactions, drains open ones for up to `timeout_ms`,
+        flushes the outbox/audit sink and returns the Rust shutdown report.
+        """
+        report = None
+        if hasattr(self._core, "shutdown") and not self._core.is_shutdown:
+            report = self._core.shutdown(timeout_ms)
+
         if hasattr(self, "_parallel_pool") and self._parallel_pool:
             self._parallel_pool.shutdown()
             self._parallel_pool = None
@@ -1458,6 +1869,7 @@ class TheusEngine:
         if hasattr(self, "_allocator") and self._allocator:
             self._allocator.cleanup()
             self._allocator = None
+        return report
 
     def log(self, *args, **kwargs):
         """DX: Standard logging stub to satisfy Linter."""
@@ -1480,6 +1892,44 @@ class TheusEngine:
         if hasattr(self._core, "process_outbox"):
             self._core.process_outbox()
 
+    async def fire_timers(self, now_ms=None):
+        """
+        [v3.6] Fire the timers due now (see `schedule_signal` / `schedule_process`): signal
+        timers commit their signal, process timers run `execute(process, **kwargs)`.
+        A failing timer is logged and does not stop the others. Returns the fired timer ids.
+        """
+        fired = []
+        for timer_id, kind, target, payload in self._core.take_due_timers(now_ms):
+            try:
+                if kind == "signal":
+                    with self.transaction(actor=f"timer:{timer_id}") as tx:
+                        tx.update(signal={target: payload})
+                else:
+                    await self.execute(target, **(payload or {}))
+                fired.append(timer_id)
+            except Exception as e:
+                logging.getLogger("theus.engine").warning(f"Timer '{timer_id}' ({kind} {target}) failed: {e!r}")
+        return fired
+
+    async def run_timers(self, poll_ms=100):
+        """[v3.6] Fire due timers every `poll_ms` until cancelled (run it as a task)."""
+        import asyncio
+
+        while True:
+            await self.fire_timers()
+            await asyncio.sleep(poll_ms / 1000.0)
+
+    def ingest(self, event, dedup_key=None, order_key=None, sequence=None):
+        """
+        [v3.6] Inbox: consume an external event transactionally.
+        The handler registered via attach_inbox_handler() receives (tx, event).
+        Returns the number of events applied (0 if duplicate, stale or parked).
+        """
+        applied = self._core.ingest(event, dedup_key=dedup_key, order_key=order_key, sequence=sequence)
+        if applied:
+            self._sync_registry_from_core()
+        return applied
+
     def __getattr__(self, name):
         return getattr(self._core, name)
 
@@ -1569,8 +2019,9 @@ class RestrictedStateProxy:
     [v3.0.4] Read-only state proxy that enforces contract input restrictions.
     """
 
-    def __init__(self, state, allowed_paths=None):
+    def __init__(self, state, allowed_paths=None, token=None):
         self._state = state
+        self._token = token
         self._allowed_paths = allowed_paths or []
         # Parse allowed paths into zone-specific key sets
         self._domain_keys = set()
@@ -1622,3 +2073,16 @@ class RestrictedStateProxy:
             return self._state.global_
         return FilteredDomainProxy(self._state.global_, self._global_keys, "global")
 
+    # [v3.6] Cooperative cancellation (same API as ProcessContext)
+    @property
+    def token(self):
+        return self._token
+
+    @property
+    def cancelled(self):
+        return self._token is not None and self._token.cancelled
+
+    def raise_if_cancelled(self):
+        if self._token is not None:
+            self._token.raise_if_cancelled()
+
@@ -21,9 +21,12 @@ try:
         _RustContextGuard = theus_core.ContextGuard
     # [RFC-001 §10] Import SupervisorProxy for __dict__ proxying wrapping check
     _RustSupervisorProxy = getattr(theus_core, "SupervisorProxy", type(None))
+    # Capability mask (all capabilities plus the admin-bypass marker) for elevated proxies.
+    _ADMIN_CAPS = getattr(theus_core, "ADMIN_CAPS", 0)
 except ImportError:
     _RustContextGuard = object
     _RustSupervisorProxy = type(None)
+    _ADMIN_CAPS = 0
 
 
 class _PrivateZoneReadAccess(Exception):
@@ -48,6 +51,8 @@ class ContextGuard:
         transaction: Any = None,
         strict_guards: bool = True,
         process_name: str = "Unknown",
+        private_allowlist: Any = (),
+        contract_mode: Optional[str] = None,
         _inner: Any = None,
         parent: Any = None,
         name: Any = None,
@@ -95,6 +100,16 @@ class ContextGuard:
         if transaction is not None:
             _current_tx.set(transaction)
         object.__setattr__(self, "_strict_guards", strict_guards)
+        object.__setattr__(self, "_private_allowlist", list(private_allowlist or ()))
+        # [v3.6] contract_mode="warn" logs out-of-contract accesses instead of denying them;
+        # nested guards inherit the mode of their parent.
+        if contract_mode is None:
+            contract_warn = bool(getattr(parent, "_contract_warn", False)) if isinstance(parent, ContextGuard) else False
+        elif contract_mode in ("enforce", "warn"):
+            contract_warn = contract_mode == "warn"
+        else:
+            raise ValueError(f"Unknown contract_mode '{contract_mode}' (expected 'enforce' or 'warn')")
+        object.__setattr__(self, "_contract_warn", contract_warn)
         object.__setattr__(self, "_parent", parent)
         object.__setattr__(self, "_name", name)
         object.__setattr__(self, "_target", target_obj)
@@ -139,6 +154,8 @@ class ContextGuard:
                 tx=transaction,
                 is_admin=False,
                 strict_guards=strict_guards,
+                private_allowlist=self._private_allowlist,
+                contract_warn=contract_warn,
             )
 
     def _check_zone_physics(self, path: str, mode: str) -> None:
@@ -171,17 +188,21 @@ class ContextGuard:
                         )
             # [RFC-001 Handbook §1.1] PRIVATE zone — hidden from non-admin
             if segment.startswith("internal_"):
-                if mode == "read" and not self._local_is_admin:
+                if mode == "read" and not self._local_is_admin and not self._break_glass_covers(path):
                     # NOTE: Raise special sentinel to tell caller to return None.
                     raise _PrivateZoneReadAccess()
 
+    def _break_glass_covers(self, path: str) -> bool:
+        """[v3.6] A break-glass grant of the transaction's engine lifts the PRIVATE zone."""
+        tx = self._transaction
+        return tx is not None and hasattr(tx, "break_glass_covers") and tx.break_glass_covers(path)
+
     def _is_allowed(self, path: str, mode: str = "read") -> bool:
         """[v3.2] Granular check for path access (supports wildcards).
         
         NOTE: This only enforces restrictions on paths belonging to REGISTERED namespaces.
         System paths (outbox, policy_id, etc.) are always allowed through to the Rust guard.
         """
-        import fnmatch
         if self._local_is_admin: return True
         
         # Use getattr to avoid recursion in __getattr__
@@ -205,6 +226,18 @@ class ContextGuard:
             return True
         
         # --- PATH IS IN A REGISTERED NAMESPACE --- enforce whitelist ---
+        return self._contract_allows(path, mode)
+
+    def _contract_allows(self, path: str, mode: str = "read") -> bool:
+        """Does the declared contract (inputs/outputs patterns) cover `path` for `mode`?"""
+        import fnmatch
+        if self._local_is_admin: return True
+        inputs = getattr(self, "_allowed_inputs", None)
+        outputs = getattr(self, "_allowed_outputs", None)
+        if inputs is None and mode == "read": return True
+        if outputs is None and mode == "write": return True
+
+        norm_path = path.replace("[", ".").replace("]", "")
         all_patterns = (inputs or set()) | (outputs or set())
         
         if mode == "read":
@@ -238,6 +271,55 @@ class ContextGuard:
     def is_proxy(self) -> bool:
         return True
 
+    def _is_visible(self, path: str, field: bool = True) -> bool:
+        """Listed by dir()/describe(): not a hidden PRIVATE field, and declared in the
+        contract (data fields) or reachable through __getattr__ (guard/proxy API)."""
+        try:
+            self._check_zone_physics(path, "read")
+        except _PrivateZoneReadAccess:
+            return False
+        return self._contract_allows(path, "read") if field else self._is_allowed(path, "read")
+
+    def __dir__(self):
+        """[v3.6] Guard API plus the fields this process contract can read."""
+        names = {n for n in dir(type(self)) if not n.startswith("_")}
+        try:
+            inner_names = dir(self._inner)
+            api = set(dir(type(self._inner)))
+        except Exception:
+            inner_names, api = [], set()
+        for n in inner_names:
+            full_path = n if self._path_prefix == "" else f"{self._path_prefix}.{n}"
+            if not n.startswith("_") and self._is_visible(full_path, field=n not in api):
+                names.add(n)
+        return sorted(names)
+
+    def _narrow_access(self, path: str, writable: bool):
+        """Contract filter handed to the Rust introspection (None = hide the path)."""
+        if not self._is_visible(path):
+            return None
+        return writable and self._contract_allows(path, "write")
+
+    def describe(self, max_depth: int = 3, max_keys: int = 50) -> dict:
+        """[v3.6] Readable subtree as `{path: {zone, access, type, len?}}` under this contract."""
+        inner_describe = getattr(self._inner, "describe", None)
+        if not callable(inner_describe):
+            return {}
+        return inner_describe(max_depth, max_keys, narrow=self._narrow_access)
+
+    def render_tree(self, html: bool = False, max_depth: int = 3, max_keys: int = 20, max_nodes: int = 200) -> str:
+        """[v3.6] Readable subtree as a text (or HTML) tree with zone badges, size-capped."""
+        render = getattr(self._inner, "render_tree", None)
+        if not callable(render):
+            return repr(self._inner)
+        return render(html=html, max_depth=max_depth, max_keys=max_keys, max_nodes=max_nodes, narrow=self._narrow_access)
+
+    def _repr_html_(self) -> str:
+        return self.render_tree(html=True)
+
+    def _repr_pretty_(self, p, cycle) -> None:
+        p.text("ContextGuard(...)" if cycle else self.render_tree())
+
     def _elevate(self, enabled: bool):
         """[RFC-001] Explicitly elevate/reset admin status on inner guard."""
         object.__setattr__(self, "_local_is_admin", enabled)
@@ -266,7 +348,7 @@ class ContextGuard:
                 "Use the Context API to read/write fields safely."
             )
         # 1. Immediate bypass for whitelisted Python-side attributes
-        if name in ("_inner", "_local_is_admin", "_log", "_elevate", "is_admin", "is_proxy", "_outbox", "_path_prefix", "_allowed_inputs", "_allowed_outputs", "_transaction", "_strict_guards", "_parent", "_name", "_target"):
+        if name in ("_inner", "_local_is_admin", "_log", "_elevate", "is_admin", "is_proxy", "_outbox", "_path_prefix", "_allowed_inputs", "_allowed_outputs", "_transaction", "_strict_guards", "_private_allowlist", "_contract_warn", "_parent", "_name", "_target"):
             return object.__getattribute__(self, name)
 
         full_path = name if self._path_prefix == "" else f"{self._path_prefix}.{name}"
@@ -278,7 +360,7 @@ class ContextGuard:
         if not self._is_allowed(full_path, "read"):
              # For discovery, we allow 'domain' or 'global' prefixes even if not explicitly in inputs, 
              # provided a sub-path IS allowed. _is_allowed already handles this parent-path check.
-             raise PermissionError(f"Illegal Read: Path '{full_path}' is restricted by Process Contract.")
+             self._deny(full_path, "read", f"Illegal Read: Path '{full_path}' is restricted by Process Contract.")
 
         val = None
         # 2. Rust delegation
@@ -324,6 +406,7 @@ class ContextGuard:
                             path_prefix=full_path,
                             transaction=_current_tx.get(),
                             strict_guards=self._strict_guards,
+                            private_allowlist=self._private_allowlist,
                             process_name=self._log.extra.get("process_name", "Unknown"),
                             _inner=val,
                             parent=self,
@@ -341,12 +424,12 @@ class ContextGuard:
         if self._local_is_admin:
             # 1. Aggressive Propagate Elevation to Rust Proxy
             if hasattr(val, "_set_capabilities"):
-                 try: val._set_capabilities(31)
+                 try: val._set_capabilities(_ADMIN_CAPS)
                  except: pass
             else:
                  for attr in ["capabilities", "_capabilities", "caps"]:
                       if hasattr(val, attr):
-                           try: setattr(val, attr, 31)
+                           try: setattr(val, attr, _ADMIN_CAPS)
                            except: pass
 
             # 2. Return elevated wrapper
@@ -390,7 +473,7 @@ class ContextGuard:
     def __getitem__(self, key: Any) -> Any:
         full_path = str(key) if self._path_prefix == "" else f"{self._path_prefix}[{key}]"
         if isinstance(key, str) and not self._is_allowed(full_path, "read"):
-             raise PermissionError(f"Illegal Read: Path '{full_path}' is restricted by Process Contract.")
+             self._deny(full_path, "read", f"Illegal Read: Path '{full_path}' is restricted by Process Contract.")
 
         val = None
         try:
@@ -426,6 +509,7 @@ class ContextGuard:
                                     path_prefix=full_path,
                                     transaction=_current_tx.get(),
                                     strict_guards=self._strict_guards,
+                                    private_allowlist=self._private_allowlist,
                                     process_name=self._log.extra.get("process_name", "Unknown"),
                                     _inner=val,
                                     parent=self,
@@ -564,8 +648,82 @@ class ContextGuard:
                 raise e
         return None
 
+    def _key_path(self, key: Any) -> str:
+        return str(key) if self._path_prefix == "" else f"{self._path_prefix}.{key}"
+
+    def _deny(self, path: str, mode: str, message: str) -> None:
+        """[v3.6] Contract denial, also recorded in the transaction's trace (engine.trace).
+        In warn mode the access is logged and allowed instead."""
+        if self._contract_warn:
+            self._log.warning(f"{message} (allowed: contract_mode='warn')")
+            return
+        tx = self._transaction
+        if tx is not None and hasattr(tx, "trace_denied"):
+            tx.trace_denied(path, mode)
+        raise PermissionError(message)
+
+    def keys(self):
+        """[v3.6] Keys the contract can read (private zone hidden)."""
+        self._check_self_read()
+        return [k for k in self._inner.keys() if not isinstance(k, str) or self._is_visible(self._key_path(k))]
+
+    def values(self):
+        return [self[k] for k in self.keys()]
+
+    def items(self):
+        return [(k, self[k]) for k in self.keys()]
+
+    def get(self, key: Any, default: Any = None) -> Any:
+        """[v3.6] `dict.get` with the same checks and wrapping as `ctx[key]`."""
+        if isinstance(key, str) and not self._is_visible(self._key_path(key)):
+            self._deny(self._key_path(key), "read", f"Illegal Read: Path '{self._key_path(key)}' is restricted by Process Contract.")
+        try:
+            return self[key]
+        except (KeyError, IndexError):
+            return default
+
+    def plan(self, fn) -> list:
+        """[v3.6] Dry run `fn(ctx)` on a throwaway transaction and return the deltas it
+        would produce (`[{path, op, value, old_value}]`). Nothing is committed."""
+        if not isinstance(self._inner, _RustContextGuard):
+            raise TypeError("plan() is only available on the process context, not on nested values")
+
+        def run(native):
+            view = ContextGuard(
+                target_obj=native._target,
+                allowed_inputs=self._allowed_inputs,
+                allowed_outputs=self._allowed_outputs,
+                path_prefix=self._path_prefix,
+                strict_guards=self._strict_guards,
+                private_allowlist=self._private_allowlist,
+                contract_mode="warn" if self._contract_warn else "enforce",
+                process_name=self._log.extra.get("process_name", "Unknown"),
+                _inner=native,
+            )
+            object.__setattr__(view, "_local_is_admin", self._local_is_admin)
+            fn(view)
+
+        return self._inner.plan(run)
+
+    def checkpoint(self) -> int:
+        """[v3.6] Keep the writes made so far even if the process later fails (the run must
+        be executed with `partial_commit=True`). Returns the checkpoint number."""
+        if self._transaction is None:
+            raise RuntimeError("checkpoint() needs an active transaction")
+        return self._transaction.checkpoint()
+
+    def run(self, path: str, *args, **kwargs) -> Any:
+        """[v3.6] Invoke the callable stored at `path` as fn(child_ctx, *args, **kwargs).
+        Requires the EXECUTE capability; the callable receives a read-only child guard."""
+        full_path = path if self._path_prefix == "" else f"{self._path_prefix}.{path}"
+        if not self._is_allowed(full_path, "read"):
+            self._deny(full_path, "read", f"Illegal Execute: Path '{full_path}' is restricted by Process Contract.")
+        if not hasattr(self._inner, "run"):
+            raise TypeError(f"ctx.run() is not available on '{full_path}'")
+        return self._inner.run(path, *args, **kwargs)
+
     def __setattr__(self, name: str, value: Any) -> None:
-        if name in ("_inner", "_local_is_admin", "_log", "_outbox", "_path_prefix", "_allowed_inputs", "_allowed_outputs", "_transaction", "_strict_guards", "_parent", "_name", "_target"):
+        if name in ("_inner", "_local_is_admin", "_log", "_outbox", "_path_prefix", "_allowed_inputs", "_allowed_outputs", "_transaction", "_strict_guards", "_private_allowlist", "_contract_warn", "_parent", "_name", "_target"):
             object.__setattr__(self, name, value)
             return
 
@@ -573,7 +731,7 @@ class ContextGuard:
         # [RFC-001 §5] Zone physics check (const_ blocked even for admin)
         self._check_zone_physics(full_path, "write")
         if not self._is_allowed(full_path, "write"):
-             raise PermissionError(f"Illegal Write: Path '{full_path}' is restricted by Process Contract.")
+             self._deny(full_path, "write", f"Illegal Write: Path '{full_path}' is restricted by Process Contract.")
 
         # Unwrap Python ContextGuard before passing to Rust (Deep Unwrap)
         def _deep_unwrap(v):
@@ -601,7 +759,7 @@ class ContextGuard:
         if isinstance(key, str):
             self._check_zone_physics(full_path, "write")
         if isinstance(key, str) and not self._is_allowed(full_path, "write"):
-             raise PermissionError(f"Illegal Write: Path '{full_path}' is restricted by Process Contract.")
+             self._deny(full_path, "write", f"Illegal Write: Path '{full_path}' is restricted by Process Contract.")
 
         def _deep_unwrap(v):
             if isinstance(v, ContextGuard):
@@ -628,10 +786,28 @@ class ContextGuard:
         return iter(self._inner)
 
     def __contains__(self, item):
+        # [v3.6] Mapping membership only reports keys the contract covers.
+        if isinstance(item, str) and self._is_mapping() and not self._is_visible(self._key_path(item)):
+            return False
         return item in self._inner
 
+    def _is_mapping(self) -> bool:
+        inner = self._inner
+        return isinstance(inner, (dict, _RustContextGuard)) or isinstance(getattr(inner, "supervisor_target", None), dict)
+
+    def _check_self_read(self) -> None:
+        """Read check on the guarded value itself (the root context is always readable)."""
+        if self._path_prefix and not self._is_allowed(self._path_prefix, "read"):
+            self._deny(self._path_prefix, "read", f"Illegal Read: Path '{self._path_prefix}' is restricted by Process Contract.")
+
     def __len__(self):
+        self._check_self_read()
         return len(self._inner)
 
+    def __bool__(self):
+        """[v3.6] Truthiness of the guarded value (empty containers are falsy)."""
+        self._check_self_read()
+        return bool(self._inner)
+
     def __repr__(self):
         return f"<ContextGuard wrapping {repr(self._inner)} admin={self._local_is_admin}>"
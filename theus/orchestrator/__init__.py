@@ -3,10 +3,13 @@ from .executor import ThreadExecutor
 from .bus import SignalBus
 from .fsm import StateMachine
 from .manager import WorkflowManager
+from .saga import SagaCoordinator, SagaStep
 
 __all__ = [
     "ThreadExecutor",
     "SignalBus",
     "StateMachine",
     "WorkflowManager",
+    "SagaCoordinator",
+    "SagaStep",
 ]
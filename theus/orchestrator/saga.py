@@ -0,0 +1,174 @@
+import copy
+import logging
+import uuid
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional
+
+from theus_core import OutboxMsg
+
+logger = logging.getLogger("Saga")
+
+# [v3.6] Outbox-driven sagas. A saga is a list of steps, each with a forward handler (the call
+# into an external system) and an optional compensate handler that undoes it. The coordinator
+# is the engine's outbox worker: every step runs as the delivery of one outbox message, and the
+# transaction that acknowledges it also records the saga's progress and enqueues the next
+# message, so progress and the next action commit atomically. When a forward handler fails
+# (after `retries` redeliveries) the completed steps are compensated in reverse order.
+#
+# Saga records live in the META zone (`meta_sagas` by default):
+#   {saga_id: {name, status, step, data, completed, error}}
+# with status "running" -> "completed", or "compensating" -> "compensated". Pending messages
+# survive a restart through `outbox_snapshot()` / `restore_outbox()`; `recover()` re-enqueues
+# the current message of any unfinished saga whose message was lost.
+# NOTE: Delivery is at-least-once: a crash between a handler's side effect and its ack runs
+# the handler again. Handlers get the message's idempotency key to deduplicate the external
+# call. A failing compensation is redelivered by the outbox (and dead-lettered after
+# `set_outbox_max_attempts`), leaving the saga "compensating".
+
+TOPIC_PREFIX = "saga."
+
+
+@dataclass(frozen=True)
+class SagaStep:
+    """One step: `forward(data, key)` / `compensate(data, key)`. `data` is the saga's start
+    payload merged with the dicts returned by earlier forward handlers; `key` is the delivery's
+    idempotency key."""
+
+    name: str
+    forward: Callable[[Dict[str, Any], str], Optional[Dict[str, Any]]]
+    compensate: Optional[Callable[[Dict[str, Any], str], Any]] = None
+    retries: int = 0
+
+
+class SagaCoordinator:
+    """
+    Drives registered sagas off the engine's outbox.
+    Messages on other topics are passed to `worker` (the application's relay), if any.
+    """
+
+    def __init__(self, engine, worker: Optional[Callable] = None, zone: str = "meta_sagas"):
+        self.engine = engine
+        self.worker = worker
+        self.zone = zone
+        self._sagas: Dict[str, List[SagaStep]] = {}
+        engine.attach_worker(self._deliver)
+
+    def register(self, name: str, steps: List[SagaStep]) -> None:
+        if not steps:
+            raise ValueError(f"Saga '{name}' needs at least one step")
+        self._sagas[name] = list(steps)
+
+    def start(self, name: str, data: Optional[Dict[str, Any]] = None, saga_id: Optional[str] = None) -> str:
+        """Record a new saga and enqueue its first step. Returns the saga id."""
+        if name not in self._sagas:
+            raise KeyError(f"Saga '{name}' is not registered")
+        saga_id = saga_id or uuid.uuid4().hex
+        if self.status(saga_id) is not None:
+            raise ValueError(f"Saga '{saga_id}' already exists")
+        record = {"name": name, "status": "running", "step": 0, "data": dict(data or {}), "completed": [], "error": None}
+        self._commit(saga_id, record, "forward")
+        return saga_id
+
+    def status(self, saga_id: str) -> Optional[Dict[str, Any]]:
+        """The saga's record (a copy), or None."""
+        sagas = self.engine._core.state.data.get(self.zone) or {}
+        record = sagas.get(saga_id)
+        return copy.deepcopy(record) if record is not None else None
+
+    def sagas(self, status: Optional[str] = None) -> Dict[str, Dict[str, Any]]:
+        sagas = copy.deepcopy(dict(self.engine._core.state.data.get(self.zone) or {}))
+        return {k: v for k, v in sagas.items() if status is None or v["status"] == status}
+
+    def run_until_idle(self, max_rounds: int = 100) -> bool:
+        """Deliver outbox messages until the queue is empty (True) or `max_rounds` is reached.
+        Delivery errors are logged; the outbox keeps the failed message for the next round."""
+        for _ in range(max_rounds):
+            if self.engine._core.outbox.len() == 0:
+                return True
+            try:
+                self.engine.process_outbox()
+            except Exception as e:
+                logger.warning(f"Outbox delivery failed: {e}")
+        return self.engine._core.outbox.len() == 0
+
+    def recover(self) -> int:
+        """After a restart: re-enqueue the current message of every unfinished saga that has
+        none pending. Returns the number of messages enqueued."""
+        pending = {m["idempotency_key"] for m in self.engine._core.outbox_snapshot()["pending"]}
+        enqueued = 0
+        for saga_id, record in self.sagas().items():
+            action = {"running": "forward", "compensating": "compensate"}.get(record["status"])
+            if action is None or self._key(saga_id, record["step"], action) in pending:
+                continue
+            self.engine._core.outbox.add(self._message(saga_id, record, action))
+            enqueued += 1
+        return enqueued
+
+    def _key(self, saga_id: str, step: int, action: str) -> str:
+        return f"{saga_id}:{step}:{action}"
+
+    def _message(self, saga_id: str, record: Dict[str, Any], action: str) -> OutboxMsg:
+        key = self._key(saga_id, record["step"], action)
+        payload = {"saga_id": saga_id, "step": record["step"], "action": action}
+        return OutboxMsg(TOPIC_PREFIX + record["name"], payload, idempotency_key=key, ordering_key=saga_id)
+
+    def _commit(self, saga_id: str, record: Dict[str, Any], action: Optional[str]) -> None:
+        """Persist `record` and enqueue its next message in one transaction."""
+        with self.engine.transaction(actor="saga", admin=True) as tx:
+            tx.update(data={self.zone: {saga_id: record}})
+            if action is not None:
+                tx.outbox.add(self._message(saga_id, record, action))
+
+    def _deliver(self, msg) -> None:
+        if not msg.topic.startswith(TOPIC_PREFIX) or msg.topic[len(TOPIC_PREFIX):] not in self._sagas:
+            if self.worker is not None:
+                self.worker(msg)
+            return
+        body = msg.payload
+        saga_id, step, action = body["saga_id"], body["step"], body["action"]
+        record = self.status(saga_id)
+        expected = "running" if action == "forward" else "compensating"
+        if record is None or record["status"] != expected or record["step"] != step:
+            return  # Stale redelivery: this step was already acknowledged.
+        steps = self._sagas[record["name"]]
+        if action == "forward":
+            self._forward(saga_id, record, steps, msg)
+        else:
+            self._compensate(saga_id, record, steps, msg)
+
+    def _forward(self, saga_id, record, steps, msg) -> None:
+        step = steps[record["step"]]
+        try:
+            out = step.forward(copy.deepcopy(record["data"]), msg.idempotency_key)
+        except Exception as e:
+            if msg.attempts < step.retries:
+                raise
+            logger.warning(f"Saga '{saga_id}' step '{step.name}' failed: {e}; compensating")
+            record["error"] = f"{step.name}: {e!r}"
+            self._begin_compensation(saga_id, record, steps, record["step"])
+            return
+        if out:
+            record["data"].update(out)
+        record["completed"].append(step.name)
+        if record["step"] + 1 == len(steps):
+            record["status"] = "completed"
+            self._commit(saga_id, record, None)
+        else:
+            record["step"] += 1
+            self._commit(saga_id, record, "forward")
+
+    def _compensate(self, saga_id, record, steps, msg) -> None:
+        step = steps[record["step"]]
+        # Raising leaves the message in the outbox for redelivery.
+        step.compensate(copy.deepcopy(record["data"]), msg.idempotency_key)
+        self._begin_compensation(saga_id, record, steps, record["step"])
+
+    def _begin_compensation(self, saga_id, record, steps, below: int) -> None:
+        """Move to the last step before `below` that has a compensate handler."""
+        pending = [i for i in range(below) if steps[i].compensate is not None]
+        if pending:
+            record["status"], record["step"] = "compensating", pending[-1]
+            self._commit(saga_id, record, "compensate")
+        else:
+            record["status"] = "compensated"
+            self._commit(saga_id, record, None)
@@ -1,4 +1,4 @@
-from typing import TYPE_CHECKING, Any, Dict, Optional
+from typing import TYPE_CHECKING, Any, Dict, Optional, Tuple
 from dataclasses import dataclass
 
 # [DX] Theus v3 requires Rust Core. Fail fast if missing.
@@ -73,6 +73,26 @@ class FunctionResult:
     key: Optional[str] = None
 
 
+@dataclass(frozen=True)
+class ProcessResult:
+    """
+    [v3.6] Structured outcome of `engine.execute_result()`.
+    `version` is None when the run wrote nothing (no commit) and `status` is the
+    transaction's commit status ("committed", "staged", "duplicate" or None).
+    """
+
+    value: Any
+    process: str
+    tx_id: int
+    version: Optional[int]
+    status: Optional[str]
+    deltas: int
+    changed_paths: Tuple[str, ...]
+    outbox: int
+    retries: int
+    duration_ms: float
+
+
 # [DX] Theus v3 requires NumPy for Managed Memory but should load basic structures without it.
 try:
     import numpy as np
@@ -198,5 +218,6 @@ __all__ = [
     "ContextError",
     "StateUpdate",
     "FunctionResult",
+    "ProcessResult",
     "ManagedAllocator",
 ]
@@ -1,173 +1,2116 @@
+# Generated from the Rust signatures by build.rs -- do not edit by hand.
+# Regenerate with `python scripts/gen_stubs.py` (wraps `theus_core.generate_stubs`).
+from typing import Any
 
+class AuditAbortError(RuntimeError): ...
 
-class AuditAbortError:
-    def __init__(self, /, *args, **kwargs): ...
-
-class AuditBlockError:
-    def __init__(self, /, *args, **kwargs): ...
+class AuditBlockError(RuntimeError): ...
 
 class AuditLevel:
-    def __init__(self, /, *args, **kwargs): ...
+    """
+    Audit Level per `MIGRATION_AUDIT.md`
+    - S (Stop): Immediate halt on first failure
+    - A (Abort): Cancel current operation, allow retry
+    - B (Block): Block after threshold exceeded
+    - C (Count): Count only, never block
+    """
+    Stop: AuditLevel
+    Abort: AuditLevel
+    Block: AuditLevel
+    Count: AuditLevel
 
 class AuditLogEntry:
-    def __init__(self, /, *args, **kwargs): ...
+    timestamp: float
+    key: str
+    message: str
+    def __str__(self) -> str: ...
+    @property
+    def tags(self) -> dict[str, Any]: ...
 
 class AuditRecipe:
-    def __init__(self, /, *args, **kwargs): ...
+    level: AuditLevel
+    threshold_max: int
+    threshold_min: int
+    reset_on_success: bool
+    def __init__(self, level: AuditLevel | None = None, threshold_max: int = 3, threshold_min: int = 0, reset_on_success: bool = True) -> None: ...
 
-class AuditStopError:
-    def __init__(self, /, *args, **kwargs): ...
+class AuditStopError(RuntimeError): ...
 
 class AuditSystem:
-    def __init__(self, /, *args, **kwargs): ...
-    def get_count(self, /, key): ...
-    def get_count_all(self, /): ...
-    def get_logs(self, /): ...
-    def log(self, /, key, message): ...
-    def log_fail(self, /, key, level=None, threshold_max=None): ...
-    def log_success(self, /, key): ...
+    def __init__(self, recipe: AuditRecipe | None = None, capacity: int = 1000) -> None: ...
+    def get_count(self, key: str) -> int:
+        """Get current count for a key."""
+        ...
+    def get_count_all(self) -> int:
+        """Get total count across all keys."""
+        ...
+    def get_logs(self, tags: dict[str, Any] | None = None) -> list[AuditLogEntry]:
+        """
+        Get all logs from ring buffer.
+        [v3.6] `tags` keeps only events of transactions carrying all of those tags.
+        """
+        ...
+    def log(self, key: str, message: str) -> None:
+        """Log a general event to ring buffer."""
+        ...
+    def log_fail(self, key: str, level: AuditLevel | None = None, threshold_max: int | None = None) -> None:
+        """
+        Log a failure event. Behavior depends on `AuditLevel`.
+        Can override global level and threshold per-call.
+        """
+        ...
+    def log_success(self, key: str) -> None:
+        """Log a success event. Resets counter if configured."""
+        ...
+    @property
+    def ring_buffer_len(self) -> int:
+        """Get number of logs in buffer."""
+        ...
+
+class AuditWarning(UserWarning): ...
+
+class BlobHandle:
+    """Handle on the engine's blob store (`ctx.blobs`, `tx.blobs`, `engine.blobs`)."""
+    def __contains__(self, id: str) -> bool: ...
+    def __repr__(self) -> str: ...
+    def get(self, id: str) -> bytes:
+        """The bytes of blob `id`. Raises `KeyError` for an unknown (or collected) id."""
+        ...
+    def put(self, data: bytes) -> str:
+        """Store `data` and return its id (`"sha256:<hex>"`); equal payloads share one blob."""
+        ...
+
+class BusyError(ContextError):
+    requester: Any
+
+class CallbackProvider:
+    """[v3.6] Seeds whatever `callback()` returns."""
+    def __init__(self, callback: Any, name: str = ...) -> None: ...
+    def fetch(self) -> Any: ...
+    @property
+    def name(self) -> str: ...
 
-class AuditWarning:
-    def __init__(self, /, *args, **kwargs): ...
+class CancellationToken:
+    def __init__(self, process: str = "") -> None:
+        """A standalone token (not tied to a run), e.g. for tests or manual plumbing."""
+        ...
+    def __repr__(self) -> str: ...
+    def cancel(self, reason: str | None = None) -> bool:
+        """
+        Requests cancellation; returns False if the token was already cancelled.
+        The attached asyncio task (if any) is cancelled on its own loop.
+        """
+        ...
+    @property
+    def cancelled(self) -> bool: ...
+    @property
+    def process(self) -> str: ...
+    def raise_if_cancelled(self) -> None: ...
+    @property
+    def reason(self) -> str | None: ...
+
+class Capability:
+    """
+    [v3.6] Capability bits as flags (`Capability.READ | Capability.UPDATE`). Behaves as an int
+    (`__index__`), so it is accepted wherever a capability mask is.
+    """
+    ALL: Capability
+    APPEND: Capability
+    DELETE: Capability
+    EXECUTE: Capability
+    NONE: Capability
+    READ: Capability
+    UPDATE: Capability
+    def __and__(self, other: Any) -> Capability: ...
+    def __bool__(self) -> bool: ...
+    def __contains__(self, other: Any) -> bool:
+        """`Capability.READ in caps`: every bit of `other` is set."""
+        ...
+    def __hash__(self) -> int: ...
+    def __index__(self) -> int: ...
+    def __init__(self, value: Any) -> None: ...
+    def __int__(self) -> int: ...
+    def __invert__(self) -> Capability: ...
+    def __or__(self, other: Any) -> Capability: ...
+    def __rand__(self, other: Any) -> Capability: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: Any, op: Any) -> Any: ...
+    def __ror__(self, other: Any) -> Capability: ...
+    def __xor__(self, other: Any) -> Capability: ...
+    @property
+    def names(self) -> list[str]:
+        """Names of the set bits (`["READ", "UPDATE"]`)."""
+        ...
+
+class CommitGroup:
+    """`with engine.group_commit(...) as group:` - see the module comment."""
+    def __enter__(self) -> CommitGroup: ...
+    def __exit__(self, *_args: Any) -> None: ...
+    def __repr__(self) -> str: ...
+    def flush(self) -> list[Any]:
+        """Installs the staged transactions now; returns their results."""
+        ...
+    @property
+    def pending(self) -> int:
+        """Transactions waiting for the next flush."""
+        ...
+    @property
+    def results(self) -> list[Any]:
+        """`[{tx_id, status, version, error}]` for every flushed member, in commit order."""
+        ...
+
+class CompressedValue:
+    """
+    A Data value held compressed in the State. Reads through proxies decompress it
+    transparently; `load()` does so explicitly.
+    """
+    def __copy__(self) -> CompressedValue:
+        """Placeholders are immutable: copies share the compressed bytes."""
+        ...
+    def __deepcopy__(self, _memo: Any) -> CompressedValue: ...
+    def __eq__(self, other: Any) -> bool: ...
+    def __repr__(self) -> str: ...
+    @property
+    def codec(self) -> str: ...
+    def load(self) -> Any:
+        """Decode the value (a fresh object on every call)."""
+        ...
+    @property
+    def nbytes(self) -> int:
+        """Compressed size held in memory."""
+        ...
+    @property
+    def raw_nbytes(self) -> int:
+        """Encoded size before compression."""
+        ...
 
 class ConfigLoader:
-    def __init__(self, /, *args, **kwargs): ...
-    def load_from_string(content): ...
+    @staticmethod
+    def load_from_string(content: str) -> None: ...
+
+class ConflictError(ContextError):
+    path: Any
+    expected_version: Any
+    actual_version: Any
 
 class ConflictManager:
-    def __init__(self, /, *args, **kwargs): ...
-    def get_failure_count(self, /, key): ...
-    def is_blocked(self, /, requester=None): ...
-    def report_conflict(self, /, key): ...
-    def report_success(self, /, key): ...
+    """Manages conflict resolution policies (Backoff, Priority)"""
+    def __init__(self, max_retries: int = 5, base_backoff_ms: int = 2) -> None: ...
+    def get_failure_count(self, key: str) -> int:
+        """Get current failure count (Internal Diagnostic)"""
+        ...
+    def is_blocked(self, requester: str | None = None) -> bool:
+        """Check if action is blocked by VIP"""
+        ...
+    def report_conflict(self, key: str) -> RetryDecision:
+        """
+        Report a conflict failure for a process/key.
+        Returns a decision on whether to retry and how long to wait.
+        """
+        ...
+    def report_success(self, key: str) -> None:
+        """Report success to reset counters."""
+        ...
 
-class ContextError:
-    def __init__(self, /, *args, **kwargs): ...
+class ContextError(Exception):
+    code: Any
 
 class ContextGuard:
-    def __init__(self, /, *args, **kwargs): ...
-    def _elevate(self, /, enabled): ...
-    def log(self, /, message): ...
+    _target: Any
+    log: Any
+    def __bool__(self) -> bool:
+        """
+        [v3.6] Python truthiness of the guarded value (needs read access to it): empty
+        containers are falsy, objects without `__bool__`/`__len__` are truthy.
+        """
+        ...
+    def __contains__(self, key: Any) -> bool:
+        """[v3.6] Mapping membership only reports keys this contract can read."""
+        ...
+    def __dir__(self) -> list[str]:
+        """[v3.6] `dir(ctx)`: the guard API plus the fields this contract can read."""
+        ...
+    def __getattr__(self, name: str) -> Any: ...
+    def __getitem__(self, key: Any) -> Any: ...
+    def __init__(self, target: Any, inputs: Any, outputs: Any, path_prefix: str | None = None, tx: Transaction | None = None, is_admin: bool = False, strict_guards: bool = False, private_allowlist: list[str] | None = None, contract_warn: bool = False) -> None: ...
+    def __iter__(self) -> Any: ...
+    def __len__(self) -> int:
+        """[v3.6] `len(guard)`: size of the guarded container (needs read access to it)."""
+        ...
+    def __setattr__(self, name: str, value: Any) -> None: ...
+    def __setitem__(self, key: Any, value: Any) -> None: ...
+    def _elevate(self, enabled: bool) -> None:
+        """
+        [RFC-001] Elevate this guard to Admin status for current thread.
+        Used by `AdminTransaction` context manager.
+        """
+        ...
+    def _repr_html_(self) -> str:
+        """[v3.6] Jupyter rich display."""
+        ...
+    def _repr_pretty_(self, p: Any, cycle: bool) -> None:
+        """[v3.6] `IPython` pretty printer."""
+        ...
+    def delegation_token(self, ttl_s: float = 300.0, caps: int | None = None) -> str:
+        """
+        [v3.6] Signed token delegating this guard's rights (paths, capability ceiling narrowed
+        by `caps`, strict mode) to a worker for `ttl_s` seconds. See `theus_core.guard_from_token`.
+        """
+        ...
+    def describe(self, max_depth: int = 3, max_keys: int = 50, narrow: Any = None) -> Any:
+        """
+        [v3.6] Readable subtree as `{path: {zone, access, type, len?}}` ("rw" = writable
+        under this contract), at most `max_depth` levels and `max_keys` children per level.
+        """
+        ...
+    def get(self, key: Any, default: Any = None) -> Any:
+        """[v3.6] `dict.get` with a read check on the key; containers come back guarded."""
+        ...
+    def items(self) -> list[tuple[Any, Any]]:
+        """[v3.6] Readable `(key, value)` pairs, values guarded like `__getitem__` results."""
+        ...
+    def keys(self) -> list[Any]:
+        """[v3.6] Keys (or attribute names) this contract can read."""
+        ...
+    def log(self, message: str) -> None:
+        """
+        DX Log method: ctx.log("msg")
+        Writes to standard output for now (or could use meta logs if accessible)
+        """
+        ...
+    @property
+    def outbox(self) -> Any:
+        """
+        [v3.3 FIX] Native getter for outbox to bypass __getattr__ shadowing from #[pyclass(dict)]
+        CRITICAL: Must return raw Outbox object, NOT wrapped in `ContextGuard`.
+        The Outbox struct has its own Arc<Mutex> buffer that is shared with Transaction.
+        Wrapping it in `ContextGuard` would cause `add()` to fail silently.
+        """
+        ...
+    def plan(self, func: Any) -> list[Any]:
+        """
+        [v3.6] Dry run: calls `func(guard)` on a throwaway transaction and returns the deltas
+        it would produce as `[{path, op, value, old_value}]`. Nothing is committed.
+        NOTE: Root guards read committed state, so this tx's own pending writes are not seen.
+        """
+        ...
+    @property
+    def policy_id(self) -> int:
+        """[RFC-001] Native getter for Flyweight Verification"""
+        ...
+    @property
+    def private_allowlist(self) -> list[str]:
+        """[v3.6] Private names readable despite strict mode."""
+        ...
+    def render_tree(self, html: bool = False, max_depth: int = 3, max_keys: int = 20, max_nodes: int = 200, narrow: Any = None) -> str:
+        """
+        [v3.6] Readable subtree drawn as indented text (or HTML) with zone badges; leaf
+        values are truncated and at most `max_nodes` entries are shown.
+        """
+        ...
+    def run(self, path: str, *args: Any, **kwargs: Any) -> Any:
+        """
+        [v3.6] Invoke the callable stored at `path` as `fn(child_ctx, *args, **kwargs)`.
+        Requires read access to `path` plus the EXECUTE capability of its zone; the callable
+        gets a read-only child guard. Every invocation is recorded in the audit buffer.
+        """
+        ...
+    def values(self) -> list[Any]:
+        """[v3.6] Readable values, guarded like `__getitem__` results."""
+        ...
+
+class DeadLetter:
+    """[v3.6] A message that exhausted its delivery attempts."""
+    msg: OutboxMsg
+    error: str
+    failed_at_ms: int
+    def __repr__(self) -> str: ...
+
+class DecryptionError(ContextError): ...
+
+class DeltaOp:
+    """
+    [v3.6] Delta op codes. `DeltaEntry.op` stays a string; members compare equal to their
+    code (`DeltaOp.SET == "SET"`) and are accepted wherever an op string is.
+    """
+    SET: DeltaOp
+    SET_ITEM: DeltaOp
+    APPEND: DeltaOp
+    EXTEND: DeltaOp
+    POP: DeltaOp
+    REMOVE: DeltaOp
+    CLEAR: DeltaOp
+    UPDATE: DeltaOp
+    TENSOR_MUTATION: DeltaOp
+    def __hash__(self) -> int:
+        """Hashes like its code, so members and strings share dict/set slots."""
+        ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self, other: Any, op: Any) -> Any: ...
+    def __str__(self) -> str: ...
+    @property
+    def code(self) -> str: ...
+
+class EngineShutdownError(ContextError): ...
+
+class EnvProvider:
+    """[v3.6] Reads `os.environ` (so changes made from Python are seen)."""
+    def __init__(self, mapping: dict[str, str] | None = None, prefix: str | None = None, required: bool = False) -> None: ...
+    def fetch(self) -> dict[str, Any]: ...
+    @property
+    def name(self) -> str: ...
 
 class FSMState:
-    def __init__(self, /, *args, **kwargs): ...
+    """
+    FSM State for Workflow execution tracking.
+    Supports: Pending -> Running -> `WaitingIO` -> Complete/Failed
+    """
+    Pending: FSMState
+    Running: FSMState
+    WaitingIO: FSMState
+    Complete: FSMState
+    Failed: FSMState
 
 class FrozenDict:
-    def __init__(self, /, *args, **kwargs): ...
-    def get(self, /, key, default=None): ...
-    def items(self, /): ...
-    def keys(self, /): ...
-    def to_dict(self, /): ...
-    def values(self, /): ...
+    def __contains__(self, key: Any) -> bool: ...
+    def __deepcopy__(self, memo: Any) -> Any: ...
+    def __eq__(self, other: Any) -> bool: ...
+    def __getattr__(self, name: Any) -> Any: ...
+    def __getitem__(self, key: Any) -> Any: ...
+    def __init__(self, data: dict[str, Any]) -> None: ...
+    def __len__(self) -> int: ...
+    def __setitem__(self, _key: Any, _val: Any) -> None: ...
+    def __str__(self) -> str: ...
+    def get(self, key: Any, default: Any = None) -> Any: ...
+    def items(self) -> Any: ...
+    def keys(self) -> Any: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    def values(self) -> Any: ...
+
+class HeavyHandle:
+    """Reference to a managed Heavy value. Store it in the Heavy zone and read it with `get()`."""
+    def __copy__(self) -> HeavyHandle:
+        """A handle is a reference: copies share the managed value."""
+        ...
+    def __deepcopy__(self, _memo: Any) -> HeavyHandle: ...
+    def __dlpack__(self, *args: Any, **kwargs: Any) -> Any: ...
+    def __dlpack_device__(self) -> tuple[int, int]: ...
+    def __enter__(self) -> HeavyHandle: ...
+    def __exit__(self, *_args: Any) -> None: ...
+    def __reduce__(self) -> Any: ...
+    def __repr__(self) -> str: ...
+    def acquire(self) -> HeavyHandle:
+        """New handle holding its own reference (e.g. for a worker outliving the transaction)."""
+        ...
+    @property
+    def device(self) -> tuple[str, int] | None:
+        """`(device, index)` of a `DLPack` tensor, e.g. `("cuda", 0)`; None for other values."""
+        ...
+    def get(self) -> Any:
+        """
+        The managed value. Readable until the last reference is gone, even after this
+        handle's own `release()`.
+        """
+        ...
+    @property
+    def id(self) -> int: ...
+    def ipc_export(self) -> Any:
+        """Picklable CUDA IPC payload for the managed tensor (torch / cupy on CUDA)."""
+        ...
+    @staticmethod
+    def ipc_import(payload: dict[str, Any]) -> Any:
+        """Rebuild the tensor from an `ipc_export()` payload in the receiving process."""
+        ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def nbytes(self) -> int: ...
+    @property
+    def refcount(self) -> int:
+        """Live references (handles + pinning transactions); 0 once finalized."""
+        ...
+    def release(self) -> None:
+        """Give up this handle's reference (idempotent)."""
+        ...
+
+class IllegalTransitionError(ContextError):
+    path: Any
+    pattern: Any
+    from_state: Any
+    to_state: Any
+    allowed: Any
+
+class IntegrityError(ContextError): ...
+
+class LockPoisonedError(ContextError): ...
+
+class MaintenanceModeError(ContextError): ...
 
 class MetaLogEntry:
-    def __init__(self, /, *args, **kwargs): ...
+    timestamp: float
+    key: str
+    message: str
+    def __repr__(self) -> str: ...
+
+class MiddlewareVetoError(ContextError): ...
+
+class ObserverEngine:
+    def __reduce__(self) -> None:
+        """Observers cannot be pickled (they are bound to a live engine)."""
+        ...
+    def __repr__(self) -> str: ...
+    def add_event_watcher(self, watcher: Any) -> None:
+        """Register `watcher(topic, payload)` for events emitted via `tx.emit()`."""
+        ...
+    def history(self, path: str, depth: int = 10) -> list[Any]:
+        """
+        Commits that wrote `path` (see `TheusEngine.blame`), newest first; Private paths are
+        dropped from every entry.
+        """
+        ...
+    @property
+    def meta_epoch(self) -> int: ...
+    def on_meta_change(self, callback: Any) -> Any:
+        """Call `callback(paths, epoch)` after each commit changing Meta paths."""
+        ...
+    def read(self, path: str, default: Any = None, version: int | None = None) -> Any:
+        """
+        Redacted copy of the value at `path` ("domain.cfg.x"); `default` if missing or
+        Private. `version` reads a retained older version.
+        """
+        ...
+    def remove_event_watcher(self, watcher: Any) -> bool: ...
+    def remove_meta_listener(self, callback: Any) -> bool: ...
+    def snapshot(self, version: int | None = None) -> dict[str, Any]:
+        """Redacted copy of the whole Data zone (`{root: value}`)."""
+        ...
+    @property
+    def version(self) -> int:
+        """Current committed version."""
+        ...
 
 class OutboxCollector:
-    def __init__(self, /, *args, **kwargs): ...
-    def add(self, /, msg): ...
-    def drain(self, /): ...
-    def len(self, /): ...
+    """Helper to collect outbox messages in Transaction"""
+    def add(self, msg: OutboxMsg) -> None: ...
+    def dead_letters(self) -> list[DeadLetter]:
+        """[v3.6] Snapshot of messages that exhausted their delivery attempts."""
+        ...
+    def drain(self) -> list[OutboxMsg]:
+        """[v3.3] Drain all messages from the buffer for Python-side flush"""
+        ...
+    def len(self) -> int:
+        """[v3.3] Get current message count"""
+        ...
+    def purge_dead_letters(self, keys: list[str] | None = None) -> int:
+        """[v3.6] Drop dead letters (all, or those with the given idempotency keys)."""
+        ...
+    def requeue_dead_letters(self, keys: list[str] | None = None) -> int:
+        """
+        [v3.6] Move dead letters (all, or those with the given idempotency keys) back to the
+        front of the queue, ahead of the same-key messages held behind them.
+        """
+        ...
+    def stats(self) -> Any:
+        """[v3.6] Non-destructive delivery metrics."""
+        ...
 
 class OutboxMsg:
-    def __init__(self, /, *args, **kwargs): ...
+    topic: str
+    schema: str | None
+    content_type: str | None
+    idempotency_key: str
+    priority: int
+    ordering_key: str | None
+    attempts: int
+    created_at_ms: int
+    def __init__(self, topic: str, payload: Any, schema: str | None = None, content_type: str | None = None, idempotency_key: str | None = None, priority: int = 0, ordering_key: str | None = None) -> None: ...
+    def __repr__(self) -> str: ...
+    @staticmethod
+    def decode(topic: str, data: Any, schema: str | None = None, content_type: str | None = None, idempotency_key: str | None = None) -> OutboxMsg:
+        """Rebuild a message from wire bytes produced by `encode()`."""
+        ...
+    def encode(self) -> bytes:
+        """Serialize the payload using `content_type` (or the default json codec)."""
+        ...
+    @property
+    def payload(self) -> Any: ...
+    @property
+    def tags(self) -> dict[str, Any]: ...
+
+class PermissionDeniedError(ContextError, PermissionError):
+    path: Any
+    zone: Any
+    required: Any
+    caps: Any
+    rule: Any
+    details: Any
+
+class PinnedView:
+    """Read handle on a pinned version: `view.domain.cfg.x`, `view.read("domain.a")`."""
+    def __enter__(self) -> PinnedView: ...
+    def __exit__(self, *_args: Any) -> None: ...
+    def __getattr__(self, name: str) -> Any: ...
+    def __repr__(self) -> str: ...
+    @property
+    def pinned(self) -> bool: ...
+    def query(self, expr: str, with_paths: bool = False) -> Any:
+        """[v3.6] JSONPath-style query over this version (see query.rs)."""
+        ...
+    def read(self, path: str, default: Any = None) -> Any: ...
+    @property
+    def state(self) -> State: ...
+    def unpin(self) -> None:
+        """Releases the pin (the view stays readable; its version may now be pruned)."""
+        ...
+    @property
+    def version(self) -> int: ...
+
+class ProcessCancelledError(TransactionCancelledError): ...
 
 class ProcessContext:
-    def __init__(self, /, *args, **kwargs): ...
+    """Ephemeral Context passed to Process"""
+    state: State
+    local: dict[str, Any]
+    outbox: Any
+    tx: Transaction | None
+    token: CancellationToken
+    def __getattr__(self, name: str) -> Any: ...
+    def __init__(self, state: State, local: dict[str, Any], tx: Transaction | None = None) -> None: ...
+    @property
+    def blobs(self) -> BlobHandle:
+        """[v3.6] Blob store of the run's transaction (see blobs.rs)."""
+        ...
+    @property
+    def cancelled(self) -> bool:
+        """[v3.6] True once the run was cancelled (engine.cancel, tx.cancel, stuck-run abort)."""
+        ...
+    @property
+    def domain(self) -> Any:
+        """
+        [FIX v3.3] Explicit Domain Getter — Transaction NOT injected into `SupervisorProxy`.
+        `SupervisorProxy` queries contextvars for Transaction when it needs to log or COW.
+        """
+        ...
+    @property
+    def domain_ctx(self) -> Any: ...
+    @property
+    def global_ctx(self) -> Any: ...
+    def raise_if_cancelled(self) -> None:
+        """[v3.6] Raises `ProcessCancelledError` if the run was cancelled."""
+        ...
+    @property
+    def transaction(self) -> Any: ...
+
+class PureScope:
+    """`with engine.pure_scope(name):` marks the enclosed code as the PURE process `name`."""
+    def __enter__(self) -> None: ...
+    def __exit__(self, *_args: Any) -> bool: ...
+
+class QuotaExceededError(ContextError, MemoryError):
+    path: Any
+    limit: Any
+    requested: Any
+
+class ReentrancyError(ContextError): ...
 
 class RetryDecision:
-    def __init__(self, /, *args, **kwargs): ...
+    should_retry: bool
+    wait_ms: int
+    suggested_backoff_ms: int
+    attempts_remaining: int
+    reason: RetryReason
+    def __repr__(self) -> str: ...
+
+class RetryReason:
+    """Why a `RetryDecision` was made (v3.6)."""
+    Backoff: RetryReason
+    BlockedByVip: RetryReason
+    VipGranted: RetryReason
+    VipRetry: RetryReason
+    Exhausted: RetryReason
+
+class RuleError(ValueError): ...
 
-class SchemaViolationError:
-    def __init__(self, /, *args, **kwargs): ...
+class RuleLimitError(RuleError): ...
+
+class SchemaViolationError(ContextError): ...
+
+class SerializationError(ValueError): ...
 
 class SignalHub:
-    def __init__(self, /, *args, **kwargs): ...
-    def publish(self, /, msg): ...
-    def subscribe(self, /): ...
+    def __init__(self) -> None: ...
+    def publish(self, msg: str) -> int: ...
+    def subscribe(self) -> SignalReceiver: ...
+
+class SignalQueue:
+    """`engine.signals`: claimable queue of committed signals."""
+    def __len__(self) -> int:
+        """Unclaimed signals."""
+        ...
+    def ack(self, seq: int, claim_version: int) -> bool:
+        """Mark a claimed signal processed (removes it). False if the lease was lost."""
+        ...
+    def claim(self, n: int, consumer_id: str, lease_s: float | None = None, topic: str | None = None) -> list[Any]:
+        """
+        Lease up to `n` unclaimed signals (oldest first, optionally only `topic`) to
+        `consumer_id` for `lease_s` seconds (default: `set_lease`). Returns
+        `[{seq, topic, payload, version, claim_version, attempts}]`; pass `seq` and
+        `claim_version` back to `ack` / `renew` / `release`.
+        """
+        ...
+    def release(self, seq: int, claim_version: int) -> bool:
+        """Give a claimed signal back without processing it. False if the lease was lost."""
+        ...
+    def renew(self, seq: int, claim_version: int, lease_s: float | None = None) -> bool:
+        """Extend a lease by `lease_s` seconds from now. False if the lease was lost."""
+        ...
+    def set_capacity(self, capacity: int) -> None: ...
+    def set_lease(self, lease_s: float) -> None:
+        """Default lease for `claim` / `renew`."""
+        ...
+    def stats(self) -> Any:
+        """
+        `{pending, claimed, enqueued, acked, released, dropped, reclaimed, reclaimed_from,
+        capacity, lease_s}`; `reclaimed_from` counts expired leases per consumer.
+        """
+        ...
 
 class SignalReceiver:
-    def __init__(self, /, *args, **kwargs): ...
-    def recv(self, /): ...
-    def recv_async(self, /): ...
+    def recv(self) -> str:
+        """Blocking receive. intended to be called via `asyncio.to_thread()`"""
+        ...
+    def recv_async(self) -> Any:
+        """
+        Non-blocking async receive. Returns Python awaitable that can be cancelled.
+
+        # Example
+        ```python
+        msg = await rx.recv_async()
+        # or with timeout:
+        msg = await asyncio.wait_for(rx.recv_async(), timeout=5.0)
+        ```
+        """
+        ...
+
+class SnapshotFormatError(SerializationError): ...
+
+class SnapshotIncompatibleError(SerializationError): ...
+
+class SpilledValue:
+    """
+    Placeholder for a Data value stored in the spill file. Reads through proxies load it
+    transparently; `load()` does so explicitly.
+    """
+    def __copy__(self) -> SpilledValue:
+        """Placeholders are immutable references: copies share the record."""
+        ...
+    def __deepcopy__(self, _memo: Any) -> SpilledValue: ...
+    def __eq__(self, other: Any) -> bool: ...
+    def __repr__(self) -> str: ...
+    def load(self) -> Any:
+        """Decode the value (a fresh object on every call)."""
+        ...
+    @property
+    def nbytes(self) -> int:
+        """Encoded size on disk."""
+        ...
 
 class State:
-    def __init__(self, /, *args, **kwargs): ...
-    def domain_proxy(self, /, read_only=None): ...
-    def get_meta_logs(self, /): ...
-    def log_meta(self, /, key, message): ...
-    def publish_signals(self, /, signal=None): ...
-    def restrict_view(self, /): ...
-    def update(self, /, data=None, heavy=None, signal=None): ...
+    """Theus v3 Immutable State"""
+    def __init__(self, data: Any = None, heavy: Any = None, signal: Any = None, version: int = 1, meta_capacity: int = 1000) -> None: ...
+    def __setattr__(self, _name: str, _value: Any) -> None: ...
+    @property
+    def data(self) -> Any: ...
+    @property
+    def domain(self) -> Any: ...
+    def domain_proxy(self, read_only: bool | None = None) -> Any:
+        """v3.1: Returns domain wrapped in `SupervisorProxy` (preserves `PyObject` idiomatics)"""
+        ...
+    def get_meta_logs(self) -> list[MetaLogEntry]:
+        """Retrieve persistent meta logs (shared across state versions)."""
+        ...
+    @property
+    def heavy(self) -> Any: ...
+    @property
+    def integrity_hash(self) -> int | None:
+        """
+        [v3.6] Checksum of the Data zone as recorded at commit time; None unless escape
+        tracking is on.
+        """
+        ...
+    def last_writer(self, path: str) -> dict[str, Any] | None:
+        """
+        [v3.6] Who last changed `path` (or the nearest tracked ancestor):
+        `{"version", "tx", "actor"}`, or None if no commit has written it.
+        """
+        ...
+    def log_meta(self, key: str, message: str) -> None:
+        """Log a system event to the Meta Zone Ring Buffer."""
+        ...
+    @property
+    def meta(self) -> list[MetaLogEntry]: ...
+    def publish_signals(self, signal: Any = None) -> None:
+        """
+        [INC-023] Deferred signal dispatch — call AFTER data is committed to engine.state.
+        `State.update()` populates `last_signals` for Flux but does NOT publish to the channel.
+        This method does the actual Tokio `broadcast::send`, guaranteeing that any subscriber
+        who receives an event can immediately query engine.state and see consistent data.
+        """
+        ...
+    def restrict_view(self) -> State: ...
+    @property
+    def signal(self) -> Any: ...
+    @property
+    def signals(self) -> Any: ...
+    def update(self, data: Any = None, heavy: Any = None, signal: Any = None) -> State: ...
+    @property
+    def version(self) -> int: ...
 
 class SupervisorCore:
-    def __init__(self, /, *args, **kwargs): ...
-    def contains(self, /, key): ...
-    def get_version(self, /, key): ...
-    def keys(self, /): ...
-    def read(self, /, key): ...
-    def remove(self, /, key): ...
-    def write(self, /, key, val): ...
+    """`SupervisorCore` - The central state manager using references"""
+    def __init__(self) -> None: ...
+    def contains(self, key: str) -> bool:
+        """Check if key exists"""
+        ...
+    def get_version(self, key: str) -> int | None:
+        """Get version for a key"""
+        ...
+    def keys(self) -> list[str]:
+        """Get all keys"""
+        ...
+    def read(self, key: str) -> Any:
+        """Read a value by key - returns reference (zero-copy)"""
+        ...
+    def remove(self, key: str) -> bool:
+        """Remove a key"""
+        ...
+    def write(self, key: str, val: Any) -> None:
+        """Write a value by key (creates if not exists)"""
+        ...
 
 class SupervisorProxy:
-    def __init__(self, /, *args, **kwargs): ...
-    def _set_capabilities(self, /, caps): ...
-    def append(self, /, item): ...
-    def clear(self, /): ...
-    def extend(self, /, iterable): ...
-    def get(self, /, key, default=None): ...
-    def insert(self, /, index, item): ...
-    def is_proxy(self, /): ...
-    def items(self, /): ...
-    def keys(self, /): ...
-    def path(self, /): ...
-    def pop(self, /, key_or_index=None, default=None): ...
-    def popitem(self, /): ...
-    def remove(self, /, value): ...
-    def reverse(self, /): ...
-    def setdefault(self, /, key, default=None): ...
-    def sort(self, /, kwargs=None): ...
-    def to_dict(self, /): ...
-    def update(self, /, other=None, kwargs=None): ...
-    def values(self, /): ...
-    def wrap_result(self, /, key_or_path, val): ...
+    """
+    `SupervisorProxy` - The Gatekeeper for Python object access
+
+    Unlike `FrozenDict` which returns copies, `SupervisorProxy` returns
+    the original Python object while intercepting mutations.
+    """
+    capabilities: int
+    def __contains__(self, key: Any) -> bool:
+        """Check if key exists (for 'in' operator)"""
+        ...
+    @property
+    def __dict__(self) -> Any:
+        """Expose internals as dict for Pydantic/Standard Library compatibility"""
+        ...
+    def __dir__(self) -> list[str]:
+        """[v3.6] `dir(proxy)`: proxy methods plus the readable keys/attributes of the target."""
+        ...
+    def __getattr__(self, name: str) -> Any:
+        """
+        Get attribute - Returns original object (or nested Proxy)
+        v3.1: Supports Dict dot-access (d.key) fallback
+        """
+        ...
+    def __getattribute__(self, name: str) -> Any:
+        """
+        [RFC-001 §10] Intercept ALL attribute access at C level.
+        __getattribute__ runs BEFORE `getset_descriptors` (including __dict__).
+        Without this, `PyO3`'s auto-generated __dict__ descriptor bypasses __getattr__.
+        """
+        ...
+    def __getitem__(self, key: Any) -> Any: ...
+    def __getnewargs__(self) -> Any: ...
+    def __getstate__(self) -> Any: ...
+    def __init__(self, target: Any, path: str = ..., read_only: bool = False, transaction: Any = None, is_shadow: bool = False, capabilities: int = 15) -> None: ...
+    def __iter__(self) -> Any:
+        """Iterator support"""
+        ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str:
+        """String representation - More descriptive for debugging"""
+        ...
+    def __richcmp__(self, other: Any, op: Any) -> Any: ...
+    def __setattr__(self, name: str, value: Any) -> None:
+        """
+        Set attribute - Intercept for logging and permission check
+        v3.1: Supports Dict dot-access (d.key = val -> d[`key`] = val)
+        """
+        ...
+    def __setitem__(self, key: Any, value: Any) -> None:
+        """Set item - For dict-like access ctx.domain[`key`] = value"""
+        ...
+    def __setstate__(self, state: Any) -> None: ...
+    def __str__(self) -> str: ...
+    def _repr_html_(self) -> str:
+        """[v3.6] Jupyter rich display."""
+        ...
+    def _repr_pretty_(self, p: Any, cycle: bool) -> None:
+        """[v3.6] `IPython` pretty printer."""
+        ...
+    def _set_capabilities(self, caps: int) -> None: ...
+    def append(self, item: Any) -> None: ...
+    def clear(self) -> None: ...
+    def describe(self, max_depth: int = 3, max_keys: int = 50, narrow: Any = None) -> Any:
+        """
+        [v3.6] Readable subtree as `{path: {zone, access, type, len?}}`, at most `max_depth`
+        levels and `max_keys` children per level. `narrow(path, writable) -> bool | None`
+        can restrict it further.
+        """
+        ...
+    def extend(self, iterable: Any) -> None: ...
+    def get(self, key: Any, default: Any = None) -> Any: ...
+    def insert(self, index: Any, item: Any) -> None: ...
+    def is_proxy(self) -> bool:
+        """
+        Helper for users confused by type checks
+        "isinstance(proxy, dict)" fails, so we provide this hint.
+        """
+        ...
+    def items(self) -> Any: ...
+    def keys(self) -> Any: ...
+    def path(self) -> str: ...
+    def pop(self, key_or_index: Any = None, default: Any = None) -> Any: ...
+    def popitem(self) -> Any: ...
+    @property
+    def read_only(self) -> bool: ...
+    def remove(self, value: Any) -> None: ...
+    def render_tree(self, html: bool = False, max_depth: int = 3, max_keys: int = 20, max_nodes: int = 200, narrow: Any = None) -> str:
+        """
+        [v3.6] Readable subtree drawn as indented text (or HTML) with zone badges; leaf
+        values are truncated and at most `max_nodes` entries are shown.
+        """
+        ...
+    def reverse(self) -> None: ...
+    def schema_fields(self) -> list[str]:
+        """
+        [v3.6] Field names the registered schema declares at this path (`[]` when unknown or
+        outside a transaction).
+        """
+        ...
+    def setdefault(self, key: Any, default: Any = None) -> Any: ...
+    def sort(self, kwargs: dict[str, Any] | None = None) -> None: ...
+    @property
+    def supervisor_target(self) -> Any:
+        """Get the underlying target (for internal use)"""
+        ...
+    def to_dict(self, *, zones: list[Any] | None = None, include_private: bool | None = None, max_depth: int | None = None) -> Any:
+        """
+        Conversion to dict (Delegates to target or returns None)
+        [v3.6] Any keyword switches to a selective export walked here: only keys whose full
+        path resolves to one of `zones` (names or `ContextZone`) are kept, `internal_*` and
+        `_underscore` keys are dropped unless `include_private`, and containers nested deeper
+        than `max_depth` levels are omitted.
+        """
+        ...
+    def update(self, other: Any = None, kwargs: Any = None) -> None: ...
+    def values(self) -> Any: ...
+    def wrap_result(self, key_or_path: str, val: Any) -> Any: ...
+
+class TenantHandle:
+    """Handle on one tenant's slice of the engine (`engine.tenant(name)`)."""
+    def __repr__(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    def path(self, path: str) -> str:
+        """Absolute path of the tenant-relative `path`."""
+        ...
+    def query(self, expr: str, with_paths: bool = False) -> Any:
+        """`engine.query()` with `$` at the tenant root; result paths are tenant-relative."""
+        ...
+    def read(self, path: str, default: Any = None) -> Any:
+        """Latest committed value at the tenant-relative `path` (containers as read-only proxies)."""
+        ...
+    @property
+    def root(self) -> str:
+        """`tenants.<name>`."""
+        ...
+    def transaction(self, write_timeout_ms: int = 5000, actor: str | None = None, tags: dict[str, Any] | None = None, isolation: str | None = None, op_id: str | None = None, partial_commit: bool = False) -> Transaction:
+        """
+        Transaction whose writes and reads are rooted under this tenant (same options as
+        `engine.transaction()`, minus `admin`).
+        """
+        ...
+    def validate_contract(self, inputs: list[str] | None = None, outputs: list[str] | None = None) -> tuple[list[str], list[str]]:
+        """
+        Contract paths (tenant-relative, or absolute under this tenant's root) rooted under the
+        tenant: `(inputs, outputs)`. Raises `PermissionDeniedError` (rule "tenant") for the first
+        path that escapes the root.
+        """
+        ...
+
+class TenantQuotaExceededError(QuotaExceededError):
+    tenant: Any
+    resource: Any
 
 class TheusEngine:
-    def __init__(self, /, *args, **kwargs): ...
-    def attach_worker(self, /, worker): ...
-    def compare_and_swap(self, /, expected_version, data=None, heavy=None, signal=None, requester=None): ...
-    def execute_process_async(self, /, name, func, tx=None): ...
-    def process_outbox(self, /): ...
-    def report_conflict(self, /, process_name): ...
-    def report_success(self, /, process_name): ...
-    def set_audit_system(self, /, audit): ...
-    def set_schema(self, /, schema): ...
-    def set_strict_cas(self, /, enabled): ...
-    def set_strict_guards(self, /, enabled): ...
-    def transaction(self, /, write_timeout_ms=5000): ...
+    def __init__(self) -> None: ...
+    def add_coercion(self, path: str, rules: Any, priority: int = ...) -> None:
+        """
+        [v3.6] Normalize values written to `path` (middleware pattern) before they are logged.
+        `rules` is `"to_decimal"` / `"to_utc"` / `"strip_strings"`, a callable `rule(value) -> value`,
+        or a list of those applied in order. Registered as middleware "coerce:<path>"; re-adding
+        a path replaces its rules.
+        """
+        ...
+    def add_event_watcher(self, watcher: Any) -> None:
+        """[v3.6] Register `watcher(topic, payload)` for events emitted via `tx.emit()`."""
+        ...
+    def add_middleware(self, name: str, handler: Any, priority: int = 0, paths: list[str] | None = None, ops: list[str] | None = None) -> None:
+        """
+        [v3.6] Intercept proxy operations: `handler(path, value, op) -> value` runs on leaf
+        reads ("get") and writes ("set" / "append" / "insert"), may transform the value or
+        veto by raising `MiddlewareVetoError`. Writes run by ascending `priority`, reads in
+        reverse. `paths` (prefixes, `*` = one segment) and `ops` narrow where it applies.
+        Re-adding `name` replaces it; applies to transactions opened afterwards too.
+        """
+        ...
+    def alloc_heavy(self, name: str, value: Any, nbytes: int | None = None, on_release: Any = None) -> HeavyHandle:
+        """
+        [v3.6] Register a Heavy value under reference counting and return its handle.
+        `nbytes` defaults to `value.nbytes` (or `sys.getsizeof`) and counts against the
+        heavy quota; `on_release(value)` runs once no handle or open transaction holds it.
+        """
+        ...
+    @property
+    def approval_paths(self) -> list[str]: ...
+    def approve(self, id: int, approver: str) -> int:
+        """
+        [v3.6] Commit proposal `id` on behalf of `approver` (a GRANT holder other than the
+        proposer). Returns the new state version.
+        """
+        ...
+    @property
+    def approvers(self) -> list[str]: ...
+    def attach_inbox_handler(self, handler: Any) -> None:
+        """[v3.6] Register the inbox handler: `handler(tx, event)` runs inside a transaction."""
+        ...
+    def attach_worker(self, worker: Any) -> None: ...
+    def blame(self, path: str, depth: int = 10, tags: dict[str, Any] | None = None) -> list[Any]:
+        """
+        [v3.6] Recent writers of `path` (or of paths above / below it), newest first:
+        `[{version, tx, actor, tags, paths, ts_ms}]`. `tx` is None for `compare_and_swap`
+        writes, whose actor is the `requester`. `tags` keeps only commits carrying those tags.
+        """
+        ...
+    def blob_stats(self) -> Any:
+        """[v3.6] `{dir, blobs, bytes, referenced, reclaimed}` of the blob store."""
+        ...
+    @property
+    def blobs(self) -> BlobHandle:
+        """
+        [v3.6] Content-addressable blob store: `engine.blobs.put(data)` returns an id to keep
+        in state instead of the bytes. Inside processes use `ctx.blobs` (protects the blob from
+        `gc_blobs` until the transaction closes).
+        """
+        ...
+    def break_glass(self, paths: list[str], reason: str, ttl_s: float, actor: str | None = None) -> int:
+        """
+        [v3.6] Lift Private-zone and tenancy restrictions on `paths` for `ttl_s` seconds, for
+        incidents (see `break_glass.rs`). `reason` is mandatory; the grant is audited and
+        announced on the outbox, and revoked automatically at expiry. Returns the grant id.
+        """
+        ...
+    def break_glass_grants(self) -> Any:
+        """[v3.6] Active break-glass grants: `[{id, paths, reason, actor, granted_at_ms, expires_at_ms}]`."""
+        ...
+    def cancel(self, target: Any, reason: str | None = None) -> int:
+        """
+        [v3.6] Cancels every in-flight run of `target` (a process name) or the given
+        `CancellationToken`; returns how many runs were newly cancelled.
+        """
+        ...
+    def cancel_timer(self, timer_id: str) -> bool:
+        """[v3.6] Drop a pending timer. Returns False if there was none."""
+        ...
+    def capability_key(self) -> bytes:
+        """[v3.6] Token signing key (hand it to workers over a trusted channel)."""
+        ...
+    def change_subscriptions(self) -> Any:
+        """[v3.6] `{id: {paths, fields, ops, include_old, events, errors}}` of change subscriptions."""
+        ...
+    def clear_faults(self, point: str | None = None) -> None:
+        """[v3.6] Disarm one injection point, or all of them."""
+        ...
+    def committed_op(self, op_id: str) -> int | None:
+        """
+        [v3.6] Version the idempotent operation `op_id` committed in (None if unknown or
+        already forgotten).
+        """
+        ...
+    def compare_and_swap(self, expected_version: int, data: Any = None, heavy: Any = None, signal: Any = None, requester: str | None = None) -> None: ...
+    def compression_stats(self) -> Any:
+        """
+        [v3.6] `{paths: {prefix: {codec, values, raw_bytes, stored_bytes, saved_bytes}},
+        raw_bytes, stored_bytes, saved_bytes, reads, hits, misses, hit_rate, cached_bytes,
+        cache_bytes}` of compressed storage.
+        """
+        ...
+    def computed(self, path: str) -> Any:
+        """[v3.6] The value of the computed `path` for the committed state (read-through)."""
+        ...
+    def computed_stats(self) -> Any:
+        """[v3.6] `{path: {hits, misses, waits, errors, cached_version}}` of computed values."""
+        ...
+    def copiers(self) -> list[str]:
+        """[v3.6] Type names with a registered copier."""
+        ...
+    def create_index(self, pattern: str, name: str | None = None) -> str:
+        """
+        [v3.6] Maintain an index of the values at `pattern` (`"domain.orders[*].customer_id"`)
+        for `lookup()`. `name` defaults to the pattern; re-creating a name rebuilds it.
+        Returns the name.
+        """
+        ...
+    def define_computed(self, path: str, func: Any, deps: list[str] | None = None) -> None:
+        """
+        [v3.6] Cache `func(state)` at `path` (a name for `computed()`, not a state path).
+        It is recomputed when a commit touches one of `deps` (every commit without deps);
+        concurrent readers of a stale value share one computation. Re-defining replaces it.
+        """
+        ...
+    def define_state_machine(self, path_pattern: str, transitions: dict[str, Any], initial: list[str] | None = None) -> None:
+        """
+        [v3.6] Lifecycle field: proxy writes to paths matching `path_pattern` (`*` = one
+        segment) must follow `transitions` (`{state: [next states]}`); a first value must be
+        in `initial` (default: any declared state). Illegal jumps raise `IllegalTransitionError`
+        and are audited as `ILLEGAL_TRANSITION`. Redefining a pattern replaces its machine.
+        """
+        ...
+    @property
+    def deterministic_seed(self) -> int | None: ...
+    def drop_index(self, name: str) -> bool:
+        """[v3.6] Drop index `name`. Returns False if there was none."""
+        ...
+    def dumps_incremental(self) -> bytes:
+        """
+        [v3.6] Serialize only what changed since the previous snapshot (`dumps_state()` or an
+        earlier `dumps_incremental()`): changed fields, key lists, op ids and timers. Chains
+        are replayed with `load_snapshot(full, *incrementals)`.
+        """
+        ...
+    def dumps_state(self) -> bytes:
+        """
+        [v3.6] Serialize the committed Data + Heavy zones (plus version, key versions, committed op ids and pending timers) to
+        msgpack bytes for cross-process transfer. numpy arrays travel as raw buffers, and
+        shared-memory arrays as their segment name (zero-copy). Never falls back to pickle.
+        """
+        ...
+    def enable_search(self, paths: list[str]) -> None:
+        """
+        [v3.6] Make the strings at (and under) `paths` ("domain.tickets.*.body") searchable
+        with `search()`. Replaces the previous paths; an empty list turns search off.
+        """
+        ...
+    def encrypt_fields(self, paths: list[str], key_provider: Any, key_id: str = ..., name: str = "encryption", priority: int = ...) -> None:
+        """
+        [v3.6] Store values at `paths` (middleware patterns, e.g. "domain.users.*.token")
+        encrypted: proxy writes and matching `tx.update()` / `update_many()` values are sealed
+        with the key `key_provider(key_id) -> 32 bytes`, bound to their path, and proxy reads
+        decrypt, so snapshots, deltas and audit values only hold ciphertext.
+        Registered as middleware `name`; the high default `priority` makes it run last on write
+        and first on read, so other middlewares see plaintext.
+        """
+        ...
+    def enter_maintenance(self, reason: str) -> None:
+        """
+        [v3.6] Engine-wide read-only mode: commits, CAS and proxy writes fail fast
+        with `MaintenanceModeError` carrying `reason` until `exit_maintenance()`.
+        """
+        ...
+    def eval_rule(self, path: str, rule: str, timeout_ms: int = 50, max_steps: int = ..., max_memory: int = ...) -> Any:
+        """
+        [v3.6] Evaluate an untrusted rule expression against a read-only copy of the Data
+        subtree at `path` ("" = whole zone). Runs in the Rust sandbox (`crate::rules`) with
+        step/time/memory budgets; exceeding one raises `RuleLimitError`, bad rules `RuleError`.
+        """
+        ...
+    def execute_process_async(self, name: str, func: Any, tx: Any = None) -> Any: ...
+    def exit_maintenance(self) -> None: ...
+    def export_snapshot(self) -> bytes:
+        """
+        [v3.6] Committed state in the stable, versioned snapshot container (see
+        `snapshot_format.rs`): header with config digest, schema fingerprint and zone policies,
+        the state payload, the embedded JSON schema, and a SHA-256 trailer.
+        """
+        ...
+    def fault_stats(self) -> Any:
+        """[v3.6] `{point: {probability, remaining, fired}}` for armed faults."""
+        ...
+    def fsck(self, repair: bool = False) -> Any:
+        """
+        [v3.6] Self-check of the engine's internal bookkeeping (see fsck.rs): open transactions'
+        shadows, conflict stamps, outbox queue, audit ring and the Heavy / blob / spill registries.
+        Returns `{ok, version, checks, issues, repaired}`; `repair=True` fixes the issues marked
+        repairable.
+        """
+        ...
+    def gc_blobs(self) -> int:
+        """
+        [v3.6] Delete blobs no retained state version references (see `set_version_retention`
+        and `pin`); blobs put by still-open transactions are kept. Returns the number deleted.
+        """
+        ...
+    def grant_approver(self, approver: str) -> None:
+        """[v3.6] Give `approver` the GRANT capability (may approve/reject proposals)."""
+        ...
+    def group_commit(self, max_batch: int = 64, window_ms: int | None = None) -> CommitGroup:
+        """
+        [v3.6] `with engine.group_commit(max_batch, window_ms) as group:` merges the commits
+        of transactions closed inside the block into shared versions (one per flush). See
+        `group.results` / `tx.commit_status` for per-transaction outcomes.
+        """
+        ...
+    def health(self) -> Any:
+        """
+        [v3.6] Process health: `{status: "ok"|"degraded", running: [{run_id, process, tx_id,
+        elapsed_ms, since_heartbeat_ms, heartbeats, expected_ms, stuck, aborted}], stuck,
+        aborted_total, policy}`.
+        """
+        ...
+    def heartbeat(self, process: str) -> int:
+        """
+        [v3.6] Dead-man switch: record progress for the running `process` (called from the
+        process itself). Returns how many of its runs were refreshed.
+        """
+        ...
+    def heavy_usage(self) -> Any:
+        """[v3.6] `{used_bytes, quota_bytes, handles}` of the managed Heavy store."""
+        ...
+    def import_snapshot(self, blob: bytes, strict: bool = True) -> None:
+        """
+        [v3.6] Replace the committed state with an `export_snapshot()` container. Format errors
+        raise `SnapshotFormatError`; with `strict` (default) a different schema or conflicting zone
+        policies raise `SnapshotIncompatibleError`. Nothing is loaded on error.
+        """
+        ...
+    def index_stats(self) -> Any:
+        """[v3.6] `{name: {pattern, keys, entries}}` of the secondary indexes."""
+        ...
+    def ingest(self, event: Any, dedup_key: str | None = None, order_key: str | None = None, sequence: int | None = None) -> int:
+        """
+        [v3.6] Inbox: consume an external event transactionally.
+        - `dedup_key` (or `event.idempotency_key`) already consumed => skipped.
+        - `order_key` + `sequence` (starting at 1) enforce per-key ordering; early events are parked.
+          A parked event whose handler fails stays parked (audited as `INBOX_HANDLER_FAILED`) and is
+          retried by the next ingest for its key.
+        Returns the number of events applied by this call (0 = duplicate/stale/parked).
+        """
+        ...
+    def inject_fault(self, point: str, probability: float = 1.0, times: int | None = None, message: str | None = None) -> None:
+        """
+        [v3.6] Chaos testing: make injection `point` ("commit", "cas", "schema", "shadow",
+        "outbox", "panic") fail with `probability`, at most `times` times. Failures raise the real
+        exception type for that point, so retry / rollback paths are exercised as-is; "panic"
+        panics inside the commit's critical section.
+        """
+        ...
+    @staticmethod
+    def inspect_snapshot(blob: bytes) -> dict[str, Any]:
+        """
+        [v3.6] Validate an `export_snapshot()` container and return its header, plus the
+        embedded JSON schema under "schema" (None without one).
+        """
+        ...
+    def invalidate_computed(self, path: str | None = None) -> None:
+        """[v3.6] Drop the cached value of `path` (all paths if None); the next read recomputes."""
+        ...
+    def is_processed(self, key: str) -> bool:
+        """[v3.6] Worker API: has the side effect for `key` already completed?"""
+        ...
+    @property
+    def is_shutdown(self) -> bool: ...
+    @property
+    def isolation(self) -> str: ...
+    def issue_capability_token(self, inputs: list[str], outputs: list[str], caps: int = ..., ttl_s: float = 300.0, strict_guards: bool = False) -> str:
+        """
+        [v3.6] Signed token granting `inputs`/`outputs` (capped by `caps`) for `ttl_s`
+        seconds; workers rebuild the guard with `theus_core.guard_from_token(target, token, key)`.
+        """
+        ...
+    def load_snapshot(self, full: bytes, *incrementals: Any) -> None:
+        """
+        [v3.6] Replace the committed state with a full snapshot plus the incrementals taken
+        after it, in order. Each link's base version and digest are checked; on any mismatch
+        nothing is loaded.
+        """
+        ...
+    def load_state(self, blob: bytes) -> None:
+        """
+        [v3.6] Replace the committed state with one produced by `dumps_state()`. The signal
+        hub and meta log of this engine are kept; version and key versions come from the blob.
+        """
+        ...
+    @staticmethod
+    def loads_state(blob: bytes) -> TheusEngine:
+        """[v3.6] Build a fresh engine whose state is loaded from `dumps_state()` bytes."""
+        ...
+    def lookup(self, index: str, value: Any) -> list[str]:
+        """[v3.6] Sorted paths whose value at index `index` equals `value` (committed state)."""
+        ...
+    @property
+    def maintenance_reason(self) -> str | None:
+        """[v3.6] Current maintenance reason, or None when writable."""
+        ...
+    def mark_processed(self, key: str) -> bool:
+        """[v3.6] Worker API: record completion of `key`. Returns False if already recorded."""
+        ...
+    @property
+    def meta_epoch(self) -> int:
+        """[v3.6] Config epoch: bumped by every commit that changes a Meta path."""
+        ...
+    def middleware_stats(self, reset: bool = False) -> dict[str, Any]:
+        """[v3.6] Per-middleware timing: `{name: {priority, calls, vetoes, errors, total_ms, avg_us}}`."""
+        ...
+    def middlewares(self) -> list[str]:
+        """[v3.6] Registered middleware names in write order."""
+        ...
+    def observed_access(self, process: str) -> dict[str, Any]:
+        """
+        [v3.6] `{runs, window, reads, writes}`: the paths `process` was allowed to read / write
+        over its last `runs` (at most `window`) tracked runs.
+        """
+        ...
+    def observer(self) -> ObserverEngine:
+        """
+        [v3.6] Read-only handle for plugins: reads, watchers and history with Private-zone
+        data always redacted, and no way to open a transaction or reach this engine.
+        """
+        ...
+    def on_meta_change(self, callback: Any) -> Any:
+        """
+        [v3.6] Call `callback(paths, epoch)` after each commit changing Meta paths. Returns
+        `callback`, so it also works as a decorator.
+        """
+        ...
+    def open_transactions(self) -> list[Any]:
+        """[v3.6] Transactions created on this engine that have not exited yet (oldest first)."""
+        ...
+    @property
+    def outbox(self) -> OutboxCollector:
+        """[v3.3] Expose Engine Outbox for manual flushing"""
+        ...
+    def outbox_snapshot(self) -> Any:
+        """[v3.6] Export pending messages + processed-id window as plain data for persistence."""
+        ...
+    def peek_pending(self, path: str) -> list[Any]:
+        """
+        [v3.6] Uncommitted writes overlapping `path` from open transactions opened with
+        `provisional=True`: `[{tx_id, actor, path, op, value, provisional}]`, oldest
+        transaction first. Values are copies; committed state and isolation are unaffected.
+        """
+        ...
+    def pending_traces(self) -> list[str]:
+        """[v3.6] Processes armed by `trace()` whose next run has not started yet."""
+        ...
+    def pin(self, version: int | None = None) -> PinnedView:
+        """
+        [v3.6] Read handle on `version` (None = current) that keeps it alive while writers
+        continue; older versions must still be retained (`set_version_retention`). Unpinned by
+        `view.unpin()`, leaving a `with` block, or when the view is garbage-collected.
+        """
+        ...
+    def pinned_versions(self) -> Any:
+        """[v3.6] `{"retention", "retained": [versions], "pinned": {version: views}}`."""
+        ...
+    @property
+    def private_allowlist(self) -> list[str]: ...
+    def process_outbox(self) -> None:
+        """
+        NOTE: The engine is not borrowed while the worker runs, so a worker may open
+        transactions (e.g. to record an acknowledgement) on this engine.
+        [v3.6] Messages sharing an `ordering_key` with a dead letter stay queued until it is
+        requeued or purged.
+        """
+        ...
+    def proposals(self) -> list[Any]:
+        """[v3.6] Pending proposals: `[{id, tx, proposer, writes: {path: value}, created_ms}]`."""
+        ...
+    @property
+    def pure_io_policy(self) -> tuple[str, list[str]]:
+        """[v3.6] `(mode, allow)` set by `set_pure_io_policy`."""
+        ...
+    def pure_io_violations(self, clear: bool = False) -> list[Any]:
+        """
+        [v3.6] I/O seen inside PURE processes: `[{process, category, event, detail,
+        rejected, ts_ms}]`, oldest first (the last 1000 are kept).
+        """
+        ...
+    def pure_scope(self, process: str, effects: list[str] | None = None, contract: str | None = None) -> PureScope:
+        """
+        [v3.6] Context manager wrapping a process call (`process` names it in records).
+        With `effects` (the contract's effect budget, `contract` its reference) every effect
+        class outside the budget is rejected; otherwise the PURE I/O policy applies.
+        """
+        ...
+    def query(self, expr: str, version: int | None = None, with_paths: bool = False) -> Any:
+        """
+        [v3.6] Evaluate a JSONPath-style `expr` ("$.domain.orders[?(@.status == 'failed')]")
+        over the Data zones of `version` (None = current; older ones must be retained).
+        Returns plain values - or `(path, value)` tuples - with Private-zone data redacted.
+        """
+        ...
+    @property
+    def recording(self) -> str | None:
+        """[v3.6] Path of the active recording, if any."""
+        ...
+    def reject(self, id: int, approver: str, reason: str | None = None) -> None:
+        """[v3.6] Drop proposal `id` (same GRANT / two-person checks as `approve`)."""
+        ...
+    def remove_coercion(self, path: str) -> bool:
+        """[v3.6] Drop the coercion rules of `path`. Returns False if there were none."""
+        ...
+    def remove_computed(self, path: str) -> bool:
+        """[v3.6] Forget the computed `path`. Returns False if it was not defined."""
+        ...
+    def remove_event_watcher(self, watcher: Any) -> bool:
+        """[v3.6] Remove a watcher previously added. Returns True if it was registered."""
+        ...
+    def remove_meta_listener(self, callback: Any) -> bool:
+        """[v3.6] Remove a Meta listener. Returns True if it was registered."""
+        ...
+    def remove_middleware(self, name: str) -> bool:
+        """[v3.6] Unregister a middleware. Returns False if there was none."""
+        ...
+    def remove_state_machine(self, path_pattern: str) -> bool:
+        """[v3.6] Stop enforcing the machine for `path_pattern`. Returns False if there was none."""
+        ...
+    def report_conflict(self, process_name: str) -> RetryDecision: ...
+    def report_success(self, process_name: str) -> None: ...
+    def require_approval(self, paths: list[str]) -> None:
+        """
+        [v3.6] Paths whose writes need a second approver (two-person rule). Commits of
+        non-admin transactions park such writes as proposals; see `proposals()`.
+        Replaces the previous list; an empty list disables the rule.
+        """
+        ...
+    def restore_outbox(self, snapshot: dict[str, Any]) -> None:
+        """[v3.6] Restore a snapshot produced by `outbox_snapshot()` (e.g. after a crash)."""
+        ...
+    def revoke_approver(self, approver: str) -> bool: ...
+    def revoke_break_glass(self, id: int) -> bool:
+        """[v3.6] End break-glass grant `id` early. Returns False if it was not active."""
+        ...
+    def schedule_process(self, process: str, delay_ms: int = 0, at_ms: int | None = None, every_ms: int | None = None, timer_id: str | None = None, kwargs: dict[str, Any] | None = None) -> str:
+        """
+        [v3.6] Run the registered process `process` (with `kwargs`) on the same schedules as
+        `schedule_signal`.
+        """
+        ...
+    def schedule_signal(self, key: str, value: Any = None, delay_ms: int = 0, at_ms: int | None = None, every_ms: int | None = None, timer_id: str | None = None) -> str:
+        """
+        [v3.6] Commit `{key: value}` to the Signal zone after `delay_ms`, at `at_ms` (engine
+        wall time) or every `every_ms`. Returns the timer id (`timer_id` replaces a timer).
+        """
+        ...
+    def search(self, terms: str, limit: int = 50) -> list[tuple[str, str, int]]:
+        """
+        [v3.6] `(path, snippet, version)` of searchable strings containing every word of
+        `terms` (case-insensitive), in path order.
+        """
+        ...
+    def search_stats(self) -> Any:
+        """[v3.6] `{patterns, documents, terms}` of the full-text index."""
+        ...
+    def seed_from(self, provider: Any) -> list[str]:
+        """
+        [v3.6] Write the `{path: value}` of `provider` (`EnvProvider`, `CallbackProvider` or any
+        object with `fetch()`) in one admin transaction by actor "seed:<name>". The audit log
+        gets the injected paths, not the values. Returns the seeded paths.
+        """
+        ...
+    def set_audit_system(self, audit: Any) -> None: ...
+    def set_blob_dir(self, path: str) -> None:
+        """
+        [v3.6] Keep blobs in `path` (created if missing, kept after the engine) instead of a
+        temp dir; blob files already there are adopted. Call before the first put.
+        """
+        ...
+    def set_capability_key(self, key: bytes) -> None:
+        """
+        [v3.6] Share a signing key across engines (at least 16 bytes). Outstanding tokens
+        signed with the previous key stop verifying.
+        """
+        ...
+    def set_compression(self, prefix: str, codec: str | None = ..., level: int | None = None) -> None:
+        """
+        [v3.6] Keep the value at `prefix` (`zone.field` path) compressed in the State with
+        `codec` ("zstd" or "lz4"; `level` applies to zstd); reads through proxies decompress it
+        lazily. `codec=None` removes the policy. A value already committed there is compressed
+        immediately.
+        """
+        ...
+    def set_compression_cache(self, cache_bytes: int) -> None:
+        """[v3.6] Size of the LRU of decompressed payloads shared by all compressed paths."""
+        ...
+    def set_copier(self, type_: Any, copier: Any = None) -> None:
+        """
+        [v3.6] Shadow copies of `type_` values (a type, `"module.QualName"` or bare
+        `"QualName"`; subclasses included) are made with `copier(value)` instead of
+        `copy.deepcopy`. `copier=None` removes the entry. A failing copier still fails the
+        shadow copy.
+        """
+        ...
+    def set_deterministic(self, seed: int | None = None) -> None:
+        """
+        [v3.6] Reproducible runs: `seed` makes this engine's backoff jitter, fault
+        probabilities and generated outbox keys come from its own seeded RNG; `None` restores
+        real randomness. Other engines are unaffected. Timestamps follow the process-wide
+        clock (see `use_global_logical_clock`).
+        """
+        ...
+    def set_escape_tracking(self, enabled: bool) -> None:
+        """
+        [v3.6] Debug mode for escaped references: every commit records the content hash of
+        the Data keys it touched and every committed container is tagged (path + identity +
+        content hash), so `verify_integrity()` can report *which* path was mutated, replaced,
+        added or removed outside a transaction. Costly; off by default.
+        """
+        ...
+    def set_expected_duration(self, expected_ms: int | None, process: str | None = None) -> None:
+        """
+        [v3.6] Expected time between heartbeats (or since start) for `process`, or for every
+        process when `process=None`. Runs silent for longer are reported as stuck;
+        `expected_ms=None` removes the expectation.
+        """
+        ...
+    def set_heavy_quota(self, quota_bytes: int | None = None) -> None:
+        """[v3.6] Byte budget for `alloc_heavy` (None = unlimited). Existing handles are kept."""
+        ...
+    def set_isolation(self, level: str) -> None:
+        """
+        [v3.6] Default isolation of new transactions: `"read_committed"` (reads see the latest
+        commit) or `"repeatable_read"` (reads resolve against the state pinned at `__enter__`).
+        """
+        ...
+    def set_leak_detection(self, threshold_ms: int | None = None, capture_stack: bool = False, on_leak: Any = None) -> None:
+        """
+        [v3.6] Leak detection: transactions still open after `threshold_ms` are reported once
+        to the audit log (key `TX_LEAK`) and to `on_leak(info)` if given. `capture_stack`
+        records the creation stack (costly; meant for debugging). `threshold_ms=None` disables.
+        """
+        ...
+    def set_lineage_retention(self, commits: int) -> None:
+        """[v3.6] Number of commits kept for `blame()` (default 1000; 0 disables the log)."""
+        ...
+    def set_op_id_retention(self, max_entries: int) -> None:
+        """[v3.6] How many committed op ids are remembered (oldest forgotten first)."""
+        ...
+    def set_outbox_max_attempts(self, max_attempts: int) -> None:
+        """[v3.6] Failed deliveries before a message is dead-lettered (default 3)."""
+        ...
+    def set_outbox_retention(self, retention_ms: int, max_entries: int = ...) -> None:
+        """[v3.6] Configure how long completed idempotency keys are remembered."""
+        ...
+    def set_private_allowlist(self, names: list[str]) -> None:
+        """
+        [v3.6] Private attribute names (e.g. `_asdict`, `_fields`) that guards still
+        resolve under strict mode. Replaces the previous list; `__dict__` is rejected.
+        """
+        ...
+    def set_pure_io_policy(self, mode: str | None = None, allow: list[str] | None = None) -> None:
+        """
+        [v3.6] I/O enforcement for PURE processes: `mode` "record" logs file / network /
+        subprocess calls made inside `pure_scope`, "reject" also fails them with
+        `PermissionError`, "off" (default) disables the check. `allow` lists categories
+        (`"file_read"`, `"file_write"`, "network", "subprocess") that stay permitted.
+        """
+        ...
+    def set_schema(self, schema: Any) -> None: ...
+    def set_shadow_cache_limit(self, max_bytes: int | None = None) -> None:
+        """
+        [v3.6] Soft bound on the estimated bytes of deepcopied shadows a transaction keeps
+        (None = unbounded). Past it, least recently used shadows that are untouched, have no
+        logged delta and are not referenced outside the transaction are evicted and re-copied
+        on their next access. Applies to transactions opened afterwards.
+        """
+        ...
+    def set_shadow_hashing(self, enabled: bool) -> None:
+        """
+        [v3.6] Record a content hash (XXH3 of the canonical encoding, or of the pickle) of
+        every shadow when it is created; at commit a shadow whose hash is unchanged is skipped
+        without comparing it (no Python `__eq__`). Costs one hash per shadow up front; a 64-bit
+        collision would hide a write. Applies to transactions opened afterwards.
+        """
+        ...
+    def set_slow_commit_threshold(self, threshold_ms: float | None = None, callback: Any = None) -> None:
+        """
+        [v3.6] Commits taking longer than `threshold_ms` are reported with their `tx.stats()`
+        breakdown (plus `tx_id`, `threshold_ms`, `tags`): to the audit log (key `SLOW_COMMIT`)
+        and to `callback(info)`, or else as a warning on the `theus.engine` logger (breakdown
+        in the record's `tx_stats` attribute). `threshold_ms=None` disables.
+        """
+        ...
+    def set_streaming_commit(self, min_deltas: int | None = None) -> None:
+        """
+        [v3.6] Streaming commit: transactions that logged at least `min_deltas` deltas apply
+        them straight onto copy-on-write copies of the touched zones instead of first building
+        a pending dict (lower peak memory for giant transactions). None disables. Transactions
+        are never streamed while approval paths are configured.
+        NOTE: `engine.execute()` hands its transaction an explicit pending dict (contract checks
+        need it), which takes precedence over deltas - streaming pays off for direct
+        `engine.transaction()` blocks.
+        """
+        ...
+    def set_strict_cas(self, enabled: bool) -> None: ...
+    def set_strict_guards(self, enabled: bool) -> None: ...
+    def set_stuck_policy(self, action: str, check_interval_ms: int | None = None) -> None:
+        """
+        [v3.6] `action="abort"` cancels the transaction of stuck runs (checked every
+        `check_interval_ms` by a background timer and on `health()`) and releases the
+        conflict priority slot they hold; "report" (default) only reports them.
+        """
+        ...
+    def set_tenant_quota(self, name: str, max_keys: int | None = None, max_bytes: int | None = None, max_commits: int | None = None, max_outbox: int | None = None) -> None:
+        """
+        [v3.6] Cap tenant `name`'s keys, bytes, commits or outbox messages (None = unlimited;
+        replaces the previous quota). Commits over a cap raise `TenantQuotaExceededError`.
+        """
+        ...
+    def set_transaction_limits(self, max_deltas: int | None = None, max_paths: int | None = None) -> None:
+        """
+        [v3.6] Hard caps per transaction: at most `max_deltas` logged deltas and `max_paths`
+        distinct paths written (None = unlimited). Exceeding one cancels the transaction,
+        logs a `TX_LIMIT_EXCEEDED` audit event and raises `TransactionLimitError`. Applies to
+        transactions opened afterwards.
+        """
+        ...
+    def set_transaction_watchdog(self, soft_deadline_ms: int | None = None, callback: Any = None, action: str = "warn") -> None:
+        """
+        [v3.6] Transaction watchdog. Once a transaction runs past `soft_deadline_ms`,
+        `callback(info)` fires once (or a `RuntimeWarning` if no callback). With `action="abort"`
+        the transaction is cancelled: proxy writes raise and the commit is rejected.
+        Pass `soft_deadline_ms=None` to disable.
+        """
+        ...
+    def set_units(self, units: dict[str, str | None], annotate_reads: bool = False) -> None:
+        """
+        [v3.6] Tag paths with their canonical unit (`{"domain.job.timeout": "ms"}`); proxy
+        writes of `(value, unit)` tuples or pint-like quantities are converted to it. A None unit
+        removes the tag. `annotate_reads=True` makes leaf reads return `(value, unit)`.
+        Registered as middlewares "unit:<path>".
+        """
+        ...
+    def set_version_retention(self, versions: int) -> None:
+        """
+        [v3.6] Keep the last `versions` committed states pinnable (0 = current only).
+        Pinned versions survive pruning.
+        """
+        ...
+    def shutdown(self, timeout_ms: int = 5000) -> Any:
+        """
+        [v3.6] Ordered teardown: reject new transactions, wait up to `timeout_ms` for
+        open ones to exit, flush the outbox (and audit sink if it has `flush()`),
+        and return a report of anything abandoned.
+        """
+        ...
+    @property
+    def signals(self) -> SignalQueue:
+        """[v3.6] Claimable queue of committed signals (`engine.signals.claim(n, consumer_id)`)."""
+        ...
+    def spill_stats(self) -> Any:
+        """
+        [v3.6] `{path, records, file_bytes, spilled_bytes, cached_bytes, cache_bytes, hits,
+        misses}` of the spill store, or None when spilling is off.
+        """
+        ...
+    def spill_to_disk(self, prefixes: list[str], path: str | None = None, cache_bytes: int = ...) -> None:
+        """
+        [v3.6] Keep the values at `prefixes` (`zone.field` paths) in a memory-mapped file
+        instead of RAM; reads through proxies fault them back in. `path` defaults to a temp
+        file removed with the engine; `cache_bytes` bounds the in-memory LRU of hot records.
+        Values already committed at those paths are spilled immediately.
+        """
+        ...
+    def start_recording(self, path: str) -> None:
+        """
+        [v3.6] Record the ordered operations of every transaction into `path` until
+        `stop_recording()`; re-run them with `theus_core.replay_recording(engine, path)`.
+        """
+        ...
+    @property
+    def state(self) -> State: ...
+    def state_machines(self) -> dict[str, Any]:
+        """[v3.6] Defined machines: `{pattern: {"transitions": {..}, "initial": [..] | None}}`."""
+        ...
+    def stop_recording(self) -> Any:
+        """
+        [v3.6] Close the recording: `{path, records}`, or None if not recording.
+        NOTE: Transactions opened before this call keep writing until the file is closed;
+        their ops after that point are dropped (replay reports them as unfinished).
+        """
+        ...
+    def subscribe_changes(self, sink: Any, paths: list[str], fields: list[str] | None = None, ops: list[str] | None = None, include_old: bool = False) -> int:
+        """
+        [v3.6] Change data capture: after each commit `sink(events)` gets the changes at
+        `paths` ("domain.orders.*"), as `{path, op, version, actor, value[, old_value]}` with
+        values projected to `fields` and kinds limited to `ops` ("insert" / "update" /
+        "delete"). Returns the subscription id.
+        """
+        ...
+    def take_due_timers(self, now_ms: int | None = None) -> list[tuple[str, str, str, Any]]:
+        """
+        [v3.6] Claim the timers due at `now_ms` (default: engine clock) for firing:
+        `[(id, kind, target, payload)]`. One-shot timers are removed, recurring ones advanced.
+        """
+        ...
+    def tenant(self, name: str) -> TenantHandle:
+        """
+        [v3.6] Register tenant `name` (idempotent) and return its handle; its paths are rooted
+        under `tenants.<name>` and other scopes may not write there (see tenancy.rs).
+        """
+        ...
+    def tenant_usage(self) -> Any:
+        """[v3.6] `{tenant: {keys, bytes, commits, outbox, quota}}`, maintained per commit."""
+        ...
+    def tenants(self) -> list[str]:
+        """[v3.6] Registered tenant names, sorted."""
+        ...
+    @property
+    def testing(self) -> Any:
+        """[v3.6] Test helpers (`engine.testing.simulate_conflict(paths, at_version)`)."""
+        ...
+    def timers(self) -> list[Any]:
+        """[v3.6] Pending timers: `[{id, kind, target, payload, fire_at_ms, every_ms, fired}]`."""
+        ...
+    def trace(self, process: str, max_events: int = ...) -> None:
+        """
+        [v3.6] Trace the next run of `process`: every guard / proxy read and write it makes
+        (path, allowed / denied, duration) is recorded, capped at `max_events`. Fetch the result
+        with `trace_report(process)` once the run has finished.
+        """
+        ...
+    def trace_report(self, process: str) -> Any:
+        """
+        [v3.6] Report of the last traced run of `process`: `{process, tx_id, started_ms,
+        duration_ms, status, error, reads, writes, denied, dropped, events: [{path, access,
+        allowed, rule, at_us, duration_us}]}`, or None if it has not been traced.
+        """
+        ...
+    def track_contract_drift(self, window: int = 100) -> None:
+        """
+        [v3.6] Record the paths every run reads and writes, keeping the last `window` runs per
+        process (`window=0` stops tracking and forgets them). See `observed_access()`.
+        """
+        ...
+    def transaction(self, write_timeout_ms: int = 5000, actor: str | None = None, admin: bool = False, tags: dict[str, Any] | None = None, isolation: str | None = None, op_id: str | None = None, partial_commit: bool = False, provisional: bool = False) -> Transaction: ...
+    @property
+    def transaction_limits(self) -> Any: ...
+    def unsubscribe_changes(self, id: int) -> bool:
+        """[v3.6] Cancel a change subscription. Returns False if the id is unknown."""
+        ...
+    def verify_capability_token(self, token: str) -> Any:
+        """[v3.6] Rights of a token signed by this engine; `PermissionError` if forged or expired."""
+        ...
+    def verify_integrity(self, raise_on_mismatch: bool = False) -> Any:
+        """
+        [v3.6] Recompute the Data zone checksum and compare it with the one recorded at
+        commit time. A mismatch means committed state was mutated outside a transaction
+        (e.g. through an escaped raw reference); it is logged as `INTEGRITY_VIOLATION`.
+        Checksums are only recorded while `set_escape_tracking(True)` is on.
+        """
+        ...
 
 class Transaction:
-    def __enter__(self, /): ...
-    def __exit__(self, /, exc_type=None, _exc_value=None, _traceback=None): ...
-    def __init__(self, /, *args, **kwargs): ...
-    def build_pending_from_deltas(self, /): ...
-    def commit(self, /): ...
-    def flush_outbox(self, /): ...
-    def get_delta_log(self, /): ...
-    def get_shadow(self, /, val, path=None): ...
-    def get_shadow_updates(self, /): ...
-    def infer_shadow_deltas(self, /): ...
-    def is_known_shadow(self, /, obj): ...
-    def log_delta(self, /, path, old_val=None, new_val=None): ...
-    def log_internal(self, /, _path, _op, _new_val=None, _old_val=None, _obj_ref=None, _key=None): ...
-    def update(self, /, data=None, heavy=None, signal=None): ...
+    def __deepcopy__(self, _memo: Any) -> None: ...
+    def __enter__(self) -> Transaction: ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, _traceback: Any = None) -> None: ...
+    def __init__(self, engine: TheusEngine | None = None, write_timeout_ms: int = 5000, actor: str | None = None, admin: bool = False, tags: dict[str, Any] | None = None, isolation: str | None = None, op_id: str | None = None, partial_commit: bool = False, provisional: bool = False) -> None: ...
+    def __reduce__(self) -> None:
+        """
+        [v3.4] Fail-fast: Transaction cannot be pickled/deepcopied.
+        Prevents silent corruption if Transaction leaks into serializable paths.
+        """
+        ...
+    @property
+    def actor(self) -> str | None: ...
+    @property
+    def blobs(self) -> BlobHandle:
+        """[v3.6] The engine's blob store; blobs put here survive `gc_blobs` while this tx is open."""
+        ...
+    def break_glass_covers(self, path: str) -> bool:
+        """
+        [v3.6] True while a break-glass grant of this transaction's engine covers `path` (the
+        Python `ContextGuard` uses it to stop hiding Private fields).
+        """
+        ...
+    def build_pending_from_deltas(self) -> Any:
+        """[v3.1 Delta Replay] Build `pending_data` from `delta_log` by replaying mutations"""
+        ...
+    def cancel(self) -> None:
+        """[v3.6] Cooperatively cancel: subsequent writes raise and the commit is rejected."""
+        ...
+    @property
+    def cancelled(self) -> bool:
+        """
+        [v3.6] True once the watchdog (action="abort") or `cancel()` stopped this transaction.
+        Long-running processes should poll this and return early.
+        """
+        ...
+    def checkpoint(self) -> int:
+        """
+        [v3.6] Mark the writes made so far as committed even if the block later raises
+        (requires `partial_commit=True`). Returns the checkpoint number.
+        """
+        ...
+    def commit(self) -> None:
+        """
+        [v3.1 Zero Trust] Commit Delta Log to Pending State
+        This applies the implicit mutations (captured in shadow objects) to the `pending_data/heavy` buffers.
+        """
+        ...
+    @property
+    def commit_error(self) -> str | None:
+        """[v3.6] Why the commit failed (None unless `commit_status == "failed"`)."""
+        ...
+    @property
+    def commit_status(self) -> str | None:
+        """
+        [v3.6] "committed", "failed", "staged" (waiting in a commit group), "duplicate" (`op_id`
+        already committed; nothing written), "partial" (the block raised; its last checkpoint
+        was committed) or None (open, rolled back by an exception).
+        """
+        ...
+    @property
+    def committed_version(self) -> int | None:
+        """[v3.6] State version this transaction's writes landed in."""
+        ...
+    @property
+    def deltas(self) -> list[Any]:
+        """[v3.6] Logged deltas as `DeltaEntry` objects (each carrying this tx's `tags`)."""
+        ...
+    def emit(self, topic: str, payload: Any) -> None:
+        """
+        [v3.6] Stage a domain event. Published to the signal hub and event watchers
+        only after this transaction commits; discarded on rollback.
+        """
+        ...
+    def flush_outbox(self) -> None:
+        """[v3.3] Manual Flush for Flux Engine / `execute()`"""
+        ...
+    def get_delta_log(self) -> list[str]:
+        """[v3.1.2] Expose raw delta log for strict contract validation"""
+        ...
+    def get_shadow(self, val: Any, path: str | None = None) -> Any:
+        """Internal: Get shadow copy for CoW/Tracking"""
+        ...
+    def get_shadow_updates(self) -> Any:
+        """
+        Get shadow updates keyed by root path (e.g., 'domain' -> `shadow_dict`)
+        This extracts all modified root-level objects for committing to State.
+        """
+        ...
+    @property
+    def id(self) -> int:
+        """[v3.6] Engine-unique transaction id (matches `engine.open_transactions()`)."""
+        ...
+    def infer_shadow_deltas(self) -> None:
+        """[v3.1.2] Infer Deltas from Shadow Mutations (Differential Merging)"""
+        ...
+    def is_known_shadow(self, obj: Any) -> bool:
+        """[INC-013] Helper: Check if object is a tracked Shadow."""
+        ...
+    @property
+    def isolation(self) -> str: ...
+    def last_rollback_report(self) -> Any:
+        """
+        [v3.6] What the last rollback of this transaction discarded (an exception in the
+        block, or a failed commit), or None. Also attached to that exception as
+        `rollback_report`.
+        """
+        ...
+    def log_delta(self, path: str, old_val: Any = None, new_val: Any = None) -> None:
+        """[v3.1 Zero Trust] Log operation for Audit"""
+        ...
+    def log_internal(self, _path: str, _op: Any, _new_val: Any = None, _old_val: Any = None, _obj_ref: Any = None, _key: str | None = None) -> None:
+        """Internal: Log operation for Audit (Full)"""
+        ...
+    @property
+    def op_id(self) -> str | None:
+        """[v3.6] Idempotency key: a transaction whose `op_id` already committed is a no-op."""
+        ...
+    @property
+    def outbox(self) -> OutboxCollector: ...
+    @property
+    def pending_data(self) -> Any: ...
+    @property
+    def pending_heavy(self) -> Any: ...
+    @property
+    def pending_signal(self) -> Any: ...
+    @property
+    def proposal(self) -> int | None:
+        """[v3.6] Id of the proposal this tx's commit parked (writes awaiting approval), if any."""
+        ...
+    def read(self, path: str, default: Any = None) -> Any:
+        """
+        [v3.6] Value at `path` ("domain.cfg.x", "heavy.frame") as of the pinned snapshot, or
+        the latest commit under read-committed isolation; containers come back as read-only
+        proxies. Pending writes of this transaction are not visible.
+        """
+        ...
+    @property
+    def read_only(self) -> bool:
+        """
+        [v3.6] True when the transaction wrote nothing and closed without committing
+        (`engine.state` and its version are unchanged).
+        """
+        ...
+    @property
+    def snapshot(self) -> State | None:
+        """
+        [v3.6] State pinned at `__enter__` under repeatable reads (None otherwise or once closed).
+        Always None for tenant transactions: the snapshot spans every tenant (use `read()`).
+        """
+        ...
+    def stats(self) -> Any:
+        """
+        [v3.6] `{shadows, shadow_copy_ms, shadow_bytes, shadow_evictions, shadow_hash_skips,
+        deltas, inference_ms, schema_ms, commit_ms}`: shadow copies made (and time spent
+        copying), estimated bytes of the shadows held and evicted under `set_shadow_cache_limit`
+        (bytes are 0 when unbounded), shadows skipped by `set_shadow_hashing`, deltas logged,
+        and the time spent inferring shadow deltas, validating the schema and committing
+        (`__exit__`).
+        """
+        ...
+    @property
+    def streamed(self) -> bool:
+        """[v3.6] True when the commit took the streaming path (`engine.set_streaming_commit`)."""
+        ...
+    @property
+    def tags(self) -> dict[str, Any]:
+        """
+        [v3.6] Correlation tags, stamped onto this tx's deltas, outbox messages, audit events
+        and lineage entries.
+        """
+        ...
+    def trace_denied(self, path: str, access: str) -> None:
+        """
+        [v3.6] Record a contract denial decided outside the Rust guards (the Python
+        `ContextGuard`) in this transaction's trace, if its run is being traced.
+        """
+        ...
+    def update(self, data: Any = None, heavy: Any = None, signal: Any = None) -> None: ...
+    def update_many(self, pairs: Any) -> int:
+        """
+        [v3.6] Bulk write: `tx.update_many([("domain.users.u1.score", 3), ...])`. Paths (dict
+        keys, dotted or bracketed) are grouped by parent; each parent gets one zone/capability
+        check (UPDATE required; admin transactions bypass all but CONSTANT), then every value is
+        merged into the pending buffers in one pass and logged as a compact delta (no old value).
+        Returns the number of writes.
+        """
+        ...
+    @property
+    def write_timeout_ms(self) -> int: ...
+
+class TransactionCancelledError(RuntimeError): ...
+
+class TransactionLimitError(TransactionCancelledError): ...
+
+class VersionMismatchError(ConflictError): ...
 
 class WorkflowEngine:
-    def __init__(self, /, *args, **kwargs): ...
-    def add_state_observer(self, /, callback): ...
-    def execute(self, /, ctx, executor): ...
-    def execute_async(self, /, ctx, executor): ...
-    def simulate(self, /, ctx): ...
-
-class WriteTimeoutError:
-    def __init__(self, /, *args, **kwargs): ...
+    def __init__(self, yaml_config: str, max_ops: int = 10000, debug: bool = False) -> None: ...
+    def add_state_observer(self, callback: Any) -> None:
+        """
+        Add an observer callback for state changes.
+        Callback signature: (`old_state`, `new_state`) -> None
+        """
+        ...
+    def execute(self, ctx: dict[str, Any], executor: Any) -> list[str]:
+        """
+        Execute the workflow using the provided executor callback.
+
+        Args:
+            ctx: `PyDict` - Context for condition evaluation (e.g., {"domain": {...}, "global": {...}})
+            executor: Callable[[str], None] - Function to execute a process by name
+
+        Returns:
+            List of executed process names (for debugging/logging)
+        """
+        ...
+    def execute_async(self, ctx: dict[str, Any], executor: Any) -> Any:
+        """
+        Execute the workflow asynchronously.
+        Wraps the synchronous execution in a thread (`asyncio.to_thread`) to avoid blocking the event loop.
+        Handles FSM state transitions correctly for async steps by blocking the worker thread.
+        """
+        ...
+    @property
+    def fsm_state(self) -> FSMState:
+        """Get current FSM state."""
+        ...
+    def simulate(self, ctx: dict[str, Any]) -> list[str]:
+        """
+        Legacy method for backward compatibility with existing tests.
+        Returns the simulated execution path without actually executing.
+        """
+        ...
+    @property
+    def state(self) -> FSMState:
+        """Alias for `fsm_state` (for test compatibility)."""
+        ...
+    @property
+    def state_history(self) -> list[FSMState]:
+        """Get state transition history."""
+        ...
+
+class WriteTimeoutError(TimeoutError): ...
+
+def advance_global_clock_ms(ms: int) -> int:
+    """[v3.6] Advance the process-wide test clock; returns the new wall time in ms."""
+    ...
+
+def allow_pickle_serializer(enabled: bool) -> None:
+    """Opt-in (or out) of the built-in `pickle` codec."""
+    ...
+
+def check_effect(effect: str) -> None:
+    """
+    `check_effect("log")`: `PermissionError` if the running process's effect budget excludes
+    `effect` (no-op outside budgeted processes).
+    """
+    ...
+
+def clear_physics_overrides() -> None: ...
+
+def effective_physics(path: str) -> tuple[str, Capability]:
+    """[v3.6] `(zone name, capabilities)` the proxies apply to `path` (overrides included)."""
+    ...
+
+def engine_metrics(reset: bool = False) -> Any:
+    """
+    [v3.6] Engine histograms as plain dicts (`reset=True` zeroes them after reading).
+    Process-wide: every engine in the process records into the same histograms.
+    """
+    ...
+
+def error_catalog() -> dict[str, Any]:
+    """`{code: {"name", "template", "custom"}}` for every catalog entry."""
+    ...
+
+def generate_stubs(path: str | None = None) -> str:
+    """
+    [v3.6] The generated `.pyi` text; also written to `path` when given (a directory gets
+    `theus_core.pyi` inside it).
+    """
+    ...
+
+def global_clock() -> tuple[str, int]:
+    """[v3.6] `(kind, wall time in ms)` of the process-wide clock."""
+    ...
+
+def guard_from_token(target: Any, token: str, key: bytes, tx: Transaction | None = None, path_prefix: str | None = None) -> ContextGuard:
+    """A `ContextGuard` over `target` holding exactly the rights delegated by `token`."""
+    ...
+
+def intern_stats(purge: bool = False) -> Any:
+    """
+    Interner occupancy: `{strings, bytes, lookups, hits, purged}`. `purge=True` first drops
+    strings no longer referenced outside the table.
+    """
+    ...
+
+def list_serializers() -> list[str]:
+    """Names of all registered codecs (sorted)."""
+    ...
+
+def lock_recoveries(reset: bool = False) -> list[Any]:
+    """
+    [v3.6] Locks recovered after a panic poisoned them (most recent last, up to 100):
+    `[{site, action, timestamp}]` with `action` "kept" or "reset". `reset=True` clears the list.
+    """
+    ...
+
+def physics_overrides() -> dict[str, int]:
+    """[v3.6] Registered overrides as `{path: caps}` (for reviews / `engine.security_report()`)."""
+    ...
+
+def profile_report(top_n: int | None = None, reset: bool = False) -> Any:
+    """
+    [v3.6] Hottest canonical paths first (reads + writes), as
+    `[{path, reads, writes, total}, ...]`. `reset=True` clears the counts after reading.
+    """
+    ...
+
+def reentrancy_stats(reset: bool = False) -> Any:
+    """
+    [v3.6] `{policy, raw_reads, blocked}`: re-entrant proxy reads served raw and re-entrant
+    operations refused since start-up (`reset=True` zeroes the counters after reading).
+    """
+    ...
+
+def register_error_formatter(code: str, formatter: Any) -> None:
+    """Use `formatter(code, params) -> str` for messages with `code`."""
+    ...
+
+def register_physics_override(path: str, caps: int) -> None: ...
+
+def register_serializer(name: str, encode: Any, decode: Any) -> None:
+    """Register a user codec. `encode(payload) -> bytes`, `decode(bytes) -> payload`."""
+    ...
+
+def replay_recording(engine: Any, file: str, stop_on_error: bool = True) -> Any:
+    """
+    [v3.6] Re-execute the transactions captured by `engine.start_recording(file)` against
+    `engine` (a core engine or a `theus.TheusEngine`), in commit order. Transactions that
+    rolled back - or never finished - while recording are reported but not replayed.
+    With `stop_on_error=False`, replay failures are reported instead of raised.
+    """
+    ...
+
+def reset_profile() -> None:
+    """[v3.6] Drop all path counts."""
+    ...
+
+def set_metrics_enabled(enabled: bool) -> None:
+    """[v3.6] Toggle histogram recording (on by default)."""
+    ...
+
+def set_profiling_enabled(enabled: bool) -> None:
+    """
+    [v3.6] Toggle path profiling for every engine in the process (off by default; counts
+    survive toggling).
+    """
+    ...
+
+def set_reentrancy_policy(policy: str) -> str:
+    """
+    [v3.6] How re-entrant proxy reads are handled: "raw" (default) or "raise". Returns the
+    previous policy. Re-entrant writes always raise `ReentrancyError`.
+    """
+    ...
+
+def unregister_error_formatter(code: str) -> bool:
+    """Restore the default template for `code`. Returns True if a formatter was registered."""
+    ...
+
+def unregister_serializer(name: str) -> bool:
+    """Remove a codec. Returns True if it was registered."""
+    ...
+
+def use_global_logical_clock() -> None:
+    """
+    [v3.6] Switch the process-wide clock to logical time: a fixed epoch advancing 1ms per
+    wall-clock reading, so timestamps depend only on the order of events (golden tests,
+    together with `engine.set_deterministic(seed)`).
+    """
+    ...
+
+def use_global_system_clock() -> None:
+    """[v3.6] Restore the system clock for the whole process."""
+    ...
+
+def use_global_test_clock(start_ms: int | None = None) -> None:
+    """
+    [v3.6] Replace the process-wide clock with a manual one starting at `start_ms` (default:
+    now); only `advance_global_clock_ms` moves it. Affects every engine in the process:
+    transaction timeouts, watchdog/leak ages, idempotency TTLs, outbox and audit timestamps.
+    """
+    ...
+
+def verify_capability_token(token: str, key: bytes) -> Any:
+    """
+    `{inputs, outputs, caps, strict_guards, private_allowlist, expires_at}` of a valid token;
+    `PermissionError` otherwise.
+    """
+    ...